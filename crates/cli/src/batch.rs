@@ -0,0 +1,177 @@
+//! Batch export helpers
+//!
+//! Finds the documents in a directory and runs the exports on a bounded pool
+//! of worker threads. A failing file is reported and does not stop the run,
+//! even when its export panics.
+
+#![cfg_attr(not(feature = "ui"), allow(dead_code))]
+
+use anyhow::{Context, Result};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Outcome of exporting one document
+#[derive(Debug)]
+pub struct BatchResult {
+    pub input: PathBuf,
+    pub output: PathBuf,
+    pub result: Result<()>,
+}
+
+/// `.json` documents directly inside `dir`, sorted by name
+pub fn document_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let entries =
+        std::fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Output file for `input` in `output_dir`, keeping the file stem
+pub fn output_path(output_dir: &Path, input: &Path, extension: &str) -> PathBuf {
+    let stem = input
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "document".to_string());
+    output_dir.join(format!("{}.{}", stem, extension))
+}
+
+/// Default number of worker threads (the available parallelism, at least one)
+pub fn default_jobs() -> usize {
+    std::thread::available_parallelism()
+        .map(|jobs| jobs.get())
+        .unwrap_or(1)
+}
+
+/// Export every input on at most `jobs` threads
+///
+/// `export` is called with the input and output paths. A panic in `export` is
+/// recorded as that input's failure. Results come back in input order.
+pub fn run<F>(
+    inputs: &[PathBuf],
+    output_dir: &Path,
+    extension: &str,
+    jobs: usize,
+    export: F,
+) -> Vec<BatchResult>
+where
+    F: Fn(&Path, &Path) -> Result<()> + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<BatchResult>>> =
+        Mutex::new(inputs.iter().map(|_| None).collect());
+    let workers = jobs.clamp(1, inputs.len().max(1));
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(input) = inputs.get(index) else {
+                    break;
+                };
+                let output = output_path(output_dir, input, extension);
+                let result = panic::catch_unwind(AssertUnwindSafe(|| export(input, &output)))
+                    .unwrap_or_else(|payload| Err(panic_error(payload.as_ref())));
+                let mut results = results.lock().unwrap_or_else(|e| e.into_inner());
+                results[index] = Some(BatchResult {
+                    input: input.clone(),
+                    output,
+                    result,
+                });
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_else(|e| e.into_inner())
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Error for an export that panicked, keeping the panic message
+fn panic_error(payload: &(dyn std::any::Any + Send)) -> anyhow::Error {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string());
+    anyhow::anyhow!("Export panicked: {}", message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_files_lists_json_only() {
+        let dir = std::env::temp_dir().join(format!("testruct-batch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in ["b.json", "a.JSON", "notes.txt"] {
+            std::fs::write(dir.join(name), "{}").unwrap();
+        }
+
+        let files = document_files(&dir).unwrap();
+        assert_eq!(files, vec![dir.join("a.JSON"), dir.join("b.json")]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_run_keeps_order_and_continues_after_failures() {
+        let inputs: Vec<PathBuf> = (0..10)
+            .map(|i| PathBuf::from(format!("exam-{}.json", i)))
+            .collect();
+        let results = run(&inputs, Path::new("out"), "pdf", 3, |input, _| {
+            if input.to_string_lossy().contains('3') {
+                anyhow::bail!("parse error")
+            }
+            Ok(())
+        });
+
+        assert_eq!(results.len(), 10);
+        assert_eq!(results[0].output, Path::new("out").join("exam-0.pdf"));
+        let failed: Vec<_> = results
+            .iter()
+            .filter(|r| r.result.is_err())
+            .map(|r| r.input.clone())
+            .collect();
+        assert_eq!(failed, vec![PathBuf::from("exam-3.json")]);
+    }
+
+    #[test]
+    fn test_run_records_a_panic_as_a_failure() {
+        let inputs: Vec<PathBuf> = (0..4)
+            .map(|i| PathBuf::from(format!("exam-{}.json", i)))
+            .collect();
+        let results = run(&inputs, Path::new("out"), "pdf", 2, |input, _| {
+            if input.to_string_lossy().contains('2') {
+                panic!("table without rows");
+            }
+            Ok(())
+        });
+
+        assert_eq!(results.len(), 4);
+        let errors: Vec<String> = results
+            .iter()
+            .filter_map(|r| r.result.as_ref().err())
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(
+            errors,
+            vec!["Export panicked: table without rows".to_string()]
+        );
+        assert!(results[3].result.is_ok());
+    }
+}
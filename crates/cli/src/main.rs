@@ -1,4 +1,7 @@
-use clap::{Parser, Subcommand};
+mod batch;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
 use testruct_core::{Project, Template};
 
 #[derive(Parser)]
@@ -14,6 +17,58 @@ enum Commands {
     Ui,
     /// List available templates from the default library
     Templates,
+    /// Export a saved document without opening the UI
+    Export {
+        /// Document JSON file to read
+        input: PathBuf,
+        /// File to write
+        output: PathBuf,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ExportFormat::Pdf)]
+        format: ExportFormat,
+        /// Resolution for PNG output
+        #[arg(long, default_value_t = 96.0)]
+        dpi: f64,
+    },
+    /// Export every document JSON file in a directory
+    BatchExport {
+        /// Directory of document JSON files
+        input_dir: PathBuf,
+        /// Directory to write the exported files to
+        output_dir: PathBuf,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ExportFormat::Pdf)]
+        format: ExportFormat,
+        /// Resolution for PNG output
+        #[arg(long, default_value_t = 96.0)]
+        dpi: f64,
+        /// Number of documents exported in parallel (defaults to the CPU count)
+        #[arg(long, short)]
+        jobs: Option<usize>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum ExportFormat {
+    Pdf,
+    /// PDF/A-1b for archiving
+    PdfA,
+    Png,
+    Svg,
+    Eps,
+}
+
+impl ExportFormat {
+    /// File extension of exported files
+    #[cfg_attr(not(feature = "ui"), allow(dead_code))]
+    fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Pdf | ExportFormat::PdfA => "pdf",
+            ExportFormat::Png => "png",
+            ExportFormat::Svg => "svg",
+            ExportFormat::Eps => "eps",
+        }
+    }
 }
 
 fn main() -> anyhow::Result<()> {
@@ -40,6 +95,120 @@ fn main() -> anyhow::Result<()> {
                 println!("{}", template.name);
             }
         }
+        Commands::Export {
+            input,
+            output,
+            format,
+            dpi,
+        } => {
+            #[cfg(feature = "ui")]
+            {
+                export_document(&input, &output, format, dpi)?;
+                println!("{}", output.display());
+            }
+            #[cfg(not(feature = "ui"))]
+            {
+                let _ = (input, output, format, dpi);
+                anyhow::bail!("Export requires the ui feature");
+            }
+        }
+        Commands::BatchExport {
+            input_dir,
+            output_dir,
+            format,
+            dpi,
+            jobs,
+        } => {
+            #[cfg(feature = "ui")]
+            {
+                batch_export(&input_dir, &output_dir, format, dpi, jobs)?;
+            }
+            #[cfg(not(feature = "ui"))]
+            {
+                let _ = (input_dir, output_dir, format, dpi, jobs);
+                anyhow::bail!("Export requires the ui feature");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Export every document in `input_dir` and print a per-file report
+///
+/// Files that fail to load or export are listed at the end; the command
+/// fails if any did.
+#[cfg(feature = "ui")]
+fn batch_export(
+    input_dir: &std::path::Path,
+    output_dir: &std::path::Path,
+    format: ExportFormat,
+    dpi: f64,
+    jobs: Option<usize>,
+) -> anyhow::Result<()> {
+    use anyhow::Context;
+
+    let inputs = batch::document_files(input_dir)?;
+    if inputs.is_empty() {
+        anyhow::bail!("No .json documents found in {}", input_dir.display());
+    }
+    std::fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create {}", output_dir.display()))?;
+
+    let jobs = jobs.unwrap_or_else(batch::default_jobs);
+    let results = batch::run(
+        &inputs,
+        output_dir,
+        format.extension(),
+        jobs,
+        |input, output| export_document(input, output, format, dpi),
+    );
+
+    let mut failed = 0;
+    for result in &results {
+        match &result.result {
+            Ok(()) => println!(
+                "ok     {} -> {}",
+                result.input.display(),
+                result.output.display()
+            ),
+            Err(e) => {
+                failed += 1;
+                println!("failed {}: {:#}", result.input.display(), e);
+            }
+        }
+    }
+    println!(
+        "{} exported, {} failed ({} total)",
+        results.len() - failed,
+        failed,
+        results.len()
+    );
+    if failed > 0 {
+        anyhow::bail!("{} of {} documents failed to export", failed, results.len());
     }
     Ok(())
 }
+
+#[cfg(feature = "ui")]
+fn export_document(
+    input: &std::path::Path,
+    output: &std::path::Path,
+    format: ExportFormat,
+    dpi: f64,
+) -> anyhow::Result<()> {
+    use anyhow::Context;
+    use testruct_ui::export;
+
+    let document = testruct_ui::io::load_document(input)
+        .with_context(|| format!("Failed to load {}", input.display()))?;
+    let catalog = &document.assets;
+
+    match format {
+        ExportFormat::Pdf => export::export_pdf(&document, output, catalog),
+        ExportFormat::PdfA => export::export_pdf_a(&document, output, catalog),
+        ExportFormat::Png => export::export_png(&document, output, dpi, catalog),
+        ExportFormat::Svg => export::export_svg(&document, output, catalog),
+        ExportFormat::Eps => export::export_eps(&document, output, catalog),
+    }
+    .with_context(|| format!("Failed to export {}", output.display()))
+}
@@ -0,0 +1,71 @@
+//! Named layers of a page
+//!
+//! A layer is a named, ordered group of top-level elements that can be shown,
+//! hidden or locked as a whole. Layers are stored bottom to top on the page
+//! and only record which elements they hold; the stacking order inside a
+//! layer is still the order of `Page::elements`.
+//!
+//! Every page has at least one layer. Elements that no layer lists (older
+//! files, or elements added since the last assignment) belong to the bottom
+//! layer.
+
+use serde::{Deserialize, Serialize};
+
+/// Name given to the layer of pages saved before layers existed
+pub const DEFAULT_LAYER_NAME: &str = "Layer 1";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LayerId(uuid::Uuid);
+
+impl LayerId {
+    pub fn new() -> Self {
+        Self(uuid::Uuid::new_v4())
+    }
+}
+
+impl Default for LayerId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A named layer holding some of the top-level elements of a page
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Layer {
+    pub id: LayerId,
+    pub name: String,
+    #[serde(default = "default_visible")]
+    pub visible: bool,
+    #[serde(default)]
+    pub locked: bool,
+    /// Top-level elements assigned to this layer
+    #[serde(default)]
+    pub element_ids: Vec<uuid::Uuid>,
+}
+
+fn default_visible() -> bool {
+    true
+}
+
+impl Layer {
+    /// A visible, unlocked layer without elements
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            id: LayerId::new(),
+            name: name.into(),
+            visible: true,
+            locked: false,
+            element_ids: Vec::new(),
+        }
+    }
+
+    /// Whether `element_id` is assigned to this layer
+    pub fn contains(&self, element_id: uuid::Uuid) -> bool {
+        self.element_ids.contains(&element_id)
+    }
+}
+
+/// Layers of a page without any saved layer information
+pub(crate) fn default_layers() -> Vec<Layer> {
+    vec![Layer::new(DEFAULT_LAYER_NAME)]
+}
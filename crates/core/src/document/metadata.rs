@@ -1,15 +1,26 @@
-use super::PageSize;
+use super::{ImageFit, PageSize};
+use crate::typography::Color;
+use crate::workspace::assets::AssetRef;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DocumentMetadata {
     pub title: String,
     pub author: String,
+    /// What the document is about (exported as the PDF subject)
+    #[serde(default)]
+    pub subject: String,
+    /// Search keywords (exported as the PDF keywords)
+    #[serde(default)]
+    pub keywords: Vec<String>,
     pub tags: Vec<String>,
     #[serde(default)]
     pub page_size: PageSize,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// Canvas view settings from the last save (None for older files)
+    #[serde(default)]
+    pub view: Option<ViewState>,
 }
 
 impl Default for DocumentMetadata {
@@ -24,20 +35,190 @@ impl DocumentMetadata {
         Self {
             title: title.into(),
             author: author.into(),
+            subject: String::new(),
+            keywords: Vec::new(),
             tags: Vec::new(),
             page_size: PageSize::A4,
             created_at: now,
             updated_at: now,
+            view: None,
         }
     }
 
     pub fn touch(&mut self) {
         self.updated_at = chrono::Utc::now();
     }
+
+    /// Keywords joined with commas, as shown in the metadata dialog and PDF info
+    pub fn keywords_text(&self) -> String {
+        self.keywords.join(", ")
+    }
+
+    /// Replace the keywords with the comma-separated entries of `text`
+    ///
+    /// Both ASCII and full-width commas separate entries; blank entries are dropped.
+    pub fn set_keywords_text(&mut self, text: &str) {
+        self.keywords = text
+            .split([',', '、', '，'])
+            .map(str::trim)
+            .filter(|keyword| !keyword.is_empty())
+            .map(str::to_string)
+            .collect();
+    }
+}
+
+/// Canvas view settings persisted with a document
+///
+/// Restored when the document is reopened so zoom, pan, guides and the
+/// display toggles come back as they were left.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewState {
+    pub zoom: f64,
+    pub pan_x: f64,
+    pub pan_y: f64,
+    pub show_grid: bool,
+    pub show_rulers: bool,
+    pub show_guides: bool,
+    pub snap_to_grid: bool,
+    pub snap_to_guides: bool,
+    pub guides: Vec<ViewGuide>,
+}
+
+impl Default for ViewState {
+    fn default() -> Self {
+        Self {
+            zoom: 1.0,
+            pan_x: 0.0,
+            pan_y: 0.0,
+            show_grid: true,
+            show_rulers: true,
+            show_guides: true,
+            snap_to_grid: true,
+            snap_to_guides: true,
+            guides: Vec::new(),
+        }
+    }
+}
+
+/// A guide line position stored in a [`ViewState`]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ViewGuide {
+    /// true = vertical line at `position` on the x axis, false = horizontal (y)
+    pub is_vertical: bool,
+    pub position: f32,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct PageMetadata {
     pub name: String,
     pub notes: Option<String>,
+    /// Painted under the page elements (None for older files)
+    #[serde(default)]
+    pub background: PageBackground,
+    /// Overrides the document page size for this page (e.g. a landscape
+    /// answer key); None uses the document size
+    #[serde(default)]
+    pub page_size: Option<PageSize>,
+}
+
+/// What fills a page behind its elements
+///
+/// `None` leaves the page blank, so the canvas shows it white and exports
+/// use their own background option (which may be transparent).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum PageBackground {
+    #[default]
+    None,
+    Color(Color),
+    Image {
+        source: AssetRef,
+        fit: ImageFit,
+    },
+}
+
+impl PageBackground {
+    /// The image asset this background uses, if any
+    pub fn asset(&self) -> Option<AssetRef> {
+        match self {
+            PageBackground::Image { source, .. } => Some(*source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view_state_round_trips_through_json() {
+        let mut metadata = DocumentMetadata::new("Doc", "Author");
+        metadata.view = Some(ViewState {
+            zoom: 1.5,
+            pan_x: -20.0,
+            show_grid: false,
+            guides: vec![ViewGuide {
+                is_vertical: true,
+                position: 120.0,
+            }],
+            ..Default::default()
+        });
+
+        let json = serde_json::to_string(&metadata).unwrap();
+        let restored: DocumentMetadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.view, metadata.view);
+    }
+
+    #[test]
+    fn test_metadata_without_view_state_still_loads() {
+        let metadata = DocumentMetadata::new("Doc", "Author");
+        let mut value = serde_json::to_value(&metadata).unwrap();
+        value.as_object_mut().unwrap().remove("view");
+
+        let restored: DocumentMetadata = serde_json::from_value(value).unwrap();
+        assert!(restored.view.is_none());
+    }
+
+    #[test]
+    fn test_subject_and_keywords_round_trip_and_default() {
+        let mut metadata = DocumentMetadata::new("Doc", "Author");
+        metadata.subject = "Algebra quiz".into();
+        metadata.set_keywords_text("math, 二次方程式、 ,quiz");
+        assert_eq!(metadata.keywords, ["math", "二次方程式", "quiz"]);
+        assert_eq!(metadata.keywords_text(), "math, 二次方程式, quiz");
+
+        let json = serde_json::to_string(&metadata).unwrap();
+        let restored: DocumentMetadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.subject, "Algebra quiz");
+        assert_eq!(restored.keywords, metadata.keywords);
+
+        let mut value = serde_json::to_value(&metadata).unwrap();
+        let object = value.as_object_mut().unwrap();
+        object.remove("subject");
+        object.remove("keywords");
+        let old: DocumentMetadata = serde_json::from_value(value).unwrap();
+        assert!(old.subject.is_empty());
+        assert!(old.keywords.is_empty());
+    }
+
+    #[test]
+    fn test_page_background_round_trips_and_defaults() {
+        let metadata = PageMetadata {
+            name: "Cover".into(),
+            notes: None,
+            background: PageBackground::Image {
+                source: AssetRef::new(),
+                fit: ImageFit::Cover,
+            },
+            page_size: None,
+        };
+        let json = serde_json::to_string(&metadata).unwrap();
+        let restored: PageMetadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.background, metadata.background);
+        assert_eq!(restored.background.asset(), metadata.background.asset());
+
+        let old: PageMetadata = serde_json::from_str(r#"{"name":"Old","notes":null}"#).unwrap();
+        assert_eq!(old.background, PageBackground::None);
+    }
 }
@@ -1,19 +1,30 @@
 mod builder;
+mod layer;
 mod metadata;
 mod page;
 mod page_size;
+mod qr_code;
+mod search;
+mod text_path;
+mod validation;
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
 
 pub use builder::{DocumentBuilder, DocumentBuilderError};
-pub use metadata::{DocumentMetadata, PageMetadata};
+pub use layer::{Layer, LayerId, DEFAULT_LAYER_NAME};
+pub use metadata::{DocumentMetadata, PageBackground, PageMetadata, ViewGuide, ViewState};
 pub use page::{
-    DocumentElement, FrameElement, GroupElement, ImageElement, Page, PageId, ShapeElement,
-    ShapeKind, TextElement,
+    DocumentElement, Fill, FrameElement, GroupElement, ImageElement, ImageFit, LineStyle, Page,
+    PageId, ShapeElement, ShapeKind, TableBorder, TableElement, TextElement, ZOrderChange,
+    DEFAULT_TEXT_PADDING,
 };
 pub use page_size::PageSize;
+pub use qr_code::{QrCodeElement, QrEncodeError, QrErrorCorrection, QrMatrix, QR_QUIET_ZONE};
+pub use search::{find_matches, replace_matches, SearchOptions, TextMatch};
+pub use text_path::{BezierSegment, FlattenedPath, TextPath};
+pub use validation::ValidationIssue;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct DocumentId(uuid::Uuid);
@@ -63,6 +74,50 @@ impl Document {
     pub fn pages(&self) -> impl Iterator<Item = &Page> {
         self.pages.iter()
     }
+
+    /// Size of `page`: its own page size if set, otherwise the document's
+    pub fn page_size_of(&self, page: &Page) -> crate::layout::Size {
+        page.metadata
+            .page_size
+            .unwrap_or(self.metadata.page_size)
+            .to_size()
+    }
+
+    /// Index of the page with the given id
+    pub fn page_index(&self, id: PageId) -> Option<usize> {
+        self.pages.iter().position(|p| p.id == id)
+    }
+
+    /// Move the page at `from` so it ends up at index `to`
+    ///
+    /// Out-of-range targets are clamped to the last page. Returns the final
+    /// index of the moved page, or `None` when nothing moved (invalid source
+    /// index or dropping a page onto itself).
+    pub fn move_page(&mut self, from: usize, to: usize) -> Option<usize> {
+        if from >= self.pages.len() {
+            return None;
+        }
+        let to = to.min(self.pages.len() - 1);
+        if from == to {
+            return None;
+        }
+        let page = self.pages.remove(from);
+        self.pages.insert(to, page);
+        Some(to)
+    }
+
+    /// Duplicate a page, inserting the copy right after the original
+    ///
+    /// The copy gets a fresh `PageId` and fresh element ids (recursively through
+    /// frames and groups) so it never collides with the source page.
+    /// Returns the id of the new page, or `None` if `id` is not in the document.
+    pub fn duplicate_page(&mut self, id: PageId) -> Option<PageId> {
+        let index = self.page_index(id)?;
+        let copy = self.pages[index].duplicate();
+        let new_id = copy.id;
+        self.pages.insert(index + 1, copy);
+        Some(new_id)
+    }
 }
 
 #[cfg(test)]
@@ -75,4 +130,773 @@ mod tests {
         assert_eq!(doc.metadata.title, "Test");
         assert_eq!(doc.pages.len(), 1);
     }
+
+    #[test]
+    fn move_page_reorders_and_clamps() {
+        let mut doc = Document::empty("Test");
+        doc.pages.push(Page::empty());
+        doc.pages.push(Page::empty());
+        let ids: Vec<PageId> = doc.pages.iter().map(|p| p.id).collect();
+        let order = |doc: &Document| doc.pages.iter().map(|p| p.id).collect::<Vec<_>>();
+
+        assert_eq!(doc.move_page(0, 2), Some(2));
+        assert_eq!(order(&doc), vec![ids[1], ids[2], ids[0]]);
+
+        // Moving back restores the original order
+        assert_eq!(doc.move_page(2, 0), Some(0));
+        assert_eq!(order(&doc), ids);
+
+        // Dropping onto itself is a no-op, out-of-range targets clamp
+        assert_eq!(doc.move_page(1, 1), None);
+        assert_eq!(doc.move_page(0, 99), Some(2));
+        assert_eq!(doc.pages[2].id, ids[0]);
+        assert_eq!(doc.move_page(5, 0), None);
+    }
+
+    #[test]
+    fn line_style_dash_pattern_scales_with_width() {
+        assert!(LineStyle::Solid.dash_pattern(3.0).is_empty());
+        assert_eq!(LineStyle::Dashed.dash_pattern(2.0), vec![8.0, 4.0]);
+        assert_eq!(LineStyle::Dotted.dash_pattern(2.0), vec![2.0, 3.0]);
+        // Hairlines and invalid widths still get a visible pattern
+        assert_eq!(LineStyle::Dashed.dash_pattern(0.5), vec![4.0, 2.0]);
+        assert_eq!(LineStyle::Dotted.dash_pattern(f32::NAN), vec![1.0, 1.5]);
+    }
+
+    #[test]
+    fn shape_without_line_style_deserializes_as_solid() {
+        let json = r#"{"id":"00000000-0000-0000-0000-000000000001","kind":"Rectangle",
+            "bounds":{"origin":{"x":0.0,"y":0.0},"size":{"width":10.0,"height":10.0}},
+            "stroke":null,"stroke_width":1.0,"fill":null}"#;
+        let shape: ShapeElement = serde_json::from_str(json).expect("valid shape");
+        assert_eq!(shape.line_style, LineStyle::Solid);
+    }
+
+    #[test]
+    fn element_opacity_defaults_to_opaque_and_clamps() {
+        let json = r#"{"id":"00000000-0000-0000-0000-000000000001","kind":"Rectangle",
+            "bounds":{"origin":{"x":0.0,"y":0.0},"size":{"width":10.0,"height":10.0}},
+            "stroke":null,"stroke_width":1.0,"fill":null}"#;
+        let shape: ShapeElement = serde_json::from_str(json).expect("valid shape");
+        let mut element = DocumentElement::Shape(shape);
+        assert_eq!(element.opacity(), 1.0);
+
+        element.set_opacity(0.4);
+        assert_eq!(element.opacity(), 0.4);
+        element.set_opacity(1.5);
+        assert_eq!(element.opacity(), 1.0);
+        element.set_opacity(-0.2);
+        assert_eq!(element.opacity(), 0.0);
+    }
+
+    #[test]
+    fn corner_radius_clamps_to_half_the_smaller_side() {
+        let json = r#"{"id":"00000000-0000-0000-0000-000000000001","kind":"Rectangle",
+            "bounds":{"origin":{"x":0.0,"y":0.0},"size":{"width":100.0,"height":40.0}},
+            "stroke":null,"stroke_width":1.0,"fill":null}"#;
+        let mut shape: ShapeElement = serde_json::from_str(json).expect("valid shape");
+        assert_eq!(shape.corner_radius, 0.0);
+
+        shape.corner_radius = 8.0;
+        assert_eq!(shape.effective_corner_radius(), 8.0);
+        shape.corner_radius = 50.0;
+        assert_eq!(shape.effective_corner_radius(), 20.0);
+        shape.corner_radius = -4.0;
+        assert_eq!(shape.effective_corner_radius(), 0.0);
+
+        shape.corner_radius = 8.0;
+        shape.kind = ShapeKind::Ellipse;
+        assert_eq!(shape.effective_corner_radius(), 0.0);
+    }
+
+    #[test]
+    fn fill_reads_plain_colors_and_round_trips_gradients() {
+        use crate::layout::{Point, Rect, Size};
+        use crate::typography::Color;
+
+        let json = r#"{"id":"00000000-0000-0000-0000-000000000001","kind":"Rectangle",
+            "bounds":{"origin":{"x":0.0,"y":0.0},"size":{"width":10.0,"height":10.0}},
+            "stroke":null,"stroke_width":1.0,"fill":{"r":1.0,"g":0.0,"b":0.0,"a":1.0}}"#;
+        let shape: ShapeElement = serde_json::from_str(json).expect("valid shape");
+        assert_eq!(
+            shape.fill,
+            Some(Fill::Solid(Color::from_rgb(1.0, 0.0, 0.0)))
+        );
+
+        let gradient = Fill::LinearGradient {
+            start: Color::from_rgb(1.0, 0.0, 0.0),
+            end: Color::from_rgb(0.0, 0.0, 1.0),
+            angle: 90.0,
+        };
+        let json = serde_json::to_string(&gradient).expect("serialize");
+        assert_eq!(
+            serde_json::from_str::<Fill>(&json).expect("parse"),
+            gradient
+        );
+
+        // Vertical gradient over a 100 × 40 box runs top to bottom through the center
+        let bounds = Rect::new(Point::new(0.0, 0.0), Size::new(100.0, 40.0));
+        let (start, end) = gradient.gradient_line(&bounds).expect("gradient");
+        assert!((start.x - 50.0).abs() < 1e-4 && start.y.abs() < 1e-4);
+        assert!((end.x - 50.0).abs() < 1e-4 && (end.y - 40.0).abs() < 1e-4);
+        assert_eq!(Fill::Solid(Color::default()).gradient_line(&bounds), None);
+    }
+
+    #[test]
+    fn image_fit_rects() {
+        use crate::layout::{Point, Rect, Size};
+        let bounds = Rect::new(Point::new(10.0, 20.0), Size::new(200.0, 100.0));
+
+        assert_eq!(ImageFit::Stretch.image_rect(50.0, 50.0, &bounds), bounds);
+        // Square image in a wide box: contain letterboxes, cover crops top and bottom
+        assert_eq!(
+            ImageFit::Contain.image_rect(50.0, 50.0, &bounds),
+            Rect::new(Point::new(60.0, 20.0), Size::new(100.0, 100.0))
+        );
+        assert_eq!(
+            ImageFit::Cover.image_rect(50.0, 50.0, &bounds),
+            Rect::new(Point::new(10.0, -30.0), Size::new(200.0, 200.0))
+        );
+        assert_eq!(ImageFit::Cover.image_rect(0.0, 50.0, &bounds), bounds);
+    }
+
+    #[test]
+    fn document_from_before_image_fit_keeps_letterboxed_images() {
+        // Saved before images had a fit mode, when they were always letterboxed
+        let json = include_str!("../../tests/fixtures/document_before_image_fit.json");
+        let document: Document = serde_json::from_str(json).expect("valid document");
+        let image_fit = |document: &Document| match &document.pages[0].elements[0] {
+            DocumentElement::Image(image) => image.fit,
+            other => panic!("expected an image, got {:?}", other),
+        };
+        assert_eq!(image_fit(&document), ImageFit::Contain);
+
+        let saved = serde_json::to_string(&document).expect("serializable");
+        assert!(saved.contains(r#""fit":"Contain""#));
+        let reloaded: Document = serde_json::from_str(&saved).expect("valid document");
+        assert_eq!(image_fit(&reloaded), ImageFit::Contain);
+        assert_eq!(
+            reloaded.pages[0].elements[0].bounds(),
+            document.pages[0].elements[0].bounds()
+        );
+    }
+
+    fn collect_ids(elements: &[DocumentElement], ids: &mut Vec<uuid::Uuid>) {
+        for element in elements {
+            ids.push(element.id());
+            match element {
+                DocumentElement::Frame(f) => collect_ids(&f.children, ids),
+                DocumentElement::Group(g) => collect_ids(&g.children, ids),
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn duplicate_page_assigns_fresh_ids() {
+        use crate::layout::{Point, Rect, Size};
+        use crate::typography::Color;
+
+        let bounds = Rect::new(Point::new(10.0, 20.0), Size::new(100.0, 50.0));
+        let shape = DocumentElement::Shape(ShapeElement {
+            id: uuid::Uuid::new_v4(),
+            kind: ShapeKind::Rectangle,
+            bounds,
+            stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
+            stroke_width: 2.0,
+            fill: None,
+            visible: true,
+            locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
+        });
+        let group = DocumentElement::Group(GroupElement {
+            id: uuid::Uuid::new_v4(),
+            name: "Group".to_string(),
+            bounds,
+            children: vec![shape.clone()],
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+        });
+        let frame = DocumentElement::Frame(FrameElement {
+            id: uuid::Uuid::new_v4(),
+            bounds,
+            children: vec![group.clone()],
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+        });
+
+        let mut doc = Document::empty("Test");
+        doc.pages.push(Page::empty());
+        let source_id = doc.pages[0].id;
+        doc.pages[0].add_element(shape);
+        doc.pages[0].add_element(group);
+        doc.pages[0].add_element(frame);
+
+        let new_id = doc.duplicate_page(source_id).expect("page exists");
+        assert_ne!(new_id, source_id);
+        assert_eq!(doc.pages.len(), 3);
+        assert_eq!(doc.page_index(new_id), Some(1));
+
+        let mut original_ids = Vec::new();
+        collect_ids(&doc.pages[0].elements, &mut original_ids);
+        let mut copy_ids = Vec::new();
+        collect_ids(&doc.pages[1].elements, &mut copy_ids);
+        assert_eq!(original_ids.len(), 6);
+        assert_eq!(original_ids.len(), copy_ids.len());
+        assert!(copy_ids.iter().all(|id| !original_ids.contains(id)));
+        // Nested copies get distinct ids too, even from each other
+        let unique: std::collections::HashSet<_> = copy_ids.iter().collect();
+        assert_eq!(unique.len(), copy_ids.len());
+
+        // Geometry and content are preserved
+        let original = &doc.pages[0].elements;
+        let copy = &doc.pages[1].elements;
+        for (a, b) in original.iter().zip(copy.iter()) {
+            assert_eq!(a.bounds(), b.bounds());
+        }
+        match (&original[1], &copy[1]) {
+            (DocumentElement::Group(a), DocumentElement::Group(b)) => {
+                assert_eq!(a.name, b.name);
+                assert_eq!(a.children.len(), b.children.len());
+                assert_eq!(a.children[0].bounds(), b.children[0].bounds());
+            }
+            _ => panic!("expected group"),
+        }
+
+        assert!(doc.duplicate_page(PageId::new()).is_none());
+    }
+
+    fn test_shape() -> DocumentElement {
+        let json = r#"{"id":"00000000-0000-0000-0000-000000000000","kind":"Rectangle",
+            "bounds":{"origin":{"x":0.0,"y":0.0},"size":{"width":10.0,"height":10.0}},
+            "stroke":null,"stroke_width":1.0,"fill":null}"#;
+        let mut shape: ShapeElement = serde_json::from_str(json).expect("valid shape");
+        shape.id = uuid::Uuid::new_v4();
+        DocumentElement::Shape(shape)
+    }
+
+    #[test]
+    fn page_without_layers_loads_with_one_default_layer() {
+        let json = serde_json::to_string(&Page::empty()).expect("serialize");
+        let mut value: serde_json::Value = serde_json::from_str(&json).expect("json");
+        value.as_object_mut().expect("object").remove("layers");
+        let page: Page = serde_json::from_value(value).expect("valid page");
+
+        assert_eq!(page.layers.len(), 1);
+        assert_eq!(page.layers[0].name, DEFAULT_LAYER_NAME);
+        assert!(page.layers[0].visible && !page.layers[0].locked);
+    }
+
+    #[test]
+    fn layers_order_drawing_and_hide_their_elements() {
+        let mut page = Page::empty();
+        let (a, b, c) = (test_shape(), test_shape(), test_shape());
+        let ids = [a.id(), b.id(), c.id()];
+        page.add_element(a);
+        page.add_element(b);
+        page.add_element(c);
+        let drawn = |page: &Page| {
+            page.layered_elements()
+                .iter()
+                .map(|element| element.id())
+                .collect::<Vec<_>>()
+        };
+
+        // Unassigned elements live on the bottom layer
+        let bottom = page.layers[0].id;
+        assert_eq!(drawn(&page), ids);
+
+        // The first element moves onto a new top layer and is drawn last
+        let top = page.add_layer("Top");
+        assert!(page.assign_to_layer(&[ids[0]], top));
+        assert_eq!(drawn(&page), vec![ids[1], ids[2], ids[0]]);
+        assert_eq!(page.layer_of(ids[0]).map(|layer| layer.id), Some(top));
+        assert_eq!(page.layer_of(ids[1]).map(|layer| layer.id), Some(bottom));
+
+        // Reordering layers puts it back underneath
+        assert_eq!(page.move_layer(1, 0), Some(0));
+        assert_eq!(drawn(&page), vec![ids[0], ids[1], ids[2]]);
+        assert_eq!(page.move_layer(0, 0), None);
+
+        page.layer_mut(top).expect("layer").visible = false;
+        assert!(page.is_in_hidden_layer(ids[0]));
+        assert_eq!(drawn(&page), vec![ids[1], ids[2]]);
+
+        page.layer_mut(bottom).expect("layer").locked = true;
+        assert!(page.is_in_locked_layer(ids[1]));
+        assert!(!page.is_in_locked_layer(ids[0]));
+
+        // Removing a layer keeps its elements on the layer below
+        assert!(page.remove_layer(top));
+        assert_eq!(page.layers.len(), 1);
+        assert!(page.layers[0].contains(ids[0]));
+        assert!(!page.remove_layer(bottom));
+    }
+
+    #[test]
+    fn copies_and_new_elements_go_on_the_right_layer() {
+        let mut page = Page::empty();
+        page.add_element(test_shape());
+        let source = test_shape();
+        let source_id = source.id();
+        page.add_element(source);
+        let middle = page.add_layer("Middle");
+        page.assign_to_layer(&[source_id], middle);
+        let top = page.add_layer("Top");
+
+        // A duplicate of an element on a non-bottom layer stays on that layer
+        let copy = page.elements[1].duplicate();
+        let copy_id = copy.id();
+        page.add_copy(source_id, copy);
+        assert_eq!(page.layer_of(copy_id).map(|layer| layer.id), Some(middle));
+        let drawn: Vec<_> = page.layered_elements().iter().map(|e| e.id()).collect();
+        assert_eq!(drawn[1..], [source_id, copy_id]);
+
+        // New elements go on the topmost unlocked, visible layer
+        assert_eq!(page.active_layer_index(), Some(2));
+        page.layer_mut(top).expect("layer").locked = true;
+        assert_eq!(page.active_layer_index(), Some(1));
+        let created = test_shape();
+        let created_id = created.id();
+        page.add_element_to_active_layer(created);
+        let layer = page.layer_of(created_id).map(|layer| layer.id);
+        assert_eq!(layer, Some(middle));
+    }
+
+    #[test]
+    fn duplicate_page_remaps_layer_members() {
+        let mut page = Page::empty();
+        page.add_element(test_shape());
+        let top = page.add_layer("Top");
+        let id = page.elements[0].id();
+        page.assign_to_layer(&[id], top);
+
+        let copy = page.duplicate();
+        let copied_id = copy.elements[0].id();
+        assert_ne!(copy.layers[1].id, top);
+        assert_eq!(copy.layers[1].name, "Top");
+        assert_eq!(copy.layers[1].element_ids, vec![copied_id]);
+    }
+
+    #[test]
+    fn validate_reports_duplicate_ids_and_invalid_sizes() {
+        use crate::layout::{Point, Rect, Size};
+
+        let bounds = Rect::new(Point::new(0.0, 0.0), Size::new(10.0, 10.0));
+        let group = GroupElement {
+            id: uuid::Uuid::new_v4(),
+            name: "Group".to_string(),
+            bounds,
+            children: Vec::new(),
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+        };
+        let mut doc = Document::empty("Test");
+        doc.pages[0].add_element(DocumentElement::Group(group.clone()));
+        assert!(doc.validate().is_empty());
+
+        // Same id again, nested in a frame on a second page
+        let mut page = Page::empty();
+        page.add_element(DocumentElement::Frame(FrameElement {
+            id: uuid::Uuid::new_v4(),
+            bounds: Rect::new(Point::new(0.0, 0.0), Size::new(-5.0, 10.0)),
+            children: vec![DocumentElement::Group(group.clone())],
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+        }));
+        doc.pages.push(page);
+        let frame_id = doc.pages[1].elements[0].id();
+
+        assert_eq!(
+            doc.validate(),
+            vec![
+                ValidationIssue::InvalidSize {
+                    page: 2,
+                    id: frame_id,
+                    width: -5.0,
+                    height: 10.0,
+                },
+                ValidationIssue::DuplicateElementId {
+                    page: 2,
+                    id: group.id,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_warns_about_off_page_zero_size_and_missing_images() {
+        use crate::layout::{Point, Rect, Size};
+        use crate::workspace::assets::AssetCatalog;
+
+        let shape = |kind: ShapeKind, bounds: Rect| {
+            let DocumentElement::Shape(mut shape) = test_shape() else {
+                unreachable!()
+            };
+            shape.kind = kind;
+            shape.bounds = bounds;
+            DocumentElement::Shape(shape)
+        };
+        let off_page = shape(
+            ShapeKind::Rectangle,
+            Rect::new(Point::new(560.0, 10.0), Size::new(50.0, 10.0)),
+        );
+        let empty = shape(
+            ShapeKind::Rectangle,
+            Rect::new(Point::new(10.0, 10.0), Size::new(0.0, 10.0)),
+        );
+        let rule = shape(
+            ShapeKind::Line,
+            Rect::new(Point::new(10.0, 10.0), Size::new(100.0, 0.0)),
+        );
+
+        let mut catalog = AssetCatalog::new();
+        let existing = std::env::temp_dir().join(format!("testruct-{}.png", uuid::Uuid::new_v4()));
+        std::fs::write(&existing, b"png").expect("write asset");
+        let image = |source| {
+            DocumentElement::Image(ImageElement {
+                id: uuid::Uuid::new_v4(),
+                source,
+                bounds: Rect::new(Point::new(10.0, 10.0), Size::new(10.0, 10.0)),
+                visible: true,
+                locked: false,
+                opacity: 1.0,
+                rotation: 0.0,
+                fit: Default::default(),
+            })
+        };
+        let present = image(catalog.register(&existing));
+        let deleted = image(catalog.register("/nonexistent/deleted.png"));
+        let unregistered = image(crate::workspace::assets::AssetRef::new());
+
+        let mut doc = Document::empty("Test");
+        let ids: Vec<uuid::Uuid> = [&off_page, &empty, &deleted, &unregistered]
+            .iter()
+            .map(|element| element.id())
+            .collect();
+        for element in [off_page, empty, rule, present, deleted, unregistered] {
+            doc.pages[0].add_element(element);
+        }
+
+        let issues = doc.validate();
+        assert_eq!(
+            issues,
+            vec![
+                ValidationIssue::OutsidePage {
+                    page: 1,
+                    id: ids[0]
+                },
+                ValidationIssue::ZeroSize {
+                    page: 1,
+                    id: ids[1]
+                },
+            ]
+        );
+        assert!(issues.iter().all(|issue| !issue.is_error()));
+        assert_eq!(issues[1].element_id(), ids[1]);
+        assert_eq!(
+            doc.validate_assets(&catalog),
+            vec![
+                ValidationIssue::MissingAsset {
+                    page: 1,
+                    id: ids[2]
+                },
+                ValidationIssue::MissingAsset {
+                    page: 1,
+                    id: ids[3]
+                },
+            ]
+        );
+
+        std::fs::remove_file(&existing).expect("remove asset");
+    }
+
+    #[test]
+    fn unlocked_element_ids_skip_locked_elements() {
+        use crate::layout::{Point, Rect, Size};
+
+        let bounds = Rect::new(Point::new(0.0, 0.0), Size::new(10.0, 10.0));
+        let group = |children: Vec<DocumentElement>, locked: bool| {
+            DocumentElement::Group(GroupElement {
+                id: uuid::Uuid::new_v4(),
+                name: "Group".to_string(),
+                bounds,
+                children,
+                visible: true,
+                locked,
+                opacity: 1.0,
+            })
+        };
+        let free = group(Vec::new(), false);
+        let pinned = group(Vec::new(), true);
+        let sibling = group(Vec::new(), false);
+        let mixed = group(vec![pinned.clone(), sibling.clone()], false);
+        let intact = group(vec![free.clone()], false);
+
+        let mut page = Page::empty();
+        page.add_element(pinned.clone());
+        page.add_element(mixed);
+        page.add_element(intact.clone());
+
+        // The mixed group is replaced by its unlocked child
+        assert_eq!(page.unlocked_element_ids(), vec![sibling.id(), intact.id()]);
+    }
+
+    #[test]
+    fn table_cells_follow_the_grid() {
+        use crate::layout::{Point, Rect, Size};
+
+        let bounds = Rect::new(Point::new(10.0, 20.0), Size::new(300.0, 100.0));
+        let mut table = TableElement::new(bounds, 2, 3);
+        assert_eq!(table.cells.len(), 6);
+        assert_eq!(
+            table.cell_bounds(1, 2),
+            Rect::new(Point::new(210.0, 70.0), Size::new(100.0, 50.0))
+        );
+        assert_eq!(table.cell_at(Point::new(215.0, 75.0)), Some((1, 2)));
+        assert_eq!(table.cell_at(Point::new(310.0, 120.0)), Some((1, 2)));
+        assert_eq!(table.cell_at(Point::new(5.0, 75.0)), None);
+
+        assert!(table.set_cell_text(1, 2, "解答"));
+        assert!(!table.set_cell_text(2, 0, "out of range"));
+        assert_eq!(table.cell(1, 2).unwrap().get_plain_text(), "解答");
+        assert_eq!(table.cell(0, 0).unwrap().get_plain_text(), "");
+
+        // A zero-sized table never creates an empty grid
+        assert_eq!(TableElement::new(bounds, 0, 0).cells.len(), 1);
+
+        let mut doc = Document::empty("Test");
+        let mut broken = TableElement::new(bounds, 2, 3);
+        broken.cells.pop();
+        doc.pages[0].add_element(DocumentElement::Table(table));
+        doc.pages[0].add_element(DocumentElement::Table(broken.clone()));
+        assert_eq!(
+            doc.validate(),
+            vec![ValidationIssue::InvalidTableCells {
+                page: 1,
+                id: broken.id,
+                rows: 2,
+                columns: 3,
+                cells: 5,
+            }]
+        );
+    }
+
+    #[test]
+    fn oversized_qr_payload_is_reported() {
+        use crate::layout::{Point, Rect, Size};
+
+        let bounds = Rect::new(Point::new(0.0, 0.0), Size::new(80.0, 80.0));
+        let id_code = QrCodeElement::new(bounds, "student:0042");
+        let mut oversized = QrCodeElement::new(bounds, "x".repeat(1300));
+        oversized.error_correction = QrErrorCorrection::High;
+
+        let mut doc = Document::empty("Test");
+        doc.pages[0].add_element(DocumentElement::QrCode(id_code));
+        doc.pages[0].add_element(DocumentElement::QrCode(oversized.clone()));
+        assert_eq!(
+            doc.validate(),
+            vec![ValidationIssue::InvalidQrCode {
+                page: 1,
+                id: oversized.id,
+                reason: QrEncodeError::PayloadTooLong {
+                    length: 1300,
+                    max: 1273,
+                    level: 'H',
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn reorder_elements_keeps_relative_order() {
+        let mut page = Page::empty();
+        for _ in 0..5 {
+            page.add_element(DocumentElement::Group(GroupElement {
+                id: uuid::Uuid::new_v4(),
+                name: "Group".to_string(),
+                bounds: Default::default(),
+                children: Vec::new(),
+                visible: true,
+                locked: false,
+                opacity: 1.0,
+            }));
+        }
+        let ids = page.element_order();
+        let picked = [ids[1], ids[3]];
+        let order = |indices: [usize; 5]| indices.map(|i| ids[i]).to_vec();
+
+        assert!(page.reorder_elements(&picked, ZOrderChange::BringForward));
+        assert_eq!(page.element_order(), order([0, 2, 1, 4, 3]));
+        assert!(page.reorder_elements(&picked, ZOrderChange::BringToFront));
+        assert_eq!(page.element_order(), order([0, 2, 4, 1, 3]));
+        assert!(!page.reorder_elements(&picked, ZOrderChange::BringForward));
+        assert!(page.reorder_elements(&picked, ZOrderChange::SendToBack));
+        assert_eq!(page.element_order(), order([1, 3, 0, 2, 4]));
+        assert!(page.reorder_elements(&picked, ZOrderChange::BringForward));
+        assert!(page.reorder_elements(&picked, ZOrderChange::SendBackward));
+        assert_eq!(page.element_order(), order([1, 3, 0, 2, 4]));
+
+        page.restore_element_order(&ids);
+        assert_eq!(page.element_order(), ids);
+    }
+
+    #[test]
+    fn pages_can_override_the_document_size() {
+        let mut doc = Document::empty("Test");
+        let mut answer_key = Page::empty();
+        answer_key.metadata.page_size = Some(PageSize::Custom(842.0, 595.0));
+        doc.pages.push(answer_key);
+
+        let sizes: Vec<_> = doc.pages().map(|page| doc.page_size_of(page)).collect();
+        assert_eq!(sizes[0], PageSize::A4.to_size());
+        assert_eq!(sizes[1], crate::layout::Size::new(842.0, 595.0));
+    }
+
+    #[test]
+    fn mixed_page_sizes_check_elements_against_their_own_page() {
+        let mut doc = Document::empty("Test");
+        let mut landscape = Page::empty();
+        landscape.metadata.page_size = Some(PageSize::Custom(842.0, 595.0));
+        doc.pages.push(landscape);
+
+        // Past the right edge of A4 (595 wide) but inside the landscape page
+        let mut ids = Vec::new();
+        for page in &mut doc.pages {
+            let mut element = test_shape();
+            if let DocumentElement::Shape(shape) = &mut element {
+                shape.bounds.origin.x = 700.0;
+            }
+            ids.push(element.id());
+            page.add_element(element);
+        }
+
+        assert_eq!(
+            doc.validate(),
+            vec![ValidationIssue::OutsidePage {
+                page: 1,
+                id: ids[0],
+            }]
+        );
+    }
+
+    #[test]
+    fn flatten_group_keeps_z_order_and_bakes_in_opacity() {
+        let group = |children: Vec<DocumentElement>, opacity: f32| {
+            DocumentElement::Group(GroupElement {
+                id: uuid::Uuid::new_v4(),
+                name: "Group".to_string(),
+                bounds: Default::default(),
+                children,
+                visible: true,
+                locked: false,
+                opacity,
+            })
+        };
+        let (a, b, c) = (test_shape(), test_shape(), test_shape());
+        let leaf_ids = [a.id(), b.id(), c.id()];
+        let inner = group(vec![b, c], 0.5);
+        let outer = group(vec![a, inner], 0.5);
+        let outer_id = outer.id();
+
+        let mut page = Page::empty();
+        let (back, front) = (test_shape(), test_shape());
+        let (back_id, front_id) = (back.id(), front.id());
+        page.add_element(back);
+        page.add_element(outer);
+        page.add_element(front);
+        page.layers[0].element_ids = page.element_order();
+
+        let ids = page.flatten_group(outer_id).expect("flattened");
+        assert_eq!(ids, leaf_ids);
+        let expected = [back_id, leaf_ids[0], leaf_ids[1], leaf_ids[2], front_id];
+        assert_eq!(page.element_order(), expected);
+        assert_eq!(page.layers[0].element_ids, expected);
+        let opacities: Vec<f32> = page.elements.iter().map(|e| e.opacity()).collect();
+        assert_eq!(opacities, [1.0, 0.5, 0.25, 0.25, 1.0]);
+
+        assert!(page.flatten_group(back_id).is_none());
+    }
+
+    #[test]
+    fn ungroup_dissolves_one_level_of_nested_groups() {
+        let shapes: Vec<DocumentElement> = (0..5).map(|_| test_shape()).collect();
+        let ids: Vec<uuid::Uuid> = shapes.iter().map(DocumentElement::id).collect();
+        let mut page = Page::empty();
+        for shape in shapes {
+            page.add_element(shape);
+        }
+        page.layers[0].element_ids = page.element_order();
+
+        let (inner_id, outer_id) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        assert!(page.group_elements(inner_id, &[ids[0], ids[1], ids[2]]));
+        assert_eq!(page.element_order(), [inner_id, ids[3], ids[4]]);
+        assert!(page.group_elements(outer_id, &[inner_id, ids[3]]));
+        assert_eq!(page.element_order(), [outer_id, ids[4]]);
+        assert_eq!(page.layers[0].element_ids, [outer_id, ids[4]]);
+
+        assert_eq!(page.ungroup(outer_id), Some(vec![inner_id, ids[3]]));
+        assert_eq!(page.element_order(), [inner_id, ids[3], ids[4]]);
+        assert_eq!(page.layers[0].element_ids, [inner_id, ids[3], ids[4]]);
+        let inner_children: Vec<uuid::Uuid> = page.elements[0]
+            .children()
+            .expect("inner group intact")
+            .iter()
+            .map(DocumentElement::id)
+            .collect();
+        assert_eq!(inner_children, &ids[..3]);
+
+        assert!(page.ungroup(ids[4]).is_none());
+        assert!(!page.group_elements(uuid::Uuid::new_v4(), &[ids[0]]));
+    }
+
+    #[test]
+    fn element_path_lists_enclosing_groups() {
+        let (leaf, sibling) = (test_shape(), test_shape());
+        let leaf_id = leaf.id();
+        let inner = DocumentElement::Frame(FrameElement {
+            id: uuid::Uuid::new_v4(),
+            bounds: Default::default(),
+            children: vec![sibling, leaf],
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+        });
+        let inner_id = inner.id();
+        let outer = DocumentElement::Group(GroupElement {
+            id: uuid::Uuid::new_v4(),
+            name: "Group".to_string(),
+            bounds: Default::default(),
+            children: vec![inner],
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+        });
+        let outer_id = outer.id();
+        let mut page = Page::empty();
+        page.add_element(test_shape());
+        page.add_element(outer);
+
+        let path: Vec<_> = page
+            .element_path(leaf_id)
+            .expect("path")
+            .iter()
+            .map(|element| element.id())
+            .collect();
+        assert_eq!(path, [outer_id, inner_id, leaf_id]);
+        assert_eq!(page.element_path(outer_id).map(|path| path.len()), Some(1));
+        assert!(page.element_path(uuid::Uuid::new_v4()).is_none());
+
+        let nested = page.element_mut(leaf_id).expect("nested element");
+        assert_eq!(nested.id(), leaf_id);
+        assert!(page.element_mut(uuid::Uuid::new_v4()).is_none());
+    }
 }
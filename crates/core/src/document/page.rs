@@ -1,4 +1,6 @@
+use super::layer::{default_layers, Layer, LayerId};
 use super::PageMetadata;
+use super::QrCodeElement;
 use serde::{Deserialize, Serialize};
 
 /// Default visibility value for elements (true for backwards compatibility)
@@ -11,6 +13,18 @@ fn default_locked() -> bool {
     false
 }
 
+/// Default opacity for elements (fully opaque for backwards compatibility)
+fn default_opacity() -> f32 {
+    1.0
+}
+
+/// Padding of text boxes from files saved before it was configurable
+pub const DEFAULT_TEXT_PADDING: f32 = 5.0;
+
+fn default_text_padding() -> crate::layout::Insets {
+    crate::layout::Insets::uniform(DEFAULT_TEXT_PADDING)
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct PageId(uuid::Uuid);
 
@@ -31,6 +45,9 @@ pub struct Page {
     pub id: PageId,
     pub metadata: PageMetadata,
     pub elements: Vec<DocumentElement>,
+    /// Named layers from bottom to top (pages from older files get one)
+    #[serde(default = "default_layers")]
+    pub layers: Vec<Layer>,
 }
 
 impl Page {
@@ -39,6 +56,7 @@ impl Page {
             id: PageId::new(),
             metadata: PageMetadata::default(),
             elements: Vec::new(),
+            layers: default_layers(),
         }
     }
 
@@ -46,6 +64,37 @@ impl Page {
         self.elements.push(element);
     }
 
+    /// Add a new element in front of the active layer's elements
+    ///
+    /// Used for elements the user creates or pastes, so they land where the
+    /// user is working instead of on the bottom layer.
+    pub fn add_element_to_active_layer(&mut self, element: DocumentElement) {
+        match self.active_layer_index() {
+            Some(index) => self.add_element_to_layer(element, index),
+            None => self.add_element(element),
+        }
+    }
+
+    /// Add a copy of the top-level element `source_id` on the original's layer
+    pub fn add_copy(&mut self, source_id: uuid::Uuid, copy: DocumentElement) {
+        match self.layer_index_of(source_id) {
+            Some(index) => self.add_element_to_layer(copy, index),
+            None => self.add_element(copy),
+        }
+    }
+
+    /// Add an element in front of the elements of the layer at `layer_index`
+    fn add_element_to_layer(&mut self, element: DocumentElement, layer_index: usize) {
+        let id = element.id();
+        self.elements.push(element);
+        if layer_index < self.layers.len() {
+            for layer in &mut self.layers {
+                layer.element_ids.retain(|member| *member != id);
+            }
+            self.layers[layer_index].element_ids.push(id);
+        }
+    }
+
     /// Bring an element to the front (top of z-order stack)
     pub fn bring_to_front(&mut self, element_id: uuid::Uuid) -> bool {
         if let Some(pos) = self.elements.iter().position(|e| e.id() == element_id) {
@@ -96,10 +145,436 @@ impl Page {
         }
     }
 
+    /// Restack several elements at once, keeping their relative order
+    ///
+    /// Forward/backward moves each run of selected elements past one
+    /// unselected neighbour. Returns false when the order did not change.
+    pub fn reorder_elements(&mut self, element_ids: &[uuid::Uuid], change: ZOrderChange) -> bool {
+        let before = self.element_order();
+        let selected = |element: &DocumentElement| element_ids.contains(&element.id());
+        match change {
+            ZOrderChange::BringToFront | ZOrderChange::SendToBack => {
+                let (picked, rest): (Vec<_>, Vec<_>) = self.elements.drain(..).partition(selected);
+                self.elements = if change == ZOrderChange::BringToFront {
+                    rest.into_iter().chain(picked).collect()
+                } else {
+                    picked.into_iter().chain(rest).collect()
+                };
+            }
+            ZOrderChange::BringForward => {
+                for i in (0..self.elements.len().saturating_sub(1)).rev() {
+                    if selected(&self.elements[i]) && !selected(&self.elements[i + 1]) {
+                        self.elements.swap(i, i + 1);
+                    }
+                }
+            }
+            ZOrderChange::SendBackward => {
+                for i in 1..self.elements.len() {
+                    if selected(&self.elements[i]) && !selected(&self.elements[i - 1]) {
+                        self.elements.swap(i, i - 1);
+                    }
+                }
+            }
+        }
+        self.element_order() != before
+    }
+
+    /// Top-level element ids from back to front
+    pub fn element_order(&self) -> Vec<uuid::Uuid> {
+        self.elements.iter().map(DocumentElement::id).collect()
+    }
+
+    /// Put the top-level elements back into a previously saved order
+    ///
+    /// Elements missing from `order` keep their place at the front.
+    pub fn restore_element_order(&mut self, order: &[uuid::Uuid]) {
+        self.elements.sort_by_key(|element| {
+            order
+                .iter()
+                .position(|id| *id == element.id())
+                .unwrap_or(usize::MAX)
+        });
+    }
+
     /// Get the z-order index of an element (0 = back, len-1 = front)
     pub fn z_order(&self, element_id: uuid::Uuid) -> Option<usize> {
         self.elements.iter().position(|e| e.id() == element_id)
     }
+
+    /// Ids to select for "select all", leaving locked elements out
+    ///
+    /// Unlocked top-level elements are returned as is. A frame or group that
+    /// holds locked elements somewhere inside is replaced by its selectable
+    /// children, so moving the selection never drags a locked element along.
+    /// Elements on locked or hidden layers are left out as well.
+    pub fn unlocked_element_ids(&self) -> Vec<uuid::Uuid> {
+        let mut ids = Vec::new();
+        for element in &self.elements {
+            let id = element.id();
+            if !self.is_in_locked_layer(id) && !self.is_in_hidden_layer(id) {
+                collect_unlocked_ids(std::slice::from_ref(element), &mut ids);
+            }
+        }
+        ids
+    }
+
+    /// Deep-copy the page with a fresh page id and fresh element ids
+    ///
+    /// Layers are copied too, with fresh ids and their elements remapped to
+    /// the copies.
+    pub fn duplicate(&self) -> Self {
+        let elements: Vec<DocumentElement> = self
+            .elements
+            .iter()
+            .map(DocumentElement::duplicate)
+            .collect();
+        let copied_id = |id: &uuid::Uuid| {
+            self.z_order(*id)
+                .and_then(|index| elements.get(index))
+                .map(DocumentElement::id)
+        };
+        let layers = self
+            .layers
+            .iter()
+            .map(|layer| Layer {
+                id: LayerId::new(),
+                element_ids: layer.element_ids.iter().filter_map(copied_id).collect(),
+                ..layer.clone()
+            })
+            .collect();
+        Self {
+            id: PageId::new(),
+            metadata: self.metadata.clone(),
+            elements,
+            layers,
+        }
+    }
+
+    /// Index of the layer holding a top-level element
+    ///
+    /// Elements no layer lists belong to the bottom layer. Returns `None` only
+    /// for a page without layers.
+    pub fn layer_index_of(&self, element_id: uuid::Uuid) -> Option<usize> {
+        if self.layers.is_empty() {
+            return None;
+        }
+        Some(
+            self.layers
+                .iter()
+                .position(|layer| layer.contains(element_id))
+                .unwrap_or(0),
+        )
+    }
+
+    /// Index of the layer new elements go on
+    ///
+    /// The topmost layer that is visible and unlocked, so a new element can be
+    /// selected right away, or the top layer when every layer is hidden or
+    /// locked. Returns `None` only for a page without layers.
+    pub fn active_layer_index(&self) -> Option<usize> {
+        self.layers
+            .iter()
+            .rposition(|layer| layer.visible && !layer.locked)
+            .or_else(|| self.layers.len().checked_sub(1))
+    }
+
+    /// The layer holding a top-level element
+    pub fn layer_of(&self, element_id: uuid::Uuid) -> Option<&Layer> {
+        self.layer_index_of(element_id)
+            .map(|index| &self.layers[index])
+    }
+
+    /// Look up a layer by id
+    pub fn layer_mut(&mut self, layer_id: LayerId) -> Option<&mut Layer> {
+        self.layers.iter_mut().find(|layer| layer.id == layer_id)
+    }
+
+    /// Whether a top-level element sits on a hidden layer
+    pub fn is_in_hidden_layer(&self, element_id: uuid::Uuid) -> bool {
+        self.layer_of(element_id)
+            .is_some_and(|layer| !layer.visible)
+    }
+
+    /// Whether a top-level element sits on a locked layer
+    pub fn is_in_locked_layer(&self, element_id: uuid::Uuid) -> bool {
+        self.layer_of(element_id).is_some_and(|layer| layer.locked)
+    }
+
+    /// Top-level elements of one layer, from back to front
+    pub fn layer_elements(&self, layer_index: usize) -> Vec<&DocumentElement> {
+        self.elements
+            .iter()
+            .filter(|element| self.layer_index_of(element.id()) == Some(layer_index))
+            .collect()
+    }
+
+    /// Top-level elements in drawing order, leaving out hidden layers
+    ///
+    /// Layers are drawn bottom to top, each in its own element order.
+    pub fn layered_elements(&self) -> Vec<&DocumentElement> {
+        if self.layers.is_empty() {
+            return self.elements.iter().collect();
+        }
+        (0..self.layers.len())
+            .filter(|&index| self.layers[index].visible)
+            .flat_map(|index| self.layer_elements(index))
+            .collect()
+    }
+
+    /// Add an empty layer on top and return its id
+    pub fn add_layer(&mut self, name: impl Into<String>) -> LayerId {
+        let layer = Layer::new(name);
+        let id = layer.id;
+        self.layers.push(layer);
+        id
+    }
+
+    /// Remove a layer, handing its elements to the layer below it
+    ///
+    /// The elements of the bottom layer go to the layer that becomes the new
+    /// bottom. The last remaining layer can't be removed.
+    pub fn remove_layer(&mut self, layer_id: LayerId) -> bool {
+        if self.layers.len() < 2 {
+            return false;
+        }
+        let Some(index) = self.layers.iter().position(|layer| layer.id == layer_id) else {
+            return false;
+        };
+        self.claim_unassigned_elements();
+        let removed = self.layers.remove(index);
+        self.layers[index.saturating_sub(1)]
+            .element_ids
+            .extend(removed.element_ids);
+        true
+    }
+
+    /// Move the layer at `from` so it ends up at index `to`
+    ///
+    /// Same rules as `Document::move_page`: out-of-range targets are clamped
+    /// and `None` means nothing moved.
+    pub fn move_layer(&mut self, from: usize, to: usize) -> Option<usize> {
+        if from >= self.layers.len() {
+            return None;
+        }
+        let to = to.min(self.layers.len() - 1);
+        if from == to {
+            return None;
+        }
+        self.claim_unassigned_elements();
+        let layer = self.layers.remove(from);
+        self.layers.insert(to, layer);
+        Some(to)
+    }
+
+    /// Record unassigned elements on the bottom layer
+    ///
+    /// Done before layers are moved or removed so those elements stay on the
+    /// layer they were shown on instead of following whichever layer ends up
+    /// at the bottom.
+    fn claim_unassigned_elements(&mut self) {
+        let unassigned: Vec<uuid::Uuid> = self
+            .elements
+            .iter()
+            .map(DocumentElement::id)
+            .filter(|id| !self.layers.iter().any(|layer| layer.contains(*id)))
+            .collect();
+        if let Some(bottom) = self.layers.first_mut() {
+            bottom.element_ids.extend(unassigned);
+        }
+    }
+
+    /// Assign top-level elements to a layer, taking them off their old layers
+    pub fn assign_to_layer(&mut self, element_ids: &[uuid::Uuid], layer_id: LayerId) -> bool {
+        if !self.layers.iter().any(|layer| layer.id == layer_id) {
+            return false;
+        }
+        let element_ids: Vec<uuid::Uuid> = element_ids
+            .iter()
+            .copied()
+            .filter(|id| self.z_order(*id).is_some())
+            .collect();
+        for layer in &mut self.layers {
+            layer.element_ids.retain(|id| !element_ids.contains(id));
+            if layer.id == layer_id {
+                layer.element_ids.extend(&element_ids);
+            }
+        }
+        !element_ids.is_empty()
+    }
+
+    /// Chain of elements from a top-level element down to `element_id`
+    ///
+    /// The last entry is the element itself and the ones before it are the
+    /// frames and groups containing it, outermost first.
+    pub fn element_path(&self, element_id: uuid::Uuid) -> Option<Vec<&DocumentElement>> {
+        fn search<'a>(
+            elements: &'a [DocumentElement],
+            element_id: uuid::Uuid,
+            path: &mut Vec<&'a DocumentElement>,
+        ) -> bool {
+            elements.iter().any(|element| {
+                path.push(element);
+                let found = element.id() == element_id
+                    || element
+                        .children()
+                        .is_some_and(|children| search(children, element_id, path));
+                if !found {
+                    path.pop();
+                }
+                found
+            })
+        }
+
+        let mut path = Vec::new();
+        search(&self.elements, element_id, &mut path).then_some(path)
+    }
+
+    /// Element `element_id` on this page, including those inside frames and groups
+    pub fn element_mut(&mut self, element_id: uuid::Uuid) -> Option<&mut DocumentElement> {
+        fn search(
+            elements: &mut [DocumentElement],
+            element_id: uuid::Uuid,
+        ) -> Option<&mut DocumentElement> {
+            elements.iter_mut().find_map(|element| {
+                if element.id() == element_id {
+                    return Some(element);
+                }
+                match element {
+                    DocumentElement::Frame(frame) => search(&mut frame.children, element_id),
+                    DocumentElement::Group(group) => search(&mut group.children, element_id),
+                    _ => None,
+                }
+            })
+        }
+
+        search(&mut self.elements, element_id)
+    }
+
+    /// Move top-level elements into a new frame used as a group container
+    ///
+    /// The frame takes the z-order position and layer of the topmost of them.
+    /// The elements keep their relative order and are moved as they are, so
+    /// groups among them stay nested. Returns `false` when none of
+    /// `element_ids` is a top-level element.
+    pub fn group_elements(&mut self, group_id: uuid::Uuid, element_ids: &[uuid::Uuid]) -> bool {
+        let indices: Vec<usize> = (0..self.elements.len())
+            .filter(|&index| element_ids.contains(&self.elements[index].id()))
+            .collect();
+        let Some(&topmost) = indices.last() else {
+            return false;
+        };
+        let topmost_id = self.elements[topmost].id();
+
+        let mut children: Vec<DocumentElement> = indices
+            .iter()
+            .rev()
+            .map(|&index| self.elements.remove(index))
+            .collect();
+        children.reverse();
+        let grouped_ids: Vec<uuid::Uuid> = children.iter().map(DocumentElement::id).collect();
+
+        let mut bounds = *children[0].bounds();
+        for child in &children[1..] {
+            let other = child.bounds();
+            let max_x =
+                (bounds.origin.x + bounds.size.width).max(other.origin.x + other.size.width);
+            let max_y =
+                (bounds.origin.y + bounds.size.height).max(other.origin.y + other.size.height);
+            bounds.origin.x = bounds.origin.x.min(other.origin.x);
+            bounds.origin.y = bounds.origin.y.min(other.origin.y);
+            bounds.size.width = max_x - bounds.origin.x;
+            bounds.size.height = max_y - bounds.origin.y;
+        }
+
+        let frame = DocumentElement::Frame(FrameElement {
+            id: group_id,
+            bounds,
+            children,
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+        });
+        self.elements.insert(topmost + 1 - indices.len(), frame);
+        for layer in &mut self.layers {
+            if let Some(position) = layer.element_ids.iter().position(|id| *id == topmost_id) {
+                layer.element_ids[position] = group_id;
+            }
+            layer.element_ids.retain(|id| !grouped_ids.contains(id));
+        }
+        true
+    }
+
+    /// Replace a top-level frame or group with its direct children
+    ///
+    /// Only one level is dissolved: frames and groups among the children are
+    /// kept with their own children. The children take the container's place
+    /// in the z-order and on its layer. Returns their ids, or `None` when
+    /// `group_id` is not a top-level frame or group with children.
+    pub fn ungroup(&mut self, group_id: uuid::Uuid) -> Option<Vec<uuid::Uuid>> {
+        self.replace_container(group_id, |group| match group {
+            DocumentElement::Frame(f) => f.children,
+            DocumentElement::Group(g) => g.children,
+            element => vec![element],
+        })
+    }
+
+    /// Replace a top-level frame or group with its flattened children
+    ///
+    /// The children take the container's place in the z-order and on its
+    /// layer. Returns the ids of the elements that replaced it, or `None` when
+    /// `group_id` is not a top-level frame or group with children.
+    pub fn flatten_group(&mut self, group_id: uuid::Uuid) -> Option<Vec<uuid::Uuid>> {
+        self.replace_container(group_id, DocumentElement::into_flattened)
+    }
+
+    fn replace_container(
+        &mut self,
+        group_id: uuid::Uuid,
+        expand: impl FnOnce(DocumentElement) -> Vec<DocumentElement>,
+    ) -> Option<Vec<uuid::Uuid>> {
+        let index = self.z_order(group_id)?;
+        if self.elements[index].children().is_none_or(<[_]>::is_empty) {
+            return None;
+        }
+        let children = expand(self.elements.remove(index));
+        let ids: Vec<uuid::Uuid> = children.iter().map(DocumentElement::id).collect();
+        self.elements.splice(index..index, children);
+        for layer in &mut self.layers {
+            if let Some(position) = layer.element_ids.iter().position(|id| *id == group_id) {
+                layer
+                    .element_ids
+                    .splice(position..=position, ids.iter().copied());
+            }
+        }
+        Some(ids)
+    }
+}
+
+/// Z-order operations for the selected elements of a page
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZOrderChange {
+    BringToFront,
+    BringForward,
+    SendBackward,
+    SendToBack,
+}
+
+fn collect_unlocked_ids(elements: &[DocumentElement], ids: &mut Vec<uuid::Uuid>) {
+    for element in elements {
+        if element.is_locked() {
+            continue;
+        }
+        match element.children() {
+            Some(children) if contains_locked(children) => collect_unlocked_ids(children, ids),
+            _ => ids.push(element.id()),
+        }
+    }
+}
+
+fn contains_locked(elements: &[DocumentElement]) -> bool {
+    elements
+        .iter()
+        .any(|element| element.is_locked() || element.children().is_some_and(contains_locked))
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -109,6 +584,8 @@ pub enum DocumentElement {
     Image(ImageElement),
     Shape(ShapeElement),
     Group(GroupElement),
+    Table(TableElement),
+    QrCode(QrCodeElement),
 }
 
 impl DocumentElement {
@@ -120,6 +597,17 @@ impl DocumentElement {
             DocumentElement::Image(i) => i.id,
             DocumentElement::Shape(s) => s.id,
             DocumentElement::Group(g) => g.id,
+            DocumentElement::Table(t) => t.id,
+            DocumentElement::QrCode(q) => q.id,
+        }
+    }
+
+    /// Nested elements of frames and groups
+    pub fn children(&self) -> Option<&[DocumentElement]> {
+        match self {
+            DocumentElement::Frame(f) => Some(&f.children),
+            DocumentElement::Group(g) => Some(&g.children),
+            _ => None,
         }
     }
 
@@ -131,6 +619,8 @@ impl DocumentElement {
             DocumentElement::Image(i) => i.visible,
             DocumentElement::Shape(s) => s.visible,
             DocumentElement::Group(g) => g.visible,
+            DocumentElement::Table(t) => t.visible,
+            DocumentElement::QrCode(q) => q.visible,
         }
     }
 
@@ -142,6 +632,8 @@ impl DocumentElement {
             DocumentElement::Image(i) => i.visible = visible,
             DocumentElement::Shape(s) => s.visible = visible,
             DocumentElement::Group(g) => g.visible = visible,
+            DocumentElement::Table(t) => t.visible = visible,
+            DocumentElement::QrCode(q) => q.visible = visible,
         }
     }
 
@@ -153,6 +645,8 @@ impl DocumentElement {
             DocumentElement::Image(i) => i.locked,
             DocumentElement::Shape(s) => s.locked,
             DocumentElement::Group(g) => g.locked,
+            DocumentElement::Table(t) => t.locked,
+            DocumentElement::QrCode(q) => q.locked,
         }
     }
 
@@ -164,9 +658,133 @@ impl DocumentElement {
             DocumentElement::Image(i) => i.locked = locked,
             DocumentElement::Shape(s) => s.locked = locked,
             DocumentElement::Group(g) => g.locked = locked,
+            DocumentElement::Table(t) => t.locked = locked,
+            DocumentElement::QrCode(q) => q.locked = locked,
+        }
+    }
+
+    /// Opacity from 0.0 to 1.0
+    pub fn opacity(&self) -> f32 {
+        match self {
+            DocumentElement::Frame(f) => f.opacity,
+            DocumentElement::Text(t) => t.opacity,
+            DocumentElement::Image(i) => i.opacity,
+            DocumentElement::Shape(s) => s.opacity,
+            DocumentElement::Group(g) => g.opacity,
+            DocumentElement::Table(t) => t.opacity,
+            DocumentElement::QrCode(q) => q.opacity,
+        }
+    }
+
+    /// Set the opacity, clamped to 0.0–1.0
+    pub fn set_opacity(&mut self, opacity: f32) {
+        let opacity = if opacity.is_finite() {
+            opacity.clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        match self {
+            DocumentElement::Frame(f) => f.opacity = opacity,
+            DocumentElement::Text(t) => t.opacity = opacity,
+            DocumentElement::Image(i) => i.opacity = opacity,
+            DocumentElement::Shape(s) => s.opacity = opacity,
+            DocumentElement::Group(g) => g.opacity = opacity,
+            DocumentElement::Table(t) => t.opacity = opacity,
+            DocumentElement::QrCode(q) => q.opacity = opacity,
+        }
+    }
+
+    /// Rotation in degrees (always 0 for frames, groups, tables and QR codes)
+    pub fn rotation(&self) -> f32 {
+        match self {
+            DocumentElement::Text(t) => t.rotation,
+            DocumentElement::Image(i) => i.rotation,
+            DocumentElement::Shape(s) => s.rotation,
+            DocumentElement::Frame(_)
+            | DocumentElement::Group(_)
+            | DocumentElement::Table(_)
+            | DocumentElement::QrCode(_) => 0.0,
         }
     }
 
+    /// Whether the element carries a rotation (frames, groups, tables and QR codes do not)
+    pub fn can_rotate(&self) -> bool {
+        !matches!(
+            self,
+            DocumentElement::Frame(_)
+                | DocumentElement::Group(_)
+                | DocumentElement::Table(_)
+                | DocumentElement::QrCode(_)
+        )
+    }
+
+    /// Set the rotation in degrees; returns false for elements that cannot rotate
+    pub fn set_rotation(&mut self, degrees: f32) -> bool {
+        match self {
+            DocumentElement::Text(t) => t.rotation = degrees,
+            DocumentElement::Image(i) => i.rotation = degrees,
+            DocumentElement::Shape(s) => s.rotation = degrees,
+            DocumentElement::Frame(_)
+            | DocumentElement::Group(_)
+            | DocumentElement::Table(_)
+            | DocumentElement::QrCode(_) => return false,
+        }
+        true
+    }
+
+    /// Deep-copy the element, assigning new ids to it and all nested children
+    pub fn duplicate(&self) -> Self {
+        let mut copy = self.clone();
+        copy.regenerate_ids();
+        copy
+    }
+
+    /// Replace this element's id (and its children's, recursively) with new ids
+    pub fn regenerate_ids(&mut self) {
+        match self {
+            DocumentElement::Frame(f) => {
+                f.id = uuid::Uuid::new_v4();
+                f.children.iter_mut().for_each(DocumentElement::regenerate_ids);
+            }
+            DocumentElement::Text(t) => t.id = uuid::Uuid::new_v4(),
+            DocumentElement::Image(i) => i.id = uuid::Uuid::new_v4(),
+            DocumentElement::Shape(s) => s.id = uuid::Uuid::new_v4(),
+            DocumentElement::Table(t) => t.id = uuid::Uuid::new_v4(),
+            DocumentElement::QrCode(q) => q.id = uuid::Uuid::new_v4(),
+            DocumentElement::Group(g) => {
+                g.id = uuid::Uuid::new_v4();
+                g.children.iter_mut().for_each(DocumentElement::regenerate_ids);
+            }
+        }
+    }
+
+    /// Dissolve frames and groups into their leaf elements, recursively
+    ///
+    /// Children are already in page coordinates. A container's opacity is
+    /// multiplied into its children's, and a hidden or locked container hides
+    /// or locks them. Other elements come back as they are.
+    pub fn into_flattened(self) -> Vec<DocumentElement> {
+        let (children, opacity, visible, locked) = match self {
+            DocumentElement::Frame(f) => (f.children, f.opacity, f.visible, f.locked),
+            DocumentElement::Group(g) => (g.children, g.opacity, g.visible, g.locked),
+            element => return vec![element],
+        };
+        children
+            .into_iter()
+            .flat_map(DocumentElement::into_flattened)
+            .map(|mut child| {
+                child.set_opacity(child.opacity() * opacity);
+                if !visible {
+                    child.set_visible(false);
+                }
+                if locked {
+                    child.set_locked(true);
+                }
+                child
+            })
+            .collect()
+    }
+
     /// Get the bounds of any document element
     pub fn bounds(&self) -> &super::super::layout::Rect {
         match self {
@@ -175,6 +793,8 @@ impl DocumentElement {
             DocumentElement::Image(i) => &i.bounds,
             DocumentElement::Shape(s) => &s.bounds,
             DocumentElement::Group(g) => &g.bounds,
+            DocumentElement::Table(t) => &t.bounds,
+            DocumentElement::QrCode(q) => &q.bounds,
         }
     }
 
@@ -186,6 +806,8 @@ impl DocumentElement {
             DocumentElement::Image(i) => &mut i.bounds,
             DocumentElement::Shape(s) => &mut s.bounds,
             DocumentElement::Group(g) => &mut g.bounds,
+            DocumentElement::Table(t) => &mut t.bounds,
+            DocumentElement::QrCode(q) => &mut q.bounds,
         }
     }
 }
@@ -199,6 +821,9 @@ pub struct FrameElement {
     pub visible: bool,
     #[serde(default = "default_locked")]
     pub locked: bool,
+    /// Opacity from 0.0 (transparent) to 1.0 (opaque)
+    #[serde(default = "default_opacity")]
+    pub opacity: f32,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -213,6 +838,33 @@ pub struct TextElement {
     pub visible: bool,
     #[serde(default = "default_locked")]
     pub locked: bool,
+    /// Opacity from 0.0 (transparent) to 1.0 (opaque)
+    #[serde(default = "default_opacity")]
+    pub opacity: f32,
+    /// Clockwise rotation in degrees about the bounds center
+    #[serde(default)]
+    pub rotation: f32,
+    /// Formatted runs rendered instead of the plain `content`
+    #[serde(default)]
+    pub rich_text: Option<crate::typography::RichText>,
+    /// Space between the bounds and the text
+    #[serde(default = "default_text_padding")]
+    pub padding: super::super::layout::Insets,
+    /// Curve the text follows instead of flowing in lines
+    #[serde(default)]
+    pub text_path: Option<super::TextPath>,
+}
+
+impl TextElement {
+    /// Rich text to render, if it still spells out `content`
+    ///
+    /// Runs that no longer match the plain content (e.g. after the text was
+    /// edited) are ignored so the box never shows replaced text.
+    pub fn rich_text_for_render(&self) -> Option<&crate::typography::RichText> {
+        self.rich_text
+            .as_ref()
+            .filter(|rich_text| rich_text.get_plain_text() == self.content)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -224,6 +876,63 @@ pub struct ImageElement {
     pub visible: bool,
     #[serde(default = "default_locked")]
     pub locked: bool,
+    /// Opacity from 0.0 (transparent) to 1.0 (opaque)
+    #[serde(default = "default_opacity")]
+    pub opacity: f32,
+    /// Clockwise rotation in degrees about the bounds center
+    #[serde(default)]
+    pub rotation: f32,
+    /// How the image is scaled into its bounds; files from before fit modes
+    /// existed load as `Contain`, which is how images were drawn then
+    #[serde(default)]
+    pub fit: ImageFit,
+}
+
+/// How an image is scaled into its element bounds
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImageFit {
+    /// Fill the bounds exactly, ignoring the aspect ratio
+    Stretch,
+    /// Fit inside the bounds keeping the aspect ratio (letterboxed)
+    #[default]
+    Contain,
+    /// Fill the bounds keeping the aspect ratio, cropping the overflow
+    Cover,
+}
+
+impl ImageFit {
+    /// Rectangle the whole image is drawn into for the given bounds
+    ///
+    /// The result is centered on the bounds. For `Cover` it extends past
+    /// them, so renderers clip to the bounds. Degenerate image sizes fall
+    /// back to the bounds themselves.
+    pub fn image_rect(
+        &self,
+        image_width: f32,
+        image_height: f32,
+        bounds: &super::super::layout::Rect,
+    ) -> super::super::layout::Rect {
+        use super::super::layout::{Point, Rect, Size};
+
+        if *self == ImageFit::Stretch || image_width <= 0.0 || image_height <= 0.0 {
+            return *bounds;
+        }
+        let scale_x = bounds.size.width / image_width;
+        let scale_y = bounds.size.height / image_height;
+        let scale = match self {
+            ImageFit::Contain => scale_x.min(scale_y),
+            _ => scale_x.max(scale_y),
+        };
+        let width = image_width * scale;
+        let height = image_height * scale;
+        Rect::new(
+            Point::new(
+                bounds.origin.x + (bounds.size.width - width) / 2.0,
+                bounds.origin.y + (bounds.size.height - height) / 2.0,
+            ),
+            Size::new(width, height),
+        )
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -233,11 +942,95 @@ pub struct ShapeElement {
     pub bounds: super::super::layout::Rect,
     pub stroke: Option<crate::typography::Color>,
     pub stroke_width: f32, // ← 新規追加（デフォルト: 2.0）
-    pub fill: Option<crate::typography::Color>,
+    /// Solid color or gradient (solid fills keep the plain color format)
+    pub fill: Option<Fill>,
     #[serde(default = "default_visible")]
     pub visible: bool,
     #[serde(default = "default_locked")]
     pub locked: bool,
+    /// Opacity from 0.0 (transparent) to 1.0 (opaque)
+    #[serde(default = "default_opacity")]
+    pub opacity: f32,
+    /// Clockwise rotation in degrees about the bounds center
+    #[serde(default)]
+    pub rotation: f32,
+    /// Polygon vertices relative to the bounds (0.0–1.0 on each axis)
+    ///
+    /// Only used by `ShapeKind::Polygon`; an empty list draws a regular pentagon.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vertices: Vec<super::super::layout::Point>,
+    /// Outline dash style
+    #[serde(default)]
+    pub line_style: LineStyle,
+    /// Corner radius for rectangles (ignored by other kinds)
+    #[serde(default)]
+    pub corner_radius: f32,
+}
+
+impl ShapeElement {
+    /// Bounds and relative vertices for a polygon through the given page points
+    ///
+    /// Returns `None` for fewer than three points. Storing vertices relative to
+    /// the bounds lets move and resize work on the bounds alone.
+    pub fn polygon_geometry(
+        points: &[super::super::layout::Point],
+    ) -> Option<(super::super::layout::Rect, Vec<super::super::layout::Point>)> {
+        use super::super::layout::{Point, Rect, Size};
+
+        if points.len() < 3 {
+            return None;
+        }
+        let min_x = points.iter().map(|p| p.x).fold(f32::MAX, f32::min);
+        let min_y = points.iter().map(|p| p.y).fold(f32::MAX, f32::min);
+        let max_x = points.iter().map(|p| p.x).fold(f32::MIN, f32::max);
+        let max_y = points.iter().map(|p| p.y).fold(f32::MIN, f32::max);
+        let width = max_x - min_x;
+        let height = max_y - min_y;
+
+        let relative = |value: f32, min: f32, extent: f32| {
+            if extent > 0.0 {
+                (value - min) / extent
+            } else {
+                0.0
+            }
+        };
+        let vertices = points
+            .iter()
+            .map(|p| Point::new(relative(p.x, min_x, width), relative(p.y, min_y, height)))
+            .collect();
+
+        Some((
+            Rect::new(Point::new(min_x, min_y), Size::new(width, height)),
+            vertices,
+        ))
+    }
+
+    /// Corner radius to draw with, clamped to half the smaller side
+    ///
+    /// Always 0.0 for shapes other than rectangles and for negative or
+    /// non-finite radii.
+    pub fn effective_corner_radius(&self) -> f32 {
+        if self.kind != ShapeKind::Rectangle || !self.corner_radius.is_finite() {
+            return 0.0;
+        }
+        let max_radius = self.bounds.size.width.min(self.bounds.size.height) / 2.0;
+        self.corner_radius.clamp(0.0, max_radius.max(0.0))
+    }
+
+    /// Page-space positions of the polygon vertices (empty for regular shapes)
+    pub fn vertex_positions(&self) -> Vec<super::super::layout::Point> {
+        let origin = self.bounds.origin;
+        let size = self.bounds.size;
+        self.vertices
+            .iter()
+            .map(|v| {
+                super::super::layout::Point::new(
+                    origin.x + v.x * size.width,
+                    origin.y + v.y * size.height,
+                )
+            })
+            .collect()
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -250,6 +1043,238 @@ pub struct GroupElement {
     pub visible: bool,
     #[serde(default = "default_locked")]
     pub locked: bool,
+    /// Opacity from 0.0 (transparent) to 1.0 (opaque)
+    #[serde(default = "default_opacity")]
+    pub opacity: f32,
+}
+
+/// Grid of text cells, e.g. an answer grid
+///
+/// Rows and columns divide the bounds evenly. `cells` is row-major and holds
+/// `rows * columns` entries, so resizing the table only touches the bounds.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TableElement {
+    pub id: uuid::Uuid,
+    pub bounds: super::super::layout::Rect,
+    pub rows: usize,
+    pub columns: usize,
+    /// Cell contents in row-major order
+    pub cells: Vec<crate::typography::RichText>,
+    /// Style for text typed into a cell
+    #[serde(default)]
+    pub cell_style: crate::typography::TextStyle,
+    /// Outline and grid lines
+    #[serde(default)]
+    pub border: TableBorder,
+    #[serde(default = "default_visible")]
+    pub visible: bool,
+    #[serde(default = "default_locked")]
+    pub locked: bool,
+    /// Opacity from 0.0 (transparent) to 1.0 (opaque)
+    #[serde(default = "default_opacity")]
+    pub opacity: f32,
+}
+
+impl TableElement {
+    /// Empty table with at least one row and one column
+    pub fn new(bounds: super::super::layout::Rect, rows: usize, columns: usize) -> Self {
+        let rows = rows.max(1);
+        let columns = columns.max(1);
+        let cell_style = crate::typography::TextStyle::default();
+        Self {
+            id: uuid::Uuid::new_v4(),
+            bounds,
+            rows,
+            columns,
+            cells: vec![
+                crate::typography::RichText::from_plain("", cell_style.clone());
+                rows * columns
+            ],
+            cell_style,
+            border: TableBorder::default(),
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+        }
+    }
+
+    /// Content of the cell at `row`, `column`
+    pub fn cell(&self, row: usize, column: usize) -> Option<&crate::typography::RichText> {
+        self.cell_index(row, column)
+            .and_then(|index| self.cells.get(index))
+    }
+
+    /// Mutable content of the cell at `row`, `column`
+    pub fn cell_mut(
+        &mut self,
+        row: usize,
+        column: usize,
+    ) -> Option<&mut crate::typography::RichText> {
+        self.cell_index(row, column)
+            .and_then(|index| self.cells.get_mut(index))
+    }
+
+    /// Replace a cell's content with plain text in the table's cell style
+    ///
+    /// Returns false when the cell does not exist.
+    pub fn set_cell_text(&mut self, row: usize, column: usize, text: &str) -> bool {
+        let content = crate::typography::RichText::from_plain(text, self.cell_style.clone());
+        match self.cell_mut(row, column) {
+            Some(cell) => {
+                *cell = content;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Page-space rectangle of the cell at `row`, `column`
+    pub fn cell_bounds(&self, row: usize, column: usize) -> super::super::layout::Rect {
+        use super::super::layout::{Point, Rect, Size};
+
+        let width = self.bounds.size.width / self.columns.max(1) as f32;
+        let height = self.bounds.size.height / self.rows.max(1) as f32;
+        Rect::new(
+            Point::new(
+                self.bounds.origin.x + column as f32 * width,
+                self.bounds.origin.y + row as f32 * height,
+            ),
+            Size::new(width, height),
+        )
+    }
+
+    /// Row and column of the cell containing a page point
+    pub fn cell_at(&self, point: super::super::layout::Point) -> Option<(usize, usize)> {
+        let bounds = &self.bounds;
+        if bounds.size.width <= 0.0
+            || bounds.size.height <= 0.0
+            || point.x < bounds.origin.x
+            || point.y < bounds.origin.y
+            || point.x > bounds.origin.x + bounds.size.width
+            || point.y > bounds.origin.y + bounds.size.height
+        {
+            return None;
+        }
+        let column =
+            ((point.x - bounds.origin.x) / bounds.size.width * self.columns as f32) as usize;
+        let row = ((point.y - bounds.origin.y) / bounds.size.height * self.rows as f32) as usize;
+        Some((
+            row.min(self.rows.saturating_sub(1)),
+            column.min(self.columns.saturating_sub(1)),
+        ))
+    }
+
+    fn cell_index(&self, row: usize, column: usize) -> Option<usize> {
+        (row < self.rows && column < self.columns).then_some(row * self.columns + column)
+    }
+}
+
+/// Line drawn around and between the cells of a table
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TableBorder {
+    pub color: crate::typography::Color,
+    pub width: f32,
+    #[serde(default)]
+    pub line_style: LineStyle,
+}
+
+impl Default for TableBorder {
+    fn default() -> Self {
+        Self {
+            color: crate::typography::Color::from_rgb(0.0, 0.0, 0.0),
+            width: 1.0,
+            line_style: LineStyle::Solid,
+        }
+    }
+}
+
+/// Paint used to fill a shape
+///
+/// Serialized untagged, so a solid fill is written as a plain color and
+/// documents from before gradients load unchanged. `LinearGradient` must
+/// stay first: every `Color` field has a default, so a gradient would
+/// otherwise also parse as a (black) solid color.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Fill {
+    /// Gradient across the bounds; `angle` is in degrees clockwise, with
+    /// 0.0 running from left (`start`) to right (`end`)
+    LinearGradient {
+        start: crate::typography::Color,
+        end: crate::typography::Color,
+        #[serde(default)]
+        angle: f32,
+    },
+    Solid(crate::typography::Color),
+}
+
+impl Fill {
+    /// The solid color, or the start color of a gradient
+    pub fn primary_color(&self) -> crate::typography::Color {
+        match self {
+            Fill::Solid(color) => *color,
+            Fill::LinearGradient { start, .. } => *start,
+        }
+    }
+
+    /// Start and end points of the gradient line for the given bounds
+    ///
+    /// The line runs through the bounds center at `angle` and is long enough
+    /// for the start and end colors to reach the far corners. Returns `None`
+    /// for solid fills.
+    pub fn gradient_line(
+        &self,
+        bounds: &super::super::layout::Rect,
+    ) -> Option<(super::super::layout::Point, super::super::layout::Point)> {
+        use super::super::layout::Point;
+
+        let Fill::LinearGradient { angle, .. } = self else {
+            return None;
+        };
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let half_length = (bounds.size.width * cos.abs() + bounds.size.height * sin.abs()) / 2.0;
+        let center_x = bounds.origin.x + bounds.size.width / 2.0;
+        let center_y = bounds.origin.y + bounds.size.height / 2.0;
+        Some((
+            Point::new(center_x - cos * half_length, center_y - sin * half_length),
+            Point::new(center_x + cos * half_length, center_y + sin * half_length),
+        ))
+    }
+}
+
+impl From<crate::typography::Color> for Fill {
+    fn from(color: crate::typography::Color) -> Self {
+        Fill::Solid(color)
+    }
+}
+
+/// Dash style for shape outlines
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineStyle {
+    #[default]
+    Solid,
+    Dashed,
+    Dotted,
+}
+
+impl LineStyle {
+    /// Dash pattern (on/off lengths) for the given stroke width
+    ///
+    /// Lengths scale with the width so thick outlines keep the same look;
+    /// widths below 1.0 use 1.0 so hairlines still show a visible pattern.
+    /// `Solid` returns an empty pattern.
+    pub fn dash_pattern(&self, stroke_width: f32) -> Vec<f64> {
+        let width = if stroke_width.is_finite() {
+            (stroke_width as f64).max(1.0)
+        } else {
+            1.0
+        };
+        match self {
+            LineStyle::Solid => Vec::new(),
+            LineStyle::Dashed => vec![width * 4.0, width * 2.0],
+            LineStyle::Dotted => vec![width, width * 1.5],
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -0,0 +1,801 @@
+//! QR code elements and a self-contained QR code encoder
+//!
+//! Payloads are encoded in byte mode (UTF-8) at the smallest version that
+//! fits the chosen error-correction level. The encoder follows ISO/IEC 18004
+//! and produces a plain module matrix, so renderers can draw the modules as
+//! vector rectangles at any size.
+
+use crate::layout::Rect;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Modules of light margin the specification requires around the symbol
+pub const QR_QUIET_ZONE: usize = 4;
+
+/// QR code, e.g. a student or exam ID on a scannable answer sheet
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QrCodeElement {
+    pub id: uuid::Uuid,
+    pub bounds: Rect,
+    /// Text encoded in the symbol
+    pub payload: String,
+    #[serde(default)]
+    pub error_correction: QrErrorCorrection,
+    #[serde(default = "default_visible")]
+    pub visible: bool,
+    #[serde(default)]
+    pub locked: bool,
+    #[serde(default = "default_opacity")]
+    pub opacity: f32,
+}
+
+fn default_visible() -> bool {
+    true
+}
+
+fn default_opacity() -> f32 {
+    1.0
+}
+
+impl QrCodeElement {
+    pub fn new(bounds: Rect, payload: impl Into<String>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4(),
+            bounds,
+            payload: payload.into(),
+            error_correction: QrErrorCorrection::default(),
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+        }
+    }
+
+    /// Encode the payload into a module matrix
+    pub fn encode(&self) -> Result<QrMatrix, QrEncodeError> {
+        QrMatrix::encode(&self.payload, self.error_correction)
+    }
+}
+
+/// How much of the symbol can be damaged and still scan
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QrErrorCorrection {
+    /// About 7 % recovery
+    Low,
+    /// About 15 % recovery
+    #[default]
+    Medium,
+    /// About 25 % recovery
+    Quartile,
+    /// About 30 % recovery
+    High,
+}
+
+impl QrErrorCorrection {
+    pub const ALL: [QrErrorCorrection; 4] = [
+        QrErrorCorrection::Low,
+        QrErrorCorrection::Medium,
+        QrErrorCorrection::Quartile,
+        QrErrorCorrection::High,
+    ];
+
+    /// Single-letter name used by the specification (L, M, Q, H)
+    pub fn letter(self) -> char {
+        match self {
+            QrErrorCorrection::Low => 'L',
+            QrErrorCorrection::Medium => 'M',
+            QrErrorCorrection::Quartile => 'Q',
+            QrErrorCorrection::High => 'H',
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Two-bit level indicator stored in the format information
+    fn format_bits(self) -> u32 {
+        match self {
+            QrErrorCorrection::Low => 0b01,
+            QrErrorCorrection::Medium => 0b00,
+            QrErrorCorrection::Quartile => 0b11,
+            QrErrorCorrection::High => 0b10,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum QrEncodeError {
+    #[error("QR payload is {length} bytes; at most {max} fit at level {level}")]
+    PayloadTooLong {
+        length: usize,
+        max: usize,
+        level: char,
+    },
+}
+
+const MIN_VERSION: usize = 1;
+const MAX_VERSION: usize = 40;
+
+// Error-correction codewords per block, indexed by [level][version]
+const ECC_CODEWORDS_PER_BLOCK: [[u8; 41]; 4] = [
+    [
+        0, 7, 10, 15, 20, 26, 18, 20, 24, 30, 18, 20, 24, 26, 30, 22, 24, 28, 30, 28, 28, 28, 28,
+        30, 30, 26, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+    ],
+    [
+        0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26, 26, 28,
+        28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28,
+    ],
+    [
+        0, 13, 22, 18, 26, 18, 24, 18, 22, 20, 24, 28, 26, 24, 20, 30, 24, 28, 28, 26, 30, 28, 30,
+        30, 30, 30, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+    ],
+    [
+        0, 17, 28, 22, 16, 22, 28, 26, 26, 24, 28, 24, 28, 22, 24, 24, 30, 28, 28, 26, 28, 30, 24,
+        30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+    ],
+];
+
+// Error-correction blocks, indexed by [level][version]
+const ERROR_CORRECTION_BLOCKS: [[u8; 41]; 4] = [
+    [
+        0, 1, 1, 1, 1, 1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 6, 6, 6, 6, 7, 8, 8, 9, 9, 10, 12, 12, 12, 13,
+        14, 15, 16, 17, 18, 19, 19, 20, 21, 22, 24, 25,
+    ],
+    [
+        0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16, 17, 17, 18, 20, 21,
+        23, 25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45, 47, 49,
+    ],
+    [
+        0, 1, 1, 2, 2, 4, 4, 6, 6, 8, 8, 8, 10, 12, 16, 12, 17, 16, 18, 21, 20, 23, 23, 25, 27, 29,
+        34, 34, 35, 38, 40, 43, 45, 48, 51, 53, 56, 59, 62, 65, 68,
+    ],
+    [
+        0, 1, 1, 2, 4, 4, 4, 5, 6, 8, 8, 11, 11, 16, 16, 18, 16, 19, 21, 25, 25, 25, 34, 30, 32,
+        35, 37, 40, 42, 45, 48, 51, 54, 57, 60, 63, 66, 70, 74, 77, 81,
+    ],
+];
+
+/// Square grid of dark (`true`) and light modules, without the quiet zone
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QrMatrix {
+    version: usize,
+    size: usize,
+    modules: Vec<bool>,
+    function: Vec<bool>,
+}
+
+impl QrMatrix {
+    /// Encode `payload` at the smallest version that fits `level`
+    pub fn encode(payload: &str, level: QrErrorCorrection) -> Result<Self, QrEncodeError> {
+        let data = payload.as_bytes();
+        let version = (MIN_VERSION..=MAX_VERSION)
+            .find(|&version| data.len() <= byte_capacity(version, level))
+            .ok_or(QrEncodeError::PayloadTooLong {
+                length: data.len(),
+                max: byte_capacity(MAX_VERSION, level),
+                level: level.letter(),
+            })?;
+
+        let codewords =
+            add_ecc_and_interleave(&data_codewords(data, version, level), version, level);
+
+        let mut matrix = QrMatrix::blank(version);
+        matrix.draw_function_patterns(level);
+        matrix.draw_codewords(&codewords);
+
+        let mask = (0..8)
+            .min_by_key(|&mask| {
+                matrix.apply_mask(mask);
+                matrix.draw_format_bits(level, mask);
+                let penalty = matrix.penalty_score();
+                matrix.apply_mask(mask);
+                penalty
+            })
+            .unwrap_or(0);
+        matrix.apply_mask(mask);
+        matrix.draw_format_bits(level, mask);
+        Ok(matrix)
+    }
+
+    /// Symbol version (1–40)
+    pub fn version(&self) -> usize {
+        self.version
+    }
+
+    /// Modules per side, without the quiet zone
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Whether the module at column `x`, row `y` is dark
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        x < self.size && y < self.size && self.modules[y * self.size + x]
+    }
+
+    fn blank(version: usize) -> Self {
+        let size = version * 4 + 17;
+        Self {
+            version,
+            size,
+            modules: vec![false; size * size],
+            function: vec![false; size * size],
+        }
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        let index = y * self.size + x;
+        self.modules[index] = dark;
+        self.function[index] = true;
+    }
+
+    fn draw_function_patterns(&mut self, level: QrErrorCorrection) {
+        let size = self.size;
+        for i in 0..size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+
+        self.draw_finder_pattern(3, 3);
+        self.draw_finder_pattern(size - 4, 3);
+        self.draw_finder_pattern(3, size - 4);
+
+        let positions = alignment_pattern_positions(self.version);
+        let last = positions.len().saturating_sub(1);
+        for (i, &x) in positions.iter().enumerate() {
+            for (j, &y) in positions.iter().enumerate() {
+                // The corners near the finder patterns have no alignment pattern
+                let near_finder = (i == 0 && (j == 0 || j == last)) || (i == last && j == 0);
+                if !near_finder {
+                    self.draw_alignment_pattern(x, y);
+                }
+            }
+        }
+
+        // Reserve the format area; the real bits are drawn once the mask is known
+        self.draw_format_bits(level, 0);
+        self.draw_version_bits();
+    }
+
+    fn draw_finder_pattern(&mut self, center_x: usize, center_y: usize) {
+        for dy in -4i32..=4 {
+            for dx in -4i32..=4 {
+                let x = center_x as i32 + dx;
+                let y = center_y as i32 + dy;
+                if (0..self.size as i32).contains(&x) && (0..self.size as i32).contains(&y) {
+                    let distance = dx.abs().max(dy.abs());
+                    self.set_function(x as usize, y as usize, distance != 2 && distance != 4);
+                }
+            }
+        }
+    }
+
+    fn draw_alignment_pattern(&mut self, center_x: usize, center_y: usize) {
+        for dy in -2i32..=2 {
+            for dx in -2i32..=2 {
+                let x = (center_x as i32 + dx) as usize;
+                let y = (center_y as i32 + dy) as usize;
+                self.set_function(x, y, dx.abs().max(dy.abs()) != 1);
+            }
+        }
+    }
+
+    fn draw_format_bits(&mut self, level: QrErrorCorrection, mask: u8) {
+        let bits = format_information(level, mask);
+        let bit = |i: usize| (bits >> i) & 1 != 0;
+        let size = self.size;
+
+        // Copy around the top-left finder pattern
+        for i in 0..6 {
+            self.set_function(8, i, bit(i));
+        }
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(i));
+        }
+
+        // Copy split between the other two finder patterns
+        for i in 0..8 {
+            self.set_function(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i, bit(i));
+        }
+        // Always-dark module
+        self.set_function(8, size - 8, true);
+    }
+
+    fn draw_version_bits(&mut self) {
+        if self.version < 7 {
+            return;
+        }
+        let bits = version_information(self.version);
+        for i in 0..18 {
+            let dark = (bits >> i) & 1 != 0;
+            let a = self.size - 11 + i % 3;
+            let b = i / 3;
+            self.set_function(a, b, dark);
+            self.set_function(b, a, dark);
+        }
+    }
+
+    /// Place the codeword bits in the two-column zigzag, skipping function modules
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let size = self.size as i32;
+        let total_bits = codewords.len() * 8;
+        let mut bit_index = 0;
+        let mut right = size - 1;
+        while right >= 1 {
+            // The vertical timing pattern is skipped as a whole column
+            if right == 6 {
+                right = 5;
+            }
+            let upward = (right + 1) & 2 == 0;
+            for vertical in 0..size {
+                let y = if upward {
+                    size - 1 - vertical
+                } else {
+                    vertical
+                } as usize;
+                for column in 0..2 {
+                    let x = (right - column) as usize;
+                    let index = y * self.size + x;
+                    if !self.function[index] && bit_index < total_bits {
+                        self.modules[index] =
+                            (codewords[bit_index / 8] >> (7 - bit_index % 8)) & 1 != 0;
+                        bit_index += 1;
+                    }
+                }
+            }
+            right -= 2;
+        }
+    }
+
+    /// XOR the data modules with mask pattern `mask`; applying it twice undoes it
+    fn apply_mask(&mut self, mask: u8) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let index = y * self.size + x;
+                if !self.function[index] && mask_bit(mask, x, y) {
+                    self.modules[index] = !self.modules[index];
+                }
+            }
+        }
+    }
+
+    /// Penalty used to choose the mask (lower scans more reliably)
+    fn penalty_score(&self) -> u32 {
+        let size = self.size;
+        let rows = (0..size).map(|y| (0..size).map(|x| self.is_dark(x, y)).collect::<Vec<_>>());
+        let columns = (0..size).map(|x| (0..size).map(|y| self.is_dark(x, y)).collect::<Vec<_>>());
+        let mut penalty: u32 = rows.chain(columns).map(|line| line_penalty(&line)).sum();
+
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let color = self.is_dark(x, y);
+                if color == self.is_dark(x + 1, y)
+                    && color == self.is_dark(x, y + 1)
+                    && color == self.is_dark(x + 1, y + 1)
+                {
+                    penalty += 3;
+                }
+            }
+        }
+
+        let total = (size * size) as i64;
+        let dark = self.modules.iter().filter(|&&dark| dark).count() as i64;
+        let deviation = ((dark * 20 - total * 10).abs() + total - 1) / total - 1;
+        penalty + deviation as u32 * 10
+    }
+}
+
+/// Runs of five or more equal modules and finder-like patterns in one line
+fn line_penalty(line: &[bool]) -> u32 {
+    let mut penalty = 0;
+    let mut run = 1;
+    for i in 1..=line.len() {
+        if i < line.len() && line[i] == line[i - 1] {
+            run += 1;
+        } else {
+            if run >= 5 {
+                penalty += run as u32 - 2;
+            }
+            run = 1;
+        }
+    }
+
+    const FINDER_LIKE: [bool; 11] = [
+        true, false, true, true, true, false, true, false, false, false, false,
+    ];
+    for window in line.windows(FINDER_LIKE.len()) {
+        if window == FINDER_LIKE || window.iter().rev().eq(FINDER_LIKE.iter()) {
+            penalty += 40;
+        }
+    }
+    penalty
+}
+
+fn mask_bit(mask: u8, x: usize, y: usize) -> bool {
+    match mask {
+        0 => (x + y).is_multiple_of(2),
+        1 => y.is_multiple_of(2),
+        2 => x.is_multiple_of(3),
+        3 => (x + y).is_multiple_of(3),
+        4 => (x / 3 + y / 2).is_multiple_of(2),
+        5 => x * y % 2 + x * y % 3 == 0,
+        6 => (x * y % 2 + x * y % 3).is_multiple_of(2),
+        _ => ((x + y) % 2 + x * y % 3).is_multiple_of(2),
+    }
+}
+
+/// 15-bit format information: level and mask protected by a BCH code
+fn format_information(level: QrErrorCorrection, mask: u8) -> u32 {
+    let data = level.format_bits() << 3 | mask as u32;
+    let mut remainder = data;
+    for _ in 0..10 {
+        remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+    }
+    (data << 10 | remainder) ^ 0x5412
+}
+
+/// 18-bit version information for versions 7 and up
+fn version_information(version: usize) -> u32 {
+    let version = version as u32;
+    let mut remainder = version;
+    for _ in 0..12 {
+        remainder = (remainder << 1) ^ ((remainder >> 11) * 0x1F25);
+    }
+    version << 12 | remainder
+}
+
+fn alignment_pattern_positions(version: usize) -> Vec<usize> {
+    if version == 1 {
+        return Vec::new();
+    }
+    let count = version / 7 + 2;
+    let step = if version == 32 {
+        26
+    } else {
+        (version * 4 + count * 2 + 1) / (count * 2 - 2) * 2
+    };
+    let size = version * 4 + 17;
+    let mut positions: Vec<usize> = (0..count - 1).map(|i| size - 7 - i * step).collect();
+    positions.push(6);
+    positions.reverse();
+    positions
+}
+
+/// Modules available for codewords after the function patterns
+fn raw_data_modules(version: usize) -> usize {
+    let mut modules = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let alignment = version / 7 + 2;
+        modules -= (25 * alignment - 10) * alignment - 55;
+        if version >= 7 {
+            modules -= 36;
+        }
+    }
+    modules
+}
+
+fn data_codeword_count(version: usize, level: QrErrorCorrection) -> usize {
+    let ecc = ECC_CODEWORDS_PER_BLOCK[level.index()][version] as usize;
+    let blocks = ERROR_CORRECTION_BLOCKS[level.index()][version] as usize;
+    raw_data_modules(version) / 8 - ecc * blocks
+}
+
+fn char_count_bits(version: usize) -> usize {
+    if version <= 9 {
+        8
+    } else {
+        16
+    }
+}
+
+/// Largest byte-mode payload the version holds at `level`
+fn byte_capacity(version: usize, level: QrErrorCorrection) -> usize {
+    (data_codeword_count(version, level) * 8 - 4 - char_count_bits(version)) / 8
+}
+
+/// Mode indicator, length, payload, terminator and padding
+fn data_codewords(data: &[u8], version: usize, level: QrErrorCorrection) -> Vec<u8> {
+    let capacity_bits = data_codeword_count(version, level) * 8;
+    let mut bits = BitBuffer::default();
+    bits.push(0b0100, 4);
+    bits.push(data.len() as u32, char_count_bits(version));
+    for &byte in data {
+        bits.push(byte as u32, 8);
+    }
+    bits.push(0, (capacity_bits - bits.len).min(4));
+    bits.push(0, (8 - bits.len % 8) % 8);
+
+    let mut codewords = bits.bytes;
+    for pad in [0xEC, 0x11].into_iter().cycle() {
+        if codewords.len() * 8 >= capacity_bits {
+            break;
+        }
+        codewords.push(pad);
+    }
+    codewords
+}
+
+#[derive(Default)]
+struct BitBuffer {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitBuffer {
+    fn push(&mut self, value: u32, count: usize) {
+        for i in (0..count).rev() {
+            if self.len.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            if (value >> i) & 1 != 0 {
+                *self.bytes.last_mut().expect("byte was just pushed") |= 0x80 >> (self.len % 8);
+            }
+            self.len += 1;
+        }
+    }
+}
+
+/// Split the data into blocks, append Reed–Solomon codewords and interleave
+fn add_ecc_and_interleave(data: &[u8], version: usize, level: QrErrorCorrection) -> Vec<u8> {
+    let blocks = ERROR_CORRECTION_BLOCKS[level.index()][version] as usize;
+    let ecc_len = ECC_CODEWORDS_PER_BLOCK[level.index()][version] as usize;
+    let raw_codewords = raw_data_modules(version) / 8;
+    let short_blocks = blocks - raw_codewords % blocks;
+    let short_block_len = raw_codewords / blocks;
+    let divisor = reed_solomon_divisor(ecc_len);
+
+    let mut offset = 0;
+    let all_blocks: Vec<Vec<u8>> = (0..blocks)
+        .map(|i| {
+            let data_len = short_block_len - ecc_len + usize::from(i >= short_blocks);
+            let mut block = data[offset..offset + data_len].to_vec();
+            offset += data_len;
+            let ecc = reed_solomon_remainder(&block, &divisor);
+            // Short blocks get a placeholder so every block has the same length
+            if i < short_blocks {
+                block.push(0);
+            }
+            block.extend(ecc);
+            block
+        })
+        .collect();
+
+    let mut result = Vec::with_capacity(raw_codewords);
+    for i in 0..all_blocks[0].len() {
+        for (j, block) in all_blocks.iter().enumerate() {
+            if i != short_block_len - ecc_len || j >= short_blocks {
+                result.push(block[i]);
+            }
+        }
+    }
+    result
+}
+
+fn reed_solomon_divisor(degree: usize) -> Vec<u8> {
+    let mut result = vec![0u8; degree];
+    result[degree - 1] = 1;
+    let mut root = 1u8;
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = gf_multiply(result[j], root);
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+        root = gf_multiply(root, 0x02);
+    }
+    result
+}
+
+fn reed_solomon_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut result = vec![0u8; divisor.len()];
+    for &byte in data {
+        let factor = byte ^ result.remove(0);
+        result.push(0);
+        for (value, &coefficient) in result.iter_mut().zip(divisor) {
+            *value ^= gf_multiply(coefficient, factor);
+        }
+    }
+    result
+}
+
+/// Multiplication in GF(2^8) modulo x^8 + x^4 + x^3 + x^2 + 1
+fn gf_multiply(x: u8, y: u8) -> u8 {
+    let mut z = 0u8;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x1D);
+        z ^= ((y >> i) & 1) * x;
+    }
+    z
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reed_solomon_matches_reference_codewords() {
+        // "HELLO WORLD" at 1-M (alphanumeric data codewords from the specification example)
+        let data = [
+            32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17,
+        ];
+        let ecc = reed_solomon_remainder(&data, &reed_solomon_divisor(10));
+        assert_eq!(ecc, [196, 35, 39, 119, 235, 215, 231, 226, 93, 23]);
+    }
+
+    #[test]
+    fn capacities_match_the_specification() {
+        assert_eq!(byte_capacity(1, QrErrorCorrection::Low), 17);
+        assert_eq!(byte_capacity(1, QrErrorCorrection::High), 7);
+        assert_eq!(byte_capacity(10, QrErrorCorrection::Medium), 213);
+        assert_eq!(byte_capacity(40, QrErrorCorrection::Low), 2953);
+        assert_eq!(byte_capacity(40, QrErrorCorrection::High), 1273);
+        for level in QrErrorCorrection::ALL {
+            let blocks = ERROR_CORRECTION_BLOCKS[level.index()];
+            for (version, &count) in blocks.iter().enumerate().skip(MIN_VERSION) {
+                assert!(raw_data_modules(version) / 8 >= count as usize);
+            }
+        }
+    }
+
+    #[test]
+    fn format_and_version_bits_match_the_specification() {
+        assert_eq!(
+            format_information(QrErrorCorrection::Medium, 0),
+            0b101010000010010
+        );
+        assert_eq!(
+            format_information(QrErrorCorrection::Low, 4),
+            0b110011000101111
+        );
+        assert_eq!(
+            format_information(QrErrorCorrection::High, 7),
+            0b000100000111011
+        );
+        assert_eq!(version_information(7), 0x07C94);
+        assert_eq!(alignment_pattern_positions(7), [6, 22, 38]);
+        assert_eq!(alignment_pattern_positions(32), [6, 34, 60, 86, 112, 138]);
+    }
+
+    #[test]
+    fn encode_picks_the_smallest_version() {
+        let matrix = QrMatrix::encode("EXAM-2026-0042", QrErrorCorrection::Medium).unwrap();
+        assert_eq!(matrix.version(), 1);
+        assert_eq!(matrix.size(), 21);
+        // Finder pattern corners and the always-dark module
+        assert!(matrix.is_dark(0, 0) && matrix.is_dark(20, 0) && matrix.is_dark(0, 20));
+        assert!(!matrix.is_dark(7, 7));
+        assert!(matrix.is_dark(8, 13));
+
+        let long = "A".repeat(300);
+        let matrix = QrMatrix::encode(&long, QrErrorCorrection::High).unwrap();
+        assert_eq!(matrix.version(), 18);
+        assert_eq!(matrix.size(), 89);
+    }
+
+    #[test]
+    fn format_information_copies_agree() {
+        let matrix = QrMatrix::encode("student:1234", QrErrorCorrection::Quartile).unwrap();
+        let size = matrix.size();
+        let first: Vec<bool> = (0..6)
+            .map(|i| matrix.is_dark(8, i))
+            .chain([
+                matrix.is_dark(8, 7),
+                matrix.is_dark(8, 8),
+                matrix.is_dark(7, 8),
+            ])
+            .chain((9..15).map(|i| matrix.is_dark(14 - i, 8)))
+            .collect();
+        let second: Vec<bool> = (0..8)
+            .map(|i| matrix.is_dark(size - 1 - i, 8))
+            .chain((8..15).map(|i| matrix.is_dark(8, size - 15 + i)))
+            .collect();
+        assert_eq!(first, second);
+
+        let bits = first
+            .iter()
+            .enumerate()
+            .fold(0u32, |bits, (i, &dark)| bits | (dark as u32) << i);
+        assert!((0..8).any(|mask| format_information(QrErrorCorrection::Quartile, mask) == bits));
+    }
+
+    /// Rows of `matrix`, `#` for dark modules and `.` for light ones
+    fn symbol_rows(matrix: &QrMatrix) -> Vec<String> {
+        (0..matrix.size())
+            .map(|y| {
+                (0..matrix.size())
+                    .map(|x| if matrix.is_dark(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Reference symbols, checked with an independent decoder (format bits,
+    // unmasking, zigzag read-out, Reed–Solomon syndromes and byte-mode payload)
+    const EXAM_1_M: [&str; 21] = [
+        "#######....##.#######",
+        "#.....#.#.....#.....#",
+        "#.###.#...#...#.###.#",
+        "#.###.#...#.#.#.###.#",
+        "#.###.#.#.#.#.#.###.#",
+        "#.....#....#..#.....#",
+        "#######.#.#.#.#######",
+        ".........####........",
+        "#.#.#.#..#..#...#..#.",
+        "#....#...#..##..###..",
+        "#####.###...#...#.###",
+        ".###.#.....#.#.#.....",
+        "...#..#...####..#.###",
+        "........###.#...##.#.",
+        "#######.....#...#####",
+        "#.....#..###.#.##..##",
+        "#.###.#.##..#...#.##.",
+        "#.###.#...#.#.#.#.##.",
+        "#.###.#.##..###.###.#",
+        "#.....#...##.#...#.#.",
+        "#######.#.#.##.#...##",
+    ];
+
+    const URL_3_Q: [&str; 29] = [
+        "#######.#.###.....#.#.#######",
+        "#.....#.####...###.#..#.....#",
+        "#.###.#.#..#.##.#..##.#.###.#",
+        "#.###.#.#..#..##..##..#.###.#",
+        "#.###.#.##.####.####..#.###.#",
+        "#.....#..##.##.#..##..#.....#",
+        "#######.#.#.#.#.#.#.#.#######",
+        "........##.###..#..#.........",
+        ".##.#.##.#..#...#..#..#.#####",
+        "#..#.#....##....#....##...#.#",
+        "..#.#.#####.#...#...#...##.##",
+        "#...#...#.##....#########..#.",
+        "##..####......####....##.....",
+        "#......#.##......##.####...##",
+        "....#.##.###.##.#.#...##..###",
+        "###.##.###....#..####..#....#",
+        ".#.#.###.#.##..##.#..###.#..#",
+        ".####..##.##...####..##...###",
+        "#..#..#..#..#..##.#..#.######",
+        ".##....##.......##..#.#..#..#",
+        "#...######..######.######....",
+        "........##...#.....##...#..##",
+        "#######.#.##.##..#.##.#.#.###",
+        "#.....#...#.#..##...#...#..##",
+        "#.###.#.#...###.....######.#.",
+        "#.###.#...##..##....##..#.#..",
+        "#.###.#.#.#..##....#...###..#",
+        "#.....#.##.#.###.#.##.##...#.",
+        "#######..#####..##..###.#..##",
+    ];
+
+    #[test]
+    fn encode_matches_reference_symbols() {
+        let matrix = QrMatrix::encode("EXAM-2026-0042", QrErrorCorrection::Medium).unwrap();
+        assert_eq!(symbol_rows(&matrix), EXAM_1_M);
+
+        // Two interleaved blocks and an alignment pattern
+        let matrix =
+            QrMatrix::encode("https://example.com/exam/42", QrErrorCorrection::Quartile).unwrap();
+        assert_eq!(matrix.version(), 3);
+        assert_eq!(symbol_rows(&matrix), URL_3_Q);
+    }
+
+    #[test]
+    fn payload_too_long_is_rejected() {
+        let payload = "x".repeat(1274);
+        assert_eq!(
+            QrMatrix::encode(&payload, QrErrorCorrection::High),
+            Err(QrEncodeError::PayloadTooLong {
+                length: 1274,
+                max: 1273,
+                level: 'H'
+            })
+        );
+    }
+}
@@ -0,0 +1,421 @@
+//! Find and replace across the text elements of a document
+//!
+//! Matching works on the plain `content` of each text element. Replacing
+//! also rewrites the rich text runs, so formatting around the replaced text
+//! is kept and the replacement takes the style of the run it starts in.
+
+use super::{Document, DocumentElement, Page, TextElement};
+use crate::typography::{RichText, TextRun, TextStyle};
+use std::ops::Range;
+
+/// How a query is matched against text
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Match upper and lower case letters exactly
+    pub case_sensitive: bool,
+    /// Only match whole words (no letter, digit or `_` on either side)
+    pub whole_word: bool,
+}
+
+/// Text element containing at least one match
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextMatch {
+    /// 0-based page index
+    pub page_index: usize,
+    pub element_id: uuid::Uuid,
+    /// Number of matches in the element
+    pub count: usize,
+}
+
+/// Byte ranges of the non-overlapping matches of `query` in `text`
+pub fn find_matches(text: &str, query: &str, options: SearchOptions) -> Vec<Range<usize>> {
+    let mut matches = Vec::new();
+    if query.is_empty() {
+        return matches;
+    }
+
+    let mut search_from = 0;
+    while search_from < text.len() {
+        let Some(range) = match_at_or_after(text, search_from, query, options) else {
+            break;
+        };
+        search_from = range.end;
+        matches.push(range);
+    }
+    matches
+}
+
+/// Replace every match of `query` in `text`, returning the new text and the match count
+pub fn replace_matches(
+    text: &str,
+    query: &str,
+    replacement: &str,
+    options: SearchOptions,
+) -> (String, usize) {
+    let matches = find_matches(text, query, options);
+    (replace_ranges(text, &matches, replacement), matches.len())
+}
+
+/// First match starting at byte `from` or later
+fn match_at_or_after(
+    text: &str,
+    from: usize,
+    query: &str,
+    options: SearchOptions,
+) -> Option<Range<usize>> {
+    text[from..].char_indices().find_map(|(offset, _)| {
+        let start = from + offset;
+        let end = start + prefix_match_len(&text[start..], query, options.case_sensitive)?;
+        (!options.whole_word || is_word_boundary(text, start, end)).then_some(start..end)
+    })
+}
+
+/// Byte length of the prefix of `text` that matches `query`, if it matches
+fn prefix_match_len(text: &str, query: &str, case_sensitive: bool) -> Option<usize> {
+    if case_sensitive {
+        return text.starts_with(query).then_some(query.len());
+    }
+    let mut text_chars = text.char_indices();
+    for query_char in query.chars() {
+        let (_, text_char) = text_chars.next()?;
+        if !text_char.to_lowercase().eq(query_char.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(text_chars.next().map_or(text.len(), |(index, _)| index))
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn is_word_boundary(text: &str, start: usize, end: usize) -> bool {
+    let before = text[..start].chars().next_back();
+    let after = text[end..].chars().next();
+    !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+}
+
+impl TextElement {
+    /// Number of matches of `query` in the content
+    pub fn count_matches(&self, query: &str, options: SearchOptions) -> usize {
+        find_matches(&self.content, query, options).len()
+    }
+
+    /// Replace every match in the content (and its rich text); returns the match count
+    pub fn replace_matches(
+        &mut self,
+        query: &str,
+        replacement: &str,
+        options: SearchOptions,
+    ) -> usize {
+        let matches = find_matches(&self.content, query, options);
+        if matches.is_empty() {
+            return 0;
+        }
+        if let Some(rich_text) = self.rich_text_for_render() {
+            self.rich_text = Some(replace_in_runs(rich_text, &matches, replacement));
+        }
+        self.content = replace_ranges(&self.content, &matches, replacement);
+        matches.len()
+    }
+
+    /// Replace the content bytes in `range` (and the matching rich text);
+    /// returns `false` if `range` is not on character boundaries of the content
+    pub fn replace_range(&mut self, range: Range<usize>, replacement: &str) -> bool {
+        if self.content.get(range.clone()).is_none() {
+            return false;
+        }
+        let ranges = [range];
+        if let Some(rich_text) = self.rich_text_for_render() {
+            self.rich_text = Some(replace_in_runs(rich_text, &ranges, replacement));
+        }
+        self.content = replace_ranges(&self.content, &ranges, replacement);
+        true
+    }
+}
+
+fn replace_ranges(text: &str, ranges: &[Range<usize>], replacement: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    for range in ranges {
+        result.push_str(&text[last..range.start]);
+        result.push_str(replacement);
+        last = range.end;
+    }
+    result.push_str(&text[last..]);
+    result
+}
+
+/// Rebuild the runs with `ranges` (byte offsets into the plain text) replaced
+fn replace_in_runs(rich_text: &RichText, ranges: &[Range<usize>], replacement: &str) -> RichText {
+    let plain = rich_text.get_plain_text();
+    let mut runs: Vec<TextRun> = Vec::new();
+    let mut push = |text: &str, style: &TextStyle| {
+        if !text.is_empty() {
+            runs.push(TextRun {
+                text: text.to_string(),
+                style: style.clone(),
+            });
+        }
+    };
+
+    let mut pending = ranges.iter().peekable();
+    // End of the last replaced match, which may reach into later runs
+    let mut skip_until = 0;
+    for (run_range, style) in rich_text.run_byte_ranges() {
+        let mut position = run_range.start.max(skip_until);
+        while position < run_range.end {
+            match pending.peek() {
+                // A match starting here: the replacement takes this run's style
+                Some(range) if range.start == position => {
+                    push(replacement, style);
+                    skip_until = range.end;
+                    position = range.end.min(run_range.end);
+                    pending.next();
+                }
+                // Text before the next match in this run
+                Some(range) if range.start < run_range.end => {
+                    push(&plain[position..range.start], style);
+                    position = range.start;
+                }
+                _ => {
+                    push(&plain[position..run_range.end], style);
+                    position = run_range.end;
+                }
+            }
+        }
+    }
+    RichText { runs }
+}
+
+impl Page {
+    /// Text element `id` on this page, including those inside frames and groups
+    pub fn text_element_mut(&mut self, id: uuid::Uuid) -> Option<&mut TextElement> {
+        find_text_mut(&mut self.elements, id)
+    }
+}
+
+fn find_text_mut(elements: &mut [DocumentElement], id: uuid::Uuid) -> Option<&mut TextElement> {
+    elements.iter_mut().find_map(|element| match element {
+        DocumentElement::Text(text) if text.id == id => Some(text),
+        DocumentElement::Frame(frame) => find_text_mut(&mut frame.children, id),
+        DocumentElement::Group(group) => find_text_mut(&mut group.children, id),
+        _ => None,
+    })
+}
+
+impl Document {
+    /// Text elements matching `query`, in page and stacking order
+    pub fn find_text(&self, query: &str, options: SearchOptions) -> Vec<TextMatch> {
+        let mut found = Vec::new();
+        for (page_index, page) in self.pages.iter().enumerate() {
+            collect_matches(&page.elements, page_index, query, options, &mut found);
+        }
+        found
+    }
+}
+
+fn collect_matches(
+    elements: &[DocumentElement],
+    page_index: usize,
+    query: &str,
+    options: SearchOptions,
+    found: &mut Vec<TextMatch>,
+) {
+    for element in elements {
+        match element {
+            DocumentElement::Text(text) => {
+                let count = text.count_matches(query, options);
+                if count > 0 {
+                    found.push(TextMatch {
+                        page_index,
+                        element_id: text.id,
+                        count,
+                    });
+                }
+            }
+            DocumentElement::Frame(frame) => {
+                collect_matches(&frame.children, page_index, query, options, found)
+            }
+            DocumentElement::Group(group) => {
+                collect_matches(&group.children, page_index, query, options, found)
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::{GroupElement, DEFAULT_TEXT_PADDING};
+    use crate::layout::{Insets, Point, Rect, Size};
+    use crate::typography::FontWeight;
+
+    fn text_element(content: &str) -> TextElement {
+        TextElement {
+            id: uuid::Uuid::new_v4(),
+            content: content.to_string(),
+            style: TextStyle::default(),
+            bounds: Rect::new(Point::new(0.0, 0.0), Size::new(200.0, 40.0)),
+            auto_resize_height: false,
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+            rotation: 0.0,
+            rich_text: None,
+            padding: Insets::uniform(DEFAULT_TEXT_PADDING),
+            text_path: None,
+        }
+    }
+
+    const WHOLE_WORD: SearchOptions = SearchOptions {
+        case_sensitive: false,
+        whole_word: true,
+    };
+
+    #[test]
+    fn find_matches_respects_case_and_word_options() {
+        let text = "Score: score, scores and SCORE";
+        assert_eq!(
+            find_matches(text, "score", SearchOptions::default()).len(),
+            4
+        );
+        let exact = SearchOptions {
+            case_sensitive: true,
+            whole_word: false,
+        };
+        assert_eq!(find_matches(text, "score", exact), vec![7..12, 14..19]);
+        assert_eq!(
+            find_matches(text, "score", WHOLE_WORD),
+            vec![0..5, 7..12, 25..30]
+        );
+        assert!(find_matches(text, "", SearchOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn replace_matches_handles_multibyte_text() {
+        let (replaced, count) =
+            replace_matches("問1 問2 問3", "問", "設問", SearchOptions::default());
+        assert_eq!(replaced, "設問1 設問2 設問3");
+        assert_eq!(count, 3);
+
+        // Overlapping candidates are replaced left to right
+        let (replaced, count) = replace_matches("aaa", "aa", "b", SearchOptions::default());
+        assert_eq!((replaced.as_str(), count), ("ba", 1));
+    }
+
+    #[test]
+    fn replacing_keeps_rich_text_runs() {
+        let bold = TextStyle {
+            weight: FontWeight::Bold,
+            ..TextStyle::default()
+        };
+        let mut text = TextElement {
+            rich_text: Some(RichText {
+                runs: vec![
+                    TextRun {
+                        text: "Name:".to_string(),
+                        style: bold.clone(),
+                    },
+                    TextRun {
+                        text: " Taro Na".to_string(),
+                        style: TextStyle::default(),
+                    },
+                    TextRun {
+                        text: "me".to_string(),
+                        style: bold.clone(),
+                    },
+                ],
+            }),
+            ..text_element("Name: Taro Name")
+        };
+
+        assert_eq!(
+            text.replace_matches("name", "氏名", SearchOptions::default()),
+            2
+        );
+        assert_eq!(text.content, "氏名: Taro 氏名");
+        let runs = &text.rich_text.as_ref().unwrap().runs;
+        let texts: Vec<&str> = runs.iter().map(|run| run.text.as_str()).collect();
+        assert_eq!(texts, ["氏名", ":", " Taro ", "氏名"]);
+        assert_eq!(runs[0].style.weight, FontWeight::Bold);
+        assert_eq!(runs[3].style.weight, FontWeight::Regular);
+        assert_eq!(
+            text.rich_text_for_render().map(RichText::get_plain_text),
+            Some(text.content.clone())
+        );
+    }
+
+    #[test]
+    fn replace_range_keeps_rich_text_runs() {
+        let bold = TextStyle {
+            weight: FontWeight::Bold,
+            ..TextStyle::default()
+        };
+        let mut text = TextElement {
+            rich_text: Some(RichText {
+                runs: vec![
+                    TextRun {
+                        text: "Teh ".to_string(),
+                        style: TextStyle::default(),
+                    },
+                    TextRun {
+                        text: "answr".to_string(),
+                        style: bold.clone(),
+                    },
+                ],
+            }),
+            ..text_element("Teh answr")
+        };
+
+        assert!(text.replace_range(4..9, "answer"));
+        assert_eq!(text.content, "Teh answer");
+        let runs = &text.rich_text.as_ref().unwrap().runs;
+        assert_eq!(runs[1].text, "answer");
+        assert_eq!(runs[1].style.weight, FontWeight::Bold);
+
+        // Ranges off the content's character boundaries are refused
+        assert!(!text.replace_range(4..20, "x"));
+        assert_eq!(text.content, "Teh answer");
+    }
+
+    #[test]
+    fn find_text_searches_every_page_and_nested_elements() {
+        let mut doc = Document::empty("Test");
+        let first = text_element("Answer: ____");
+        let nested = text_element("answer the questions");
+        let nested_id = nested.id;
+        doc.pages[0].add_element(DocumentElement::Text(first.clone()));
+        doc.pages.push(Page::empty());
+        doc.pages[1].add_element(DocumentElement::Group(GroupElement {
+            id: uuid::Uuid::new_v4(),
+            name: "Group".to_string(),
+            bounds: Rect::new(Point::new(0.0, 0.0), Size::new(200.0, 40.0)),
+            children: vec![DocumentElement::Text(nested)],
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+        }));
+
+        let found = doc.find_text("answer", WHOLE_WORD);
+        assert_eq!(
+            found,
+            vec![
+                TextMatch {
+                    page_index: 0,
+                    element_id: first.id,
+                    count: 1
+                },
+                TextMatch {
+                    page_index: 1,
+                    element_id: nested_id,
+                    count: 1
+                },
+            ]
+        );
+        let text = doc.pages[1].text_element_mut(nested_id).unwrap();
+        assert_eq!(text.replace_matches("answer", "Answer", WHOLE_WORD), 1);
+        assert_eq!(text.content, "Answer the questions");
+    }
+}
@@ -0,0 +1,304 @@
+//! Curves that text can follow instead of flowing in lines
+//!
+//! A [`TextPath`] is resolved against the element bounds and flattened into a
+//! polyline; renderers then ask the [`FlattenedPath`] for the position and
+//! direction at each glyph's distance along it.
+
+use crate::layout::{Point, Rect, Size};
+use crate::typography::TextAlignment;
+use serde::{Deserialize, Serialize};
+
+/// Line segments used to approximate a full circle
+const CIRCLE_SEGMENTS: usize = 180;
+
+/// Line segments used to approximate each Bézier segment
+const BEZIER_SEGMENTS: usize = 32;
+
+/// Curve that a text element is drawn along
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum TextPath {
+    /// Circle centred in the element bounds, run clockwise
+    ///
+    /// `start_angle` is in degrees clockwise from 12 o'clock. Glyphs stand on
+    /// the circle with their tops pointing outwards.
+    Circle { radius: f32, start_angle: f32 },
+    /// Cubic Bézier curve with points relative to the bounds origin
+    Bezier {
+        start: Point,
+        segments: Vec<BezierSegment>,
+    },
+}
+
+/// One cubic segment of a [`TextPath::Bezier`], continuing from the previous end
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BezierSegment {
+    pub control1: Point,
+    pub control2: Point,
+    pub end: Point,
+}
+
+impl TextPath {
+    /// A circle touching the shorter side of `bounds`, starting at the top
+    pub fn circle_in(bounds: &Rect) -> Self {
+        Self::Circle {
+            radius: bounds.size.width.min(bounds.size.height) / 2.0,
+            start_angle: 0.0,
+        }
+    }
+
+    /// Largest circle radius that stays inside `bounds`
+    pub fn max_circle_radius(bounds: &Rect) -> f32 {
+        (bounds.size.width.min(bounds.size.height) / 2.0).max(1.0)
+    }
+
+    /// Area the text can cover: `bounds` grown to the path plus `font_size`
+    ///
+    /// Glyphs stand on the path, so they may reach one font size beyond it
+    /// on either side.
+    pub fn text_extent(&self, bounds: &Rect, font_size: f32) -> Rect {
+        let flattened = self.flatten(bounds);
+        let (mut min_x, mut min_y) = (bounds.origin.x, bounds.origin.y);
+        let mut max_x = bounds.origin.x + bounds.size.width;
+        let mut max_y = bounds.origin.y + bounds.size.height;
+        for point in flattened.points() {
+            min_x = min_x.min(point.x - font_size);
+            min_y = min_y.min(point.y - font_size);
+            max_x = max_x.max(point.x + font_size);
+            max_y = max_y.max(point.y + font_size);
+        }
+        Rect::new(
+            Point::new(min_x, min_y),
+            Size::new(max_x - min_x, max_y - min_y),
+        )
+    }
+
+    /// Whether the path returns to its start
+    pub fn is_closed(&self) -> bool {
+        matches!(self, Self::Circle { .. })
+    }
+
+    /// Center of a circular path in document coordinates
+    pub fn circle_center(bounds: &Rect) -> Point {
+        Point::new(
+            bounds.origin.x + bounds.size.width / 2.0,
+            bounds.origin.y + bounds.size.height / 2.0,
+        )
+    }
+
+    /// Point at `angle` degrees clockwise from 12 o'clock on a circle
+    pub fn circle_point(center: Point, radius: f32, angle: f32) -> Point {
+        let radians = angle.to_radians();
+        Point::new(
+            center.x + radius * radians.sin(),
+            center.y - radius * radians.cos(),
+        )
+    }
+
+    /// The path in document coordinates as a polyline
+    pub fn flatten(&self, bounds: &Rect) -> FlattenedPath {
+        let mut points = Vec::new();
+        match self {
+            Self::Circle {
+                radius,
+                start_angle,
+            } => {
+                let center = Self::circle_center(bounds);
+                for step in 0..=CIRCLE_SEGMENTS {
+                    let angle = start_angle + 360.0 * step as f32 / CIRCLE_SEGMENTS as f32;
+                    points.push(Self::circle_point(center, *radius, angle));
+                }
+            }
+            Self::Bezier { start, segments } => {
+                let offset = |p: Point| Point::new(bounds.origin.x + p.x, bounds.origin.y + p.y);
+                let mut from = offset(*start);
+                points.push(from);
+                for segment in segments {
+                    let (c1, c2, to) = (
+                        offset(segment.control1),
+                        offset(segment.control2),
+                        offset(segment.end),
+                    );
+                    for step in 1..=BEZIER_SEGMENTS {
+                        let t = step as f32 / BEZIER_SEGMENTS as f32;
+                        points.push(cubic_point(from, c1, c2, to, t));
+                    }
+                    from = to;
+                }
+            }
+        }
+        FlattenedPath::new(points, self.is_closed())
+    }
+
+    /// Distance along the path at which text `text_width` long starts
+    ///
+    /// On open paths the alignment places the text at the start, middle or
+    /// end. A circle has no ends, so the text starts at, is centred on or
+    /// ends at the start angle instead; the result may then be negative.
+    pub fn start_distance(&self, length: f32, text_width: f32, alignment: TextAlignment) -> f32 {
+        match (self.is_closed(), alignment) {
+            (_, TextAlignment::Start | TextAlignment::Justified) => 0.0,
+            (true, TextAlignment::Center) => -text_width / 2.0,
+            (true, TextAlignment::End) => -text_width,
+            (false, TextAlignment::Center) => (length - text_width) / 2.0,
+            (false, TextAlignment::End) => length - text_width,
+        }
+    }
+}
+
+fn cubic_point(p0: Point, p1: Point, p2: Point, p3: Point, t: f32) -> Point {
+    let u = 1.0 - t;
+    let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+    Point::new(
+        a * p0.x + b * p1.x + c * p2.x + d * p3.x,
+        a * p0.y + b * p1.y + c * p2.y + d * p3.y,
+    )
+}
+
+/// A [`TextPath`] resolved to document coordinates
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlattenedPath {
+    points: Vec<Point>,
+    /// Distance from the first point to each point
+    distances: Vec<f32>,
+    closed: bool,
+}
+
+impl FlattenedPath {
+    fn new(points: Vec<Point>, closed: bool) -> Self {
+        let mut distances = Vec::with_capacity(points.len());
+        let mut total = 0.0;
+        for (index, point) in points.iter().enumerate() {
+            if index > 0 {
+                let previous = points[index - 1];
+                total += (point.x - previous.x).hypot(point.y - previous.y);
+            }
+            distances.push(total);
+        }
+        Self {
+            points,
+            distances,
+            closed,
+        }
+    }
+
+    /// Total length of the path
+    pub fn length(&self) -> f32 {
+        self.distances.last().copied().unwrap_or(0.0)
+    }
+
+    /// Points of the polyline in order
+    pub fn points(&self) -> &[Point] {
+        &self.points
+    }
+
+    /// Position and direction `distance` along the path
+    ///
+    /// The direction is the clockwise angle in radians from the +x axis.
+    /// Closed paths wrap around; open paths return `None` past either end.
+    pub fn point_at(&self, distance: f32) -> Option<(Point, f32)> {
+        let length = self.length();
+        if self.points.len() < 2 || length <= 0.0 {
+            return None;
+        }
+        let distance = if self.closed {
+            distance.rem_euclid(length)
+        } else if (0.0..=length).contains(&distance) {
+            distance
+        } else {
+            return None;
+        };
+
+        // First segment whose end reaches `distance`, skipping zero-length ones
+        let index = self
+            .distances
+            .iter()
+            .skip(1)
+            .position(|&end| end >= distance)
+            .unwrap_or(self.points.len() - 2);
+        let (from, to) = (self.points[index], self.points[index + 1]);
+        let segment = self.distances[index + 1] - self.distances[index];
+        let t = if segment > 0.0 {
+            (distance - self.distances[index]) / segment
+        } else {
+            0.0
+        };
+        let point = Point::new(from.x + (to.x - from.x) * t, from.y + (to.y - from.y) * t);
+        Some((point, (to.y - from.y).atan2(to.x - from.x)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::Size;
+
+    fn bounds() -> Rect {
+        Rect::new(Point::new(100.0, 100.0), Size::new(200.0, 200.0))
+    }
+
+    #[test]
+    fn circle_starts_at_the_start_angle_and_runs_clockwise() {
+        let path = TextPath::Circle {
+            radius: 50.0,
+            start_angle: 90.0,
+        };
+        let flattened = path.flatten(&bounds());
+        assert!((flattened.length() - 2.0 * std::f32::consts::PI * 50.0).abs() < 0.1);
+
+        // 3 o'clock, heading straight down
+        let (point, angle) = flattened.point_at(0.0).unwrap();
+        assert!((point.x - 250.0).abs() < 0.01 && (point.y - 200.0).abs() < 0.01);
+        assert!((angle - std::f32::consts::FRAC_PI_2).abs() < 0.02);
+
+        // Closed paths wrap around
+        let (wrapped, _) = flattened.point_at(-flattened.length() / 4.0).unwrap();
+        assert!((wrapped.x - 200.0).abs() < 0.1 && (wrapped.y - 150.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn bezier_is_relative_to_the_bounds_and_open() {
+        let path = TextPath::Bezier {
+            start: Point::new(0.0, 0.0),
+            segments: vec![BezierSegment {
+                control1: Point::new(30.0, 0.0),
+                control2: Point::new(70.0, 0.0),
+                end: Point::new(100.0, 0.0),
+            }],
+        };
+        let flattened = path.flatten(&bounds());
+        assert!((flattened.length() - 100.0).abs() < 0.01);
+
+        let (point, angle) = flattened.point_at(50.0).unwrap();
+        assert!((point.x - 150.0).abs() < 0.5 && point.y == 100.0);
+        assert_eq!(angle, 0.0);
+        assert!(flattened.point_at(100.5).is_none());
+        assert!(flattened.point_at(-0.5).is_none());
+
+        assert_eq!(
+            path.start_distance(100.0, 40.0, TextAlignment::Center),
+            30.0
+        );
+        assert_eq!(
+            TextPath::circle_in(&bounds()).start_distance(100.0, 40.0, TextAlignment::Center),
+            -20.0
+        );
+    }
+
+    #[test]
+    fn text_extent_covers_glyphs_standing_on_the_path() {
+        assert_eq!(TextPath::max_circle_radius(&bounds()), 100.0);
+
+        // A circle inside the bounds only grows by the glyph height
+        let extent = TextPath::circle_in(&bounds()).text_extent(&bounds(), 12.0);
+        assert!((extent.origin.x - 88.0).abs() < 0.01 && (extent.origin.y - 88.0).abs() < 0.01);
+        assert!((extent.size.width - 224.0).abs() < 0.01);
+
+        let large = TextPath::Circle {
+            radius: 300.0,
+            start_angle: 0.0,
+        };
+        let extent = large.text_extent(&bounds(), 10.0);
+        assert!((extent.origin.x - -110.0).abs() < 0.01);
+        assert!((extent.size.height - 620.0).abs() < 0.01);
+    }
+}
@@ -0,0 +1,211 @@
+use super::{Document, DocumentElement, QrEncodeError, ShapeKind};
+use crate::layout::Size;
+use crate::workspace::assets::AssetCatalog;
+use std::collections::HashSet;
+use thiserror::Error;
+
+/// Tolerance in points before an element counts as past the page edge
+const PAGE_EDGE_TOLERANCE: f32 = 0.5;
+
+/// A problem found in a document that deserialized successfully
+#[derive(Clone, Debug, PartialEq, Error)]
+pub enum ValidationIssue {
+    #[error("element id {id} is used more than once (page {page})")]
+    DuplicateElementId { page: usize, id: uuid::Uuid },
+    #[error("element {id} on page {page} has an invalid size ({width} × {height})")]
+    InvalidSize {
+        page: usize,
+        id: uuid::Uuid,
+        width: f32,
+        height: f32,
+    },
+    #[error("table {id} on page {page} has {cells} cells for {rows} × {columns}")]
+    InvalidTableCells {
+        page: usize,
+        id: uuid::Uuid,
+        rows: usize,
+        columns: usize,
+        cells: usize,
+    },
+    #[error("QR code {id} on page {page} cannot be encoded: {reason}")]
+    InvalidQrCode {
+        page: usize,
+        id: uuid::Uuid,
+        reason: QrEncodeError,
+    },
+    #[error("element {id} on page {page} has zero size")]
+    ZeroSize { page: usize, id: uuid::Uuid },
+    #[error("element {id} on page {page} extends past the page edge")]
+    OutsidePage { page: usize, id: uuid::Uuid },
+    #[error("image {id} on page {page} refers to a missing asset")]
+    MissingAsset { page: usize, id: uuid::Uuid },
+}
+
+impl ValidationIssue {
+    /// 1-based number of the page holding the element
+    pub fn page(&self) -> usize {
+        match self {
+            Self::DuplicateElementId { page, .. }
+            | Self::InvalidSize { page, .. }
+            | Self::InvalidTableCells { page, .. }
+            | Self::InvalidQrCode { page, .. }
+            | Self::ZeroSize { page, .. }
+            | Self::OutsidePage { page, .. }
+            | Self::MissingAsset { page, .. } => *page,
+        }
+    }
+
+    /// Id of the offending element
+    pub fn element_id(&self) -> uuid::Uuid {
+        match self {
+            Self::DuplicateElementId { id, .. }
+            | Self::InvalidSize { id, .. }
+            | Self::InvalidTableCells { id, .. }
+            | Self::InvalidQrCode { id, .. }
+            | Self::ZeroSize { id, .. }
+            | Self::OutsidePage { id, .. }
+            | Self::MissingAsset { id, .. } => *id,
+        }
+    }
+
+    /// Whether the issue breaks an invariant the editor relies on
+    ///
+    /// The other issues are warnings: the document still renders and exports,
+    /// just probably not as intended.
+    pub fn is_error(&self) -> bool {
+        matches!(
+            self,
+            Self::DuplicateElementId { .. }
+                | Self::InvalidSize { .. }
+                | Self::InvalidTableCells { .. }
+                | Self::InvalidQrCode { .. }
+        )
+    }
+}
+
+impl Document {
+    /// Check invariants that serde cannot express, plus likely mistakes
+    ///
+    /// Errors: element ids must be unique across the whole document
+    /// (including frame and group children), sizes must be finite and
+    /// non-negative, tables must hold one cell per row and column and QR code
+    /// payloads must fit in a symbol.
+    /// Warnings: zero-size elements and top-level elements that reach past
+    /// the page edge (rotation is ignored).
+    /// Page numbers in the returned issues are 1-based.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let mut seen = HashSet::new();
+        for (index, page) in self.pages.iter().enumerate() {
+            let page_size = self.page_size_of(page);
+            for element in &page.elements {
+                if is_outside(element, page_size) {
+                    issues.push(ValidationIssue::OutsidePage {
+                        page: index + 1,
+                        id: element.id(),
+                    });
+                }
+            }
+            validate_elements(&page.elements, index + 1, &mut seen, &mut issues);
+        }
+        issues
+    }
+
+    /// Images whose asset is not in `catalog` or whose file no longer exists
+    pub fn validate_assets(&self, catalog: &AssetCatalog) -> Vec<ValidationIssue> {
+        fn collect(
+            elements: &[DocumentElement],
+            page: usize,
+            catalog: &AssetCatalog,
+            issues: &mut Vec<ValidationIssue>,
+        ) {
+            for element in elements {
+                match element {
+                    DocumentElement::Image(image) => {
+                        let missing = catalog
+                            .get(image.source)
+                            .is_none_or(|asset| !asset.path.exists());
+                        if missing {
+                            issues.push(ValidationIssue::MissingAsset { page, id: image.id });
+                        }
+                    }
+                    DocumentElement::Frame(f) => collect(&f.children, page, catalog, issues),
+                    DocumentElement::Group(g) => collect(&g.children, page, catalog, issues),
+                    _ => {}
+                }
+            }
+        }
+
+        let mut issues = Vec::new();
+        for (index, page) in self.pages.iter().enumerate() {
+            collect(&page.elements, index + 1, catalog, &mut issues);
+        }
+        issues
+    }
+}
+
+fn is_outside(element: &DocumentElement, page_size: Size) -> bool {
+    let bounds = element.bounds();
+    bounds.origin.x < -PAGE_EDGE_TOLERANCE
+        || bounds.origin.y < -PAGE_EDGE_TOLERANCE
+        || bounds.origin.x + bounds.size.width > page_size.width + PAGE_EDGE_TOLERANCE
+        || bounds.origin.y + bounds.size.height > page_size.height + PAGE_EDGE_TOLERANCE
+}
+
+/// Lines and arrows only need one non-zero dimension
+fn is_zero_size(element: &DocumentElement) -> bool {
+    let size = element.bounds().size;
+    match element {
+        DocumentElement::Shape(s) if matches!(s.kind, ShapeKind::Line | ShapeKind::Arrow) => {
+            size.width == 0.0 && size.height == 0.0
+        }
+        _ => size.width == 0.0 || size.height == 0.0,
+    }
+}
+
+fn validate_elements(
+    elements: &[DocumentElement],
+    page: usize,
+    seen: &mut HashSet<uuid::Uuid>,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    for element in elements {
+        let id = element.id();
+        if !seen.insert(id) {
+            issues.push(ValidationIssue::DuplicateElementId { page, id });
+        }
+
+        let size = element.bounds().size;
+        let valid = |value: f32| value.is_finite() && value >= 0.0;
+        if !valid(size.width) || !valid(size.height) {
+            issues.push(ValidationIssue::InvalidSize {
+                page,
+                id,
+                width: size.width,
+                height: size.height,
+            });
+        } else if is_zero_size(element) {
+            issues.push(ValidationIssue::ZeroSize { page, id });
+        }
+
+        match element {
+            DocumentElement::Frame(f) => validate_elements(&f.children, page, seen, issues),
+            DocumentElement::Group(g) => validate_elements(&g.children, page, seen, issues),
+            DocumentElement::Table(t) if t.rows == 0 || t.cells.len() != t.rows * t.columns => {
+                issues.push(ValidationIssue::InvalidTableCells {
+                    page,
+                    id,
+                    rows: t.rows,
+                    columns: t.columns,
+                    cells: t.cells.len(),
+                });
+            }
+            DocumentElement::QrCode(q) => {
+                if let Err(reason) = q.encode() {
+                    issues.push(ValidationIssue::InvalidQrCode { page, id, reason });
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use super::{CanvasLayout, Rect, Size};
+use super::{CanvasLayout, Point, Rect, Size};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -19,6 +19,39 @@ pub enum LayoutError {
     NotEnoughRoom,
 }
 
+/// Column settings for [`LayoutEngine::flow_items`]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct FlowOptions {
+    /// Number of columns each section is split into
+    pub columns: usize,
+    /// Spacing between columns and between stacked items
+    pub gap: f32,
+}
+
+impl Default for FlowOptions {
+    fn default() -> Self {
+        Self {
+            columns: 1,
+            gap: 8.0,
+        }
+    }
+}
+
+/// Positions produced by [`LayoutEngine::flow_items`]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct FlowResult {
+    /// One entry per input item; `None` when the item did not fit
+    pub frames: Vec<Option<Rect>>,
+    /// Indices of the items that could not be placed
+    pub overflow: Vec<usize>,
+}
+
+impl FlowResult {
+    pub fn has_overflow(&self) -> bool {
+        !self.overflow.is_empty()
+    }
+}
+
 pub struct LayoutEngine;
 
 impl LayoutEngine {
@@ -34,4 +67,156 @@ impl LayoutEngine {
         }
         Ok(LayoutResult { frames })
     }
+
+    /// Flow items top-to-bottom through the section columns, in order
+    ///
+    /// Sections are filled in the order they were added; without sections the
+    /// page area inside the margin is used. An item that does not fit in the
+    /// space left in a column moves to the next column (then the next
+    /// section). Items bigger than an empty column are reported as overflow,
+    /// as is everything left once all columns are full.
+    pub fn flow_items(canvas: &CanvasLayout, items: &[Size], options: FlowOptions) -> FlowResult {
+        let columns = options.columns.max(1);
+        let gap = options.gap.max(0.0);
+
+        let regions: Vec<Rect> = if canvas.sections.is_empty() {
+            vec![Rect::new(
+                Point::new(canvas.margin, canvas.margin),
+                Size::new(
+                    (canvas.page_size.width - canvas.margin * 2.0).max(0.0),
+                    (canvas.page_size.height - canvas.margin * 2.0).max(0.0),
+                ),
+            )]
+        } else {
+            canvas
+                .sections
+                .iter()
+                .map(|section| section.bounds)
+                .collect()
+        };
+
+        let column_slots: Vec<Rect> = regions
+            .iter()
+            .flat_map(|region| {
+                let width =
+                    ((region.size.width - gap * (columns - 1) as f32) / columns as f32).max(0.0);
+                (0..columns).map(move |column| {
+                    Rect::new(
+                        Point::new(
+                            region.origin.x + column as f32 * (width + gap),
+                            region.origin.y,
+                        ),
+                        Size::new(width, region.size.height),
+                    )
+                })
+            })
+            .collect();
+
+        let mut result = FlowResult::default();
+        let mut slot_index = 0;
+        let mut cursor_y = column_slots
+            .first()
+            .map(|slot| slot.origin.y)
+            .unwrap_or(0.0);
+
+        for (index, item) in items.iter().enumerate() {
+            let fits_any_column = column_slots
+                .iter()
+                .any(|slot| item.width <= slot.size.width && item.height <= slot.size.height);
+            if !fits_any_column {
+                result.frames.push(None);
+                result.overflow.push(index);
+                continue;
+            }
+
+            let mut placed = None;
+            while let Some(slot) = column_slots.get(slot_index) {
+                let bottom = slot.origin.y + slot.size.height;
+                if item.width <= slot.size.width && cursor_y + item.height <= bottom {
+                    placed = Some(Rect::new(Point::new(slot.origin.x, cursor_y), *item));
+                    cursor_y += item.height + gap;
+                    break;
+                }
+                slot_index += 1;
+                if let Some(next) = column_slots.get(slot_index) {
+                    cursor_y = next.origin.y;
+                }
+            }
+
+            if placed.is_none() {
+                result.overflow.push(index);
+            }
+            result.frames.push(placed);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::LayoutSection;
+
+    fn section(y: f32, height: f32) -> LayoutSection {
+        LayoutSection::new(
+            "section",
+            Rect::new(Point::new(0.0, y), Size::new(200.0, height)),
+        )
+    }
+
+    #[test]
+    fn test_flow_items_stacks_then_moves_to_next_column() {
+        let mut canvas = CanvasLayout::new(Size::new(200.0, 300.0));
+        canvas.add_section(section(0.0, 100.0));
+        let options = FlowOptions {
+            columns: 2,
+            gap: 10.0,
+        };
+        let items = [
+            Size::new(90.0, 40.0),
+            Size::new(90.0, 40.0),
+            Size::new(90.0, 40.0),
+        ];
+
+        let result = LayoutEngine::flow_items(&canvas, &items, options);
+
+        assert!(!result.has_overflow());
+        assert_eq!(result.frames[0].unwrap().origin, Point::new(0.0, 0.0));
+        assert_eq!(result.frames[1].unwrap().origin, Point::new(0.0, 50.0));
+        // Third item does not fit under the second one (100 + 40 > 100)
+        assert_eq!(result.frames[2].unwrap().origin, Point::new(105.0, 0.0));
+    }
+
+    #[test]
+    fn test_flow_items_respects_section_boundaries() {
+        let mut canvas = CanvasLayout::new(Size::new(200.0, 300.0));
+        canvas.add_section(section(0.0, 50.0));
+        canvas.add_section(section(120.0, 100.0));
+        let items = [Size::new(100.0, 40.0), Size::new(100.0, 40.0)];
+
+        let result = LayoutEngine::flow_items(&canvas, &items, FlowOptions::default());
+
+        assert_eq!(result.frames[0].unwrap().origin.y, 0.0);
+        assert_eq!(result.frames[1].unwrap().origin.y, 120.0);
+    }
+
+    #[test]
+    fn test_flow_items_reports_overflow() {
+        let mut canvas = CanvasLayout::new(Size::new(200.0, 300.0));
+        canvas.add_section(section(0.0, 100.0));
+        let items = [
+            Size::new(100.0, 150.0),
+            Size::new(100.0, 60.0),
+            Size::new(100.0, 60.0),
+        ];
+
+        let result = LayoutEngine::flow_items(&canvas, &items, FlowOptions::default());
+
+        // The first item is taller than the section, the last one runs out of room
+        assert_eq!(result.overflow, vec![0, 2]);
+        assert!(result.frames[0].is_none());
+        assert!(result.frames[1].is_some());
+        assert!(result.frames[2].is_none());
+    }
 }
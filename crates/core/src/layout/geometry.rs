@@ -41,4 +41,66 @@ impl Rect {
             && point.x <= self.origin.x + self.size.width
             && point.y <= self.origin.y + self.size.height
     }
+
+    /// The area left inside `insets`, never narrower or shorter than zero
+    pub fn inset(&self, insets: &Insets) -> Rect {
+        Rect {
+            origin: Point::new(self.origin.x + insets.left, self.origin.y + insets.top),
+            size: Size::new(
+                (self.size.width - insets.horizontal()).max(0.0),
+                (self.size.height - insets.vertical()).max(0.0),
+            ),
+        }
+    }
+}
+
+/// Space kept free inside the edges of a box
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Insets {
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32,
+}
+
+impl Insets {
+    pub const fn uniform(value: f32) -> Self {
+        Self {
+            top: value,
+            right: value,
+            bottom: value,
+            left: value,
+        }
+    }
+
+    /// Left plus right inset
+    pub fn horizontal(&self) -> f32 {
+        self.left + self.right
+    }
+
+    /// Top plus bottom inset
+    pub fn vertical(&self) -> f32 {
+        self.top + self.bottom
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inset_shrinks_and_clamps() {
+        let rect = Rect::new(Point::new(10.0, 20.0), Size::new(100.0, 8.0));
+        let insets = Insets {
+            top: 2.0,
+            right: 4.0,
+            bottom: 10.0,
+            left: 6.0,
+        };
+        assert_eq!(
+            rect.inset(&insets),
+            Rect::new(Point::new(16.0, 22.0), Size::new(90.0, 0.0))
+        );
+        assert_eq!(Insets::uniform(5.0).horizontal(), 10.0);
+    }
 }
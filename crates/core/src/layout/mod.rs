@@ -5,5 +5,5 @@ mod engine;
 mod geometry;
 
 pub use canvas::{CanvasLayout, LayoutSection};
-pub use engine::{LayoutEngine, LayoutRequest, LayoutResult};
-pub use geometry::{Point, Rect, Size};
+pub use engine::{FlowOptions, FlowResult, LayoutEngine, LayoutRequest, LayoutResult};
+pub use geometry::{Insets, Point, Rect, Size};
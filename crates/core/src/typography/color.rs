@@ -26,9 +26,77 @@ impl Color {
     }
 }
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+/// Project color palette shown in the swatches panel
+///
+/// The three named colors are always present; `swatches` holds the colors
+/// the user added on top of them.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
 pub struct Palette {
     pub primary: Color,
     pub secondary: Color,
     pub background: Color,
+    pub swatches: Vec<Color>,
+}
+
+impl Palette {
+    /// Named colors followed by the user swatches, in display order
+    pub fn colors(&self) -> Vec<Color> {
+        let mut colors = vec![self.primary, self.secondary, self.background];
+        colors.extend(self.swatches.iter().copied());
+        colors
+    }
+
+    /// Add a user swatch, returning `false` if the palette already has it
+    pub fn add_swatch(&mut self, color: Color) -> bool {
+        if self.colors().contains(&color) {
+            return false;
+        }
+        self.swatches.push(color);
+        true
+    }
+
+    /// Remove the user swatch at `index` (an index into `swatches`)
+    pub fn remove_swatch(&mut self, index: usize) -> Option<Color> {
+        (index < self.swatches.len()).then(|| self.swatches.remove(index))
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            primary: Color::from_rgb(0.1, 0.1, 0.1),
+            secondary: Color::from_rgb(0.2, 0.4, 0.8),
+            background: Color::from_rgb(1.0, 1.0, 1.0),
+            swatches: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palette_adds_unique_swatches_after_named_colors() {
+        let mut palette = Palette::default();
+        let red = Color::from_rgb(1.0, 0.0, 0.0);
+
+        assert!(palette.add_swatch(red));
+        assert!(!palette.add_swatch(red));
+        assert!(!palette.add_swatch(palette.background));
+        assert_eq!(palette.colors().len(), 4);
+        assert_eq!(palette.colors()[3], red);
+
+        assert_eq!(palette.remove_swatch(1), None);
+        assert_eq!(palette.remove_swatch(0), Some(red));
+        assert!(palette.swatches.is_empty());
+    }
+
+    #[test]
+    fn palette_fills_missing_fields_from_defaults() {
+        let palette: Palette = serde_json::from_str(r#"{"swatches":[{"r":1.0}]}"#).unwrap();
+        assert_eq!(palette.primary, Palette::default().primary);
+        assert_eq!(palette.swatches, vec![Color::from_rgb(1.0, 0.0, 0.0)]);
+    }
 }
@@ -28,6 +28,21 @@ impl FontCatalog {
     pub fn find(&self, family: &str) -> Option<&FontDescriptor> {
         self.fonts.get(family)
     }
+
+    /// All registered fonts sorted by family name, ignoring case
+    pub fn descriptors(&self) -> Vec<&FontDescriptor> {
+        let mut descriptors: Vec<&FontDescriptor> = self.fonts.values().collect();
+        descriptors.sort_by_cached_key(|descriptor| descriptor.family.to_lowercase());
+        descriptors
+    }
+
+    pub fn len(&self) -> usize {
+        self.fonts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fonts.is_empty()
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -35,3 +50,44 @@ pub struct FontDescriptor {
     pub family: String,
     pub weights: Vec<super::text_style::FontWeight>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::typography::FontWeight;
+
+    fn descriptor(family: &str) -> FontDescriptor {
+        FontDescriptor {
+            family: family.to_string(),
+            weights: vec![FontWeight::Regular],
+        }
+    }
+
+    #[test]
+    fn descriptors_are_sorted_case_insensitively() {
+        let mut catalog = FontCatalog::new();
+        for family in [
+            "noto Serif JP",
+            "Arial",
+            "Noto Sans CJK JP",
+            "ヒラギノ角ゴシック",
+        ] {
+            catalog.register(descriptor(family));
+        }
+
+        let families: Vec<&str> = catalog
+            .descriptors()
+            .into_iter()
+            .map(|descriptor| descriptor.family.as_str())
+            .collect();
+        assert_eq!(
+            families,
+            [
+                "Arial",
+                "Noto Sans CJK JP",
+                "noto Serif JP",
+                "ヒラギノ角ゴシック"
+            ]
+        );
+    }
+}
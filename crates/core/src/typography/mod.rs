@@ -3,9 +3,11 @@
 mod color;
 mod font_catalog;
 pub mod rich_text;
+pub mod spelling;
 mod text_style;
 
 pub use color::{Color, Palette};
 pub use font_catalog::{FontCatalog, FontDescriptor};
 pub use rich_text::{RichText, TextRun};
-pub use text_style::{FontWeight, TextAlignment, TextStyle};
+pub use spelling::{SpellChecker, SpellingError};
+pub use text_style::{FontWeight, TextAlignment, TextStyle, VerticalAlignment};
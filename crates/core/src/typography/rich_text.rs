@@ -38,6 +38,22 @@ impl RichText {
             .join("")
     }
 
+    /// Byte range of each run within the plain text, paired with its style
+    ///
+    /// Offsets are UTF-8 byte offsets (what Pango attributes expect), so a
+    /// Japanese character advances the offset by its full encoded length.
+    pub fn run_byte_ranges(&self) -> Vec<(std::ops::Range<usize>, &TextStyle)> {
+        let mut offset = 0;
+        self.runs
+            .iter()
+            .map(|run| {
+                let start = offset;
+                offset += run.text.len();
+                (start..offset, &run.style)
+            })
+            .collect()
+    }
+
     /// Get the total text length in characters
     pub fn text_length(&self) -> usize {
         self.runs.iter().map(|run| run.text.len()).sum()
@@ -317,4 +333,31 @@ mod tests {
         // Should merge back to single run
         assert_eq!(text.runs.len(), 1);
     }
+
+    #[test]
+    fn test_run_byte_ranges_with_multibyte_text() {
+        let style = TextStyle::default();
+        let mut bold = style.clone();
+        bold.weight = FontWeight::Bold;
+        let text = RichText {
+            runs: vec![
+                TextRun {
+                    text: "問題".to_string(),
+                    style: bold,
+                },
+                TextRun {
+                    text: "1: abc".to_string(),
+                    style,
+                },
+            ],
+        };
+
+        let ranges = text.run_byte_ranges();
+
+        // Each kanji is 3 bytes in UTF-8
+        assert_eq!(ranges[0].0, 0..6);
+        assert_eq!(ranges[0].1.weight, FontWeight::Bold);
+        assert_eq!(ranges[1].0, 6..12);
+        assert_eq!(&text.get_plain_text()[ranges[1].0.clone()], "1: abc");
+    }
 }
@@ -0,0 +1,579 @@
+//! Spell checking against Hunspell dictionaries
+//!
+//! Dictionaries are the `<language>.dic` / `<language>.aff` pairs shipped by
+//! LibreOffice and most Linux distributions. Affix rules are expanded when the
+//! dictionary is loaded, so lookups are plain set queries; compounding and
+//! the rarer Hunspell options are not supported.
+//!
+//! Only words written in Latin script are checked. Japanese and Chinese text
+//! has no spaces between words, so it is never reported as misspelled.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Directories searched for dictionaries after those listed in `$DICPATH`
+pub const DICTIONARY_DIRS: &[&str] = &[
+    "/usr/share/hunspell",
+    "/usr/share/myspell",
+    "/usr/share/myspell/dicts",
+    "/usr/local/share/hunspell",
+    "/opt/homebrew/share/hunspell",
+    "/Library/Spelling",
+];
+
+/// Longest word, in characters, for which suggestions two edits away are searched
+pub const MAX_DISTANCE_TWO_LEN: usize = 12;
+
+/// Strings tried two edits away before the suggestion search gives up
+pub const MAX_DISTANCE_TWO_CANDIDATES: usize = 250_000;
+
+#[derive(Debug, Error)]
+pub enum SpellingError {
+    #[error("no dictionary found for language {0}")]
+    DictionaryNotFound(String),
+    #[error("failed to read dictionary {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+/// Word list with the forms derived from its affix rules
+#[derive(Clone, Debug, Default)]
+pub struct SpellChecker {
+    words: HashSet<String>,
+    /// Lowercase letters tried when building suggestions
+    alphabet: Vec<char>,
+}
+
+impl SpellChecker {
+    /// Load the dictionary for `language` (such as `en_US`) from the standard locations
+    pub fn load(language: &str) -> Result<Self, SpellingError> {
+        let file_name = format!("{}.dic", language);
+        let search_path = std::env::var_os("DICPATH")
+            .map(|paths| std::env::split_paths(&paths).collect::<Vec<_>>())
+            .unwrap_or_default();
+        let dic_path = search_path
+            .into_iter()
+            .chain(DICTIONARY_DIRS.iter().map(PathBuf::from))
+            .map(|dir| dir.join(&file_name))
+            .find(|path| path.is_file())
+            .ok_or_else(|| SpellingError::DictionaryNotFound(language.to_string()))?;
+        Self::from_files(&dic_path)
+    }
+
+    /// Load a `.dic` file and the `.aff` file next to it, if there is one
+    pub fn from_files(dic_path: &Path) -> Result<Self, SpellingError> {
+        let read = |path: &Path| {
+            std::fs::read(path)
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                .map_err(|source| SpellingError::Io {
+                    path: path.to_path_buf(),
+                    source,
+                })
+        };
+        let dic = read(dic_path)?;
+        let aff_path = dic_path.with_extension("aff");
+        let aff = if aff_path.is_file() {
+            Some(read(&aff_path)?)
+        } else {
+            None
+        };
+        Ok(Self::from_hunspell(&dic, aff.as_deref()))
+    }
+
+    /// Build a checker from the contents of a `.dic` file and its optional `.aff` file
+    pub fn from_hunspell(dic: &str, aff: Option<&str>) -> Self {
+        let affixes = aff.map(AffixRules::parse).unwrap_or_default();
+        let mut words = HashSet::new();
+        let mut lines = dic.lines();
+        // The first line holds the approximate word count
+        if let Some(first) = lines.next() {
+            if first.trim().parse::<usize>().is_err() {
+                lines = dic.lines();
+            }
+        }
+        for line in lines {
+            let Some(entry) = line.split_whitespace().next() else {
+                continue;
+            };
+            let (stem, flags) = entry.split_once('/').unwrap_or((entry, ""));
+            affixes.expand(stem, &affixes.parse_flags(flags), &mut words);
+        }
+        Self::from_words(words)
+    }
+
+    /// Build a checker from a plain list of correctly spelled words
+    pub fn from_words<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let words: HashSet<String> = words.into_iter().map(Into::into).collect();
+        let alphabet: BTreeSet<char> = words
+            .iter()
+            .flat_map(|word| word.chars())
+            .filter(|c| c.is_alphabetic())
+            .flat_map(char::to_lowercase)
+            .collect();
+        Self {
+            words,
+            alphabet: alphabet.into_iter().collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Whether `word` is spelled correctly
+    ///
+    /// Capitalized and all-caps forms of dictionary words are accepted, so
+    /// "The" and "THE" pass when the dictionary has "the".
+    pub fn check(&self, word: &str) -> bool {
+        if self.words.contains(word) {
+            return true;
+        }
+        let lower = word.to_lowercase();
+        let is_capitalized = word.chars().skip(1).all(|c| !c.is_uppercase());
+        let is_all_caps = word.chars().all(|c| !c.is_lowercase());
+        if !(is_capitalized || is_all_caps) {
+            return false;
+        }
+        self.words.contains(&lower) || (is_all_caps && self.words.contains(&capitalize(&lower)))
+    }
+
+    /// Byte ranges of the misspelled words in `text`
+    pub fn misspelled_ranges(&self, text: &str) -> Vec<Range<usize>> {
+        latin_words(text)
+            .filter(|range| !self.check(&text[range.clone()]))
+            .collect()
+    }
+
+    /// Up to `limit` dictionary words one or two edits away from `word`
+    ///
+    /// Words one edit away come first. Suggestions follow the capitalization
+    /// of `word`. The search two edits away grows with the square of the word
+    /// length, so it is skipped for long words and stops after
+    /// [`MAX_DISTANCE_TWO_CANDIDATES`] strings.
+    pub fn suggestions(&self, word: &str, limit: usize) -> Vec<String> {
+        let lower = word.to_lowercase();
+        let near_edits = self.edits(&lower);
+        let near: BTreeSet<String> = near_edits
+            .iter()
+            .filter(|candidate| self.check(candidate))
+            .cloned()
+            .collect();
+        let mut found: Vec<String> = near.into_iter().take(limit).collect();
+        if found.is_empty() && lower.chars().count() <= MAX_DISTANCE_TWO_LEN {
+            let far: BTreeSet<String> = near_edits
+                .iter()
+                .flat_map(|edit| self.edits(edit))
+                .take(MAX_DISTANCE_TWO_CANDIDATES)
+                .filter(|candidate| candidate != &lower && self.check(candidate))
+                .collect();
+            found = far.into_iter().take(limit).collect();
+        }
+
+        let is_all_caps = word.chars().count() > 1 && word.chars().all(|c| !c.is_lowercase());
+        let is_capitalized = word.chars().next().is_some_and(char::is_uppercase);
+        found
+            .into_iter()
+            .map(|suggestion| {
+                if is_all_caps {
+                    suggestion.to_uppercase()
+                } else if is_capitalized {
+                    capitalize(&suggestion)
+                } else {
+                    suggestion
+                }
+            })
+            .collect()
+    }
+
+    /// Every string one deletion, transposition, replacement or insertion away from `word`
+    fn edits(&self, word: &str) -> Vec<String> {
+        let chars: Vec<char> = word.chars().collect();
+        let join = |parts: &[&[char]]| parts.concat().into_iter().collect::<String>();
+        let mut edits = Vec::new();
+        for i in 0..=chars.len() {
+            let (head, tail) = chars.split_at(i);
+            if let Some((_, rest)) = tail.split_first() {
+                edits.push(join(&[head, rest]));
+            }
+            if tail.len() > 1 {
+                edits.push(join(&[head, &[tail[1], tail[0]], &tail[2..]]));
+            }
+            for &letter in &self.alphabet {
+                if let Some((&current, rest)) = tail.split_first() {
+                    if current != letter {
+                        edits.push(join(&[head, &[letter], rest]));
+                    }
+                }
+                edits.push(join(&[head, &[letter], tail]));
+            }
+        }
+        edits
+    }
+}
+
+/// Byte range of the checked word containing byte `index`, if any
+pub fn word_at(text: &str, index: usize) -> Option<Range<usize>> {
+    latin_words(text).find(|range| range.start <= index && index <= range.end)
+}
+
+/// Latin letters, the only script spell checking applies to
+fn is_latin_letter(c: char) -> bool {
+    c.is_ascii_alphabetic() || (c.is_alphabetic() && ('\u{00C0}'..='\u{024F}').contains(&c))
+}
+
+/// Byte ranges of the words to check in `text`
+///
+/// A word is a run of Latin letters, with apostrophes allowed between
+/// letters. Runs touching digits (part numbers, "2nd") are skipped.
+fn latin_words(text: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    let mut chars = text.char_indices().peekable();
+    std::iter::from_fn(move || loop {
+        let (start, first) = chars.next()?;
+        if !is_latin_letter(first) && !first.is_ascii_digit() {
+            continue;
+        }
+        let mut end = start + first.len_utf8();
+        let mut has_digit = first.is_ascii_digit();
+        while let Some(&(index, c)) = chars.peek() {
+            if is_latin_letter(c) || c.is_ascii_digit() {
+                has_digit |= c.is_ascii_digit();
+            } else if matches!(c, '\'' | '’')
+                && text[index + c.len_utf8()..]
+                    .chars()
+                    .next()
+                    .is_some_and(is_latin_letter)
+            {
+                // Apostrophe inside a word ("don't")
+            } else {
+                break;
+            }
+            end = index + c.len_utf8();
+            chars.next();
+        }
+        if !has_digit {
+            return Some(start..end);
+        }
+    })
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// How flags are written in the dictionary (`FLAG` in the `.aff` file)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum FlagFormat {
+    /// One character per flag
+    #[default]
+    Single,
+    /// Two characters per flag
+    Long,
+    /// Comma-separated numbers
+    Numeric,
+}
+
+/// One prefix or suffix rule
+#[derive(Clone, Debug)]
+struct AffixRule {
+    strip: String,
+    add: String,
+    condition: Vec<CharClass>,
+}
+
+/// One position of an affix condition
+#[derive(Clone, Debug)]
+enum CharClass {
+    Any,
+    Set { chars: Vec<char>, negated: bool },
+}
+
+impl CharClass {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            CharClass::Any => true,
+            CharClass::Set { chars, negated } => chars.contains(&c) != *negated,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct AffixClass {
+    cross_product: bool,
+    rules: Vec<AffixRule>,
+}
+
+/// Prefix and suffix rules of an `.aff` file
+#[derive(Clone, Debug, Default)]
+struct AffixRules {
+    flag_format: FlagFormat,
+    prefixes: HashMap<String, AffixClass>,
+    suffixes: HashMap<String, AffixClass>,
+}
+
+impl AffixRules {
+    fn parse(aff: &str) -> Self {
+        let mut rules = Self::default();
+        for line in aff.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                ["FLAG", "long", ..] => rules.flag_format = FlagFormat::Long,
+                ["FLAG", "num", ..] => rules.flag_format = FlagFormat::Numeric,
+                [kind @ ("PFX" | "SFX"), flag, cross, count]
+                    if count.parse::<usize>().is_ok() && matches!(*cross, "Y" | "N") =>
+                {
+                    let classes = if *kind == "PFX" {
+                        &mut rules.prefixes
+                    } else {
+                        &mut rules.suffixes
+                    };
+                    classes.insert(
+                        flag.to_string(),
+                        AffixClass {
+                            cross_product: *cross == "Y",
+                            rules: Vec::new(),
+                        },
+                    );
+                }
+                [kind @ ("PFX" | "SFX"), flag, strip, add, rest @ ..] => {
+                    let classes = if *kind == "PFX" {
+                        &mut rules.prefixes
+                    } else {
+                        &mut rules.suffixes
+                    };
+                    let Some(class) = classes.get_mut(*flag) else {
+                        continue;
+                    };
+                    // Continuation flags after the affix are not supported
+                    let add = add.split('/').next().unwrap_or_default();
+                    class.rules.push(AffixRule {
+                        strip: if *strip == "0" {
+                            String::new()
+                        } else {
+                            strip.to_string()
+                        },
+                        add: if add == "0" {
+                            String::new()
+                        } else {
+                            add.to_string()
+                        },
+                        condition: parse_condition(rest.first().copied().unwrap_or(".")),
+                    });
+                }
+                _ => {}
+            }
+        }
+        rules
+    }
+
+    fn parse_flags(&self, flags: &str) -> Vec<String> {
+        match self.flag_format {
+            FlagFormat::Single => flags.chars().map(String::from).collect(),
+            FlagFormat::Long => {
+                let chars: Vec<char> = flags.chars().collect();
+                chars.chunks(2).map(|pair| pair.iter().collect()).collect()
+            }
+            FlagFormat::Numeric => flags
+                .split(',')
+                .map(|flag| flag.trim().to_string())
+                .collect(),
+        }
+    }
+
+    /// Add `stem` and every form its flags derive to `words`
+    fn expand(&self, stem: &str, flags: &[String], words: &mut HashSet<String>) {
+        words.insert(stem.to_string());
+
+        let mut cross_suffixed = Vec::new();
+        for class in flags.iter().filter_map(|flag| self.suffixes.get(flag)) {
+            for rule in &class.rules {
+                if let Some(word) = apply_suffix(stem, rule) {
+                    if class.cross_product {
+                        cross_suffixed.push(word.clone());
+                    }
+                    words.insert(word);
+                }
+            }
+        }
+
+        for class in flags.iter().filter_map(|flag| self.prefixes.get(flag)) {
+            for rule in &class.rules {
+                if let Some(word) = apply_prefix(stem, rule) {
+                    words.insert(word);
+                }
+                if class.cross_product {
+                    for suffixed in &cross_suffixed {
+                        if let Some(word) = apply_prefix(suffixed, rule) {
+                            words.insert(word);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Parse a condition such as `[^aeiou]y` into one class per character
+fn parse_condition(condition: &str) -> Vec<CharClass> {
+    let mut classes = Vec::new();
+    let mut chars = condition.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => classes.push(CharClass::Any),
+            '[' => {
+                let mut set: Vec<char> = chars.by_ref().take_while(|&c| c != ']').collect();
+                let negated = set.first() == Some(&'^');
+                if negated {
+                    set.remove(0);
+                }
+                classes.push(CharClass::Set {
+                    chars: set,
+                    negated,
+                });
+            }
+            c => classes.push(CharClass::Set {
+                chars: vec![c],
+                negated: false,
+            }),
+        }
+    }
+    classes
+}
+
+fn condition_matches<'a>(condition: &[CharClass], chars: impl Iterator<Item = char> + 'a) -> bool {
+    let chars: Vec<char> = chars.take(condition.len()).collect();
+    chars.len() == condition.len()
+        && condition
+            .iter()
+            .zip(chars)
+            .all(|(class, c)| class.matches(c))
+}
+
+fn apply_suffix(word: &str, rule: &AffixRule) -> Option<String> {
+    let tail: Vec<char> = word.chars().rev().take(rule.condition.len()).collect();
+    let reversed_condition: Vec<CharClass> = rule.condition.iter().rev().cloned().collect();
+    if !condition_matches(&reversed_condition, tail.into_iter()) {
+        return None;
+    }
+    let base = word.strip_suffix(rule.strip.as_str())?;
+    (!base.is_empty()).then(|| format!("{}{}", base, rule.add))
+}
+
+fn apply_prefix(word: &str, rule: &AffixRule) -> Option<String> {
+    if !condition_matches(&rule.condition, word.chars()) {
+        return None;
+    }
+    let base = word.strip_prefix(rule.strip.as_str())?;
+    (!base.is_empty()).then(|| format!("{}{}", rule.add, base))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFF: &str = "SET UTF-8
+PFX U Y 1
+PFX U 0 un .
+
+SFX S Y 2
+SFX S y ies [^aeiou]y
+SFX S 0 s [^y]
+
+SFX D N 2
+SFX D 0 d e
+SFX D 0 ed [^e]
+";
+
+    const DIC: &str = "4
+study/S
+answer/SDU
+score/SD
+Tokyo
+";
+
+    fn checker() -> SpellChecker {
+        SpellChecker::from_hunspell(DIC, Some(AFF))
+    }
+
+    #[test]
+    fn affix_rules_expand_dictionary_forms() {
+        let checker = checker();
+        for word in [
+            "study", "studies", "answers", "answered", "scored", "unanswer",
+        ] {
+            assert!(checker.check(word), "{} should be accepted", word);
+        }
+        // Conditions limit where a rule applies
+        assert!(!checker.check("studys"));
+        assert!(!checker.check("scoreed"));
+        // Prefixes combine only with suffixes from cross-product classes
+        assert!(checker.check("unanswers"));
+        assert!(!checker.check("unanswered"));
+    }
+
+    #[test]
+    fn capitalization_follows_the_dictionary() {
+        let checker = checker();
+        assert!(checker.check("Answer"));
+        assert!(checker.check("ANSWER"));
+        assert!(checker.check("TOKYO"));
+        assert!(!checker.check("tokyo"));
+        assert!(!checker.check("aNswer"));
+    }
+
+    #[test]
+    fn only_latin_words_are_checked() {
+        let checker = checker();
+        let text = "問1: Answr the qestion（2nd）don't 東京";
+        let misspelled: Vec<&str> = checker
+            .misspelled_ranges(text)
+            .into_iter()
+            .map(|range| &text[range])
+            .collect();
+        assert_eq!(misspelled, ["Answr", "the", "qestion", "don't"]);
+        assert_eq!(
+            word_at(text, text.find("qestion").unwrap() + 3).map(|range| &text[range]),
+            Some("qestion")
+        );
+        assert_eq!(word_at(text, 0), None);
+    }
+
+    #[test]
+    fn suggestions_are_close_dictionary_words() {
+        let checker = checker();
+        assert_eq!(checker.suggestions("Answr", 5), ["Answer"]);
+        assert_eq!(checker.suggestions("stuides", 5), ["studies"]);
+        // Two edits away when nothing is closer
+        assert_eq!(checker.suggestions("scorred", 5), ["scored"]);
+        assert_eq!(checker.suggestions("xylophone", 5), Vec::<String>::new());
+    }
+
+    #[test]
+    fn long_words_skip_the_two_edit_search() {
+        let checker = SpellChecker::from_words(["internationalization"]);
+        assert_eq!(
+            checker.suggestions("internationalizatoin", 5),
+            ["internationalization"]
+        );
+        // Two edits away, but longer than MAX_DISTANCE_TWO_LEN
+        assert_eq!(
+            checker.suggestions("intrnationalizatoin", 5),
+            Vec::<String>::new()
+        );
+    }
+}
@@ -12,9 +12,18 @@ pub struct TextStyle {
     pub underline: bool,
     pub strikethrough: bool,
     pub background_color: Option<Color>,
+    #[serde(default = "default_line_height")]
     pub line_height: f32, // 行間（相対値、例: 1.0 = 通常、1.5 = 1.5倍）
     #[serde(default)]
+    pub letter_spacing: f32, // 文字間隔（pt、0.0 = 通常）
+    #[serde(default)]
     pub vertical: bool, // 縦書きモード（デフォルト: false）
+    #[serde(default)]
+    pub vertical_alignment: VerticalAlignment, // ボックス内の縦位置（デフォルト: 上揃え）
+}
+
+fn default_line_height() -> f32 {
+    1.0
 }
 
 impl Default for TextStyle {
@@ -29,8 +38,10 @@ impl Default for TextStyle {
             underline: false,
             strikethrough: false,
             background_color: None,
-            line_height: 1.0, // デフォルトは通常の行間
-            vertical: false, // デフォルトは横書き
+            line_height: default_line_height(), // デフォルトは通常の行間
+            letter_spacing: 0.0,                // デフォルトは通常の文字間隔
+            vertical: false,                    // デフォルトは横書き
+            vertical_alignment: VerticalAlignment::Top,
         }
     }
 }
@@ -52,3 +63,59 @@ pub enum TextAlignment {
     End,
     Justified,
 }
+
+/// Placement of the text block inside its box
+///
+/// For vertical text this runs along the block direction, so `Top` keeps the
+/// columns at the right edge and `Bottom` moves them to the left.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum VerticalAlignment {
+    #[default]
+    Top,
+    Middle,
+    Bottom,
+}
+
+impl VerticalAlignment {
+    /// Offset of a block `extent` long from the start of `available`
+    ///
+    /// Text taller than the box stays anchored at the top.
+    pub fn offset(self, available: f32, extent: f32) -> f32 {
+        let free = (available - extent).max(0.0);
+        match self {
+            Self::Top => 0.0,
+            Self::Middle => free / 2.0,
+            Self::Bottom => free,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_style_without_spacing_fields_uses_defaults() {
+        let mut value = serde_json::to_value(TextStyle::default()).unwrap();
+        let object = value.as_object_mut().unwrap();
+        object.remove("line_height");
+        object.remove("letter_spacing");
+
+        let style: TextStyle = serde_json::from_value(value).unwrap();
+        assert_eq!(style.line_height, 1.0);
+        assert_eq!(style.letter_spacing, 0.0);
+    }
+
+    #[test]
+    fn test_vertical_alignment_offsets_and_default() {
+        assert_eq!(VerticalAlignment::Top.offset(100.0, 40.0), 0.0);
+        assert_eq!(VerticalAlignment::Middle.offset(100.0, 40.0), 30.0);
+        assert_eq!(VerticalAlignment::Bottom.offset(100.0, 40.0), 60.0);
+        assert_eq!(VerticalAlignment::Bottom.offset(20.0, 40.0), 0.0);
+
+        let mut value = serde_json::to_value(TextStyle::default()).unwrap();
+        value.as_object_mut().unwrap().remove("vertical_alignment");
+        let style: TextStyle = serde_json::from_value(value).unwrap();
+        assert_eq!(style.vertical_alignment, VerticalAlignment::Top);
+    }
+}
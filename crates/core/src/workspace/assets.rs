@@ -1,5 +1,6 @@
+use crate::document::{Document, DocumentElement};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -29,6 +30,63 @@ impl AssetCatalog {
     pub fn get(&self, id: AssetRef) -> Option<&AssetMetadata> {
         self.assets.get(&id)
     }
+
+    pub fn len(&self) -> usize {
+        self.assets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.assets.is_empty()
+    }
+
+    /// Copy every entry of `other` into this catalog, keeping their ids
+    pub fn extend_from(&mut self, other: &AssetCatalog) {
+        self.assets
+            .extend(other.assets.iter().map(|(id, meta)| (*id, meta.clone())));
+    }
+
+    /// Whether any entry is not referenced by an image in `document`
+    pub fn has_unused(&self, document: &Document) -> bool {
+        let referenced = referenced_assets(document);
+        self.assets.keys().any(|id| !referenced.contains(id))
+    }
+
+    /// Drop entries not referenced by any image in `document`
+    ///
+    /// Images nested in frames and groups count as references. Returns the
+    /// number of entries removed.
+    pub fn prune_unused(&mut self, document: &Document) -> usize {
+        let referenced = referenced_assets(document);
+        let before = self.assets.len();
+        self.assets.retain(|id, _| referenced.contains(id));
+        before - self.assets.len()
+    }
+}
+
+/// Every asset referenced by an image or page background in the document
+fn referenced_assets(document: &Document) -> HashSet<AssetRef> {
+    fn collect(elements: &[DocumentElement], referenced: &mut HashSet<AssetRef>) {
+        for element in elements {
+            match element {
+                DocumentElement::Image(image) => {
+                    referenced.insert(image.source);
+                }
+                DocumentElement::Frame(frame) => collect(&frame.children, referenced),
+                DocumentElement::Group(group) => collect(&group.children, referenced),
+                DocumentElement::Text(_)
+                | DocumentElement::Shape(_)
+                | DocumentElement::Table(_)
+                | DocumentElement::QrCode(_) => {}
+            }
+        }
+    }
+
+    let mut referenced = HashSet::new();
+    for page in &document.pages {
+        collect(&page.elements, &mut referenced);
+        referenced.extend(page.metadata.background.asset());
+    }
+    referenced
 }
 
 impl Default for AssetCatalog {
@@ -57,3 +115,90 @@ pub struct AssetMetadata {
     pub id: AssetRef,
     pub path: PathBuf,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::{FrameElement, GroupElement, ImageElement, Page, PageBackground};
+    use crate::layout::{Point, Rect, Size};
+
+    fn image(source: AssetRef) -> DocumentElement {
+        DocumentElement::Image(ImageElement {
+            id: uuid::Uuid::new_v4(),
+            source,
+            bounds: Rect::new(Point::new(0.0, 0.0), Size::new(10.0, 10.0)),
+            visible: true,
+            locked: false,
+            rotation: 0.0,
+            fit: Default::default(),
+            opacity: 1.0,
+        })
+    }
+
+    #[test]
+    fn prune_unused_keeps_nested_references() {
+        let mut catalog = AssetCatalog::new();
+        let top = catalog.register("top.png");
+        let in_group = catalog.register("group.png");
+        let in_frame = catalog.register("frame.png");
+        let unused = catalog.register("deleted.png");
+
+        let bounds = Rect::new(Point::new(0.0, 0.0), Size::new(50.0, 50.0));
+        let mut page = Page::empty();
+        page.add_element(image(top));
+        page.add_element(DocumentElement::Frame(FrameElement {
+            id: uuid::Uuid::new_v4(),
+            bounds,
+            children: vec![DocumentElement::Group(GroupElement {
+                id: uuid::Uuid::new_v4(),
+                name: "Group".to_string(),
+                bounds,
+                children: vec![image(in_group)],
+                visible: true,
+                locked: false,
+                opacity: 1.0,
+            })],
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+        }));
+        let mut second = Page::empty();
+        second.add_element(DocumentElement::Frame(FrameElement {
+            id: uuid::Uuid::new_v4(),
+            bounds,
+            children: vec![image(in_frame)],
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+        }));
+        let mut document = Document::empty("Assets");
+        document.pages = vec![page, second];
+
+        assert!(catalog.has_unused(&document));
+        assert_eq!(catalog.prune_unused(&document), 1);
+        assert_eq!(catalog.len(), 3);
+        assert!(catalog.get(unused).is_none());
+        assert!(catalog.get(in_group).is_some());
+        assert!(catalog.get(in_frame).is_some());
+        assert!(!catalog.has_unused(&document));
+        assert_eq!(catalog.prune_unused(&document), 0);
+    }
+
+    #[test]
+    fn prune_unused_keeps_page_background_images() {
+        let mut catalog = AssetCatalog::new();
+        let background = catalog.register("paper.png");
+        catalog.register("deleted.png");
+
+        let mut page = Page::empty();
+        page.metadata.background = PageBackground::Image {
+            source: background,
+            fit: Default::default(),
+        };
+        let mut document = Document::empty("Assets");
+        document.pages = vec![page];
+
+        assert_eq!(catalog.prune_unused(&document), 1);
+        assert!(catalog.get(background).is_some());
+    }
+}
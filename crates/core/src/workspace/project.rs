@@ -1,5 +1,6 @@
-use crate::document::{Document, DocumentId};
+use crate::document::{Document, DocumentId, Page, PageBackground};
 use crate::template::{TemplateLibrary, TemplateRef};
+use crate::typography::Palette;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -42,9 +43,8 @@ impl Project {
             let mut builder = crate::document::DocumentBuilder::new().with_title(&tmpl.name);
             for page in &tmpl.pages {
                 builder = builder.add_page(crate::document::Page {
-                    id: crate::document::PageId::new(),
                     metadata: page.metadata.clone(),
-                    elements: Vec::new(),
+                    ..crate::document::Page::empty()
                 });
             }
             builder.build().expect("template produces document")
@@ -75,8 +75,68 @@ pub struct ProjectSettings {
     pub snap_to_guides: bool,
     pub snap_distance: f32,
 
+    // Hit-testing settings
+    /// Resize-handle hit radius in screen pixels (independent of zoom)
+    #[serde(default = "default_handle_tolerance")]
+    pub handle_tolerance: f32,
+
     // Autosave settings
     pub autosave_enabled: bool,
+    /// Seconds between crash recovery snapshots of unsaved changes (0 disables them)
+    #[serde(default = "default_recovery_interval_secs")]
+    pub recovery_interval_secs: u32,
+
+    // Undo settings
+    /// Commands kept in the undo history; the oldest is dropped past this
+    #[serde(default = "default_max_history")]
+    pub max_history: usize,
+
+    // Page settings
+    /// Background given to newly added pages
+    #[serde(default)]
+    pub default_page_background: PageBackground,
+    /// Distance kept from the page edge by the align-to-page actions, in points
+    #[serde(default = "default_page_edge_margin")]
+    pub page_edge_margin: f32,
+
+    // Color settings
+    /// Colors offered in the swatches panel
+    #[serde(default)]
+    pub palette: Palette,
+
+    // Spelling settings
+    /// Hunspell dictionary used to check text while editing (such as `en_US`),
+    /// or `None` to turn spell checking off
+    ///
+    /// Only Latin-script words are checked, so Japanese text is never flagged.
+    /// Off by default: loading a dictionary reads and indexes it at startup.
+    #[serde(default)]
+    pub spell_check_language: Option<String>,
+}
+
+impl ProjectSettings {
+    /// An empty page carrying the project's default background
+    pub fn new_page(&self) -> Page {
+        let mut page = Page::empty();
+        page.metadata.background = self.default_page_background.clone();
+        page
+    }
+}
+
+fn default_handle_tolerance() -> f32 {
+    8.0
+}
+
+fn default_recovery_interval_secs() -> u32 {
+    60
+}
+
+fn default_max_history() -> usize {
+    100
+}
+
+fn default_page_edge_margin() -> f32 {
+    20.0
 }
 
 impl Default for ProjectSettings {
@@ -91,7 +151,14 @@ impl Default for ProjectSettings {
             snap_to_grid: true,
             snap_to_guides: true,
             snap_distance: 5.0,
+            handle_tolerance: default_handle_tolerance(),
             autosave_enabled: true,
+            recovery_interval_secs: default_recovery_interval_secs(),
+            max_history: default_max_history(),
+            default_page_background: PageBackground::None,
+            page_edge_margin: default_page_edge_margin(),
+            palette: Palette::default(),
+            spell_check_language: None,
         }
     }
 }
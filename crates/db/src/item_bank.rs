@@ -2,15 +2,38 @@
 //!
 //! Provides CRUD operations and search functionality for the item database.
 
-use crate::models::{Difficulty, Item, ItemType, Passage};
+use crate::models::{Choice, Difficulty, Item, ItemType, Passage, Tag};
 use crate::schema;
-use anyhow::Result;
+use anyhow::{bail, Result};
 use chrono::Utc;
-use rusqlite::{params, Connection};
+use rusqlite::{params, params_from_iter, Connection, Row};
 use std::path::Path;
 use tracing::{debug, info};
 use uuid::Uuid;
 
+/// Sort order for paged item listings
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ItemSort {
+    /// Oldest items first
+    CreatedAsc,
+    /// Newest items first
+    #[default]
+    CreatedDesc,
+    /// By title, case-insensitive
+    Alphabetical,
+}
+
+impl ItemSort {
+    /// SQL `ORDER BY` clause; `id` breaks ties so pages never overlap
+    fn order_by(&self) -> &'static str {
+        match self {
+            ItemSort::CreatedAsc => "created_at ASC, id ASC",
+            ItemSort::CreatedDesc => "created_at DESC, id DESC",
+            ItemSort::Alphabetical => "title COLLATE NOCASE ASC, id ASC",
+        }
+    }
+}
+
 /// ItemBank manages access to the SQLite database
 pub struct ItemBank {
     conn: Connection,
@@ -186,6 +209,37 @@ impl ItemBank {
         Ok(items)
     }
 
+    /// List one page of items
+    ///
+    /// Returns the items in `offset..offset + limit` for the given sort order
+    /// together with the total number of items, for building page controls.
+    /// An offset past the end yields an empty page.
+    pub fn list_items(
+        &self,
+        offset: usize,
+        limit: usize,
+        sort: ItemSort,
+    ) -> Result<(Vec<Item>, i64)> {
+        debug!(
+            "Listing items from {} (limit {}, {:?})",
+            offset, limit, sort
+        );
+
+        let sql = format!(
+            "SELECT id, title, description, content, item_type, difficulty, passage_id, created_at, updated_at
+             FROM items ORDER BY {} LIMIT ?1 OFFSET ?2",
+            sort.order_by()
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let mut items = stmt
+            .query_map(params![limit as i64, offset as i64], item_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.load_skill_ids(&mut items)?;
+
+        Ok((items, self.count_items()?))
+    }
+
     /// Search items by title
     pub fn search_items(&self, query: &str) -> Result<Vec<Item>> {
         debug!("Searching for items: {}", query);
@@ -232,6 +286,55 @@ impl ItemBank {
         Ok(items)
     }
 
+    /// Full-text search over item titles, stems and choices
+    ///
+    /// Matches `query` case-insensitively as a substring of the title,
+    /// content (stem) or any choice text. When `tags` is non-empty only items
+    /// carrying every one of the tags are returned. Results are ordered by
+    /// relevance (title hits before stem hits before choice hits), then by
+    /// recency. An empty query returns all items matching the tag filter.
+    pub fn search(&self, query: &str, tags: &[Tag]) -> Result<Vec<Item>> {
+        debug!("Searching items: {:?} (tags: {})", query, tags.len());
+
+        let pattern = format!("%{}%", escape_like(query.trim()));
+        let mut sql = String::from(
+            "SELECT id, title, description, content, item_type, difficulty, passage_id, created_at, updated_at,
+                    CASE
+                        WHEN title LIKE ?1 ESCAPE '\\' THEN 0
+                        WHEN content LIKE ?1 ESCAPE '\\' THEN 1
+                        ELSE 2
+                    END AS rank
+             FROM items
+             WHERE (title LIKE ?1 ESCAPE '\\'
+                    OR content LIKE ?1 ESCAPE '\\'
+                    OR EXISTS (SELECT 1 FROM choices
+                               WHERE choices.item_id = items.id AND choices.text LIKE ?1 ESCAPE '\\'))",
+        );
+
+        let mut values = vec![pattern];
+        if !tags.is_empty() {
+            let placeholders: Vec<String> =
+                (0..tags.len()).map(|i| format!("?{}", i + 2)).collect();
+            sql.push_str(&format!(
+                " AND (SELECT COUNT(DISTINCT tag_id) FROM item_tags
+                       WHERE item_tags.item_id = items.id AND tag_id IN ({})) = {}",
+                placeholders.join(", "),
+                tags.len()
+            ));
+            values.extend(tags.iter().map(|tag| tag.id.to_string()));
+        }
+        sql.push_str(" ORDER BY rank, created_at DESC LIMIT 100");
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let mut items = stmt
+            .query_map(params_from_iter(values.iter()), item_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.load_skill_ids(&mut items)?;
+
+        Ok(items)
+    }
+
     /// Filter items by difficulty
     pub fn get_items_by_difficulty(&self, difficulty: Difficulty) -> Result<Vec<Item>> {
         debug!("Getting items by difficulty: {:?}", difficulty);
@@ -288,6 +391,150 @@ impl ItemBank {
         Ok(())
     }
 
+    // ========== Choice and tag operations ==========
+
+    /// Insert a choice for an item
+    pub fn insert_choice(&self, choice: &Choice) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO choices (id, item_id, text, is_correct, \"order\")
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                choice.id.to_string(),
+                choice.item_id.to_string(),
+                &choice.text,
+                choice.is_correct,
+                choice.order,
+            ],
+        )?;
+
+        debug!("Choice {} inserted for item {}", choice.id, choice.item_id);
+        Ok(())
+    }
+
+    /// Insert a new tag
+    pub fn insert_tag(&self, tag: &Tag) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO tags (id, name, category) VALUES (?1, ?2, ?3)",
+            params![tag.id.to_string(), &tag.name, &tag.category],
+        )?;
+
+        info!("Tag {} inserted", tag.id);
+        Ok(())
+    }
+
+    /// Attach a tag to an item
+    pub fn tag_item(&self, item_id: &Uuid, tag_id: &Uuid) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO item_tags (item_id, tag_id) VALUES (?1, ?2)",
+            params![item_id.to_string(), tag_id.to_string()],
+        )?;
+
+        Ok(())
+    }
+
+    /// Get all tags, ordered by category and name
+    pub fn get_tags(&self) -> Result<Vec<Tag>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, category FROM tags ORDER BY category, name COLLATE NOCASE",
+        )?;
+
+        let tags = stmt
+            .query_map([], tag_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(tags)
+    }
+
+    /// Get the tags attached to an item
+    pub fn get_item_tags(&self, item_id: &Uuid) -> Result<Vec<Tag>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tags.id, tags.name, tags.category FROM tags
+             JOIN item_tags ON item_tags.tag_id = tags.id
+             WHERE item_tags.item_id = ?1 ORDER BY tags.category, tags.name COLLATE NOCASE",
+        )?;
+
+        let tags = stmt
+            .query_map(params![item_id.to_string()], tag_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(tags)
+    }
+
+    /// Rename a tag
+    ///
+    /// Items reference tags by ID, so every tagged item sees the new name.
+    pub fn rename_tag(&self, id: &Uuid, name: &str) -> Result<()> {
+        let name = name.trim();
+        if name.is_empty() {
+            bail!("Tag name must not be empty");
+        }
+
+        let updated = self.conn.execute(
+            "UPDATE tags SET name = ?1 WHERE id = ?2",
+            params![name, id.to_string()],
+        )?;
+        if updated == 0 {
+            bail!("Tag {} not found", id);
+        }
+
+        info!("Tag {} renamed to {}", id, name);
+        Ok(())
+    }
+
+    /// Merge tag `from` into tag `into` and delete `from`
+    ///
+    /// Items tagged with `from` are retagged with `into`; items that already
+    /// had both keep a single association. Runs in one transaction.
+    pub fn merge_tags(&self, from: &Uuid, into: &Uuid) -> Result<()> {
+        if from == into {
+            bail!("Cannot merge tag {} into itself", from);
+        }
+
+        let tx = self.conn.unchecked_transaction()?;
+        for id in [from, into] {
+            let exists: i64 = tx.query_row(
+                "SELECT COUNT(*) FROM tags WHERE id = ?1",
+                params![id.to_string()],
+                |row| row.get(0),
+            )?;
+            if exists == 0 {
+                bail!("Tag {} not found", id);
+            }
+        }
+
+        tx.execute(
+            "INSERT OR IGNORE INTO item_tags (item_id, tag_id)
+             SELECT item_id, ?2 FROM item_tags WHERE tag_id = ?1",
+            params![from.to_string(), into.to_string()],
+        )?;
+        tx.execute(
+            "DELETE FROM item_tags WHERE tag_id = ?1",
+            params![from.to_string()],
+        )?;
+        tx.execute("DELETE FROM tags WHERE id = ?1", params![from.to_string()])?;
+        tx.commit()?;
+
+        info!("Tag {} merged into {}", from, into);
+        Ok(())
+    }
+
+    /// Delete a tag and remove it from every item
+    pub fn delete_tag(&self, id: &Uuid) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "DELETE FROM item_tags WHERE tag_id = ?1",
+            params![id.to_string()],
+        )?;
+        let deleted = tx.execute("DELETE FROM tags WHERE id = ?1", params![id.to_string()])?;
+        if deleted == 0 {
+            bail!("Tag {} not found", id);
+        }
+        tx.commit()?;
+
+        info!("Tag {} deleted", id);
+        Ok(())
+    }
+
     // ========== Passage operations ==========
 
     /// Insert a new passage
@@ -365,6 +612,58 @@ impl ItemBank {
     }
 }
 
+/// Build an item from the first nine columns of an `items` query row
+///
+/// Skill IDs are left empty; callers fill them with `load_skill_ids`.
+fn item_from_row(row: &Row) -> rusqlite::Result<Item> {
+    let created_at_str = row.get::<_, String>(7)?;
+    let updated_at_str = row.get::<_, String>(8)?;
+
+    let created_at = chrono::DateTime::parse_from_rfc3339(&created_at_str)
+        .map_err(|_| rusqlite::Error::InvalidParameterName("created_at".to_string()))?
+        .with_timezone(&Utc);
+
+    let updated_at = chrono::DateTime::parse_from_rfc3339(&updated_at_str)
+        .map_err(|_| rusqlite::Error::InvalidParameterName("updated_at".to_string()))?
+        .with_timezone(&Utc);
+
+    Ok(Item {
+        id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap(),
+        title: row.get(1)?,
+        description: row.get(2)?,
+        content: row.get(3)?,
+        item_type: parse_item_type(&row.get::<_, String>(4)?),
+        difficulty: parse_difficulty(&row.get::<_, String>(5)?),
+        skill_ids: Vec::new(),
+        passage_id: row
+            .get::<_, Option<String>>(6)?
+            .map(|s| Uuid::parse_str(&s).unwrap()),
+        created_at,
+        updated_at,
+    })
+}
+
+/// Build a tag from an `id, name, category` query row
+fn tag_from_row(row: &Row) -> rusqlite::Result<Tag> {
+    Ok(Tag {
+        id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap_or_default(),
+        name: row.get(1)?,
+        category: row.get(2)?,
+    })
+}
+
+/// Escape `%`, `_` and `\` so user input is matched literally by `LIKE ... ESCAPE '\'`
+fn escape_like(query: &str) -> String {
+    let mut escaped = String::with_capacity(query.len());
+    for c in query.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
 /// Helper function to parse ItemType from string
 fn parse_item_type(s: &str) -> ItemType {
     match s {
@@ -448,4 +747,192 @@ mod tests {
 
         Ok(())
     }
+
+    fn sample_item(title: &str, content: &str, minutes_ago: i64) -> Item {
+        let created_at = Utc::now() - chrono::Duration::minutes(minutes_ago);
+        Item {
+            id: Uuid::new_v4(),
+            title: title.to_string(),
+            description: None,
+            content: content.to_string(),
+            item_type: ItemType::MultipleChoice,
+            difficulty: Difficulty::Medium,
+            skill_ids: vec![],
+            passage_id: None,
+            created_at,
+            updated_at: created_at,
+        }
+    }
+
+    #[test]
+    fn test_list_items_pages_and_sorts() -> Result<()> {
+        let bank = ItemBank::memory()?;
+
+        let charlie = sample_item("charlie", "c", 30);
+        let alpha = sample_item("Alpha", "a", 20);
+        let bravo = sample_item("bravo", "b", 10);
+        for item in [&charlie, &alpha, &bravo] {
+            bank.insert_item(item)?;
+        }
+
+        let titles = |items: Vec<Item>| -> Vec<String> {
+            items.into_iter().map(|item| item.title).collect()
+        };
+
+        let (items, total) = bank.list_items(0, 2, ItemSort::Alphabetical)?;
+        assert_eq!(total, 3);
+        assert_eq!(titles(items), vec!["Alpha", "bravo"]);
+
+        let (items, _) = bank.list_items(2, 2, ItemSort::Alphabetical)?;
+        assert_eq!(titles(items), vec!["charlie"]);
+
+        let (items, _) = bank.list_items(0, 10, ItemSort::CreatedDesc)?;
+        assert_eq!(titles(items), vec!["bravo", "Alpha", "charlie"]);
+
+        let (items, _) = bank.list_items(1, 1, ItemSort::CreatedAsc)?;
+        assert_eq!(titles(items), vec!["Alpha"]);
+
+        // Boundary offsets
+        let (items, total) = bank.list_items(3, 2, ItemSort::CreatedAsc)?;
+        assert!(items.is_empty());
+        assert_eq!(total, 3);
+        assert!(bank.list_items(100, 2, ItemSort::CreatedAsc)?.0.is_empty());
+        assert!(bank.list_items(0, 0, ItemSort::CreatedAsc)?.0.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_matches_stems_choices_and_tags() -> Result<()> {
+        let bank = ItemBank::memory()?;
+
+        let fractions = sample_item("Fractions", "What is 1/2 + 1/4?", 30);
+        let photosynthesis = sample_item("Plants", "Which gas do plants absorb?", 20);
+        let older_plants = sample_item("Plant cells", "Name the organelle.", 10);
+        let percent = sample_item("Percentages", "What is 50% of 80?", 5);
+        for item in [&fractions, &photosynthesis, &older_plants, &percent] {
+            bank.insert_item(item)?;
+        }
+
+        bank.insert_choice(&Choice {
+            id: Uuid::new_v4(),
+            item_id: older_plants.id,
+            text: "Chloroplast".to_string(),
+            is_correct: true,
+            order: 0,
+        })?;
+
+        let science = Tag {
+            id: Uuid::new_v4(),
+            name: "Science".to_string(),
+            category: "subject".to_string(),
+        };
+        let grade5 = Tag {
+            id: Uuid::new_v4(),
+            name: "Grade 5".to_string(),
+            category: "grade".to_string(),
+        };
+        bank.insert_tag(&science)?;
+        bank.insert_tag(&grade5)?;
+        bank.tag_item(&photosynthesis.id, &science.id)?;
+        bank.tag_item(&older_plants.id, &science.id)?;
+        bank.tag_item(&older_plants.id, &grade5.id)?;
+
+        // Stem match, case-insensitive
+        let hits = bank.search("PLANTS ABSORB", &[])?;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, photosynthesis.id);
+
+        // Choice text match
+        let hits = bank.search("chloro", &[])?;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, older_plants.id);
+
+        // Title hits rank before stem hits, then newest first
+        let hits = bank.search("plant", &[])?;
+        let ids: Vec<_> = hits.iter().map(|item| item.id).collect();
+        assert_eq!(ids, vec![older_plants.id, photosynthesis.id]);
+
+        // Tag filtering requires every tag
+        let hits = bank.search("", std::slice::from_ref(&science))?;
+        assert_eq!(hits.len(), 2);
+        let hits = bank.search("", &[science, grade5])?;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, older_plants.id);
+
+        // LIKE wildcards in the query are matched literally
+        let hits = bank.search("50%", &[])?;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, percent.id);
+        assert!(bank.search("_", &[])?.is_empty());
+
+        Ok(())
+    }
+
+    fn sample_tag(name: &str) -> Tag {
+        Tag {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            category: "subject".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_rename_and_delete_tag() -> Result<()> {
+        let bank = ItemBank::memory()?;
+        let item = sample_item("Item", "content", 0);
+        bank.insert_item(&item)?;
+        let tag = sample_tag("Mathz");
+        bank.insert_tag(&tag)?;
+        bank.tag_item(&item.id, &tag.id)?;
+
+        bank.rename_tag(&tag.id, " Math ")?;
+        assert_eq!(bank.get_item_tags(&item.id)?[0].name, "Math");
+        assert!(bank.rename_tag(&tag.id, "  ").is_err());
+        assert!(bank.rename_tag(&Uuid::new_v4(), "Other").is_err());
+
+        bank.delete_tag(&tag.id)?;
+        assert!(bank.get_tags()?.is_empty());
+        assert!(bank.get_item_tags(&item.id)?.is_empty());
+        assert!(bank.delete_tag(&tag.id).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_tags_without_duplicates() -> Result<()> {
+        let bank = ItemBank::memory()?;
+        let only_from = sample_item("Only from", "a", 2);
+        let both = sample_item("Both", "b", 1);
+        for item in [&only_from, &both] {
+            bank.insert_item(item)?;
+        }
+
+        let from = sample_tag("Maths");
+        let into = sample_tag("Math");
+        bank.insert_tag(&from)?;
+        bank.insert_tag(&into)?;
+        bank.tag_item(&only_from.id, &from.id)?;
+        bank.tag_item(&both.id, &from.id)?;
+        bank.tag_item(&both.id, &into.id)?;
+
+        bank.merge_tags(&from.id, &into.id)?;
+
+        let tags = bank.get_tags()?;
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].id, into.id);
+        for item in [&only_from, &both] {
+            let item_tags = bank.get_item_tags(&item.id)?;
+            assert_eq!(item_tags.len(), 1);
+            assert_eq!(item_tags[0].id, into.id);
+        }
+        assert_eq!(bank.search("", std::slice::from_ref(&into))?.len(), 2);
+
+        // Failed merges leave everything untouched
+        assert!(bank.merge_tags(&into.id, &into.id).is_err());
+        assert!(bank.merge_tags(&Uuid::new_v4(), &into.id).is_err());
+        assert_eq!(bank.get_tags()?.len(), 1);
+
+        Ok(())
+    }
 }
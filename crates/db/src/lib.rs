@@ -6,7 +6,7 @@ pub mod item_bank;
 pub mod models;
 pub mod schema;
 
-pub use item_bank::ItemBank;
+pub use item_bank::{ItemBank, ItemSort};
 pub use models::{Choice, Item, Passage, Tag};
 
 use anyhow::Result;
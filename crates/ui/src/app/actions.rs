@@ -1,8 +1,12 @@
 use gtk4::{gio, glib, prelude::*};
 
 pub fn register_global_actions(app: &gtk4::Application) {
+    // Close the windows rather than quitting outright so each one can
+    // prompt about unsaved changes; the application exits with the last one
     add_action(app, "quit", |application| {
-        application.quit();
+        for window in application.windows() {
+            window.close();
+        }
     });
 
     let actions: [(&str, Option<&str>); 7] = [
@@ -1,10 +1,12 @@
 //! Auto-save functionality for Testruct Studio
 //!
-//! Provides automatic saving of documents after a configurable delay.
+//! Provides automatic saving of documents after a configurable delay, and
+//! periodic crash recovery snapshots of unsaved changes.
 
 use crate::app::AppState;
+use crate::io::recovery;
 use gtk4::glib;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// Default auto-save delay in seconds
 pub const DEFAULT_AUTO_SAVE_DELAY_SECS: u64 = 30;
@@ -53,7 +55,7 @@ fn perform_auto_save(state: &AppState) {
     };
 
     // Get the active document
-    let document = match state.active_document() {
+    let document = match state.document_to_save() {
         Some(doc) => doc,
         None => {
             tracing::warn!("Auto-save skipped: no active document");
@@ -64,8 +66,9 @@ fn perform_auto_save(state: &AppState) {
     // Perform the save
     match crate::io::file_io::save_document(&document, &path) {
         Ok(_) => {
-            // Mark as saved (this also updates window title)
-            state.mark_as_saved(path.clone());
+            // Mark as saved (this also updates window title) without
+            // touching the recent files list
+            state.mark_as_auto_saved();
             // Clear the last modified time to prevent immediate re-save
             state.clear_last_modified_time();
             tracing::info!("💾 Auto-saved: {}", path.display());
@@ -76,6 +79,49 @@ fn perform_auto_save(state: &AppState) {
     }
 }
 
+/// Start the crash recovery timer
+///
+/// Every `ProjectSettings::recovery_interval_secs` seconds, a document with
+/// changes made since the previous snapshot is written to the recovery
+/// file (see [`recovery::autosave_path`]). The interval is re-read each
+/// tick, so changes in the settings dialog apply without a restart.
+pub fn start_recovery_timer(state: AppState) -> glib::SourceId {
+    let mut last_snapshot = Instant::now();
+    glib::timeout_add_local(Duration::from_secs(1), move || {
+        let interval = state.with_project(|project| project.settings.recovery_interval_secs);
+        if interval == 0 || last_snapshot.elapsed().as_secs() < interval as u64 {
+            return glib::ControlFlow::Continue;
+        }
+
+        let changed_since_snapshot = state.is_modified()
+            && state
+                .last_modified_time()
+                .is_some_and(|modified| modified > last_snapshot);
+        last_snapshot = Instant::now();
+        if changed_since_snapshot {
+            write_recovery_snapshot(&state);
+        }
+        glib::ControlFlow::Continue
+    })
+}
+
+/// Write the active document to the recovery file
+fn write_recovery_snapshot(state: &AppState) {
+    let Some(path) = recovery::autosave_path() else {
+        tracing::debug!("Recovery snapshot skipped: no documents directory");
+        return;
+    };
+    let Some(document) = state.document_to_save() else {
+        return;
+    };
+
+    let source = state.current_file_path();
+    match recovery::write_snapshot(&path, &document, source.as_deref()) {
+        Ok(()) => tracing::info!("💾 Recovery snapshot saved: {}", path.display()),
+        Err(e) => tracing::error!("❌ Recovery snapshot failed: {:#}", e),
+    }
+}
+
 /// Stop the auto-save timer
 ///
 /// # Arguments
@@ -0,0 +1,357 @@
+//! Keyboard shortcut bindings
+//!
+//! Maps window actions to accelerator strings (such as `<Primary>s`). User
+//! remappings are persisted to disk; actions without a remapping keep their
+//! default shortcut.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Remappable actions: action name, label shown in the editor, default accelerator
+pub const DEFAULT_BINDINGS: &[(&str, &str, &str)] = &[
+    // File
+    ("win.new", "新規作成", "<Primary>n"),
+    ("win.open", "ファイルを開く", "<Primary>o"),
+    ("win.save", "保存", "<Primary>s"),
+    ("win.save-as", "名前を付けて保存", "<Primary><Shift>s"),
+    // Edit
+    ("win.undo", "取り消し", "<Primary>z"),
+    ("win.redo", "やり直し", "<Primary><Shift>z"),
+    ("win.select-all", "すべて選択", "<Primary>a"),
+    ("win.find-replace", "検索と置換", "<Primary>f"),
+    (
+        "win.insert-special-character",
+        "特殊文字を挿入",
+        "<Primary><Shift>k",
+    ),
+    ("win.cut", "切り取り", "<Primary>x"),
+    ("win.copy", "コピー", "<Primary>c"),
+    ("win.paste", "貼り付け", "<Primary>v"),
+    ("win.paste-in-place", "同じ位置に貼り付け", "<Primary><Shift>v"),
+    ("win.duplicate", "複製", "<Primary>d"),
+    ("win.group", "グループ化", "<Primary>g"),
+    ("win.ungroup", "グループ解除", "<Primary><Shift>g"),
+    // Z-order
+    (
+        "win.bring-to-front",
+        "最前面へ移動",
+        "<Primary><Shift>bracketright",
+    ),
+    ("win.bring-forward", "前面へ移動", "<Primary>bracketright"),
+    ("win.send-backward", "背面へ移動", "<Primary>bracketleft"),
+    (
+        "win.send-to-back",
+        "最背面へ移動",
+        "<Primary><Shift>bracketleft",
+    ),
+    // Pages
+    ("win.add-page", "ページを追加", "<Primary><Shift>n"),
+    ("win.delete-page", "ページを削除", "<Primary><Shift>d"),
+    ("win.duplicate-page", "ページを複製", "<Primary><Alt>d"),
+    (
+        "win.move-page-up",
+        "ページを上へ",
+        "<Primary><Shift>Page_Up",
+    ),
+    (
+        "win.move-page-down",
+        "ページを下へ",
+        "<Primary><Shift>Page_Down",
+    ),
+    ("win.insert-image", "画像を挿入", "<Primary>i"),
+    (
+        "win.save-template",
+        "テンプレートとして保存",
+        "<Primary><Alt>s",
+    ),
+    // View
+    ("win.toggle-grid", "グリッド表示切替", "F8"),
+    ("win.toggle-guides", "ガイド表示切替", "F7"),
+    ("win.toggle-rulers", "ルーラー表示切替", "F6"),
+    ("win.zoom-in", "ズームイン", "<Primary>equal"),
+    ("win.zoom-out", "ズームアウト", "<Primary>minus"),
+    ("win.zoom-100", "100% 表示", "<Primary>0"),
+    ("win.zoom-fit-window", "ウィンドウに合わせる", "<Primary>1"),
+    ("win.zoom-fit-selection", "選択範囲に合わせる", "<Primary>2"),
+    // Alignment
+    ("win.align-left", "左揃え", "<Primary><Shift>l"),
+    ("win.align-center-h", "左右中央揃え", "<Primary><Shift>c"),
+    ("win.align-right", "右揃え", "<Primary><Shift>r"),
+    ("win.align-top", "上揃え", "<Primary><Shift>t"),
+    ("win.align-center-v", "上下中央揃え", "<Primary><Shift>m"),
+    ("win.align-bottom", "下揃え", "<Primary><Shift>b"),
+    ("win.distribute-h", "水平方向に分布", "<Primary><Alt>h"),
+    ("win.distribute-v", "垂直方向に分布", "<Primary><Alt>v"),
+    // Help
+    ("win.keyboard-shortcuts", "キーボードショートカット", "F1"),
+];
+
+/// User remappings of keyboard shortcuts
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyBindings {
+    /// Accelerator per remapped action (an empty string unbinds the action)
+    pub bindings: BTreeMap<String, String>,
+}
+
+impl KeyBindings {
+    /// Create bindings that use every default shortcut
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load key bindings from disk
+    pub fn load() -> Self {
+        if let Some(path) = Self::config_path() {
+            if path.exists() {
+                match std::fs::read_to_string(&path) {
+                    Ok(contents) => match serde_json::from_str::<Self>(&contents) {
+                        Ok(bindings) => return bindings,
+                        Err(e) => {
+                            tracing::warn!("Failed to parse key bindings: {}", e);
+                        }
+                    },
+                    Err(e) => {
+                        tracing::warn!("Failed to read key bindings: {}", e);
+                    }
+                }
+            }
+        }
+        Self::new()
+    }
+
+    /// Save key bindings to disk
+    pub fn save(&self) {
+        if let Some(path) = Self::config_path() {
+            // Create parent directory if needed
+            if let Some(parent) = path.parent() {
+                if !parent.exists() {
+                    if let Err(e) = std::fs::create_dir_all(parent) {
+                        tracing::warn!("Failed to create config directory: {}", e);
+                        return;
+                    }
+                }
+            }
+
+            match serde_json::to_string_pretty(self) {
+                Ok(contents) => {
+                    if let Err(e) = std::fs::write(&path, contents) {
+                        tracing::warn!("Failed to save key bindings: {}", e);
+                    } else {
+                        tracing::debug!("Key bindings saved to {}", path.display());
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to serialize key bindings: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Default accelerator for `action`
+    pub fn default_accelerator(action: &str) -> Option<&'static str> {
+        DEFAULT_BINDINGS
+            .iter()
+            .find(|(name, _, _)| *name == action)
+            .map(|(_, _, accel)| *accel)
+    }
+
+    /// Accelerator for `action`, falling back to its default
+    ///
+    /// Returns `None` when the action is unbound.
+    pub fn accelerator(&self, action: &str) -> Option<&str> {
+        let accel = match self.bindings.get(action) {
+            Some(accel) => accel.as_str(),
+            None => Self::default_accelerator(action)?,
+        };
+        (!accel.is_empty()).then_some(accel)
+    }
+
+    /// Bind `action` to `accel` (empty to unbind)
+    ///
+    /// Binding an action back to its default drops the remapping.
+    pub fn set(&mut self, action: &str, accel: &str) {
+        if Self::default_accelerator(action) == Some(accel) {
+            self.bindings.remove(action);
+        } else {
+            self.bindings.insert(action.to_string(), accel.to_string());
+        }
+    }
+
+    /// Restore the default shortcut of `action`
+    pub fn reset(&mut self, action: &str) {
+        self.bindings.remove(action);
+    }
+
+    /// Every remappable action with its effective accelerator (empty if unbound)
+    pub fn effective(&self) -> Vec<(String, String)> {
+        let mut actions: Vec<&str> = DEFAULT_BINDINGS.iter().map(|(name, _, _)| *name).collect();
+        for action in self.bindings.keys() {
+            if !actions.contains(&action.as_str()) {
+                actions.push(action);
+            }
+        }
+        actions
+            .into_iter()
+            .map(|action| {
+                let accel = self.accelerator(action).unwrap_or_default();
+                (action.to_string(), accel.to_string())
+            })
+            .collect()
+    }
+
+    /// Other actions already bound to `accel`
+    pub fn conflicts_with(&self, action: &str, accel: &str) -> Vec<String> {
+        let Some(key) = normalize_accelerator(accel) else {
+            return Vec::new();
+        };
+        self.effective()
+            .into_iter()
+            .filter(|(other, other_accel)| {
+                other != action && normalize_accelerator(other_accel).as_ref() == Some(&key)
+            })
+            .map(|(other, _)| other)
+            .collect()
+    }
+
+    /// Groups of actions sharing the same accelerator
+    pub fn conflicts(&self) -> Vec<Vec<String>> {
+        let mut by_key: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (action, accel) in self.effective() {
+            if let Some(key) = normalize_accelerator(&accel) {
+                by_key.entry(key).or_default().push(action);
+            }
+        }
+        by_key
+            .into_values()
+            .filter(|actions| actions.len() > 1)
+            .collect()
+    }
+
+    /// Apply the effective bindings to the application
+    pub fn apply(&self, app: &gtk4::Application) {
+        use gtk4::prelude::GtkApplicationExt;
+
+        for (action, accel) in self.effective() {
+            if accel.is_empty() {
+                app.set_accels_for_action(&action, &[]);
+            } else {
+                app.set_accels_for_action(&action, &[accel.as_str()]);
+            }
+        }
+    }
+
+    /// Get config file path
+    fn config_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|mut p| {
+            p.push("testruct");
+            p.push("key_bindings.json");
+            p
+        })
+    }
+}
+
+/// Comparable form of an accelerator string
+///
+/// Modifiers are sorted and `<Control>`/`<Ctrl>` are treated as `<Primary>`,
+/// so `<Shift><Control>S` and `<Primary><Shift>s` compare equal. Returns
+/// `None` for an empty accelerator.
+pub fn normalize_accelerator(accel: &str) -> Option<String> {
+    let mut rest = accel.trim();
+    let mut modifiers = Vec::new();
+    while let Some(stripped) = rest.strip_prefix('<') {
+        let end = stripped.find('>')?;
+        let modifier = match stripped[..end].to_lowercase().as_str() {
+            "control" | "ctrl" | "primary" => "primary".to_string(),
+            "mod1" => "alt".to_string(),
+            other => other.to_string(),
+        };
+        modifiers.push(modifier);
+        rest = &stripped[end + 1..];
+    }
+    if rest.is_empty() {
+        return None;
+    }
+    modifiers.sort();
+    modifiers.dedup();
+    let mut key: String = modifiers.iter().map(|m| format!("<{}>", m)).collect();
+    key.push_str(&rest.to_lowercase());
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unmapped_actions_use_defaults() {
+        let mut bindings = KeyBindings::new();
+        assert_eq!(bindings.accelerator("win.save"), Some("<Primary>s"));
+        assert_eq!(bindings.accelerator("win.unknown"), None);
+
+        bindings.set("win.save", "<Primary><Alt>s");
+        assert_eq!(bindings.accelerator("win.save"), Some("<Primary><Alt>s"));
+        assert_eq!(bindings.accelerator("win.open"), Some("<Primary>o"));
+
+        bindings.set("win.open", "");
+        assert_eq!(bindings.accelerator("win.open"), None);
+
+        // Rebinding to the default drops the remapping
+        bindings.set("win.save", "<Primary>s");
+        bindings.reset("win.open");
+        assert!(bindings.bindings.is_empty());
+    }
+
+    #[test]
+    fn test_conflict_detection() {
+        let mut bindings = KeyBindings::new();
+        assert_eq!(
+            bindings.conflicts_with("win.new", "<Control>S"),
+            vec!["win.save".to_string()]
+        );
+        assert!(bindings.conflicts_with("win.save", "<Primary>s").is_empty());
+        assert!(bindings.conflicts_with("win.new", "").is_empty());
+
+        bindings.set("win.zoom-in", "F8");
+        assert!(bindings.conflicts().contains(&vec![
+            "win.toggle-grid".to_string(),
+            "win.zoom-in".to_string()
+        ]));
+    }
+
+    #[test]
+    fn test_normalize_accelerator() {
+        assert_eq!(
+            normalize_accelerator("<Shift><Control>S"),
+            normalize_accelerator("<Primary><Shift>s")
+        );
+        assert_ne!(
+            normalize_accelerator("<Primary>s"),
+            normalize_accelerator("<Primary><Shift>s")
+        );
+        assert_eq!(normalize_accelerator(""), None);
+        assert_eq!(normalize_accelerator("<Primary>"), None);
+    }
+
+    #[test]
+    fn test_default_bindings_do_not_conflict() {
+        let mut seen = std::collections::HashMap::new();
+        for (action, _, accel) in DEFAULT_BINDINGS {
+            let key = normalize_accelerator(accel).expect("default accelerator is valid");
+            if let Some(other) = seen.insert(key, action) {
+                panic!("{} and {} share {}", other, action, accel);
+            }
+        }
+    }
+
+    #[test]
+    fn test_serialization_round_trip() {
+        let mut bindings = KeyBindings::new();
+        bindings.set("win.find-replace", "<Primary>h");
+        let json = serde_json::to_string(&bindings).unwrap();
+        assert_eq!(
+            serde_json::from_str::<KeyBindings>(&json).unwrap(),
+            bindings
+        );
+    }
+}
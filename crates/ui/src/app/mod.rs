@@ -1,12 +1,14 @@
 mod actions;
 pub mod auto_save;
+pub mod key_bindings;
 mod recent_files;
 mod state;
 
 use crate::window::MainWindow;
 use gtk4::{gio, glib, prelude::*, Application};
 
-pub use auto_save::{start_auto_save_timer, DEFAULT_AUTO_SAVE_DELAY_SECS};
+pub use auto_save::{start_auto_save_timer, start_recovery_timer, DEFAULT_AUTO_SAVE_DELAY_SECS};
+pub use key_bindings::KeyBindings;
 pub use recent_files::RecentFiles;
 pub use state::AppState;
 
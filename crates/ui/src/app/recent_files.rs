@@ -1,6 +1,7 @@
 //! Recent files management
 //!
-//! Tracks recently opened files and persists them to disk.
+//! Tracks recently opened and saved files and persists them to disk.
+//! Entries whose files have been deleted or moved are dropped on load.
 
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -26,8 +27,13 @@ impl RecentFiles {
         if let Some(path) = Self::config_path() {
             if path.exists() {
                 match std::fs::read_to_string(&path) {
-                    Ok(contents) => match serde_json::from_str(&contents) {
-                        Ok(recent) => return recent,
+                    Ok(contents) => match serde_json::from_str::<Self>(&contents) {
+                        Ok(mut recent) => {
+                            if recent.remove_missing() {
+                                recent.save();
+                            }
+                            return recent;
+                        }
                         Err(e) => {
                             tracing::warn!("Failed to parse recent files: {}", e);
                         }
@@ -87,6 +93,15 @@ impl RecentFiles {
         self.save();
     }
 
+    /// Drop entries whose files no longer exist
+    ///
+    /// Returns `true` if any entry was removed. The list is not saved.
+    pub fn remove_missing(&mut self) -> bool {
+        let before = self.files.len();
+        self.files.retain(|path| path.exists());
+        self.files.len() != before
+    }
+
     /// Clear all recent files
     pub fn clear(&mut self) {
         self.files.clear();
@@ -132,4 +147,18 @@ mod tests {
         recent.files.truncate(MAX_RECENT_FILES);
         assert_eq!(recent.files.len(), MAX_RECENT_FILES);
     }
+
+    #[test]
+    fn test_remove_missing_keeps_existing_files_in_order() {
+        let manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+        let mut recent = RecentFiles::new();
+        recent
+            .files
+            .push(PathBuf::from("/nonexistent/testruct/missing.json"));
+        recent.files.push(manifest.clone());
+
+        assert!(recent.remove_missing());
+        assert_eq!(recent.files, vec![manifest]);
+        assert!(!recent.remove_missing());
+    }
 }
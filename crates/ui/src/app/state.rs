@@ -1,12 +1,16 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::rc::{Rc, Weak};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
+use crate::app::key_bindings::KeyBindings;
 use crate::app::recent_files::RecentFiles;
 use crate::undo_redo::UndoRedoStack;
 use gtk4::glib::WeakRef;
-use gtk4::prelude::GtkWindowExt;
+use gtk4::prelude::{GtkWindowExt, WidgetExt};
 use gtk4::ApplicationWindow;
+use testruct_core::document::ViewState;
 use testruct_core::workspace::assets::AssetCatalog;
 use testruct_core::{Document, DocumentId, Project};
 use testruct_db::ItemBank;
@@ -21,29 +25,37 @@ impl Default for AppState {
         // Initialize in-memory database for item bank
         let item_bank = ItemBank::memory().expect("Failed to initialize item bank");
 
+        let project = Project::default();
+        let undo_redo_stack = UndoRedoStack::with_capacity(project.settings.max_history);
+
         let app_state = Self {
             inner: Arc::new(Mutex::new(AppShared {
-                project: Project::default(),
+                project,
                 active_document: None,
                 active_page_index: 0,
-                undo_redo_stack: Arc::new(Mutex::new(crate::undo_redo::UndoRedoStack::default())),
+                undo_redo_stack: Arc::new(Mutex::new(undo_redo_stack)),
                 item_bank: Arc::new(Mutex::new(item_bank)),
                 asset_catalog: Arc::new(Mutex::new(AssetCatalog::new())),
                 window: None,
                 recent_files: RecentFiles::load(),
+                key_bindings: KeyBindings::load(),
                 current_file_path: None,
                 is_modified: false,
                 auto_save_enabled: true,
                 last_modified_time: None,
+                page_revisions: HashMap::new(),
+                pending_edit_flushes: Vec::new(),
+                view_state_source: None,
             })),
         };
 
         // Initialize with a default document
         {
             let mut inner = app_state.inner.lock().expect("state");
+            let first_page = inner.project.settings.new_page();
             let doc = testruct_core::document::DocumentBuilder::new()
                 .with_title("Untitled")
-                .add_page(testruct_core::document::Page::empty())
+                .add_page(first_page)
                 .build()
                 .expect("document");
 
@@ -112,21 +124,65 @@ impl AppState {
         self.with_undo_stack(|stack| stack.can_redo())
     }
 
+    /// Register a hook that pushes an edit still being coalesced
+    ///
+    /// Controls that group several changes into one undo step (such as the
+    /// property sliders) register here, so undo and redo act on the finished
+    /// step rather than on the command before it.
+    ///
+    /// Only a weak reference is kept, so the hook can hold this state without
+    /// keeping itself alive; it is dropped together with the control's `Rc`.
+    pub fn register_pending_edit(&self, flush: &Rc<dyn Fn()>) {
+        let mut inner = self.inner.lock().expect("state");
+        inner
+            .pending_edit_flushes
+            .retain(|hook| hook.strong_count() > 0);
+        inner.pending_edit_flushes.push(Rc::downgrade(flush));
+    }
+
+    /// Push every edit still being coalesced onto the undo stack
+    pub fn flush_pending_edits(&self) {
+        // The hooks push commands through this state, so run them unlocked
+        let flushes = {
+            let inner = self.inner.lock().expect("state");
+            inner.pending_edit_flushes.clone()
+        };
+        for flush in flushes.iter().filter_map(Weak::upgrade) {
+            flush();
+        }
+    }
+
+    /// Undo the last command, marking the document as modified on success
+    ///
+    /// Pending coalesced edits are pushed first, so they are what gets undone.
     pub fn undo(&self) -> bool {
-        self.with_undo_stack(|stack| stack.undo())
+        self.flush_pending_edits();
+        // Commands edit the document through this state, so the state lock
+        // must not be held while they run
+        let stack = self.undo_redo_stack();
+        let undone = stack.lock().expect("undo stack").undo();
+        if undone {
+            self.mark_as_modified();
+        }
+        undone
     }
 
+    /// Redo the last undone command, marking the document as modified on success
     pub fn redo(&self) -> bool {
-        self.with_undo_stack(|stack| stack.redo())
+        self.flush_pending_edits();
+        let stack = self.undo_redo_stack();
+        let redone = stack.lock().expect("undo stack").redo();
+        if redone {
+            self.mark_as_modified();
+        }
+        redone
     }
 
+    /// Record an executed command and mark the document as modified
     pub fn push_command(&self, command: Box<dyn crate::undo_redo::Command>) {
-        let stack = {
-            let inner = self.inner.lock().expect("state");
-            inner.undo_redo_stack.clone()
-        };
-        let mut undo_stack = stack.lock().expect("undo stack");
-        undo_stack.push(command);
+        let stack = self.undo_redo_stack();
+        stack.lock().expect("undo stack").push(command);
+        self.mark_as_modified();
     }
 
     pub fn add_element_to_active_page(
@@ -138,7 +194,7 @@ impl AppState {
             let page_index = inner.active_page_index;
             if let Some(doc) = inner.project.document_mut(doc_id) {
                 if page_index < doc.pages.len() {
-                    doc.pages[page_index].add_element(element);
+                    doc.pages[page_index].add_element_to_active_layer(element);
                     return Ok(());
                 }
             }
@@ -157,11 +213,14 @@ impl AppState {
     }
 
     /// Add a new page to the active document
+    ///
+    /// The page gets the project's default background.
     pub fn add_page(&self) -> Result<(), String> {
         let mut inner = self.inner.lock().expect("state");
+        let page = inner.project.settings.new_page();
         if let Some(doc_id) = inner.active_document {
             if let Some(doc) = inner.project.document_mut(doc_id) {
-                doc.pages.push(testruct_core::document::Page::empty());
+                doc.pages.push(page);
                 return Ok(());
             }
         }
@@ -169,12 +228,19 @@ impl AppState {
     }
 
     /// Delete a page from the active document by index
+    ///
+    /// The active page index is clamped so it never points past the last page.
     pub fn delete_page(&self, index: usize) -> Result<(), String> {
         let mut inner = self.inner.lock().expect("state");
+        let active = inner.active_page_index;
         if let Some(doc_id) = inner.active_document {
             if let Some(doc) = inner.project.document_mut(doc_id) {
                 if index < doc.pages.len() && doc.pages.len() > 1 {
                     doc.pages.remove(index);
+                    let last = doc.pages.len() - 1;
+                    // Stay on the same page when an earlier page is removed
+                    let active = if index < active { active - 1 } else { active };
+                    inner.active_page_index = active.min(last);
                     return Ok(());
                 }
                 return Err("Cannot delete the only page".to_string());
@@ -184,13 +250,15 @@ impl AppState {
     }
 
     /// Duplicate a page in the active document
+    ///
+    /// The copy gets fresh page and element ids and is inserted after the original.
     pub fn duplicate_page(&self, index: usize) -> Result<(), String> {
         let mut inner = self.inner.lock().expect("state");
         if let Some(doc_id) = inner.active_document {
             if let Some(doc) = inner.project.document_mut(doc_id) {
                 if index < doc.pages.len() {
-                    let page_clone = doc.pages[index].clone();
-                    doc.pages.insert(index + 1, page_clone);
+                    let page_id = doc.pages[index].id;
+                    doc.duplicate_page(page_id);
                     return Ok(());
                 }
                 return Err("Page index out of bounds".to_string());
@@ -199,6 +267,33 @@ impl AppState {
         Err("No active document".to_string())
     }
 
+    /// Move a page to a new position in the active document
+    ///
+    /// The active page index follows the page the user was looking at.
+    /// Returns the final index of the moved page (targets are clamped).
+    pub fn move_page(&self, from: usize, to: usize) -> Result<usize, String> {
+        let mut inner = self.inner.lock().expect("state");
+        let active = inner.active_page_index;
+        if let Some(doc_id) = inner.active_document {
+            if let Some(doc) = inner.project.document_mut(doc_id) {
+                let Some(new_index) = doc.move_page(from, to) else {
+                    return Err("Page not moved".to_string());
+                };
+                inner.active_page_index = if active == from {
+                    new_index
+                } else if from < active && active <= new_index {
+                    active - 1
+                } else if new_index <= active && active < from {
+                    active + 1
+                } else {
+                    active
+                };
+                return Ok(new_index);
+            }
+        }
+        Err("No active document".to_string())
+    }
+
     /// Move a page up in the active document
     pub fn move_page_up(&self, index: usize) -> Result<(), String> {
         let mut inner = self.inner.lock().expect("state");
@@ -264,6 +359,17 @@ impl AppState {
         Err("No active document".to_string())
     }
 
+    /// Pull the active page index back inside the page range after pages are removed
+    pub fn clamp_active_page_index(&self) {
+        let mut inner = self.inner.lock().expect("state");
+        let page_count = inner
+            .active_document
+            .and_then(|id| inner.project.document(id))
+            .map(|doc| doc.pages.len())
+            .unwrap_or(0);
+        inner.active_page_index = inner.active_page_index.min(page_count.saturating_sub(1));
+    }
+
     /// Get a reference to the active page (read-only)
     pub fn active_page(&self) -> Option<testruct_core::document::Page> {
         let inner = self.inner.lock().expect("state");
@@ -277,6 +383,15 @@ impl AppState {
         None
     }
 
+    /// Size of the active page, honouring the page's own size override
+    pub fn active_page_size(&self) -> Option<testruct_core::layout::Size> {
+        let inner = self.inner.lock().expect("state");
+        let doc = inner.project.document(inner.active_document?)?;
+        doc.pages
+            .get(inner.active_page_index)
+            .map(|page| doc.page_size_of(page))
+    }
+
     /// Execute a function on the active page (mutable access)
     pub fn with_active_page<F, R>(&self, f: F) -> Option<R>
     where
@@ -294,22 +409,13 @@ impl AppState {
         None
     }
 
-    /// Get all object IDs in the active page of the active document
-    pub fn get_all_object_ids(&self) -> Vec<uuid::Uuid> {
-        if let Some(page) = self.active_page() {
-            page.elements
-                .iter()
-                .map(|element| match element {
-                    testruct_core::document::DocumentElement::Shape(shape) => shape.id,
-                    testruct_core::document::DocumentElement::Text(text) => text.id,
-                    testruct_core::document::DocumentElement::Image(image) => image.id,
-                    testruct_core::document::DocumentElement::Frame(frame) => frame.id,
-                    testruct_core::document::DocumentElement::Group(group) => group.id,
-                })
-                .collect()
-        } else {
-            Vec::new()
-        }
+    /// Ids selected by "select all" on the active page
+    ///
+    /// Locked elements are left out; see `Page::unlocked_element_ids`.
+    pub fn selectable_object_ids(&self) -> Vec<uuid::Uuid> {
+        self.active_page()
+            .map(|page| page.unlocked_element_ids())
+            .unwrap_or_default()
     }
 
     /// Set the main window reference
@@ -348,10 +454,18 @@ impl AppState {
         let doc_id = document.id;
         inner.project.add_document(document);
         inner.active_document = Some(doc_id);
+        inner.active_page_index = 0;
     }
 
     /// Replace the active document with a new one (for loading documents)
-    pub fn set_active_document(&self, document: Document) {
+    ///
+    /// Auto-resize text boxes are refit first so they match this machine's
+    /// fonts from the very first render.
+    pub fn set_active_document(&self, mut document: Document) {
+        for page in &mut document.pages {
+            crate::canvas::rendering::fit_text_heights(&mut page.elements);
+        }
+
         let mut inner = self.inner.lock().expect("state");
         let doc_id = document.id;
 
@@ -363,24 +477,95 @@ impl AppState {
         // Add new document and set as active
         inner.project.add_document(document);
         inner.active_document = Some(doc_id);
+        inner.active_page_index = 0;
     }
 
-    /// Add a file to the recent files list
-    pub fn add_recent_file(&self, path: PathBuf) {
+    /// Record the canvas view settings on the active document
+    ///
+    /// Stored in the document metadata so the next save persists them. A
+    /// view change alone does not mark the document as modified.
+    pub fn set_view_state(&self, view: ViewState) {
+        self.with_active_document(|doc| {
+            if doc.metadata.view.as_ref() != Some(&view) {
+                doc.metadata.view = Some(view);
+            }
+        });
+    }
+
+    /// Register where the current canvas view settings come from
+    ///
+    /// They are read when the document is saved (see [`Self::document_to_save`])
+    /// rather than on every zoom or pan.
+    pub fn set_view_state_source(&self, source: impl Fn() -> ViewState + 'static) {
         let mut inner = self.inner.lock().expect("state");
-        inner.recent_files.add_file(path);
+        inner.view_state_source = Some(Rc::new(source));
+    }
+
+    /// The active document with the current canvas view recorded on it
+    pub fn document_to_save(&self) -> Option<Document> {
+        let source = self.inner.lock().expect("state").view_state_source.clone();
+        if let Some(source) = source {
+            self.set_view_state(source());
+        }
+        self.active_document()
+    }
+
+    /// Add a file to the recent files list (most recent first)
+    pub fn add_recent_file(&self, path: PathBuf) {
+        {
+            let mut inner = self.inner.lock().expect("state");
+            inner.recent_files.add_file(path);
+        }
+        self.notify_recent_files_changed();
     }
 
     /// Get a copy of the recent files list
+    ///
+    /// Entries whose files no longer exist are removed first.
     pub fn recent_files(&self) -> Vec<PathBuf> {
-        let inner = self.inner.lock().expect("state");
+        let mut inner = self.inner.lock().expect("state");
+        if inner.recent_files.remove_missing() {
+            inner.recent_files.save();
+        }
         inner.recent_files.files.clone()
     }
 
+    /// Remove a file from the recent files list
+    pub fn remove_recent_file(&self, path: &std::path::Path) {
+        {
+            let mut inner = self.inner.lock().expect("state");
+            inner.recent_files.remove_file(&path.to_path_buf());
+        }
+        self.notify_recent_files_changed();
+    }
+
     /// Clear the recent files list
     pub fn clear_recent_files(&self) {
-        let mut inner = self.inner.lock().expect("state");
-        inner.recent_files.clear();
+        {
+            let mut inner = self.inner.lock().expect("state");
+            inner.recent_files.clear();
+        }
+        self.notify_recent_files_changed();
+    }
+
+    /// Get a copy of the keyboard shortcut bindings
+    pub fn key_bindings(&self) -> KeyBindings {
+        self.inner.lock().expect("state").key_bindings.clone()
+    }
+
+    /// Replace the keyboard shortcut bindings and save them to disk
+    ///
+    /// The caller applies them to the application with [`KeyBindings::apply`].
+    pub fn set_key_bindings(&self, bindings: KeyBindings) {
+        bindings.save();
+        self.inner.lock().expect("state").key_bindings = bindings;
+    }
+
+    /// Ask the window to rebuild its Open Recent menu
+    fn notify_recent_files_changed(&self) {
+        if let Some(window) = self.window() {
+            let _ = window.activate_action("win.recent-files-changed", None);
+        }
     }
 
     // ========== File path and modification tracking ==========
@@ -410,16 +595,29 @@ impl AppState {
     }
 
     /// Mark the document as saved (sets modified to false and optionally updates the file path)
+    ///
+    /// Called after every successful save or load, so it also moves the file
+    /// to the front of the recent files list.
     pub fn mark_as_saved(&self, path: PathBuf) {
         {
             let mut inner = self.inner.lock().expect("state");
             inner.is_modified = false;
-            inner.current_file_path = Some(path);
+            inner.current_file_path = Some(path.clone());
         }
+        self.add_recent_file(path);
         self.update_window_title();
         tracing::info!("📁 Document marked as saved");
     }
 
+    /// Mark the document as saved by auto-save
+    ///
+    /// Auto-save writes to the current file, so unlike [`Self::mark_as_saved`]
+    /// this leaves the recent files list alone.
+    pub fn mark_as_auto_saved(&self) {
+        self.inner.lock().expect("state").is_modified = false;
+        self.update_window_title();
+    }
+
     /// Clear document state (for new document)
     pub fn clear_document_state(&self) {
         {
@@ -431,15 +629,39 @@ impl AppState {
     }
 
     /// Mark document as modified and update window title
+    ///
+    /// Edits always target the active page, so its content revision is bumped too.
     pub fn mark_as_modified(&self) {
         {
             let mut inner = self.inner.lock().expect("state");
             inner.is_modified = true;
             inner.last_modified_time = Some(Instant::now());
+            let active_page_id = inner
+                .active_document
+                .and_then(|id| inner.project.document(id))
+                .and_then(|doc| doc.pages.get(inner.active_page_index))
+                .map(|page| page.id);
+            if let Some(page_id) = active_page_id {
+                *inner.page_revisions.entry(page_id).or_insert(0) += 1;
+            }
         }
         self.update_window_title();
     }
 
+    // ========== Page revisions ==========
+
+    /// Current content revision of a page (0 if never edited)
+    pub fn page_revision(&self, page_id: testruct_core::document::PageId) -> u64 {
+        let inner = self.inner.lock().expect("state");
+        inner.page_revisions.get(&page_id).copied().unwrap_or(0)
+    }
+
+    /// Mark a page's content as changed so cached previews are regenerated
+    pub fn bump_page_revision(&self, page_id: testruct_core::document::PageId) {
+        let mut inner = self.inner.lock().expect("state");
+        *inner.page_revisions.entry(page_id).or_insert(0) += 1;
+    }
+
     // ========== Auto-save management ==========
 
     /// Check if auto-save is enabled
@@ -529,6 +751,8 @@ struct AppShared {
     asset_catalog: Arc<Mutex<AssetCatalog>>,
     window: Option<WeakRef<ApplicationWindow>>,
     recent_files: RecentFiles,
+    /// User remappings of keyboard shortcuts
+    key_bindings: KeyBindings,
     /// Current file path for the active document (None if unsaved)
     current_file_path: Option<PathBuf>,
     /// Whether the document has been modified since last save
@@ -537,4 +761,10 @@ struct AppShared {
     auto_save_enabled: bool,
     /// Time of last modification (for auto-save timer)
     last_modified_time: Option<Instant>,
+    /// Per-page content revision, bumped whenever a page is edited
+    page_revisions: HashMap<testruct_core::document::PageId, u64>,
+    /// Hooks that push edits still being coalesced into one undo step
+    pending_edit_flushes: Vec<Weak<dyn Fn()>>,
+    /// Current canvas view settings, recorded on the document when saving
+    view_state_source: Option<Rc<dyn Fn() -> ViewState>>,
 }
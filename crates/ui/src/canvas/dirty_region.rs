@@ -2,10 +2,21 @@
 //!
 //! Tracks which areas of the canvas have changed and need to be redrawn.
 //! This allows for efficient partial redraws instead of redrawing the entire canvas.
+//!
+//! GTK4 drawing areas have no `queue_draw_area`, so the canvas keeps its last
+//! frame in a [`FrameCache`] and repaints only the dirty rectangles into it.
+//! A frame queued without any dirty rectangle is a full redraw.
 
+use gtk4::cairo::{self, Format, ImageSurface};
 use std::cell::RefCell;
 use std::rc::Rc;
-use testruct_core::layout::Rect;
+use testruct_core::layout::{Point, Rect, Size};
+
+/// Padding added around dirty rectangles, in widget pixels
+///
+/// Covers selection handles, the rotation handle and stroke overhang so a
+/// partial redraw never leaves stale pixels along an element's edge.
+pub const DIRTY_MARGIN: f64 = 32.0;
 
 /// Represents a region that needs to be redrawn
 #[derive(Clone, Debug, Default)]
@@ -119,6 +130,76 @@ impl DirtyRegion {
     }
 }
 
+/// Square covering `bounds` at any rotation about its center
+pub fn rotation_extent(bounds: &Rect) -> Rect {
+    let radius = bounds.size.width.hypot(bounds.size.height) / 2.0;
+    let center_x = bounds.origin.x + bounds.size.width / 2.0;
+    let center_y = bounds.origin.y + bounds.size.height / 2.0;
+    Rect::new(
+        Point::new(center_x - radius, center_y - radius),
+        Size::new(radius * 2.0, radius * 2.0),
+    )
+}
+
+/// View parameters a frame was rendered with
+///
+/// A cached frame can only be patched while all of these stay the same;
+/// zooming, panning, resizing the widget or switching pages repaints fully.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameKey {
+    pub width: i32,
+    pub height: i32,
+    pub scale: i32,
+    pub zoom: f64,
+    pub pan_x: f64,
+    pub pan_y: f64,
+    pub ruler_size: f64,
+    pub page_index: usize,
+}
+
+impl FrameKey {
+    /// Widget-space rectangle for a document-space `rect`, padded by [`DIRTY_MARGIN`]
+    pub fn to_widget(&self, rect: &Rect) -> Rect {
+        let x = rect.origin.x as f64 * self.zoom + self.ruler_size + self.pan_x - DIRTY_MARGIN;
+        let y = rect.origin.y as f64 * self.zoom + self.ruler_size + self.pan_y - DIRTY_MARGIN;
+        let width = rect.size.width as f64 * self.zoom + DIRTY_MARGIN * 2.0;
+        let height = rect.size.height as f64 * self.zoom + DIRTY_MARGIN * 2.0;
+        Rect::new(
+            Point::new(x as f32, y as f32),
+            Size::new(width as f32, height as f32),
+        )
+    }
+}
+
+/// The last rendered canvas frame
+///
+/// Kept so that a frame with dirty regions only repaints those areas
+/// instead of the whole canvas.
+pub struct FrameCache {
+    pub key: FrameKey,
+    pub surface: ImageSurface,
+    /// Widget-space extents of the transient overlays (drag box, snap lines,
+    /// readouts) drawn in this frame; the next partial frame repaints them
+    pub overlay_rects: Vec<Rect>,
+}
+
+impl FrameCache {
+    /// Allocate a surface for `key` at the widget's scale factor
+    pub fn new(key: FrameKey) -> Result<Self, cairo::Error> {
+        let surface = ImageSurface::create(
+            Format::ARgb32,
+            key.width.max(1) * key.scale,
+            key.height.max(1) * key.scale,
+        )?;
+        surface.set_device_scale(key.scale as f64, key.scale as f64);
+        Ok(Self {
+            key,
+            surface,
+            overlay_rects: Vec::new(),
+        })
+    }
+}
+
 /// Thread-safe dirty region tracker
 pub type DirtyRegionTracker = Rc<RefCell<DirtyRegion>>;
 
@@ -181,6 +262,38 @@ mod tests {
         assert!(region.get_rects().len() <= 2);
     }
 
+    #[test]
+    fn test_rotation_extent_covers_rotated_bounds() {
+        let bounds = Rect::new(Point::new(10.0, 20.0), Size::new(30.0, 40.0));
+        let extent = rotation_extent(&bounds);
+
+        // Half of the 50 unit diagonal around the center (25, 40)
+        assert_eq!(extent.origin, Point::new(0.0, 15.0));
+        assert_eq!(extent.size, Size::new(50.0, 50.0));
+    }
+
+    #[test]
+    fn test_frame_key_to_widget_applies_view_and_margin() {
+        let key = FrameKey {
+            width: 800,
+            height: 600,
+            scale: 1,
+            zoom: 2.0,
+            pan_x: 5.0,
+            pan_y: 7.0,
+            ruler_size: 20.0,
+            page_index: 0,
+        };
+        let rect = key.to_widget(&Rect::new(Point::new(10.0, 10.0), Size::new(50.0, 25.0)));
+
+        let margin = DIRTY_MARGIN as f32;
+        assert_eq!(rect.origin, Point::new(45.0 - margin, 47.0 - margin));
+        assert_eq!(
+            rect.size,
+            Size::new(100.0 + margin * 2.0, 50.0 + margin * 2.0)
+        );
+    }
+
     #[test]
     fn test_too_many_rects_triggers_full_redraw() {
         let mut region = DirtyRegion::new();
@@ -6,10 +6,112 @@ use gtk4::cairo::Context;
 use testruct_core::layout::Size;
 use testruct_core::typography::Color;
 
+/// Pixels per inch used as the canvas base unit
+const PIXELS_PER_INCH: f64 = 96.0;
+
+/// Measurement unit shown on the rulers
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RulerUnit {
+    #[default]
+    Pixels,
+    Millimeters,
+    Points,
+}
+
+impl RulerUnit {
+    /// Canvas pixels covered by one unit (96 DPI for mm, 72 DPI for points)
+    pub fn pixels_per_unit(self) -> f64 {
+        match self {
+            RulerUnit::Pixels => 1.0,
+            RulerUnit::Millimeters => PIXELS_PER_INCH / 25.4,
+            RulerUnit::Points => PIXELS_PER_INCH / 72.0,
+        }
+    }
+
+    /// Convert a canvas pixel distance to this unit
+    pub fn from_pixels(self, pixels: f64) -> f64 {
+        pixels / self.pixels_per_unit()
+    }
+
+    /// Convert a distance in this unit to canvas pixels
+    pub fn to_pixels(self, value: f64) -> f64 {
+        value * self.pixels_per_unit()
+    }
+
+    /// Tick spacing in units: (minor, medium, labelled major)
+    ///
+    /// Chosen so minor ticks stay at least ~10px apart and labels don't overlap.
+    pub fn tick_steps(self) -> (u32, u32, u32) {
+        match self {
+            RulerUnit::Pixels => (10, 50, 100),
+            RulerUnit::Millimeters => (5, 10, 50),
+            RulerUnit::Points => (10, 50, 100),
+        }
+    }
+
+    /// Short label for UI display
+    pub fn label(self) -> &'static str {
+        match self {
+            RulerUnit::Pixels => "px",
+            RulerUnit::Millimeters => "mm",
+            RulerUnit::Points => "pt",
+        }
+    }
+
+    /// Next unit in the px → mm → pt cycle
+    pub fn next(self) -> Self {
+        match self {
+            RulerUnit::Pixels => RulerUnit::Millimeters,
+            RulerUnit::Millimeters => RulerUnit::Points,
+            RulerUnit::Points => RulerUnit::Pixels,
+        }
+    }
+}
+
+/// A single ruler tick mark
+#[derive(Clone, Debug, PartialEq)]
+pub struct RulerTick {
+    /// Offset from the ruler origin in canvas pixels
+    pub offset: f64,
+    /// Tick length in pixels
+    pub length: f64,
+    /// Measurement text for labelled ticks
+    pub label: Option<String>,
+}
+
+/// Compute the tick marks covering `extent` canvas pixels in the given unit
+pub fn ruler_ticks(extent: f64, unit: RulerUnit) -> Vec<RulerTick> {
+    let (minor, medium, major) = unit.tick_steps();
+    let mut ticks = Vec::new();
+    let mut value: u32 = 0;
+    loop {
+        let offset = unit.to_pixels(value as f64);
+        if offset > extent {
+            break;
+        }
+        let (length, label) = if value.is_multiple_of(major) {
+            (10.0, (value > 0).then(|| value.to_string()))
+        } else if value.is_multiple_of(medium) {
+            (7.0, None)
+        } else {
+            (5.0, None)
+        };
+        ticks.push(RulerTick {
+            offset,
+            length,
+            label,
+        });
+        value += minor;
+    }
+    ticks
+}
+
 /// Configuration for ruler rendering
 #[derive(Clone, Debug)]
 pub struct RulerConfig {
     pub size: f64,
+    /// Unit used for tick spacing and labels
+    pub unit: RulerUnit,
     pub bg_color: Color,
     pub tick_color: Color,
     pub text_color: Color,
@@ -19,6 +121,7 @@ impl Default for RulerConfig {
     fn default() -> Self {
         Self {
             size: 20.0,
+            unit: RulerUnit::default(),
             bg_color: Color {
                 r: 0.95,
                 g: 0.95,
@@ -105,87 +208,67 @@ pub fn draw_rulers(
     ctx.set_font_size(9.0);
 
     // Horizontal ruler markings
-    let mut x = 0.0;
-    while x <= canvas_width - size {
-        let screen_x = x + size;
-        let tick_height = if (x as i32) % 100 == 0 {
-            10.0
-        } else if (x as i32) % 50 == 0 {
-            7.0
-        } else if (x as i32) % 10 == 0 {
-            5.0
-        } else {
-            0.0
-        };
-
-        if tick_height > 0.0 {
-            ctx.move_to(screen_x, size - tick_height);
-            ctx.line_to(screen_x, size);
-            ctx.stroke()?;
+    for tick in ruler_ticks(canvas_width - size, config.unit) {
+        let screen_x = tick.offset + size;
+        ctx.move_to(screen_x, size - tick.length);
+        ctx.line_to(screen_x, size);
+        ctx.stroke()?;
 
-            // Draw measurement text
-            if (x as i32) % 100 == 0 && x > 0.0 {
-                ctx.set_source_rgb(
-                    config.text_color.r as f64,
-                    config.text_color.g as f64,
-                    config.text_color.b as f64,
-                );
-                let text = format!("{}", x as i32);
-                if let Ok(extents) = ctx.text_extents(&text) {
-                    ctx.move_to(screen_x - extents.width() / 2.0, 12.0);
-                    ctx.show_text(&text)?;
-                }
-                ctx.set_source_rgb(
-                    config.tick_color.r as f64,
-                    config.tick_color.g as f64,
-                    config.tick_color.b as f64,
-                );
+        // Draw measurement text
+        if let Some(text) = &tick.label {
+            ctx.set_source_rgb(
+                config.text_color.r as f64,
+                config.text_color.g as f64,
+                config.text_color.b as f64,
+            );
+            if let Ok(extents) = ctx.text_extents(text) {
+                ctx.move_to(screen_x - extents.width() / 2.0, 12.0);
+                ctx.show_text(text)?;
             }
+            ctx.set_source_rgb(
+                config.tick_color.r as f64,
+                config.tick_color.g as f64,
+                config.tick_color.b as f64,
+            );
         }
-        x += 10.0;
     }
 
     // Vertical ruler markings
-    let mut y = 0.0;
-    while y <= canvas_height - size {
-        let screen_y = y + size;
-        let tick_width = if (y as i32) % 100 == 0 {
-            10.0
-        } else if (y as i32) % 50 == 0 {
-            7.0
-        } else if (y as i32) % 10 == 0 {
-            5.0
-        } else {
-            0.0
-        };
-
-        if tick_width > 0.0 {
-            ctx.move_to(size - tick_width, screen_y);
-            ctx.line_to(size, screen_y);
-            ctx.stroke()?;
+    for tick in ruler_ticks(canvas_height - size, config.unit) {
+        let screen_y = tick.offset + size;
+        ctx.move_to(size - tick.length, screen_y);
+        ctx.line_to(size, screen_y);
+        ctx.stroke()?;
 
-            // Draw measurement text
-            if (y as i32) % 100 == 0 && y > 0.0 {
-                ctx.set_source_rgb(
-                    config.text_color.r as f64,
-                    config.text_color.g as f64,
-                    config.text_color.b as f64,
-                );
-                let text = format!("{}", y as i32);
-                ctx.save()?;
-                ctx.move_to(6.0, screen_y + 3.0);
-                ctx.show_text(&text)?;
-                ctx.restore()?;
-                ctx.set_source_rgb(
-                    config.tick_color.r as f64,
-                    config.tick_color.g as f64,
-                    config.tick_color.b as f64,
-                );
-            }
+        // Draw measurement text
+        if let Some(text) = &tick.label {
+            ctx.set_source_rgb(
+                config.text_color.r as f64,
+                config.text_color.g as f64,
+                config.text_color.b as f64,
+            );
+            ctx.save()?;
+            ctx.move_to(6.0, screen_y + 3.0);
+            ctx.show_text(text)?;
+            ctx.restore()?;
+            ctx.set_source_rgb(
+                config.tick_color.r as f64,
+                config.tick_color.g as f64,
+                config.tick_color.b as f64,
+            );
         }
-        y += 10.0;
     }
 
+    // Unit indicator in the corner square
+    ctx.set_source_rgb(
+        config.text_color.r as f64,
+        config.text_color.g as f64,
+        config.text_color.b as f64,
+    );
+    ctx.set_font_size(7.0);
+    ctx.move_to(3.0, size - 6.0);
+    ctx.show_text(config.unit.label())?;
+
     // Translate context past rulers for content drawing
     ctx.translate(size, size);
 
@@ -202,6 +285,12 @@ pub enum GridStyle {
     Dots,
 }
 
+/// Smallest grid spacing in pixels; denser grids are unreadable and slow to draw
+pub const MIN_GRID_SPACING: f32 = 2.0;
+
+/// Largest grid spacing offered in the view options
+pub const MAX_GRID_SPACING: f32 = 200.0;
+
 /// Grid configuration
 #[derive(Clone, Debug)]
 pub struct GridConfig {
@@ -239,6 +328,15 @@ impl GridConfig {
             ..Default::default()
         }
     }
+
+    /// Spacing used for drawing and snapping, never below `MIN_GRID_SPACING`
+    pub fn effective_spacing(&self) -> f32 {
+        if self.spacing.is_finite() {
+            self.spacing.max(MIN_GRID_SPACING)
+        } else {
+            GridConfig::default().spacing
+        }
+    }
 }
 
 /// Draw the grid pattern
@@ -261,7 +359,7 @@ pub fn draw_grid_with_config(
         config.color.a as f64,
     );
 
-    let grid_spacing = config.spacing as f64;
+    let grid_spacing = config.effective_spacing() as f64;
 
     match config.style {
         GridStyle::Lines => {
@@ -336,3 +434,43 @@ pub fn draw_guides(ctx: &Context, guides: &[Guide], page_size: &Size) -> Result<
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ruler_unit_conversion() {
+        assert_eq!(RulerUnit::Pixels.to_pixels(100.0), 100.0);
+        assert!((RulerUnit::Millimeters.to_pixels(25.4) - 96.0).abs() < 1e-9);
+        assert!((RulerUnit::Points.to_pixels(72.0) - 96.0).abs() < 1e-9);
+        assert!((RulerUnit::Millimeters.from_pixels(96.0) - 25.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ruler_ticks_in_millimeters() {
+        let ticks = ruler_ticks(200.0, RulerUnit::Millimeters);
+        // 200px ≈ 52.9mm → ticks at 0, 5, ..., 50mm
+        assert_eq!(ticks.len(), 11);
+        assert!((ticks[1].offset - RulerUnit::Millimeters.to_pixels(5.0)).abs() < 1e-9);
+        assert_eq!(ticks[0].label, None);
+        assert_eq!(ticks[2].length, 7.0);
+        assert_eq!(ticks[10].label.as_deref(), Some("50"));
+    }
+
+    #[test]
+    fn test_effective_spacing_is_clamped() {
+        assert_eq!(GridConfig::with_spacing(15.0).effective_spacing(), 15.0);
+        let min = MIN_GRID_SPACING;
+        assert_eq!(GridConfig::with_spacing(0.0).effective_spacing(), min);
+        assert_eq!(GridConfig::with_spacing(-5.0).effective_spacing(), min);
+        assert_eq!(GridConfig::with_spacing(f32::NAN).effective_spacing(), 20.0);
+    }
+
+    #[test]
+    fn test_ruler_unit_cycles() {
+        let unit = RulerUnit::default();
+        assert_eq!(unit, RulerUnit::Pixels);
+        assert_eq!(unit.next().next().next(), RulerUnit::Pixels);
+    }
+}
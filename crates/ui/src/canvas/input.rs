@@ -17,7 +17,12 @@
 //! | gesture | gesture.rs | 120 | ジェスチャー統合、公開API |
 //! | gesture_click | gesture_click.rs | 337 | クリック、ダブルクリック、選択処理 |
 //! | gesture_drag | gesture_drag.rs | 359 | ドラッグ、移動、リサイズ、図形作成 |
+//! | guide_drag | guide_drag.rs | 197 | ルーラーからのガイド作成・移動・削除 |
+//! | context_menu | context_menu.rs | 250 | 右クリックのコンテキストメニュー |
+//! | character_palette | character_palette.rs | 210 | 特殊文字パレット |
 //! | mouse | mouse.rs | 88 | カーソル管理 |
+//! | spelling_menu | spelling_menu.rs | 170 | 誤字の右クリック候補メニュー |
+//! | table_edit | table_edit.rs | 270 | 表のセル編集 |
 //! | 統合 | input.rs | 110 | イベントハンドラー初期化 |
 //!
 //! # 使用例
@@ -63,22 +68,31 @@
 //!
 //! 新しい入力処理を追加する場合：
 //!
-//! 1. **キーボードショートカット追加**: `keyboard::setup_keyboard_events` に条件を追加
+//! 1. **キーボードショートカット追加**: 変更可能なキーは `win.*` アクションと
+//!    [`KeyBindings`](crate::app::KeyBindings) の既定値に、キャンバス固有のキーは
+//!    `keyboard::setup_keyboard_events` に条件を追加
 //! 2. **ジェスチャー処理追加**: `gesture::setup_click_gesture` または `gesture::setup_drag_gesture` を修正
 //! 3. **カーソル表示カスタマイズ**: `mouse::setup_mouse_tracking` でカーソル名を追加
 //!
 //! 詳細は各モジュールドキュメントを参照してください。
 
+pub mod character_palette;
 pub mod coordinates;
+mod context_menu;
 mod gesture;
 mod gesture_click;
 mod gesture_drag;
+mod guide_drag;
 pub mod ime;
 mod keyboard;
 mod keyboard_shortcuts;
 mod mouse;
+mod spelling_menu;
+mod table_edit;
 
-pub use self::keyboard_shortcuts::move_selected_objects;
+pub use self::keyboard_shortcuts::{
+    handle_cut, handle_duplicate, handle_paste, handle_paste_text_in_editing, move_selected_objects,
+};
 
 use crate::app::AppState;
 use crate::canvas::CanvasRenderState;
@@ -105,6 +119,7 @@ use std::rc::Rc;
 /// - マウス動作追跡（EventControllerMotion）
 /// - クリックジェスチャー（GestureClick）
 /// - ドラッグジェスチャー（GestureDrag）
+/// - コンテキストメニュー（右クリックの GestureClick、誤字では候補メニュー）
 /// - IME (Input Method Editor) for Japanese input
 pub fn wire_pointer_events(
     drawing_area: &DrawingArea,
@@ -120,4 +135,5 @@ pub fn wire_pointer_events(
     keyboard::setup_keyboard_events(drawing_area, render_state, app_state, ime_manager.clone());
     mouse::setup_mouse_tracking(drawing_area, render_state, app_state);
     gesture::setup_gestures(drawing_area, render_state, app_state, ime_manager, ime_entry);
+    context_menu::setup_context_menu(drawing_area, render_state, app_state, ime_entry);
 }
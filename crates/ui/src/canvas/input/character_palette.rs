@@ -0,0 +1,210 @@
+//! 特殊文字パレットモジュール
+//!
+//! テキスト編集中に ×、÷、°、分数などの記号をポップオーバーから選び、
+//! カーソル位置に挿入します。挿入はキー入力と同じ
+//! [`insert_text_at_cursor`] を通るため、選択範囲の置き換えや
+//! 文字（`char`）単位のカーソル移動も通常の入力と同じように動作します。
+//!
+//! | 分類 | 例 |
+//! |------|----|
+//! | 数学 | × ÷ ± ≠ ≦ ≧ ° ½ √ π |
+//! | 句読点・括弧 | 、。・…「」『』【】※ |
+//! | 矢印 | → ← ↑ ↓ ⇒ ⇔ |
+//! | 記号 | ○ ● △ □ ☆ ✓ ① ② |
+//!
+//! `win.insert-special-character`（既定は Ctrl+Shift+K）で開きます。
+
+use super::keyboard::text_editing_keys::insert_text_at_cursor;
+use crate::app::AppState;
+use crate::canvas::input::coordinates::document_to_widget;
+use crate::canvas::CanvasRenderState;
+use gtk4::prelude::*;
+use gtk4::{gdk, Button, DrawingArea, Entry, FlowBox, Label, Notebook, Popover};
+use testruct_core::document::DocumentElement;
+use uuid::Uuid;
+
+/// 分類ごとの文字（タブの表示名と文字の一覧）
+pub const CHARACTER_CATEGORIES: &[(&str, &[&str])] = &[
+    (
+        "数学",
+        &[
+            "×", "÷", "±", "∓", "≠", "≒", "≦", "≧", "＜", "＞", "∞", "√", "π", "°", "′", "″", "½",
+            "⅓", "⅔", "¼", "¾", "²", "³", "∠", "⊥", "∥", "∴", "∵", "∈", "∪", "∩", "Σ",
+        ],
+    ),
+    (
+        "句読点・括弧",
+        &[
+            "、", "。", "・", "…", "‥", "「", "」", "『", "』", "【", "】", "〔", "〕", "〈", "〉",
+            "《", "》", "※", "〃", "々", "ー", "—", "～", "§", "¶", "†", "‡",
+        ],
+    ),
+    (
+        "矢印",
+        &[
+            "→", "←", "↑", "↓", "↔", "↕", "⇒", "⇔", "↗", "↘", "↙", "↖", "⇨", "⇦", "⇧", "⇩",
+        ],
+    ),
+    (
+        "記号",
+        &[
+            "○", "●", "◎", "△", "▲", "□", "■", "◇", "◆", "☆", "★", "✓", "✗", "①", "②", "③", "④",
+            "⑤", "⑥", "⑦", "⑧", "⑨", "⑩", "〒", "℃", "㎝", "㎏", "№",
+        ],
+    ),
+];
+
+/// 1 行に並べる文字数
+const CHARACTERS_PER_LINE: u32 = 8;
+
+/// 編集中のテキストボックスに特殊文字パレットを表示
+///
+/// ポップオーバーはテキストボックスを指して表示されます。テキスト編集中で
+/// なければ何もせず `false` を返します。
+pub fn show_character_palette(
+    drawing_area: &DrawingArea,
+    render_state: &CanvasRenderState,
+    app_state: &AppState,
+    ime_entry: &Entry,
+) -> bool {
+    let Some(text_id) = render_state.tool_state.borrow().editing_text_id else {
+        return false;
+    };
+    let Some(bounds) = editing_text(app_state, text_id).map(|text| text.bounds) else {
+        return false;
+    };
+
+    let (left, top) =
+        document_to_widget(bounds.origin.x as f64, bounds.origin.y as f64, render_state);
+    let (right, bottom) = document_to_widget(
+        (bounds.origin.x + bounds.size.width) as f64,
+        (bounds.origin.y + bounds.size.height) as f64,
+        render_state,
+    );
+
+    let popover = Popover::new();
+    popover.set_parent(drawing_area);
+    popover.set_pointing_to(Some(&gdk::Rectangle::new(
+        left as i32,
+        top as i32,
+        (right - left).max(1.0) as i32,
+        (bottom - top).max(1.0) as i32,
+    )));
+    popover.connect_closed(|popover| popover.unparent());
+
+    let notebook = Notebook::new();
+    for (category, characters) in CHARACTER_CATEGORIES {
+        let flow_box = FlowBox::new();
+        flow_box.set_selection_mode(gtk4::SelectionMode::None);
+        flow_box.set_min_children_per_line(CHARACTERS_PER_LINE);
+        flow_box.set_max_children_per_line(CHARACTERS_PER_LINE);
+        flow_box.set_homogeneous(true);
+
+        for &character in characters.iter() {
+            let button = Button::with_label(character);
+            button.add_css_class("flat");
+            let code_points: Vec<String> = character
+                .chars()
+                .map(|c| format!("U+{:04X}", c as u32))
+                .collect();
+            button.set_tooltip_text(Some(&code_points.join(" ")));
+
+            let popover = popover.clone();
+            let render_state = render_state.clone();
+            let app_state = app_state.clone();
+            let drawing_area = drawing_area.clone();
+            let ime_entry = ime_entry.clone();
+            button.connect_clicked(move |_| {
+                insert_character(
+                    &app_state,
+                    &render_state,
+                    &drawing_area,
+                    &ime_entry,
+                    text_id,
+                    character,
+                );
+                popover.popdown();
+            });
+            flow_box.insert(&button, -1);
+        }
+        notebook.append_page(&flow_box, Some(&Label::new(Some(category))));
+    }
+    popover.set_child(Some(&notebook));
+    popover.popup();
+    true
+}
+
+/// 文字をカーソル位置に挿入し、IME 用 Entry の内容とカーソルも合わせる
+fn insert_character(
+    app_state: &AppState,
+    render_state: &CanvasRenderState,
+    drawing_area: &DrawingArea,
+    ime_entry: &Entry,
+    text_id: Uuid,
+    character: &str,
+) {
+    // Editing may have ended while the palette was open
+    if render_state.tool_state.borrow().editing_text_id != Some(text_id) {
+        return;
+    }
+    let mut cursor_pos = render_state.tool_state.borrow().editing_cursor_pos;
+    insert_text_at_cursor(
+        app_state,
+        render_state,
+        drawing_area,
+        text_id,
+        character,
+        &mut cursor_pos,
+    );
+    app_state.mark_as_modified();
+
+    if ime_entry.is_visible() {
+        // Entry の changed はカーソルを末尾へ移すので、設定後に戻す
+        if let Some(text) = editing_text(app_state, text_id) {
+            ime_entry.set_text(&text.content);
+        }
+        ime_entry.set_position(cursor_pos as i32);
+        render_state
+            .tool_state
+            .borrow_mut()
+            .move_editing_cursor(cursor_pos, false);
+        ime_entry.grab_focus();
+    } else {
+        drawing_area.grab_focus();
+    }
+    tracing::info!(
+        "✅ Inserted special character '{}', cursor now at {}",
+        character,
+        cursor_pos
+    );
+}
+
+/// アクティブページ上の編集中テキスト
+fn editing_text(
+    app_state: &AppState,
+    text_id: Uuid,
+) -> Option<testruct_core::document::TextElement> {
+    app_state.active_page().and_then(|page| {
+        page.elements.into_iter().find_map(|element| match element {
+            DocumentElement::Text(text) if text.id == text_id => Some(text),
+            _ => None,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palette_characters_are_unique_and_visible() {
+        let mut seen = std::collections::HashSet::new();
+        for (_, characters) in CHARACTER_CATEGORIES {
+            for character in characters.iter() {
+                assert!(seen.insert(*character), "duplicate {}", character);
+                assert!(!character.chars().any(char::is_control));
+            }
+        }
+        assert!(seen.contains("×") && seen.contains("÷") && seen.contains("½"));
+    }
+}
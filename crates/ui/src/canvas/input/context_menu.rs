@@ -0,0 +1,245 @@
+//! 右クリックのコンテキストメニューモジュール
+//!
+//! キャンバス上の右クリック（副ボタンの GestureClick）で、クリック位置に
+//! 応じた操作をポップオーバーメニューで表示します。各項目は既存の
+//! ウィンドウアクション（`win.*`）に対応します。
+//!
+//! | クリック位置 | メニュー |
+//! |-------------|---------|
+//! | 編集中テキストの誤字 | スペルの候補（[`spelling_menu`](super::spelling_menu)） |
+//! | オブジェクト | 切り取り・コピー・削除、最前面へ・最背面へ、ロック/ロック解除、グループ化/グループ解除、テキストを編集 |
+//! | 空白 | 貼り付け、すべて選択 |
+//!
+//! 選択されていないオブジェクトを右クリックすると、そのオブジェクトだけを
+//! 選択してからメニューを表示します。
+
+use super::coordinates::{widget_to_document, DocumentCoords};
+use super::spelling_menu;
+use crate::app::AppState;
+use crate::canvas::selection::HitTest;
+use crate::canvas::CanvasRenderState;
+use gtk4::prelude::*;
+use gtk4::{gdk, gio, DrawingArea, Entry, GestureClick, PopoverMenu};
+use testruct_core::document::DocumentElement;
+use testruct_core::layout::Point;
+
+/// メニュー項目（表示名とアクション名）
+type MenuItem = (&'static str, &'static str);
+
+/// メニューの内容を決める選択状態
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MenuContext {
+    /// 選択中のオブジェクト数（0 なら空白のクリック）
+    pub selected: usize,
+    /// 選択がテキストボックス 1 つだけか
+    pub single_text: bool,
+    /// ロックされたオブジェクトを含むか
+    pub any_locked: bool,
+    /// ロックされていないオブジェクトを含むか
+    pub any_unlocked: bool,
+    /// グループを含むか
+    pub any_group: bool,
+}
+
+/// コンテキストメニューを設定
+pub fn setup_context_menu(
+    drawing_area: &DrawingArea,
+    render_state: &CanvasRenderState,
+    app_state: &AppState,
+    ime_entry: &Entry,
+) {
+    let gesture = GestureClick::new();
+    gesture.set_button(gdk::BUTTON_SECONDARY);
+
+    let render_state = render_state.clone();
+    let app_state = app_state.clone();
+    let drawing_area_menu = drawing_area.clone();
+    let ime_entry = ime_entry.clone();
+    gesture.connect_pressed(move |gesture, _n_press, x, y| {
+        gesture.set_state(gtk4::EventSequenceState::Claimed);
+        let doc = widget_to_document(x, y, &render_state);
+
+        // Misspelled words in the text being edited get suggestions instead
+        if spelling_menu::show_spelling_menu(
+            &drawing_area_menu,
+            &render_state,
+            &app_state,
+            &ime_entry,
+            (x, y),
+            doc,
+        ) {
+            return;
+        }
+
+        let context = select_for_menu(&render_state, &app_state, doc);
+        let popover = PopoverMenu::from_model(Some(&menu_model(&context)));
+        popover.set_parent(&drawing_area_menu);
+        popover.set_has_arrow(false);
+        popover.set_pointing_to(Some(&gdk::Rectangle::new(x as i32, y as i32, 1, 1)));
+        popover.connect_closed(|popover| popover.unparent());
+        popover.popup();
+    });
+    drawing_area.add_controller(gesture);
+}
+
+/// クリック位置のオブジェクトを選択し、メニューの内容を決める
+///
+/// 選択中のオブジェクトをクリックした場合は選択をそのまま使います。
+/// 空白のクリックでは選択を変えず、空白用のメニューになります。
+fn select_for_menu(
+    render_state: &CanvasRenderState,
+    app_state: &AppState,
+    doc: DocumentCoords,
+) -> MenuContext {
+    let Some(page) = app_state.active_page() else {
+        return MenuContext::default();
+    };
+    let point = Point::new(doc.x as f32, doc.y as f32);
+    let Some(hit) = HitTest::topmost_at(point, &page).map(|hit| hit.top_level()) else {
+        return MenuContext::default();
+    };
+
+    let selection_changed = {
+        let mut selected = render_state.selected_ids.borrow_mut();
+        let changed = !selected.contains(&hit);
+        if changed {
+            *selected = vec![hit];
+        }
+        changed
+    };
+    if selection_changed {
+        render_state.notify_selection_changed();
+    }
+
+    let selected = render_state.selected_ids.borrow();
+    let elements: Vec<&DocumentElement> = page
+        .elements
+        .iter()
+        .filter(|element| selected.contains(&element.id()))
+        .collect();
+    MenuContext {
+        selected: elements.len(),
+        single_text: matches!(elements[..], [DocumentElement::Text(_)]),
+        any_locked: elements.iter().any(|element| element.is_locked()),
+        any_unlocked: elements.iter().any(|element| !element.is_locked()),
+        any_group: elements
+            .iter()
+            .any(|element| matches!(element, DocumentElement::Group(_))),
+    }
+}
+
+/// メニューのセクション（区切り線で分ける項目のまとまり）
+pub fn menu_sections(context: &MenuContext) -> Vec<Vec<MenuItem>> {
+    if context.selected == 0 {
+        return vec![vec![
+            ("貼り付け", "win.paste"),
+            ("すべて選択", "win.select-all"),
+        ]];
+    }
+
+    let mut sections = Vec::new();
+    if context.single_text {
+        sections.push(vec![("テキストを編集", "win.edit-text")]);
+    }
+    sections.push(vec![
+        ("切り取り", "win.cut"),
+        ("コピー", "win.copy"),
+        ("削除", "win.delete"),
+    ]);
+    sections.push(vec![
+        ("最前面へ移動", "win.bring-to-front"),
+        ("最背面へ移動", "win.send-to-back"),
+    ]);
+
+    let mut lock = Vec::new();
+    if context.any_unlocked {
+        lock.push(("ロック", "win.lock"));
+    }
+    if context.any_locked {
+        lock.push(("ロック解除", "win.unlock"));
+    }
+    sections.push(lock);
+
+    let mut group = Vec::new();
+    if context.selected > 1 {
+        group.push(("グループ化", "win.group"));
+    }
+    if context.any_group {
+        group.push(("グループ解除", "win.ungroup"));
+    }
+    if !group.is_empty() {
+        sections.push(group);
+    }
+    sections
+}
+
+fn menu_model(context: &MenuContext) -> gio::Menu {
+    let menu = gio::Menu::new();
+    for items in menu_sections(context) {
+        let section = gio::Menu::new();
+        for (label, action) in items {
+            section.append(Some(label), Some(action));
+        }
+        menu.append_section(None, &section);
+    }
+    menu
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn actions(context: &MenuContext) -> Vec<&'static str> {
+        menu_sections(context)
+            .into_iter()
+            .flatten()
+            .map(|(_, action)| action)
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_canvas_offers_paste_and_select_all() {
+        assert_eq!(
+            actions(&MenuContext::default()),
+            ["win.paste", "win.select-all"]
+        );
+    }
+
+    #[test]
+    fn test_element_menu_follows_selection() {
+        let text = MenuContext {
+            selected: 1,
+            single_text: true,
+            any_unlocked: true,
+            ..Default::default()
+        };
+        let text_actions = actions(&text);
+        assert_eq!(text_actions[0], "win.edit-text");
+        assert!(text_actions.contains(&"win.lock"));
+        assert!(!text_actions.contains(&"win.unlock"));
+        assert!(!text_actions.contains(&"win.group"));
+
+        let mixed = MenuContext {
+            selected: 2,
+            any_locked: true,
+            any_unlocked: true,
+            any_group: true,
+            ..Default::default()
+        };
+        let mixed_actions = actions(&mixed);
+        assert!(!mixed_actions.contains(&"win.edit-text"));
+        for action in [
+            "win.cut",
+            "win.copy",
+            "win.delete",
+            "win.bring-to-front",
+            "win.send-to-back",
+            "win.lock",
+            "win.unlock",
+            "win.group",
+            "win.ungroup",
+        ] {
+            assert!(mixed_actions.contains(&action), "missing {}", action);
+        }
+    }
+}
@@ -120,5 +120,5 @@ pub fn setup_gestures(
     ime_entry: &Entry,
 ) {
     gesture_click::setup_click_gesture(drawing_area, render_state, app_state, ime_manager, ime_entry);
-    gesture_drag::setup_drag_gesture(drawing_area, render_state, app_state);
+    gesture_drag::setup_drag_gesture(drawing_area, render_state, app_state, ime_entry);
 }
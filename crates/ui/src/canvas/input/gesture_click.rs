@@ -14,29 +14,50 @@
 //! | クリック | 単一選択（既存選択をクリア） |
 //! | Shift+クリック | 選択に追加 |
 //! | Ctrl+クリック | トグル選択（選択/解除） |
+//! | Alt+クリック | グループ内の子要素を直接選択 |
 //! | ダブルクリック（テキスト） | テキスト編集モードに進入 |
+//! | クリック（編集中のテキスト内） | クリック位置にカーソルを移動 |
+//! | Shift+クリック（編集中のテキスト内） | カーソル位置からクリック位置までを選択 |
 //! | ダブルクリック（画像） | 画像ファイル選択ダイアログを表示 |
+//! | ダブルクリック（表） | 表に入り、その位置のセルを編集 |
+//! | ダブルクリック（QR コード） | QR コードの内容を編集するダイアログを表示 |
+//! | クリック（表に入っている間） | クリックしたセルを編集、表の外なら表から出る |
 //! | 空白クリック | 選択をクリア |
+//! | Shift/Ctrl+空白クリック | 選択を維持（続くマーキーが離したときに追加を判断） |
+//! | 空白クリック（テキストツール） | 既定サイズのテキストボックスを作成して編集開始 |
+//! | クリック（多角形ツール） | 頂点を追加 |
+//! | ダブルクリック（多角形ツール） | 多角形を確定（Enter でも可） |
+//! | クリック（スポイトツール） | クリック位置の色を選択中の図形の塗りに適用 |
+//! | Shift+クリック（スポイトツール） | クリック位置の色を選択中の図形の線に適用 |
 //!
 //! ## リサイズハンドル
 //!
 //! 選択オブジェクトのリサイズハンドル（8方向）を検出し、クリック時に
 //! リサイズ操作の開始位置として設定します。
+//!
+//! ## 回転ハンドル
+//!
+//! 回転可能な選択オブジェクト（図形・テキスト・画像）の上辺中央の上にある
+//! 回転ハンドルを検出し、ドラッグによる回転操作を開始します。
 
 use super::coordinates::widget_to_document;
 use super::ime::ImeManager;
 use crate::app::AppState;
-use crate::canvas::mouse::{test_resize_handle, CanvasMousePos};
+use crate::canvas::mouse::{
+    test_resize_handle, test_rotation_handle, CanvasMousePos, ROTATION_HANDLE_DISTANCE,
+};
 use crate::canvas::selection::HitTest;
-use crate::canvas::tools::ToolMode;
+use crate::canvas::tools::{
+    ShapeFactory, ToolMode, DEFAULT_TEXT_BOX_HEIGHT, DEFAULT_TEXT_BOX_WIDTH,
+};
 use crate::canvas::CanvasRenderState;
 use gtk4::gdk;
 use gtk4::prelude::*;
 use gtk4::{DrawingArea, Entry, GestureClick};
 use std::cell::RefCell;
 use std::rc::Rc;
-use testruct_core::document::DocumentElement;
-use testruct_core::layout::Rect;
+use testruct_core::document::{DocumentElement, Fill};
+use testruct_core::layout::{Point, Rect};
 
 /// クリックジェスチャーを設定
 pub fn setup_click_gesture(
@@ -70,6 +91,7 @@ pub fn setup_click_gesture(
             };
             let shift_pressed = modifier_state.contains(gdk::ModifierType::SHIFT_MASK);
             let ctrl_pressed = modifier_state.contains(gdk::ModifierType::CONTROL_MASK);
+            let alt_pressed = modifier_state.contains(gdk::ModifierType::ALT_MASK);
 
             // Check for double-click (n_press == 2) for text editing or image selection
             eprintln!("\n=== Click Event ===");
@@ -90,11 +112,38 @@ pub fn setup_click_gesture(
             drop(config);
             drop(ruler_config);
 
+            if n_press == 1
+                && place_cursor_in_editing_text(
+                    &state,
+                    &app_state_click,
+                    &drawing_area_click,
+                    &ime_entry_click,
+                    canvas_x,
+                    canvas_y,
+                    shift_pressed,
+                )
+            {
+                return;
+            }
+
+            if n_press == 1
+                && super::table_edit::handle_click_in_table(
+                    &state,
+                    &app_state_click,
+                    &drawing_area_click,
+                    canvas_x,
+                    canvas_y,
+                )
+            {
+                return;
+            }
+
             if n_press == 2 {
                 eprintln!("Double-click detected at ({:.0}, {:.0})", x, y);
                 // Try to find a text or image element at this position
+                let page_index = app_state_click.active_page_index();
                 if let Some(document) = app_state_click.active_document() {
-                    if let Some(page) = document.pages.first() {
+                    if let Some(page) = document.pages.get(page_index) {
                         // Use document coordinates from unified conversion
                         let doc_x = canvas_x;
                         let doc_y = canvas_y;
@@ -191,8 +240,9 @@ pub fn setup_click_gesture(
                                                     };
 
                                                     // Store the image path in the document
+                                                    let page_index = app_state_dialog.active_page_index();
                                                     let _ = app_state_dialog.with_active_document(|doc| {
-                                                        if let Some(page) = doc.pages.first_mut() {
+                                                        if let Some(page) = doc.pages.get_mut(page_index) {
                                                             for element in &mut page.elements {
                                                                 if let DocumentElement::Image(img) = element {
                                                                     if img.id == image_id {
@@ -216,6 +266,60 @@ pub fn setup_click_gesture(
                                         return;
                                     }
                                 }
+                                DocumentElement::Table(table) => {
+                                    let point = Point::new(doc_x as f32, doc_y as f32);
+                                    if let Some(cell) = table.cell_at(point) {
+                                        super::table_edit::begin_cell_editing(
+                                            &state,
+                                            &app_state_click,
+                                            &drawing_area_click,
+                                            table.id,
+                                            cell,
+                                        );
+                                        return;
+                                    }
+                                }
+                                DocumentElement::QrCode(qr) => {
+                                    let bounds = &qr.bounds;
+                                    if doc_x >= bounds.origin.x as f64
+                                        && doc_x <= (bounds.origin.x + bounds.size.width) as f64
+                                        && doc_y >= bounds.origin.y as f64
+                                        && doc_y <= (bounds.origin.y + bounds.size.height) as f64
+                                    {
+                                        let mut selected = state.selected_ids.borrow_mut();
+                                        selected.clear();
+                                        selected.push(qr.id);
+                                        drop(selected);
+                                        drawing_area_click.queue_draw();
+
+                                        // QR コードの内容を編集するダイアログを表示
+                                        if let Some(window) = app_state_click.window() {
+                                            let qr_id = qr.id;
+                                            let app_state_dialog = app_state_click.clone();
+                                            let drawing_area_dialog = drawing_area_click.clone();
+
+                                            use gtk4::glib::Cast;
+                                            let window_ref = window.upcast::<gtk4::Window>();
+
+                                            crate::dialogs::show_qr_code_dialog(
+                                                &window_ref,
+                                                Some(qr),
+                                                Box::new(move |payload, level| {
+                                                    let command = crate::undo_redo::AppQrCodeCommand::new(
+                                                        app_state_dialog.clone(),
+                                                        qr_id,
+                                                        app_state_dialog.active_page_index(),
+                                                        payload,
+                                                        level,
+                                                    );
+                                                    app_state_dialog.push_command(Box::new(command));
+                                                    drawing_area_dialog.queue_draw();
+                                                }),
+                                            );
+                                        }
+                                        return;
+                                    }
+                                }
                                 _ => {}
                             }
                         }
@@ -224,11 +328,21 @@ pub fn setup_click_gesture(
             }
 
             // Get the active document
+            let page_index = app_state_click.active_page_index();
             if let Some(document) = app_state_click.active_document() {
                 // Use document coordinates from unified conversion (already calculated above)
                 let doc_x = canvas_x;
                 let doc_y = canvas_y;
                 let canvas_mouse_pos = CanvasMousePos::new(doc_x, doc_y);
+                let handle_hit_size = state.config.borrow().handle_hit_size();
+
+                // The rotation handle sits outside the bounds, so test it before resize handles
+                if let Some(page) = document.pages.get(page_index) {
+                    if start_rotation(&state, page, canvas_mouse_pos, handle_hit_size, x, y) {
+                        drawing_area_click.queue_draw();
+                        return;
+                    }
+                }
 
                 // IMPORTANT: Check if clicking on a resize handle FIRST
                 // This must happen BEFORE double-click text editing check
@@ -236,7 +350,7 @@ pub fn setup_click_gesture(
                 let selected_ids = state.selected_ids.borrow();
                 let mut resize_detected = false;
 
-                if let Some(page) = document.pages.first() {
+                if let Some(page) = document.pages.get(page_index) {
                     for element in &page.elements {
                         let element_id = match element {
                             DocumentElement::Shape(shape) => shape.id,
@@ -244,6 +358,8 @@ pub fn setup_click_gesture(
                             DocumentElement::Image(image) => image.id,
                             DocumentElement::Frame(frame) => frame.id,
                             DocumentElement::Group(group) => group.id,
+                            DocumentElement::Table(table) => table.id,
+                            DocumentElement::QrCode(qr) => qr.id,
                         };
 
                         // Only check resize handles for selected objects
@@ -251,8 +367,8 @@ pub fn setup_click_gesture(
                             continue;
                         }
 
-                        // Skip resize handles for locked elements
-                        if element.is_locked() {
+                        // Skip resize handles for locked elements and locked layers
+                        if element.is_locked() || page.is_in_locked_layer(element_id) {
                             continue;
                         }
 
@@ -262,11 +378,12 @@ pub fn setup_click_gesture(
                             DocumentElement::Image(image) => &image.bounds,
                             DocumentElement::Frame(frame) => &frame.bounds,
                             DocumentElement::Group(group) => &group.bounds,
+                            DocumentElement::Table(table) => &table.bounds,
+                            DocumentElement::QrCode(qr) => &qr.bounds,
                         };
 
-                        // Test for resize handle hit
-                        // Use larger detection range (16.0 instead of 8.0) to account for coordinate display issues
-                        if let Some(handle) = test_resize_handle(canvas_mouse_pos, bounds, 16.0) {
+                        // Test for resize handle hit (tolerance is zoom-independent)
+                        if let Some(handle) = test_resize_handle(canvas_mouse_pos, bounds, handle_hit_size) {
                             // Store resize state
                             let mut tool_state = state.tool_state.borrow_mut();
                             tool_state.resizing_object_id = Some(element_id);
@@ -309,54 +426,14 @@ pub fn setup_click_gesture(
                     drawing_area_click.queue_draw();
                     return;
                 }
-                if let Some(page) = document.pages.first() {
-                    // Build list of objects with their bounds for hit testing
-                    let mut objects: Vec<(uuid::Uuid, Rect)> = Vec::new();
-
-                    eprintln!("\n📋 Page Elements:");
-                    for element in &page.elements {
-                        match element {
-                            DocumentElement::Shape(shape) => {
-                                eprintln!("  Shape {}: bounds=({:.0}, {:.0}, {:.0}x{:.0})",
-                                    shape.id, shape.bounds.origin.x, shape.bounds.origin.y,
-                                    shape.bounds.size.width, shape.bounds.size.height);
-                                objects.push((shape.id, shape.bounds));
-                            }
-                            DocumentElement::Text(text) => {
-                                eprintln!("  Text  {}: bounds=({:.0}, {:.0}, {:.0}x{:.0})",
-                                    text.id, text.bounds.origin.x, text.bounds.origin.y,
-                                    text.bounds.size.width, text.bounds.size.height);
-                                objects.push((text.id, text.bounds));
-                            }
-                            DocumentElement::Image(image) => {
-                                eprintln!("  Image {}: bounds=({:.0}, {:.0}, {:.0}x{:.0})",
-                                    image.id, image.bounds.origin.x, image.bounds.origin.y,
-                                    image.bounds.size.width, image.bounds.size.height);
-                                objects.push((image.id, image.bounds));
-                            }
-                            DocumentElement::Frame(frame) => {
-                                eprintln!("  Frame {}: bounds=({:.0}, {:.0}, {:.0}x{:.0})",
-                                    frame.id, frame.bounds.origin.x, frame.bounds.origin.y,
-                                    frame.bounds.size.width, frame.bounds.size.height);
-                                objects.push((frame.id, frame.bounds));
-                            }
-                            DocumentElement::Group(group) => {
-                                eprintln!("  Group {}: bounds=({:.0}, {:.0}, {:.0}x{:.0})",
-                                    group.id, group.bounds.origin.x, group.bounds.origin.y,
-                                    group.bounds.size.width, group.bounds.size.height);
-                                objects.push((group.id, group.bounds));
-                            }
-                        }
-                    }
+                if let Some(page) = document.pages.get(page_index) {
                     eprintln!("Canvas Click at: ({:.2}, {:.2})", doc_x, doc_y);
 
-                    // Convert to references for hit testing
-                    let object_refs: Vec<(uuid::Uuid, &Rect)> =
-                        objects.iter().map(|(id, bounds)| (*id, bounds)).collect();
-
-                    // Use document coordinates from unified conversion (already calculated above)
-                    // Perform hit test
-                    if let Some(clicked_id) = HitTest::hit_test(&object_refs, doc_x, doc_y) {
+                    // The topmost element wins. Clicking inside a group selects the
+                    // group; Alt+click selects the innermost child directly
+                    let hit = HitTest::topmost_at(Point::new(doc_x as f32, doc_y as f32), page)
+                        .map(|hit| if alt_pressed { hit.deepest() } else { hit.top_level() });
+                    if let Some(clicked_id) = hit {
                         tracing::info!("Hit test: selected object {}", clicked_id);
 
                         // Update selection based on modifier keys
@@ -392,6 +469,10 @@ pub fn setup_click_gesture(
                         }
                         drop(selected);
                         drawing_area_click.queue_draw();
+                    } else if shift_pressed || ctrl_pressed {
+                        // Shift/Ctrl keep the selection; a marquee started here
+                        // decides on release whether to extend it
+                        tracing::debug!("Kept selection (modified empty space click)");
                     } else {
                         // Clicked on empty space: clear selection
                         let mut selected = state.selected_ids.borrow_mut();
@@ -402,7 +483,301 @@ pub fn setup_click_gesture(
                     }
                 }
             }
+        } else if current_tool == ToolMode::Polygon {
+            if n_press >= 2 {
+                super::keyboard_shortcuts::handle_finish_polygon(
+                    &state,
+                    &app_state_click,
+                    &drawing_area_click,
+                );
+            } else {
+                let doc_coords = widget_to_document(x, y, &state);
+                state
+                    .tool_state
+                    .borrow_mut()
+                    .add_polygon_point(Point::new(doc_coords.x as f32, doc_coords.y as f32));
+                drawing_area_click.grab_focus();
+                drawing_area_click.queue_draw();
+            }
+        } else if current_tool == ToolMode::Eyedropper && n_press == 1 {
+            let to_stroke = gesture
+                .last_event(None)
+                .is_some_and(|event| event.modifier_state().contains(gdk::ModifierType::SHIFT_MASK));
+            let doc_coords = widget_to_document(x, y, &state);
+            sample_color_at(
+                &state,
+                &app_state_click,
+                &drawing_area_click,
+                Point::new(doc_coords.x as f32, doc_coords.y as f32),
+                to_stroke,
+            );
         }
     });
     drawing_area.add_controller(click_gesture);
 }
+
+/// 回転ハンドルのクリック: 回転ドラッグを開始
+///
+/// 選択中でロックされていない回転可能な要素のハンドルに当たった場合、
+/// 開始前の角度（Undo 用）を保存して `true` を返します。実際の回転は
+/// gesture_drag 側で行います。
+fn start_rotation(
+    state: &CanvasRenderState,
+    page: &testruct_core::document::Page,
+    point: CanvasMousePos,
+    handle_hit_size: f64,
+    x: f64,
+    y: f64,
+) -> bool {
+    let distance = ROTATION_HANDLE_DISTANCE / state.config.borrow().zoom;
+    let selected_ids = state.selected_ids.borrow();
+    let hit = page.elements.iter().find(|element| {
+        selected_ids.contains(&element.id())
+            && !element.is_locked()
+            && !page.is_in_locked_layer(element.id())
+            && element.can_rotate()
+            && test_rotation_handle(
+                point,
+                element.bounds(),
+                element.rotation(),
+                handle_hit_size,
+                distance,
+            )
+    });
+    let Some(element) = hit else {
+        return false;
+    };
+
+    let mut tool_state = state.tool_state.borrow_mut();
+    tool_state.rotating_object_id = Some(element.id());
+    tool_state.rotation_original = Some(element.rotation());
+    tool_state.rotation_label = None;
+    tool_state.drag_start = Some((x, y));
+    tracing::info!("Started rotating object {}", element.id());
+    true
+}
+
+/// 編集中のテキスト内クリック: クリック位置にカーソルを移動
+///
+/// クリック位置を Pango レイアウトで文字位置に変換するため、折り返しや
+/// 複数行のテキストでも正しい位置に移動します。Shift+クリックでは
+/// 現在のカーソル位置からクリック位置までを選択します。編集中でない場合や
+/// テキストの外をクリックした場合は `false` を返し、通常の選択処理に任せます。
+fn place_cursor_in_editing_text(
+    state: &CanvasRenderState,
+    app_state: &AppState,
+    drawing_area: &DrawingArea,
+    ime_entry: &Entry,
+    doc_x: f64,
+    doc_y: f64,
+    extend_selection: bool,
+) -> bool {
+    let Some(editing_id) = state.tool_state.borrow().editing_text_id else {
+        return false;
+    };
+    let Some(page) = app_state.active_page() else {
+        return false;
+    };
+    let Some(text) = page.elements.iter().find_map(|element| match element {
+        DocumentElement::Text(text) if text.id == editing_id => Some(text),
+        _ => None,
+    }) else {
+        return false;
+    };
+
+    let bounds = &text.bounds;
+    let inside = doc_x >= bounds.origin.x as f64
+        && doc_x <= (bounds.origin.x + bounds.size.width) as f64
+        && doc_y >= bounds.origin.y as f64
+        && doc_y <= (bounds.origin.y + bounds.size.height) as f64;
+    if !inside {
+        return false;
+    }
+
+    let cursor_pos = crate::canvas::rendering::text_cursor_index_at(
+        bounds,
+        &text.content,
+        &text.style,
+        &text.padding,
+        doc_x,
+        doc_y,
+    );
+    state
+        .tool_state
+        .borrow_mut()
+        .move_editing_cursor(cursor_pos, extend_selection);
+    // Keep the IME entry caret in step with the canvas cursor
+    ime_entry.set_position(cursor_pos as i32);
+    drawing_area.queue_draw();
+    tracing::debug!("Text cursor placed at {} by click", cursor_pos);
+    true
+}
+
+/// スポイトツールのクリック: ページの色を取得して適用
+///
+/// レンダリング結果の 1 ピクセルを読み取り、`ToolState::sampled_color` に
+/// 保存します。図形が選択されていれば塗り（`to_stroke` のときは線）に
+/// Undo 可能なコマンドで適用します。取得後は図形作成と同様に選択ツールへ
+/// 戻ります。
+fn sample_color_at(
+    state: &CanvasRenderState,
+    app_state: &AppState,
+    drawing_area: &DrawingArea,
+    point: Point,
+    to_stroke: bool,
+) {
+    state.tool_state.borrow_mut().current_tool = ToolMode::Select;
+
+    let Some(document) = app_state.active_document() else {
+        return;
+    };
+    let Some(page) = document.pages.get(app_state.active_page_index()) else {
+        return;
+    };
+    let page_size = document.page_size_of(page);
+    let catalog = app_state.asset_catalog();
+    let sampled = {
+        let catalog = catalog.lock().expect("asset catalog");
+        crate::export::image::sample_page_color(page, page_size, point, &catalog)
+    };
+    let color = match sampled {
+        Ok(Some(color)) => color,
+        Ok(None) => {
+            tracing::debug!(
+                "🎨 Eyedropper: nothing painted at ({:.1}, {:.1})",
+                point.x,
+                point.y
+            );
+            return;
+        }
+        Err(e) => {
+            tracing::warn!("⚠️ Eyedropper sampling failed: {}", e);
+            return;
+        }
+    };
+    state.tool_state.borrow_mut().sampled_color = Some(color);
+    tracing::info!(
+        "🎨 Eyedropper picked ({:.2}, {:.2}, {:.2})",
+        color.r,
+        color.g,
+        color.b
+    );
+
+    let selected: Vec<uuid::Uuid> = state.selected_ids.borrow().clone();
+    let shape_fill = page.elements.iter().find_map(|element| match element {
+        DocumentElement::Shape(shape) if selected.contains(&shape.id) => Some(shape.fill),
+        _ => None,
+    });
+    let Some(current_fill) = shape_fill else {
+        return;
+    };
+
+    let value = if to_stroke {
+        crate::undo_redo::PropertyValue::StrokeColor(Some(color))
+    } else {
+        // Gradients keep their end color and angle; the sample sets the start
+        let fill = match current_fill {
+            Some(Fill::LinearGradient { end, angle, .. }) => Fill::LinearGradient {
+                start: color,
+                end,
+                angle,
+            },
+            _ => Fill::Solid(color),
+        };
+        crate::undo_redo::PropertyValue::Fill(Some(fill))
+    };
+    let command = crate::undo_redo::AppPropertyChangeCommand::new(
+        app_state.clone(),
+        selected,
+        app_state.active_page_index(),
+        value,
+    );
+    app_state.push_command(Box::new(command));
+    drawing_area.queue_draw();
+    // Refresh the property panel for the unchanged selection
+    state.notify_selection_changed();
+}
+
+/// テキストツールでの空白クリック: 既定サイズのテキストボックスを作成
+///
+/// 作成は Undo 可能なコマンドとして実行し、新しいボックスを選択して
+/// 即座に編集モード（IME フォーカス付き）に入ります。押したときではなく、
+/// gesture_drag の drag_end で移動量がしきい値以下のときに呼ばれるため、
+/// ドラッグした場合はドラッグ範囲のボックスだけが作成されます。
+pub(super) fn create_text_box_at(
+    state: &CanvasRenderState,
+    app_state: &AppState,
+    drawing_area: &DrawingArea,
+    ime_entry: &Entry,
+    doc_x: f64,
+    doc_y: f64,
+) {
+    // A click while editing just finishes the current edit
+    if state.tool_state.borrow().editing_text_id.is_some() {
+        state.tool_state.borrow_mut().exit_text_editing();
+        ime_entry.set_visible(false);
+        ime_entry.set_text("");
+        drawing_area.grab_focus();
+        drawing_area.queue_draw();
+        return;
+    }
+
+    // Clicking an existing (visible) object does not create a new box
+    let hit = app_state
+        .active_page()
+        .map(|page| {
+            let objects: Vec<(uuid::Uuid, Rect)> = page
+                .layered_elements()
+                .into_iter()
+                .filter(|e| e.is_visible())
+                .map(|e| (e.id(), *e.bounds()))
+                .collect();
+            let object_refs: Vec<(uuid::Uuid, &Rect)> =
+                objects.iter().map(|(id, bounds)| (*id, bounds)).collect();
+            HitTest::hit_test(&object_refs, doc_x, doc_y).is_some()
+        })
+        .unwrap_or(false);
+    if hit {
+        return;
+    }
+
+    let element = ShapeFactory::create_text(
+        doc_x,
+        doc_y,
+        DEFAULT_TEXT_BOX_WIDTH,
+        DEFAULT_TEXT_BOX_HEIGHT,
+        String::new(),
+    );
+    let text_id = element.id();
+    let command = crate::undo_redo::AppCreateCommand::new(
+        app_state.clone(),
+        element,
+        app_state.active_page_index(),
+    );
+    app_state.push_command(Box::new(command));
+    app_state.mark_as_modified();
+
+    {
+        let mut selected = state.selected_ids.borrow_mut();
+        selected.clear();
+        selected.push(text_id);
+    }
+    {
+        let mut tool_state = state.tool_state.borrow_mut();
+        tool_state.editing_text_id = Some(text_id);
+        tool_state.editing_cursor_pos = 0;
+    }
+
+    // Show IME Entry for Japanese input support
+    ime_entry.set_text("");
+    ime_entry.set_visible(true);
+    ime_entry.grab_focus();
+
+    drawing_area.queue_draw();
+    tracing::info!(
+        "✅ Text box created at ({:.0}, {:.0}) and editing started: {}",
+        doc_x,
+        doc_y,
+        text_id
+    );
+}
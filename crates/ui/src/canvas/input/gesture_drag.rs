@@ -4,24 +4,61 @@
 //!
 //! # ドラッグジェスチャー (GestureDrag)
 //!
-//! ドラッグ操作により3つの処理が実行されます：
+//! ドラッグ操作により以下の処理が実行されます：
 //!
 //! ## 1. オブジェクト移動
 //! Select ツール + 選択オブジェクトをドラッグ
 //! - `delta_x`, `delta_y` でオブジェクト座標を更新
+//! - ロックされた要素は選択中でも移動しない
 //! - グリッドスナップ対応
+//! - ドラッグ中はカーソル付近に移動先の位置とサイズを表示（ルーラー単位）
+//! - `AppMoveCommand` で Undo/Redo 対応
 //!
 //! ## 2. オブジェクトリサイズ
 //! リサイズハンドルをドラッグ
 //! - `calculate_resize_bounds()` で新しい寸法を計算
 //! - ハンドルタイプ（TopLeft, Top, TopRight など）に基づいて計算
-//! - グリッドスナップ対応
+//! - 角のハンドルでは Shift キーで縦横比を維持（画像は既定で維持し、Shift で解除）
+//! - 縦横比を維持するときはハンドルの反対側の角を固定
+//! - 動かした辺をガイド（しきい値内なら優先）またはグリッドにスナップ
+//! - スナップ中の辺にはガイドと同じ形の指示線を別の色で表示（drag_end で消去）
+//! - ドラッグ中はカーソル付近に現在の位置とサイズを表示（ルーラー単位）
+//! - 開始時の寸法から `AppResizeCommand` を作成（Undo で元の寸法に戻る）
+//!
+//! ## 3. オブジェクト回転
+//! 回転ハンドルをドラッグ
+//! - `rotation_angle_at()` で要素中心からカーソルへの角度を計算
+//! - Shift キーを押している間は 15° 単位にスナップ、それ以外は自由回転
+//! - ドラッグ中はカーソル付近に現在の角度を表示
+//! - 開始時の角度から `AppPropertyChangeCommand` を作成（Undo で元の角度に戻る）
 //!
-//! ## 3. 図形作成
+//! ## 4. 図形作成
 //! Rectangle、Circle、Line、Arrow、Text、Image ツール + ドラッグ
 //! - `ShapeFactory` で新規要素を作成
 //! - ドラッグ開始・終了座標で図形サイズを決定
+//! - 開始・終了の角はガイドにスナップ（しきい値内ならグリッドより優先）
+//! - `AppCreateCommand` で Undo/Redo 対応
 //! - 作成後は自動的に Select ツールに切り替え
+//! - テキストツールでドラッグせずに離した場合は既定サイズのテキストボックスを
+//!   1 つ作成（`gesture_click::create_text_box_at`）
+//!
+//! ## 5. 範囲選択（マーキー）
+//! Select ツール + 何もない場所からドラッグ
+//! - ドラッグ中は選択範囲の矩形を半透明で表示
+//! - 矩形に触れている要素を選択（Ctrl/Cmd: 完全に含まれる要素のみ）
+//! - Shift キーで既存の選択に追加
+//!
+//! ## 6. 計測
+//! Measure ツール + ドラッグ
+//! - 開始点から現在位置までの寸法線を表示し、距離と水平・垂直成分をルーラー単位で表示
+//! - 端点はガイドと要素の辺・中心にスナップ（それぞれのスナップ設定が有効な場合）
+//! - 要素は作成せず、次の計測またはツールの切り替えまで表示を残す
+//!
+//! ## 7. ガイド
+//! ルーラーからドラッグ、または Select ツールで既存ガイドをドラッグ
+//! - `guide_drag` モジュールでガイドを作成・移動
+//! - 同じ向きのルーラー上で離すとガイドを削除
+//! - ガイドのドラッグ中は要素の選択・移動を行わない
 //!
 //! # ドラッグ処理の流れ
 //!
@@ -35,45 +72,62 @@
 //!   └─ キャンバス再描画
 //!
 //! drag_end
-//!   ├─ 操作タイプ判定（リサイズ/移動/作成）
+//!   ├─ 操作タイプ判定（回転/リサイズ/移動/作成）
 //!   ├─ ドキュメント更新
 //!   ├─ グリッドスナップ適用
 //!   └─ ドラッグ状態をクリア
 //! ```
 
-use super::coordinates::widget_to_document;
+use super::coordinates::{widget_to_document, DocumentCoords};
 use crate::app::AppState;
-use crate::canvas::mouse::calculate_resize_bounds;
-use crate::canvas::rendering::snap_rect_to_grid;
+use crate::canvas::mouse::{
+    calculate_resize_bounds, constrain_resize_to_aspect, rotation_angle_at, CanvasMousePos,
+    ResizeHandle,
+};
+use crate::canvas::measure::{snap_measure_point, Measurement};
 use crate::canvas::selection::HitTest;
-use crate::canvas::snapping::{ObjectAlignmentPoints, SmartGuideEngine};
+use crate::canvas::snapping::{snap_to_object_edges, SnapLine};
 use crate::canvas::tools::{ShapeFactory, ToolMode};
 use crate::canvas::CanvasRenderState;
 use gtk4::gdk;
 use gtk4::prelude::*;
-use gtk4::{DrawingArea, GestureDrag};
+use gtk4::{DrawingArea, Entry, GestureDrag};
 use testruct_core::document::DocumentElement;
 use testruct_core::layout::{Point, Rect, Size};
 
+/// これを超えて動いたらクリックではなくドラッグとみなす移動量（px）
+const DRAG_THRESHOLD: f64 = 5.0;
+
 /// ドラッグジェスチャーを設定
 pub fn setup_drag_gesture(
     drawing_area: &DrawingArea,
     render_state: &CanvasRenderState,
     app_state: &AppState,
+    ime_entry: &Entry,
 ) {
     let drag_gesture = GestureDrag::new();
     drag_gesture.set_button(gdk::BUTTON_PRIMARY);
     drag_gesture.set_propagation_phase(gtk4::PropagationPhase::Capture);
 
     let render_state_drag = render_state.clone();
-    let drawing_area_drag = drawing_area.clone();
+    let drawing_area_begin = drawing_area.clone();
     let app_state_drag_begin = app_state.clone();
 
-    drag_gesture.connect_drag_begin(move |_gesture, x, y| {
+    drag_gesture.connect_drag_begin(move |gesture, x, y| {
         let state = render_state_drag.clone();
+
+        // ルーラーまたは既存ガイドからのドラッグはガイド操作として扱う
+        if super::guide_drag::begin_guide_drag(&state, x, y) {
+            gesture.set_state(gtk4::EventSequenceState::Claimed);
+            state.tool_state.borrow_mut().drag_start = Some((x, y));
+            drawing_area_begin.queue_draw();
+            return;
+        }
+
         let tool_state = state.tool_state.borrow();
         let current_tool = tool_state.current_tool;
-        let is_resizing = tool_state.resizing_object_id.is_some();
+        let is_resizing =
+            tool_state.resizing_object_id.is_some() || tool_state.rotating_object_id.is_some();
         drop(tool_state);
 
         // Store drag start position as RAW window coordinates
@@ -82,9 +136,17 @@ pub fn setup_drag_gesture(
         // Conversion to canvas coordinates happens only in drag_end
         let mut tool_state = state.tool_state.borrow_mut();
         tool_state.drag_start = Some((x, y));
-        tool_state.last_drag_offset = Some((0.0, 0.0)); // Reset offset tracking
         tool_state.marquee_selecting = false; // Reset marquee state
 
+        // A new measurement replaces the previous one
+        if current_tool == ToolMode::Measure {
+            let start = measure_point(&state, &app_state_drag_begin, x, y);
+            tool_state.measure_line = Some(Measurement::new(start, start));
+            drop(tool_state);
+            drawing_area_begin.queue_draw();
+            return;
+        }
+
         // Check if we should start marquee selection
         // Condition: Select mode + not resizing + no object under cursor
         if current_tool == ToolMode::Select && !is_resizing {
@@ -92,15 +154,19 @@ pub fn setup_drag_gesture(
             let doc_coords = widget_to_document(x, y, &state);
 
             // Check if clicking on an object
+            let page_index = app_state_drag_begin.active_page_index();
             let clicked_on_object = app_state_drag_begin.with_active_document(|doc| {
-                if let Some(page) = doc.pages.first() {
-                    let objects: Vec<(uuid::Uuid, &Rect)> = page.elements.iter()
+                if let Some(page) = doc.pages.get(page_index) {
+                    let objects: Vec<(uuid::Uuid, &Rect)> = page.layered_elements().into_iter()
+                        .filter(|e| e.is_visible())
                         .map(|e| match e {
                             DocumentElement::Shape(s) => (s.id, &s.bounds),
                             DocumentElement::Text(t) => (t.id, &t.bounds),
                             DocumentElement::Image(i) => (i.id, &i.bounds),
                             DocumentElement::Frame(f) => (f.id, &f.bounds),
                             DocumentElement::Group(g) => (g.id, &g.bounds),
+                            DocumentElement::Table(t) => (t.id, &t.bounds),
+                            DocumentElement::QrCode(q) => (q.id, &q.bounds),
                         })
                         .collect();
                     HitTest::hit_test(&objects, doc_coords.x, doc_coords.y).is_some()
@@ -112,17 +178,27 @@ pub fn setup_drag_gesture(
             if !clicked_on_object {
                 // Start marquee selection
                 tool_state.marquee_selecting = true;
-                eprintln!("📦 Starting marquee selection at ({:.1}, {:.1})", x, y);
+                tracing::debug!("📦 Starting marquee selection at ({:.1}, {:.1})", x, y);
             }
         }
         drop(tool_state);
     });
 
     let render_state_update = render_state.clone();
+    let drawing_area_drag = drawing_area.clone();
     let app_state_drag_update = app_state.clone();
-    drag_gesture.connect_drag_update(move |_gesture, offset_x, offset_y| {
+    drag_gesture.connect_drag_update(move |gesture, offset_x, offset_y| {
         let state = render_state_update.clone();
 
+        let guide_drag_start = state.tool_state.borrow().drag_start;
+        if let Some((start_x, start_y)) = guide_drag_start {
+            if super::guide_drag::update_guide_drag(&state, start_x + offset_x, start_y + offset_y)
+            {
+                drawing_area_drag.queue_draw();
+                return;
+            }
+        }
+
         // Extract all values we need from tool_state first, then drop the borrow
         let (start_x, start_y, current_tool, is_resizing, resizing_object_id, resize_handle, marquee_selecting) = {
             let tool_state = state.tool_state.borrow();
@@ -144,11 +220,6 @@ pub fn setup_drag_gesture(
         let current_x = start_x + offset_x;
         let current_y = start_y + offset_y;
 
-        eprintln!("🔵 Drag Update:");
-        eprintln!("  Start: ({:.1}, {:.1})", start_x, start_y);
-        eprintln!("  Offset: ({:.1}, {:.1})", offset_x, offset_y);
-        eprintln!("  Current: ({:.1}, {:.1})", current_x, current_y);
-
         tracing::info!(
             "drag update [{:?}]: from ({:.0}, {:.0}) to ({:.0}, {:.0}), offset=({:.1}, {:.1})",
             current_tool,
@@ -160,150 +231,108 @@ pub fn setup_drag_gesture(
             offset_y
         );
 
-        if is_resizing {
-            // REAL-TIME RESIZE with delta calculation
-            // Calculate only the delta from last frame to avoid cumulative growth
-            if let (Some(object_id), Some(handle)) = (resizing_object_id, resize_handle) {
-                let (last_offset_x, last_offset_y) = {
-                    let tool_state = state.tool_state.borrow();
-                    tool_state.last_drag_offset.unwrap_or((0.0, 0.0))
-                };
-
-                // Delta is the change from last frame
-                let delta_x_pixels = offset_x - last_offset_x;
-                let delta_y_pixels = offset_y - last_offset_y;
-
-                let config = state.config.borrow();
-                let delta_x = delta_x_pixels / config.zoom;
-                let delta_y = delta_y_pixels / config.zoom;
-                let snap_enabled = config.snap_to_grid;
-                let grid_spacing = config.grid_spacing();
-                drop(config);
-
-                // Update document with delta
-                let _ = app_state_drag_update.with_mutable_active_document(|document| {
-                    if let Some(page) = document.pages.first_mut() {
-                        for element in page.elements.iter_mut() {
-                            match element {
-                                DocumentElement::Text(text) if text.id == object_id => {
-                                    let mut new_bounds = calculate_resize_bounds(
-                                        &text.bounds,
-                                        handle,
-                                        delta_x,
-                                        delta_y,
-                                    );
-                                    if snap_enabled {
-                                        new_bounds = snap_rect_to_grid(&new_bounds, grid_spacing);
-                                    }
-                                    text.bounds = new_bounds;
-                                    return true;
-                                }
-                                DocumentElement::Shape(shape) if shape.id == object_id => {
-                                    let mut new_bounds = calculate_resize_bounds(
-                                        &shape.bounds,
-                                        handle,
-                                        delta_x,
-                                        delta_y,
-                                    );
-                                    if snap_enabled {
-                                        new_bounds = snap_rect_to_grid(&new_bounds, grid_spacing);
-                                    }
-                                    shape.bounds = new_bounds;
-                                    return true;
-                                }
-                                DocumentElement::Image(image) if image.id == object_id => {
-                                    let mut new_bounds = calculate_resize_bounds(
-                                        &image.bounds,
-                                        handle,
-                                        delta_x,
-                                        delta_y,
-                                    );
-                                    if snap_enabled {
-                                        new_bounds = snap_rect_to_grid(&new_bounds, grid_spacing);
-                                    }
-                                    image.bounds = new_bounds;
-                                    return true;
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                    false
-                });
+        let rotating_object_id = state.tool_state.borrow().rotating_object_id;
+        if let Some(object_id) = rotating_object_id {
+            // REAL-TIME ROTATION: Shift snaps to 15° steps
+            let snap = gesture
+                .current_event_state()
+                .contains(gdk::ModifierType::SHIFT_MASK);
+            let page_index = app_state_drag_update.active_page_index();
+            let doc_point = widget_to_document(current_x, current_y, &state);
+            if let Some(angle) = rotation_at(
+                &app_state_drag_update,
+                page_index,
+                object_id,
+                doc_point,
+                snap,
+            ) {
+                // The rotation extent covers every angle, so one mark suffices
+                state.mark_elements_dirty(&app_state_drag_update, &[object_id]);
+                set_element_rotation(&app_state_drag_update, page_index, object_id, angle);
+                let label_pos = Point::new(doc_point.x as f32, doc_point.y as f32);
+                state.tool_state.borrow_mut().rotation_label = Some((label_pos, angle));
+            }
 
-                // Update last offset for next frame
-                let mut tool_state = state.tool_state.borrow_mut();
-                tool_state.last_drag_offset = Some((offset_x, offset_y));
-                drop(tool_state);
+            *state.drag_box.borrow_mut() = None;
+        } else if is_resizing {
+            // REAL-TIME RESIZE from the bounds captured at resize start
+            // Recomputing from the original bounds keeps the preview free of
+            // cumulative drift and matches what drag_end commits
+            let original_bounds = state.tool_state.borrow().resize_element_bounds;
+            if let (Some(object_id), Some(handle), Some(original_bounds)) =
+                (resizing_object_id, resize_handle, original_bounds)
+            {
+                let page_index = app_state_drag_update.active_page_index();
+                let keep_aspect = keeps_aspect_ratio(
+                    &app_state_drag_update,
+                    page_index,
+                    object_id,
+                    gesture.current_event_state(),
+                );
+                let (new_bounds, snap_lines) = resized_bounds(
+                    &state,
+                    &app_state_drag_update,
+                    &original_bounds,
+                    handle,
+                    offset_x,
+                    offset_y,
+                    keep_aspect,
+                );
+                // Show which edges landed on a guide or grid line
+                *state.snap_lines.borrow_mut() = snap_lines;
+                state.mark_elements_dirty(&app_state_drag_update, &[object_id]);
+                set_element_bounds(&app_state_drag_update, page_index, object_id, new_bounds);
+                state.mark_elements_dirty(&app_state_drag_update, &[object_id]);
+                state.tool_state.borrow_mut().measurement =
+                    Some((cursor_point(current_x, current_y, &state), new_bounds));
             }
 
             // Don't show drag_box preview during resize
             *state.drag_box.borrow_mut() = None;
         } else if current_tool == ToolMode::Select {
             // SMART GUIDES: Calculate and show smart guides during Select tool dragging
-            let selected = state.selected_ids.borrow();
-            if !selected.is_empty() && (offset_x.abs() > 2.0 || offset_y.abs() > 2.0) {
-                let selected_ids: Vec<uuid::Uuid> = selected.clone();
-                drop(selected);
-
+            let selected_ids = movable_ids(&app_state_drag_update, &state.selected_ids.borrow());
+            if !selected_ids.is_empty() && (offset_x.abs() > 2.0 || offset_y.abs() > 2.0) {
                 let config = state.config.borrow();
                 let delta_x = offset_x / config.zoom;
                 let delta_y = offset_y / config.zoom;
                 drop(config);
 
-                // Get bounds of first selected element for smart guide calculation
-                if let Some(first_id) = selected_ids.first() {
-                    let first_id = *first_id;
-                    let snap_data: Option<(Rect, Vec<ObjectAlignmentPoints>)> = app_state_drag_update.with_active_document(|doc| {
-                        let page = doc.pages.first()?;
-                        let mut dragging_bounds: Option<Rect> = None;
-                        let mut others: Vec<ObjectAlignmentPoints> = Vec::new();
-
-                        for element in &page.elements {
-                            let (id, bounds) = match element {
-                                DocumentElement::Shape(s) => (s.id, &s.bounds),
-                                DocumentElement::Text(t) => (t.id, &t.bounds),
-                                DocumentElement::Image(i) => (i.id, &i.bounds),
-                                DocumentElement::Frame(f) => (f.id, &f.bounds),
-                                DocumentElement::Group(g) => (g.id, &g.bounds),
-                            };
-
-                            if id == first_id {
-                                // Calculate the preview position
-                                let preview_bounds = Rect {
-                                    origin: Point {
-                                        x: bounds.origin.x + delta_x as f32,
-                                        y: bounds.origin.y + delta_y as f32,
-                                    },
-                                    size: bounds.size,
-                                };
-                                dragging_bounds = Some(preview_bounds);
-                            } else if !selected_ids.contains(&id) {
-                                others.push(ObjectAlignmentPoints::from_rect(id, bounds));
-                            }
-                        }
+                // Object-edge snapping: show where the selection would align
+                let config = state.config.borrow();
+                let snap_to_objects = config.snap_to_objects;
+                let threshold = config.guide_snap_distance / config.zoom as f32;
+                drop(config);
 
-                        Some((dragging_bounds?, others))
-                    }).flatten();
-
-                    if let Some((bounds, others)) = snap_data {
-                        // Calculate smart guides
-                        let smart_engine = SmartGuideEngine::default();
-                        let result = smart_engine.calculate_snap(
-                            &bounds,
-                            &others,
-                            1200.0, // canvas width
-                            1600.0, // canvas height
-                        );
+                let (delta_x, delta_y) = if snap_to_objects {
+                    let (snapped_x, snapped_y, snap_lines) = snap_move_delta(
+                        &app_state_drag_update,
+                        &selected_ids,
+                        delta_x as f32,
+                        delta_y as f32,
+                        threshold,
+                    );
+                    *state.snap_lines.borrow_mut() = snap_lines;
+                    (snapped_x, snapped_y)
+                } else {
+                    state.snap_lines.borrow_mut().clear();
+                    (delta_x as f32, delta_y as f32)
+                };
 
-                        // Update snap lines for rendering
-                        *state.snap_lines.borrow_mut() = result.snap_lines;
-                    }
-                }
+                // Readout of where the selection will land
+                let measurement =
+                    selection_bounds(&app_state_drag_update, &selected_ids).map(|mut bounds| {
+                        bounds.origin.x += delta_x;
+                        bounds.origin.y += delta_y;
+                        (cursor_point(current_x, current_y, &state), bounds)
+                    });
+                state.tool_state.borrow_mut().measurement = measurement;
+                // Only the overlays move until drag_end commits the move
+                state.mark_elements_dirty(&app_state_drag_update, &selected_ids);
             } else {
-                drop(selected);
                 // Clear snap lines if not dragging
                 state.snap_lines.borrow_mut().clear();
+                state.tool_state.borrow_mut().measurement = None;
             }
 
             // Show marquee selection rectangle if marquee_selecting is active
@@ -328,16 +357,27 @@ pub fn setup_drag_gesture(
                     },
                 };
 
+                state.mark_dirty(marquee_rect);
                 *state.drag_box.borrow_mut() = Some(marquee_rect);
             } else {
                 *state.drag_box.borrow_mut() = None;
             }
+        } else if current_tool == ToolMode::Measure {
+            // MEASUREMENT: Move the free end of the dimension line
+            let end = measure_point(&state, &app_state_drag_update, current_x, current_y);
+            if let Some(measurement) = state.tool_state.borrow_mut().measure_line.as_mut() {
+                measurement.end = end;
+            }
+            *state.drag_box.borrow_mut() = None;
+            state.snap_lines.borrow_mut().clear();
         } else {
             // SHAPE CREATION: Show drag_box preview for new shapes
             // Convert widget coordinates to document coordinates using unified helper
             // Note: GTK4 GestureDrag provides widget-relative coordinates
-            let start_doc = widget_to_document(start_x, start_y, &state);
-            let current_doc = widget_to_document(current_x, current_y, &state);
+            let start_doc =
+                snap_creation_point(&state, widget_to_document(start_x, start_y, &state));
+            let current_doc =
+                snap_creation_point(&state, widget_to_document(current_x, current_y, &state));
 
             let doc_x1 = start_doc.x;
             let doc_y1 = start_doc.y;
@@ -362,6 +402,7 @@ pub fn setup_drag_gesture(
                 },
             };
 
+            state.mark_dirty(drag_rect);
             *state.drag_box.borrow_mut() = Some(drag_rect);
             // Clear snap lines for shape creation
             state.snap_lines.borrow_mut().clear();
@@ -373,12 +414,22 @@ pub fn setup_drag_gesture(
     let render_state_end = render_state.clone();
     let drawing_area_end = drawing_area.clone();
     let app_state_drag_end = app_state.clone();
+    let ime_entry_end = ime_entry.clone();
 
-    drag_gesture.connect_drag_end(move |_gesture, offset_x, offset_y| {
+    drag_gesture.connect_drag_end(move |gesture, offset_x, offset_y| {
         let state = render_state_end.clone();
 
+        let guide_drag_start = state.tool_state.borrow().drag_start;
+        if let Some((start_x, start_y)) = guide_drag_start {
+            if super::guide_drag::end_guide_drag(&state, start_x + offset_x, start_y + offset_y) {
+                state.tool_state.borrow_mut().drag_start = None;
+                drawing_area_end.queue_draw();
+                return;
+            }
+        }
+
         // Extract all values we need from tool_state, then drop the borrow immediately
-        let (start_x, start_y, current_tool, is_resizing, resizing_object_id, resize_handle, resize_original_bounds, resize_element_bounds, marquee_selecting) = {
+        let (start_x, start_y, current_tool, is_resizing, resizing_object_id, resize_handle, resize_element_bounds, marquee_selecting) = {
             let tool_state = state.tool_state.borrow();
             if let Some((start_x, start_y)) = tool_state.drag_start {
                 (
@@ -388,7 +439,6 @@ pub fn setup_drag_gesture(
                     tool_state.resizing_object_id.is_some(),
                     tool_state.resizing_object_id,
                     tool_state.resize_handle,
-                    tool_state.resize_original_bounds,
                     tool_state.resize_element_bounds,
                     tool_state.marquee_selecting,
                 )
@@ -406,173 +456,168 @@ pub fn setup_drag_gesture(
                 current_tool, offset_x, offset_y, start_x, start_y, current_x, current_y
             );
 
-            if is_resizing && (offset_x.abs() > 2.0 || offset_y.abs() > 2.0) {
-                // Apply resize
-                eprintln!("🔄 RESIZE DETECTED: is_resizing={}, resizing_object_id={:?}, resize_handle={:?}",
-                    is_resizing, resizing_object_id, resize_handle);
-
-                if let (Some(object_id), Some(handle), Some(_mouse_pos)) = (resizing_object_id, resize_handle, resize_original_bounds) {
-                    // Calculate document-space delta
-                    let config = state.config.borrow();
-                    let delta_x = offset_x / config.zoom;
-                    let delta_y = offset_y / config.zoom;
-                    let snap_enabled = config.snap_to_grid;
-                    let grid_spacing = config.grid_spacing();
-                    drop(config);
-
-                    eprintln!("✏️ Applying resize: delta=({:.2}, {:.2}), handle={:?}", delta_x, delta_y, handle);
-
-                    // Apply resize directly to the document
-                    let resize_applied = app_state_drag_end.with_mutable_active_document(|document| {
-                        if let Some(page) = document.pages.first_mut() {
-                            for element in page.elements.iter_mut() {
-                                match element {
-                                    DocumentElement::Text(text) if text.id == object_id => {
-                                        let old_bounds = text.bounds;
-                                        let mut new_bounds = calculate_resize_bounds(&text.bounds, handle, delta_x, delta_y);
-                                        if snap_enabled {
-                                            new_bounds = snap_rect_to_grid(&new_bounds, grid_spacing);
-                                        }
-                                        text.bounds = new_bounds;
-                                        eprintln!("✅ Resized TEXT {} with handle {:?}: {:?} -> {:?}",
-                                            object_id, handle, old_bounds, text.bounds);
-                                        return true;
-                                    }
-                                    DocumentElement::Shape(shape) if shape.id == object_id => {
-                                        let old_bounds = shape.bounds;
-                                        let mut new_bounds = calculate_resize_bounds(&shape.bounds, handle, delta_x, delta_y);
-                                        if snap_enabled {
-                                            new_bounds = snap_rect_to_grid(&new_bounds, grid_spacing);
-                                        }
-                                        shape.bounds = new_bounds;
-                                        eprintln!("✅ Resized SHAPE {} with handle {:?}: {:?} -> {:?}",
-                                            object_id, handle, old_bounds, shape.bounds);
-                                        return true;
-                                    }
-                                    DocumentElement::Image(image) if image.id == object_id => {
-                                        let old_bounds = image.bounds;
-                                        let mut new_bounds = calculate_resize_bounds(&image.bounds, handle, delta_x, delta_y);
-                                        if snap_enabled {
-                                            new_bounds = snap_rect_to_grid(&new_bounds, grid_spacing);
-                                        }
-                                        image.bounds = new_bounds;
-                                        eprintln!("✅ Resized IMAGE {} with handle {:?}: {:?} -> {:?}",
-                                            object_id, handle, old_bounds, image.bounds);
-                                        return true;
-                                    }
-                                    DocumentElement::Frame(frame) if frame.id == object_id => {
-                                        let old_bounds = frame.bounds;
-                                        let mut new_bounds = calculate_resize_bounds(&frame.bounds, handle, delta_x, delta_y);
-                                        if snap_enabled {
-                                            new_bounds = snap_rect_to_grid(&new_bounds, grid_spacing);
-                                        }
-                                        frame.bounds = new_bounds;
-                                        eprintln!("✅ Resized FRAME {} with handle {:?}: {:?} -> {:?}",
-                                            object_id, handle, old_bounds, frame.bounds);
-                                        return true;
-                                    }
-                                    _ => {}
-                                }
-                            }
-                        }
-                        false
-                    });
-
-                    if resize_applied.unwrap_or(false) {
-                        // Create undo command for resize operation
-                        if let Some(old_bounds) = resize_element_bounds {
-                            // Get the new bounds from the document
-                            let new_bounds: Option<Rect> = app_state_drag_end.with_active_document(|doc| {
-                                if let Some(page) = doc.pages.first() {
-                                    for element in &page.elements {
-                                        let (id, bounds) = match element {
-                                            DocumentElement::Shape(s) => (s.id, &s.bounds),
-                                            DocumentElement::Text(t) => (t.id, &t.bounds),
-                                            DocumentElement::Image(i) => (i.id, &i.bounds),
-                                            DocumentElement::Frame(f) => (f.id, &f.bounds),
-                                            DocumentElement::Group(g) => (g.id, &g.bounds),
-                                        };
-                                        if id == object_id {
-                                            return Some(*bounds);
-                                        }
-                                    }
-                                }
-                                None
-                            }).flatten();
-
-                            if let Some(new_bounds) = new_bounds {
-                                let page_index = app_state_drag_end.active_page_index();
-                                let command = crate::undo_redo::AppResizeCommand::new(
-                                    app_state_drag_end.clone(),
-                                    object_id,
-                                    page_index,
-                                    old_bounds,
-                                    new_bounds,
-                                );
-                                app_state_drag_end.push_command(Box::new(command));
-                                tracing::info!("✅ Resize undo command created for object {}", object_id);
-                            }
-                        }
+            let (rotating_object_id, rotation_original) = {
+                let tool_state = state.tool_state.borrow();
+                (tool_state.rotating_object_id, tool_state.rotation_original)
+            };
+            if let (Some(object_id), Some(old_rotation)) = (rotating_object_id, rotation_original) {
+                let page_index = app_state_drag_end.active_page_index();
+                let snap = gesture
+                    .current_event_state()
+                    .contains(gdk::ModifierType::SHIFT_MASK);
+                let doc_point = widget_to_document(current_x, current_y, &state);
+                let new_rotation =
+                    rotation_at(&app_state_drag_end, page_index, object_id, doc_point, snap);
+
+                // Put the pre-drag angle back so the command captures it as the undo value
+                set_element_rotation(&app_state_drag_end, page_index, object_id, old_rotation);
+                match new_rotation {
+                    Some(angle) if (angle - old_rotation).abs() > 0.001 => {
+                        let command = crate::undo_redo::AppPropertyChangeCommand::new(
+                            app_state_drag_end.clone(),
+                            vec![object_id],
+                            page_index,
+                            crate::undo_redo::PropertyValue::Rotation(angle),
+                        );
+                        app_state_drag_end.push_command(Box::new(command));
+                        app_state_drag_end.mark_as_modified();
+                        tracing::info!(
+                            "✅ Rotation undo command created for object {}: {:.1}° -> {:.1}°",
+                            object_id,
+                            old_rotation,
+                            angle
+                        );
+                    }
+                    _ => {}
+                }
+            } else if is_resizing {
+                let page_index = app_state_drag_end.active_page_index();
+                match (resizing_object_id, resize_handle, resize_element_bounds) {
+                    (Some(object_id), Some(handle), Some(old_bounds))
+                        if offset_x.abs() > 2.0 || offset_y.abs() > 2.0 =>
+                    {
+                        let keep_aspect = keeps_aspect_ratio(
+                            &app_state_drag_end,
+                            page_index,
+                            object_id,
+                            gesture.current_event_state(),
+                        );
+                        let (new_bounds, _) = resized_bounds(
+                            &state,
+                            &app_state_drag_end,
+                            &old_bounds,
+                            handle,
+                            offset_x,
+                            offset_y,
+                            keep_aspect,
+                        );
+                        eprintln!(
+                            "✏️ Applying resize: handle={:?}, {:?} -> {:?}",
+                            handle, old_bounds, new_bounds
+                        );
 
-                        // Mark document as modified after resize
+                        // The command sets absolute bounds, so the live preview is simply
+                        // overwritten and undo restores the pre-drag bounds exactly
+                        let command = crate::undo_redo::AppResizeCommand::new(
+                            app_state_drag_end.clone(),
+                            object_id,
+                            page_index,
+                            old_bounds,
+                            new_bounds,
+                        );
+                        app_state_drag_end.push_command(Box::new(command));
                         app_state_drag_end.mark_as_modified();
-                    } else {
-                        eprintln!("❌ WARNING: Resize was not applied to document!");
+                        tracing::info!("✅ Resize undo command created for object {}", object_id);
+                    }
+                    (Some(object_id), Some(_), Some(old_bounds)) => {
+                        // Too small to count as a resize: drop the live preview
+                        set_element_bounds(&app_state_drag_end, page_index, object_id, old_bounds);
+                    }
+                    _ => {
+                        eprintln!("❌ ERROR: Missing resize state - object_id={:?}, handle={:?}, bounds={:?}",
+                            resizing_object_id, resize_handle, resize_element_bounds);
                     }
-                } else {
-                    eprintln!("❌ ERROR: Missing resize state - object_id={:?}, handle={:?}, bounds={:?}",
-                        resizing_object_id, resize_handle, resize_original_bounds);
                 }
             } else if current_tool == ToolMode::Select && marquee_selecting && (offset_x.abs() > 5.0 || offset_y.abs() > 5.0) {
-                // MARQUEE SELECTION: Select all objects within the marquee rectangle
+                // MARQUEE SELECTION: Select all objects touching the marquee rectangle
+                // (Ctrl/Cmd: only objects fully inside it; Shift: add to the selection)
+                let modifiers = gesture.current_event_state();
+                let extend = modifiers.contains(gdk::ModifierType::SHIFT_MASK);
+                let contained_only = modifiers.intersects(
+                    gdk::ModifierType::CONTROL_MASK | gdk::ModifierType::META_MASK,
+                );
                 let start_doc = widget_to_document(start_x, start_y, &state);
                 let current_doc = widget_to_document(current_x, current_y, &state);
 
                 // Find all objects in the marquee selection area
+                let page_index = app_state_drag_end.active_page_index();
                 let selected_by_marquee: Vec<uuid::Uuid> = app_state_drag_end.with_active_document(|doc| {
-                    if let Some(page) = doc.pages.first() {
-                        let objects: Vec<(uuid::Uuid, &Rect)> = page.elements.iter()
+                    if let Some(page) = doc.pages.get(page_index) {
+                        let objects: Vec<(uuid::Uuid, &Rect)> = page.layered_elements().into_iter()
+                            .filter(|e| e.is_visible())
                             .map(|e| match e {
                                 DocumentElement::Shape(s) => (s.id, &s.bounds),
                                 DocumentElement::Text(t) => (t.id, &t.bounds),
                                 DocumentElement::Image(i) => (i.id, &i.bounds),
                                 DocumentElement::Frame(f) => (f.id, &f.bounds),
                                 DocumentElement::Group(g) => (g.id, &g.bounds),
+                                DocumentElement::Table(t) => (t.id, &t.bounds),
+                                DocumentElement::QrCode(q) => (q.id, &q.bounds),
                             })
                             .collect();
-                        HitTest::hit_test_rect(&objects, start_doc.x, start_doc.y, current_doc.x, current_doc.y)
+                        let (x1, y1, x2, y2) = (start_doc.x, start_doc.y, current_doc.x, current_doc.y);
+                        if contained_only {
+                            HitTest::contained_in_rect(&objects, x1, y1, x2, y2)
+                        } else {
+                            HitTest::hit_test_rect(&objects, x1, y1, x2, y2)
+                        }
                     } else {
                         Vec::new()
                     }
                 }).unwrap_or_default();
 
                 // Update selection with marquee-selected objects
-                if !selected_by_marquee.is_empty() {
-                    let mut selected = state.selected_ids.borrow_mut();
+                let mut selected = state.selected_ids.borrow_mut();
+                if !extend {
                     selected.clear();
-                    selected.extend(selected_by_marquee.iter().copied());
-                    drop(selected);
-                    eprintln!("📦 Marquee selection complete: {} object(s) selected", selected_by_marquee.len());
-                    tracing::info!("Marquee selected {} object(s)", selected_by_marquee.len());
+                }
+                for id in &selected_by_marquee {
+                    if !selected.contains(id) {
+                        selected.push(*id);
+                    }
+                }
+                let total = selected.len();
+                drop(selected);
+                if selected_by_marquee.is_empty() {
+                    tracing::debug!("📦 Marquee selection complete: no objects in selection area");
                 } else {
-                    // Clear selection if nothing was selected
-                    let mut selected = state.selected_ids.borrow_mut();
-                    selected.clear();
-                    drop(selected);
-                    eprintln!("📦 Marquee selection complete: no objects in selection area");
+                    tracing::debug!("📦 Marquee selection complete: {} object(s) selected", total);
+                    tracing::info!("Marquee selected {} object(s)", selected_by_marquee.len());
                 }
             } else if current_tool == ToolMode::Select && !marquee_selecting && (offset_x.abs() > 5.0 || offset_y.abs() > 5.0) {
-                // Move selected objects with Undo support
-                let selected = state.selected_ids.borrow();
-                if !selected.is_empty() {
+                // Move selected objects with Undo support (locked ones stay put)
+                let selected_ids = movable_ids(&app_state_drag_end, &state.selected_ids.borrow());
+                if !selected_ids.is_empty() {
                     // Transform screen offset to document offset
                     let config = state.config.borrow();
-                    let delta_x = (offset_x / config.zoom) as f32;
-                    let delta_y = (offset_y / config.zoom) as f32;
+                    let mut delta_x = (offset_x / config.zoom) as f32;
+                    let mut delta_y = (offset_y / config.zoom) as f32;
+                    let snap_to_objects = config.snap_to_objects;
+                    let threshold = config.guide_snap_distance / config.zoom as f32;
                     drop(config);
 
-                    let selected_ids: Vec<uuid::Uuid> = selected.clone();
-                    drop(selected);
+                    // Apply the same object-edge snap that was previewed during the drag
+                    if snap_to_objects {
+                        let (snapped_x, snapped_y, _) = snap_move_delta(
+                            &app_state_drag_end,
+                            &selected_ids,
+                            delta_x,
+                            delta_y,
+                            threshold,
+                        );
+                        delta_x = snapped_x;
+                        delta_y = snapped_y;
+                    }
 
                     // Create and execute move command with undo support
                     let page_index = app_state_drag_end.active_page_index();
@@ -590,81 +635,49 @@ pub fn setup_drag_gesture(
 
                     tracing::info!("Moved {} selected object(s) (with undo support)", selected_ids.len());
                 }
-            } else if current_tool != ToolMode::Select && (offset_x.abs() > 5.0 || offset_y.abs() > 5.0) {
+            } else if current_tool == ToolMode::Measure {
+                // The measurement stays on screen until the next one or a tool switch
+                let end = measure_point(&state, &app_state_drag_end, current_x, current_y);
+                if let Some(measurement) = state.tool_state.borrow_mut().measure_line.as_mut() {
+                    measurement.end = end;
+                }
+            } else if current_tool == ToolMode::Text && !is_drag(offset_x, offset_y) {
+                // A click without a drag makes one default-size box at the press point
+                let doc_coords = widget_to_document(start_x, start_y, &state);
+                super::gesture_click::create_text_box_at(
+                    &state,
+                    &app_state_drag_end,
+                    &drawing_area_end,
+                    &ime_entry_end,
+                    doc_coords.x,
+                    doc_coords.y,
+                );
+            } else if current_tool != ToolMode::Select && is_drag(offset_x, offset_y) {
                 // Shape/Text creation based on tool
                 // Convert widget coordinates to document coordinates using unified helper
                 // Note: GTK4 GestureDrag provides widget-relative coordinates
-                let start_doc = widget_to_document(start_x, start_y, &state);
-                let current_doc = widget_to_document(current_x, current_y, &state);
-
-                let doc_start_x = start_doc.x;
-                let doc_start_y = start_doc.y;
-                let doc_current_x = current_doc.x;
-                let doc_current_y = current_doc.y;
+                let start_doc =
+                    snap_creation_point(&state, widget_to_document(start_x, start_y, &state));
+                let current_doc =
+                    snap_creation_point(&state, widget_to_document(current_x, current_y, &state));
 
                 eprintln!("📐 Shape creation coordinate transformation:");
                 eprintln!("  Widget: start=({:.1}, {:.1}), current=({:.1}, {:.1})", start_x, start_y, current_x, current_y);
-                eprintln!("  Document: start=({:.2}, {:.2}), current=({:.2}, {:.2})", doc_start_x, doc_start_y, doc_current_x, doc_current_y);
+                eprintln!("  Document: start=({:.2}, {:.2}), current=({:.2}, {:.2})", start_doc.x, start_doc.y, current_doc.x, current_doc.y);
 
                 tracing::info!("Creating {} element with drag offset ({:.1}, {:.1})", current_tool.name(), offset_x, offset_y);
 
-                let element = match current_tool {
-                    ToolMode::Rectangle => ShapeFactory::create_rectangle(
-                        doc_start_x.min(doc_current_x),
-                        doc_start_y.min(doc_current_y),
-                        (doc_start_x - doc_current_x).abs(),
-                        (doc_start_y - doc_current_y).abs(),
-                    ),
-                    ToolMode::Circle => ShapeFactory::create_circle(
-                        doc_start_x.min(doc_current_x),
-                        doc_start_y.min(doc_current_y),
-                        (doc_start_x - doc_current_x).abs(),
-                        (doc_start_y - doc_current_y).abs(),
-                    ),
-                    ToolMode::Line => ShapeFactory::create_line(
-                        doc_start_x,
-                        doc_start_y,
-                        doc_current_x,
-                        doc_current_y,
-                    ),
-                    ToolMode::Arrow => ShapeFactory::create_arrow(
-                        doc_start_x,
-                        doc_start_y,
-                        doc_current_x,
-                        doc_current_y,
-                    ),
-                    ToolMode::Image => ShapeFactory::create_image(
-                        doc_start_x.min(doc_current_x),
-                        doc_start_y.min(doc_current_y),
-                        (doc_start_x - doc_current_x).abs(),
-                        (doc_start_y - doc_current_y).abs(),
-                    ),
-                    ToolMode::Text => {
-                        tracing::info!("Creating text box at ({:.0}, {:.0}) size ({:.0}x{:.0}) (document coords)",
-                            doc_start_x, doc_start_y, (doc_start_x - doc_current_x).abs(), (doc_start_y - doc_current_y).abs());
-                        ShapeFactory::create_text(
-                            doc_start_x,
-                            doc_start_y,
-                            (doc_start_x - doc_current_x).abs(),
-                            (doc_start_y - doc_current_y).abs(),
-                            "テキストを入力".to_string(),
-                        )
-                    },
-                    _ => {
-                        tracing::warn!("Tool {:?} is not supported for creation", current_tool);
-                        return;
-                    }
+                let Some(element) = created_element(current_tool, start_doc, current_doc) else {
+                    tracing::warn!("Tool {:?} is not supported for creation", current_tool);
+                    return;
                 };
 
-                // Add element to document
-                if let Err(e) = app_state_drag_end.add_element_to_active_page(element) {
-                    tracing::warn!("Failed to add element: {}", e);
+                // Add element to document through the undo stack
+                if !add_created_element(&app_state_drag_end, element) {
+                    tracing::warn!("Failed to add element: No active document or pages");
                 } else {
                     tracing::info!("{} element added to document", current_tool.name());
 
-                    // Mark document as modified after element creation
-                    app_state_drag_end.mark_as_modified();
-
                     // Auto-switch back to Select tool after creating an element
                     let mut tool_state_auto = state.tool_state.borrow_mut();
                     tool_state_auto.current_tool = ToolMode::Select;
@@ -675,8 +688,8 @@ pub fn setup_drag_gesture(
                     drawing_area_end.queue_draw();
                 }
             } else {
-                tracing::debug!("Drag ignored: tool={:?}, offset=({:.1}, {:.1}), threshold=5.0px",
-                    current_tool, offset_x, offset_y);
+                tracing::debug!("Drag ignored: tool={:?}, offset=({:.1}, {:.1}), threshold={}px",
+                    current_tool, offset_x, offset_y, DRAG_THRESHOLD);
             }
         } // End of scope block
 
@@ -687,6 +700,10 @@ pub fn setup_drag_gesture(
         tool_state.resize_handle = None;
         tool_state.resize_original_bounds = None;
         tool_state.resize_element_bounds = None;
+        tool_state.rotating_object_id = None;
+        tool_state.rotation_original = None;
+        tool_state.rotation_label = None;
+        tool_state.measurement = None;
         tool_state.marquee_selecting = false;
         drop(tool_state);
 
@@ -698,3 +715,304 @@ pub fn setup_drag_gesture(
 
     drawing_area.add_controller(drag_gesture);
 }
+
+/// 移動量がクリックのしきい値を超えているか
+fn is_drag(offset_x: f64, offset_y: f64) -> bool {
+    offset_x.abs() > DRAG_THRESHOLD || offset_y.abs() > DRAG_THRESHOLD
+}
+
+/// 作成ツールのドラッグ範囲から新しい要素を作成（作成できないツールは `None`）
+fn created_element(
+    tool: ToolMode,
+    start: DocumentCoords,
+    end: DocumentCoords,
+) -> Option<DocumentElement> {
+    let (x, y) = (start.x.min(end.x), start.y.min(end.y));
+    let (width, height) = ((start.x - end.x).abs(), (start.y - end.y).abs());
+    let element = match tool {
+        ToolMode::Rectangle => ShapeFactory::create_rectangle(x, y, width, height),
+        ToolMode::Circle => ShapeFactory::create_circle(x, y, width, height),
+        ToolMode::Line => ShapeFactory::create_line(start.x, start.y, end.x, end.y),
+        ToolMode::Arrow => ShapeFactory::create_arrow(start.x, start.y, end.x, end.y),
+        ToolMode::Image => ShapeFactory::create_image(x, y, width, height),
+        ToolMode::Text => {
+            tracing::info!(
+                "Creating text box at ({:.0}, {:.0}) size ({:.0}x{:.0}) (document coords)",
+                start.x,
+                start.y,
+                width,
+                height
+            );
+            let placeholder = "テキストを入力".to_string();
+            ShapeFactory::create_text(start.x, start.y, width, height, placeholder)
+        }
+        _ => return None,
+    };
+    Some(element)
+}
+
+/// 作成した要素を Undo 可能なコマンドでアクティブページに追加
+///
+/// アクティブページがなければ何もせず `false` を返します。
+fn add_created_element(app_state: &AppState, element: DocumentElement) -> bool {
+    let page_index = app_state.active_page_index();
+    let page_exists = app_state
+        .with_active_document(|doc| page_index < doc.pages.len())
+        .unwrap_or(false);
+    if !page_exists {
+        return false;
+    }
+    let command = crate::undo_redo::AppCreateCommand::new(app_state.clone(), element, page_index);
+    app_state.push_command(Box::new(command));
+    true
+}
+
+/// 図形作成中の角をガイドとグリッドにスナップ
+fn snap_creation_point(state: &CanvasRenderState, point: DocumentCoords) -> DocumentCoords {
+    let (x, y) = state
+        .config
+        .borrow()
+        .snap_creation_point(point.x as f32, point.y as f32);
+    DocumentCoords::new(x as f64, y as f64)
+}
+
+/// Measure tool endpoint at a widget position, snapped to guides and element edges
+fn measure_point(state: &CanvasRenderState, app_state: &AppState, x: f64, y: f64) -> Point {
+    let point = cursor_point(x, y, state);
+    let elements: Vec<Rect> = app_state
+        .active_page()
+        .map(|page| {
+            page.layered_elements()
+                .into_iter()
+                .filter(|element| element.is_visible())
+                .map(|element| *element.bounds())
+                .collect()
+        })
+        .unwrap_or_default();
+    snap_measure_point(&state.config.borrow(), point, &elements)
+}
+
+/// Whether a corner resize of `element_id` keeps its aspect ratio
+///
+/// Images keep it unless Shift is held; other elements only while Shift is held.
+fn keeps_aspect_ratio(
+    app_state: &AppState,
+    page_index: usize,
+    element_id: uuid::Uuid,
+    modifiers: gdk::ModifierType,
+) -> bool {
+    let shift = modifiers.contains(gdk::ModifierType::SHIFT_MASK);
+    let is_image = app_state
+        .with_active_document(|doc| {
+            doc.pages
+                .get(page_index)
+                .and_then(|page| page.elements.iter().find(|e| e.id() == element_id))
+                .is_some_and(|element| matches!(element, DocumentElement::Image(_)))
+        })
+        .unwrap_or(false);
+    shift != is_image
+}
+
+/// Bounds for a handle resize, computed from the bounds at resize start
+///
+/// `offset_x`/`offset_y` are the total drag offset in widget pixels. The
+/// moved edges snap to guides or the grid; the returned lines mark the edges
+/// that snapped. With `keep_aspect`, corner handles keep the original aspect
+/// ratio, applied after snapping so the ratio survives it, and only the lines
+/// of edges still on their snap position are kept.
+fn resized_bounds(
+    state: &CanvasRenderState,
+    app_state: &AppState,
+    original_bounds: &Rect,
+    handle: ResizeHandle,
+    offset_x: f64,
+    offset_y: f64,
+    keep_aspect: bool,
+) -> (Rect, Vec<SnapLine>) {
+    let config = state.config.borrow();
+    let new_bounds = calculate_resize_bounds(
+        original_bounds,
+        handle,
+        offset_x / config.zoom,
+        offset_y / config.zoom,
+    );
+    let page_size = app_state.active_page_size().unwrap_or_default();
+    let (new_bounds, mut snap_lines) = config.snap_resize_bounds(&new_bounds, handle, &page_size);
+    if !keep_aspect {
+        return (new_bounds, snap_lines);
+    }
+
+    let constrained = constrain_resize_to_aspect(original_bounds, &new_bounds, handle);
+    snap_lines.retain(|line| {
+        let (start, size) = if line.is_horizontal {
+            (constrained.origin.y, constrained.size.height)
+        } else {
+            (constrained.origin.x, constrained.size.width)
+        };
+        [start, start + size]
+            .iter()
+            .any(|edge| (edge - line.position).abs() < 0.01)
+    });
+    (constrained, snap_lines)
+}
+
+/// Overwrite an element's bounds without recording an undo step (live preview)
+fn set_element_bounds(app_state: &AppState, page_index: usize, element_id: uuid::Uuid, bounds: Rect) {
+    let _ = app_state.with_mutable_active_document(|document| {
+        if let Some(page) = document.pages.get_mut(page_index) {
+            if let Some(element) = page.elements.iter_mut().find(|e| e.id() == element_id) {
+                *element.bounds_mut() = bounds;
+            }
+        }
+    });
+}
+
+/// Rotation for an element when the handle is dragged to `point`
+///
+/// Returns `None` if the element is no longer on the page.
+fn rotation_at(
+    app_state: &AppState,
+    page_index: usize,
+    element_id: uuid::Uuid,
+    point: DocumentCoords,
+    snap: bool,
+) -> Option<f32> {
+    let bounds = app_state
+        .with_active_document(|document| {
+            document
+                .pages
+                .get(page_index)
+                .and_then(|page| page.elements.iter().find(|e| e.id() == element_id))
+                .map(|element| *element.bounds())
+        })
+        .flatten()?;
+    let point = CanvasMousePos::new(point.x, point.y);
+    Some(rotation_angle_at(&bounds, point, snap))
+}
+
+/// Overwrite an element's rotation without recording an undo step (live preview)
+fn set_element_rotation(
+    app_state: &AppState,
+    page_index: usize,
+    element_id: uuid::Uuid,
+    degrees: f32,
+) {
+    let _ = app_state.with_mutable_active_document(|document| {
+        if let Some(page) = document.pages.get_mut(page_index) {
+            if let Some(element) = page.elements.iter_mut().find(|e| e.id() == element_id) {
+                element.set_rotation(degrees);
+            }
+        }
+    });
+}
+
+/// Snap a pending move of the selection to the other objects on the active page
+///
+/// The selection's combined bounds are offset by the delta and snapped with
+/// [`snap_to_object_edges`]. Returns the adjusted delta and the alignment
+/// lines to draw.
+fn snap_move_delta(
+    app_state: &AppState,
+    selected_ids: &[uuid::Uuid],
+    delta_x: f32,
+    delta_y: f32,
+    threshold: f32,
+) -> (f32, f32, Vec<SnapLine>) {
+    let Some(page) = app_state.active_page() else {
+        return (delta_x, delta_y, Vec::new());
+    };
+    let Some(moving) = union_bounds(&page, selected_ids) else {
+        return (delta_x, delta_y, Vec::new());
+    };
+
+    let others: Vec<Rect> = page
+        .layered_elements()
+        .into_iter()
+        .filter(|element| !selected_ids.contains(&element.id()) && element.is_visible())
+        .map(|element| *element.bounds())
+        .collect();
+
+    let mut candidate = moving;
+    candidate.origin.x += delta_x;
+    candidate.origin.y += delta_y;
+    let result = snap_to_object_edges(&candidate, &others, threshold);
+
+    (
+        result.bounds.origin.x - moving.origin.x,
+        result.bounds.origin.y - moving.origin.y,
+        result.snap_lines,
+    )
+}
+
+/// 選択中の要素のうちロックされていないもの（移動の対象）
+///
+/// ロックされたレイヤー上の要素も移動しません。
+fn movable_ids(app_state: &AppState, selected_ids: &[uuid::Uuid]) -> Vec<uuid::Uuid> {
+    let Some(page) = app_state.active_page() else {
+        return Vec::new();
+    };
+    page.elements
+        .iter()
+        .filter(|element| selected_ids.contains(&element.id()) && !element.is_locked())
+        .filter(|element| !page.is_in_locked_layer(element.id()))
+        .map(|element| element.id())
+        .collect()
+}
+
+/// Bounding box of the selected elements on the active page
+fn selection_bounds(app_state: &AppState, selected_ids: &[uuid::Uuid]) -> Option<Rect> {
+    union_bounds(&app_state.active_page()?, selected_ids)
+}
+
+/// Union of the bounds of the elements in `ids`
+fn union_bounds(page: &testruct_core::document::Page, ids: &[uuid::Uuid]) -> Option<Rect> {
+    page.elements
+        .iter()
+        .filter(|element| ids.contains(&element.id()))
+        .map(|element| *element.bounds())
+        .reduce(|current, bounds| {
+            let min_x = current.origin.x.min(bounds.origin.x);
+            let min_y = current.origin.y.min(bounds.origin.y);
+            let max_x =
+                (current.origin.x + current.size.width).max(bounds.origin.x + bounds.size.width);
+            let max_y =
+                (current.origin.y + current.size.height).max(bounds.origin.y + bounds.size.height);
+            Rect::new(
+                Point::new(min_x, min_y),
+                Size::new(max_x - min_x, max_y - min_y),
+            )
+        })
+}
+
+/// Widget position converted to a document point for cursor readouts
+fn cursor_point(x: f64, y: f64, state: &CanvasRenderState) -> Point {
+    let doc = widget_to_document(x, y, state);
+    Point::new(doc.x as f32, doc.y as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_tool_drag_creates_one_text_box() {
+        let app_state = AppState::default();
+        let start = DocumentCoords::new(20.0, 30.0);
+        let end = DocumentCoords::new(220.0, 90.0);
+
+        // The press creates nothing; the release of a drag adds the dragged box
+        assert!(is_drag(200.0, 60.0));
+        let element = created_element(ToolMode::Text, start, end).expect("text tool creates");
+        assert!(add_created_element(&app_state, element));
+
+        let page = app_state.active_page().expect("active page");
+        assert_eq!(page.elements.len(), 1);
+        assert_eq!(
+            *page.elements[0].bounds(),
+            Rect::new(Point::new(20.0, 30.0), Size::new(200.0, 60.0))
+        );
+
+        // Jitter within the threshold still counts as a click
+        assert!(!is_drag(3.0, -4.0));
+    }
+}
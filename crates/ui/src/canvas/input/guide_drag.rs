@@ -0,0 +1,199 @@
+//! ガイドのドラッグ操作モジュール
+//!
+//! ルーラーからガイドを引き出して作成し、既存のガイドをドラッグで移動・削除します。
+//!
+//! - 上のルーラーからドラッグ: 水平ガイドを作成
+//! - 左のルーラーからドラッグ: 垂直ガイドを作成
+//! - Select ツールでガイド付近をドラッグ: ガイドを移動
+//! - ガイドを同じ向きのルーラー上で離す: ガイドを削除
+//!
+//! ガイドは `RenderConfig::guides` にドキュメント座標で保存されます。
+//! ドラッグ中のガイドは `ToolState::guide_drag` にインデックスで記録します。
+//! ルーラーやガイドが非表示のときはガイドを作成・移動しません。
+
+use super::coordinates::widget_to_document;
+use crate::canvas::grid_rendering::{Guide, GuideOrientation};
+use crate::canvas::tools::ToolMode;
+use crate::canvas::CanvasRenderState;
+
+/// ガイドを掴める距離（スクリーンピクセル）
+const GUIDE_HIT_TOLERANCE: f64 = 4.0;
+
+/// ガイドのドラッグを開始
+///
+/// ルーラー上からの場合は新しいガイドを追加し、それ以外は
+/// カーソル付近の既存ガイドを掴みます。ドラッグを開始した場合は `true` を返します。
+pub fn begin_guide_drag(state: &CanvasRenderState, x: f64, y: f64) -> bool {
+    let ruler_size = state.ruler_config.borrow().size;
+    let doc = widget_to_document(x, y, state);
+    let select_tool = state.tool_state.borrow().current_tool == ToolMode::Select;
+
+    let mut config = state.config.borrow_mut();
+    if !config.show_guides {
+        return false;
+    }
+
+    let index = match ruler_orientation(config.show_rulers, ruler_size, x, y) {
+        Some(orientation) => {
+            let position = match orientation {
+                GuideOrientation::Horizontal => doc.y,
+                GuideOrientation::Vertical => doc.x,
+            };
+            config.guides.push(Guide::new(orientation, position as f32));
+            config.guides.len() - 1
+        }
+        None if select_tool => {
+            let tolerance = GUIDE_HIT_TOLERANCE / config.zoom;
+            let Some(index) = guide_at(&config.guides, doc.x, doc.y, tolerance) else {
+                return false;
+            };
+            index
+        }
+        None => return false,
+    };
+    drop(config);
+
+    state.tool_state.borrow_mut().guide_drag = Some(index);
+    tracing::debug!("📏 Guide drag started (guide {})", index);
+    true
+}
+
+/// ドラッグ中のガイドをカーソル位置へ移動
+///
+/// ガイドのドラッグ中でなければ `false` を返します。
+pub fn update_guide_drag(state: &CanvasRenderState, x: f64, y: f64) -> bool {
+    let Some(index) = state.tool_state.borrow().guide_drag else {
+        return false;
+    };
+    move_guide(state, index, x, y);
+    true
+}
+
+/// ガイドのドラッグを終了
+///
+/// 同じ向きのルーラー上で離した場合はガイドを削除します。
+/// ガイドのドラッグ中でなければ `false` を返します。
+pub fn end_guide_drag(state: &CanvasRenderState, x: f64, y: f64) -> bool {
+    let Some(index) = state.tool_state.borrow_mut().guide_drag.take() else {
+        return false;
+    };
+    move_guide(state, index, x, y);
+
+    let ruler_size = state.ruler_config.borrow().size;
+    let mut config = state.config.borrow_mut();
+    let Some(orientation) = config.guides.get(index).map(|guide| guide.orientation) else {
+        return true;
+    };
+    let over_ruler = match orientation {
+        GuideOrientation::Horizontal => y < ruler_size,
+        GuideOrientation::Vertical => x < ruler_size,
+    };
+    if over_ruler {
+        config.guides.remove(index);
+        tracing::info!("🗑 Guide removed");
+    } else {
+        tracing::info!("✅ Guide placed at {:.1}", config.guides[index].position);
+    }
+    true
+}
+
+/// ガイド `index` をウィジェット座標 (`x`, `y`) の位置へ移動
+fn move_guide(state: &CanvasRenderState, index: usize, x: f64, y: f64) {
+    let doc = widget_to_document(x, y, state);
+    if let Some(guide) = state.config.borrow_mut().guides.get_mut(index) {
+        guide.position = match guide.orientation {
+            GuideOrientation::Horizontal => doc.y as f32,
+            GuideOrientation::Vertical => doc.x as f32,
+        };
+    }
+}
+
+/// ルーラー上の位置なら、そこから引き出すガイドの向き
+///
+/// 上のルーラーは水平ガイド、左のルーラーは垂直ガイド。左上の角は対象外です。
+fn ruler_orientation(
+    show_rulers: bool,
+    ruler_size: f64,
+    x: f64,
+    y: f64,
+) -> Option<GuideOrientation> {
+    if !show_rulers {
+        return None;
+    }
+    match (x < ruler_size, y < ruler_size) {
+        (false, true) => Some(GuideOrientation::Horizontal),
+        (true, false) => Some(GuideOrientation::Vertical),
+        _ => None,
+    }
+}
+
+/// ドキュメント座標 (`doc_x`, `doc_y`) から `tolerance` 以内にある最前面のガイド
+fn guide_at(guides: &[Guide], doc_x: f64, doc_y: f64, tolerance: f64) -> Option<usize> {
+    guides.iter().rposition(|guide| {
+        let distance = match guide.orientation {
+            GuideOrientation::Horizontal => (doc_y - guide.position as f64).abs(),
+            GuideOrientation::Vertical => (doc_x - guide.position as f64).abs(),
+        };
+        distance <= tolerance
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drag_from_ruler_creates_and_deletes_guide() {
+        // デフォルト: ruler=20, pan=(0,0), zoom=1.0
+        let state = CanvasRenderState::default();
+        state.config.borrow_mut().guides.clear();
+
+        // 上のルーラーから引き出すと水平ガイド
+        assert!(begin_guide_drag(&state, 100.0, 10.0));
+        assert!(update_guide_drag(&state, 100.0, 70.0));
+        assert!(end_guide_drag(&state, 100.0, 120.0));
+        {
+            let config = state.config.borrow();
+            assert_eq!(config.guides.len(), 1);
+            assert_eq!(config.guides[0].orientation, GuideOrientation::Horizontal);
+            assert!((config.guides[0].position - 100.0).abs() < 0.01);
+        }
+
+        // 既存のガイドを掴んで左のルーラーに戻しても水平ガイドは残る
+        assert!(begin_guide_drag(&state, 300.0, 122.0));
+        assert!(end_guide_drag(&state, 10.0, 150.0));
+        assert_eq!(state.config.borrow().guides.len(), 1);
+        assert!((state.config.borrow().guides[0].position - 130.0).abs() < 0.01);
+
+        // 上のルーラーに戻すと削除
+        assert!(begin_guide_drag(&state, 300.0, 150.0));
+        assert!(end_guide_drag(&state, 300.0, 5.0));
+        assert!(state.config.borrow().guides.is_empty());
+        assert!(!end_guide_drag(&state, 300.0, 5.0));
+    }
+
+    #[test]
+    fn test_guide_drag_needs_ruler_or_guide() {
+        let state = CanvasRenderState::default();
+        state.config.borrow_mut().guides.clear();
+
+        // ガイドのない場所、左上の角、非表示のルーラーでは開始しない
+        assert!(!begin_guide_drag(&state, 200.0, 200.0));
+        assert!(!begin_guide_drag(&state, 5.0, 5.0));
+        state.config.borrow_mut().show_rulers = false;
+        assert!(!begin_guide_drag(&state, 100.0, 10.0));
+
+        // 左のルーラーからは垂直ガイド
+        state.config.borrow_mut().show_rulers = true;
+        assert!(begin_guide_drag(&state, 10.0, 100.0));
+        assert!(end_guide_drag(&state, 60.0, 100.0));
+        assert_eq!(
+            state.config.borrow().guides[0].orientation,
+            GuideOrientation::Vertical
+        );
+
+        // Select 以外のツールでは既存ガイドを掴まない
+        state.tool_state.borrow_mut().current_tool = ToolMode::Rectangle;
+        assert!(!begin_guide_drag(&state, 60.0, 300.0));
+    }
+}
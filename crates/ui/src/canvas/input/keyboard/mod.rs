@@ -13,8 +13,10 @@
 //!
 //! - **テキスト編集**: 文字入力、削除、カーソル移動（`text_editing_keys` で実装）
 //! - **テキスト配置**: 左揃え、右揃え、中央揃え、両端揃え（`text_alignment_keys` で実装）
-//! - **ショートカット統合**: `keyboard_shortcuts` モジュールと連携
-//! - **オブジェクト移動**: 矢印キーによるオブジェクト移動
+//! - **ショートカット統合**: `keyboard_shortcuts` モジュールと連携（コピー・ペースト・
+//!   取り消しなどは `win.*` アクションとしてキー割り当てに従う）
+//! - **オブジェクト移動**: 矢印キーによるオブジェクト移動（グリッドスナップ時は 1 マス、Shift で 5 マス）
+//! - **ツール切り替え**: 修飾キーなしの V/T/I/R/E/L/A/P（テキスト編集中は無効）
 //!
 //! # 使用例
 //!
@@ -28,7 +30,8 @@
 //!
 //! テキスト要素をダブルクリックすると編集モードに進入し、以下が可能になります：
 //! - 文字の挿入・削除（`text_editing_keys` で処理）
-//! - カーソル移動（`text_editing_keys` で処理）
+//! - カーソル移動と Shift による範囲選択（`text_editing_keys` で処理）
+//! - Ctrl+A で全選択
 //! - テキスト配置の変更（`text_alignment_keys` で処理）
 //! - Escape キーで編集終了
 
@@ -86,8 +89,15 @@ pub fn setup_keyboard_events(
 
             eprintln!("📱 IME callback invoked with text: '{}'", composed_text);
 
-            let tool_state_ref = render_state_ime_cb.tool_state.borrow();
-            if let Some(text_id) = tool_state_ref.editing_text_id {
+            let editing_text_id = render_state_ime_cb.tool_state.borrow().editing_text_id;
+            if let Some(text_id) = editing_text_id {
+                // Composed text replaces the selection
+                text_editing_keys::delete_text_selection(
+                    &app_state_ime_cb,
+                    &render_state_ime_cb,
+                    text_id,
+                );
+                let tool_state_ref = render_state_ime_cb.tool_state.borrow();
                 let mut cursor_pos = tool_state_ref.editing_cursor_pos;
                 eprintln!("📝 Text ID: {:?}, Initial cursor: {}", text_id, cursor_pos);
                 drop(tool_state_ref);
@@ -97,8 +107,9 @@ pub fn setup_keyboard_events(
                     eprintln!("  Inserting '{}' at position {}", ch, cursor_pos);
 
                     // Use the existing character insertion logic
+                    let page_index = app_state_ime_cb.active_page_index();
                     app_state_ime_cb.with_mutable_active_document(|doc| {
-                        if let Some(page) = doc.pages.first_mut() {
+                        if let Some(page) = doc.pages.get_mut(page_index) {
                             for element in &mut page.elements {
                                 if let testruct_core::document::DocumentElement::Text(text) =
                                     element
@@ -129,6 +140,7 @@ pub fn setup_keyboard_events(
                     // Increment cursor for next character
                     cursor_pos += 1;
                 }
+                text_editing_keys::fit_edited_text(&app_state_ime_cb, text_id);
 
                 // Update cursor position in tool state and refresh canvas
                 let mut tool_state = render_state_ime_cb.tool_state.borrow_mut();
@@ -189,31 +201,27 @@ pub fn setup_keyboard_events(
         // composition is complete, which we handle in the callback registered above.
         // Direct key handling continues here for non-composition keys (arrows, escape, etc)
 
-        // Handle Ctrl+Shift+I to insert image
-        if ctrl_pressed && shift_pressed && keyval == gtk4::gdk::Key::i {
-            keyboard_shortcuts::handle_insert_image(&app_state_keyboard, &drawing_area_keyboard);
-            return gtk4::glib::Propagation::Stop;
-        }
-
-        // Handle Ctrl+Shift+S to save as template
-        if ctrl_pressed && shift_pressed && keyval == gtk4::gdk::Key::s {
-            keyboard_shortcuts::handle_save_template(&app_state_keyboard);
-            return gtk4::glib::Propagation::Stop;
-        }
-
-        // Handle Paste: Ctrl+V (must be before text editing keys to prevent 'v' insertion)
-        if ctrl_pressed && keyval == gtk4::gdk::Key::v {
-            if in_text_editing {
-                // Paste text (including Japanese) during text editing mode
-                keyboard_shortcuts::handle_paste_text_in_editing(
-                    &app_state_keyboard,
-                    &render_state_kbd,
-                    &drawing_area_keyboard,
-                );
-                eprintln!("📋 Text paste in editing mode");
-            } else {
-                // Paste elements when not in text editing
-                keyboard_shortcuts::handle_paste(&app_state_keyboard, &drawing_area_keyboard);
+        // Handle Select All in text editing: Ctrl+A
+        if ctrl_pressed && in_text_editing && keyval == gtk4::gdk::Key::a {
+            if let Some(text_id) = editing_text_id {
+                let char_count = app_state_keyboard
+                    .active_page()
+                    .and_then(|page| {
+                        page.elements.iter().find_map(|element| match element {
+                            testruct_core::document::DocumentElement::Text(text)
+                                if text.id == text_id =>
+                            {
+                                Some(text.content.chars().count())
+                            }
+                            _ => None,
+                        })
+                    })
+                    .unwrap_or(0);
+                render_state_kbd
+                    .tool_state
+                    .borrow_mut()
+                    .select_all_text(char_count);
+                drawing_area_keyboard.queue_draw();
             }
             return gtk4::glib::Propagation::Stop;
         }
@@ -233,18 +241,33 @@ pub fn setup_keyboard_events(
             }
         }
 
+        // Other Ctrl shortcuts (paste, undo, duplicate, ...) are window actions
+        // bound through KeyBindings; let them through instead of typing the key
+        if ctrl_pressed && in_text_editing {
+            return gtk4::glib::Propagation::Proceed;
+        }
+
         // Handle text editing keys
         if in_text_editing {
             if let Some(text_id) = editing_text_id {
-                if let Some(should_stop) = handle_text_editing_key(
+                // 編集前後のテキストボックス領域だけを再描画
+                render_state_kbd.mark_elements_dirty(&app_state_keyboard, &[text_id]);
+                let handled = handle_text_editing_key(
                     &app_state_keyboard,
                     &render_state_kbd,
                     &drawing_area_keyboard,
                     text_id,
                     keyval,
                     &mut cursor_pos,
-                    &ime_manager,
-                ) {
+                    shift_pressed,
+                );
+                if handled.is_some() {
+                    render_state_kbd.mark_elements_dirty(&app_state_keyboard, &[text_id]);
+                } else {
+                    // 後続のショートカットが何を変更するか分からないため全体を再描画
+                    render_state_kbd.mark_full_redraw();
+                }
+                if let Some(should_stop) = handled {
                     if should_stop {
                         return gtk4::glib::Propagation::Stop;
                     }
@@ -252,64 +275,56 @@ pub fn setup_keyboard_events(
             }
         }
 
-        // Handle Copy: Ctrl+C
-        if ctrl_pressed && !in_text_editing && keyval == gtk4::gdk::Key::c {
-            keyboard_shortcuts::handle_copy(
-                &render_state_kbd,
-                &app_state_keyboard,
-                &drawing_area_keyboard,
-            );
-            return gtk4::glib::Propagation::Stop;
-        }
-
-        // Handle Cut: Ctrl+X
-        if ctrl_pressed && !in_text_editing && keyval == gtk4::gdk::Key::x {
-            keyboard_shortcuts::handle_cut(
-                &render_state_kbd,
-                &app_state_keyboard,
-                &drawing_area_keyboard,
-            );
-            return gtk4::glib::Propagation::Stop;
+        // Polygon in progress: Enter closes it, Escape discards it, BackSpace removes a vertex
+        let polygon_in_progress = {
+            let tool_state = render_state_kbd.tool_state.borrow();
+            tool_state.current_tool == crate::canvas::tools::ToolMode::Polygon
+                && !tool_state.polygon_points.is_empty()
+        };
+        if polygon_in_progress {
+            match keyval {
+                gtk4::gdk::Key::Return | gtk4::gdk::Key::KP_Enter => {
+                    keyboard_shortcuts::handle_finish_polygon(
+                        &render_state_kbd,
+                        &app_state_keyboard,
+                        &drawing_area_keyboard,
+                    );
+                    return gtk4::glib::Propagation::Stop;
+                }
+                gtk4::gdk::Key::Escape => {
+                    render_state_kbd.tool_state.borrow_mut().polygon_points.clear();
+                    drawing_area_keyboard.queue_draw();
+                    return gtk4::glib::Propagation::Stop;
+                }
+                gtk4::gdk::Key::BackSpace => {
+                    render_state_kbd.tool_state.borrow_mut().polygon_points.pop();
+                    drawing_area_keyboard.queue_draw();
+                    return gtk4::glib::Propagation::Stop;
+                }
+                _ => {}
+            }
         }
 
-        // Handle Duplicate: Ctrl+D
-        if ctrl_pressed && !in_text_editing && keyval == gtk4::gdk::Key::d {
-            keyboard_shortcuts::handle_duplicate(
+        // Single-key tool switching (V, T, R, ...) when NOT in text editing
+        let alt_pressed = state.contains(gtk4::gdk::ModifierType::ALT_MASK);
+        if !in_text_editing
+            && !ctrl_pressed
+            && !alt_pressed
+            && keyboard_shortcuts::handle_tool_shortcut(
                 &render_state_kbd,
-                &app_state_keyboard,
                 &drawing_area_keyboard,
-            );
+                keyval,
+            )
+        {
             return gtk4::glib::Propagation::Stop;
         }
 
-        // Handle Undo: Ctrl+Z (without Shift)
-        if ctrl_pressed && !in_text_editing && keyval == gtk4::gdk::Key::z && !shift_pressed {
-            if app_state_keyboard.undo() {
-                drawing_area_keyboard.queue_draw();
-                tracing::info!("✅ Undo performed");
-            } else {
-                tracing::debug!("⚠️ Nothing to undo");
-            }
-            return gtk4::glib::Propagation::Stop;
-        }
-
-        // Handle Redo: Ctrl+Y or Ctrl+Shift+Z
-        if ctrl_pressed && !in_text_editing {
-            let is_redo = keyval == gtk4::gdk::Key::y
-                || (keyval == gtk4::gdk::Key::z && shift_pressed);
-            if is_redo {
-                if app_state_keyboard.redo() {
-                    drawing_area_keyboard.queue_draw();
-                    tracing::info!("✅ Redo performed");
-                } else {
-                    tracing::debug!("⚠️ Nothing to redo");
-                }
-                return gtk4::glib::Propagation::Stop;
-            }
-        }
-
         // Handle object movement when NOT in text editing
-        let movement_amount = if shift_pressed { 10.0 } else { 1.0 };
+        // (one grid cell per press while snapping to the grid)
+        let movement_amount = render_state_kbd
+            .config
+            .borrow()
+            .nudge_distance(shift_pressed);
 
         // Handle Delete key to delete selected objects
         if !in_text_editing && (keyval == gtk4::gdk::Key::Delete || keyval == gtk4::gdk::Key::BackSpace) {
@@ -51,8 +51,9 @@ pub fn handle_text_alignment(
     };
 
     if let Some(new_alignment) = alignment {
+        let page_index = app_state.active_page_index();
         app_state.with_active_document(|doc| {
-            if let Some(page) = doc.pages.first_mut() {
+            if let Some(page) = doc.pages.get_mut(page_index) {
                 for element in &mut page.elements {
                     if let DocumentElement::Text(text) = element {
                         if text.id == text_id {
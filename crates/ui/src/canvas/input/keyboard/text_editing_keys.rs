@@ -7,22 +7,22 @@
 //! | キー | 説明 |
 //! |------|------|
 //! | Escape | テキスト編集終了 |
-//! | BackSpace | 前の文字削除 |
-//! | Delete | カーソル位置の文字削除 |
+//! | BackSpace | 前の文字削除（選択中は選択範囲を削除） |
+//! | Delete | カーソル位置の文字削除（選択中は選択範囲を削除） |
 //! | Left | カーソル左移動 |
 //! | Right | カーソル右移動 |
 //! | Home | カーソル行頭移動 |
 //! | End | カーソル行末移動 |
+//! | Shift+Left/Right/Home/End | 選択範囲を拡張 |
 //! | Return | 改行挿入 |
-//! | その他 | 通常文字入力（ASCII、Unicode） |
+//! | その他 | 通常文字入力（ASCII、Unicode、選択中は選択範囲を置換） |
 
-use super::super::ime::ImeManager;
 use crate::app::AppState;
+use crate::canvas::rendering::fit_text_height;
+use crate::canvas::tools::{insert_at_char, remove_char_range};
 use crate::canvas::CanvasRenderState;
 use gtk4::prelude::*;
 use gtk4::DrawingArea;
-use std::cell::RefCell;
-use std::rc::Rc;
 use testruct_core::document::DocumentElement;
 use uuid::Uuid;
 
@@ -38,6 +38,7 @@ use uuid::Uuid;
 /// - `text_id`: 編集中のテキスト要素ID
 /// - `keyval`: 押されたキーの値
 /// - `cursor_pos`: カーソル位置（可変参照）
+/// - `extend_selection`: Shift 押下中（カーソル移動で選択範囲を拡張）
 ///
 /// # 戻り値
 ///
@@ -50,15 +51,12 @@ pub fn handle_text_editing_key(
     text_id: Uuid,
     keyval: gtk4::gdk::Key,
     cursor_pos: &mut usize,
-    _ime_manager: &Rc<RefCell<ImeManager>>,
+    extend_selection: bool,
 ) -> Option<bool> {
     match keyval {
         gtk4::gdk::Key::Escape => {
             // Exit text editing mode
-            let mut tool_state = render_state.tool_state.borrow_mut();
-            tool_state.editing_text_id = None;
-            tool_state.editing_cursor_pos = 0;
-            drop(tool_state);
+            render_state.tool_state.borrow_mut().exit_text_editing();
 
             // NOTE: IME focus management is handled automatically by GTK4
             // on macOS with EventControllerKey, so no explicit focus_out/reset needed
@@ -68,10 +66,16 @@ pub fn handle_text_editing_key(
             Some(true)
         }
         gtk4::gdk::Key::BackSpace => {
+            if delete_text_selection(app_state, render_state, text_id) {
+                *cursor_pos = render_state.tool_state.borrow().editing_cursor_pos;
+                drawing_area.queue_draw();
+                return Some(true);
+            }
             // Delete character before cursor (use char count, not byte count)
             if *cursor_pos > 0 {
+                let page_index = app_state.active_page_index();
                 app_state.with_active_document(|doc| {
-                    if let Some(page) = doc.pages.first_mut() {
+                    if let Some(page) = doc.pages.get_mut(page_index) {
                         for element in &mut page.elements {
                             if let DocumentElement::Text(text) = element {
                                 if text.id == text_id {
@@ -93,6 +97,7 @@ pub fn handle_text_editing_key(
                         }
                     }
                 });
+                fit_edited_text(app_state, text_id);
                 let mut tool_state = render_state.tool_state.borrow_mut();
                 tool_state.editing_cursor_pos = *cursor_pos;
                 drop(tool_state);
@@ -102,9 +107,15 @@ pub fn handle_text_editing_key(
             Some(true)
         }
         gtk4::gdk::Key::Delete => {
+            if delete_text_selection(app_state, render_state, text_id) {
+                *cursor_pos = render_state.tool_state.borrow().editing_cursor_pos;
+                drawing_area.queue_draw();
+                return Some(true);
+            }
             // Delete character at cursor (use char count, not byte count)
+            let page_index = app_state.active_page_index();
             app_state.with_active_document(|doc| {
-                if let Some(page) = doc.pages.first_mut() {
+                if let Some(page) = doc.pages.get_mut(page_index) {
                     for element in &mut page.elements {
                         if let DocumentElement::Text(text) = element {
                             if text.id == text_id {
@@ -124,38 +135,40 @@ pub fn handle_text_editing_key(
                     }
                 }
             });
+            fit_edited_text(app_state, text_id);
             drawing_area.queue_draw();
             tracing::info!("✅ Deleted character at cursor position {}", cursor_pos);
             Some(true)
         }
         gtk4::gdk::Key::Left => {
             // Move cursor left
-            if *cursor_pos > 0 {
-                *cursor_pos -= 1;
-                let mut tool_state = render_state.tool_state.borrow_mut();
-                tool_state.editing_cursor_pos = *cursor_pos;
-                drop(tool_state);
-                drawing_area.queue_draw();
-                tracing::debug!("Cursor moved to position {}", cursor_pos);
-            }
+            *cursor_pos = cursor_pos.saturating_sub(1);
+            render_state
+                .tool_state
+                .borrow_mut()
+                .move_editing_cursor(*cursor_pos, extend_selection);
+            drawing_area.queue_draw();
+            tracing::debug!("Cursor moved to position {}", cursor_pos);
             Some(true)
         }
         gtk4::gdk::Key::Right => {
             // Move cursor right
+            let page_index = app_state.active_page_index();
             if let Some(document) = app_state.active_document() {
-                if let Some(page) = document.pages.first() {
+                if let Some(page) = document.pages.get(page_index) {
                     for element in &page.elements {
                         if let DocumentElement::Text(text) = element {
-                            if text.id == text_id && *cursor_pos < text.content.len() {
+                            if text.id == text_id && *cursor_pos < text.content.chars().count() {
                                 *cursor_pos += 1;
                             }
                         }
                     }
                 }
             }
-            let mut tool_state = render_state.tool_state.borrow_mut();
-            tool_state.editing_cursor_pos = *cursor_pos;
-            drop(tool_state);
+            render_state
+                .tool_state
+                .borrow_mut()
+                .move_editing_cursor(*cursor_pos, extend_selection);
             drawing_area.queue_draw();
             tracing::debug!("Cursor moved to position {}", cursor_pos);
             Some(true)
@@ -163,58 +176,46 @@ pub fn handle_text_editing_key(
         gtk4::gdk::Key::Home => {
             // Move cursor to start
             *cursor_pos = 0;
-            let mut tool_state = render_state.tool_state.borrow_mut();
-            tool_state.editing_cursor_pos = *cursor_pos;
-            drop(tool_state);
+            render_state
+                .tool_state
+                .borrow_mut()
+                .move_editing_cursor(*cursor_pos, extend_selection);
             drawing_area.queue_draw();
             tracing::debug!("Cursor moved to start");
             Some(true)
         }
         gtk4::gdk::Key::End => {
             // Move cursor to end
+            let page_index = app_state.active_page_index();
             if let Some(document) = app_state.active_document() {
-                if let Some(page) = document.pages.first() {
+                if let Some(page) = document.pages.get(page_index) {
                     for element in &page.elements {
                         if let DocumentElement::Text(text) = element {
                             if text.id == text_id {
-                                *cursor_pos = text.content.len();
+                                *cursor_pos = text.content.chars().count();
                             }
                         }
                     }
                 }
             }
-            let mut tool_state = render_state.tool_state.borrow_mut();
-            tool_state.editing_cursor_pos = *cursor_pos;
-            drop(tool_state);
+            render_state
+                .tool_state
+                .borrow_mut()
+                .move_editing_cursor(*cursor_pos, extend_selection);
             drawing_area.queue_draw();
             tracing::debug!("Cursor moved to end");
             Some(true)
         }
         gtk4::gdk::Key::Return => {
             // Insert newline character for multiline support
-            app_state.with_active_document(|doc| {
-                if let Some(page) = doc.pages.first_mut() {
-                    for element in &mut page.elements {
-                        if let DocumentElement::Text(text) = element {
-                            if text.id == text_id {
-                                // Insert newline using char-based indexing
-                                let mut chars: Vec<char> = text.content.chars().collect();
-
-                                // Insert newline at cursor position
-                                if *cursor_pos <= chars.len() {
-                                    chars.insert(*cursor_pos, '\n');
-                                    text.content = chars.iter().collect();
-                                    *cursor_pos += 1;
-                                }
-                            }
-                        }
-                    }
-                }
-            });
-            let mut tool_state = render_state.tool_state.borrow_mut();
-            tool_state.editing_cursor_pos = *cursor_pos;
-            drop(tool_state);
-            drawing_area.queue_draw();
+            insert_text_at_cursor(
+                app_state,
+                render_state,
+                drawing_area,
+                text_id,
+                "\n",
+                cursor_pos,
+            );
             tracing::info!("✅ Inserted newline at position {}", *cursor_pos - 1);
             Some(true)
         }
@@ -223,29 +224,14 @@ pub fn handle_text_editing_key(
             if let Some(ch) = keyval.to_unicode() {
                 // Accept any printable character (not just ASCII)
                 if !ch.is_control() {
-                    app_state.with_active_document(|doc| {
-                        if let Some(page) = doc.pages.first_mut() {
-                            for element in &mut page.elements {
-                                if let DocumentElement::Text(text) = element {
-                                    if text.id == text_id {
-                                        // Insert character using char-based indexing
-                                        let mut chars: Vec<char> = text.content.chars().collect();
-
-                                        // Insert character at cursor position
-                                        if *cursor_pos <= chars.len() {
-                                            chars.insert(*cursor_pos, ch);
-                                            text.content = chars.iter().collect();
-                                            *cursor_pos += 1;
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    });
-                    let mut tool_state = render_state.tool_state.borrow_mut();
-                    tool_state.editing_cursor_pos = *cursor_pos;
-                    drop(tool_state);
-                    drawing_area.queue_draw();
+                    insert_text_at_cursor(
+                        app_state,
+                        render_state,
+                        drawing_area,
+                        text_id,
+                        ch.encode_utf8(&mut [0; 4]),
+                        cursor_pos,
+                    );
                     tracing::debug!(
                         "✅ Inserted character '{}' at position {}",
                         ch,
@@ -258,3 +244,105 @@ pub fn handle_text_editing_key(
         }
     }
 }
+
+/// カーソル位置に文字列を挿入
+///
+/// 選択中の場合は選択範囲を置き換えます。`cursor_pos` は文字（`char`）単位の
+/// 位置で、挿入した文字数だけ進みます（マルチバイトの記号や日本語も 1 文字）。
+/// 通常の文字入力と改行、特殊文字パレットが共通で使います。
+pub fn insert_text_at_cursor(
+    app_state: &AppState,
+    render_state: &CanvasRenderState,
+    drawing_area: &DrawingArea,
+    text_id: Uuid,
+    inserted: &str,
+    cursor_pos: &mut usize,
+) {
+    // Typing replaces the selection
+    if delete_text_selection(app_state, render_state, text_id) {
+        *cursor_pos = render_state.tool_state.borrow().editing_cursor_pos;
+    }
+    let page_index = app_state.active_page_index();
+    app_state.with_active_document(|doc| {
+        if let Some(page) = doc.pages.get_mut(page_index) {
+            for element in &mut page.elements {
+                if let DocumentElement::Text(text) = element {
+                    if text.id == text_id {
+                        // Insert using char-based indexing
+                        let (content, end) = insert_at_char(&text.content, *cursor_pos, inserted);
+                        text.content = content;
+                        *cursor_pos = end;
+                    }
+                }
+            }
+        }
+    });
+    fit_edited_text(app_state, text_id);
+    let mut tool_state = render_state.tool_state.borrow_mut();
+    tool_state.editing_cursor_pos = *cursor_pos;
+    drop(tool_state);
+    drawing_area.queue_draw();
+}
+
+/// 選択範囲を削除
+///
+/// 選択中の文字を削除してカーソルを範囲の先頭に移動し、選択を解除します。
+/// 選択がない場合は何もせず `false` を返します。
+pub fn delete_text_selection(
+    app_state: &AppState,
+    render_state: &CanvasRenderState,
+    text_id: Uuid,
+) -> bool {
+    let selection = {
+        let mut tool_state = render_state.tool_state.borrow_mut();
+        let selection = tool_state.text_selection();
+        if selection.is_none() {
+            // Drop an empty anchor so the next edit doesn't start a selection
+            tool_state.selection_anchor = None;
+        }
+        selection
+    };
+    let Some(range) = selection else {
+        return false;
+    };
+
+    let page_index = app_state.active_page_index();
+    app_state.with_active_document(|doc| {
+        if let Some(page) = doc.pages.get_mut(page_index) {
+            for element in &mut page.elements {
+                if let DocumentElement::Text(text) = element {
+                    if text.id == text_id {
+                        text.content = remove_char_range(&text.content, range.clone());
+                    }
+                }
+            }
+        }
+    });
+    fit_edited_text(app_state, text_id);
+
+    render_state
+        .tool_state
+        .borrow_mut()
+        .move_editing_cursor(range.start, false);
+    tracing::info!("✅ Deleted selected text {:?}", range);
+    true
+}
+
+/// 編集中のテキストボックスを内容に合わせて再フィット
+///
+/// `auto_resize_height` が有効な要素のみ、現在の幅で測った高さ
+/// （要素の `padding` を含む）に更新します。
+pub fn fit_edited_text(app_state: &AppState, text_id: Uuid) {
+    let page_index = app_state.active_page_index();
+    app_state.with_active_document(|doc| {
+        if let Some(page) = doc.pages.get_mut(page_index) {
+            for element in &mut page.elements {
+                if let DocumentElement::Text(text) = element {
+                    if text.id == text_id {
+                        fit_text_height(text);
+                    }
+                }
+            }
+        }
+    });
+}
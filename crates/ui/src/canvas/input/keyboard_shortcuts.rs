@@ -1,119 +1,70 @@
 //! キーボードショートカット処理モジュール
 //!
-//! コピー、カット、ペースト、複製、オブジェクト移動などのショートカット機能を提供します。
+//! カット、ペースト、複製、オブジェクト移動などのショートカット機能を提供します。
+//!
+//! カット・ペースト・複製はウィンドウの `win.cut` / `win.paste` /
+//! `win.paste-in-place` / `win.duplicate` アクションから呼び出されるため、
+//! キーは [`KeyBindings`](crate::app::KeyBindings) で変更できます。
 //!
 //! # 主な機能
 //!
-//! - **コピー/カット/ペースト**: Ctrl+C/X/V
+//! - **カット/ペースト**: Ctrl+X/V（Ctrl+Shift+V は同じ位置にペースト）
 //! - **複製**: Ctrl+D
 //! - **オブジェクト移動**: 矢印キー（Shift: 10px、通常: 1px）
-//! - **画像挿入**: Ctrl+Shift+I
-//! - **テンプレート保存**: Ctrl+Shift+S
+//! - **ツール切り替え**: V/T/I/R/E/L/A/P（テキスト編集中以外）
 //!
 //! # キーボード操作一覧
 //!
 //! | キー | 説明 |
 //! |------|------|
-//! | Ctrl+C | 選択オブジェクトをコピー |
 //! | Ctrl+X | 選択オブジェクトをカット（削除後にコピー） |
-//! | Ctrl+V | クリップボードからペースト |
+//! | Ctrl+V | カーソル位置を中心にペースト（要素、無ければ画像かテキスト） |
+//! | Ctrl+Shift+V | コピー元と同じ位置にペースト |
 //! | Ctrl+D | 選択オブジェクトを複製 |
-//! | Ctrl+Shift+I | 画像挿入 |
-//! | Ctrl+Shift+S | テンプレートとして保存 |
 //! | ←→↑↓ | オブジェクト移動（Shift: 10px、通常: 1px） |
+//! | Enter（多角形ツール） | 配置中の多角形を確定 |
+//! | Escape（多角形ツール） | 配置中の多角形を破棄 |
+//! | BackSpace（多角形ツール） | 最後の頂点を取り消し |
+//! | V / T / I | 選択 / テキスト / 画像ツール |
+//! | R / E / L / A / P | 長方形 / 円 / 直線 / 矢印 / 多角形ツール |
 
+use super::coordinates::widget_to_document;
 use crate::app::AppState;
+use crate::canvas::tools::ToolMode;
 use crate::canvas::CanvasRenderState;
 use gtk4::prelude::*;
 use gtk4::DrawingArea;
 use testruct_core::document::DocumentElement;
 use uuid::Uuid;
 
-/// 画像挿入処理（Ctrl+Shift+I）
-///
-/// 新しい画像要素を現在のページに追加します。
-///
-/// # 引数
-///
-/// - `app_state`: アプリケーション状態
-/// - `drawing_area`: 描画エリア（再描画用）
-pub fn handle_insert_image(app_state: &AppState, drawing_area: &DrawingArea) {
-    app_state.with_active_document(|doc| {
-        if let Some(page) = doc.pages.first_mut() {
-            let image = DocumentElement::Image(testruct_core::document::ImageElement {
-                id: Uuid::new_v4(),
-                source: testruct_core::workspace::assets::AssetRef::new(),
-                bounds: testruct_core::layout::Rect {
-                    origin: testruct_core::layout::Point { x: 100.0, y: 100.0 },
-                    size: testruct_core::layout::Size {
-                        width: 200.0,
-                        height: 150.0,
-                    },
-                },
-                visible: true,
-                locked: false,
-            });
-            page.elements.push(image);
-        }
-    });
-    drawing_area.queue_draw();
-    tracing::info!("✅ Image inserted");
-}
-
-/// テンプレート保存処理（Ctrl+Shift+S）
-///
-/// 現在のドキュメントをテンプレートとして保存します。
-///
-/// # 引数
-///
-/// - `app_state`: アプリケーション状態
-pub fn handle_save_template(app_state: &AppState) {
-    if let Some(document) = app_state.active_document() {
-        let template_name = chrono::Local::now()
-            .format("template_%Y%m%d_%H%M%S")
-            .to_string();
-        match crate::templates::save_template(&template_name, &document) {
-            Ok(_) => {
-                tracing::info!("✅ Document saved as template: {}", template_name);
-            }
-            Err(e) => {
-                tracing::error!("Failed to save template: {}", e);
-            }
-        }
-    }
-}
-
-/// コピー処理（Ctrl+C）
+/// ツール切り替え処理（V/T/I/R/E/L/A/P/K/M）
 ///
-/// 選択されたオブジェクトをクリップボードにコピーします。
+/// 修飾キーなしの文字キーに対応するツールへ切り替えます。
+/// 作成途中の多角形と計測線は破棄されます。ツールパレットのボタンは
+/// ツール状態の監視によって追従します。
 ///
-/// # 引数
+/// # 戻り値
 ///
-/// - `render_state`: キャンバス描画状態
-/// - `app_state`: アプリケーション状態
-/// - `drawing_area`: 描画エリア（再描画用）
-pub fn handle_copy(
+/// キーがツールに割り当てられていれば `true`
+pub fn handle_tool_shortcut(
     render_state: &CanvasRenderState,
-    app_state: &AppState,
     drawing_area: &DrawingArea,
-) {
-    let selected = render_state.selected_ids.borrow();
-    if !selected.is_empty() {
-        if let Some(document) = app_state.active_document() {
-            if let Some(page) = document.pages.first() {
-                let elements: Vec<_> = page
-                    .elements
-                    .iter()
-                    .filter(|e| selected.contains(&e.id()))
-                    .cloned()
-                    .collect();
-
-                crate::clipboard::copy_to_clipboard(elements);
-                tracing::info!("✅ Copied {} objects to clipboard", selected.len());
-                drawing_area.queue_draw();
-            }
-        }
+    keyval: gtk4::gdk::Key,
+) -> bool {
+    let Some(tool) = keyval.to_unicode().and_then(ToolMode::from_shortcut_key) else {
+        return false;
+    };
+
+    let mut tool_state = render_state.tool_state.borrow_mut();
+    if tool_state.current_tool != tool {
+        tool_state.current_tool = tool;
+        tool_state.polygon_points.clear();
+        tool_state.measure_line = None;
+        tracing::info!("✅ Tool switched to: {} (shortcut)", tool.name());
     }
+    drop(tool_state);
+    drawing_area.queue_draw();
+    true
 }
 
 /// カット処理（Ctrl+X）
@@ -134,8 +85,10 @@ pub fn handle_cut(
     let selected_count = selected.len();
 
     if !selected.is_empty() {
+        render_state.mark_elements_dirty(app_state, &selected);
+        let page_index = app_state.active_page_index();
         app_state.with_mutable_active_document(|doc| {
-            if let Some(page) = doc.pages.first_mut() {
+            if let Some(page) = doc.pages.get_mut(page_index) {
                 // Copy selected elements to clipboard
                 let elements: Vec<_> = page
                     .elements
@@ -144,7 +97,7 @@ pub fn handle_cut(
                     .cloned()
                     .collect();
 
-                crate::clipboard::copy_to_clipboard(elements);
+                crate::clipboard::copy_elements(&drawing_area.clipboard(), elements);
 
                 // Delete the selected elements
                 page.elements.retain(|e| !selected.contains(&e.id()));
@@ -202,37 +155,249 @@ pub fn handle_delete(
     }
 }
 
-/// ペースト処理（Ctrl+V）
+/// 多角形の確定処理（Enter / ダブルクリック）
 ///
-/// クリップボードからオブジェクトをペーストします。
+/// 多角形ツールで配置した頂点から図形を作成します。Undo対応。
+/// 頂点が3つ未満の場合は作成せずに破棄します。いずれの場合も選択ツールに戻ります。
 ///
 /// # 引数
 ///
+/// - `render_state`: キャンバス描画状態
 /// - `app_state`: アプリケーション状態
 /// - `drawing_area`: 描画エリア（再描画用）
-pub fn handle_paste(app_state: &AppState, drawing_area: &DrawingArea) {
-    if crate::clipboard::has_clipboard_content() {
-        if let Some(pasted_elements) = crate::clipboard::paste_from_clipboard() {
-            if !pasted_elements.is_empty() {
-                let paste_count = pasted_elements.len();
-                app_state.with_mutable_active_document(|doc| {
-                    if let Some(page) = doc.pages.first_mut() {
-                        for elem in pasted_elements {
-                            page.add_element(elem);
-                        }
-                    }
-                });
+pub fn handle_finish_polygon(
+    render_state: &CanvasRenderState,
+    app_state: &AppState,
+    drawing_area: &DrawingArea,
+) {
+    let element = render_state.tool_state.borrow_mut().finish_polygon();
 
-                // Mark document as modified (updates title and triggers auto-save timer)
-                app_state.mark_as_modified();
+    if let Some(element) = element {
+        let element_id = element.id();
+        let command = crate::undo_redo::AppCreateCommand::new(
+            app_state.clone(),
+            element,
+            app_state.active_page_index(),
+        );
+        app_state.push_command(Box::new(command));
+        app_state.mark_as_modified();
+
+        {
+            let mut selected = render_state.selected_ids.borrow_mut();
+            selected.clear();
+            selected.push(element_id);
+        }
+        render_state.notify_selection_changed();
+        tracing::info!("✅ Polygon created (Undo available): {}", element_id);
+    } else {
+        tracing::debug!("⚠️ Polygon discarded: needs at least 3 vertices");
+    }
+
+    drawing_area.queue_draw();
+}
 
-                tracing::info!("✅ Pasted {} elements", paste_count);
-                drawing_area.queue_draw();
+/// ペースト処理（Ctrl+V / Ctrl+Shift+V）
+///
+/// クリップボードからオブジェクトをペーストします。
+/// 他のインスタンスでコピーされた要素はシステムクリップボードから読み込み、
+/// 無い場合はアプリ内クリップボードを使います（読み込みは非同期）。
+/// 要素は最後のカーソル位置を中心に配置し、`in_place` の場合は
+/// コピー元と同じ座標に配置します（[`paste_placement`] 参照）。
+/// 要素が無く、システムクリップボードに画像がある場合は画像要素として
+/// キャンバス中央に、テキストがある場合はテキスト要素として貼り付けます。
+///
+/// # 引数
+///
+/// - `app_state`: アプリケーション状態
+/// - `render_state`: キャンバス描画状態（配置位置とスナップの計算用）
+/// - `drawing_area`: 描画エリア（再描画用）
+/// - `in_place`: コピー元と同じ位置に貼り付けるか
+pub fn handle_paste(
+    app_state: &AppState,
+    render_state: &CanvasRenderState,
+    drawing_area: &DrawingArea,
+    in_place: bool,
+) {
+    let app_state = app_state.clone();
+    let render_state = render_state.clone();
+    let drawing_area_paste = drawing_area.clone();
+    let clipboard = drawing_area.clipboard();
+    let placement = paste_placement(&render_state, in_place);
+    crate::clipboard::paste_elements(&clipboard.clone(), placement, move |pasted| {
+        let Some(mut pasted_elements) = pasted.filter(|elements| !elements.is_empty()) else {
+            if crate::clipboard::has_image(&clipboard) {
+                handle_paste_image(&clipboard, &app_state, &render_state, &drawing_area_paste);
+            } else {
+                handle_paste_text(&clipboard, &app_state, &render_state, &drawing_area_paste);
             }
+            return;
+        };
+        if matches!(placement, crate::clipboard::PastePlacement::CenteredAt(_)) {
+            snap_pasted_elements(&mut pasted_elements, &render_state);
         }
+
+        let paste_count = pasted_elements.len();
+        let page_index = app_state.active_page_index();
+        app_state.with_mutable_active_document(|doc| {
+            if let Some(page) = doc.pages.get_mut(page_index) {
+                for elem in pasted_elements {
+                    page.add_element_to_active_layer(elem);
+                }
+            }
+        });
+
+        // Mark document as modified (updates title and triggers auto-save timer)
+        app_state.mark_as_modified();
+
+        tracing::info!("✅ Pasted {} elements", paste_count);
+        drawing_area_paste.queue_draw();
+    });
+}
+
+/// 貼り付け位置の決定
+///
+/// `in_place` ならコピー元と同じ座標、そうでなければ最後のポインタ位置を
+/// 中心に配置します。ポインタがまだキャンバスに乗っていない場合は
+/// 従来どおり少しずつずらして貼り付けます。
+pub fn paste_placement(
+    render_state: &CanvasRenderState,
+    in_place: bool,
+) -> crate::clipboard::PastePlacement {
+    use crate::clipboard::PastePlacement;
+
+    if in_place {
+        return PastePlacement::InPlace;
+    }
+    match *render_state.last_pointer.borrow() {
+        Some(pointer) => PastePlacement::CenteredAt(pointer),
+        None => PastePlacement::Offset,
     }
 }
 
+/// カーソル位置に貼り付けた要素のスナップ
+///
+/// 貼り付けた要素全体の左上をガイドまたはグリッドにスナップし、
+/// 全要素を同じだけ移動します（配置関係は保たれます）。
+pub fn snap_pasted_elements(elements: &mut [DocumentElement], render_state: &CanvasRenderState) {
+    let Some(bounds) = crate::clipboard::elements_bounds(elements) else {
+        return;
+    };
+    let (x, y) = render_state
+        .config
+        .borrow()
+        .snap_creation_point(bounds.origin.x, bounds.origin.y);
+    crate::clipboard::offset_elements(elements, (x - bounds.origin.x, y - bounds.origin.y));
+}
+
+/// 画像ペースト処理
+///
+/// システムクリップボードの画像を PNG としてアセットに保存し、
+/// 表示中のキャンバス中央に画像要素を作成します。Undo対応。
+fn handle_paste_image(
+    clipboard: &gtk4::gdk::Clipboard,
+    app_state: &AppState,
+    render_state: &CanvasRenderState,
+    drawing_area: &DrawingArea,
+) {
+    let center = widget_to_document(
+        drawing_area.width() as f64 / 2.0,
+        drawing_area.height() as f64 / 2.0,
+        render_state,
+    );
+    let center = testruct_core::layout::Point::new(center.x as f32, center.y as f32);
+
+    let app_state = app_state.clone();
+    let render_state = render_state.clone();
+    let drawing_area = drawing_area.clone();
+    crate::clipboard::read_image_png(clipboard, move |png| {
+        let Some(png) = png else {
+            return;
+        };
+        let Some(page_size) = app_state.active_page_size() else {
+            return;
+        };
+
+        let dir = crate::clipboard::pasted_image_dir(app_state.current_file_path().as_deref());
+        let path = match crate::clipboard::save_pasted_image(&png, &dir) {
+            Ok(path) => path,
+            Err(e) => {
+                tracing::error!("❌ Failed to save pasted image: {}", e);
+                return;
+            }
+        };
+        let asset_ref = {
+            let asset_catalog = app_state.asset_catalog();
+            let mut catalog = asset_catalog.lock().expect("asset catalog");
+            catalog.register(&path)
+        };
+
+        let Some(element) = crate::clipboard::create_image_element_from_clipboard(
+            &png, center, page_size, asset_ref,
+        ) else {
+            tracing::warn!("⚠️ Clipboard image is not a usable PNG");
+            return;
+        };
+
+        let element_id = element.id();
+        let command = crate::undo_redo::AppCreateCommand::new(
+            app_state.clone(),
+            element,
+            app_state.active_page_index(),
+        );
+        app_state.push_command(Box::new(command));
+        app_state.mark_as_modified();
+
+        {
+            let mut selected = render_state.selected_ids.borrow_mut();
+            selected.clear();
+            selected.push(element_id);
+        }
+        render_state.notify_selection_changed();
+        tracing::info!("✅ Pasted image from clipboard: {}", path.display());
+        drawing_area.queue_draw();
+    });
+}
+
+/// テキストペースト処理
+///
+/// システムクリップボードのテキストからテキスト要素を作成します。Undo対応。
+fn handle_paste_text(
+    clipboard: &gtk4::gdk::Clipboard,
+    app_state: &AppState,
+    render_state: &CanvasRenderState,
+    drawing_area: &DrawingArea,
+) {
+    let app_state = app_state.clone();
+    let render_state = render_state.clone();
+    let drawing_area = drawing_area.clone();
+    clipboard.read_text_async(gtk4::gio::Cancellable::NONE, move |result| {
+        let Ok(Some(text)) = result else {
+            return;
+        };
+        if text.is_empty() {
+            return;
+        }
+
+        let element = crate::clipboard::create_text_element_from_clipboard(&text, None);
+        let element_id = element.id();
+        let command = crate::undo_redo::AppCreateCommand::new(
+            app_state.clone(),
+            element,
+            app_state.active_page_index(),
+        );
+        app_state.push_command(Box::new(command));
+
+        {
+            let mut selected = render_state.selected_ids.borrow_mut();
+            selected.clear();
+            selected.push(element_id);
+        }
+        render_state.notify_selection_changed();
+        tracing::info!("✅ Pasted text from clipboard ({} chars)", text.len());
+        drawing_area.queue_draw();
+    });
+}
+
 /// 複製処理（Ctrl+D）
 ///
 /// 選択されたオブジェクトを複製し、少しオフセットして配置します。
@@ -249,8 +414,10 @@ pub fn handle_duplicate(
 ) {
     let selected = render_state.selected_ids.borrow().clone();
     if !selected.is_empty() {
+        let page_index = app_state.active_page_index();
+        let mut new_ids = Vec::new();
         app_state.with_mutable_active_document(|doc| {
-            if let Some(page) = doc.pages.first_mut() {
+            if let Some(page) = doc.pages.get_mut(page_index) {
                 let mut new_elements = Vec::new();
 
                 for orig_elem in page.elements.iter().filter(|e| selected.contains(&e.id())) {
@@ -284,16 +451,30 @@ pub fn handle_duplicate(
                             group.bounds.origin.x += 20.0;
                             group.bounds.origin.y += 20.0;
                         }
+                        DocumentElement::Table(table) => {
+                            table.id = new_id;
+                            table.bounds.origin.x += 20.0;
+                            table.bounds.origin.y += 20.0;
+                        }
+                        DocumentElement::QrCode(qr) => {
+                            qr.id = new_id;
+                            qr.bounds.origin.x += 20.0;
+                            qr.bounds.origin.y += 20.0;
+                        }
                     }
 
-                    new_elements.push(new_elem);
+                    new_elements.push((orig_elem.id(), new_elem));
                 }
 
-                for elem in new_elements {
-                    page.add_element(elem);
+                // Copies stay on their original's layer
+                for (source_id, elem) in new_elements {
+                    new_ids.push(elem.id());
+                    page.add_copy(source_id, elem);
                 }
             }
         });
+        // The copies sit outside the originals' area, so repaint where they landed
+        render_state.mark_elements_dirty(app_state, &new_ids);
 
         // Mark document as modified (updates title and triggers auto-save timer)
         app_state.mark_as_modified();
@@ -322,10 +503,21 @@ pub fn move_selected_objects(
     let selected = render_state.selected_ids.borrow();
 
     if !selected.is_empty() {
+        render_state.mark_elements_dirty(app_state, &selected);
+        let page_index = app_state.active_page_index();
         app_state.with_mutable_active_document(|doc| {
-            if let Some(page) = doc.pages.first_mut() {
+            if let Some(page) = doc.pages.get_mut(page_index) {
+                let in_locked_layer: Vec<uuid::Uuid> = page
+                    .element_order()
+                    .into_iter()
+                    .filter(|id| page.is_in_locked_layer(*id))
+                    .collect();
                 for element in &mut page.elements {
-                    if selected.contains(&element.id()) {
+                    // Locked elements (and locked layers) stay put even when selected
+                    if selected.contains(&element.id())
+                        && !element.is_locked()
+                        && !in_locked_layer.contains(&element.id())
+                    {
                         match element {
                             DocumentElement::Text(text) => {
                                 text.bounds.origin.x += delta_x;
@@ -347,11 +539,20 @@ pub fn move_selected_objects(
                                 group.bounds.origin.x += delta_x;
                                 group.bounds.origin.y += delta_y;
                             }
+                            DocumentElement::Table(table) => {
+                                table.bounds.origin.x += delta_x;
+                                table.bounds.origin.y += delta_y;
+                            }
+                            DocumentElement::QrCode(qr) => {
+                                qr.bounds.origin.x += delta_x;
+                                qr.bounds.origin.y += delta_y;
+                            }
                         }
                     }
                 }
             }
         });
+        render_state.mark_elements_dirty(app_state, &selected);
 
         // Mark document as modified (updates title and triggers auto-save timer)
         app_state.mark_as_modified();
@@ -373,9 +574,10 @@ pub fn handle_paste_text_in_editing(
     drawing_area: &DrawingArea,
 ) {
     eprintln!("🔧 handle_paste_text_in_editing called");
-    let tool_state = render_state.tool_state.borrow();
-    if let Some(text_id) = tool_state.editing_text_id {
-        let cursor_pos = tool_state.editing_cursor_pos;
+    let editing_text_id = render_state.tool_state.borrow().editing_text_id;
+    if let Some(text_id) = editing_text_id {
+        let tool_state = render_state.tool_state.borrow();
+        let mut cursor_pos = tool_state.editing_cursor_pos;
         eprintln!(
             "✅ Text editing active: ID={:?}, cursor={}",
             text_id, cursor_pos
@@ -401,9 +603,19 @@ pub fn handle_paste_text_in_editing(
                         pasted_text, cursor_pos
                     );
 
+                    // Pasted text replaces the selection
+                    if super::keyboard::text_editing_keys::delete_text_selection(
+                        app_state,
+                        render_state,
+                        text_id,
+                    ) {
+                        cursor_pos = render_state.tool_state.borrow().editing_cursor_pos;
+                    }
+
                     // Insert pasted text character by character
+                    let page_index = app_state.active_page_index();
                     app_state.with_mutable_active_document(|doc| {
-                        if let Some(page) = doc.pages.first_mut() {
+                        if let Some(page) = doc.pages.get_mut(page_index) {
                             for element in &mut page.elements {
                                 if let DocumentElement::Text(text) = element {
                                     if text.id == text_id {
@@ -428,6 +640,7 @@ pub fn handle_paste_text_in_editing(
                             }
                         }
                     });
+                    super::keyboard::text_editing_keys::fit_edited_text(app_state, text_id);
 
                     // Update cursor position to end of pasted text
                     let pasted_char_count = pasted_text.chars().count();
@@ -8,6 +8,8 @@
 //! - **マウス座標追跡**: スクリーン座標をドキュメント座標に変換
 //! - **リサイズハンドル検出**: 選択オブジェクトのハンドル範囲を調査
 //! - **カーソル変更**: ハンドルタイプに応じてカーソルを更新
+//! - **位置通知**: ドキュメント座標を `notify_pointer_moved` でステータスバーなどに通知
+//! - **最終位置の記録**: `last_pointer` に保存し、カーソル位置への貼り付けに使用
 //!
 //! # カーソルタイプ
 //!
@@ -43,7 +45,8 @@
 //! # パフォーマンス
 //!
 //! `motion` イベントは高頻度で発生（通常 60Hz 以上）するため、処理は最小限に
-//! 留めて実装されています。リサイズハンドル検出のみで、他の処理は行いません。
+//! 留めて実装されています。リサイズハンドル検出と位置通知のみで、キャンバスの
+//! 再描画は行いません。
 
 use crate::app::AppState;
 use crate::canvas::mouse::{test_resize_handle, CanvasMousePos, ResizeHandle};
@@ -51,6 +54,7 @@ use crate::canvas::CanvasRenderState;
 use gtk4::gdk;
 use gtk4::prelude::*;
 use gtk4::{DrawingArea, EventControllerMotion};
+use testruct_core::layout::Point;
 
 /// マウス動作追跡を初期化
 ///
@@ -80,17 +84,22 @@ pub fn setup_mouse_tracking(
         let screen_y = y - (ruler_config.size + config.pan_y);
         let doc_x = screen_x / config.zoom;
         let doc_y = screen_y / config.zoom;
+        let handle_hit_size = config.handle_hit_size();
         drop(config);
         drop(ruler_config);
 
         let canvas_mouse_pos = CanvasMousePos { x: doc_x, y: doc_y };
+        let pointer = Point::new(doc_x as f32, doc_y as f32);
+        *state.last_pointer.borrow_mut() = Some(pointer);
+        state.notify_pointer_moved(Some(pointer));
 
         // Check if cursor is over a resize handle of selected objects
         let selected = state.selected_ids.borrow();
         let mut cursor_name = "default";
 
+        let page_index = app_state_motion.active_page_index();
         if let Some(document) = app_state_motion.active_document() {
-            if let Some(page) = document.pages.first() {
+            if let Some(page) = document.pages.get(page_index) {
                 for selected_id in selected.iter() {
                     // Find the selected element
                     for element in &page.elements {
@@ -110,11 +119,17 @@ pub fn setup_mouse_tracking(
                             testruct_core::document::DocumentElement::Group(group) => {
                                 (group.id, &group.bounds)
                             }
+                            testruct_core::document::DocumentElement::Table(table) => {
+                                (table.id, &table.bounds)
+                            }
+                            testruct_core::document::DocumentElement::QrCode(qr) => {
+                                (qr.id, &qr.bounds)
+                            }
                         };
 
                         if elem_id == *selected_id {
                             // Test for resize handle hit
-                            if let Some(handle) = test_resize_handle(canvas_mouse_pos, bounds, 8.0)
+                            if let Some(handle) = test_resize_handle(canvas_mouse_pos, bounds, handle_hit_size)
                             {
                                 cursor_name = match handle {
                                     ResizeHandle::TopLeft | ResizeHandle::BottomRight => {
@@ -141,5 +156,11 @@ pub fn setup_mouse_tracking(
         let cursor = gdk::Cursor::from_name(cursor_name, None);
         drawing_area_cursor.set_cursor(cursor.as_ref());
     });
+
+    // ポインタがキャンバス外に出たら位置表示を消す
+    let render_state_leave = render_state.clone();
+    motion.connect_leave(move |_controller| {
+        render_state_leave.notify_pointer_moved(None);
+    });
     drawing_area.add_controller(motion);
 }
@@ -0,0 +1,179 @@
+//! スペルチェック候補メニューモジュール
+//!
+//! テキスト編集中に赤い波線の付いた単語を右クリックすると、
+//! 辞書の候補をポップオーバーで表示し、選んだ候補で単語を置き換えます。
+//!
+//! | 操作 | 動作 |
+//! |------|------|
+//! | 右クリック（編集中の誤字） | 候補メニューを表示 |
+//! | 候補をクリック | 単語を置き換え、カーソルを単語の末尾へ移動 |
+//!
+//! 右クリックは [`context_menu`](super::context_menu) が受け取り、誤字の上で
+//! なければ通常のコンテキストメニューを表示します。
+
+use super::coordinates::DocumentCoords;
+use super::keyboard::text_editing_keys::fit_edited_text;
+use crate::app::AppState;
+use crate::canvas::rendering::text_cursor_index_at;
+use crate::canvas::rendering_text::char_to_byte_index;
+use crate::canvas::CanvasRenderState;
+use crate::undo_redo::AppReplaceTextCommand;
+use gtk4::prelude::*;
+use gtk4::{gdk, Box as GtkBox, Button, DrawingArea, Entry, Label, Orientation, Popover};
+use std::ops::Range;
+use testruct_core::document::DocumentElement;
+use uuid::Uuid;
+
+/// クリック位置が編集中テキストの誤字なら候補メニューを表示
+///
+/// `(x, y)` はウィジェット座標、`doc` は同じ位置のドキュメント座標です。
+/// 誤字でなければ何もせず `false` を返します。
+pub fn show_spelling_menu(
+    drawing_area: &DrawingArea,
+    render_state: &CanvasRenderState,
+    app_state: &AppState,
+    ime_entry: &Entry,
+    (x, y): (f64, f64),
+    doc: DocumentCoords,
+) -> bool {
+    let Some((text_id, range, suggestions)) = misspelling_at(render_state, app_state, doc.x, doc.y)
+    else {
+        return false;
+    };
+    show_suggestions(
+        drawing_area,
+        render_state,
+        app_state,
+        ime_entry,
+        (x, y),
+        text_id,
+        range,
+        suggestions,
+    );
+    true
+}
+
+/// 編集中のテキストでクリック位置にある誤字（バイト範囲）と候補
+fn misspelling_at(
+    render_state: &CanvasRenderState,
+    app_state: &AppState,
+    doc_x: f64,
+    doc_y: f64,
+) -> Option<(Uuid, Range<usize>, Vec<String>)> {
+    let editing_id = render_state.tool_state.borrow().editing_text_id?;
+    let page = app_state.active_page()?;
+    let text = page.elements.iter().find_map(|element| match element {
+        DocumentElement::Text(text) if text.id == editing_id => Some(text),
+        _ => None,
+    })?;
+
+    let bounds = &text.bounds;
+    let inside = doc_x >= bounds.origin.x as f64
+        && doc_x <= (bounds.origin.x + bounds.size.width) as f64
+        && doc_y >= bounds.origin.y as f64
+        && doc_y <= (bounds.origin.y + bounds.size.height) as f64;
+    if !inside {
+        return None;
+    }
+
+    let char_index =
+        text_cursor_index_at(bounds, &text.content, &text.style, &text.padding, doc_x, doc_y);
+    let byte_index = char_to_byte_index(&text.content, char_index);
+    let (range, suggestions) = render_state
+        .spell_check
+        .borrow()
+        .misspelling_at(&text.content, byte_index)?;
+    Some((editing_id, range, suggestions))
+}
+
+/// クリック位置に候補のポップオーバーを表示
+#[allow(clippy::too_many_arguments)]
+fn show_suggestions(
+    drawing_area: &DrawingArea,
+    render_state: &CanvasRenderState,
+    app_state: &AppState,
+    ime_entry: &Entry,
+    (x, y): (f64, f64),
+    text_id: Uuid,
+    range: Range<usize>,
+    suggestions: Vec<String>,
+) {
+    let popover = Popover::new();
+    popover.set_parent(drawing_area);
+    popover.set_has_arrow(false);
+    popover.set_pointing_to(Some(&gdk::Rectangle::new(x as i32, y as i32, 1, 1)));
+    popover.connect_closed(|popover| popover.unparent());
+
+    let menu = GtkBox::new(Orientation::Vertical, 2);
+    if suggestions.is_empty() {
+        let label = Label::new(Some("候補がありません"));
+        label.add_css_class("dim-label");
+        menu.append(&label);
+    }
+    for suggestion in suggestions {
+        let button = Button::with_label(&suggestion);
+        button.add_css_class("flat");
+        let popover = popover.clone();
+        let render_state = render_state.clone();
+        let app_state = app_state.clone();
+        let drawing_area = drawing_area.clone();
+        let ime_entry = ime_entry.clone();
+        let range = range.clone();
+        button.connect_clicked(move |_| {
+            replace_word(
+                &render_state,
+                &app_state,
+                &ime_entry,
+                text_id,
+                range.clone(),
+                &suggestion,
+            );
+            popover.popdown();
+            drawing_area.queue_draw();
+        });
+        menu.append(&button);
+    }
+    popover.set_child(Some(&menu));
+    popover.popup();
+}
+
+/// 誤字を候補で置き換え、カーソルを置き換えた単語の末尾へ移動
+///
+/// 置き換えは [`AppReplaceTextCommand`] で行うため、リッチテキストの
+/// ランも更新され、元に戻す操作で取り消せます。
+fn replace_word(
+    render_state: &CanvasRenderState,
+    app_state: &AppState,
+    ime_entry: &Entry,
+    text_id: Uuid,
+    range: Range<usize>,
+    replacement: &str,
+) {
+    let page_index = app_state.active_page_index();
+    let start = range.start;
+    let command =
+        AppReplaceTextCommand::range(app_state.clone(), page_index, text_id, range, replacement);
+    app_state.push_command(Box::new(command));
+    fit_edited_text(app_state, text_id);
+
+    let end = start + replacement.len();
+    let cursor_pos = app_state
+        .with_active_page(|page| {
+            page.elements.iter().find_map(|element| match element {
+                DocumentElement::Text(text) if text.id == text_id => {
+                    text.content.get(..end).map(|before| before.chars().count())
+                }
+                _ => None,
+            })
+        })
+        .flatten();
+    let Some(cursor_pos) = cursor_pos else {
+        return;
+    };
+    render_state
+        .tool_state
+        .borrow_mut()
+        .move_editing_cursor(cursor_pos, false);
+    ime_entry.set_position(cursor_pos as i32);
+    tracing::info!("✅ Replaced misspelled word with '{}'", replacement);
+}
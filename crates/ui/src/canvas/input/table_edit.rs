@@ -0,0 +1,297 @@
+//! 表のセル編集モジュール
+//!
+//! 表をダブルクリックすると表の中に入り、クリックしたセルを編集します。
+//!
+//! - ダブルクリック（表）: 表に入り、その位置のセルの編集を開始
+//! - クリック（表に入っている間）: クリックしたセルの編集を開始
+//! - 表の外をクリック: 表から出て通常の選択に戻る
+//! - Enter: 確定して下のセルへ（最終行では確定して閉じる）
+//! - Tab / Shift+Tab: 確定して次 / 前のセルへ
+//! - Escape: 変更を破棄して閉じる
+//!
+//! セルの内容はセルの下に表示するポップオーバーの入力欄で編集し、
+//! `AppTableCellCommand` で確定するため元に戻せます。
+//! 表に入っている状態は `ToolState::editing_table_id`、
+//! 編集中のセルは `ToolState::editing_table_cell` に記録します。
+
+use super::coordinates::document_to_widget;
+use crate::app::AppState;
+use crate::canvas::CanvasRenderState;
+use gtk4::prelude::*;
+use gtk4::{gdk, glib, DrawingArea, Entry, EventControllerKey, Popover, PositionType};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use testruct_core::document::{DocumentElement, TableElement};
+use testruct_core::layout::Point;
+
+// 表示中のセルエディター（同時に開くのは 1 つだけ）
+thread_local! {
+    static CELL_EDITOR: RefCell<Option<Popover>> = const { RefCell::new(None) };
+}
+
+/// 確定後に編集を移すセル
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CellMove {
+    /// 下のセル（Enter）
+    Down,
+    /// 右のセル、行末では次の行の先頭（Tab）
+    Next,
+    /// 左のセル、行頭では前の行の末尾（Shift+Tab）
+    Previous,
+}
+
+/// `row`, `column` から `direction` へ移動した先のセル（表の外なら `None`）
+pub fn neighbor_cell(
+    rows: usize,
+    columns: usize,
+    (row, column): (usize, usize),
+    direction: CellMove,
+) -> Option<(usize, usize)> {
+    let index = row * columns + column;
+    let target = match direction {
+        CellMove::Down => index + columns,
+        CellMove::Next => index + 1,
+        CellMove::Previous => index.checked_sub(1)?,
+    };
+    (target < rows * columns).then(|| (target / columns, target % columns))
+}
+
+/// 表 `table_id` のセル `row`, `column` の編集を開始
+pub fn begin_cell_editing(
+    state: &CanvasRenderState,
+    app_state: &AppState,
+    drawing_area: &DrawingArea,
+    table_id: uuid::Uuid,
+    (row, column): (usize, usize),
+) {
+    close_cell_editor();
+
+    let Some(table) = find_table(app_state, table_id) else {
+        return;
+    };
+    let Some(content) = table.cell(row, column) else {
+        return;
+    };
+    let original = content.get_plain_text();
+
+    {
+        let mut tool_state = state.tool_state.borrow_mut();
+        tool_state.editing_table_id = Some(table_id);
+        tool_state.editing_table_cell = Some((row, column));
+    }
+    {
+        let mut selected = state.selected_ids.borrow_mut();
+        selected.clear();
+        selected.push(table_id);
+    }
+    drawing_area.queue_draw();
+
+    show_cell_editor(
+        state,
+        app_state,
+        drawing_area,
+        &table,
+        (row, column),
+        original,
+    );
+    tracing::info!("▦ Editing table cell {}, {}", row + 1, column + 1);
+}
+
+/// 表に入っている間のクリックを処理
+///
+/// 表の中のクリックならそのセルの編集を開始して `true` を返します。
+/// 表の外なら表から出て `false` を返し、通常のクリック処理に任せます。
+pub fn handle_click_in_table(
+    state: &CanvasRenderState,
+    app_state: &AppState,
+    drawing_area: &DrawingArea,
+    doc_x: f64,
+    doc_y: f64,
+) -> bool {
+    let Some(table_id) = state.tool_state.borrow().editing_table_id else {
+        return false;
+    };
+    let cell = find_table(app_state, table_id)
+        .and_then(|table| table.cell_at(Point::new(doc_x as f32, doc_y as f32)));
+    match cell {
+        Some(cell) => {
+            begin_cell_editing(state, app_state, drawing_area, table_id, cell);
+            true
+        }
+        None => {
+            exit_table(state, drawing_area);
+            false
+        }
+    }
+}
+
+/// 表から出る（開いているセルエディターは確定して閉じる）
+pub fn exit_table(state: &CanvasRenderState, drawing_area: &DrawingArea) {
+    close_cell_editor();
+    state.tool_state.borrow_mut().exit_table_editing();
+    drawing_area.queue_draw();
+}
+
+/// 表示中のセルエディターを閉じる（内容は `closed` ハンドラーで確定）
+fn close_cell_editor() {
+    if let Some(popover) = CELL_EDITOR.with(|editor| editor.borrow_mut().take()) {
+        popover.popdown();
+    }
+}
+
+/// アクティブページの表 `table_id`
+fn find_table(app_state: &AppState, table_id: uuid::Uuid) -> Option<TableElement> {
+    app_state
+        .active_page()?
+        .elements
+        .into_iter()
+        .find_map(|element| match element {
+            DocumentElement::Table(table) if table.id == table_id => Some(table),
+            _ => None,
+        })
+}
+
+/// セルの下に入力欄付きのポップオーバーを表示
+fn show_cell_editor(
+    state: &CanvasRenderState,
+    app_state: &AppState,
+    drawing_area: &DrawingArea,
+    table: &TableElement,
+    cell: (usize, usize),
+    original: String,
+) {
+    let bounds = table.cell_bounds(cell.0, cell.1);
+    let (x, y) = document_to_widget(bounds.origin.x as f64, bounds.origin.y as f64, state);
+    let zoom = state.config.borrow().zoom;
+    let pointing_to = gdk::Rectangle::new(
+        x as i32,
+        y as i32,
+        ((bounds.size.width as f64 * zoom) as i32).max(1),
+        ((bounds.size.height as f64 * zoom) as i32).max(1),
+    );
+
+    let entry = Entry::new();
+    entry.set_text(&original);
+    entry.set_width_chars(24);
+
+    let popover = Popover::new();
+    popover.set_child(Some(&entry));
+    popover.set_parent(drawing_area);
+    popover.set_pointing_to(Some(&pointing_to));
+    popover.set_position(PositionType::Bottom);
+
+    let table_id = table.id;
+    let dimensions = (table.rows, table.columns);
+    let cancelled = Rc::new(Cell::new(false));
+    let next_cell: Rc<Cell<Option<(usize, usize)>>> = Rc::new(Cell::new(None));
+
+    // Enter: 確定して下のセルへ
+    let popover_activate = popover.clone();
+    let next_activate = next_cell.clone();
+    entry.connect_activate(move |_| {
+        next_activate.set(neighbor_cell(
+            dimensions.0,
+            dimensions.1,
+            cell,
+            CellMove::Down,
+        ));
+        popover_activate.popdown();
+    });
+
+    // Tab / Shift+Tab / Escape
+    let key_controller = EventControllerKey::new();
+    key_controller.set_propagation_phase(gtk4::PropagationPhase::Capture);
+    let popover_key = popover.clone();
+    let cancelled_key = cancelled.clone();
+    let next_key = next_cell.clone();
+    key_controller.connect_key_pressed(move |_, key, _, modifiers| {
+        let direction = match key {
+            gdk::Key::Escape => {
+                cancelled_key.set(true);
+                popover_key.popdown();
+                return glib::Propagation::Stop;
+            }
+            gdk::Key::ISO_Left_Tab => CellMove::Previous,
+            gdk::Key::Tab if modifiers.contains(gdk::ModifierType::SHIFT_MASK) => {
+                CellMove::Previous
+            }
+            gdk::Key::Tab => CellMove::Next,
+            _ => return glib::Propagation::Proceed,
+        };
+        next_key.set(neighbor_cell(dimensions.0, dimensions.1, cell, direction));
+        popover_key.popdown();
+        glib::Propagation::Stop
+    });
+    entry.add_controller(key_controller);
+
+    let state_closed = state.clone();
+    let app_state_closed = app_state.clone();
+    let drawing_area_closed = drawing_area.clone();
+    let entry_closed = entry.clone();
+    popover.connect_closed(move |popover| {
+        let text = entry_closed.text().to_string();
+        if !cancelled.get() && text != original {
+            let command = crate::undo_redo::AppTableCellCommand::new(
+                app_state_closed.clone(),
+                table_id,
+                app_state_closed.active_page_index(),
+                cell,
+                text,
+            );
+            app_state_closed.push_command(Box::new(command));
+        }
+
+        {
+            let mut tool_state = state_closed.tool_state.borrow_mut();
+            if tool_state.editing_table_cell == Some(cell) {
+                tool_state.editing_table_cell = None;
+            }
+        }
+        CELL_EDITOR.with(|editor| {
+            let mut editor = editor.borrow_mut();
+            if editor.as_ref() == Some(popover) {
+                *editor = None;
+            }
+        });
+        drawing_area_closed.queue_draw();
+
+        // 閉じ終わってから親から外し、次のセルへ移る
+        let popover = popover.clone();
+        let next = next_cell.get();
+        let state = state_closed.clone();
+        let app_state = app_state_closed.clone();
+        let drawing_area = drawing_area_closed.clone();
+        glib::idle_add_local_once(move || {
+            popover.unparent();
+            if let Some(next) = next {
+                begin_cell_editing(&state, &app_state, &drawing_area, table_id, next);
+            } else if CELL_EDITOR.with(|editor| editor.borrow().is_none()) {
+                // 別のセルのエディターが開いていなければキャンバスに戻す
+                drawing_area.grab_focus();
+            }
+        });
+    });
+
+    CELL_EDITOR.with(|editor| *editor.borrow_mut() = Some(popover.clone()));
+    popover.popup();
+    entry.grab_focus();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neighbor_cell_moves_through_the_grid() {
+        // 2 行 3 列
+        assert_eq!(neighbor_cell(2, 3, (0, 1), CellMove::Down), Some((1, 1)));
+        assert_eq!(neighbor_cell(2, 3, (1, 1), CellMove::Down), None);
+        assert_eq!(neighbor_cell(2, 3, (0, 2), CellMove::Next), Some((1, 0)));
+        assert_eq!(neighbor_cell(2, 3, (1, 2), CellMove::Next), None);
+        assert_eq!(
+            neighbor_cell(2, 3, (1, 0), CellMove::Previous),
+            Some((0, 2))
+        );
+        assert_eq!(neighbor_cell(2, 3, (0, 0), CellMove::Previous), None);
+    }
+}
@@ -0,0 +1,184 @@
+//! Measure tool overlay
+//!
+//! Dragging with the Measure tool draws a dimension line between two points,
+//! labelled with the distance and its horizontal and vertical components in
+//! the current ruler unit. Nothing is added to the document; the line stays
+//! until the next measurement or until another tool is chosen.
+
+use super::grid_rendering::RulerUnit;
+use super::overlays;
+use super::rendering::{snap_to_guide, GuideOrientation, RenderConfig};
+use super::snapping::snap_to_object_edges;
+use gtk4::cairo::Context;
+use testruct_core::layout::{Point, Rect, Size};
+
+/// Length of the ticks across the ends of the dimension line, in screen pixels
+const END_TICK_LENGTH: f64 = 8.0;
+
+/// A measured span in document coordinates
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Measurement {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl Measurement {
+    pub fn new(start: Point, end: Point) -> Self {
+        Self { start, end }
+    }
+
+    /// Horizontal component (positive to the right)
+    pub fn dx(&self) -> f32 {
+        self.end.x - self.start.x
+    }
+
+    /// Vertical component (positive downwards)
+    pub fn dy(&self) -> f32 {
+        self.end.y - self.start.y
+    }
+
+    /// Straight-line distance between the two points
+    pub fn distance(&self) -> f32 {
+        self.dx().hypot(self.dy())
+    }
+
+    /// Smallest rectangle containing both points
+    pub fn bounds(&self) -> Rect {
+        let min = Point::new(self.start.x.min(self.end.x), self.start.y.min(self.end.y));
+        Rect::new(min, Size::new(self.dx().abs(), self.dy().abs()))
+    }
+}
+
+/// Distance and component readout for `measurement` in the given ruler unit
+///
+/// Millimeters get one decimal place, pixels and points are whole numbers.
+pub fn measurement_label(measurement: &Measurement, unit: RulerUnit) -> String {
+    let decimals = match unit {
+        RulerUnit::Millimeters => 1,
+        RulerUnit::Pixels | RulerUnit::Points => 0,
+    };
+    let value = |pixels: f32| unit.from_pixels(pixels as f64);
+    format!(
+        "{:.*} {}  (ΔX: {:.*}  ΔY: {:.*})",
+        decimals,
+        value(measurement.distance()),
+        unit.label(),
+        decimals,
+        value(measurement.dx().abs()),
+        decimals,
+        value(measurement.dy().abs()),
+    )
+}
+
+/// Snap a measurement endpoint to guides and element edges
+///
+/// Each axis snaps to a guide within the guide snap distance (screen pixels)
+/// when guide snapping is on, otherwise to the closest edge or center of an
+/// element in `elements` when object snapping is on.
+pub fn snap_measure_point(config: &RenderConfig, point: Point, elements: &[Rect]) -> Point {
+    let zoom = if config.zoom > 0.0 { config.zoom } else { 1.0 };
+    let threshold = config.guide_snap_distance / zoom as f32;
+
+    let edges = if config.snap_to_objects {
+        let probe = Rect::new(point, Size::new(0.0, 0.0));
+        snap_to_object_edges(&probe, elements, threshold)
+            .bounds
+            .origin
+    } else {
+        point
+    };
+    let guide = |value: f32, orientation: GuideOrientation| {
+        if config.snap_to_guides {
+            snap_to_guide(value, &config.guides, orientation, threshold)
+        } else {
+            None
+        }
+    };
+    Point::new(
+        guide(point.x, GuideOrientation::Vertical).unwrap_or(edges.x),
+        guide(point.y, GuideOrientation::Horizontal).unwrap_or(edges.y),
+    )
+}
+
+/// Draw the dimension line, its components and the distance label
+///
+/// Drawn in document coordinates; line widths and the label are divided by
+/// `zoom` so they keep the same on-screen size.
+pub fn draw_measurement(
+    ctx: &Context,
+    measurement: &Measurement,
+    unit: RulerUnit,
+    zoom: f64,
+) -> Result<(), gtk4::cairo::Error> {
+    let (x1, y1) = (measurement.start.x as f64, measurement.start.y as f64);
+    let (x2, y2) = (measurement.end.x as f64, measurement.end.y as f64);
+
+    // Horizontal and vertical components as a dashed right angle
+    ctx.set_source_rgba(0.4, 0.4, 0.4, 0.9);
+    ctx.set_line_width(1.0 / zoom);
+    ctx.set_dash(&[4.0 / zoom, 3.0 / zoom], 0.0);
+    ctx.move_to(x1, y1);
+    ctx.line_to(x2, y1);
+    ctx.line_to(x2, y2);
+    ctx.stroke()?;
+    ctx.set_dash(&[], 0.0);
+
+    // Dimension line with a tick across each end
+    ctx.set_source_rgb(0.85, 0.15, 0.15);
+    ctx.set_line_width(1.5 / zoom);
+    ctx.move_to(x1, y1);
+    ctx.line_to(x2, y2);
+    let length = measurement.distance() as f64;
+    if length > 0.0 {
+        let half_tick = END_TICK_LENGTH / 2.0 / zoom;
+        let (nx, ny) = (-(y2 - y1) / length, (x2 - x1) / length);
+        for (x, y) in [(x1, y1), (x2, y2)] {
+            ctx.move_to(x - nx * half_tick, y - ny * half_tick);
+            ctx.line_to(x + nx * half_tick, y + ny * half_tick);
+        }
+    }
+    ctx.stroke()?;
+
+    let text = measurement_label(measurement, unit);
+    overlays::draw_cursor_readout(ctx, measurement.end, &text, zoom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::canvas::rendering::Guide;
+
+    #[test]
+    fn test_measurement_label_uses_ruler_unit() {
+        let measurement = Measurement::new(Point::new(10.0, 10.0), Point::new(106.0, 138.0));
+        assert_eq!(measurement.distance(), 160.0);
+        assert_eq!(
+            measurement_label(&measurement, RulerUnit::Pixels),
+            "160 px  (ΔX: 96  ΔY: 128)"
+        );
+        assert_eq!(
+            measurement_label(&measurement, RulerUnit::Millimeters),
+            "42.3 mm  (ΔX: 25.4  ΔY: 33.9)"
+        );
+    }
+
+    #[test]
+    fn test_snap_measure_point_prefers_guides_over_edges() {
+        let mut config = RenderConfig {
+            snap_to_grid: false,
+            ..Default::default()
+        };
+        config
+            .guides
+            .push(Guide::new(GuideOrientation::Vertical, 52.0));
+        let elements = [Rect::new(Point::new(48.0, 100.0), Size::new(50.0, 20.0))];
+
+        let snapped = snap_measure_point(&config, Point::new(50.0, 118.0), &elements);
+        assert_eq!(snapped, Point::new(52.0, 120.0));
+
+        config.snap_to_guides = false;
+        config.snap_to_objects = false;
+        let free = snap_measure_point(&config, Point::new(50.0, 118.0), &elements);
+        assert_eq!(free, Point::new(50.0, 118.0));
+    }
+}
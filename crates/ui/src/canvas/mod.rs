@@ -3,16 +3,20 @@ pub mod dirty_region;
 pub mod grid_rendering;
 pub mod input;
 pub mod keyboard;
+pub mod measure;
 pub mod mouse;
 pub mod overlays;
 pub mod page_thumbnail;
 pub mod rendering;
 pub mod rendering_images;
+pub mod rendering_qr;
 pub mod rendering_selection;
+pub mod rendering_table;
 pub mod rendering_text;
 pub mod selection;
 pub mod shapes_rendering;
 pub mod snapping;
+pub mod spell_check;
 pub mod text_editor;
 pub mod tools;
 
@@ -21,10 +25,17 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 use crate::app::AppState;
-use dirty_region::DirtyRegionTracker;
+use dirty_region::{DirtyRegionTracker, FrameCache, FrameKey};
 use grid_rendering::RulerConfig;
 use rendering::RenderConfig;
 
+/// Callback invoked with the new selection whenever it changes
+pub type SelectionListener = Rc<dyn Fn(&[uuid::Uuid])>;
+
+/// Callback invoked with the pointer position in document coordinates,
+/// or `None` once the pointer leaves the canvas
+pub type PointerListener = Rc<dyn Fn(Option<testruct_core::layout::Point>)>;
+
 /// Render state tracking
 #[derive(Clone)]
 pub struct CanvasRenderState {
@@ -36,6 +47,19 @@ pub struct CanvasRenderState {
     pub dirty_region: DirtyRegionTracker,
     /// Active smart guide snap lines to render
     pub snap_lines: Rc<RefCell<Vec<snapping::SnapLine>>>,
+    /// Decoded image surfaces, so frames don't reload images from disk
+    pub image_cache: Rc<RefCell<rendering_images::ImageSurfaceCache>>,
+    /// Dictionary for underlining misspelled words in the text being edited
+    pub spell_check: Rc<RefCell<spell_check::SpellCheckState>>,
+    /// Last pointer position over the canvas in document coordinates, kept
+    /// after the pointer leaves so paste-at-cursor still has a target
+    pub last_pointer: Rc<RefCell<Option<testruct_core::layout::Point>>>,
+    /// Listeners notified when the selection changes
+    selection_listeners: Rc<RefCell<Vec<SelectionListener>>>,
+    /// Listeners notified when the pointer moves over the canvas
+    pointer_listeners: Rc<RefCell<Vec<PointerListener>>>,
+    /// Last rendered frame, patched in place for partial redraws
+    frame_cache: Rc<RefCell<Option<FrameCache>>>,
 }
 
 impl Default for CanvasRenderState {
@@ -48,6 +72,98 @@ impl Default for CanvasRenderState {
             tool_state: Rc::new(RefCell::new(tools::ToolState::default())),
             dirty_region: dirty_region::new_tracker(),
             snap_lines: Rc::new(RefCell::new(Vec::new())),
+            image_cache: Rc::new(RefCell::new(Default::default())),
+            spell_check: Rc::new(RefCell::new(Default::default())),
+            last_pointer: Rc::new(RefCell::new(None)),
+            selection_listeners: Rc::new(RefCell::new(Vec::new())),
+            pointer_listeners: Rc::new(RefCell::new(Vec::new())),
+            frame_cache: Rc::new(RefCell::new(None)),
+        }
+    }
+}
+
+impl CanvasRenderState {
+    /// Register a callback to run after the selection changes
+    pub fn connect_selection_changed<F>(&self, listener: F)
+    where
+        F: Fn(&[uuid::Uuid]) + 'static,
+    {
+        self.selection_listeners.borrow_mut().push(Rc::new(listener));
+    }
+
+    /// Notify all selection listeners with the current selection
+    pub fn notify_selection_changed(&self) {
+        let selection = self.selected_ids.borrow().clone();
+        // Clone the list so listeners may register further callbacks
+        let listeners = self.selection_listeners.borrow().clone();
+        for listener in listeners {
+            listener(&selection);
+        }
+    }
+
+    /// Register a callback to run when the pointer moves over the canvas
+    ///
+    /// Listeners run on every motion event, so they must stay cheap and must
+    /// not queue canvas redraws.
+    pub fn connect_pointer_moved<F>(&self, listener: F)
+    where
+        F: Fn(Option<testruct_core::layout::Point>) + 'static,
+    {
+        self.pointer_listeners.borrow_mut().push(Rc::new(listener));
+    }
+
+    /// Notify all pointer listeners of the pointer position (document coordinates)
+    pub fn notify_pointer_moved(&self, position: Option<testruct_core::layout::Point>) {
+        let listeners = self.pointer_listeners.borrow().clone();
+        for listener in listeners {
+            listener(position);
+        }
+    }
+
+    /// Limit the next redraw to `rect` (document coordinates)
+    ///
+    /// Dirty rectangles accumulate until the next frame. Callers still queue
+    /// the draw themselves; a draw queued with nothing marked repaints fully.
+    pub fn mark_dirty(&self, rect: testruct_core::layout::Rect) {
+        self.dirty_region.borrow_mut().add_rect(rect);
+    }
+
+    /// Mark the current extent of the elements in `ids` on the active page dirty
+    ///
+    /// Call before and after changing the elements so both the old and the
+    /// new area are repainted. Rotated elements mark their full sweep, and
+    /// text on a path the glyphs along it.
+    pub fn mark_elements_dirty(&self, app_state: &AppState, ids: &[uuid::Uuid]) {
+        let Some(page) = app_state.active_page() else {
+            return;
+        };
+        for element in page.elements.iter().filter(|e| ids.contains(&e.id())) {
+            let bounds = match element {
+                testruct_core::document::DocumentElement::Text(text) => match &text.text_path {
+                    Some(path) => path.text_extent(&text.bounds, text.style.font_size),
+                    None => text.bounds,
+                },
+                _ => *element.bounds(),
+            };
+            if element.rotation().abs() > f32::EPSILON {
+                self.mark_dirty(dirty_region::rotation_extent(&bounds));
+            } else {
+                self.mark_dirty(bounds);
+            }
+        }
+    }
+
+    /// Repaint the whole canvas on the next frame
+    pub fn mark_full_redraw(&self) {
+        self.dirty_region.borrow_mut().mark_full_redraw();
+    }
+
+    /// Restore the view settings saved with a freshly loaded document
+    ///
+    /// Documents saved before view state was persisted keep the current view.
+    pub fn apply_document_view(&self, document: &testruct_core::Document) {
+        if let Some(view) = &document.metadata.view {
+            self.config.borrow_mut().apply_view_state(view);
         }
     }
 }
@@ -101,6 +217,10 @@ impl CanvasView {
 
         let render_state = CanvasRenderState::default();
 
+        // Saving records the canvas view settings on the document
+        let view_config = render_state.config.clone();
+        app_state.set_view_state_source(move || view_config.borrow().view_state());
+
         // Connect IME Entry signals for Japanese input (must be after render_state is created)
         let render_state_ime = render_state.clone();
         let app_state_ime = app_state.clone();
@@ -112,8 +232,9 @@ impl CanvasView {
                 drop(tool_state);
 
                 // Update text element content
+                let page_index = app_state_ime.active_page_index();
                 app_state_ime.with_mutable_active_document(|doc| {
-                    if let Some(page) = doc.pages.first_mut() {
+                    if let Some(page) = doc.pages.get_mut(page_index) {
                         for element in &mut page.elements {
                             if let testruct_core::document::DocumentElement::Text(text_elem) = element {
                                 if text_elem.id == text_id {
@@ -139,10 +260,7 @@ impl CanvasView {
         let drawing_area_activate = drawing_area.clone();
         ime_entry.connect_activate(move |_entry| {
             // Exit text editing mode
-            let mut tool_state = render_state_activate.tool_state.borrow_mut();
-            tool_state.editing_text_id = None;
-            tool_state.editing_cursor_pos = 0;
-            drop(tool_state);
+            render_state_activate.tool_state.borrow_mut().exit_text_editing();
 
             ime_entry_activate.set_visible(false);
             ime_entry_activate.set_text("");
@@ -180,10 +298,7 @@ impl CanvasView {
             // Escape: Cancel editing
             if keyval == gtk4::gdk::Key::Escape {
                 // Exit text editing mode
-                let mut tool_state = render_state_key.tool_state.borrow_mut();
-                tool_state.editing_text_id = None;
-                tool_state.editing_cursor_pos = 0;
-                drop(tool_state);
+                render_state_key.tool_state.borrow_mut().exit_text_editing();
 
                 ime_entry_key.set_visible(false);
                 ime_entry_key.set_text("");
@@ -198,6 +313,7 @@ impl CanvasView {
 
         // Setup drawing function
         Self::setup_draw_func(&drawing_area, &app_state, &render_state);
+        Self::watch_image_files(&drawing_area, &render_state);
 
         // Wire up all event handlers - must happen AFTER container setup
         input::wire_pointer_events(&drawing_area, &render_state, &app_state, &ime_entry);
@@ -238,19 +354,176 @@ impl CanvasView {
         let app_state_clone = app_state.clone();
         let render_state = render_state.clone();
 
-        drawing_area.set_draw_func(move |_area, ctx, width, height| {
-            if let Err(e) = Self::draw_canvas(
-                ctx,
-                width as f64,
-                height as f64,
-                &app_state_clone,
-                &render_state,
-            ) {
+        drawing_area.set_draw_func(move |area, ctx, width, height| {
+            let key = FrameKey {
+                width,
+                height,
+                scale: area.scale_factor().max(1),
+                zoom: render_state.config.borrow().zoom,
+                pan_x: render_state.config.borrow().pan_x,
+                pan_y: render_state.config.borrow().pan_y,
+                ruler_size: render_state.ruler_config.borrow().size,
+                page_index: app_state_clone.active_page_index(),
+            };
+            if let Err(e) = Self::draw_frame(ctx, key, &app_state_clone, &render_state) {
                 eprintln!("Canvas draw error: {}", e);
             }
         });
     }
 
+    /// Repaint images whose files changed on disk
+    ///
+    /// Drawing never stats the image files; this timer compares the cached
+    /// modification times instead.
+    fn watch_image_files(drawing_area: &DrawingArea, render_state: &CanvasRenderState) {
+        let weak_area = drawing_area.downgrade();
+        let render_state = render_state.clone();
+        gtk4::glib::timeout_add_local(rendering_images::IMAGE_RECHECK_INTERVAL, move || {
+            // Stop once the canvas has been destroyed
+            let Some(drawing_area) = weak_area.upgrade() else {
+                return gtk4::glib::ControlFlow::Break;
+            };
+            if render_state.image_cache.borrow_mut().drop_changed() {
+                render_state.mark_full_redraw();
+                drawing_area.queue_draw();
+            }
+            gtk4::glib::ControlFlow::Continue
+        });
+    }
+
+    /// Draw the canvas through the frame cache
+    ///
+    /// When the previous frame was rendered with the same view and only some
+    /// rectangles are dirty, the scene is redrawn clipped to those rectangles
+    /// (plus the overlays of this and the previous frame) on top of the cached
+    /// frame. Anything else — zoom, pan, resize, page switch or a draw queued
+    /// without dirty rectangles — repaints the whole canvas.
+    fn draw_frame(
+        ctx: &gtk4::cairo::Context,
+        key: FrameKey,
+        app_state: &AppState,
+        render_state: &CanvasRenderState,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let region = std::mem::take(&mut *render_state.dirty_region.borrow_mut());
+        let overlay_rects = Self::overlay_rects(render_state, &key);
+
+        let mut cache = render_state.frame_cache.borrow_mut();
+        let reusable = cache.as_ref().is_some_and(|cache| cache.key == key);
+        let clip_rects = if reusable && !region.is_empty() && !region.needs_full_redraw() {
+            let previous_overlays = cache.as_ref().map(|c| c.overlay_rects.clone());
+            let mut rects: Vec<_> = region
+                .get_rects()
+                .iter()
+                .map(|r| key.to_widget(r))
+                .collect();
+            rects.extend(previous_overlays.unwrap_or_default());
+            rects.extend(overlay_rects.iter().copied());
+            Some(rects)
+        } else {
+            None
+        };
+        if !reusable {
+            *cache = Some(FrameCache::new(key)?);
+        }
+        let Some(cache) = cache.as_mut() else {
+            return Ok(());
+        };
+
+        {
+            let frame_ctx = gtk4::cairo::Context::new(&cache.surface)?;
+            if let Some(rects) = &clip_rects {
+                for rect in rects {
+                    frame_ctx.rectangle(
+                        rect.origin.x as f64,
+                        rect.origin.y as f64,
+                        rect.size.width as f64,
+                        rect.size.height as f64,
+                    );
+                }
+                frame_ctx.clip();
+            }
+            Self::draw_canvas(
+                &frame_ctx,
+                key.width as f64,
+                key.height as f64,
+                app_state,
+                render_state,
+            )?;
+        }
+        cache.overlay_rects = overlay_rects;
+
+        ctx.set_source_surface(&cache.surface, 0.0, 0.0)?;
+        ctx.paint()?;
+        Ok(())
+    }
+
+    /// Widget-space extents of the transient overlays drawn over the page
+    ///
+    /// These move without touching the document, so partial frames always
+    /// repaint where they were and where they are now.
+    fn overlay_rects(
+        render_state: &CanvasRenderState,
+        key: &FrameKey,
+    ) -> Vec<testruct_core::layout::Rect> {
+        use testruct_core::layout::{Point, Rect, Size};
+
+        // Generous on-screen size of a cursor readout label
+        const READOUT_SIZE: Size = Size {
+            width: 320.0,
+            height: 32.0,
+        };
+
+        let mut rects = Vec::new();
+        if let Some(drag_rect) = render_state.drag_box.borrow().as_ref() {
+            rects.push(key.to_widget(drag_rect));
+        }
+        for line in render_state.snap_lines.borrow().iter() {
+            let min = line.bounds.0.min(line.bounds.1);
+            let max = line.bounds.0.max(line.bounds.1);
+            let rect = if line.is_horizontal {
+                Rect::new(Point::new(min, line.position), Size::new(max - min, 0.0))
+            } else {
+                Rect::new(Point::new(line.position, min), Size::new(0.0, max - min))
+            };
+            rects.push(key.to_widget(&rect));
+        }
+
+        let tool_state = render_state.tool_state.borrow();
+        if tool_state.current_tool == tools::ToolMode::Polygon {
+            let points = &tool_state.polygon_points;
+            if let Some(first) = points.first() {
+                let (mut min, mut max) = (*first, *first);
+                for point in points {
+                    min = Point::new(min.x.min(point.x), min.y.min(point.y));
+                    max = Point::new(max.x.max(point.x), max.y.max(point.y));
+                }
+                let rect = Rect::new(min, Size::new(max.x - min.x, max.y - min.y));
+                rects.push(key.to_widget(&rect));
+            }
+        }
+
+        if let Some(measurement) = tool_state.measure_line.as_ref() {
+            rects.push(key.to_widget(&measurement.bounds()));
+        }
+
+        let readout_points = [
+            tool_state.rotation_label.map(|(point, _)| point),
+            tool_state.measurement.map(|(point, _)| point),
+            tool_state.measure_line.map(|measurement| measurement.end),
+        ];
+        for point in readout_points.into_iter().flatten() {
+            let anchor = key.to_widget(&Rect::new(point, Size::new(0.0, 0.0)));
+            rects.push(Rect::new(
+                anchor.origin,
+                Size::new(
+                    anchor.size.width + READOUT_SIZE.width,
+                    anchor.size.height + READOUT_SIZE.height,
+                ),
+            ));
+        }
+        rects
+    }
+
     /// Main canvas drawing function
     fn draw_canvas(
         ctx: &gtk4::cairo::Context,
@@ -275,16 +548,23 @@ impl CanvasView {
             return Ok(());
         };
 
-        // Get page size from document metadata
-        let page_size = document.metadata.page_size.to_size();
-
-        // Draw page border
-        rendering::draw_page_border(ctx, &page_size)?;
+        // Pages may override the document's page size
+        let page_size = document.page_size_of(&page);
 
         // Apply zoom and pan (ruler offset already applied by draw_rulers)
         ctx.translate(config.pan_x, config.pan_y);
         ctx.scale(config.zoom, config.zoom);
 
+        // Draw page background and border
+        rendering::draw_page_background(
+            ctx,
+            &page_size,
+            &page.metadata.background,
+            app_state,
+            &mut render_state.image_cache.borrow_mut(),
+        )?;
+        rendering::draw_page_border(ctx, &page_size)?;
+
         // Draw grid if enabled
         if config.show_grid {
             grid_rendering::draw_grid_with_config(ctx, &page_size, &config.grid_config)?;
@@ -302,27 +582,70 @@ impl CanvasView {
 
         // Draw drag preview box (blue outline while dragging)
         if let Some(drag_rect) = render_state.drag_box.borrow().as_ref() {
-            ctx.set_source_rgb(0.05, 0.49, 0.86); // Blue color
-            ctx.set_line_width(2.0 / config.zoom); // Account for zoom
             ctx.rectangle(
                 drag_rect.origin.x as f64,
                 drag_rect.origin.y as f64,
                 drag_rect.size.width as f64,
                 drag_rect.size.height as f64,
             );
+            if render_state.tool_state.borrow().marquee_selecting {
+                // Marquee selection: translucent fill with a dashed outline
+                ctx.set_source_rgba(0.05, 0.49, 0.86, 0.1);
+                ctx.fill_preserve()?;
+                ctx.set_dash(&[4.0 / config.zoom, 2.0 / config.zoom], 0.0);
+                ctx.set_line_width(1.0 / config.zoom);
+            } else {
+                ctx.set_line_width(2.0 / config.zoom); // Account for zoom
+            }
+            ctx.set_source_rgb(0.05, 0.49, 0.86); // Blue color
             ctx.stroke()?;
+            ctx.set_dash(&[], 0.0);
+        }
+
+        // Draw polygon in progress (placed vertices joined by a dashed outline)
+        {
+            let tool_state = render_state.tool_state.borrow();
+            let points = &tool_state.polygon_points;
+            if tool_state.current_tool == tools::ToolMode::Polygon && !points.is_empty() {
+                ctx.set_source_rgb(0.05, 0.49, 0.86);
+                ctx.set_line_width(1.5 / config.zoom);
+                ctx.set_dash(&[4.0 / config.zoom, 2.0 / config.zoom], 0.0);
+                for (i, point) in points.iter().enumerate() {
+                    if i == 0 {
+                        ctx.move_to(point.x as f64, point.y as f64);
+                    } else {
+                        ctx.line_to(point.x as f64, point.y as f64);
+                    }
+                }
+                ctx.stroke()?;
+                ctx.set_dash(&[], 0.0);
+
+                let marker = 3.0 / config.zoom;
+                for point in points {
+                    ctx.rectangle(
+                        point.x as f64 - marker,
+                        point.y as f64 - marker,
+                        marker * 2.0,
+                        marker * 2.0,
+                    );
+                }
+                ctx.fill()?;
+            }
         }
 
         // Draw smart guide snap lines
         let snap_lines = render_state.snap_lines.borrow();
-        if !snap_lines.is_empty() {
+        let (edge_lines, grid_lines): (Vec<_>, Vec<_>) = snap_lines
+            .iter()
+            .partition(|line| line.line_type == snapping::SnapLineType::ObjectEdge);
+        if !edge_lines.is_empty() {
             ctx.set_source_rgb(1.0, 0.4, 0.7); // Magenta/pink color for smart guides
             ctx.set_line_width(1.0 / config.zoom); // Thin line adjusted for zoom
 
             // Set dashed line pattern
             ctx.set_dash(&[5.0 / config.zoom, 3.0 / config.zoom], 0.0);
 
-            for line in snap_lines.iter() {
+            for line in edge_lines {
                 if line.is_horizontal {
                     // Horizontal line at Y position
                     ctx.move_to(line.bounds.0 as f64, line.position as f64);
@@ -338,12 +661,47 @@ impl CanvasView {
             // Reset dash pattern
             ctx.set_dash(&[], 0.0);
         }
+        // Resized edges resting on a guide or grid line: solid like a guide,
+        // in orange so they don't read as guides
+        if !grid_lines.is_empty() {
+            ctx.set_source_rgb(1.0, 0.55, 0.0);
+            ctx.set_line_width(1.0 / config.zoom);
+            for line in grid_lines {
+                if line.is_horizontal {
+                    ctx.move_to(line.bounds.0 as f64, line.position as f64);
+                    ctx.line_to(line.bounds.1 as f64, line.position as f64);
+                } else {
+                    ctx.move_to(line.position as f64, line.bounds.0 as f64);
+                    ctx.line_to(line.position as f64, line.bounds.1 as f64);
+                }
+                ctx.stroke()?;
+            }
+        }
         drop(snap_lines);
 
+        // Current angle next to the cursor while rotating with the handle
+        let tool_state = render_state.tool_state.borrow();
+        if let Some((point, angle)) = tool_state.rotation_label {
+            overlays::draw_cursor_readout(ctx, point, &format!("{:.0}°", angle), config.zoom)?;
+        }
+
+        // Position and size next to the cursor while moving or resizing
+        if let Some((point, bounds)) = tool_state.measurement {
+            let text = overlays::measurement_text(&bounds, ruler_config.unit);
+            overlays::draw_cursor_readout(ctx, point, &text, config.zoom)?;
+        }
+
+        // Dimension line of the Measure tool
+        if tool_state.current_tool == tools::ToolMode::Measure {
+            if let Some(measurement) = tool_state.measure_line.as_ref() {
+                measure::draw_measurement(ctx, measurement, ruler_config.unit, config.zoom)?;
+            }
+        }
+
         Ok(())
     }
 
-    /// Draw all document elements on the page
+    /// Draw all document elements on the page, layer by layer
     fn draw_elements(
         ctx: &gtk4::cairo::Context,
         page: &testruct_core::document::Page,
@@ -351,8 +709,15 @@ impl CanvasView {
         render_state: &CanvasRenderState,
         app_state: &AppState,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        for element in &page.elements {
-            Self::draw_element(ctx, element, selected_ids, render_state, app_state)?;
+        for element in page.layered_elements() {
+            if page.is_in_locked_layer(element.id()) && !element.is_locked() {
+                // Elements of a locked layer get the same badge and no handles
+                let mut element = element.clone();
+                element.set_locked(true);
+                Self::draw_element(ctx, &element, selected_ids, render_state, app_state)?;
+            } else {
+                Self::draw_element(ctx, element, selected_ids, render_state, app_state)?;
+            }
         }
         Ok(())
     }
@@ -372,6 +737,18 @@ impl CanvasView {
             return Ok(());
         }
 
+        // Rotated elements (and their selection box and handles) are drawn in a
+        // context turned about the element center
+        let rotation = element.rotation();
+        if rotation != 0.0 {
+            ctx.save()?;
+            rendering::rotate_about_center(ctx, element.bounds(), rotation);
+        }
+
+        // Element contents are painted through a group at the element opacity;
+        // selection boxes and handles stay fully opaque
+        let opacity = element.opacity();
+
         match element {
             DocumentElement::Group(group) => {
                 // Draw group border (similar to frame but with different styling)
@@ -395,13 +772,19 @@ impl CanvasView {
                         a: 1.0,
                     };
                     rendering::draw_selection_box(ctx, &group.bounds, &selection_color)?;
-                    rendering::draw_resize_handles(ctx, &group.bounds, &selection_color)?;
+                    // Locked elements can't be resized, so they get no handles
+                    if !element.is_locked() {
+                        rendering::draw_resize_handles(ctx, &group.bounds, &selection_color)?;
+                    }
                 }
 
                 // Recursively draw group children
-                for child in &group.children {
-                    Self::draw_element(ctx, child, selected_ids, render_state, app_state)?;
-                }
+                rendering::paint_with_opacity(ctx, opacity, || {
+                    for child in &group.children {
+                        Self::draw_element(ctx, child, selected_ids, render_state, app_state)?;
+                    }
+                    Ok::<_, Box<dyn std::error::Error>>(())
+                })?;
             }
             DocumentElement::Frame(frame) => {
                 // Draw frame border
@@ -425,13 +808,18 @@ impl CanvasView {
                         a: 1.0,
                     };
                     rendering::draw_selection_box(ctx, &frame.bounds, &selection_color)?;
-                    rendering::draw_resize_handles(ctx, &frame.bounds, &selection_color)?;
+                    if !element.is_locked() {
+                        rendering::draw_resize_handles(ctx, &frame.bounds, &selection_color)?;
+                    }
                 }
 
                 // Recursively draw frame children
-                for child in &frame.children {
-                    Self::draw_element(ctx, child, selected_ids, render_state, app_state)?;
-                }
+                rendering::paint_with_opacity(ctx, opacity, || {
+                    for child in &frame.children {
+                        Self::draw_element(ctx, child, selected_ids, render_state, app_state)?;
+                    }
+                    Ok::<_, Box<dyn std::error::Error>>(())
+                })?;
             }
             DocumentElement::Text(text) => {
                 // Use actual bounds from text element
@@ -443,13 +831,53 @@ impl CanvasView {
                 let tool_state = render_state.tool_state.borrow();
                 let is_editing = tool_state.editing_text_id == Some(text.id);
                 let cursor_pos = tool_state.editing_cursor_pos;
+                let text_selection = tool_state.text_selection();
                 drop(tool_state);
 
-                rendering::draw_text_element(ctx, text_bounds, &text.content, &text.style)?;
+                rendering::paint_with_opacity(ctx, opacity, || match &text.text_path {
+                    // Path text is shown flat while editing so the cursor lines up
+                    Some(path) if !is_editing => rendering::draw_text_on_path(
+                        ctx,
+                        text_bounds,
+                        &text.content,
+                        &text.style,
+                        path,
+                    ),
+                    _ => rendering::draw_text_element(
+                        ctx,
+                        text_bounds,
+                        &text.content,
+                        &text.style,
+                        text.rich_text_for_render(),
+                        &text.padding,
+                    ),
+                })?;
 
                 if is_editing {
                     // Draw editing frame
                     rendering::draw_text_editing_frame(ctx, text_bounds)?;
+                    let misspelled = render_state
+                        .spell_check
+                        .borrow()
+                        .misspelled_ranges(&text.content);
+                    rendering::draw_spelling_underlines(
+                        ctx,
+                        text_bounds,
+                        &text.content,
+                        &misspelled,
+                        &text.style,
+                        &text.padding,
+                    )?;
+                    if let Some(selection) = text_selection {
+                        rendering::draw_text_selection(
+                            ctx,
+                            text_bounds,
+                            &text.content,
+                            selection,
+                            &text.style,
+                            &text.padding,
+                        )?;
+                    }
                     // Draw cursor
                     rendering::draw_text_cursor(
                         ctx,
@@ -457,6 +885,7 @@ impl CanvasView {
                         &text.content,
                         cursor_pos,
                         &text.style,
+                        &text.padding,
                     )?;
                 } else if is_selected {
                     let selection_color = testruct_core::typography::Color {
@@ -466,18 +895,28 @@ impl CanvasView {
                         a: 1.0,
                     };
                     rendering::draw_selection_box(ctx, text_bounds, &selection_color)?;
-                    rendering::draw_resize_handles(ctx, text_bounds, &selection_color)?;
+                    if !element.is_locked() {
+                        rendering::draw_resize_handles(ctx, text_bounds, &selection_color)?;
+                    }
                 }
             }
             DocumentElement::Image(image) => {
                 // Draw image element with actual image or fallback to placeholder
-                if let Err(e) =
-                    rendering::draw_image_element(ctx, &image.bounds, &image.source, app_state)
-                {
-                    tracing::warn!("Failed to render image: {}", e);
-                    // Fallback to placeholder if rendering fails
-                    rendering::draw_image_placeholder(ctx, &image.bounds)?;
-                }
+                rendering::paint_with_opacity(ctx, opacity, || {
+                    if let Err(e) = rendering::draw_image_element(
+                        ctx,
+                        &image.bounds,
+                        &image.source,
+                        image.fit,
+                        app_state,
+                        &mut render_state.image_cache.borrow_mut(),
+                    ) {
+                        tracing::warn!("Failed to render image: {}", e);
+                        // Fallback to placeholder if rendering fails
+                        rendering::draw_image_placeholder(ctx, &image.bounds)?;
+                    }
+                    Ok::<_, gtk4::cairo::Error>(())
+                })?;
 
                 let is_selected = selected_ids.contains(&image.id);
                 if is_selected {
@@ -488,56 +927,110 @@ impl CanvasView {
                         a: 1.0,
                     };
                     rendering::draw_selection_box(ctx, &image.bounds, &selection_color)?;
-                    rendering::draw_resize_handles(ctx, &image.bounds, &selection_color)?;
+                    if !element.is_locked() {
+                        rendering::draw_resize_handles(ctx, &image.bounds, &selection_color)?;
+                    }
                 }
             }
             DocumentElement::Shape(shape) => {
-                match shape.kind {
-                    ShapeKind::Rectangle => {
-                        rendering::draw_rectangle(
-                            ctx,
-                            &shape.bounds,
-                            &shape.stroke,
-                            shape.stroke_width,
-                            &shape.fill,
-                        )?;
-                    }
-                    ShapeKind::Ellipse => {
-                        rendering::draw_ellipse(
-                            ctx,
-                            &shape.bounds,
-                            &shape.stroke,
-                            shape.stroke_width,
-                            &shape.fill,
-                        )?;
-                    }
-                    ShapeKind::Line => {
-                        rendering::draw_line(
-                            ctx,
-                            &shape.bounds,
-                            &shape.stroke,
-                            shape.stroke_width,
-                        )?;
+                rendering::paint_with_opacity(ctx, opacity, || {
+                    shapes_rendering::apply_line_style(ctx, shape.line_style, shape.stroke_width);
+                    match shape.kind {
+                        ShapeKind::Rectangle => {
+                            rendering::draw_rectangle(
+                                ctx,
+                                &shape.bounds,
+                                &shape.stroke,
+                                shape.stroke_width,
+                                &shape.fill,
+                                shape.effective_corner_radius(),
+                            )?;
+                        }
+                        ShapeKind::Ellipse => {
+                            rendering::draw_ellipse(
+                                ctx,
+                                &shape.bounds,
+                                &shape.stroke,
+                                shape.stroke_width,
+                                &shape.fill,
+                            )?;
+                        }
+                        ShapeKind::Line => {
+                            rendering::draw_line(
+                                ctx,
+                                &shape.bounds,
+                                &shape.stroke,
+                                shape.stroke_width,
+                            )?;
+                        }
+                        ShapeKind::Arrow => {
+                            rendering::draw_arrow(
+                                ctx,
+                                &shape.bounds,
+                                &shape.stroke,
+                                shape.stroke_width,
+                            )?;
+                        }
+                        ShapeKind::Polygon => {
+                            rendering::draw_polygon(
+                                ctx,
+                                &shape.bounds,
+                                &shape.vertex_positions(),
+                                &shape.stroke,
+                                shape.stroke_width,
+                                &shape.fill,
+                            )?;
+                        }
                     }
-                    ShapeKind::Arrow => {
-                        rendering::draw_arrow(
-                            ctx,
-                            &shape.bounds,
-                            &shape.stroke,
-                            shape.stroke_width,
-                        )?;
+                    shapes_rendering::clear_line_style(ctx);
+                    Ok::<_, Box<dyn std::error::Error>>(())
+                })?;
+
+                let is_selected = selected_ids.contains(&shape.id);
+                if is_selected {
+                    let selection_color = testruct_core::typography::Color {
+                        r: 0.05,
+                        g: 0.49,
+                        b: 0.86,
+                        a: 1.0,
+                    };
+                    rendering::draw_selection_box(ctx, &shape.bounds, &selection_color)?;
+                    if !element.is_locked() {
+                        rendering::draw_resize_handles(ctx, &shape.bounds, &selection_color)?;
                     }
-                    ShapeKind::Polygon => {
-                        rendering::draw_polygon(
-                            ctx,
-                            &shape.bounds,
-                            &shape.stroke,
-                            shape.stroke_width,
-                        )?;
+                }
+            }
+            DocumentElement::Table(table) => {
+                rendering::paint_with_opacity(ctx, opacity, || rendering::draw_table(ctx, table))?;
+
+                // Highlight the cell being edited while the table is entered
+                let tool_state = render_state.tool_state.borrow();
+                let editing_cell = tool_state
+                    .editing_table_cell
+                    .filter(|_| tool_state.editing_table_id == Some(table.id));
+                drop(tool_state);
+                if let Some((row, column)) = editing_cell {
+                    rendering::draw_table_cell_highlight(ctx, table, row, column)?;
+                }
+
+                let is_selected = selected_ids.contains(&table.id);
+                if is_selected {
+                    let selection_color = testruct_core::typography::Color {
+                        r: 0.05,
+                        g: 0.49,
+                        b: 0.86,
+                        a: 1.0,
+                    };
+                    rendering::draw_selection_box(ctx, &table.bounds, &selection_color)?;
+                    if !element.is_locked() {
+                        rendering::draw_resize_handles(ctx, &table.bounds, &selection_color)?;
                     }
                 }
+            }
+            DocumentElement::QrCode(qr) => {
+                rendering::paint_with_opacity(ctx, opacity, || rendering::draw_qr_code(ctx, qr))?;
 
-                let is_selected = selected_ids.contains(&shape.id);
+                let is_selected = selected_ids.contains(&qr.id);
                 if is_selected {
                     let selection_color = testruct_core::typography::Color {
                         r: 0.05,
@@ -545,11 +1038,35 @@ impl CanvasView {
                         b: 0.86,
                         a: 1.0,
                     };
-                    rendering::draw_selection_box(ctx, &shape.bounds, &selection_color)?;
-                    rendering::draw_resize_handles(ctx, &shape.bounds, &selection_color)?;
+                    rendering::draw_selection_box(ctx, &qr.bounds, &selection_color)?;
+                    if !element.is_locked() {
+                        rendering::draw_resize_handles(ctx, &qr.bounds, &selection_color)?;
+                    }
                 }
             }
         }
+
+        // Lock badge on selected locked elements (they can be selected to unlock them)
+        if element.is_locked() && selected_ids.contains(&element.id()) {
+            let zoom = render_state.config.borrow().zoom;
+            rendering::draw_lock_badge(ctx, element.bounds(), zoom)?;
+        }
+
+        // Rotation handle for selected elements that can be rotated
+        if element.can_rotate() && !element.is_locked() && selected_ids.contains(&element.id()) {
+            let selection_color = testruct_core::typography::Color {
+                r: 0.05,
+                g: 0.49,
+                b: 0.86,
+                a: 1.0,
+            };
+            let distance = mouse::ROTATION_HANDLE_DISTANCE / render_state.config.borrow().zoom;
+            rendering::draw_rotation_handle(ctx, element.bounds(), distance, &selection_color)?;
+        }
+
+        if rotation != 0.0 {
+            ctx.restore()?;
+        }
         Ok(())
     }
 
@@ -5,7 +5,7 @@
 
 use std::cell::RefCell;
 use std::rc::Rc;
-use testruct_core::layout::{Point, Rect};
+use testruct_core::layout::{Point, Rect, Size};
 
 /// Mouse interaction state
 #[derive(Clone, Debug, PartialEq)]
@@ -51,6 +51,19 @@ pub enum ResizeHandle {
     Left,
 }
 
+impl ResizeHandle {
+    /// Whether the handle sits on a corner and resizes both dimensions
+    pub fn is_corner(self) -> bool {
+        matches!(
+            self,
+            ResizeHandle::TopLeft
+                | ResizeHandle::TopRight
+                | ResizeHandle::BottomRight
+                | ResizeHandle::BottomLeft
+        )
+    }
+}
+
 /// Guide orientation
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum GuideOrientation {
@@ -156,6 +169,65 @@ pub fn test_resize_handle(
     None
 }
 
+/// Distance of the rotation handle above the top edge, in screen pixels
+pub const ROTATION_HANDLE_DISTANCE: f64 = 24.0;
+
+/// Angle increment used when rotation snapping is active (Shift held)
+pub const ROTATION_SNAP_DEGREES: f32 = 15.0;
+
+/// Position of the rotation handle in the element's unrotated frame
+///
+/// `distance` is in document units (screen distance divided by zoom).
+pub fn rotation_handle_position(bounds: &Rect, distance: f64) -> CanvasMousePos {
+    CanvasMousePos::new(
+        bounds.origin.x as f64 + bounds.size.width as f64 / 2.0,
+        bounds.origin.y as f64 - distance,
+    )
+}
+
+/// Test if a point hits the rotation handle of an element rotated by `rotation` degrees
+pub fn test_rotation_handle(
+    point: CanvasMousePos,
+    bounds: &Rect,
+    rotation: f32,
+    handle_size: f64,
+    distance: f64,
+) -> bool {
+    // Undo the element rotation so the handle can be tested in the unrotated frame
+    let center_x = bounds.origin.x as f64 + bounds.size.width as f64 / 2.0;
+    let center_y = bounds.origin.y as f64 + bounds.size.height as f64 / 2.0;
+    let (sin, cos) = (-(rotation as f64).to_radians()).sin_cos();
+    let dx = point.x - center_x;
+    let dy = point.y - center_y;
+    let local_x = center_x + dx * cos - dy * sin;
+    let local_y = center_y + dx * sin + dy * cos;
+
+    let handle = rotation_handle_position(bounds, distance);
+    let half_size = handle_size / 2.0;
+    (local_x - handle.x).abs() <= half_size && (local_y - handle.y).abs() <= half_size
+}
+
+/// Rotation in degrees (clockwise from straight up) that points the handle at `point`
+///
+/// With `snap` the angle is rounded to `ROTATION_SNAP_DEGREES`. The result is
+/// normalized to -180..=180 to match the rotation spinner.
+pub fn rotation_angle_at(bounds: &Rect, point: CanvasMousePos, snap: bool) -> f32 {
+    let center_x = bounds.origin.x as f64 + bounds.size.width as f64 / 2.0;
+    let center_y = bounds.origin.y as f64 + bounds.size.height as f64 / 2.0;
+    let mut angle = (point.x - center_x).atan2(center_y - point.y).to_degrees() as f32;
+    if snap {
+        angle = (angle / ROTATION_SNAP_DEGREES).round() * ROTATION_SNAP_DEGREES;
+    }
+    if angle <= -180.0 {
+        angle += 360.0;
+    }
+    // Avoid displaying "-0°"
+    if angle == 0.0 {
+        angle = 0.0;
+    }
+    angle
+}
+
 /// Get the new bounds after a resize operation
 pub fn calculate_resize_bounds(
     original_bounds: &Rect,
@@ -211,6 +283,42 @@ pub fn calculate_resize_bounds(
     new_bounds
 }
 
+/// Keep a corner resize at the aspect ratio of `original_bounds`
+///
+/// The larger of the two scale factors wins, and the corner opposite the
+/// handle stays where it was. Edge handles only change one dimension, so
+/// their bounds are returned as they are.
+pub fn constrain_resize_to_aspect(
+    original_bounds: &Rect,
+    new_bounds: &Rect,
+    handle: ResizeHandle,
+) -> Rect {
+    let original = original_bounds.size;
+    if !handle.is_corner() || original.width <= 0.0 || original.height <= 0.0 {
+        return *new_bounds;
+    }
+
+    let scale = (new_bounds.size.width / original.width)
+        .max(new_bounds.size.height / original.height)
+        .max(10.0 / original.width)
+        .max(10.0 / original.height);
+    let width = original.width * scale;
+    let height = original.height * scale;
+
+    // Anchor on the corner opposite the handle
+    let left = original_bounds.origin.x;
+    let top = original_bounds.origin.y;
+    let right = left + original.width;
+    let bottom = top + original.height;
+    let (x, y) = match handle {
+        ResizeHandle::TopLeft => (right - width, bottom - height),
+        ResizeHandle::TopRight => (left, bottom - height),
+        ResizeHandle::BottomLeft => (right - width, top),
+        _ => (left, top),
+    };
+    Rect::new(Point::new(x, y), Size::new(width, height))
+}
+
 /// Mouse event handler state
 pub struct MouseEventHandler {
     pub interaction: Rc<RefCell<MouseInteraction>>,
@@ -303,6 +411,39 @@ mod tests {
     use super::*;
     use testruct_core::layout::Size;
 
+    #[test]
+    fn test_rotation_angle_at_and_snapping() {
+        let bounds = Rect::new(Point::new(0.0, 0.0), Size::new(100.0, 100.0));
+        let angle = |x, y, snap| rotation_angle_at(&bounds, CanvasMousePos::new(x, y), snap);
+
+        assert_eq!(angle(50.0, -20.0, false), 0.0);
+        assert!((angle(150.0, 50.0, false) - 90.0).abs() < 1e-4);
+        assert!((angle(-50.0, 50.0, false) + 90.0).abs() < 1e-4);
+        assert!((angle(50.0, 150.0, false) - 180.0).abs() < 1e-4);
+
+        // 20° free, snapped to the nearest 15° step
+        let (sin, cos) = 20f64.to_radians().sin_cos();
+        let (x, y) = (50.0 + 60.0 * sin, 50.0 - 60.0 * cos);
+        assert!((angle(x, y, false) - 20.0).abs() < 1e-3);
+        assert_eq!(angle(x, y, true), 15.0);
+    }
+
+    #[test]
+    fn test_rotation_handle_follows_rotation() {
+        let bounds = Rect::new(Point::new(0.0, 0.0), Size::new(100.0, 40.0));
+        let hit = |x, y, rotation| {
+            test_rotation_handle(CanvasMousePos::new(x, y), &bounds, rotation, 8.0, 24.0)
+        };
+
+        // Unrotated: handle 24 above the top center
+        assert!(hit(50.0, -24.0, 0.0));
+        assert!(!hit(50.0, 20.0, 0.0));
+
+        // Rotated 90° clockwise: handle sits right of the center
+        assert!(hit(50.0 + 20.0 + 24.0, 20.0, 90.0));
+        assert!(!hit(50.0, -24.0, 90.0));
+    }
+
     #[test]
     fn test_widget_to_canvas_conversion() {
         let pos = widget_to_canvas(
@@ -345,6 +486,46 @@ mod tests {
         assert_eq!(new_bounds.size.height, 125.0);
     }
 
+    #[test]
+    fn test_constrained_resize_keeps_ratio_and_anchor() {
+        let bounds = Rect::new(Point::new(10.0, 20.0), Size::new(100.0, 50.0));
+
+        // Dragging the top-left corner: the bottom-right corner stays put
+        let free = calculate_resize_bounds(&bounds, ResizeHandle::TopLeft, -100.0, -10.0);
+        let kept = constrain_resize_to_aspect(&bounds, &free, ResizeHandle::TopLeft);
+        assert_eq!(kept.size, Size::new(200.0, 100.0));
+        assert_eq!(kept.origin.x + kept.size.width, 110.0);
+        assert_eq!(kept.origin.y + kept.size.height, 70.0);
+
+        // Bottom-right keeps the origin
+        let free = calculate_resize_bounds(&bounds, ResizeHandle::BottomRight, 0.0, 50.0);
+        let kept = constrain_resize_to_aspect(&bounds, &free, ResizeHandle::BottomRight);
+        assert_eq!(
+            kept,
+            Rect::new(Point::new(10.0, 20.0), Size::new(200.0, 100.0))
+        );
+
+        // Edge handles are not constrained
+        let free = calculate_resize_bounds(&bounds, ResizeHandle::Right, 50.0, 0.0);
+        assert_eq!(
+            constrain_resize_to_aspect(&bounds, &free, ResizeHandle::Right),
+            free
+        );
+    }
+
+    #[test]
+    fn test_resize_handle_respects_custom_tolerance() {
+        let bounds = Rect::new(Point::new(0.0, 0.0), Size::new(100.0, 100.0));
+        let near_corner = CanvasMousePos::new(106.0, 106.0);
+
+        // 6px away from the bottom-right handle: outside an 8px box, inside a 16px box
+        assert_eq!(test_resize_handle(near_corner, &bounds, 8.0), None);
+        assert_eq!(
+            test_resize_handle(near_corner, &bounds, 16.0),
+            Some(ResizeHandle::BottomRight)
+        );
+    }
+
     #[test]
     fn test_mouse_event_handler_drag() {
         let handler = MouseEventHandler::new();
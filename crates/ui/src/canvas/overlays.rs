@@ -1,4 +1,12 @@
+//! Canvas overlays
+//!
+//! Widget overlays on top of the canvas and the small readouts drawn next
+//! to the cursor during drags (rotation angle, position and size).
+
+use super::grid_rendering::RulerUnit;
+use gtk4::cairo::Context;
 use gtk4::{prelude::*, Label, Overlay};
+use testruct_core::layout::{Point, Rect};
 
 pub fn add_ruler_overlay(overlay: &Overlay) {
     let label = Label::builder().label("Rulers TBD").build();
@@ -6,3 +14,80 @@ pub fn add_ruler_overlay(overlay: &Overlay) {
     label.set_margin_top(8);
     label.set_margin_start(8);
 }
+
+/// Position and size readout for `bounds` in the given ruler unit
+///
+/// Millimeters get one decimal place, pixels and points are whole numbers.
+pub fn measurement_text(bounds: &Rect, unit: RulerUnit) -> String {
+    let decimals = match unit {
+        RulerUnit::Millimeters => 1,
+        RulerUnit::Pixels | RulerUnit::Points => 0,
+    };
+    let value = |pixels: f32| unit.from_pixels(pixels as f64);
+    format!(
+        "X: {:.*}  Y: {:.*}  W: {:.*}  H: {:.*} {}",
+        decimals,
+        value(bounds.origin.x),
+        decimals,
+        value(bounds.origin.y),
+        decimals,
+        value(bounds.size.width),
+        decimals,
+        value(bounds.size.height),
+        unit.label()
+    )
+}
+
+/// Draw a dark label with `text` slightly below-right of `point`
+///
+/// `point` is in document coordinates; sizes are divided by `zoom` so the
+/// label keeps the same on-screen size at every zoom level.
+pub fn draw_cursor_readout(
+    ctx: &Context,
+    point: Point,
+    text: &str,
+    zoom: f64,
+) -> Result<(), gtk4::cairo::Error> {
+    let padding = 3.0 / zoom;
+    ctx.set_font_size(11.0 / zoom);
+    let extents = ctx.text_extents(text)?;
+    let x = point.x as f64 + 12.0 / zoom;
+    let y = point.y as f64 + 12.0 / zoom;
+
+    ctx.set_source_rgba(0.1, 0.1, 0.1, 0.8);
+    ctx.rectangle(
+        x - padding,
+        y - padding,
+        extents.width() + padding * 2.0,
+        extents.height() + padding * 2.0,
+    );
+    ctx.fill()?;
+
+    ctx.set_source_rgb(1.0, 1.0, 1.0);
+    ctx.move_to(x - extents.x_bearing(), y - extents.y_bearing());
+    ctx.show_text(text)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use testruct_core::layout::Size;
+
+    #[test]
+    fn test_measurement_text_uses_ruler_unit() {
+        let bounds = Rect::new(Point::new(96.0, 48.0), Size::new(192.0, 9.6));
+        assert_eq!(
+            measurement_text(&bounds, RulerUnit::Pixels),
+            "X: 96  Y: 48  W: 192  H: 10 px"
+        );
+        assert_eq!(
+            measurement_text(&bounds, RulerUnit::Millimeters),
+            "X: 25.4  Y: 12.7  W: 50.8  H: 2.5 mm"
+        );
+        assert_eq!(
+            measurement_text(&bounds, RulerUnit::Points),
+            "X: 72  Y: 36  W: 144  H: 7 pt"
+        );
+    }
+}
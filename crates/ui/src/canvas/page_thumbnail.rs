@@ -1,9 +1,13 @@
 //! Page thumbnail/preview generation
 //!
-//! Generate small preview images of pages for the pages panel
+//! Generate small preview images of pages for the pages panel.
+//! Rendered thumbnails are cached per page and only regenerated when the
+//! page's content revision changes.
 
 use cairo::{Context, Format, ImageSurface};
-use testruct_core::document::Page;
+use std::collections::HashMap;
+use std::rc::Rc;
+use testruct_core::document::{Page, PageBackground, PageId};
 
 /// Page thumbnail dimensions
 pub const THUMBNAIL_WIDTH: i32 = 180;
@@ -18,8 +22,13 @@ pub fn generate_page_thumbnail(page: &Page) -> Result<Vec<u8>, String> {
     let context =
         Context::new(&surface).map_err(|e| format!("Failed to create context: {:?}", e))?;
 
-    // Draw white background
-    context.set_source_rgb(1.0, 1.0, 1.0);
+    // Draw the page background color (white otherwise)
+    match &page.metadata.background {
+        PageBackground::Color(color) => {
+            context.set_source_rgb(color.r as f64, color.g as f64, color.b as f64)
+        }
+        _ => context.set_source_rgb(1.0, 1.0, 1.0),
+    }
     context
         .paint()
         .map_err(|e| format!("Failed to paint background: {:?}", e))?;
@@ -141,6 +150,40 @@ fn render_page_elements(context: &Context, page: &Page) -> Result<(), String> {
                 context.set_source_rgb(0.5, 0.5, 0.5);
                 context.rectangle(x, y, w, h);
             }
+            testruct_core::document::DocumentElement::Table(table) => {
+                let bounds = &table.bounds;
+                let x = 2.0 + bounds.origin.x as f64 * scale_x;
+                let y = 2.0 + bounds.origin.y as f64 * scale_y;
+                let w = bounds.size.width as f64 * scale_x;
+                let h = bounds.size.height as f64 * scale_y;
+
+                // Draw the outline and grid lines
+                context.rectangle(x, y, w, h);
+                for column in 1..table.columns {
+                    let line_x = x + w * column as f64 / table.columns as f64;
+                    context.move_to(line_x, y);
+                    context.line_to(line_x, y + h);
+                }
+                for row in 1..table.rows {
+                    let line_y = y + h * row as f64 / table.rows as f64;
+                    context.move_to(x, line_y);
+                    context.line_to(x + w, line_y);
+                }
+            }
+            testruct_core::document::DocumentElement::QrCode(qr) => {
+                let bounds = &qr.bounds;
+                let x = 2.0 + bounds.origin.x as f64 * scale_x;
+                let y = 2.0 + bounds.origin.y as f64 * scale_y;
+                let w = bounds.size.width as f64 * scale_x;
+                let h = bounds.size.height as f64 * scale_y;
+
+                // Draw the outline and the three finder corners
+                context.rectangle(x, y, w, h);
+                let corner = w.min(h) / 4.0;
+                context.rectangle(x, y, corner, corner);
+                context.rectangle(x + w - corner, y, corner, corner);
+                context.rectangle(x, y + h - corner, corner, corner);
+            }
         }
     }
 
@@ -163,3 +206,64 @@ pub fn has_page_changed(page: &Page, last_hash: Option<u64>) -> bool {
     let current_hash = page.elements.len() as u64;
     last_hash != Some(current_hash)
 }
+
+/// Cached thumbnail for one page
+struct CachedThumbnail {
+    revision: u64,
+    png_data: Rc<Vec<u8>>,
+}
+
+/// Thumbnail cache keyed by page id and content revision
+///
+/// A lookup renders the page only if it has no cached entry or the cached
+/// entry was rendered at a different revision.
+#[derive(Default)]
+pub struct ThumbnailCache {
+    entries: HashMap<PageId, CachedThumbnail>,
+    render_count: usize,
+}
+
+impl ThumbnailCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the PNG thumbnail for `page`, rendering it only when stale
+    pub fn get_or_render(&mut self, page: &Page, revision: u64) -> Result<Rc<Vec<u8>>, String> {
+        if let Some(cached) = self.entries.get(&page.id) {
+            if cached.revision == revision {
+                return Ok(cached.png_data.clone());
+            }
+        }
+
+        let png_data = Rc::new(generate_page_thumbnail(page)?);
+        self.render_count += 1;
+        self.entries.insert(
+            page.id,
+            CachedThumbnail {
+                revision,
+                png_data: png_data.clone(),
+            },
+        );
+        Ok(png_data)
+    }
+
+    /// Drop entries for pages that no longer exist
+    pub fn retain_pages(&mut self, pages: &[Page]) {
+        self.entries.retain(|id, _| pages.iter().any(|page| page.id == *id));
+    }
+
+    /// Number of thumbnails rendered since the cache was created
+    pub fn render_count(&self) -> usize {
+        self.render_count
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
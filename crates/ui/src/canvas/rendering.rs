@@ -7,23 +7,54 @@
 //! Text rendering has been moved to the `rendering_text` module.
 //! Selection and resize handles have been moved to the `rendering_selection` module.
 //! Image rendering has been moved to the `rendering_images` module.
+//! Table rendering lives in the `rendering_table` module.
+//! QR code rendering lives in the `rendering_qr` module.
 
 use gtk4::cairo::{self, Context};
-use testruct_core::layout::{Point, Size};
+use testruct_core::document::{PageBackground, ViewGuide, ViewState};
+use testruct_core::layout::{Point, Rect, Size};
+use testruct_core::workspace::ProjectSettings;
+
+use super::snapping::{SnapLine, SnapLineType};
 
 // Re-export types from grid_rendering for backward compatibility
-pub use super::grid_rendering::{Guide, GuideOrientation, RulerConfig, GridConfig, GridStyle};
+pub use super::grid_rendering::{
+    Guide, GuideOrientation, RulerConfig, RulerUnit, GridConfig, GridStyle, MAX_GRID_SPACING,
+    MIN_GRID_SPACING,
+};
 
 // Re-export from rendering_text module
 pub use super::rendering_text::{
-    draw_text_cursor, draw_text_editing_frame, draw_text_element, measure_text_height, TEXT_PADDING,
+    draw_spelling_underlines, draw_text_cursor, draw_text_editing_frame, draw_text_element,
+    draw_text_on_path, draw_text_selection, fit_text_height, fit_text_heights, measure_text_height,
+    text_cursor_index_at,
 };
 
 // Re-export from rendering_selection module
-pub use super::rendering_selection::{draw_resize_handles, draw_selection_box, ResizeHandle};
+pub use super::rendering_selection::{
+    draw_lock_badge, draw_resize_handles, draw_rotation_handle, draw_selection_box, ResizeHandle,
+};
+
+// Re-export from rendering_table module
+pub use super::rendering_table::{draw_table, draw_table_cell_highlight};
+
+// Re-export from rendering_qr module
+pub use super::rendering_qr::draw_qr_code;
 
 // Re-export from rendering_images module
-pub use super::rendering_images::{draw_image_element, draw_image_placeholder};
+pub use super::rendering_images::{
+    draw_background_image, draw_image_element, draw_image_placeholder,
+};
+
+/// Margin around the page when fitting it to the window (screen pixels)
+pub const FIT_MARGIN: f64 = 20.0;
+
+/// Arrow-key nudge distances in pixels when grid snapping is off
+const NUDGE_DISTANCE: f32 = 1.0;
+const LARGE_NUDGE_DISTANCE: f32 = 10.0;
+
+/// Grid cells moved by a Shift+arrow nudge when grid snapping is on
+const LARGE_NUDGE_CELLS: f32 = 5.0;
 
 /// Canvas rendering state
 #[derive(Clone, Debug)]
@@ -39,6 +70,10 @@ pub struct RenderConfig {
     pub guides: Vec<Guide>,
     pub snap_to_guides: bool,
     pub guide_snap_distance: f32,
+    /// Snap moved objects to the edges and centers of other objects
+    pub snap_to_objects: bool,
+    /// Resize-handle hit radius in screen pixels
+    pub handle_tolerance: f64,
 }
 
 impl Default for RenderConfig {
@@ -55,19 +90,252 @@ impl Default for RenderConfig {
             guides: Vec::new(),
             snap_to_guides: true,
             guide_snap_distance: 5.0,
+            snap_to_objects: true,
+            handle_tolerance: 8.0,
         }
     }
 }
 
 impl RenderConfig {
     /// Get grid spacing (convenience method)
+    ///
+    /// Returns the same clamped value the grid is drawn with, so snapping
+    /// always lands on visible grid points.
     pub fn grid_spacing(&self) -> f32 {
-        self.grid_config.spacing
+        self.grid_config.effective_spacing()
     }
 
-    /// Set grid spacing
+    /// Set grid spacing, clamped to `MIN_GRID_SPACING`..=`MAX_GRID_SPACING`
     pub fn set_grid_spacing(&mut self, spacing: f32) {
-        self.grid_config.spacing = spacing;
+        self.grid_config.spacing = if spacing.is_finite() {
+            spacing.clamp(MIN_GRID_SPACING, MAX_GRID_SPACING)
+        } else {
+            GridConfig::default().spacing
+        };
+    }
+
+    /// Resize-handle hit box size in document units
+    ///
+    /// The tolerance is defined in screen pixels, so it is divided by the
+    /// zoom to keep handles equally clickable at any zoom level.
+    pub fn handle_hit_size(&self) -> f64 {
+        let zoom = if self.zoom > 0.0 { self.zoom } else { 1.0 };
+        self.handle_tolerance * 2.0 / zoom
+    }
+
+    /// Snap a corner of a shape being drawn, in document coordinates
+    ///
+    /// Each axis snaps to a guide within the guide snap distance (in screen
+    /// pixels) when guide snapping is on; otherwise it falls back to the grid
+    /// when grid snapping is on.
+    pub fn snap_creation_point(&self, x: f32, y: f32) -> (f32, f32) {
+        let snap_axis = |value: f32, orientation: GuideOrientation| {
+            self.snap_axis(value, orientation)
+                .map_or(value, |(position, _)| position)
+        };
+        (
+            snap_axis(x, GuideOrientation::Vertical),
+            snap_axis(y, GuideOrientation::Horizontal),
+        )
+    }
+
+    /// Snap the edges a resize handle moves, in document coordinates
+    ///
+    /// Each moved edge snaps like [`Self::snap_creation_point`]: to a guide
+    /// when one is close enough, otherwise to the grid. Returns the snapped
+    /// bounds and a page-long indicator line for every edge that landed on a
+    /// guide or grid line. Edges that would collapse the box stay unsnapped.
+    pub fn snap_resize_bounds(
+        &self,
+        bounds: &Rect,
+        handle: ResizeHandle,
+        page_size: &Size,
+    ) -> (Rect, Vec<SnapLine>) {
+        let (moves_left, moves_right, moves_top, moves_bottom) = match handle {
+            ResizeHandle::TopLeft => (true, false, true, false),
+            ResizeHandle::Top => (false, false, true, false),
+            ResizeHandle::TopRight => (false, true, true, false),
+            ResizeHandle::Right => (false, true, false, false),
+            ResizeHandle::BottomRight => (false, true, false, true),
+            ResizeHandle::Bottom => (false, false, false, true),
+            ResizeHandle::BottomLeft => (true, false, false, true),
+            ResizeHandle::Left => (true, false, false, false),
+        };
+
+        let mut left = bounds.origin.x;
+        let mut top = bounds.origin.y;
+        let mut right = left + bounds.size.width;
+        let mut bottom = top + bounds.size.height;
+        let mut lines = Vec::new();
+        let mut snap_edge = |edge: &mut f32, opposite: f32, orientation: GuideOrientation| {
+            let Some((position, line_type)) = self.snap_axis(*edge, orientation) else {
+                return;
+            };
+            if (position - opposite).abs() < 1.0 {
+                return;
+            }
+            *edge = position;
+            let is_horizontal = orientation == GuideOrientation::Horizontal;
+            let extent = if is_horizontal {
+                page_size.width
+            } else {
+                page_size.height
+            };
+            lines.push(SnapLine {
+                line_type,
+                position,
+                is_horizontal,
+                bounds: (0.0, extent),
+            });
+        };
+        if moves_left {
+            snap_edge(&mut left, right, GuideOrientation::Vertical);
+        }
+        if moves_right {
+            snap_edge(&mut right, left, GuideOrientation::Vertical);
+        }
+        if moves_top {
+            snap_edge(&mut top, bottom, GuideOrientation::Horizontal);
+        }
+        if moves_bottom {
+            snap_edge(&mut bottom, top, GuideOrientation::Horizontal);
+        }
+
+        let snapped = Rect::new(
+            Point::new(left.min(right), top.min(bottom)),
+            Size::new((right - left).abs(), (bottom - top).abs()),
+        );
+        (snapped, lines)
+    }
+
+    /// Guide or grid position a coordinate snaps to, if snapping is on
+    ///
+    /// Guides within the guide snap distance (screen pixels) win over the grid.
+    fn snap_axis(&self, value: f32, orientation: GuideOrientation) -> Option<(f32, SnapLineType)> {
+        let zoom = if self.zoom > 0.0 { self.zoom } else { 1.0 };
+        let guide_distance = self.guide_snap_distance / zoom as f32;
+        let guide = if self.snap_to_guides {
+            snap_to_guide(value, &self.guides, orientation, guide_distance)
+        } else {
+            None
+        };
+        match guide {
+            Some(position) => Some((position, SnapLineType::Guide)),
+            None if self.snap_to_grid => {
+                Some((snap_to_grid(value, self.grid_spacing()), SnapLineType::Grid))
+            }
+            None => None,
+        }
+    }
+
+    /// Distance an arrow-key nudge moves the selection, in document units
+    ///
+    /// With grid snapping on a nudge moves one grid cell (`large`: five);
+    /// otherwise 1px (`large`: 10px).
+    pub fn nudge_distance(&self, large: bool) -> f32 {
+        match (self.snap_to_grid, large) {
+            (true, false) => self.grid_spacing(),
+            (true, true) => self.grid_spacing() * LARGE_NUDGE_CELLS,
+            (false, false) => NUDGE_DISTANCE,
+            (false, true) => LARGE_NUDGE_DISTANCE,
+        }
+    }
+
+    /// Apply snapping and hit-testing values from the project settings
+    pub fn apply_project_settings(&mut self, settings: &ProjectSettings) {
+        self.snap_to_grid = settings.snap_to_grid;
+        self.snap_to_guides = settings.snap_to_guides;
+        self.guide_snap_distance = settings.snap_distance;
+        self.handle_tolerance = settings.handle_tolerance as f64;
+    }
+
+    /// Zoom and center the page inside the visible canvas area
+    ///
+    /// `viewport_width`/`viewport_height` are the visible widget size in
+    /// pixels, including the rulers. `FIT_MARGIN` pixels are kept free on
+    /// every side of the page.
+    pub fn fit_page_to_viewport(
+        &mut self,
+        page_size: &Size,
+        viewport_width: f64,
+        viewport_height: f64,
+        ruler_size: f64,
+    ) {
+        let available_width = (viewport_width - ruler_size - FIT_MARGIN * 2.0).max(1.0);
+        let available_height = (viewport_height - ruler_size - FIT_MARGIN * 2.0).max(1.0);
+        let page_width = (page_size.width as f64).max(1.0);
+        let page_height = (page_size.height as f64).max(1.0);
+
+        let zoom = (available_width / page_width)
+            .min(available_height / page_height)
+            .clamp(0.1, 4.0);
+
+        self.zoom = zoom;
+        self.pan_x = FIT_MARGIN + (available_width - page_width * zoom) / 2.0;
+        self.pan_y = FIT_MARGIN + (available_height - page_height * zoom) / 2.0;
+    }
+
+    /// Pan so `bounds` (document coordinates) is centered in the viewport
+    ///
+    /// The zoom is kept. The viewport is measured as for
+    /// [`fit_page_to_viewport`](Self::fit_page_to_viewport).
+    pub fn center_on(
+        &mut self,
+        bounds: &Rect,
+        viewport_width: f64,
+        viewport_height: f64,
+        ruler_size: f64,
+    ) {
+        let center_x = bounds.origin.x as f64 + bounds.size.width as f64 / 2.0;
+        let center_y = bounds.origin.y as f64 + bounds.size.height as f64 / 2.0;
+        self.pan_x = (viewport_width - ruler_size) / 2.0 - center_x * self.zoom;
+        self.pan_y = (viewport_height - ruler_size) / 2.0 - center_y * self.zoom;
+    }
+
+    /// Snapshot of the view settings that are persisted with the document
+    pub fn view_state(&self) -> ViewState {
+        ViewState {
+            zoom: self.zoom,
+            pan_x: self.pan_x,
+            pan_y: self.pan_y,
+            show_grid: self.show_grid,
+            show_rulers: self.show_rulers,
+            show_guides: self.show_guides,
+            snap_to_grid: self.snap_to_grid,
+            snap_to_guides: self.snap_to_guides,
+            guides: self
+                .guides
+                .iter()
+                .map(|guide| ViewGuide {
+                    is_vertical: guide.orientation == GuideOrientation::Vertical,
+                    position: guide.position,
+                })
+                .collect(),
+        }
+    }
+
+    /// Restore view settings saved with a document
+    pub fn apply_view_state(&mut self, view: &ViewState) {
+        self.zoom = view.zoom.clamp(0.1, 4.0);
+        self.pan_x = view.pan_x;
+        self.pan_y = view.pan_y;
+        self.show_grid = view.show_grid;
+        self.show_rulers = view.show_rulers;
+        self.show_guides = view.show_guides;
+        self.snap_to_grid = view.snap_to_grid;
+        self.snap_to_guides = view.snap_to_guides;
+        self.guides = view
+            .guides
+            .iter()
+            .map(|guide| {
+                let orientation = if guide.is_vertical {
+                    GuideOrientation::Vertical
+                } else {
+                    GuideOrientation::Horizontal
+                };
+                Guide::new(orientation, guide.position)
+            })
+            .collect();
     }
 }
 
@@ -93,6 +361,34 @@ pub fn draw_background(
 // Re-export draw_grid for backward compatibility
 pub use super::grid_rendering::{draw_grid, draw_grid_with_config};
 
+/// Fill the page with its background color or image
+pub fn draw_page_background(
+    ctx: &Context,
+    page_size: &Size,
+    background: &PageBackground,
+    app_state: &crate::app::AppState,
+    image_cache: &mut super::rendering_images::ImageSurfaceCache,
+) -> Result<(), cairo::Error> {
+    match background {
+        PageBackground::None => {}
+        PageBackground::Color(color) => {
+            ctx.set_source_rgba(
+                color.r as f64,
+                color.g as f64,
+                color.b as f64,
+                color.a as f64,
+            );
+            ctx.rectangle(0.0, 0.0, page_size.width as f64, page_size.height as f64);
+            ctx.fill()?;
+        }
+        PageBackground::Image { source, fit } => {
+            let bounds = Rect::new(Point::new(0.0, 0.0), *page_size);
+            draw_background_image(ctx, &bounds, *source, *fit, app_state, image_cache);
+        }
+    }
+    Ok(())
+}
+
 /// Draw page border
 pub fn draw_page_border(ctx: &Context, page_size: &Size) -> Result<(), cairo::Error> {
     ctx.set_source_rgb(0.8, 0.8, 0.8);
@@ -102,6 +398,36 @@ pub fn draw_page_border(ctx: &Context, page_size: &Size) -> Result<(), cairo::Er
     Ok(())
 }
 
+/// Rotate the context clockwise by `degrees` about the center of `bounds`
+///
+/// Callers save the context before and restore it after drawing the element.
+pub fn rotate_about_center(ctx: &Context, bounds: &Rect, degrees: f32) {
+    let center_x = bounds.origin.x as f64 + bounds.size.width as f64 / 2.0;
+    let center_y = bounds.origin.y as f64 + bounds.size.height as f64 / 2.0;
+    ctx.translate(center_x, center_y);
+    ctx.rotate((degrees as f64).to_radians());
+    ctx.translate(-center_x, -center_y);
+}
+
+/// Run `draw` in a Cairo group and paint it with `opacity`
+///
+/// Fully opaque elements are drawn directly without an intermediate group.
+/// The group is always popped, even when `draw` fails.
+pub fn paint_with_opacity<E: From<cairo::Error>>(
+    ctx: &Context,
+    opacity: f32,
+    draw: impl FnOnce() -> Result<(), E>,
+) -> Result<(), E> {
+    if opacity >= 1.0 {
+        return draw();
+    }
+    ctx.push_group();
+    let result = draw();
+    ctx.pop_group_to_source()?;
+    ctx.paint_with_alpha(opacity.max(0.0) as f64)?;
+    result
+}
+
 // Re-export shape drawing functions for backward compatibility
 pub use super::shapes_rendering::{
     draw_arrow, draw_ellipse, draw_line, draw_polygon, draw_rectangle,
@@ -188,4 +514,166 @@ mod tests {
         assert!(config.show_grid);
         assert!(config.show_rulers);
     }
+
+    #[test]
+    fn test_handle_hit_size_scales_with_zoom() {
+        let mut config = RenderConfig::default();
+        config.handle_tolerance = 10.0;
+        assert_eq!(config.handle_hit_size(), 20.0);
+
+        config.zoom = 2.0;
+        assert_eq!(config.handle_hit_size(), 10.0);
+    }
+
+    #[test]
+    fn test_set_grid_spacing_clamps() {
+        let mut config = RenderConfig::default();
+        config.set_grid_spacing(8.0);
+        assert_eq!(config.grid_spacing(), 8.0);
+
+        config.set_grid_spacing(0.5);
+        assert_eq!(config.grid_spacing(), MIN_GRID_SPACING);
+
+        config.set_grid_spacing(1000.0);
+        assert_eq!(config.grid_spacing(), MAX_GRID_SPACING);
+    }
+
+    #[test]
+    fn test_snap_creation_point_prefers_guides_over_grid() {
+        let mut config = RenderConfig::default();
+        config.snap_to_grid = true;
+        config.snap_to_guides = true;
+        config.set_grid_spacing(10.0);
+        config.guides = vec![
+            Guide::new(GuideOrientation::Vertical, 103.0),
+            Guide::new(GuideOrientation::Horizontal, 48.0),
+        ];
+
+        // Near both guides: guides win over the closer grid lines
+        assert_eq!(config.snap_creation_point(101.0, 51.0), (103.0, 48.0));
+        // Out of guide range: grid snapping
+        assert_eq!(config.snap_creation_point(131.0, 74.0), (130.0, 70.0));
+
+        // Guide range is in screen pixels
+        config.zoom = 2.0;
+        assert_eq!(config.snap_creation_point(106.0, 49.0), (110.0, 48.0));
+
+        config.snap_to_guides = false;
+        config.snap_to_grid = false;
+        assert_eq!(config.snap_creation_point(101.0, 51.0), (101.0, 51.0));
+    }
+
+    #[test]
+    fn test_nudge_distance_follows_grid_snapping() {
+        let mut config = RenderConfig::default();
+        config.set_grid_spacing(8.0);
+
+        config.snap_to_grid = true;
+        assert_eq!(config.nudge_distance(false), 8.0);
+        assert_eq!(config.nudge_distance(true), 40.0);
+
+        config.snap_to_grid = false;
+        assert_eq!(config.nudge_distance(false), 1.0);
+        assert_eq!(config.nudge_distance(true), 10.0);
+    }
+
+    #[test]
+    fn test_snap_resize_bounds_snaps_moved_edges() {
+        let mut config = RenderConfig::default();
+        config.set_grid_spacing(10.0);
+        config.guides = vec![Guide::new(GuideOrientation::Vertical, 103.0)];
+        let page = Size::new(500.0, 800.0);
+        let bounds = Rect::new(Point::new(12.0, 21.0), Size::new(89.0, 47.0));
+
+        // Bottom-right: the right edge lands on the guide, the bottom on the grid
+        let (snapped, lines) = config.snap_resize_bounds(&bounds, ResizeHandle::BottomRight, &page);
+        assert_eq!(snapped.origin, bounds.origin);
+        assert_eq!(snapped.size, Size::new(91.0, 49.0));
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].line_type, SnapLineType::Guide);
+        assert!(!lines[0].is_horizontal);
+        assert_eq!(lines[0].bounds, (0.0, 800.0));
+        assert_eq!(lines[1].line_type, SnapLineType::Grid);
+        assert_eq!(lines[1].position, 70.0);
+
+        // With snapping off nothing moves and no lines are shown
+        config.snap_to_grid = false;
+        config.snap_to_guides = false;
+        let (snapped, lines) = config.snap_resize_bounds(&bounds, ResizeHandle::Left, &page);
+        assert_eq!(snapped, bounds);
+        assert!(lines.is_empty());
+    }
+
+    #[test]
+    fn test_apply_project_settings() {
+        let mut settings = ProjectSettings::default();
+        settings.snap_to_grid = false;
+        settings.snap_distance = 12.0;
+        settings.handle_tolerance = 4.0;
+
+        let mut config = RenderConfig::default();
+        config.apply_project_settings(&settings);
+        assert!(!config.snap_to_grid);
+        assert_eq!(config.guide_snap_distance, 12.0);
+        assert_eq!(config.handle_tolerance, 4.0);
+    }
+
+    #[test]
+    fn test_fit_page_to_viewport_centers_page() {
+        let mut config = RenderConfig::default();
+        let page = Size::new(100.0, 200.0);
+        // 20px rulers + 20px margins leave 400x400 for the page
+        config.fit_page_to_viewport(&page, 460.0, 460.0, 20.0);
+
+        assert_eq!(config.zoom, 2.0);
+        // Height-bound: the page fills the vertical space, centered horizontally
+        assert_eq!(config.pan_y, FIT_MARGIN);
+        assert_eq!(config.pan_x, FIT_MARGIN + (400.0 - 200.0) / 2.0);
+    }
+
+    #[test]
+    fn test_center_on_keeps_zoom() {
+        let mut config = RenderConfig {
+            zoom: 2.0,
+            ..Default::default()
+        };
+        let bounds = Rect::new(Point::new(100.0, 300.0), Size::new(50.0, 20.0));
+        config.center_on(&bounds, 420.0, 320.0, 20.0);
+
+        assert_eq!(config.zoom, 2.0);
+        // The bounds center (125, 310) lands mid-way across the area past the rulers
+        assert_eq!(20.0 + config.pan_x + 125.0 * 2.0, 20.0 + 200.0);
+        assert_eq!(20.0 + config.pan_y + 310.0 * 2.0, 20.0 + 150.0);
+    }
+
+    #[test]
+    fn test_fit_page_to_viewport_clamps_zoom() {
+        let mut config = RenderConfig::default();
+        config.fit_page_to_viewport(&Size::new(10.0, 10.0), 4000.0, 4000.0, 20.0);
+        assert_eq!(config.zoom, 4.0);
+
+        config.fit_page_to_viewport(&Size::new(100000.0, 100000.0), 100.0, 100.0, 20.0);
+        assert_eq!(config.zoom, 0.1);
+    }
+
+    #[test]
+    fn test_view_state_round_trip() {
+        let mut config = RenderConfig::default();
+        config.zoom = 2.5;
+        config.pan_x = 40.0;
+        config.show_grid = false;
+        config.guides = vec![
+            Guide::new(GuideOrientation::Vertical, 100.0),
+            Guide::new(GuideOrientation::Horizontal, 250.0),
+        ];
+
+        let mut restored = RenderConfig::default();
+        restored.apply_view_state(&config.view_state());
+        assert_eq!(restored.zoom, 2.5);
+        assert_eq!(restored.pan_x, 40.0);
+        assert!(!restored.show_grid);
+        assert_eq!(restored.guides.len(), 2);
+        assert_eq!(restored.guides[0].orientation, GuideOrientation::Vertical);
+        assert_eq!(restored.guides[1].position, 250.0);
+    }
 }
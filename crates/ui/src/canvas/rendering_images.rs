@@ -1,11 +1,173 @@
 //! Image rendering functions for canvas
 //!
 //! This module handles rendering of image elements, including placeholders
-//! and actual image loading and display.
+//! and actual image loading and display. Decoded images are kept in an
+//! [`ImageSurfaceCache`] so drawing a frame does not touch the disk.
 
 use gtk4::cairo::{self, Context};
 use gtk4::pango;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use testruct_core::document::ImageFit;
 use testruct_core::layout::Rect;
+use testruct_core::workspace::assets::AssetRef;
+
+/// Default memory budget for decoded images (256 MiB)
+pub const DEFAULT_IMAGE_CACHE_BUDGET: usize = 256 * 1024 * 1024;
+
+/// How often the canvas checks cached images for changed files
+pub const IMAGE_RECHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A decoded image and what it was decoded from
+struct CachedImage {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    surface: cairo::ImageSurface,
+    bytes: usize,
+    last_used: u64,
+}
+
+/// Decoded Cairo surfaces keyed by asset
+///
+/// An entry is reloaded when its asset points at a different file. Drawing
+/// does not stat the files; [`ImageSurfaceCache::drop_changed`] compares the
+/// modification times on a timer instead. Once the decoded size exceeds the
+/// budget, the least recently used entries are evicted.
+pub struct ImageSurfaceCache {
+    entries: HashMap<AssetRef, CachedImage>,
+    budget_bytes: usize,
+    used_bytes: usize,
+    clock: u64,
+}
+
+impl Default for ImageSurfaceCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_IMAGE_CACHE_BUDGET)
+    }
+}
+
+impl ImageSurfaceCache {
+    /// Create an empty cache holding at most `budget_bytes` of pixel data
+    pub fn new(budget_bytes: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            budget_bytes,
+            used_bytes: 0,
+            clock: 0,
+        }
+    }
+
+    /// Decoded surface for `asset`, loading `path` on a miss or when the
+    /// asset now points at another file
+    pub fn surface(
+        &mut self,
+        asset: AssetRef,
+        path: &Path,
+    ) -> Result<cairo::ImageSurface, Box<dyn std::error::Error>> {
+        self.clock += 1;
+        if let Some(entry) = self.entries.get_mut(&asset) {
+            if entry.path == path {
+                entry.last_used = self.clock;
+                return Ok(entry.surface.clone());
+            }
+        }
+
+        let modified = file_modified(path);
+        let surface = decode_image_surface(path)?;
+        self.insert(asset, path.to_path_buf(), modified, surface.clone());
+        Ok(surface)
+    }
+
+    /// Drop the entries whose file changed since it was decoded
+    ///
+    /// Returns whether any entry was dropped, i.e. whether images need to be
+    /// drawn again.
+    pub fn drop_changed(&mut self) -> bool {
+        let changed: Vec<AssetRef> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| file_modified(&entry.path) != entry.modified)
+            .map(|(asset, _)| *asset)
+            .collect();
+        for asset in &changed {
+            self.invalidate(*asset);
+        }
+        !changed.is_empty()
+    }
+
+    /// Drop the cached surface for `asset`
+    pub fn invalidate(&mut self, asset: AssetRef) {
+        if let Some(entry) = self.entries.remove(&asset) {
+            self.used_bytes -= entry.bytes;
+        }
+    }
+
+    /// Drop every cached surface
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.used_bytes = 0;
+    }
+
+    /// Number of cached images
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no image is cached
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Pixel data currently held, in bytes
+    pub fn used_bytes(&self) -> usize {
+        self.used_bytes
+    }
+
+    fn insert(
+        &mut self,
+        asset: AssetRef,
+        path: PathBuf,
+        modified: Option<SystemTime>,
+        surface: cairo::ImageSurface,
+    ) {
+        self.invalidate(asset);
+        let bytes = (surface.stride() * surface.height()).max(0) as usize;
+        self.entries.insert(
+            asset,
+            CachedImage {
+                path,
+                modified,
+                surface,
+                bytes,
+                last_used: self.clock,
+            },
+        );
+        self.used_bytes += bytes;
+        self.evict(asset);
+    }
+
+    /// Evict least recently used entries until within budget, keeping `keep`
+    fn evict(&mut self, keep: AssetRef) {
+        while self.used_bytes > self.budget_bytes {
+            let oldest = self
+                .entries
+                .iter()
+                .filter(|(asset, _)| **asset != keep)
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(asset, _)| *asset);
+            let Some(oldest) = oldest else {
+                break;
+            };
+            self.invalidate(oldest);
+        }
+    }
+}
+
+/// Modification time of the file at `path`, if it can be read
+fn file_modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
 
 /// Draw a placeholder for image elements
 pub fn draw_image_placeholder(ctx: &Context, bounds: &Rect) -> Result<(), cairo::Error> {
@@ -93,16 +255,18 @@ pub fn draw_image_element(
     ctx: &Context,
     bounds: &Rect,
     asset_ref: &testruct_core::workspace::assets::AssetRef,
+    fit: ImageFit,
     app_state: &crate::app::AppState,
+    image_cache: &mut ImageSurfaceCache,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Try to get the asset catalog and load the image
     let catalog = app_state.asset_catalog();
     let cat = catalog.lock().expect("asset catalog");
 
     if let Some(metadata) = cat.get(*asset_ref) {
-        // Try to load and render the actual image
-        if load_and_render_image(ctx, bounds, &metadata.path).is_ok() {
-            return Ok(());
+        // Try to render the actual image
+        if let Ok(surface) = image_cache.surface(*asset_ref, &metadata.path) {
+            return render_image_surface(ctx, bounds, &surface, fit);
         }
     }
 
@@ -110,12 +274,33 @@ pub fn draw_image_element(
     draw_image_placeholder(ctx, bounds).map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
 }
 
-/// Load and render an image file to Cairo context
-fn load_and_render_image(
+/// Draw a page background image over `bounds`
+///
+/// A missing or unreadable file leaves the page blank rather than drawing
+/// a placeholder across the whole page.
+pub fn draw_background_image(
     ctx: &Context,
     bounds: &Rect,
-    path: &std::path::Path,
-) -> Result<(), Box<dyn std::error::Error>> {
+    asset_ref: testruct_core::workspace::assets::AssetRef,
+    fit: ImageFit,
+    app_state: &crate::app::AppState,
+    image_cache: &mut ImageSurfaceCache,
+) {
+    let catalog = app_state.asset_catalog();
+    let cat = catalog.lock().expect("asset catalog");
+    let Some(metadata) = cat.get(asset_ref) else {
+        return;
+    };
+    let result = image_cache
+        .surface(asset_ref, &metadata.path)
+        .and_then(|surface| render_image_surface(ctx, bounds, &surface, fit));
+    if let Err(e) = result {
+        tracing::warn!("⚠️ Failed to draw page background: {}", e);
+    }
+}
+
+/// Decode an image file into a Cairo surface
+fn decode_image_surface(path: &Path) -> Result<cairo::ImageSurface, Box<dyn std::error::Error>> {
     // Load image file
     let image = image::open(path)?;
 
@@ -145,8 +330,20 @@ fn load_and_render_image(
         stride,
     )
     .map_err(|e| format!("Failed to create Cairo surface: {:?}", e))?;
+    Ok(surface)
+}
 
-    // Draw the image to fit bounds
+/// Render a decoded image surface into `bounds` according to `fit`
+fn render_image_surface(
+    ctx: &Context,
+    bounds: &Rect,
+    surface: &cairo::ImageSurface,
+    fit: ImageFit,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let img_width = surface.width();
+    let img_height = surface.height();
+
+    // Draw the image into bounds according to the fit mode
     ctx.save()
         .map_err(|e| format!("Failed to save context: {:?}", e))?;
 
@@ -161,21 +358,23 @@ fn load_and_render_image(
     ctx.fill()
         .map_err(|e| format!("Failed to fill background: {:?}", e))?;
 
-    // Calculate scale to fit image in bounds while maintaining aspect ratio
-    let scale_x = bounds.size.width as f64 / img_width as f64;
-    let scale_y = bounds.size.height as f64 / img_height as f64;
-    let scale = scale_x.min(scale_y);
-
-    // Calculate position to center image
-    let scaled_width = img_width as f64 * scale;
-    let scaled_height = img_height as f64 * scale;
-    let offset_x = bounds.origin.x as f64 + (bounds.size.width as f64 - scaled_width) / 2.0;
-    let offset_y = bounds.origin.y as f64 + (bounds.size.height as f64 - scaled_height) / 2.0;
+    // Cover overflows the bounds, so clip to them
+    ctx.rectangle(
+        bounds.origin.x as f64,
+        bounds.origin.y as f64,
+        bounds.size.width as f64,
+        bounds.size.height as f64,
+    );
+    ctx.clip();
 
     // Draw image
-    ctx.translate(offset_x, offset_y);
-    ctx.scale(scale, scale);
-    ctx.set_source_surface(&surface, 0.0, 0.0)
+    let target = fit.image_rect(img_width as f32, img_height as f32, bounds);
+    ctx.translate(target.origin.x as f64, target.origin.y as f64);
+    ctx.scale(
+        target.size.width as f64 / img_width as f64,
+        target.size.height as f64 / img_height as f64,
+    );
+    ctx.set_source_surface(surface, 0.0, 0.0)
         .map_err(|e| format!("Failed to set image source: {:?}", e))?;
     ctx.paint()
         .map_err(|e| format!("Failed to paint image: {:?}", e))?;
@@ -184,3 +383,69 @@ fn load_and_render_image(
         .map_err(|e| format!("Failed to restore context: {:?}", e))?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn surface(width: i32, height: i32) -> cairo::ImageSurface {
+        cairo::ImageSurface::create(cairo::Format::Rgb24, width, height).expect("surface")
+    }
+
+    #[test]
+    fn test_image_cache_evicts_least_recently_used() {
+        // Room for two 10×10 RGB24 surfaces (400 bytes each)
+        let mut cache = ImageSurfaceCache::new(800);
+        let (first, second, third) = (AssetRef::new(), AssetRef::new(), AssetRef::new());
+
+        cache.insert(first, PathBuf::from("first.png"), None, surface(10, 10));
+        cache.clock += 1;
+        cache.insert(second, PathBuf::from("second.png"), None, surface(10, 10));
+        cache.clock += 1;
+        // Touch the first entry so the second becomes the oldest
+        cache.entries.get_mut(&first).expect("cached").last_used = cache.clock;
+        cache.clock += 1;
+        cache.insert(third, PathBuf::from("third.png"), None, surface(10, 10));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.entries.contains_key(&first));
+        assert!(!cache.entries.contains_key(&second));
+        assert!(cache.entries.contains_key(&third));
+        assert_eq!(cache.used_bytes(), 800);
+    }
+
+    #[test]
+    fn test_image_cache_keeps_oversized_newest_entry() {
+        let mut cache = ImageSurfaceCache::new(100);
+        let asset = AssetRef::new();
+        cache.insert(asset, PathBuf::from("large.png"), None, surface(10, 10));
+
+        assert_eq!(cache.len(), 1);
+        cache.invalidate(asset);
+        assert!(cache.is_empty());
+        assert_eq!(cache.used_bytes(), 0);
+    }
+
+    #[test]
+    fn test_image_cache_drops_entries_whose_file_changed() {
+        let path = std::env::temp_dir().join(format!("testruct-image-{}.png", std::process::id()));
+        std::fs::write(&path, b"not decoded").unwrap();
+
+        let mut cache = ImageSurfaceCache::default();
+        let (current, stale) = (AssetRef::new(), AssetRef::new());
+        cache.insert(current, path.clone(), file_modified(&path), surface(10, 10));
+        cache.insert(
+            stale,
+            path.clone(),
+            Some(SystemTime::UNIX_EPOCH),
+            surface(10, 10),
+        );
+
+        assert!(cache.drop_changed());
+        assert!(cache.entries.contains_key(&current));
+        assert!(!cache.entries.contains_key(&stale));
+        assert!(!cache.drop_changed());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
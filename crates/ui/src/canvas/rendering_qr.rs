@@ -0,0 +1,78 @@
+//! QR code rendering functions for canvas
+//!
+//! Modules are filled as vector rectangles, so QR codes stay sharp at any
+//! zoom and export to PDF and SVG without rasterizing. The exporters call
+//! the same routine as the canvas.
+
+use gtk4::cairo::{self, Context};
+use testruct_core::document::{QrCodeElement, QR_QUIET_ZONE};
+use testruct_core::layout::Rect;
+
+/// Draw a QR code as a square symbol centered in its bounds
+///
+/// The symbol includes the light quiet zone. Payloads that cannot be encoded
+/// are drawn as a crossed-out placeholder instead.
+pub fn draw_qr_code(ctx: &Context, qr: &QrCodeElement) -> Result<(), cairo::Error> {
+    let Ok(matrix) = qr.encode() else {
+        return draw_qr_placeholder(ctx, &qr.bounds);
+    };
+
+    let side = qr.bounds.size.width.min(qr.bounds.size.height).max(0.0) as f64;
+    let x = qr.bounds.origin.x as f64 + (qr.bounds.size.width as f64 - side) / 2.0;
+    let y = qr.bounds.origin.y as f64 + (qr.bounds.size.height as f64 - side) / 2.0;
+    let module = side / (matrix.size() + QR_QUIET_ZONE * 2) as f64;
+    let symbol_x = x + module * QR_QUIET_ZONE as f64;
+    let symbol_y = y + module * QR_QUIET_ZONE as f64;
+
+    ctx.save()?;
+    ctx.set_source_rgb(1.0, 1.0, 1.0);
+    ctx.rectangle(x, y, side, side);
+    ctx.fill()?;
+
+    // One rectangle per horizontal run of dark modules keeps the path small
+    // and avoids hairline seams between neighbouring modules
+    ctx.set_source_rgb(0.0, 0.0, 0.0);
+    for row in 0..matrix.size() {
+        let mut column = 0;
+        while column < matrix.size() {
+            if !matrix.is_dark(column, row) {
+                column += 1;
+                continue;
+            }
+            let start = column;
+            while column < matrix.size() && matrix.is_dark(column, row) {
+                column += 1;
+            }
+            ctx.rectangle(
+                symbol_x + start as f64 * module,
+                symbol_y + row as f64 * module,
+                (column - start) as f64 * module,
+                module,
+            );
+        }
+    }
+    ctx.fill()?;
+    ctx.restore()
+}
+
+/// Crossed-out box shown when the payload does not fit in a QR code
+fn draw_qr_placeholder(ctx: &Context, bounds: &Rect) -> Result<(), cairo::Error> {
+    let x = bounds.origin.x as f64;
+    let y = bounds.origin.y as f64;
+    let width = bounds.size.width as f64;
+    let height = bounds.size.height as f64;
+
+    ctx.save()?;
+    ctx.set_source_rgb(0.98, 0.93, 0.93);
+    ctx.rectangle(x, y, width, height);
+    ctx.fill_preserve()?;
+    ctx.set_source_rgb(0.8, 0.2, 0.2);
+    ctx.set_line_width(1.5);
+    ctx.stroke()?;
+    ctx.move_to(x, y);
+    ctx.line_to(x + width, y + height);
+    ctx.move_to(x + width, y);
+    ctx.line_to(x, y + height);
+    ctx.stroke()?;
+    ctx.restore()
+}
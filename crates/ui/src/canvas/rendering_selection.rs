@@ -169,3 +169,75 @@ mod tests {
         assert_eq!(center.y, 20.0);
     }
 }
+
+/// Draw the rotation handle: a short stem above the top edge ending in a circle
+///
+/// `distance` is the stem length in document units.
+pub fn draw_rotation_handle(
+    ctx: &Context,
+    bounds: &Rect,
+    distance: f64,
+    handle_color: &Color,
+) -> Result<(), cairo::Error> {
+    let center_x = bounds.origin.x as f64 + bounds.size.width as f64 / 2.0;
+    let top = bounds.origin.y as f64;
+    let radius = ResizeHandle::SIZE / 2.0;
+
+    ctx.set_source_rgb(
+        handle_color.r as f64,
+        handle_color.g as f64,
+        handle_color.b as f64,
+    );
+    ctx.set_line_width(1.0);
+    ctx.move_to(center_x, top);
+    ctx.line_to(center_x, top - distance + radius);
+    ctx.stroke()?;
+
+    ctx.arc(center_x, top - distance, radius, 0.0, std::f64::consts::TAU);
+    ctx.fill_preserve()?;
+
+    // White border
+    ctx.set_source_rgb(1.0, 1.0, 1.0);
+    ctx.stroke()?;
+
+    Ok(())
+}
+
+/// Draw a padlock badge just outside the top-right corner of a selection box
+///
+/// Marks selected elements that are locked and cannot be moved or resized.
+/// `zoom` keeps the badge the same on-screen size at every zoom level.
+pub fn draw_lock_badge(ctx: &Context, bounds: &Rect, zoom: f64) -> Result<(), cairo::Error> {
+    let size = 14.0 / zoom;
+    let x = bounds.origin.x as f64 + bounds.size.width as f64 + 4.0 / zoom;
+    let y = bounds.origin.y as f64 - size - 4.0 / zoom;
+
+    // Round dark backdrop
+    ctx.set_source_rgba(0.15, 0.15, 0.15, 0.85);
+    ctx.arc(
+        x + size / 2.0,
+        y + size / 2.0,
+        size / 2.0 + 2.0 / zoom,
+        0.0,
+        std::f64::consts::TAU,
+    );
+    ctx.fill()?;
+
+    // Shackle
+    ctx.set_source_rgb(1.0, 1.0, 1.0);
+    ctx.set_line_width(1.5 / zoom);
+    ctx.arc(
+        x + size / 2.0,
+        y + size * 0.45,
+        size * 0.22,
+        std::f64::consts::PI,
+        0.0,
+    );
+    ctx.stroke()?;
+
+    // Body
+    ctx.rectangle(x + size * 0.2, y + size * 0.45, size * 0.6, size * 0.4);
+    ctx.fill()?;
+
+    Ok(())
+}
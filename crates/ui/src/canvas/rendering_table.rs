@@ -0,0 +1,96 @@
+//! Table rendering functions for canvas
+//!
+//! Draws the cell text and grid lines of table elements. The PDF, SVG and
+//! image exporters call the same routine, so exported tables match the canvas.
+
+use super::rendering_text::draw_text_element;
+use super::shapes_rendering::{
+    apply_line_style, clear_line_style, set_stroke_source, stroke_line_width,
+};
+use gtk4::cairo::{self, Context};
+use testruct_core::document::{TableElement, DEFAULT_TEXT_PADDING};
+use testruct_core::layout::Insets;
+
+/// Inset of the text in each cell
+const CELL_PADDING: Insets = Insets::uniform(DEFAULT_TEXT_PADDING);
+
+/// Draw a table: cell text first, then the grid on top of it
+pub fn draw_table(ctx: &Context, table: &TableElement) -> Result<(), cairo::Error> {
+    for row in 0..table.rows {
+        for column in 0..table.columns {
+            let Some(content) = table.cell(row, column) else {
+                continue;
+            };
+            let text = content.get_plain_text();
+            if text.is_empty() {
+                continue;
+            }
+            draw_text_element(
+                ctx,
+                &table.cell_bounds(row, column),
+                &text,
+                &table.cell_style,
+                Some(content),
+                &CELL_PADDING,
+            )?;
+        }
+    }
+    draw_table_grid(ctx, table)
+}
+
+/// Stroke the outline and the lines between rows and columns
+fn draw_table_grid(ctx: &Context, table: &TableElement) -> Result<(), cairo::Error> {
+    let border = &table.border;
+    let Some(line_width) = stroke_line_width(border.width) else {
+        return Ok(());
+    };
+
+    let x = table.bounds.origin.x as f64;
+    let y = table.bounds.origin.y as f64;
+    let width = table.bounds.size.width as f64;
+    let height = table.bounds.size.height as f64;
+
+    ctx.save()?;
+    set_stroke_source(ctx, &border.color);
+    ctx.set_line_width(line_width);
+    apply_line_style(ctx, border.line_style, border.width);
+
+    ctx.rectangle(x, y, width, height);
+    for column in 1..table.columns {
+        let line_x = x + width * column as f64 / table.columns as f64;
+        ctx.move_to(line_x, y);
+        ctx.line_to(line_x, y + height);
+    }
+    for row in 1..table.rows {
+        let line_y = y + height * row as f64 / table.rows as f64;
+        ctx.move_to(x, line_y);
+        ctx.line_to(x + width, line_y);
+    }
+    ctx.stroke()?;
+
+    clear_line_style(ctx);
+    ctx.restore()
+}
+
+/// Highlight the cell being edited
+pub fn draw_table_cell_highlight(
+    ctx: &Context,
+    table: &TableElement,
+    row: usize,
+    column: usize,
+) -> Result<(), cairo::Error> {
+    let cell = table.cell_bounds(row, column);
+    ctx.save()?;
+    ctx.set_source_rgba(0.05, 0.49, 0.86, 0.12);
+    ctx.rectangle(
+        cell.origin.x as f64,
+        cell.origin.y as f64,
+        cell.size.width as f64,
+        cell.size.height as f64,
+    );
+    ctx.fill_preserve()?;
+    ctx.set_source_rgb(0.05, 0.49, 0.86);
+    ctx.set_line_width(2.0);
+    ctx.stroke()?;
+    ctx.restore()
+}
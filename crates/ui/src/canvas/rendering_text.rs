@@ -5,17 +5,115 @@
 
 use gtk4::cairo::{self, Context};
 use gtk4::pango;
-use testruct_core::layout::Rect;
+use testruct_core::document::TextPath;
+use testruct_core::layout::{Insets, Rect};
+use testruct_core::typography::{FontWeight, RichText};
 
-/// Text element rendering constants
-pub const TEXT_PADDING: f64 = 5.0;
+/// Map a text alignment onto a Pango layout
+///
+/// Justified text is left aligned with Pango justification enabled. Pango
+/// leaves the last line of each paragraph unstretched, so single-line text
+/// and paragraph endings keep their natural spacing.
+pub fn apply_text_alignment(
+    layout: &pango::Layout,
+    alignment: testruct_core::typography::TextAlignment,
+) {
+    let (pango_alignment, justify) = pango_alignment(alignment);
+    layout.set_alignment(pango_alignment);
+    layout.set_justify(justify);
+}
+
+/// Apply a style's line height multiplier and letter spacing to a layout
+///
+/// Letter spacing is appended to the layout's attributes, so call this after
+/// `set_attributes`. A line height of 1.0 keeps Pango's natural spacing.
+pub fn apply_text_spacing(layout: &pango::Layout, style: &testruct_core::typography::TextStyle) {
+    layout.set_line_spacing(style.line_height.max(0.0));
+    if style.letter_spacing != 0.0 {
+        let attrs = layout.attributes().unwrap_or_default();
+        let spacing = (style.letter_spacing * pango::SCALE as f32) as i32;
+        attrs.insert(pango::AttrInt::new_letter_spacing(spacing));
+        layout.set_attributes(Some(&attrs));
+    }
+}
+
+/// Pango alignment and justify flag for a text alignment
+pub fn pango_alignment(
+    alignment: testruct_core::typography::TextAlignment,
+) -> (pango::Alignment, bool) {
+    use testruct_core::typography::TextAlignment;
+    match alignment {
+        TextAlignment::Start => (pango::Alignment::Left, false),
+        TextAlignment::Center => (pango::Alignment::Center, false),
+        TextAlignment::End => (pango::Alignment::Right, false),
+        TextAlignment::Justified => (pango::Alignment::Left, true),
+    }
+}
+
+/// Build per-run Pango attributes (weight, italic, color, decorations) for rich text
+///
+/// Offsets come from [`RichText::run_byte_ranges`], so they are byte offsets
+/// and stay valid for multi-byte Japanese text.
+pub fn rich_text_attributes(rich_text: &RichText) -> pango::AttrList {
+    let attrs = pango::AttrList::new();
+    for (range, style) in rich_text.run_byte_ranges() {
+        let insert = |attr: pango::Attribute| {
+            let mut attr = attr;
+            attr.set_start_index(range.start as u32);
+            attr.set_end_index(range.end as u32);
+            attrs.insert(attr);
+        };
+
+        insert(pango::AttrInt::new_weight(pango_weight(style.weight)).into());
+        let font_style = if style.italic {
+            pango::Style::Italic
+        } else {
+            pango::Style::Normal
+        };
+        insert(pango::AttrInt::new_style(font_style).into());
+        let channel = |value: f32| (value.clamp(0.0, 1.0) * u16::MAX as f32).round() as u16;
+        insert(
+            pango::AttrColor::new_foreground(
+                channel(style.color.r),
+                channel(style.color.g),
+                channel(style.color.b),
+            )
+            .into(),
+        );
+        let underline = if style.underline {
+            pango::Underline::Single
+        } else {
+            pango::Underline::None
+        };
+        insert(pango::AttrInt::new_underline(underline).into());
+        insert(pango::AttrInt::new_strikethrough(style.strikethrough).into());
+    }
+    attrs
+}
+
+/// Pango weight for a font weight
+pub fn pango_weight(weight: FontWeight) -> pango::Weight {
+    match weight {
+        FontWeight::Thin => pango::Weight::Thin,
+        FontWeight::Light => pango::Weight::Light,
+        FontWeight::Regular => pango::Weight::Normal,
+        FontWeight::Medium => pango::Weight::Medium,
+        FontWeight::Bold => pango::Weight::Bold,
+        FontWeight::Black => pango::Weight::Ultrabold,
+    }
+}
 
 /// Draw a text element with line wrapping support
+///
+/// The text is laid out and clipped inside `bounds` minus `padding`; the
+/// background color still fills the whole bounds.
 pub fn draw_text_element(
     ctx: &Context,
     bounds: &Rect,
     text: &str,
     style: &testruct_core::typography::TextStyle,
+    rich_text: Option<&RichText>,
+    padding: &Insets,
 ) -> Result<(), cairo::Error> {
     ctx.save()?;
 
@@ -32,11 +130,12 @@ pub fn draw_text_element(
     }
 
     // Clipping rectangle
+    let content = bounds.inset(padding);
     ctx.rectangle(
-        bounds.origin.x as f64,
-        bounds.origin.y as f64,
-        bounds.size.width as f64,
-        bounds.size.height as f64,
+        content.origin.x as f64,
+        content.origin.y as f64,
+        content.size.width as f64,
+        content.size.height as f64,
     );
     ctx.clip();
 
@@ -68,13 +167,7 @@ pub fn draw_text_element(
     layout.set_font_description(Some(&font_desc));
 
     // Apply text alignment
-    let pango_alignment = match style.alignment {
-        testruct_core::typography::TextAlignment::Start => pango::Alignment::Left,
-        testruct_core::typography::TextAlignment::Center => pango::Alignment::Center,
-        testruct_core::typography::TextAlignment::End => pango::Alignment::Right,
-        testruct_core::typography::TextAlignment::Justified => pango::Alignment::Center, // Fallback for justified
-    };
-    layout.set_alignment(pango_alignment);
+    apply_text_alignment(&layout, style.alignment);
 
     // Configure for vertical or horizontal text
     if style.vertical {
@@ -85,12 +178,10 @@ pub fn draw_text_element(
         layout.context_changed();
 
         // For vertical text, use height as the constraint
-        let available_height = (bounds.size.height as f64 - (TEXT_PADDING * 2.0)).max(0.0);
-        layout.set_width((available_height * pango::SCALE as f64) as i32);
+        layout.set_width((content.size.height as f64 * pango::SCALE as f64) as i32);
     } else {
         // Horizontal text mode (横書き)
-        let available_width = (bounds.size.width as f64 - (TEXT_PADDING * 2.0)).max(0.0);
-        layout.set_width((available_width * pango::SCALE as f64) as i32);
+        layout.set_width((content.size.width as f64 * pango::SCALE as f64) as i32);
     }
 
     // Apply text decorations
@@ -103,7 +194,10 @@ pub fn draw_text_element(
         let strikethrough_attr = pango::AttrInt::new_strikethrough(true);
         attrs.insert(strikethrough_attr);
     }
+    // Rich text runs replace the element-wide decorations
+    let attrs = rich_text.map(rich_text_attributes).unwrap_or(attrs);
     layout.set_attributes(Some(&attrs));
+    apply_text_spacing(&layout, style);
 
     // Set text color
     ctx.set_source_rgb(
@@ -112,20 +206,12 @@ pub fn draw_text_element(
         style.color.b as f64,
     );
 
-    // Position and render based on text direction
+    // Position and render based on text direction and vertical alignment;
+    // vertical text is rotated 90 degrees clockwise about the layout origin
+    let (origin_x, origin_y) = text_layout_origin(bounds, style, padding, &layout);
+    ctx.translate(origin_x, origin_y);
     if style.vertical {
-        // Vertical text: translate to top-right corner, rotate 90 degrees clockwise
-        ctx.translate(
-            bounds.origin.x as f64 + bounds.size.width as f64 - TEXT_PADDING,
-            bounds.origin.y as f64 + TEXT_PADDING,
-        );
         ctx.rotate(std::f64::consts::FRAC_PI_2);
-    } else {
-        // Horizontal text: normal positioning
-        ctx.translate(
-            bounds.origin.x as f64 + TEXT_PADDING,
-            bounds.origin.y as f64 + TEXT_PADDING,
-        );
     }
 
     // Render layout
@@ -135,6 +221,97 @@ pub fn draw_text_element(
     Ok(())
 }
 
+/// Draw a text element along `path`, one character at a time
+///
+/// The whole text is laid out once on a single line so Pango supplies each
+/// character's advance (with kerning and letter spacing). Every character is
+/// then drawn as its own layout, centred on its distance along the path,
+/// rotated to the path direction and with its baseline on the path. Line
+/// breaks become spaces, and padding and vertical alignment do not apply.
+/// Rich text runs are not honoured: every character is drawn in `style`.
+pub fn draw_text_on_path(
+    ctx: &Context,
+    bounds: &Rect,
+    text: &str,
+    style: &testruct_core::typography::TextStyle,
+    path: &TextPath,
+) -> Result<(), cairo::Error> {
+    ctx.save()?;
+
+    if let Some(bg_color) = style.background_color {
+        ctx.set_source_rgb(bg_color.r as f64, bg_color.g as f64, bg_color.b as f64);
+        ctx.rectangle(
+            bounds.origin.x as f64,
+            bounds.origin.y as f64,
+            bounds.size.width as f64,
+            bounds.size.height as f64,
+        );
+        ctx.fill()?;
+    }
+
+    let mut font_desc = pango::FontDescription::new();
+    font_desc.set_family(&style.font_family);
+    font_desc.set_size((style.font_size * pango::SCALE as f32) as i32);
+    font_desc.set_weight(pango_weight(style.weight));
+    if style.italic {
+        font_desc.set_style(pango::Style::Italic);
+    }
+
+    let attrs = pango::AttrList::new();
+    if style.underline {
+        attrs.insert(pango::AttrInt::new_underline(pango::Underline::Single));
+    }
+    if style.strikethrough {
+        attrs.insert(pango::AttrInt::new_strikethrough(true));
+    }
+
+    let line = text.replace('\n', " ");
+    let layout = pangocairo::functions::create_layout(ctx);
+    layout.set_font_description(Some(&font_desc));
+    layout.set_text(&line);
+    layout.set_attributes(Some(&attrs));
+    apply_text_spacing(&layout, style);
+
+    let scale = pango::SCALE as f64;
+    let baseline = layout.baseline() as f64 / scale;
+    let (_, logical_rect) = layout.pixel_extents();
+    let flattened = path.flatten(bounds);
+    let start = path.start_distance(
+        flattened.length(),
+        logical_rect.width() as f32,
+        style.alignment,
+    );
+
+    ctx.set_source_rgb(
+        style.color.r as f64,
+        style.color.g as f64,
+        style.color.b as f64,
+    );
+
+    let glyph_layout = pangocairo::functions::create_layout(ctx);
+    glyph_layout.set_font_description(Some(&font_desc));
+    glyph_layout.set_attributes(Some(&attrs));
+    for (byte_index, character) in line.char_indices() {
+        let extents = layout.index_to_pos(byte_index as i32);
+        let x = extents.x() as f64 / scale;
+        let width = extents.width().abs() as f64 / scale;
+        let Some((point, angle)) = flattened.point_at(start + (x + width / 2.0) as f32) else {
+            continue;
+        };
+
+        glyph_layout.set_text(character.encode_utf8(&mut [0; 4]));
+        ctx.save()?;
+        ctx.translate(point.x as f64, point.y as f64);
+        ctx.rotate(angle as f64);
+        ctx.move_to(-width / 2.0, -baseline);
+        pangocairo::functions::show_layout(ctx, &glyph_layout);
+        ctx.restore()?;
+    }
+
+    ctx.restore()?;
+    Ok(())
+}
+
 /// Measure the height of a text block for a given width and style.
 ///
 /// This mirrors the rendering configuration (padding, font settings, alignment)
@@ -145,6 +322,7 @@ pub fn measure_text_height(
     text: &str,
     style: &testruct_core::typography::TextStyle,
     width: f32,
+    padding: &Insets,
 ) -> f32 {
     // Use an off-screen surface to create a Pango layout consistent with canvas rendering.
     let surface = cairo::ImageSurface::create(cairo::Format::ARgb32, 1, 1)
@@ -175,13 +353,7 @@ pub fn measure_text_height(
 
     layout.set_font_description(Some(&font_desc));
 
-    let alignment = match style.alignment {
-        testruct_core::typography::TextAlignment::Start => pango::Alignment::Left,
-        testruct_core::typography::TextAlignment::Center => pango::Alignment::Center,
-        testruct_core::typography::TextAlignment::End => pango::Alignment::Right,
-        testruct_core::typography::TextAlignment::Justified => pango::Alignment::Center,
-    };
-    layout.set_alignment(alignment);
+    apply_text_alignment(&layout, style.alignment);
 
     let attrs = pango::AttrList::new();
     if style.underline {
@@ -193,6 +365,7 @@ pub fn measure_text_height(
         attrs.insert(strike_attr);
     }
     layout.set_attributes(Some(&attrs));
+    apply_text_spacing(&layout, style);
 
     // Handle vertical vs horizontal text
     if style.vertical {
@@ -203,7 +376,7 @@ pub fn measure_text_height(
         layout.context_changed();
 
         // For vertical text, width parameter represents the available height
-        let available_height = (width as f64 - (TEXT_PADDING * 2.0)).max(0.0);
+        let available_height = (width as f64 - padding.vertical() as f64).max(0.0);
         layout.set_width((available_height * pango::SCALE as f64) as i32);
         layout.set_wrap(pango::WrapMode::WordChar);
 
@@ -211,13 +384,13 @@ pub fn measure_text_height(
         let (_, logical_rect) = layout.pixel_extents();
         let layout_width = logical_rect.width().max(0) as f64;
 
-        let padded_width = layout_width + (TEXT_PADDING * 2.0);
-        let min_width = (style.font_size as f64) + (TEXT_PADDING * 2.0);
+        let padded_width = layout_width + padding.horizontal() as f64;
+        let min_width = (style.font_size as f64) + padding.horizontal() as f64;
 
         padded_width.max(min_width) as f32
     } else {
         // Horizontal text mode
-        let available_width = (width as f64 - (TEXT_PADDING * 2.0)).max(0.0);
+        let available_width = (width as f64 - padding.horizontal() as f64).max(0.0);
         layout.set_width((available_width * pango::SCALE as f64) as i32);
         layout.set_wrap(pango::WrapMode::WordChar);
 
@@ -225,112 +398,343 @@ pub fn measure_text_height(
         let (_, logical_rect) = layout.pixel_extents();
         let layout_height = logical_rect.height().max(0) as f64;
 
-        let padded_height = layout_height + (TEXT_PADDING * 2.0);
-        let min_height = (style.font_size as f64) + (TEXT_PADDING * 2.0);
+        let padded_height = layout_height + padding.vertical() as f64;
+        let min_height = (style.font_size as f64) + padding.vertical() as f64;
 
         padded_height.max(min_height) as f32
     }
 }
 
-/// Draw a text cursor for editing mode
-pub fn draw_text_cursor(
+/// Refit an auto-resize text box to its content at the current width
+///
+/// Does nothing unless `auto_resize_height` is set. The measured extent
+/// includes the element's padding; vertical text grows in width instead of
+/// height. Returns true when the bounds changed.
+pub fn fit_text_height(text: &mut testruct_core::document::TextElement) -> bool {
+    if !text.auto_resize_height {
+        return false;
+    }
+
+    let size = &mut text.bounds.size;
+    let (available, fitted) = if text.style.vertical {
+        (size.height, &mut size.width)
+    } else {
+        (size.width, &mut size.height)
+    };
+    let measured =
+        measure_text_height(&text.content, &text.style, available.max(1.0), &text.padding)
+            .max(1.0);
+    if (*fitted - measured).abs() < 0.01 {
+        return false;
+    }
+    *fitted = measured;
+    true
+}
+
+/// Refit every auto-resize text box in `elements`, including nested ones
+pub fn fit_text_heights(elements: &mut [testruct_core::document::DocumentElement]) {
+    use testruct_core::document::DocumentElement;
+
+    for element in elements {
+        match element {
+            DocumentElement::Text(text) => {
+                fit_text_height(text);
+            }
+            DocumentElement::Frame(frame) => fit_text_heights(&mut frame.children),
+            DocumentElement::Group(group) => fit_text_heights(&mut group.children),
+            DocumentElement::Shape(_)
+            | DocumentElement::Image(_)
+            | DocumentElement::Table(_)
+            | DocumentElement::QrCode(_) => {}
+        }
+    }
+}
+
+/// Byte offset of the `char_pos`-th character, clamped to the end of `text`
+///
+/// The editor tracks the cursor in characters while Pango indexes by byte,
+/// so multi-byte (e.g. Japanese) text needs this conversion.
+pub fn char_to_byte_index(text: &str, char_pos: usize) -> usize {
+    text.char_indices()
+        .nth(char_pos)
+        .map(|(byte_index, _)| byte_index)
+        .unwrap_or(text.len())
+}
+
+/// Character position of a byte offset, clamped to the end of `text`
+fn byte_to_char_index(text: &str, byte_index: usize) -> usize {
+    text.char_indices()
+        .take_while(|(index, _)| *index < byte_index)
+        .count()
+}
+
+/// Build a layout configured like `draw_text_element`, in layout coordinates
+///
+/// Pair with [`text_layout_origin`] to map between layout and document space.
+fn editing_layout(
     ctx: &Context,
     bounds: &Rect,
     text: &str,
-    cursor_pos: usize,
     style: &testruct_core::typography::TextStyle,
-) -> Result<(), cairo::Error> {
-    ctx.save()?;
-
-    let x_offset = bounds.origin.x as f64 + TEXT_PADDING;
-    let y_offset = bounds.origin.y as f64 + TEXT_PADDING;
-
-    // Create Pango layout for the FULL text to handle line wrapping correctly
+    padding: &Insets,
+) -> pango::Layout {
     let layout = pangocairo::functions::create_layout(ctx);
     layout.set_text(text);
 
-    // Set font with styling
     let mut font_desc = pango::FontDescription::new();
     font_desc.set_family(&style.font_family);
     font_desc.set_size((style.font_size * pango::SCALE as f32) as i32);
-
-    // Apply font weight
-    let pango_weight = match style.weight {
-        testruct_core::typography::FontWeight::Thin => pango::Weight::Thin,
-        testruct_core::typography::FontWeight::Light => pango::Weight::Light,
-        testruct_core::typography::FontWeight::Regular => pango::Weight::Normal,
-        testruct_core::typography::FontWeight::Medium => pango::Weight::Medium,
-        testruct_core::typography::FontWeight::Bold => pango::Weight::Bold,
-        testruct_core::typography::FontWeight::Black => pango::Weight::Ultrabold,
-    };
-    font_desc.set_weight(pango_weight);
-
-    // Apply italic style
+    font_desc.set_weight(pango_weight(style.weight));
     if style.italic {
         font_desc.set_style(pango::Style::Italic);
     }
-
     layout.set_font_description(Some(&font_desc));
+    apply_text_alignment(&layout, style.alignment);
+    apply_text_spacing(&layout, style);
 
-    // Set width constraint to match text rendering
-    let available_width = (bounds.size.width as f64 - (TEXT_PADDING * 2.0)).max(0.0);
-    layout.set_width((available_width * pango::SCALE as f64) as i32);
-
-    // Get actual line spacing from Pango layout
-    let (_, logical_rect) = layout.extents();
-    let line_height = (logical_rect.height() as f64 / pango::SCALE as f64)
-        / (text.matches('\n').count() as f64 + 1.0).max(1.0);
-
-    // Split text into lines and find which line the cursor is on
-    let lines: Vec<&str> = text.split('\n').collect();
-    let mut char_count = 0;
-    let mut current_line = 0;
-    let mut pos_in_line = 0;
-
-    for (line_idx, line) in lines.iter().enumerate() {
-        let line_len = line.chars().count();
-        if char_count + line_len >= cursor_pos {
-            // Cursor is on this line
-            current_line = line_idx;
-            pos_in_line = cursor_pos - char_count;
-            break;
-        }
-        char_count += line_len + 1; // +1 for the newline character
+    if style.vertical {
+        let pango_context = layout.context();
+        pango_context.set_base_gravity(pango::Gravity::East);
+        pango_context.set_gravity_hint(pango::GravityHint::Strong);
+        layout.context_changed();
+
+        let available_height = bounds.inset(padding).size.height as f64;
+        layout.set_width((available_height * pango::SCALE as f64) as i32);
+    } else {
+        let available_width = bounds.inset(padding).size.width as f64;
+        layout.set_width((available_width * pango::SCALE as f64) as i32);
     }
+    layout
+}
 
-    // Get the current line text
-    let current_line_text = if current_line < lines.len() {
-        lines[current_line]
+/// Document position of the origin of `layout`, a fully configured layout
+///
+/// The layout starts at the content corner (inside `padding`) and is moved
+/// along the block direction by the style's vertical alignment, using the
+/// layout's logical pixel height. Vertical text is additionally rotated 90°
+/// clockwise about this point, so its offset moves it to the left.
+pub fn text_layout_origin(
+    bounds: &Rect,
+    style: &testruct_core::typography::TextStyle,
+    padding: &Insets,
+    layout: &pango::Layout,
+) -> (f64, f64) {
+    let content = bounds.inset(padding);
+    let (_, logical_rect) = layout.pixel_extents();
+    let extent = logical_rect.height().max(0) as f32;
+    let top = content.origin.y as f64;
+    if style.vertical {
+        let offset = style.vertical_alignment.offset(content.size.width, extent);
+        ((content.origin.x + content.size.width - offset) as f64, top)
     } else {
-        ""
-    };
+        let offset = style.vertical_alignment.offset(content.size.height, extent);
+        (content.origin.x as f64, top + offset as f64)
+    }
+}
 
-    // Measure text up to cursor position on the current line
-    let text_before_cursor_in_line = current_line_text
-        .chars()
-        .take(pos_in_line)
-        .collect::<String>();
+/// Draw a text cursor for editing mode
+///
+/// The layout is configured like `draw_text_element`, so Pango reports the
+/// cursor position on the correct wrapped line with the line's own height.
+pub fn draw_text_cursor(
+    ctx: &Context,
+    bounds: &Rect,
+    text: &str,
+    cursor_pos: usize,
+    style: &testruct_core::typography::TextStyle,
+    padding: &Insets,
+) -> Result<(), cairo::Error> {
+    ctx.save()?;
 
-    let layout_line = pangocairo::functions::create_layout(ctx);
-    layout_line.set_text(&text_before_cursor_in_line);
-    layout_line.set_font_description(Some(&font_desc));
-    layout_line.set_width((available_width * pango::SCALE as f64) as i32);
+    let layout = editing_layout(ctx, bounds, text, style, padding);
+    let (origin_x, origin_y) = text_layout_origin(bounds, style, padding, &layout);
+    ctx.translate(origin_x, origin_y);
+    if style.vertical {
+        ctx.rotate(std::f64::consts::FRAC_PI_2);
+    }
 
-    let (ink_rect, _logical_rect) = layout_line.pixel_extents();
-    let line_x = x_offset + ink_rect.width() as f64;
-    let line_y = y_offset + (current_line as f64 * line_height);
+    // Cursor rectangle in layout coordinates (Pango units)
+    let byte_index = char_to_byte_index(text, cursor_pos);
+    let pos = layout.index_to_pos(byte_index as i32);
+    let scale = pango::SCALE as f64;
+    let cursor_x = pos.x() as f64 / scale;
+    let cursor_y = pos.y() as f64 / scale;
+    let cursor_height = if pos.height() > 0 {
+        pos.height() as f64 / scale
+    } else {
+        style.font_size as f64
+    };
 
     // Draw text cursor as a thin vertical line
     ctx.set_source_rgb(0.0, 0.5, 1.0); // Blue cursor
     ctx.set_line_width(2.0);
-    ctx.move_to(line_x, line_y);
-    ctx.line_to(line_x, line_y + line_height);
+    ctx.move_to(cursor_x, cursor_y);
+    ctx.line_to(cursor_x, cursor_y + cursor_height);
+    ctx.stroke()?;
+
+    ctx.restore()?;
+    Ok(())
+}
+
+/// Highlight the selected character range of the text being edited
+///
+/// Draws a translucent rectangle over each line the range touches, so the
+/// highlight follows wrapping and multi-line text.
+pub fn draw_text_selection(
+    ctx: &Context,
+    bounds: &Rect,
+    text: &str,
+    selection: std::ops::Range<usize>,
+    style: &testruct_core::typography::TextStyle,
+    padding: &Insets,
+) -> Result<(), cairo::Error> {
+    if selection.is_empty() {
+        return Ok(());
+    }
+    ctx.save()?;
+
+    let layout = editing_layout(ctx, bounds, text, style, padding);
+    let (origin_x, origin_y) = text_layout_origin(bounds, style, padding, &layout);
+    ctx.translate(origin_x, origin_y);
+    if style.vertical {
+        ctx.rotate(std::f64::consts::FRAC_PI_2);
+    }
+
+    let start = char_to_byte_index(text, selection.start) as i32;
+    let end = char_to_byte_index(text, selection.end) as i32;
+    let scale = pango::SCALE as f64;
+
+    ctx.set_source_rgba(0.2, 0.6, 1.0, 0.35);
+    let mut iter = layout.iter();
+    loop {
+        if let Some(line) = iter.line_readonly() {
+            let line_start = line.start_index();
+            let line_end = line_start + line.length();
+            if start < line_end && end > line_start {
+                let (y0, y1) = iter.line_yrange();
+                let ranges = line.x_ranges(start.max(line_start), end.min(line_end));
+                for pair in ranges.chunks_exact(2) {
+                    ctx.rectangle(
+                        pair[0] as f64 / scale,
+                        y0 as f64 / scale,
+                        (pair[1] - pair[0]) as f64 / scale,
+                        (y1 - y0) as f64 / scale,
+                    );
+                }
+            }
+        }
+        if !iter.next_line() {
+            break;
+        }
+    }
+    ctx.fill()?;
+
+    ctx.restore()?;
+    Ok(())
+}
+
+/// Wavelength and height of the misspelling squiggle (document units)
+const SQUIGGLE_PERIOD: f64 = 4.0;
+const SQUIGGLE_HEIGHT: f64 = 1.5;
+
+/// Underline misspelled words of the text being edited with a red squiggle
+///
+/// `ranges` are byte ranges into `text`, as returned by the spell checker.
+/// A word wrapped across lines gets a squiggle on each line.
+pub fn draw_spelling_underlines(
+    ctx: &Context,
+    bounds: &Rect,
+    text: &str,
+    ranges: &[std::ops::Range<usize>],
+    style: &testruct_core::typography::TextStyle,
+    padding: &Insets,
+) -> Result<(), cairo::Error> {
+    if ranges.is_empty() {
+        return Ok(());
+    }
+    ctx.save()?;
+
+    let layout = editing_layout(ctx, bounds, text, style, padding);
+    let (origin_x, origin_y) = text_layout_origin(bounds, style, padding, &layout);
+    ctx.translate(origin_x, origin_y);
+    if style.vertical {
+        ctx.rotate(std::f64::consts::FRAC_PI_2);
+    }
+
+    let scale = pango::SCALE as f64;
+    ctx.set_source_rgb(0.9, 0.1, 0.1);
+    ctx.set_line_width(1.0);
+    let mut iter = layout.iter();
+    loop {
+        if let Some(line) = iter.line_readonly() {
+            let line_start = line.start_index();
+            let line_end = line_start + line.length();
+            let baseline = iter.baseline() as f64 / scale + SQUIGGLE_HEIGHT;
+            for range in ranges {
+                let (start, end) = (range.start as i32, range.end as i32);
+                if start >= line_end || end <= line_start {
+                    continue;
+                }
+                let x_ranges = line.x_ranges(start.max(line_start), end.min(line_end));
+                for pair in x_ranges.chunks_exact(2) {
+                    let (x0, x1) = (pair[0] as f64 / scale, pair[1] as f64 / scale);
+                    ctx.move_to(x0, baseline);
+                    let mut x = x0;
+                    let mut up = true;
+                    while x < x1 {
+                        x = (x + SQUIGGLE_PERIOD / 2.0).min(x1);
+                        let y = if up {
+                            baseline + SQUIGGLE_HEIGHT
+                        } else {
+                            baseline
+                        };
+                        ctx.line_to(x, y);
+                        up = !up;
+                    }
+                }
+            }
+        }
+        if !iter.next_line() {
+            break;
+        }
+    }
     ctx.stroke()?;
 
     ctx.restore()?;
     Ok(())
 }
 
+/// Character position of the cursor nearest to a document point
+///
+/// Inverse of the mapping in [`draw_text_cursor`]; clicks past the end of a
+/// line land at the end of that line, on wrapped and multi-line text alike.
+pub fn text_cursor_index_at(
+    bounds: &Rect,
+    text: &str,
+    style: &testruct_core::typography::TextStyle,
+    padding: &Insets,
+    doc_x: f64,
+    doc_y: f64,
+) -> usize {
+    // Use an off-screen surface to create a Pango layout consistent with canvas rendering.
+    let surface = cairo::ImageSurface::create(cairo::Format::ARgb32, 1, 1)
+        .expect("Failed to create surface for text hit testing");
+    let ctx =
+        Context::new(&surface).expect("Failed to create Cairo context for text hit testing");
+    let layout = editing_layout(&ctx, bounds, text, style, padding);
+
+    let (origin_x, origin_y) = text_layout_origin(bounds, style, padding, &layout);
+    let (dx, dy) = (doc_x - origin_x, doc_y - origin_y);
+    // Undo the 90° clockwise rotation used for vertical text
+    let (layout_x, layout_y) = if style.vertical { (dy, -dx) } else { (dx, dy) };
+
+    let scale = pango::SCALE as f64;
+    let (_inside, byte_index, trailing) =
+        layout.xy_to_index((layout_x * scale) as i32, (layout_y * scale) as i32);
+    let char_index = byte_to_char_index(text, byte_index.max(0) as usize) + trailing.max(0) as usize;
+    char_index.min(text.chars().count())
+}
+
 /// Draw a frame to indicate text editing mode
 pub fn draw_text_editing_frame(ctx: &Context, bounds: &Rect) -> Result<(), cairo::Error> {
     ctx.save()?;
@@ -350,3 +754,43 @@ pub fn draw_text_editing_frame(ctx: &Context, bounds: &Rect) -> Result<(), cairo
     ctx.restore()?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use testruct_core::typography::TextAlignment;
+
+    #[test]
+    fn test_justified_maps_to_left_with_justify() {
+        assert_eq!(
+            pango_alignment(TextAlignment::Justified),
+            (pango::Alignment::Left, true)
+        );
+        assert_eq!(
+            pango_alignment(TextAlignment::Center),
+            (pango::Alignment::Center, false)
+        );
+    }
+
+    #[test]
+    fn test_char_to_byte_index_handles_multibyte_text() {
+        assert_eq!(char_to_byte_index("abc", 2), 2);
+        assert_eq!(char_to_byte_index("日本語", 1), 3);
+        assert_eq!(char_to_byte_index("a日b", 2), 4);
+        // Positions past the end clamp to the text length
+        assert_eq!(char_to_byte_index("日本", 5), 6);
+        assert_eq!(char_to_byte_index("", 0), 0);
+    }
+
+    #[test]
+    fn test_byte_to_char_index_round_trips() {
+        let text = "a日本b";
+        for char_pos in 0..=text.chars().count() {
+            assert_eq!(
+                byte_to_char_index(text, char_to_byte_index(text, char_pos)),
+                char_pos
+            );
+        }
+        assert_eq!(byte_to_char_index(text, 100), 4);
+    }
+}
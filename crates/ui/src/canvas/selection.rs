@@ -4,7 +4,8 @@
 
 use std::cell::RefCell;
 use std::rc::Rc;
-use testruct_core::layout::Rect;
+use testruct_core::document::{DocumentElement, Page};
+use testruct_core::layout::{Point, Rect};
 
 /// Selection mode
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -144,6 +145,29 @@ impl SelectionBounds {
     }
 }
 
+/// Element found under a point, from the page-level element down to the innermost child
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElementHit {
+    path: Vec<uuid::Uuid>,
+}
+
+impl ElementHit {
+    /// The element directly on the page (the outermost group for grouped children)
+    pub fn top_level(&self) -> uuid::Uuid {
+        self.path[0]
+    }
+
+    /// The innermost element under the point
+    pub fn deepest(&self) -> uuid::Uuid {
+        self.path[self.path.len() - 1]
+    }
+
+    /// Ids from the page-level element to the innermost one
+    pub fn path(&self) -> &[uuid::Uuid] {
+        &self.path
+    }
+}
+
 /// Hit testing for object selection
 pub struct HitTest;
 
@@ -163,6 +187,46 @@ impl HitTest {
         None
     }
 
+    /// Find the topmost visible element at a point on a page
+    ///
+    /// Later elements are drawn on top, so they win, and elements of hidden
+    /// layers are skipped. Groups and frames are
+    /// searched recursively (children are stored in page coordinates and the
+    /// last child is topmost); a point inside a container but over none of
+    /// its children hits the container itself.
+    pub fn topmost_at(point: Point, page: &Page) -> Option<ElementHit> {
+        let mut path = Vec::new();
+        page.layered_elements()
+            .into_iter()
+            .rev()
+            .any(|element| Self::hit_element(element, point, &mut path))
+            .then_some(ElementHit { path })
+    }
+
+    fn hit_elements(elements: &[DocumentElement], point: Point, path: &mut Vec<uuid::Uuid>) -> bool {
+        elements
+            .iter()
+            .rev()
+            .any(|element| Self::hit_element(element, point, path))
+    }
+
+    fn hit_element(element: &DocumentElement, point: Point, path: &mut Vec<uuid::Uuid>) -> bool {
+        if !element.is_visible() {
+            return false;
+        }
+        path.push(element.id());
+        let children = match element {
+            DocumentElement::Group(group) => group.children.as_slice(),
+            DocumentElement::Frame(frame) => frame.children.as_slice(),
+            _ => &[],
+        };
+        if Self::hit_elements(children, point, path) || element.bounds().contains(point) {
+            return true;
+        }
+        path.pop();
+        false
+    }
+
     /// Find objects in selection rectangle
     pub fn hit_test_rect(
         objects: &[(uuid::Uuid, &Rect)],
@@ -190,6 +254,33 @@ impl HitTest {
             .map(|(id, _)| *id)
             .collect()
     }
+
+    /// Find objects lying entirely inside the selection rectangle
+    pub fn contained_in_rect(
+        objects: &[(uuid::Uuid, &Rect)],
+        select_x1: f64,
+        select_y1: f64,
+        select_x2: f64,
+        select_y2: f64,
+    ) -> Vec<uuid::Uuid> {
+        let min_x = select_x1.min(select_x2);
+        let max_x = select_x1.max(select_x2);
+        let min_y = select_y1.min(select_y2);
+        let max_y = select_y1.max(select_y2);
+
+        objects
+            .iter()
+            .filter(|(_, bounds)| {
+                let obj_left = bounds.origin.x as f64;
+                let obj_right = obj_left + bounds.size.width as f64;
+                let obj_top = bounds.origin.y as f64;
+                let obj_bottom = obj_top + bounds.size.height as f64;
+
+                obj_left >= min_x && obj_right <= max_x && obj_top >= min_y && obj_bottom <= max_y
+            })
+            .map(|(id, _)| *id)
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -242,4 +333,92 @@ mod tests {
         state.toggle(id);
         assert!(!state.is_selected(id));
     }
+
+    #[test]
+    fn test_marquee_intersect_and_contain() {
+        let inside = shape_at(10.0, 10.0, 20.0);
+        let straddling = shape_at(40.0, 40.0, 20.0);
+        let outside = shape_at(100.0, 100.0, 20.0);
+        let objects: Vec<(uuid::Uuid, &Rect)> = [&inside, &straddling, &outside]
+            .iter()
+            .map(|element| (element.id(), element.bounds()))
+            .collect();
+
+        assert_eq!(
+            HitTest::hit_test_rect(&objects, 50.0, 50.0, 0.0, 0.0),
+            vec![inside.id(), straddling.id()]
+        );
+        assert_eq!(
+            HitTest::contained_in_rect(&objects, 50.0, 50.0, 0.0, 0.0),
+            vec![inside.id()]
+        );
+    }
+
+    fn shape_at(x: f32, y: f32, size: f32) -> DocumentElement {
+        use testruct_core::document::{ShapeElement, ShapeKind};
+        use testruct_core::layout::Size;
+        DocumentElement::Shape(ShapeElement {
+            id: uuid::Uuid::new_v4(),
+            kind: ShapeKind::Rectangle,
+            bounds: Rect::new(Point::new(x, y), Size::new(size, size)),
+            stroke: None,
+            stroke_width: 1.0,
+            fill: None,
+            visible: true,
+            locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
+        })
+    }
+
+    fn frame_of(children: Vec<DocumentElement>) -> DocumentElement {
+        use testruct_core::document::FrameElement;
+        use testruct_core::layout::Size;
+        DocumentElement::Frame(FrameElement {
+            id: uuid::Uuid::new_v4(),
+            bounds: Rect::new(Point::new(0.0, 0.0), Size::new(100.0, 100.0)),
+            children,
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+        })
+    }
+
+    #[test]
+    fn test_topmost_at_prefers_last_drawn() {
+        let mut page = Page::empty();
+        let bottom = shape_at(0.0, 0.0, 50.0);
+        let top = shape_at(10.0, 10.0, 50.0);
+        let top_id = top.id();
+        page.add_element(bottom);
+        page.add_element(top);
+
+        let hit = HitTest::topmost_at(Point::new(20.0, 20.0), &page).unwrap();
+        assert_eq!(hit.top_level(), top_id);
+        assert!(HitTest::topmost_at(Point::new(200.0, 200.0), &page).is_none());
+    }
+
+    #[test]
+    fn test_topmost_at_descends_into_nested_groups() {
+        let child = shape_at(60.0, 60.0, 20.0);
+        let child_id = child.id();
+        let inner = frame_of(vec![shape_at(0.0, 0.0, 20.0), child]);
+        let inner_id = inner.id();
+        let outer = frame_of(vec![inner]);
+        let outer_id = outer.id();
+        let mut page = Page::empty();
+        page.add_element(outer);
+
+        let hit = HitTest::topmost_at(Point::new(70.0, 70.0), &page).unwrap();
+        assert_eq!(hit.top_level(), outer_id);
+        assert_eq!(hit.deepest(), child_id);
+        assert_eq!(hit.path(), &[outer_id, inner_id, child_id]);
+
+        // Inside the groups but over no child: the innermost container is hit
+        let hit = HitTest::topmost_at(Point::new(40.0, 40.0), &page).unwrap();
+        assert_eq!(hit.deepest(), inner_id);
+    }
 }
@@ -4,45 +4,123 @@
 //! ellipses, lines, arrows, and polygons.
 
 use gtk4::cairo::Context;
-use testruct_core::layout::Rect;
+use testruct_core::document::{Fill, LineStyle};
+use testruct_core::layout::{Point, Rect};
 use testruct_core::typography::Color;
 
-/// Draw a rectangle shape
+/// Line width to stroke with, or `None` when the shape should not be stroked
+///
+/// Zero, negative and non-finite widths (e.g. from hand-edited files) draw no
+/// outline instead of producing a Cairo error or a hairline.
+pub fn stroke_line_width(stroke_width: f32) -> Option<f64> {
+    (stroke_width.is_finite() && stroke_width > 0.0).then_some(stroke_width as f64)
+}
+
+/// Set the dash pattern for the outline of a shape
+///
+/// Call `clear_line_style` after the shape is drawn so the pattern does not
+/// leak into selection boxes and other overlays.
+pub fn apply_line_style(ctx: &Context, line_style: LineStyle, stroke_width: f32) {
+    ctx.set_dash(&line_style.dash_pattern(stroke_width), 0.0);
+}
+
+/// Set the context source to a shape fill
+///
+/// Gradients become a Cairo linear gradient along `Fill::gradient_line`
+/// for `bounds`.
+pub fn set_fill_source(ctx: &Context, fill: &Fill, bounds: &Rect) -> Result<(), cairo::Error> {
+    match fill {
+        Fill::Solid(color) => {
+            ctx.set_source_rgba(
+                color.r as f64,
+                color.g as f64,
+                color.b as f64,
+                color.a as f64,
+            );
+        }
+        Fill::LinearGradient { start, end, .. } => {
+            let Some((from, to)) = fill.gradient_line(bounds) else {
+                return Ok(());
+            };
+            let gradient =
+                cairo::LinearGradient::new(from.x as f64, from.y as f64, to.x as f64, to.y as f64);
+            for (offset, color) in [(0.0, start), (1.0, end)] {
+                gradient.add_color_stop_rgba(
+                    offset,
+                    color.r as f64,
+                    color.g as f64,
+                    color.b as f64,
+                    color.a as f64,
+                );
+            }
+            ctx.set_source(&gradient)?;
+        }
+    }
+    Ok(())
+}
+
+/// Use `color` as the stroke source, including its alpha
+pub fn set_stroke_source(ctx: &Context, color: &Color) {
+    ctx.set_source_rgba(
+        color.r as f64,
+        color.g as f64,
+        color.b as f64,
+        color.a as f64,
+    );
+}
+
+/// Reset the context to solid strokes
+pub fn clear_line_style(ctx: &Context) {
+    ctx.set_dash(&[], 0.0);
+}
+
+/// Add a rectangle path with rounded corners to the context
+///
+/// `radius` should already be clamped to half the smaller side (see
+/// `ShapeElement::effective_corner_radius`); zero adds a plain rectangle.
+pub fn rounded_rectangle_path(ctx: &Context, bounds: &Rect, radius: f64) {
+    let x = bounds.origin.x as f64;
+    let y = bounds.origin.y as f64;
+    let width = bounds.size.width as f64;
+    let height = bounds.size.height as f64;
+    if radius <= 0.0 {
+        ctx.rectangle(x, y, width, height);
+        return;
+    }
+
+    // Corner arc centers, clockwise from the top-right corner
+    use std::f64::consts::{FRAC_PI_2, PI};
+    let (left, top) = (x + radius, y + radius);
+    let (right, bottom) = (x + width - radius, y + height - radius);
+    ctx.new_sub_path();
+    ctx.arc(right, top, radius, -FRAC_PI_2, 0.0);
+    ctx.arc(right, bottom, radius, 0.0, FRAC_PI_2);
+    ctx.arc(left, bottom, radius, FRAC_PI_2, PI);
+    ctx.arc(left, top, radius, PI, PI + FRAC_PI_2);
+    ctx.close_path();
+}
+
+/// Draw a rectangle shape, rounding the corners by `corner_radius`
 pub fn draw_rectangle(
     ctx: &Context,
     bounds: &Rect,
     stroke: &Option<Color>,
     stroke_width: f32,
-    fill: &Option<Color>,
+    fill: &Option<Fill>,
+    corner_radius: f32,
 ) -> Result<(), cairo::Error> {
-    if let Some(fill_color) = fill {
-        ctx.set_source_rgb(
-            fill_color.r as f64,
-            fill_color.g as f64,
-            fill_color.b as f64,
-        );
-        ctx.rectangle(
-            bounds.origin.x as f64,
-            bounds.origin.y as f64,
-            bounds.size.width as f64,
-            bounds.size.height as f64,
-        );
+    let radius = corner_radius as f64;
+
+    if let Some(fill) = fill {
+        set_fill_source(ctx, fill, bounds)?;
+        rounded_rectangle_path(ctx, bounds, radius);
         ctx.fill()?;
     }
 
-    if let Some(stroke_color) = stroke {
-        ctx.set_source_rgb(
-            stroke_color.r as f64,
-            stroke_color.g as f64,
-            stroke_color.b as f64,
-        );
-        ctx.set_line_width(stroke_width as f64);
-        ctx.rectangle(
-            bounds.origin.x as f64,
-            bounds.origin.y as f64,
-            bounds.size.width as f64,
-            bounds.size.height as f64,
-        );
+    if let (Some(stroke_color), Some(line_width)) = (stroke, stroke_line_width(stroke_width)) {
+        set_stroke_source(ctx, stroke_color);
+        ctx.set_line_width(line_width);
+        rounded_rectangle_path(ctx, bounds, radius);
         ctx.stroke()?;
     }
 
@@ -55,7 +133,7 @@ pub fn draw_ellipse(
     bounds: &Rect,
     stroke: &Option<Color>,
     stroke_width: f32,
-    fill: &Option<Color>,
+    fill: &Option<Fill>,
 ) -> Result<(), cairo::Error> {
     let cx = bounds.origin.x as f64 + bounds.size.width as f64 / 2.0;
     let cy = bounds.origin.y as f64 + bounds.size.height as f64 / 2.0;
@@ -69,23 +147,18 @@ pub fn draw_ellipse(
     ctx.arc(0.0, 0.0, 1.0, 0.0, 2.0 * std::f64::consts::PI);
     ctx.restore()?;
 
-    if let Some(fill_color) = fill {
-        ctx.set_source_rgb(
-            fill_color.r as f64,
-            fill_color.g as f64,
-            fill_color.b as f64,
-        );
+    if let Some(fill) = fill {
+        set_fill_source(ctx, fill, bounds)?;
         ctx.fill_preserve()?;
     }
 
-    if let Some(stroke_color) = stroke {
-        ctx.set_source_rgb(
-            stroke_color.r as f64,
-            stroke_color.g as f64,
-            stroke_color.b as f64,
-        );
-        ctx.set_line_width(stroke_width as f64);
+    if let (Some(stroke_color), Some(line_width)) = (stroke, stroke_line_width(stroke_width)) {
+        set_stroke_source(ctx, stroke_color);
+        ctx.set_line_width(line_width);
         ctx.stroke()?;
+    } else {
+        // Clear the path if no stroke
+        ctx.new_path();
     }
 
     Ok(())
@@ -98,13 +171,9 @@ pub fn draw_line(
     stroke: &Option<Color>,
     stroke_width: f32,
 ) -> Result<(), cairo::Error> {
-    if let Some(stroke_color) = stroke {
-        ctx.set_source_rgb(
-            stroke_color.r as f64,
-            stroke_color.g as f64,
-            stroke_color.b as f64,
-        );
-        ctx.set_line_width(stroke_width as f64);
+    if let (Some(stroke_color), Some(line_width)) = (stroke, stroke_line_width(stroke_width)) {
+        set_stroke_source(ctx, stroke_color);
+        ctx.set_line_width(line_width);
         ctx.move_to(bounds.origin.x as f64, bounds.origin.y as f64);
         ctx.line_to(
             bounds.origin.x as f64 + bounds.size.width as f64,
@@ -129,6 +198,9 @@ pub fn draw_arrow(
     stroke: &Option<Color>,
     stroke_width: f32,
 ) -> Result<(), cairo::Error> {
+    let Some(line_width) = stroke_line_width(stroke_width) else {
+        return Ok(());
+    };
     let stroke_color = stroke.unwrap_or(Color {
         r: 0.0,
         g: 0.0,
@@ -146,7 +218,7 @@ pub fn draw_arrow(
     let angle = (y2 - y1).atan2(x2 - x1);
 
     // Arrowhead size proportional to stroke width (minimum 8, scales with stroke)
-    let arrow_length = (stroke_width as f64 * 4.0).clamp(8.0, 24.0);
+    let arrow_length = (line_width * 4.0).clamp(8.0, 24.0);
     let arrow_width = arrow_length * 0.6; // Width is 60% of length
 
     // Calculate arrowhead vertices
@@ -164,14 +236,10 @@ pub fn draw_arrow(
     let right_y = back_y + arrow_width * (angle - std::f64::consts::FRAC_PI_2).sin();
 
     // Set color
-    ctx.set_source_rgb(
-        stroke_color.r as f64,
-        stroke_color.g as f64,
-        stroke_color.b as f64,
-    );
+    set_stroke_source(ctx, &stroke_color);
 
     // Draw the line (from start to the back of the arrowhead)
-    ctx.set_line_width(stroke_width as f64);
+    ctx.set_line_width(line_width);
     ctx.move_to(x1, y1);
     ctx.line_to(back_x, back_y);
     ctx.stroke()?;
@@ -186,23 +254,41 @@ pub fn draw_arrow(
     Ok(())
 }
 
-/// Draw a regular polygon shape
+/// Draw a polygon shape
 ///
-/// Draws a regular polygon (equal sides and angles) inscribed in the bounding rectangle.
-/// Default is a pentagon (5 sides). The polygon is oriented with the first vertex at the top.
+/// Draws a closed path through `vertices` (page coordinates). With fewer than
+/// three vertices a regular pentagon inscribed in the bounds is drawn instead,
+/// which keeps polygons created before freeform vertices existed unchanged.
 ///
 /// # Arguments
 /// * `ctx` - Cairo drawing context
 /// * `bounds` - Bounding rectangle for the polygon
+/// * `vertices` - Polygon vertices in page coordinates
 /// * `stroke` - Optional stroke color
 /// * `stroke_width` - Width of the stroke line
+/// * `fill` - Optional fill
 pub fn draw_polygon(
     ctx: &Context,
     bounds: &Rect,
+    vertices: &[Point],
     stroke: &Option<Color>,
     stroke_width: f32,
+    fill: &Option<Fill>,
 ) -> Result<(), cairo::Error> {
-    draw_regular_polygon(ctx, bounds, stroke, stroke_width, &None, 5)
+    if vertices.len() < 3 {
+        return draw_regular_polygon(ctx, bounds, stroke, stroke_width, fill, 5);
+    }
+
+    for (i, vertex) in vertices.iter().enumerate() {
+        if i == 0 {
+            ctx.move_to(vertex.x as f64, vertex.y as f64);
+        } else {
+            ctx.line_to(vertex.x as f64, vertex.y as f64);
+        }
+    }
+    ctx.close_path();
+
+    fill_and_stroke_path(ctx, bounds, stroke, stroke_width, fill)
 }
 
 /// Draw a regular polygon with configurable number of sides and optional fill
@@ -212,14 +298,14 @@ pub fn draw_polygon(
 /// * `bounds` - Bounding rectangle for the polygon
 /// * `stroke` - Optional stroke color
 /// * `stroke_width` - Width of the stroke line
-/// * `fill` - Optional fill color
+/// * `fill` - Optional fill
 /// * `sides` - Number of sides (minimum 3)
 pub fn draw_regular_polygon(
     ctx: &Context,
     bounds: &Rect,
     stroke: &Option<Color>,
     stroke_width: f32,
-    fill: &Option<Color>,
+    fill: &Option<Fill>,
     sides: usize,
 ) -> Result<(), cairo::Error> {
     // Ensure at least 3 sides
@@ -248,26 +334,27 @@ pub fn draw_regular_polygon(
     }
     ctx.close_path();
 
+    fill_and_stroke_path(ctx, bounds, stroke, stroke_width, fill)
+}
+
+/// Fill and stroke the current path, then clear it
+fn fill_and_stroke_path(
+    ctx: &Context,
+    bounds: &Rect,
+    stroke: &Option<Color>,
+    stroke_width: f32,
+    fill: &Option<Fill>,
+) -> Result<(), cairo::Error> {
     // Fill first (so stroke draws on top)
-    if let Some(fill_color) = fill {
-        ctx.set_source_rgba(
-            fill_color.r as f64,
-            fill_color.g as f64,
-            fill_color.b as f64,
-            fill_color.a as f64,
-        );
+    if let Some(fill) = fill {
+        set_fill_source(ctx, fill, bounds)?;
         ctx.fill_preserve()?;
     }
 
     // Then stroke
-    if let Some(stroke_color) = stroke {
-        ctx.set_source_rgba(
-            stroke_color.r as f64,
-            stroke_color.g as f64,
-            stroke_color.b as f64,
-            stroke_color.a as f64,
-        );
-        ctx.set_line_width(stroke_width as f64);
+    if let (Some(stroke_color), Some(line_width)) = (stroke, stroke_line_width(stroke_width)) {
+        set_stroke_source(ctx, stroke_color);
+        ctx.set_line_width(line_width);
         ctx.stroke()?;
     } else {
         // Clear the path if no stroke
@@ -283,7 +370,7 @@ pub fn draw_triangle(
     bounds: &Rect,
     stroke: &Option<Color>,
     stroke_width: f32,
-    fill: &Option<Color>,
+    fill: &Option<Fill>,
 ) -> Result<(), cairo::Error> {
     draw_regular_polygon(ctx, bounds, stroke, stroke_width, fill, 3)
 }
@@ -294,7 +381,7 @@ pub fn draw_hexagon(
     bounds: &Rect,
     stroke: &Option<Color>,
     stroke_width: f32,
-    fill: &Option<Color>,
+    fill: &Option<Fill>,
 ) -> Result<(), cairo::Error> {
     draw_regular_polygon(ctx, bounds, stroke, stroke_width, fill, 6)
 }
@@ -306,7 +393,7 @@ pub fn draw_hexagon(
 /// * `bounds` - Bounding rectangle for the star
 /// * `stroke` - Optional stroke color
 /// * `stroke_width` - Width of the stroke line
-/// * `fill` - Optional fill color
+/// * `fill` - Optional fill
 /// * `points` - Number of star points (minimum 3)
 /// * `inner_ratio` - Ratio of inner radius to outer radius (0.0-1.0, default ~0.38)
 pub fn draw_star(
@@ -314,7 +401,7 @@ pub fn draw_star(
     bounds: &Rect,
     stroke: &Option<Color>,
     stroke_width: f32,
-    fill: &Option<Color>,
+    fill: &Option<Fill>,
     points: usize,
     inner_ratio: f64,
 ) -> Result<(), cairo::Error> {
@@ -350,30 +437,24 @@ pub fn draw_star(
     }
     ctx.close_path();
 
-    // Fill first
-    if let Some(fill_color) = fill {
-        ctx.set_source_rgba(
-            fill_color.r as f64,
-            fill_color.g as f64,
-            fill_color.b as f64,
-            fill_color.a as f64,
-        );
-        ctx.fill_preserve()?;
-    }
+    fill_and_stroke_path(ctx, bounds, stroke, stroke_width, fill)
+}
 
-    // Then stroke
-    if let Some(stroke_color) = stroke {
-        ctx.set_source_rgba(
-            stroke_color.r as f64,
-            stroke_color.g as f64,
-            stroke_color.b as f64,
-            stroke_color.a as f64,
-        );
-        ctx.set_line_width(stroke_width as f64);
-        ctx.stroke()?;
-    } else {
-        ctx.new_path();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stroke_line_width_passes_positive_widths() {
+        assert_eq!(stroke_line_width(2.5), Some(2.5));
+        assert_eq!(stroke_line_width(0.1), Some(0.1f32 as f64));
     }
 
-    Ok(())
+    #[test]
+    fn test_stroke_line_width_rejects_invalid_widths() {
+        assert_eq!(stroke_line_width(0.0), None);
+        assert_eq!(stroke_line_width(-3.0), None);
+        assert_eq!(stroke_line_width(f32::NAN), None);
+        assert_eq!(stroke_line_width(f32::INFINITY), None);
+    }
 }
@@ -1,6 +1,7 @@
 //! Grid and guide snapping functionality for canvas objects
 //!
-//! Provides snapping to grid lines and user-defined guides for precise positioning.
+//! Provides snapping to grid lines, user-defined guides and the edges/centers
+//! of other objects for precise positioning.
 
 use testruct_core::layout::{Point, Rect};
 
@@ -425,9 +426,94 @@ pub struct SmartGuideResult {
     pub snapped_y: bool,
 }
 
+/// Result of snapping a moving rectangle to other objects
+#[derive(Debug, Clone)]
+pub struct ObjectSnapResult {
+    /// Candidate bounds shifted so the matched edge/center lines up
+    pub bounds: Rect,
+    /// Alignment lines to draw while the snap is active
+    pub snap_lines: Vec<SnapLine>,
+}
+
+/// Snap a moving rectangle's edges and center to those of other objects
+///
+/// Each axis snaps independently to the closest match within `threshold`
+/// (document units). The returned lines span the moving rectangle and the
+/// object it aligned with.
+pub fn snap_to_object_edges(moving: &Rect, others: &[Rect], threshold: f32) -> ObjectSnapResult {
+    let left = moving.origin.x;
+    let top = moving.origin.y;
+    let (width, height) = (moving.size.width, moving.size.height);
+    let moving_x = [left, left + width / 2.0, left + width];
+    let moving_y = [top, top + height / 2.0, top + height];
+
+    // Best (distance, offset, line position, other rect) per axis
+    let mut best_x: Option<(f32, f32, f32, &Rect)> = None;
+    let mut best_y: Option<(f32, f32, f32, &Rect)> = None;
+
+    for other in others {
+        let ox = other.origin.x;
+        let oy = other.origin.y;
+        let other_x = [ox, ox + other.size.width / 2.0, ox + other.size.width];
+        let other_y = [oy, oy + other.size.height / 2.0, oy + other.size.height];
+
+        for &mx in &moving_x {
+            for &target in &other_x {
+                let distance = (target - mx).abs();
+                if distance <= threshold && best_x.is_none_or(|(d, ..)| distance < d) {
+                    best_x = Some((distance, target - mx, target, other));
+                }
+            }
+        }
+        for &my in &moving_y {
+            for &target in &other_y {
+                let distance = (target - my).abs();
+                if distance <= threshold && best_y.is_none_or(|(d, ..)| distance < d) {
+                    best_y = Some((distance, target - my, target, other));
+                }
+            }
+        }
+    }
+
+    let mut bounds = *moving;
+    let mut snap_lines = Vec::new();
+
+    if let Some((_, offset, _, _)) = best_x {
+        bounds.origin.x += offset;
+    }
+    if let Some((_, offset, _, _)) = best_y {
+        bounds.origin.y += offset;
+    }
+
+    // Lines are computed from the snapped bounds so they touch both objects
+    if let Some((_, _, position, other)) = best_x {
+        let start = bounds.origin.y.min(other.origin.y);
+        let end = (bounds.origin.y + bounds.size.height).max(other.origin.y + other.size.height);
+        snap_lines.push(SnapLine {
+            line_type: SnapLineType::ObjectEdge,
+            position,
+            is_horizontal: false,
+            bounds: (start, end),
+        });
+    }
+    if let Some((_, _, position, other)) = best_y {
+        let start = bounds.origin.x.min(other.origin.x);
+        let end = (bounds.origin.x + bounds.size.width).max(other.origin.x + other.size.width);
+        snap_lines.push(SnapLine {
+            line_type: SnapLineType::ObjectEdge,
+            position,
+            is_horizontal: true,
+            bounds: (start, end),
+        });
+    }
+
+    ObjectSnapResult { bounds, snap_lines }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use testruct_core::layout::Size;
 
     #[test]
     fn test_snap_to_grid_x() {
@@ -506,4 +592,41 @@ mod tests {
 
         assert_eq!(result.position.x, 100.0);
     }
+
+    #[test]
+    fn test_snap_to_object_edges_aligns_left_and_center() {
+        let other = Rect::new(Point::new(100.0, 100.0), Size::new(50.0, 50.0));
+        // Left edge 3 units off, vertical center 2 units off
+        let moving = Rect::new(Point::new(103.0, 117.0), Size::new(20.0, 20.0));
+
+        let result = snap_to_object_edges(&moving, &[other], 5.0);
+
+        assert_eq!(result.bounds.origin.x, 100.0);
+        assert_eq!(result.bounds.origin.y, 115.0);
+        assert_eq!(result.snap_lines.len(), 2);
+        assert!(result.snap_lines.iter().any(|l| !l.is_horizontal && l.position == 100.0));
+        assert!(result.snap_lines.iter().any(|l| l.is_horizontal && l.position == 125.0));
+    }
+
+    #[test]
+    fn test_snap_to_object_edges_outside_threshold() {
+        let other = Rect::new(Point::new(0.0, 0.0), Size::new(10.0, 10.0));
+        let moving = Rect::new(Point::new(40.0, 40.0), Size::new(10.0, 10.0));
+
+        let result = snap_to_object_edges(&moving, &[other], 5.0);
+
+        assert_eq!(result.bounds, moving);
+        assert!(result.snap_lines.is_empty());
+    }
+
+    #[test]
+    fn test_snap_to_object_edges_prefers_closest_match() {
+        let far = Rect::new(Point::new(104.0, 0.0), Size::new(10.0, 10.0));
+        let near = Rect::new(Point::new(101.0, 500.0), Size::new(10.0, 10.0));
+        let moving = Rect::new(Point::new(100.0, 300.0), Size::new(30.0, 30.0));
+
+        let result = snap_to_object_edges(&moving, &[far, near], 5.0);
+
+        assert_eq!(result.bounds.origin.x, 101.0);
+    }
 }
@@ -0,0 +1,104 @@
+//! Spell checking of the text being edited
+//!
+//! Holds the dictionary for the project's spell check language. Misspelled
+//! words are underlined by `rendering_text::draw_spelling_underlines` and
+//! corrected from the canvas context menu.
+
+use std::ops::Range;
+use testruct_core::typography::SpellChecker;
+
+/// Suggestions offered for a misspelled word
+pub const MAX_SUGGESTIONS: usize = 5;
+
+/// Dictionary loaded for the current spell check language
+#[derive(Debug, Default)]
+pub struct SpellCheckState {
+    language: Option<String>,
+    checker: Option<SpellChecker>,
+}
+
+impl SpellCheckState {
+    /// Switch to the dictionary for `language`, or turn checking off with `None`
+    ///
+    /// The dictionary is only reloaded when the language changes. A missing
+    /// dictionary is logged and leaves checking off.
+    pub fn set_language(&mut self, language: Option<&str>) {
+        if self.language.as_deref() == language {
+            return;
+        }
+        self.language = language.map(str::to_string);
+        self.checker = language.and_then(|language| match SpellChecker::load(language) {
+            Ok(checker) => {
+                tracing::info!(
+                    "📖 Loaded {} dictionary ({} words)",
+                    language,
+                    checker.len()
+                );
+                Some(checker)
+            }
+            Err(e) => {
+                tracing::warn!("⚠️ Spell checking disabled: {}", e);
+                None
+            }
+        });
+    }
+
+    /// Use `checker` directly instead of loading a dictionary
+    pub fn set_checker(&mut self, language: &str, checker: SpellChecker) {
+        self.language = Some(language.to_string());
+        self.checker = Some(checker);
+    }
+
+    pub fn checker(&self) -> Option<&SpellChecker> {
+        self.checker.as_ref()
+    }
+
+    /// Byte ranges of the misspelled words in `text` (empty when checking is off)
+    pub fn misspelled_ranges(&self, text: &str) -> Vec<Range<usize>> {
+        self.checker
+            .as_ref()
+            .map(|checker| checker.misspelled_ranges(text))
+            .unwrap_or_default()
+    }
+
+    /// The misspelled word at byte `index` of `text` and its suggestions
+    pub fn misspelling_at(&self, text: &str, index: usize) -> Option<(Range<usize>, Vec<String>)> {
+        let checker = self.checker.as_ref()?;
+        let range = testruct_core::typography::spelling::word_at(text, index)?;
+        let word = &text[range.clone()];
+        if checker.check(word) {
+            return None;
+        }
+        Some((range, checker.suggestions(word, MAX_SUGGESTIONS)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_misspelling_at_offers_suggestions() {
+        let mut state = SpellCheckState::default();
+        assert!(state.misspelled_ranges("teh answer").is_empty());
+
+        state.set_checker("en_US", SpellChecker::from_words(["the", "answer"]));
+        let text = "問題: teh answer";
+        let index = text.find("teh").unwrap();
+        assert_eq!(state.misspelled_ranges(text), vec![index..index + 3]);
+        assert_eq!(
+            state.misspelling_at(text, index + 1),
+            Some((index..index + 3, vec!["the".to_string()]))
+        );
+        assert_eq!(
+            state.misspelling_at(text, text.find("answer").unwrap()),
+            None
+        );
+
+        // Same language: the injected dictionary is kept
+        state.set_language(Some("en_US"));
+        assert!(state.checker().is_some());
+        state.set_language(None);
+        assert!(state.checker().is_none());
+    }
+}
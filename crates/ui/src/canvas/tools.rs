@@ -1,11 +1,13 @@
 //! Canvas tool modes and creation operations
 //!
-//! Provides tool modes (Select, Rectangle, Circle, Text) and shape creation functionality.
+//! Provides tool modes (Select, Rectangle, Circle, Polygon, Text, Eyedropper) and shape creation functionality.
 
 use testruct_core::document::{
-    DocumentElement, ImageElement, ShapeElement, ShapeKind, TextElement,
+    DocumentElement, ImageElement, QrCodeElement, QrErrorCorrection, ShapeElement, ShapeKind,
+    TableElement, TextElement, DEFAULT_TEXT_PADDING,
 };
-use testruct_core::layout::{Point, Rect, Size};
+use testruct_core::layout::{Insets, Point, Rect, Size};
+use testruct_core::typography::Color;
 use testruct_core::workspace::assets::AssetRef;
 use uuid::Uuid;
 
@@ -22,10 +24,16 @@ pub enum ToolMode {
     Line,
     /// Create arrow shapes
     Arrow,
+    /// Create freeform polygons by clicking vertices
+    Polygon,
     /// Insert image objects
     Image,
     /// Create text objects
     Text,
+    /// Pick a color from the rendered page
+    Eyedropper,
+    /// Measure the distance between two points (nothing is created)
+    Measure,
     /// Pan the canvas (space+drag or middle mouse)
     Pan,
 }
@@ -44,8 +52,11 @@ impl ToolMode {
             Self::Circle => "Circle",
             Self::Line => "Line",
             Self::Arrow => "Arrow",
+            Self::Polygon => "Polygon",
             Self::Image => "Image",
             Self::Text => "Text",
+            Self::Eyedropper => "Eyedropper",
+            Self::Measure => "Measure",
             Self::Pan => "Pan",
         }
     }
@@ -57,13 +68,63 @@ impl ToolMode {
             Self::Circle => "crosshair",
             Self::Line => "crosshair",
             Self::Arrow => "crosshair",
+            Self::Polygon => "crosshair",
             Self::Image => "crosshair",
             Self::Text => "text",
+            Self::Eyedropper => "crosshair",
+            Self::Measure => "crosshair",
             Self::Pan => "grab",
         }
     }
+
+    /// Single-letter key that switches to this tool (shown uppercase in tooltips)
+    pub fn shortcut_key(&self) -> Option<char> {
+        match self {
+            Self::Select => Some('v'),
+            Self::Rectangle => Some('r'),
+            Self::Circle => Some('e'),
+            Self::Line => Some('l'),
+            Self::Arrow => Some('a'),
+            Self::Polygon => Some('p'),
+            Self::Image => Some('i'),
+            Self::Text => Some('t'),
+            Self::Eyedropper => Some('k'),
+            Self::Measure => Some('m'),
+            Self::Pan => None,
+        }
+    }
+
+    /// Tool bound to a shortcut letter, case-insensitive
+    pub fn from_shortcut_key(key: char) -> Option<Self> {
+        let key = key.to_ascii_lowercase();
+        [
+            Self::Select,
+            Self::Rectangle,
+            Self::Circle,
+            Self::Line,
+            Self::Arrow,
+            Self::Polygon,
+            Self::Image,
+            Self::Text,
+            Self::Eyedropper,
+            Self::Measure,
+        ]
+        .into_iter()
+        .find(|tool| tool.shortcut_key() == Some(key))
+    }
 }
 
+/// Size of a text box created by clicking with the Text tool
+pub const DEFAULT_TEXT_BOX_WIDTH: f64 = 200.0;
+pub const DEFAULT_TEXT_BOX_HEIGHT: f64 = 40.0;
+
+/// Size of one cell of an inserted table
+pub const DEFAULT_TABLE_CELL_WIDTH: f64 = 80.0;
+pub const DEFAULT_TABLE_CELL_HEIGHT: f64 = 30.0;
+
+/// Side length of a newly inserted QR code
+pub const DEFAULT_QR_CODE_SIZE: f64 = 96.0;
+
 /// Factory for creating shape elements
 pub struct ShapeFactory;
 
@@ -93,6 +154,11 @@ impl ShapeFactory {
             fill: None,
             visible: true,
             locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
         })
     }
 
@@ -121,6 +187,11 @@ impl ShapeFactory {
             fill: None,
             visible: true,
             locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
         })
     }
 
@@ -143,6 +214,11 @@ impl ShapeFactory {
             auto_resize_height: false,
             visible: true,
             locked: false,
+            rotation: 0.0,
+            rich_text: None,
+            padding: Insets::uniform(DEFAULT_TEXT_PADDING),
+            text_path: None,
+            opacity: 1.0,
         })
     }
 
@@ -174,6 +250,11 @@ impl ShapeFactory {
             fill: None,
             visible: true,
             locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
         })
     }
 
@@ -205,9 +286,41 @@ impl ShapeFactory {
             fill: None,
             visible: true,
             locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
         })
     }
 
+    /// Create a freeform polygon through the given vertices (document coordinates)
+    ///
+    /// Returns `None` for fewer than three vertices.
+    pub fn create_polygon(points: &[Point]) -> Option<DocumentElement> {
+        let (bounds, vertices) = ShapeElement::polygon_geometry(points)?;
+        Some(DocumentElement::Shape(ShapeElement {
+            id: Uuid::new_v4(),
+            kind: ShapeKind::Polygon,
+            bounds,
+            stroke: Some(testruct_core::typography::Color {
+                r: 0.0,
+                g: 0.0,
+                b: 0.0,
+                a: 1.0,
+            }),
+            stroke_width: 2.0,
+            fill: None,
+            visible: true,
+            locked: false,
+            rotation: 0.0,
+            vertices,
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
+        }))
+    }
+
     /// Create an image element
     pub fn create_image(x: f64, y: f64, width: f64, height: f64) -> DocumentElement {
         DocumentElement::Image(ImageElement {
@@ -225,8 +338,39 @@ impl ShapeFactory {
             },
             visible: true,
             locked: false,
+            rotation: 0.0,
+            fit: Default::default(),
+            opacity: 1.0,
         })
     }
+
+    /// Create an empty table with default-sized cells at (`x`, `y`)
+    pub fn create_table(x: f64, y: f64, rows: usize, columns: usize) -> DocumentElement {
+        let bounds = Rect::new(
+            Point::new(x as f32, y as f32),
+            Size::new(
+                (DEFAULT_TABLE_CELL_WIDTH * columns.max(1) as f64) as f32,
+                (DEFAULT_TABLE_CELL_HEIGHT * rows.max(1) as f64) as f32,
+            ),
+        );
+        DocumentElement::Table(TableElement::new(bounds, rows, columns))
+    }
+
+    /// Create a default-sized QR code encoding `payload` at (`x`, `y`)
+    pub fn create_qr_code(
+        x: f64,
+        y: f64,
+        payload: impl Into<String>,
+        level: QrErrorCorrection,
+    ) -> DocumentElement {
+        let bounds = Rect::new(
+            Point::new(x as f32, y as f32),
+            Size::new(DEFAULT_QR_CODE_SIZE as f32, DEFAULT_QR_CODE_SIZE as f32),
+        );
+        let mut qr = QrCodeElement::new(bounds, payload);
+        qr.error_correction = level;
+        DocumentElement::QrCode(qr)
+    }
 }
 
 /// Tool state for shape creation
@@ -246,16 +390,34 @@ pub struct ToolState {
     pub resize_original_bounds: Option<crate::canvas::mouse::CanvasMousePos>,
     /// Original element bounds before resize started (for undo)
     pub resize_element_bounds: Option<testruct_core::layout::Rect>,
-    /// Previous drag offset (for computing delta in real-time resize)
-    pub last_drag_offset: Option<(f64, f64)>,
+    /// Element being turned with the rotation handle
+    pub rotating_object_id: Option<uuid::Uuid>,
+    /// Rotation of that element before the drag started (for undo)
+    pub rotation_original: Option<f32>,
+    /// Angle readout shown next to the cursor while rotating (document position, degrees)
+    pub rotation_label: Option<(Point, f32)>,
+    /// Position/size readout shown while moving or resizing (cursor position, element bounds)
+    pub measurement: Option<(Point, Rect)>,
     /// ID of text element currently being edited
     pub editing_text_id: Option<uuid::Uuid>,
     /// Cursor position in the edited text
     pub editing_cursor_pos: usize,
-    /// Text selection start position (for future multi-click selection)
-    pub editing_selection_start: Option<usize>,
+    /// Other end of the text selection; the selection spans anchor..cursor
+    pub selection_anchor: Option<usize>,
     /// Whether marquee selection is in progress
     pub marquee_selecting: bool,
+    /// Vertices placed so far with the Polygon tool (document coordinates)
+    pub polygon_points: Vec<Point>,
+    /// Last color picked with the Eyedropper tool
+    pub sampled_color: Option<Color>,
+    /// Span shown by the Measure tool (cleared when switching tools)
+    pub measure_line: Option<crate::canvas::measure::Measurement>,
+    /// Guide being dragged out of the ruler or moved (index into `RenderConfig::guides`)
+    pub guide_drag: Option<usize>,
+    /// Table entered with a double-click for cell editing
+    pub editing_table_id: Option<uuid::Uuid>,
+    /// Row and column of the cell being edited in that table
+    pub editing_table_cell: Option<(usize, usize)>,
 }
 
 impl Default for ToolState {
@@ -270,11 +432,240 @@ impl Default for ToolState {
             resize_handle: None,
             resize_original_bounds: None,
             resize_element_bounds: None,
-            last_drag_offset: None,
+            rotating_object_id: None,
+            rotation_original: None,
+            rotation_label: None,
+            measurement: None,
             editing_text_id: None,
             editing_cursor_pos: 0,
-            editing_selection_start: None,
+            selection_anchor: None,
             marquee_selecting: false,
+            polygon_points: Vec::new(),
+            sampled_color: None,
+            measure_line: None,
+            guide_drag: None,
+            editing_table_id: None,
+            editing_table_cell: None,
+        }
+    }
+}
+
+impl ToolState {
+    /// Leave text editing mode
+    ///
+    /// If the Text tool is still active (box created by a click), switch back
+    /// to Select just like drag-created elements do.
+    pub fn exit_text_editing(&mut self) {
+        self.editing_text_id = None;
+        self.editing_cursor_pos = 0;
+        self.selection_anchor = None;
+        if self.current_tool == ToolMode::Text {
+            self.current_tool = ToolMode::Select;
+        }
+    }
+
+    /// Leave the table entered for cell editing
+    pub fn exit_table_editing(&mut self) {
+        self.editing_table_id = None;
+        self.editing_table_cell = None;
+    }
+
+    /// Selected character range in the edited text, if any is non-empty
+    pub fn text_selection(&self) -> Option<std::ops::Range<usize>> {
+        let anchor = self.selection_anchor?;
+        let cursor = self.editing_cursor_pos;
+        (anchor != cursor).then(|| anchor.min(cursor)..anchor.max(cursor))
+    }
+
+    /// Move the text cursor, extending the selection (Shift) or clearing it
+    pub fn move_editing_cursor(&mut self, cursor_pos: usize, extend_selection: bool) {
+        if extend_selection {
+            self.selection_anchor.get_or_insert(self.editing_cursor_pos);
+        } else {
+            self.selection_anchor = None;
+        }
+        self.editing_cursor_pos = cursor_pos;
+    }
+
+    /// Select every character of an edited text with `char_count` characters
+    pub fn select_all_text(&mut self, char_count: usize) {
+        self.selection_anchor = Some(0);
+        self.editing_cursor_pos = char_count;
+    }
+
+    /// Add a vertex to the polygon in progress
+    ///
+    /// A click on (or very near) the previous vertex is ignored, so the two
+    /// presses of a closing double-click don't add duplicate points.
+    pub fn add_polygon_point(&mut self, point: Point) {
+        let duplicate = self.polygon_points.last().is_some_and(|last| {
+            (last.x - point.x).abs() < 1.0 && (last.y - point.y).abs() < 1.0
+        });
+        if !duplicate {
+            self.polygon_points.push(point);
+        }
+    }
+
+    /// Take the polygon in progress as a new element and return to Select
+    ///
+    /// Returns `None` (discarding the points) when fewer than three were placed.
+    pub fn finish_polygon(&mut self) -> Option<DocumentElement> {
+        let points = std::mem::take(&mut self.polygon_points);
+        self.current_tool = ToolMode::Select;
+        ShapeFactory::create_polygon(&points)
+    }
+}
+
+/// Remove a character range from text (char positions, clamped to the text)
+pub fn remove_char_range(content: &str, range: std::ops::Range<usize>) -> String {
+    content
+        .chars()
+        .enumerate()
+        .filter(|(i, _)| !range.contains(i))
+        .map(|(_, c)| c)
+        .collect()
+}
+
+/// Insert text at a char position (clamped to the end of the text)
+///
+/// Returns the new content and the char position just past the insertion.
+pub fn insert_at_char(content: &str, char_pos: usize, inserted: &str) -> (String, usize) {
+    let mut chars: Vec<char> = content.chars().collect();
+    let char_pos = char_pos.min(chars.len());
+    chars.splice(char_pos..char_pos, inserted.chars());
+    (
+        chars.into_iter().collect(),
+        char_pos + inserted.chars().count(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_shortcut_keys_round_trip() {
+        for tool in [
+            ToolMode::Select,
+            ToolMode::Rectangle,
+            ToolMode::Text,
+            ToolMode::Eyedropper,
+            ToolMode::Measure,
+        ] {
+            let key = tool.shortcut_key().expect("palette tools have a key");
+            let upper = key.to_ascii_uppercase();
+            assert_eq!(ToolMode::from_shortcut_key(key), Some(tool));
+            assert_eq!(ToolMode::from_shortcut_key(upper), Some(tool));
+        }
+        assert_eq!(ToolMode::Pan.shortcut_key(), None);
+        assert_eq!(ToolMode::from_shortcut_key('z'), None);
+    }
+
+    #[test]
+    fn test_exit_text_editing_switches_text_tool_to_select() {
+        let mut state = ToolState {
+            current_tool: ToolMode::Text,
+            editing_text_id: Some(Uuid::new_v4()),
+            editing_cursor_pos: 3,
+            ..Default::default()
+        };
+
+        state.exit_text_editing();
+
+        assert_eq!(state.editing_text_id, None);
+        assert_eq!(state.editing_cursor_pos, 0);
+        assert_eq!(state.current_tool, ToolMode::Select);
+    }
+
+    #[test]
+    fn test_shift_movement_extends_text_selection() {
+        let mut state = ToolState {
+            editing_cursor_pos: 2,
+            ..Default::default()
+        };
+
+        state.move_editing_cursor(4, true);
+        state.move_editing_cursor(5, true);
+        assert_eq!(state.text_selection(), Some(2..5));
+
+        // Moving back past the anchor flips the range
+        state.move_editing_cursor(0, true);
+        assert_eq!(state.text_selection(), Some(0..2));
+
+        // Plain movement clears it
+        state.move_editing_cursor(1, false);
+        assert_eq!(state.text_selection(), None);
+        assert_eq!(state.selection_anchor, None);
+    }
+
+    #[test]
+    fn test_select_all_and_remove_range() {
+        let mut state = ToolState::default();
+        state.select_all_text(3);
+        assert_eq!(state.text_selection(), Some(0..3));
+
+        assert_eq!(remove_char_range("日本語テキスト", 1..3), "日テキスト");
+        assert_eq!(remove_char_range("abc", 1..10), "a");
+        assert_eq!(
+            insert_at_char("3 4 = 12", 1, " ×"),
+            ("3 × 4 = 12".to_string(), 3)
+        );
+        assert_eq!(insert_at_char("約", 9, "½"), ("約½".to_string(), 2));
+
+        state.exit_text_editing();
+        assert_eq!(state.text_selection(), None);
+    }
+
+    #[test]
+    fn test_exit_text_editing_keeps_other_tools() {
+        let mut state = ToolState {
+            current_tool: ToolMode::Rectangle,
+            editing_text_id: Some(Uuid::new_v4()),
+            ..Default::default()
+        };
+
+        state.exit_text_editing();
+
+        assert_eq!(state.current_tool, ToolMode::Rectangle);
+    }
+
+    #[test]
+    fn test_create_polygon_uses_relative_vertices() {
+        let points = [
+            Point::new(10.0, 10.0),
+            Point::new(110.0, 10.0),
+            Point::new(60.0, 60.0),
+        ];
+        let Some(DocumentElement::Shape(shape)) = ShapeFactory::create_polygon(&points) else {
+            panic!("expected a polygon shape");
+        };
+
+        assert_eq!(shape.kind, ShapeKind::Polygon);
+        assert_eq!(shape.bounds, Rect::new(Point::new(10.0, 10.0), Size::new(100.0, 50.0)));
+        assert_eq!(shape.vertices[2], Point::new(0.5, 1.0));
+        assert_eq!(shape.vertex_positions(), points.to_vec());
+    }
+
+    #[test]
+    fn test_finish_polygon_needs_three_points() {
+        let mut state = ToolState {
+            current_tool: ToolMode::Polygon,
+            ..Default::default()
+        };
+        state.add_polygon_point(Point::new(0.0, 0.0));
+        state.add_polygon_point(Point::new(50.0, 0.0));
+        // Second press of a double-click lands on the same spot
+        state.add_polygon_point(Point::new(50.0, 0.0));
+        assert_eq!(state.polygon_points.len(), 2);
+
+        assert!(state.finish_polygon().is_none());
+        assert!(state.polygon_points.is_empty());
+        assert_eq!(state.current_tool, ToolMode::Select);
+
+        state.current_tool = ToolMode::Polygon;
+        for point in [(0.0, 0.0), (50.0, 0.0), (25.0, 40.0)] {
+            state.add_polygon_point(Point::new(point.0, point.1));
         }
+        assert!(state.finish_polygon().is_some());
     }
 }
@@ -3,14 +3,19 @@
 //! Provides a global clipboard for storing document elements that can be
 //! pasted into the document. Supports:
 //! - Internal element copy/paste
+//! - Element copy/paste between running instances through the system
+//!   clipboard (JSON under [`ELEMENTS_MIME_TYPE`])
 //! - External text paste (creates TextElement)
 //! - External image paste (creates ImageElement)
 //! - Cumulative paste offset to avoid overlapping
+//! - Paste in place and paste centered on a point (see [`PastePlacement`])
 
+use gtk4::{gdk, gio, glib, prelude::*};
 use once_cell::sync::Lazy;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
-use testruct_core::document::{DocumentElement, ImageElement, TextElement};
-use testruct_core::layout::{Point, Rect, Size};
+use testruct_core::document::{DocumentElement, ImageElement, TextElement, DEFAULT_TEXT_PADDING};
+use testruct_core::layout::{Insets, Point, Rect, Size};
 use testruct_core::typography::TextStyle;
 use testruct_core::workspace::assets::AssetRef;
 
@@ -20,6 +25,9 @@ const BASE_PASTE_OFFSET: f32 = 20.0;
 /// Maximum cumulative offset before reset
 const MAX_PASTE_OFFSET: f32 = 200.0;
 
+/// MIME type of the JSON-serialized elements placed on the system clipboard
+pub const ELEMENTS_MIME_TYPE: &str = "application/x-testruct-elements";
+
 /// Clipboard data structure holding copied elements and metadata
 #[derive(Clone, Debug)]
 pub struct ClipboardData {
@@ -66,6 +74,17 @@ impl ClipboardData {
     }
 }
 
+/// Where pasted elements are placed
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PastePlacement {
+    /// Shifted by the cumulative paste offset, so repeated pastes don't overlap
+    Offset,
+    /// At exactly the copied coordinates
+    InPlace,
+    /// Moved so the combined bounds of the pasted elements center on a point
+    CenteredAt(Point),
+}
+
 /// External clipboard content types
 #[derive(Clone, Debug)]
 pub enum ExternalClipboardContent {
@@ -83,6 +102,12 @@ static CLIPBOARD: Lazy<Mutex<Option<ClipboardData>>> = Lazy::new(|| Mutex::new(N
 /// Paste count tracker for cumulative offset
 static PASTE_COUNT: Lazy<Mutex<u32>> = Lazy::new(|| Mutex::new(0));
 
+/// Last element payload pasted from the system clipboard
+///
+/// Pasting the same payload again keeps stepping the offset; a new payload
+/// starts over.
+static LAST_SYSTEM_PAYLOAD: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+
 /// Copy elements to clipboard (resets paste count)
 pub fn copy_to_clipboard(elements: Vec<DocumentElement>) {
     let len = elements.len();
@@ -98,24 +123,195 @@ pub fn copy_to_clipboard(elements: Vec<DocumentElement>) {
 
 /// Get elements from clipboard (deep cloned with new IDs and cumulative offset)
 pub fn paste_from_clipboard() -> Option<Vec<DocumentElement>> {
+    paste_from_clipboard_at(PastePlacement::Offset)
+}
+
+/// Get elements from clipboard (deep cloned with new IDs) placed as requested
+///
+/// Only [`PastePlacement::Offset`] advances the cumulative paste offset.
+pub fn paste_from_clipboard_at(placement: PastePlacement) -> Option<Vec<DocumentElement>> {
     let mut clipboard = CLIPBOARD.lock().expect("clipboard lock");
 
     clipboard.as_mut().map(|data| {
-        let offset = data.current_offset();
-        data.increment_paste_count();
-
-        data.elements
+        let mut pasted: Vec<DocumentElement> = data
+            .elements
             .iter()
             .map(|elem| {
                 let mut new_elem = elem.clone();
                 regenerate_element_id(&mut new_elem);
-                offset_element_bounds(&mut new_elem, offset);
                 new_elem
             })
-            .collect()
+            .collect();
+        let offset = placement_offset(&pasted, placement, || {
+            let offset = data.current_offset();
+            data.increment_paste_count();
+            offset
+        });
+        offset_elements(&mut pasted, offset);
+        pasted
     })
 }
 
+/// Copy elements to the in-process clipboard and publish them on the system clipboard
+///
+/// Other instances read them back through [`paste_elements`].
+pub fn copy_elements(clipboard: &gdk::Clipboard, elements: Vec<DocumentElement>) {
+    match serde_json::to_string(&elements) {
+        Ok(json) => {
+            let bytes = glib::Bytes::from_owned(json.into_bytes());
+            let provider = gdk::ContentProvider::for_bytes(ELEMENTS_MIME_TYPE, &bytes);
+            if let Err(e) = clipboard.set_content(Some(&provider)) {
+                tracing::warn!("⚠️ Failed to set system clipboard: {}", e);
+            }
+        }
+        Err(e) => {
+            tracing::error!("❌ Failed to serialize clipboard content: {}", e);
+        }
+    }
+
+    copy_to_clipboard(elements);
+}
+
+/// Read elements to paste, preferring the system clipboard
+///
+/// Elements copied in another instance are read from the system clipboard.
+/// When it holds no [`ELEMENTS_MIME_TYPE`] data (or the data is our own),
+/// the in-process clipboard is used instead. The callback receives elements
+/// with new IDs, placed according to `placement`.
+pub fn paste_elements<F>(clipboard: &gdk::Clipboard, placement: PastePlacement, callback: F)
+where
+    F: FnOnce(Option<Vec<DocumentElement>>) + 'static,
+{
+    if clipboard.is_local() || !clipboard.formats().contain_mime_type(ELEMENTS_MIME_TYPE) {
+        callback(paste_from_clipboard_at(placement));
+        return;
+    }
+
+    clipboard.read_async(
+        &[ELEMENTS_MIME_TYPE],
+        glib::Priority::DEFAULT,
+        gio::Cancellable::NONE,
+        move |result| {
+            let stream = match result {
+                Ok((stream, _mime_type)) => stream,
+                Err(e) => {
+                    tracing::warn!("⚠️ Failed to read system clipboard: {}", e);
+                    callback(paste_from_clipboard_at(placement));
+                    return;
+                }
+            };
+
+            let buffer = gio::MemoryOutputStream::new_resizable();
+            let buffer_ref = buffer.clone();
+            buffer.splice_async(
+                &stream,
+                gio::OutputStreamSpliceFlags::CLOSE_SOURCE
+                    | gio::OutputStreamSpliceFlags::CLOSE_TARGET,
+                glib::Priority::DEFAULT,
+                gio::Cancellable::NONE,
+                move |result| {
+                    let pasted = match result {
+                        Ok(_) => {
+                            let bytes = buffer_ref.steal_as_bytes();
+                            std::str::from_utf8(&bytes)
+                                .ok()
+                                .and_then(|json| paste_from_json_at(json, placement))
+                        }
+                        Err(e) => {
+                            tracing::warn!("⚠️ Failed to read system clipboard: {}", e);
+                            None
+                        }
+                    };
+                    callback(pasted.or_else(|| paste_from_clipboard_at(placement)));
+                },
+            );
+        },
+    );
+}
+
+/// Elements from a system clipboard payload (new IDs, cumulative offset)
+///
+/// Returns None if the payload is not a serialized element list.
+pub fn paste_from_json(json: &str) -> Option<Vec<DocumentElement>> {
+    paste_from_json_at(json, PastePlacement::Offset)
+}
+
+/// Elements from a system clipboard payload (new IDs) placed as requested
+///
+/// Returns None if the payload is not a serialized element list.
+pub fn paste_from_json_at(json: &str, placement: PastePlacement) -> Option<Vec<DocumentElement>> {
+    let elements: Vec<DocumentElement> = match serde_json::from_str(json) {
+        Ok(elements) => elements,
+        Err(e) => {
+            tracing::warn!("⚠️ Ignoring invalid clipboard elements: {}", e);
+            return None;
+        }
+    };
+
+    {
+        let mut last = LAST_SYSTEM_PAYLOAD.lock().expect("system payload lock");
+        if last.as_deref() != Some(json) {
+            *last = Some(json.to_string());
+            reset_paste_offset();
+        }
+    }
+
+    let mut pasted: Vec<DocumentElement> =
+        elements.iter().map(DocumentElement::duplicate).collect();
+    let offset = placement_offset(&pasted, placement, get_paste_offset);
+    offset_elements(&mut pasted, offset);
+    tracing::info!("📋 Read {} elements from system clipboard", elements.len());
+    Some(pasted)
+}
+
+/// Offset that moves `elements` to `placement`
+///
+/// `cumulative` supplies the stepping offset for [`PastePlacement::Offset`]
+/// and is only called for it.
+fn placement_offset(
+    elements: &[DocumentElement],
+    placement: PastePlacement,
+    cumulative: impl FnOnce() -> (f32, f32),
+) -> (f32, f32) {
+    match placement {
+        PastePlacement::Offset => cumulative(),
+        PastePlacement::InPlace => (0.0, 0.0),
+        PastePlacement::CenteredAt(center) => match elements_bounds(elements) {
+            Some(bounds) => (
+                center.x - (bounds.origin.x + bounds.size.width / 2.0),
+                center.y - (bounds.origin.y + bounds.size.height / 2.0),
+            ),
+            None => (0.0, 0.0),
+        },
+    }
+}
+
+/// Combined bounds of `elements`, or None when there are none
+pub fn elements_bounds(elements: &[DocumentElement]) -> Option<Rect> {
+    let first = *elements.first()?.bounds();
+    let (mut left, mut top) = (first.origin.x, first.origin.y);
+    let (mut right, mut bottom) = (left + first.size.width, top + first.size.height);
+    for bounds in elements.iter().skip(1).map(DocumentElement::bounds) {
+        left = left.min(bounds.origin.x);
+        top = top.min(bounds.origin.y);
+        right = right.max(bounds.origin.x + bounds.size.width);
+        bottom = bottom.max(bounds.origin.y + bounds.size.height);
+    }
+    Some(Rect::new(
+        Point::new(left, top),
+        Size::new(right - left, bottom - top),
+    ))
+}
+
+/// Move pasted elements by `offset`
+pub fn offset_elements(elements: &mut [DocumentElement], offset: (f32, f32)) {
+    if offset != (0.0, 0.0) {
+        for element in elements {
+            offset_element_bounds(element, offset);
+        }
+    }
+}
+
 /// Get current paste offset (for external paste operations)
 pub fn get_paste_offset() -> (f32, f32) {
     let mut count = PASTE_COUNT.lock().expect("paste count lock");
@@ -153,6 +349,11 @@ pub fn create_text_element_from_clipboard(text: &str, position: Option<(f32, f32
         auto_resize_height: true,
         visible: true,
         locked: false,
+        rotation: 0.0,
+        rich_text: None,
+        padding: Insets::uniform(DEFAULT_TEXT_PADDING),
+        text_path: None,
+        opacity: 1.0,
     };
 
     tracing::info!("📝 Created TextElement from clipboard text ({} chars)", text.len());
@@ -160,44 +361,101 @@ pub fn create_text_element_from_clipboard(text: &str, position: Option<(f32, f32
 }
 
 /// Create an ImageElement from external clipboard image data
-/// Returns None if image data is invalid or can't be processed
+///
+/// The image is centered on `center` at its intrinsic pixel size, scaled
+/// down to fit the page if needed and kept within the page.
+/// Returns None if image data is not a PNG with a usable size
 pub fn create_image_element_from_clipboard(
     image_data: &[u8],
-    position: Option<(f32, f32)>,
+    center: Point,
+    page_size: Size,
     asset_ref: AssetRef,
 ) -> Option<DocumentElement> {
-    let offset = get_paste_offset();
-    let (x, y) = position.unwrap_or((100.0, 100.0));
-
-    // Try to determine image dimensions from PNG header
-    let (width, height) = parse_png_dimensions(image_data).unwrap_or((200.0, 200.0));
-
-    // Scale down if too large
-    let max_size = 400.0;
-    let scale = if width > max_size || height > max_size {
-        max_size / width.max(height)
-    } else {
-        1.0
-    };
-
-    let scaled_width = width * scale;
-    let scaled_height = height * scale;
+    let (width, height) = parse_png_dimensions(image_data)?;
+    if width <= 0.0 || height <= 0.0 {
+        return None;
+    }
+    let bounds = image_paste_bounds(Size::new(width, height), center, page_size);
 
     let image_element = ImageElement {
         id: uuid::Uuid::new_v4(),
         source: asset_ref,
-        bounds: Rect::new(
-            Point::new(x + offset.0, y + offset.1),
-            Size::new(scaled_width, scaled_height),
-        ),
+        bounds,
         visible: true,
         locked: false,
+        rotation: 0.0,
+        fit: Default::default(),
+        opacity: 1.0,
     };
 
-    tracing::info!("🖼️ Created ImageElement from clipboard ({:.0}x{:.0})", scaled_width, scaled_height);
+    tracing::info!(
+        "🖼️ Created ImageElement from clipboard ({:.0}x{:.0})",
+        bounds.size.width,
+        bounds.size.height
+    );
     Some(DocumentElement::Image(image_element))
 }
 
+/// Bounds for a pasted image: intrinsic size fitted to the page, centered and clamped
+fn image_paste_bounds(image_size: Size, center: Point, page_size: Size) -> Rect {
+    let scale = (page_size.width / image_size.width)
+        .min(page_size.height / image_size.height)
+        .min(1.0);
+    let width = image_size.width * scale;
+    let height = image_size.height * scale;
+
+    let max_x = (page_size.width - width).max(0.0);
+    let max_y = (page_size.height - height).max(0.0);
+    let x = (center.x - width / 2.0).clamp(0.0, max_x);
+    let y = (center.y - height / 2.0).clamp(0.0, max_y);
+
+    Rect::new(Point::new(x, y), Size::new(width, height))
+}
+
+/// Check if the system clipboard offers an image
+pub fn has_image(clipboard: &gdk::Clipboard) -> bool {
+    clipboard
+        .formats()
+        .contains_type(gdk::Texture::static_type())
+}
+
+/// Read an image from the system clipboard, encoded as PNG
+pub fn read_image_png<F>(clipboard: &gdk::Clipboard, callback: F)
+where
+    F: FnOnce(Option<glib::Bytes>) + 'static,
+{
+    clipboard.read_texture_async(gio::Cancellable::NONE, move |result| match result {
+        Ok(Some(texture)) => callback(Some(texture.save_to_png_bytes())),
+        Ok(None) => callback(None),
+        Err(e) => {
+            tracing::warn!("⚠️ Failed to read clipboard image: {}", e);
+            callback(None);
+        }
+    });
+}
+
+/// Directory pasted images are saved to
+///
+/// Next to the project file (`<name>_assets`) once it has been saved,
+/// otherwise in the user cache directory.
+pub fn pasted_image_dir(project_path: Option<&Path>) -> PathBuf {
+    let project = project_path.and_then(|path| Some((path.parent()?, path.file_stem()?)));
+    match project {
+        Some((parent, stem)) => parent.join(format!("{}_assets", stem.to_string_lossy())),
+        None => glib::user_cache_dir()
+            .join("testruct")
+            .join("pasted-images"),
+    }
+}
+
+/// Save pasted PNG data as a new file in `dir`, returning its path
+pub fn save_pasted_image(image_data: &[u8], dir: &Path) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(format!("pasted-{}.png", uuid::Uuid::new_v4()));
+    std::fs::write(&path, image_data)?;
+    Ok(path)
+}
+
 /// Parse PNG dimensions from header
 fn parse_png_dimensions(data: &[u8]) -> Option<(f32, f32)> {
     // PNG header: 8 bytes signature, then IHDR chunk
@@ -258,6 +516,12 @@ fn regenerate_element_id(element: &mut DocumentElement) {
         DocumentElement::Group(group) => {
             group.id = uuid::Uuid::new_v4();
         }
+        DocumentElement::Table(table) => {
+            table.id = uuid::Uuid::new_v4();
+        }
+        DocumentElement::QrCode(qr) => {
+            qr.id = uuid::Uuid::new_v4();
+        }
     }
 }
 
@@ -286,6 +550,14 @@ fn offset_element_bounds(element: &mut DocumentElement, offset: (f32, f32)) {
             group.bounds.origin.x += offset.0;
             group.bounds.origin.y += offset.1;
         }
+        DocumentElement::Table(table) => {
+            table.bounds.origin.x += offset.0;
+            table.bounds.origin.y += offset.1;
+        }
+        DocumentElement::QrCode(qr) => {
+            qr.bounds.origin.x += offset.0;
+            qr.bounds.origin.y += offset.1;
+        }
     }
 }
 
@@ -305,6 +577,11 @@ mod tests {
             auto_resize_height: false,
             visible: true,
             locked: false,
+            rotation: 0.0,
+            rich_text: None,
+            padding: Insets::uniform(DEFAULT_TEXT_PADDING),
+            text_path: None,
+            opacity: 1.0,
         }
     }
 
@@ -373,6 +650,74 @@ mod tests {
         clear_clipboard();
     }
 
+    #[test]
+    fn test_paste_from_json() {
+        let text = create_test_text(10.0, 10.0, "From another window");
+        let original_id = text.id;
+        let json = serde_json::to_string(&vec![DocumentElement::Text(text)]).unwrap();
+
+        let pasted = paste_from_json(&json).expect("valid payload");
+        assert_eq!(pasted.len(), 1);
+        if let DocumentElement::Text(t) = &pasted[0] {
+            assert_ne!(t.id, original_id);
+            assert_eq!(t.content, "From another window");
+        } else {
+            panic!("expected a text element");
+        }
+
+        assert!(paste_from_json("not json").is_none());
+    }
+
+    #[test]
+    fn test_paste_placement_in_place_and_centered() {
+        let first = create_test_text(10.0, 10.0, "A");
+        let second = create_test_text(110.0, 50.0, "B");
+        let json = serde_json::to_string(&vec![
+            DocumentElement::Text(first),
+            DocumentElement::Text(second),
+        ])
+        .unwrap();
+
+        let in_place = paste_from_json_at(&json, PastePlacement::InPlace).expect("payload");
+        assert_eq!(in_place[0].bounds().origin, Point::new(10.0, 10.0));
+        assert_eq!(in_place[1].bounds().origin, Point::new(110.0, 50.0));
+
+        // Combined bounds 10..210 × 10..70, centered on (300, 300)
+        let center = PastePlacement::CenteredAt(Point::new(300.0, 300.0));
+        let centered = paste_from_json_at(&json, center).expect("payload");
+        let bounds = elements_bounds(&centered).expect("bounds");
+        assert_eq!(bounds.origin, Point::new(200.0, 270.0));
+        assert_eq!(bounds.size, Size::new(200.0, 60.0));
+    }
+
+    #[test]
+    fn test_image_paste_bounds_keeps_small_images_at_size() {
+        let bounds = image_paste_bounds(
+            Size::new(100.0, 50.0),
+            Point::new(300.0, 400.0),
+            Size::new(595.0, 842.0),
+        );
+        assert_eq!(bounds.size, Size::new(100.0, 50.0));
+        assert_eq!(bounds.origin, Point::new(250.0, 375.0));
+    }
+
+    #[test]
+    fn test_image_paste_bounds_fits_and_clamps_to_page() {
+        let bounds = image_paste_bounds(
+            Size::new(1190.0, 421.0),
+            Point::new(500.0, 10.0),
+            Size::new(595.0, 842.0),
+        );
+        assert_eq!(bounds.size, Size::new(595.0, 210.5));
+        assert_eq!(bounds.origin, Point::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn test_pasted_image_dir_next_to_project() {
+        let dir = pasted_image_dir(Some(Path::new("/tmp/exam/unit1.json")));
+        assert_eq!(dir, PathBuf::from("/tmp/exam/unit1_assets"));
+    }
+
     #[test]
     fn test_multiple_paste_operations() {
         clear_clipboard();
@@ -0,0 +1,207 @@
+//! Export options dialogs
+//!
+//! The image dialog lets the user pick the output resolution either as DPI or
+//! as a page scale factor. The two inputs are linked: editing one updates the
+//! other. The PDF dialog sets crop marks and bleed for print output.
+
+use crate::export::{
+    dpi_to_scale, scale_to_dpi, ExportConfig, ExportFormat, PdfExportOptions, MAX_EXPORT_SCALE,
+    MIN_EXPORT_SCALE,
+};
+use gtk4::prelude::*;
+use gtk4::{
+    Adjustment, Align, Box as GtkBox, Button, CheckButton, Label, Orientation, SpinButton, Window,
+};
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// Show the image export options dialog (PNG/JPEG)
+///
+/// `on_confirmed` is called with the resulting config when the user presses export.
+pub fn show_image_export_options(
+    parent: &Window,
+    format: ExportFormat,
+    on_confirmed: Box<dyn Fn(ExportConfig)>,
+) {
+    let dialog = gtk4::ApplicationWindow::builder()
+        .transient_for(parent)
+        .modal(true)
+        .title("画像エクスポート")
+        .default_width(360)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_start(16);
+    main_box.set_margin_end(16);
+    main_box.set_margin_top(16);
+    main_box.set_margin_bottom(16);
+
+    let title = Label::new(Some(&format!("{} エクスポート設定", format.display_name())));
+    title.add_css_class("title-3");
+    title.set_halign(Align::Start);
+    main_box.append(&title);
+
+    // DPI input
+    let dpi_box = GtkBox::new(Orientation::Horizontal, 8);
+    let dpi_label = Label::new(Some("解像度 (DPI):"));
+    dpi_label.set_size_request(120, -1);
+    dpi_box.append(&dpi_label);
+    let default_scale = ExportConfig::default().scale();
+    let dpi_adj = Adjustment::new(
+        scale_to_dpi(default_scale),
+        scale_to_dpi(MIN_EXPORT_SCALE),
+        scale_to_dpi(MAX_EXPORT_SCALE),
+        1.0,
+        24.0,
+        0.0,
+    );
+    let dpi_spin = SpinButton::new(Some(&dpi_adj), 1.0, 0);
+    dpi_box.append(&dpi_spin);
+    main_box.append(&dpi_box);
+
+    // Scale input
+    let scale_box = GtkBox::new(Orientation::Horizontal, 8);
+    let scale_label = Label::new(Some("倍率 (×):"));
+    scale_label.set_size_request(120, -1);
+    scale_box.append(&scale_label);
+    let scale_adj = Adjustment::new(default_scale, MIN_EXPORT_SCALE, MAX_EXPORT_SCALE, 0.1, 1.0, 0.0);
+    let scale_spin = SpinButton::new(Some(&scale_adj), 0.1, 2);
+    scale_box.append(&scale_spin);
+    main_box.append(&scale_box);
+
+    // Keep DPI and scale in sync without feedback loops
+    let syncing = Rc::new(Cell::new(false));
+    {
+        let syncing = syncing.clone();
+        let scale_spin = scale_spin.clone();
+        dpi_spin.connect_value_changed(move |spin| {
+            if syncing.replace(true) {
+                return;
+            }
+            scale_spin.set_value(dpi_to_scale(spin.value()));
+            syncing.set(false);
+        });
+    }
+    {
+        let syncing = syncing.clone();
+        let dpi_spin = dpi_spin.clone();
+        scale_spin.connect_value_changed(move |spin| {
+            if syncing.replace(true) {
+                return;
+            }
+            dpi_spin.set_value(scale_to_dpi(spin.value()));
+            syncing.set(false);
+        });
+    }
+
+    // Button box
+    let button_box = GtkBox::new(Orientation::Horizontal, 6);
+    button_box.set_halign(Align::End);
+    button_box.set_homogeneous(true);
+    button_box.set_margin_top(12);
+
+    let cancel_btn = Button::with_label("キャンセル");
+    let dialog_ref = dialog.clone();
+    cancel_btn.connect_clicked(move |_| {
+        dialog_ref.close();
+    });
+    button_box.append(&cancel_btn);
+
+    let export_btn = Button::with_label("エクスポート");
+    export_btn.add_css_class("suggested-action");
+    let dialog_ref = dialog.clone();
+    export_btn.connect_clicked(move |_| {
+        let base = ExportConfig {
+            format,
+            ..Default::default()
+        };
+        match base.with_scale(scale_spin.value()) {
+            Ok(config) => {
+                dialog_ref.close();
+                on_confirmed(config);
+            }
+            Err(e) => {
+                tracing::warn!("⚠️ Invalid export scale: {}", e);
+            }
+        }
+    });
+    button_box.append(&export_btn);
+
+    main_box.append(&button_box);
+    dialog.set_child(Some(&main_box));
+    dialog.present();
+}
+
+/// Largest bleed offered in the PDF dialog, in millimeters
+const MAX_BLEED_MM: f64 = 20.0;
+
+/// Show the PDF export options dialog (crop marks and bleed)
+///
+/// `on_confirmed` is called with the chosen options when the user presses export.
+pub fn show_pdf_export_options(parent: &Window, on_confirmed: Box<dyn Fn(PdfExportOptions)>) {
+    let dialog = gtk4::ApplicationWindow::builder()
+        .transient_for(parent)
+        .modal(true)
+        .title("PDF エクスポート")
+        .default_width(360)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_start(16);
+    main_box.set_margin_end(16);
+    main_box.set_margin_top(16);
+    main_box.set_margin_bottom(16);
+
+    let title = Label::new(Some("PDF エクスポート設定"));
+    title.add_css_class("title-3");
+    title.set_halign(Align::Start);
+    main_box.append(&title);
+
+    let crop_marks_check = CheckButton::with_label("トンボ（トリムマーク）を付ける");
+    main_box.append(&crop_marks_check);
+
+    // Bleed input
+    let bleed_box = GtkBox::new(Orientation::Horizontal, 8);
+    let bleed_label = Label::new(Some("塗り足し (mm):"));
+    bleed_label.set_size_request(120, -1);
+    bleed_box.append(&bleed_label);
+    let bleed_adj = Adjustment::new(0.0, 0.0, MAX_BLEED_MM, 0.5, 1.0, 0.0);
+    let bleed_spin = SpinButton::new(Some(&bleed_adj), 0.5, 1);
+    bleed_box.append(&bleed_spin);
+    main_box.append(&bleed_box);
+
+    let hint = Label::new(Some("印刷所に入稿する場合は塗り足し 3mm が一般的です"));
+    hint.add_css_class("dim-label");
+    hint.set_halign(Align::Start);
+    main_box.append(&hint);
+
+    // Button box
+    let button_box = GtkBox::new(Orientation::Horizontal, 6);
+    button_box.set_halign(Align::End);
+    button_box.set_homogeneous(true);
+    button_box.set_margin_top(12);
+
+    let cancel_btn = Button::with_label("キャンセル");
+    let dialog_ref = dialog.clone();
+    cancel_btn.connect_clicked(move |_| {
+        dialog_ref.close();
+    });
+    button_box.append(&cancel_btn);
+
+    let export_btn = Button::with_label("エクスポート");
+    export_btn.add_css_class("suggested-action");
+    let dialog_ref = dialog.clone();
+    export_btn.connect_clicked(move |_| {
+        let options = PdfExportOptions {
+            crop_marks: crop_marks_check.is_active(),
+            bleed_mm: bleed_spin.value() as f32,
+        };
+        dialog_ref.close();
+        on_confirmed(options);
+    });
+    button_box.append(&export_btn);
+
+    main_box.append(&button_box);
+    dialog.set_child(Some(&main_box));
+    dialog.present();
+}
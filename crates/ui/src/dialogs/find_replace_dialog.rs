@@ -0,0 +1,221 @@
+//! Find and replace dialog
+//!
+//! Searches the text of every text element on every page. "Find next" steps
+//! through the matching elements, and "Replace all" rewrites every match as
+//! one undoable batch.
+
+use crate::app::AppState;
+use crate::undo_redo::AppReplaceTextCommand;
+use gtk4::prelude::*;
+use gtk4::{Align, Box as GtkBox, Button, CheckButton, Entry, Label, Orientation, Window};
+use std::cell::RefCell;
+use std::rc::Rc;
+use testruct_core::document::{SearchOptions, TextMatch};
+
+/// Matches of the current query, recomputed when the query or options change
+#[derive(Default)]
+struct SearchState {
+    matches: Option<Vec<TextMatch>>,
+    current: Option<usize>,
+}
+
+/// Show the find and replace dialog
+///
+/// The dialog is not modal so the canvas can be inspected between searches.
+/// `on_navigate` is called with each match found by "find next", and
+/// `on_replaced` with the number of replaced occurrences after "replace all".
+pub fn show_find_replace_dialog(
+    parent: &Window,
+    app_state: AppState,
+    on_navigate: Box<dyn Fn(TextMatch)>,
+    on_replaced: Box<dyn Fn(usize)>,
+) {
+    let dialog = gtk4::ApplicationWindow::builder()
+        .transient_for(parent)
+        .modal(false)
+        .title("検索と置換")
+        .default_width(380)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_start(16);
+    main_box.set_margin_end(16);
+    main_box.set_margin_top(16);
+    main_box.set_margin_bottom(16);
+
+    let title = Label::new(Some("テキストの検索と置換"));
+    title.add_css_class("title-3");
+    title.set_halign(Align::Start);
+    main_box.append(&title);
+
+    let find_entry = entry_row(&main_box, "検索:");
+    let replace_entry = entry_row(&main_box, "置換:");
+
+    let case_check = CheckButton::with_label("大文字と小文字を区別");
+    main_box.append(&case_check);
+    let word_check = CheckButton::with_label("単語単位");
+    main_box.append(&word_check);
+
+    let status_label = Label::new(None);
+    status_label.add_css_class("dim-label");
+    status_label.set_halign(Align::Start);
+    main_box.append(&status_label);
+
+    let search_state = Rc::new(RefCell::new(SearchState::default()));
+
+    // Any change to the query or options starts the search over
+    let invalidate = {
+        let search_state = search_state.clone();
+        let status_label = status_label.clone();
+        move || {
+            *search_state.borrow_mut() = SearchState::default();
+            status_label.set_text("");
+        }
+    };
+    let reset = invalidate.clone();
+    find_entry.connect_changed(move |_| reset());
+    let reset = invalidate.clone();
+    case_check.connect_toggled(move |_| reset());
+    let reset = invalidate.clone();
+    word_check.connect_toggled(move |_| reset());
+
+    let current_options = {
+        let case_check = case_check.clone();
+        let word_check = word_check.clone();
+        move || SearchOptions {
+            case_sensitive: case_check.is_active(),
+            whole_word: word_check.is_active(),
+        }
+    };
+
+    // Button box
+    let button_box = GtkBox::new(Orientation::Horizontal, 6);
+    button_box.set_halign(Align::End);
+    button_box.set_homogeneous(true);
+    button_box.set_margin_top(12);
+
+    let close_btn = Button::with_label("閉じる");
+    let dialog_ref = dialog.clone();
+    close_btn.connect_clicked(move |_| {
+        dialog_ref.close();
+    });
+    button_box.append(&close_btn);
+
+    let replace_all_btn = Button::with_label("すべて置換");
+    button_box.append(&replace_all_btn);
+
+    let find_next_btn = Button::with_label("次を検索");
+    find_next_btn.add_css_class("suggested-action");
+    button_box.append(&find_next_btn);
+
+    let state_ref = app_state.clone();
+    let find_ref = find_entry.clone();
+    let status_ref = status_label.clone();
+    let search_ref = search_state.clone();
+    let options = current_options.clone();
+    find_next_btn.connect_clicked(move |_| {
+        let query = find_ref.text();
+        if query.is_empty() {
+            status_ref.set_text("検索する文字列を入力してください");
+            return;
+        }
+
+        let next = {
+            let mut search = search_ref.borrow_mut();
+            let SearchState { matches, current } = &mut *search;
+            let matches = matches.get_or_insert_with(|| {
+                state_ref
+                    .with_active_document(|doc| doc.find_text(&query, options()))
+                    .unwrap_or_default()
+            });
+            if matches.is_empty() {
+                None
+            } else {
+                let index = current.map_or(0, |current| (current + 1) % matches.len());
+                let total: usize = matches.iter().map(|found| found.count).sum();
+                *current = Some(index);
+                Some((matches[index], index, matches.len(), total))
+            }
+        };
+
+        match next {
+            Some((found, index, elements, total)) => {
+                status_ref.set_text(&format!(
+                    "{} 件見つかりました（{} 個中 {} 個目の要素）",
+                    total,
+                    elements,
+                    index + 1
+                ));
+                on_navigate(found);
+            }
+            None => status_ref.set_text("見つかりませんでした"),
+        }
+    });
+
+    let find_ref = find_entry.clone();
+    let replace_ref = replace_entry.clone();
+    let status_ref = status_label.clone();
+    let search_ref = search_state.clone();
+    replace_all_btn.connect_clicked(move |_| {
+        let query = find_ref.text().to_string();
+        if query.is_empty() {
+            status_ref.set_text("検索する文字列を入力してください");
+            return;
+        }
+        let replacement = replace_ref.text().to_string();
+        let options = current_options();
+
+        let matches = app_state
+            .with_active_document(|doc| doc.find_text(&query, options))
+            .unwrap_or_default();
+        if matches.is_empty() {
+            status_ref.set_text("見つかりませんでした");
+            return;
+        }
+
+        // One batch so a single undo restores every element
+        app_state.with_undo_stack(|stack| stack.begin_batch("Replace All"));
+        for found in &matches {
+            let command = AppReplaceTextCommand::new(
+                app_state.clone(),
+                found.page_index,
+                found.element_id,
+                query.clone(),
+                replacement.clone(),
+                options,
+            );
+            app_state.push_command(Box::new(command));
+        }
+        app_state.with_undo_stack(|stack| stack.end_batch());
+
+        let total: usize = matches.iter().map(|found| found.count).sum();
+        *search_ref.borrow_mut() = SearchState::default();
+        status_ref.set_text(&format!("{} 件置換しました", total));
+        on_replaced(total);
+    });
+
+    // Enter in the search field finds the next match
+    let find_next_ref = find_next_btn.clone();
+    find_entry.connect_activate(move |_| {
+        find_next_ref.emit_clicked();
+    });
+
+    main_box.append(&button_box);
+    dialog.set_child(Some(&main_box));
+    dialog.present();
+    find_entry.grab_focus();
+}
+
+/// Append a labelled text entry
+fn entry_row(parent: &GtkBox, label: &str) -> Entry {
+    let row = GtkBox::new(Orientation::Horizontal, 8);
+    let label = Label::new(Some(label));
+    label.set_size_request(60, -1);
+    label.set_halign(Align::Start);
+    row.append(&label);
+    let entry = Entry::new();
+    entry.set_hexpand(true);
+    row.append(&entry);
+    parent.append(&row);
+    entry
+}
@@ -1,11 +1,20 @@
 //! JSON editor dialog for viewing and editing documents as JSON
 //!
 //! Allows users to view the current document structure in JSON format
-//! and make direct edits to the JSON representation.
+//! and make direct edits to the JSON representation. Edits are only applied
+//! when the JSON parses as a document and passes `Document::validate`;
+//! otherwise the problem is shown below the editor and, for parse errors,
+//! the offending line is highlighted.
 
 use crate::app::AppState;
 use gtk4::prelude::*;
-use gtk4::{Box as GtkBox, Button, Dialog, Label, Orientation, ScrolledWindow, TextView, Window};
+use gtk4::{
+    Box as GtkBox, Button, Dialog, Label, Orientation, ScrolledWindow, TextBuffer, TextView, Window,
+};
+use testruct_core::document::Document;
+
+/// Text tag marking the line of the last parse error
+const ERROR_TAG: &str = "json-error";
 
 /// Show the JSON editor dialog for the active document
 pub fn show_json_editor(parent: &Window, app_state: AppState) {
@@ -50,6 +59,23 @@ pub fn show_json_editor(parent: &Window, app_state: AppState) {
     scrolled.set_child(Some(&text_view));
     main_box.append(&scrolled);
 
+    text_view.buffer().create_tag(
+        Some(ERROR_TAG),
+        &[
+            ("background", &"rgba(224, 27, 36, 0.25)"),
+            ("underline", &gtk4::pango::Underline::Error),
+        ],
+    );
+
+    // Validation problems from the last save attempt
+    let error_label = Label::new(None);
+    error_label.add_css_class("error");
+    error_label.set_halign(gtk4::Align::Start);
+    error_label.set_wrap(true);
+    error_label.set_selectable(true);
+    error_label.set_visible(false);
+    main_box.append(&error_label);
+
     // Load current document as JSON
     if let Some(doc) = app_state.active_document() {
         if let Ok(json_string) = serde_json::to_string_pretty(&doc) {
@@ -76,39 +102,50 @@ pub fn show_json_editor(parent: &Window, app_state: AppState) {
     let app_state_save = app_state.clone();
     let dialog_save = dialog.clone();
     let text_view_save = text_view.clone();
+    let error_label_save = error_label.clone();
 
     save_btn.connect_clicked(move |_| {
         let buffer = text_view_save.buffer();
         let start_iter = buffer.start_iter();
         let end_iter = buffer.end_iter();
         let json_text = buffer.text(&start_iter, &end_iter, false).to_string();
+        buffer.remove_tag_by_name(ERROR_TAG, &start_iter, &end_iter);
 
-        // Try to parse JSON and update document
-        match serde_json::from_str::<testruct_core::document::Document>(&json_text) {
-            Ok(new_doc) => {
-                // Update the active document
-                app_state_save.with_active_document(|current_doc| {
-                    *current_doc = new_doc;
-                });
-                tracing::info!("✅ Document updated from JSON");
-                dialog_save.close();
-            }
+        let new_doc = match serde_json::from_str::<Document>(&json_text) {
+            Ok(new_doc) => new_doc,
             Err(e) => {
                 tracing::error!("❌ JSON parse error: {}", e);
-                // Show error in a dialog or status message
-                let error_dialog = gtk4::MessageDialog::new(
-                    Some(&dialog_save),
-                    gtk4::DialogFlags::MODAL,
-                    gtk4::MessageType::Error,
-                    gtk4::ButtonsType::Ok,
-                    "JSON エラー",
-                );
-                error_dialog.set_secondary_text(Some(&format!("パース エラー: {}", e)));
-                error_dialog.run_async(|dialog, _| {
-                    dialog.close();
-                });
+                highlight_error_line(&text_view_save, &buffer, e.line(), e.column());
+                error_label_save.set_text(&format!("パース エラー: {}", e));
+                error_label_save.set_visible(true);
+                return;
             }
+        };
+
+        // Warnings such as off-page elements are left for the preflight check
+        let issues: Vec<_> = new_doc
+            .validate()
+            .into_iter()
+            .filter(|issue| issue.is_error())
+            .collect();
+        if !issues.is_empty() {
+            tracing::error!("❌ Edited document has {} problem(s)", issues.len());
+            let lines: Vec<String> = issues.iter().map(|issue| format!("• {}", issue)).collect();
+            error_label_save.set_text(&format!(
+                "ドキュメントに問題があります:\n{}",
+                lines.join("\n")
+            ));
+            error_label_save.set_visible(true);
+            return;
         }
+
+        // Update the active document
+        app_state_save.with_active_document(|current_doc| {
+            *current_doc = new_doc;
+        });
+        app_state_save.mark_as_modified();
+        tracing::info!("✅ Document updated from JSON");
+        dialog_save.close();
     });
     button_box.append(&save_btn);
 
@@ -141,3 +178,26 @@ pub fn show_json_editor(parent: &Window, app_state: AppState) {
 
     dialog.present();
 }
+
+/// Highlight a 1-based `line` and put the cursor at byte `column` on it
+///
+/// serde_json reports column 0 for errors at the end of the input; the
+/// cursor then goes to the start of the line.
+fn highlight_error_line(text_view: &TextView, buffer: &TextBuffer, line: usize, column: usize) {
+    let line = line.saturating_sub(1) as i32;
+    let Some(line_start) = buffer.iter_at_line(line) else {
+        return;
+    };
+    let mut line_end = line_start;
+    if !line_end.ends_line() {
+        line_end.forward_to_line_end();
+    }
+    buffer.apply_tag_by_name(ERROR_TAG, &line_start, &line_end);
+
+    let mut cursor = buffer
+        .iter_at_line_index(line, column.saturating_sub(1) as i32)
+        .unwrap_or(line_start);
+    buffer.place_cursor(&cursor);
+    text_view.scroll_to_iter(&mut cursor, 0.1, false, 0.0, 0.0);
+    text_view.grab_focus();
+}
@@ -0,0 +1,244 @@
+//! Keyboard shortcut editor
+//!
+//! Lists every remappable action with its shortcut. Clicking a shortcut waits
+//! for a new key combination; combinations already used by another action are
+//! rejected. Saving persists the bindings and applies them immediately.
+
+use crate::app::key_bindings::DEFAULT_BINDINGS;
+use crate::app::{AppState, KeyBindings};
+use gtk4::prelude::*;
+use gtk4::{
+    gdk, glib, Align, Box as GtkBox, Button, EventControllerKey, Grid, Label, Orientation,
+    ScrolledWindow, Window,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Show the keyboard shortcut editor
+pub fn show_key_bindings_dialog(parent: &Window, app_state: AppState) {
+    let dialog = Window::builder()
+        .transient_for(parent)
+        .modal(true)
+        .title("キーボードショートカットの設定")
+        .default_width(480)
+        .default_height(600)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_start(16);
+    main_box.set_margin_end(16);
+    main_box.set_margin_top(16);
+    main_box.set_margin_bottom(16);
+
+    let hint = Label::new(Some(
+        "ショートカットをクリックして新しいキーを押してください。\n\
+         Esc で取り消し、BackSpace で割り当てを解除します。",
+    ));
+    hint.set_halign(Align::Start);
+    hint.add_css_class("dim-label");
+    main_box.append(&hint);
+
+    let bindings = Rc::new(RefCell::new(app_state.key_bindings()));
+    // Action waiting for a new key combination
+    let capturing: Rc<RefCell<Option<String>>> = Rc::new(RefCell::new(None));
+
+    let grid = Grid::new();
+    grid.set_row_spacing(4);
+    grid.set_column_spacing(12);
+    let mut shortcut_buttons = Vec::new();
+    for (row, (action, label, _)) in DEFAULT_BINDINGS.iter().enumerate() {
+        let name_label = Label::new(Some(label));
+        name_label.set_halign(Align::Start);
+        name_label.set_hexpand(true);
+        grid.attach(&name_label, 0, row as i32, 1, 1);
+
+        let shortcut_button = Button::new();
+        shortcut_button.set_width_request(160);
+        grid.attach(&shortcut_button, 1, row as i32, 1, 1);
+
+        let reset_button = Button::from_icon_name("edit-undo-symbolic");
+        reset_button.set_tooltip_text(Some("初期値に戻す"));
+        reset_button.add_css_class("flat");
+        grid.attach(&reset_button, 2, row as i32, 1, 1);
+
+        shortcut_buttons.push((action.to_string(), shortcut_button, reset_button));
+    }
+    let shortcut_buttons = Rc::new(shortcut_buttons);
+
+    let scrolled = ScrolledWindow::new();
+    scrolled.set_vexpand(true);
+    scrolled.set_child(Some(&grid));
+    main_box.append(&scrolled);
+
+    let status_label = Label::new(None);
+    status_label.set_halign(Align::Start);
+    status_label.set_wrap(true);
+    main_box.append(&status_label);
+
+    let refresh: Rc<dyn Fn()> = {
+        let bindings = bindings.clone();
+        let capturing = capturing.clone();
+        let shortcut_buttons = shortcut_buttons.clone();
+        let status_label = status_label.clone();
+        Rc::new(move || {
+            let bindings = bindings.borrow();
+            let capturing = capturing.borrow();
+            for (action, shortcut_button, reset_button) in shortcut_buttons.iter() {
+                if capturing.as_deref() == Some(action.as_str()) {
+                    shortcut_button.set_label("キーを押してください…");
+                } else {
+                    shortcut_button.set_label(&accelerator_label(bindings.accelerator(action)));
+                }
+                reset_button.set_sensitive(bindings.bindings.contains_key(action));
+            }
+            let conflicts = bindings.conflicts();
+            if conflicts.is_empty() {
+                status_label.set_text("");
+            } else {
+                let groups: Vec<String> = conflicts
+                    .iter()
+                    .map(|actions| {
+                        actions
+                            .iter()
+                            .map(|action| action_label(action))
+                            .collect::<Vec<_>>()
+                            .join("・")
+                    })
+                    .collect();
+                status_label.set_text(&format!(
+                    "⚠️ 同じショートカットが割り当てられています: {}",
+                    groups.join(" / ")
+                ));
+            }
+        })
+    };
+
+    for (action, shortcut_button, reset_button) in shortcut_buttons.iter() {
+        let capturing = capturing.clone();
+        let refresh_capture = refresh.clone();
+        let capture_action = action.clone();
+        shortcut_button.connect_clicked(move |_| {
+            *capturing.borrow_mut() = Some(capture_action.clone());
+            refresh_capture();
+        });
+
+        let bindings = bindings.clone();
+        let refresh_reset = refresh.clone();
+        let reset_action = action.clone();
+        reset_button.connect_clicked(move |_| {
+            bindings.borrow_mut().reset(&reset_action);
+            refresh_reset();
+        });
+    }
+
+    // Record the next key combination for the action being captured
+    let key_controller = EventControllerKey::new();
+    key_controller.set_propagation_phase(gtk4::PropagationPhase::Capture);
+    {
+        let bindings = bindings.clone();
+        let capturing = capturing.clone();
+        let refresh = refresh.clone();
+        let status_label = status_label.clone();
+        key_controller.connect_key_pressed(move |_, keyval, _keycode, modifiers| {
+            let Some(action) = capturing.borrow().clone() else {
+                return glib::Propagation::Proceed;
+            };
+            let modifiers = modifiers & gtk4::accelerator_get_default_mod_mask();
+
+            if keyval == gdk::Key::Escape && modifiers.is_empty() {
+                capturing.replace(None);
+                refresh();
+                return glib::Propagation::Stop;
+            }
+            if keyval == gdk::Key::BackSpace && modifiers.is_empty() {
+                bindings.borrow_mut().set(&action, "");
+                capturing.replace(None);
+                refresh();
+                return glib::Propagation::Stop;
+            }
+            // Wait for a non-modifier key
+            if !gtk4::accelerator_valid(keyval, modifiers) {
+                return glib::Propagation::Stop;
+            }
+
+            let accel = gtk4::accelerator_name(keyval, modifiers);
+            let conflicts = bindings.borrow().conflicts_with(&action, &accel);
+            capturing.replace(None);
+            if conflicts.is_empty() {
+                bindings.borrow_mut().set(&action, &accel);
+                refresh();
+            } else {
+                refresh();
+                let names: Vec<String> = conflicts.iter().map(|a| action_label(a)).collect();
+                status_label.set_text(&format!(
+                    "⚠️ {} は「{}」で使われています",
+                    gtk4::accelerator_get_label(keyval, modifiers),
+                    names.join("・")
+                ));
+            }
+            glib::Propagation::Stop
+        });
+    }
+    dialog.add_controller(key_controller);
+
+    // Button box
+    let button_box = GtkBox::new(Orientation::Horizontal, 8);
+    let reset_all_btn = Button::with_label("すべて初期値に戻す");
+    let spacer = GtkBox::new(Orientation::Horizontal, 0);
+    spacer.set_hexpand(true);
+    let cancel_btn = Button::with_label("キャンセル");
+    let save_btn = Button::with_label("保存");
+    save_btn.add_css_class("suggested-action");
+    button_box.append(&reset_all_btn);
+    button_box.append(&spacer);
+    button_box.append(&cancel_btn);
+    button_box.append(&save_btn);
+    main_box.append(&button_box);
+
+    {
+        let bindings = bindings.clone();
+        let capturing = capturing.clone();
+        let refresh = refresh.clone();
+        reset_all_btn.connect_clicked(move |_| {
+            *bindings.borrow_mut() = KeyBindings::new();
+            capturing.replace(None);
+            refresh();
+        });
+    }
+
+    let dialog_cancel = dialog.clone();
+    cancel_btn.connect_clicked(move |_| dialog_cancel.close());
+
+    let dialog_save = dialog.clone();
+    let parent = parent.clone();
+    save_btn.connect_clicked(move |_| {
+        let bindings = bindings.borrow().clone();
+        if let Some(app) = parent.application() {
+            bindings.apply(&app);
+        }
+        app_state.set_key_bindings(bindings);
+        tracing::info!("✅ Key bindings saved");
+        dialog_save.close();
+    });
+
+    refresh();
+    dialog.set_child(Some(&main_box));
+    dialog.present();
+}
+
+/// Display form of an accelerator ("なし" when unbound)
+fn accelerator_label(accel: Option<&str>) -> String {
+    accel
+        .and_then(gtk4::accelerator_parse)
+        .map(|(key, modifiers)| gtk4::accelerator_get_label(key, modifiers).to_string())
+        .unwrap_or_else(|| "なし".to_string())
+}
+
+/// Label shown for `action` in the editor
+fn action_label(action: &str) -> String {
+    DEFAULT_BINDINGS
+        .iter()
+        .find(|(name, _, _)| *name == action)
+        .map(|(_, label, _)| label.to_string())
+        .unwrap_or_else(|| action.to_string())
+}
@@ -0,0 +1,101 @@
+//! Document properties dialog
+//!
+//! Edits the title, author, subject and keywords of the active document.
+//! They are saved with the document and written to the PDF document info
+//! on export.
+
+use crate::app::AppState;
+use gtk4::prelude::*;
+use gtk4::{Align, Box as GtkBox, Button, Entry, Grid, Label, Orientation, Window};
+
+/// Show the document properties dialog for the active document
+pub fn show_metadata_dialog(parent: &Window, app_state: AppState) {
+    let Some(metadata) = app_state.active_document().map(|doc| doc.metadata) else {
+        tracing::warn!("⚠️ No active document for the properties dialog");
+        return;
+    };
+
+    let dialog = Window::builder()
+        .transient_for(parent)
+        .modal(true)
+        .title("ドキュメントのプロパティ")
+        .default_width(420)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_start(16);
+    main_box.set_margin_end(16);
+    main_box.set_margin_top(16);
+    main_box.set_margin_bottom(16);
+
+    let grid = Grid::new();
+    grid.set_row_spacing(8);
+    grid.set_column_spacing(12);
+    let title_entry = entry_row(&grid, 0, "タイトル:", &metadata.title);
+    let author_entry = entry_row(&grid, 1, "作成者:", &metadata.author);
+    let subject_entry = entry_row(&grid, 2, "件名:", &metadata.subject);
+    let keywords_entry = entry_row(&grid, 3, "キーワード:", &metadata.keywords_text());
+    keywords_entry.set_placeholder_text(Some("カンマ区切り（例: 数学, 期末試験）"));
+    main_box.append(&grid);
+
+    let hint = Label::new(Some("PDF に書き出すと文書情報として埋め込まれます"));
+    hint.add_css_class("dim-label");
+    hint.set_halign(Align::Start);
+    main_box.append(&hint);
+
+    // Button box
+    let button_box = GtkBox::new(Orientation::Horizontal, 6);
+    button_box.set_halign(Align::End);
+    button_box.set_margin_top(12);
+
+    let cancel_btn = Button::with_label("キャンセル");
+    let dialog_cancel = dialog.clone();
+    cancel_btn.connect_clicked(move |_| dialog_cancel.close());
+    button_box.append(&cancel_btn);
+
+    let save_btn = Button::with_label("OK");
+    save_btn.add_css_class("suggested-action");
+    let dialog_save = dialog.clone();
+    save_btn.connect_clicked(move |_| {
+        let title = title_entry.text().trim().to_string();
+        let author = author_entry.text().trim().to_string();
+        let subject = subject_entry.text().trim().to_string();
+        let keywords = keywords_entry.text();
+        let changed = app_state
+            .with_active_document(|doc| {
+                let before = doc.metadata.clone();
+                let metadata = &mut doc.metadata;
+                metadata.title = title;
+                metadata.author = author;
+                metadata.subject = subject;
+                metadata.set_keywords_text(&keywords);
+                metadata.title != before.title
+                    || metadata.author != before.author
+                    || metadata.subject != before.subject
+                    || metadata.keywords != before.keywords
+            })
+            .unwrap_or(false);
+        if changed {
+            app_state.mark_as_modified();
+            tracing::info!("✅ Document properties updated");
+        }
+        dialog_save.close();
+    });
+    button_box.append(&save_btn);
+    main_box.append(&button_box);
+
+    dialog.set_child(Some(&main_box));
+    dialog.present();
+}
+
+/// Add a labelled entry holding `value` to row `row` of `grid`
+fn entry_row(grid: &Grid, row: i32, label: &str, value: &str) -> Entry {
+    let label = Label::new(Some(label));
+    label.set_halign(Align::Start);
+    grid.attach(&label, 0, row, 1, 1);
+    let entry = Entry::new();
+    entry.set_text(value);
+    entry.set_hexpand(true);
+    grid.attach(&entry, 1, row, 1, 1);
+    entry
+}
@@ -5,23 +5,43 @@
 #![allow(deprecated)]
 
 pub mod about_dialog;
+pub mod export_options;
+pub mod find_replace_dialog;
 pub mod image_dialog;
 pub mod item_dialog;
 pub mod json_editor;
+pub mod key_bindings_dialog;
+pub mod metadata_dialog;
+pub mod preflight_dialog;
 mod project_settings;
+pub mod qr_code_dialog;
 pub mod recent_files_dialog;
+pub mod recovery_dialog;
+pub mod save_template_dialog;
 pub mod shortcuts_dialog;
+pub mod table_dialog;
 pub mod template_browser;
+pub mod undo_history_dialog;
 pub mod unsaved_dialog;
 pub mod user_manual_dialog;
 
 pub use about_dialog::{get_app_name, get_version, show_about_dialog, APP_NAME, APP_VERSION};
+pub use export_options::{show_image_export_options, show_pdf_export_options};
+pub use find_replace_dialog::show_find_replace_dialog;
 pub use image_dialog::{show_image_chooser, show_image_chooser_async};
 pub use item_dialog::{create_new_item, delete_item};
 pub use json_editor::show_json_editor;
+pub use key_bindings_dialog::show_key_bindings_dialog;
+pub use metadata_dialog::show_metadata_dialog;
+pub use preflight_dialog::show_preflight_dialog;
 pub use project_settings::show_project_settings;
+pub use qr_code_dialog::show_qr_code_dialog;
 pub use recent_files_dialog::show_recent_files_dialog;
+pub use recovery_dialog::offer_crash_recovery;
+pub use save_template_dialog::show_save_template_dialog;
 pub use shortcuts_dialog::show_shortcuts_dialog;
-pub use template_browser::show_template_browser_async;
+pub use table_dialog::show_insert_table_dialog;
+pub use template_browser::{show_template_browser_async, TemplateSelection};
+pub use undo_history_dialog::show_undo_history_dialog;
 pub use unsaved_dialog::{check_unsaved_changes, show_unsaved_dialog, UnsavedDialogResponse};
 pub use user_manual_dialog::show_user_manual_dialog;
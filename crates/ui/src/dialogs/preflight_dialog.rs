@@ -0,0 +1,218 @@
+//! Preflight dialog
+//!
+//! Lists the problems found in the active document before it is exported:
+//! duplicate ids, invalid or zero sizes, elements past the page edge and
+//! images whose asset is missing. Double-clicking an issue selects the
+//! offending element and shows its page. Before an export it also offers
+//! to go ahead regardless.
+
+use crate::app::AppState;
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Box as GtkBox, Button, Image, Label, ListBox, ListBoxRow, Orientation, ScrolledWindow,
+    Window,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use testruct_core::document::{Document, ValidationIssue};
+use testruct_core::workspace::assets::AssetCatalog;
+
+/// Every issue of `document`, including missing image assets, by page
+pub fn preflight_issues(document: &Document, catalog: &AssetCatalog) -> Vec<ValidationIssue> {
+    let mut issues = document.validate();
+    issues.extend(document.validate_assets(catalog));
+    issues.sort_by_key(ValidationIssue::page);
+    issues
+}
+
+/// Issues of the active document, or an empty list without one
+pub fn active_document_issues(app_state: &AppState) -> Vec<ValidationIssue> {
+    let Some(document) = app_state.active_document() else {
+        return Vec::new();
+    };
+    let catalog = app_state.asset_catalog();
+    let catalog = catalog.lock().expect("Failed to lock asset catalog");
+    preflight_issues(&document, &catalog)
+}
+
+/// Show the preflight dialog for the active document
+///
+/// The dialog is not modal so fixes can be made while it is open.
+/// `on_navigate` is called with the 0-based page index and the element id
+/// of a double-clicked issue. With `on_export`, the dialog gets a button
+/// that closes it and runs the pending export anyway.
+pub fn show_preflight_dialog(
+    parent: &Window,
+    app_state: AppState,
+    on_navigate: Box<dyn Fn(usize, uuid::Uuid)>,
+    on_export: Option<Box<dyn Fn()>>,
+) {
+    let dialog = gtk4::ApplicationWindow::builder()
+        .transient_for(parent)
+        .modal(false)
+        .title("プリフライト")
+        .default_width(480)
+        .default_height(360)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 8);
+    main_box.set_margin_start(12);
+    main_box.set_margin_end(12);
+    main_box.set_margin_top(12);
+    main_box.set_margin_bottom(12);
+
+    let summary_label = Label::new(None);
+    summary_label.add_css_class("title-4");
+    summary_label.set_halign(Align::Start);
+    main_box.append(&summary_label);
+
+    let hint = Label::new(Some("ダブルクリックで該当するオブジェクトを選択します"));
+    hint.add_css_class("dim-label");
+    hint.set_halign(Align::Start);
+    main_box.append(&hint);
+
+    let scrolled = ScrolledWindow::new();
+    scrolled.set_vexpand(true);
+    scrolled.set_hexpand(true);
+    let list_box = ListBox::new();
+    list_box.set_selection_mode(gtk4::SelectionMode::Single);
+    list_box.set_activate_on_single_click(false);
+    scrolled.set_child(Some(&list_box));
+    main_box.append(&scrolled);
+
+    let issues: Rc<RefCell<Vec<ValidationIssue>>> = Rc::new(RefCell::new(Vec::new()));
+    let refresh = {
+        let app_state = app_state.clone();
+        let issues = issues.clone();
+        let list_box = list_box.clone();
+        let summary_label = summary_label.clone();
+        move || {
+            let found = active_document_issues(&app_state);
+            fill_issue_list(&list_box, &found);
+            summary_label.set_text(&summary_text(&found));
+            *issues.borrow_mut() = found;
+        }
+    };
+    refresh();
+
+    let row_issues = issues.clone();
+    list_box.connect_row_activated(move |_, row| {
+        let issue = usize::try_from(row.index())
+            .ok()
+            .and_then(|index| row_issues.borrow().get(index).cloned());
+        if let Some(issue) = issue {
+            on_navigate(issue.page() - 1, issue.element_id());
+        }
+    });
+
+    // Button box
+    let button_box = GtkBox::new(Orientation::Horizontal, 8);
+    button_box.set_halign(Align::End);
+
+    let recheck_btn = Button::with_label("再チェック");
+    recheck_btn.connect_clicked(move |_| refresh());
+    button_box.append(&recheck_btn);
+
+    let close_btn = Button::with_label("閉じる");
+    let dialog_close = dialog.clone();
+    close_btn.connect_clicked(move |_| dialog_close.close());
+    button_box.append(&close_btn);
+
+    if let Some(on_export) = on_export {
+        let export_btn = Button::with_label("このまま書き出す");
+        export_btn.add_css_class("suggested-action");
+        let dialog_export = dialog.clone();
+        export_btn.connect_clicked(move |_| {
+            dialog_export.close();
+            on_export();
+        });
+        button_box.append(&export_btn);
+    }
+    main_box.append(&button_box);
+
+    dialog.set_child(Some(&main_box));
+    dialog.present();
+}
+
+/// Replace the rows of `list_box` with one row per issue
+fn fill_issue_list(list_box: &ListBox, issues: &[ValidationIssue]) {
+    while let Some(child) = list_box.first_child() {
+        list_box.remove(&child);
+    }
+
+    for issue in issues {
+        let row_box = GtkBox::new(Orientation::Horizontal, 8);
+        row_box.set_margin_start(8);
+        row_box.set_margin_end(8);
+        row_box.set_margin_top(4);
+        row_box.set_margin_bottom(4);
+
+        let icon = if issue.is_error() {
+            "dialog-error-symbolic"
+        } else {
+            "dialog-warning-symbolic"
+        };
+        row_box.append(&Image::from_icon_name(icon));
+
+        let text = Label::new(Some(&format!(
+            "ページ {}: {}",
+            issue.page(),
+            issue_text(issue)
+        )));
+        text.set_halign(Align::Start);
+        text.set_wrap(true);
+        row_box.append(&text);
+
+        let row = ListBoxRow::new();
+        row.set_child(Some(&row_box));
+        list_box.append(&row);
+    }
+}
+
+/// Error and warning counts, or a note that nothing was found
+fn summary_text(issues: &[ValidationIssue]) -> String {
+    if issues.is_empty() {
+        return "問題は見つかりませんでした".to_string();
+    }
+    let errors = issues.iter().filter(|issue| issue.is_error()).count();
+    format!("エラー {} 件、警告 {} 件", errors, issues.len() - errors)
+}
+
+/// Description of one issue for the list
+fn issue_text(issue: &ValidationIssue) -> String {
+    match issue {
+        ValidationIssue::DuplicateElementId { .. } => "ID が重複しています".to_string(),
+        ValidationIssue::InvalidSize { width, height, .. } => {
+            format!("サイズが不正です ({} × {})", width, height)
+        }
+        ValidationIssue::InvalidTableCells {
+            rows,
+            columns,
+            cells,
+            ..
+        } => format!("{} × {} の表にセルが {} 個あります", rows, columns, cells),
+        ValidationIssue::InvalidQrCode { reason, .. } => {
+            format!("QR コードを生成できません: {}", reason)
+        }
+        ValidationIssue::ZeroSize { .. } => "サイズが 0 です".to_string(),
+        ValidationIssue::OutsidePage { .. } => "ページの外にはみ出しています".to_string(),
+        ValidationIssue::MissingAsset { .. } => "画像ファイルが見つかりません".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_counts_errors_and_warnings() {
+        let id = uuid::Uuid::new_v4();
+        let issues = [
+            ValidationIssue::DuplicateElementId { page: 1, id },
+            ValidationIssue::OutsidePage { page: 2, id },
+            ValidationIssue::MissingAsset { page: 2, id },
+        ];
+        assert_eq!(summary_text(&issues), "エラー 1 件、警告 2 件");
+        assert_eq!(summary_text(&[]), "問題は見つかりませんでした");
+    }
+}
@@ -1,11 +1,21 @@
 use crate::app::AppState;
+use crate::canvas::CanvasRenderState;
+use crate::panels::property_handlers::color_to_hex;
 use gtk4::Align;
 use gtk4::{
-    prelude::*, Adjustment, Box as GtkBox, Button, CheckButton, Label, Orientation, SpinButton,
-    Window,
+    prelude::*, Adjustment, Box as GtkBox, Button, CheckButton, ColorDialog, DropDown, Entry,
+    Label, Orientation, SpinButton, Window,
 };
+use std::cell::Cell;
+use std::rc::Rc;
+use testruct_core::document::{ImageFit, PageBackground};
+use testruct_core::typography::Color;
+use testruct_core::workspace::assets::AssetRef;
 
-pub fn show_project_settings(parent: &Window, app_state: AppState) {
+/// Fit modes in the order of the background fit dropdown
+const BACKGROUND_FITS: [ImageFit; 3] = [ImageFit::Stretch, ImageFit::Contain, ImageFit::Cover];
+
+pub fn show_project_settings(parent: &Window, app_state: AppState, render_state: CanvasRenderState) {
     // Create dialog window for project settings
     let dialog = gtk4::ApplicationWindow::builder()
         .transient_for(parent)
@@ -105,6 +115,144 @@ pub fn show_project_settings(parent: &Window, app_state: AppState) {
     snap_dist_box.append(&snap_spin);
     main_box.append(&snap_dist_box);
 
+    // Handle hit tolerance setting (screen pixels, independent of zoom)
+    let handle_tol_box = GtkBox::new(Orientation::Horizontal, 8);
+    handle_tol_box.set_homogeneous(false);
+    let handle_tol_label = Label::new(Some("ハンドル判定範囲 (px):"));
+    handle_tol_label.set_size_request(150, -1);
+    handle_tol_box.append(&handle_tol_label);
+    let current_handle_tol = app_state.project().settings.handle_tolerance as f64;
+    let handle_tol_adj = Adjustment::new(current_handle_tol, 2.0, 32.0, 1.0, 4.0, 0.0);
+    let handle_tol_spin = SpinButton::new(Some(&handle_tol_adj), 1.0, 0);
+    handle_tol_box.append(&handle_tol_spin);
+    main_box.append(&handle_tol_box);
+
+    // Page Settings Section
+    let page_label = Label::new(Some("ページ設定"));
+    page_label.add_css_class("title-3");
+    page_label.set_halign(Align::Start);
+    page_label.set_margin_top(12);
+    main_box.append(&page_label);
+
+    // Default background for new pages: none, a color or an image
+    let current_background = app_state.project().settings.default_page_background;
+    let bg_color = Rc::new(Cell::new(match &current_background {
+        PageBackground::Color(color) => *color,
+        _ => Color::from_rgb(1.0, 1.0, 1.0),
+    }));
+    let bg_image: Rc<Cell<Option<AssetRef>>> = Rc::new(Cell::new(current_background.asset()));
+
+    let bg_box = GtkBox::new(Orientation::Horizontal, 8);
+    bg_box.set_homogeneous(false);
+    let bg_label = Label::new(Some("新規ページの背景:"));
+    bg_label.set_size_request(150, -1);
+    bg_box.append(&bg_label);
+    let bg_kind = DropDown::from_strings(&["なし", "色", "画像"]);
+    bg_kind.set_selected(match current_background {
+        PageBackground::None => 0,
+        PageBackground::Color(_) => 1,
+        PageBackground::Image { .. } => 2,
+    });
+    bg_box.append(&bg_kind);
+    main_box.append(&bg_box);
+
+    let bg_options_box = GtkBox::new(Orientation::Horizontal, 8);
+    bg_options_box.set_margin_start(158);
+    let bg_color_btn = Button::with_label(&color_to_hex(&bg_color.get()));
+    bg_color_btn.set_tooltip_text(Some("背景色を選択"));
+    bg_options_box.append(&bg_color_btn);
+    let bg_image_btn = Button::with_label(if bg_image.get().is_some() {
+        "画像を変更..."
+    } else {
+        "画像を選択..."
+    });
+    bg_options_box.append(&bg_image_btn);
+    let bg_fit = DropDown::from_strings(&["引き伸ばし", "全体を表示", "トリミング"]);
+    if let PageBackground::Image { fit, .. } = current_background {
+        let index = BACKGROUND_FITS.iter().position(|f| *f == fit).unwrap_or(0);
+        bg_fit.set_selected(index as u32);
+    }
+    bg_options_box.append(&bg_fit);
+    main_box.append(&bg_options_box);
+
+    // Only the controls for the chosen kind are editable
+    let update_bg_sensitivity = {
+        let bg_color_btn = bg_color_btn.clone();
+        let bg_image_btn = bg_image_btn.clone();
+        let bg_fit = bg_fit.clone();
+        move |kind: u32| {
+            bg_color_btn.set_sensitive(kind == 1);
+            bg_image_btn.set_sensitive(kind == 2);
+            bg_fit.set_sensitive(kind == 2);
+        }
+    };
+    update_bg_sensitivity(bg_kind.selected());
+    bg_kind.connect_selected_notify(move |dropdown| update_bg_sensitivity(dropdown.selected()));
+
+    let bg_color_for_btn = bg_color.clone();
+    let parent_for_color = parent.clone();
+    bg_color_btn.connect_clicked(move |button| {
+        let dialog = ColorDialog::builder()
+            .modal(true)
+            .title("背景色を選択")
+            .with_alpha(false)
+            .build();
+        let current = bg_color_for_btn.get();
+        let initial = gtk4::gdk::RGBA::new(current.r, current.g, current.b, current.a);
+        let bg_color = bg_color_for_btn.clone();
+        let button = button.clone();
+        dialog.choose_rgba(
+            Some(&parent_for_color),
+            Some(&initial),
+            None::<&gtk4::gio::Cancellable>,
+            move |result| {
+                if let Ok(rgba) = result {
+                    let color = Color::from_rgb(rgba.red(), rgba.green(), rgba.blue());
+                    bg_color.set(color);
+                    button.set_label(&color_to_hex(&color));
+                }
+            },
+        );
+    });
+
+    let bg_image_for_btn = bg_image.clone();
+    let parent_for_image = parent.clone();
+    let app_state_image = app_state.clone();
+    bg_image_btn.connect_clicked(move |button| {
+        let bg_image = bg_image_for_btn.clone();
+        let app_state = app_state_image.clone();
+        let button = button.clone();
+        crate::dialogs::show_image_chooser_async(
+            &parent_for_image,
+            Box::new(move |path| {
+                let asset_ref = app_state
+                    .asset_catalog()
+                    .lock()
+                    .expect("asset catalog")
+                    .register(&path);
+                bg_image.set(Some(asset_ref));
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| "画像".to_string());
+                button.set_label(&name);
+            }),
+        );
+    });
+
+    // Margin kept by the align-to-page actions
+    let edge_margin_box = GtkBox::new(Orientation::Horizontal, 8);
+    edge_margin_box.set_homogeneous(false);
+    let edge_margin_label = Label::new(Some("ページ端の余白 (pt):"));
+    edge_margin_label.set_size_request(150, -1);
+    edge_margin_label.set_tooltip_text(Some("ページの端に揃えるときに空ける距離"));
+    edge_margin_box.append(&edge_margin_label);
+    let current_edge_margin = app_state.project().settings.page_edge_margin as f64;
+    let edge_margin_adj = Adjustment::new(current_edge_margin, 0.0, 200.0, 1.0, 10.0, 0.0);
+    let edge_margin_spin = SpinButton::new(Some(&edge_margin_adj), 1.0, 0);
+    edge_margin_box.append(&edge_margin_spin);
+    main_box.append(&edge_margin_box);
+
     // Autosave Settings Section
     let autosave_label = Label::new(Some("自動保存設定"));
     autosave_label.add_css_class("title-3");
@@ -129,6 +277,72 @@ pub fn show_project_settings(parent: &Window, app_state: AppState) {
     autosave_box.append(&autosave_spin);
     main_box.append(&autosave_box);
 
+    // Crash recovery snapshot interval (0 disables snapshots)
+    let recovery_box = GtkBox::new(Orientation::Horizontal, 8);
+    recovery_box.set_homogeneous(false);
+    let recovery_label = Label::new(Some("復旧データ保存間隔 (秒):"));
+    recovery_label.set_size_request(150, -1);
+    recovery_label.set_tooltip_text(Some(
+        "未保存の変更をクラッシュ復旧用に保存する間隔（0 で無効）",
+    ));
+    recovery_box.append(&recovery_label);
+    let current_recovery = app_state.project().settings.recovery_interval_secs as f64;
+    let recovery_adj = Adjustment::new(current_recovery, 0.0, 3600.0, 10.0, 60.0, 0.0);
+    let recovery_spin = SpinButton::new(Some(&recovery_adj), 1.0, 0);
+    recovery_box.append(&recovery_spin);
+    main_box.append(&recovery_box);
+
+    // Undo Settings Section
+    let undo_label = Label::new(Some("取り消し設定"));
+    undo_label.add_css_class("title-3");
+    undo_label.set_halign(Align::Start);
+    undo_label.set_margin_top(12);
+    main_box.append(&undo_label);
+
+    // Number of commands kept; the oldest is dropped past this
+    let history_box = GtkBox::new(Orientation::Horizontal, 8);
+    history_box.set_homogeneous(false);
+    let history_label = Label::new(Some("取り消し履歴の上限:"));
+    history_label.set_size_request(150, -1);
+    history_label.set_tooltip_text(Some("保存しておく操作の数。多いほどメモリを使います"));
+    history_box.append(&history_label);
+    let current_history = app_state.project().settings.max_history as f64;
+    let history_adj = Adjustment::new(current_history, 10.0, 1000.0, 10.0, 100.0, 0.0);
+    let history_spin = SpinButton::new(Some(&history_adj), 1.0, 0);
+    history_box.append(&history_spin);
+    main_box.append(&history_box);
+
+    // Spelling Settings Section
+    let spelling_label = Label::new(Some("スペルチェック"));
+    spelling_label.add_css_class("title-3");
+    spelling_label.set_halign(Align::Start);
+    spelling_label.set_margin_top(12);
+    main_box.append(&spelling_label);
+
+    let current_language = app_state.project().settings.spell_check_language;
+    let spell_check = CheckButton::with_label("編集中のテキストの誤字に下線を表示");
+    spell_check.set_active(current_language.is_some());
+    main_box.append(&spell_check);
+
+    // Hunspell dictionary name; only Latin-script words are checked
+    let language_box = GtkBox::new(Orientation::Horizontal, 8);
+    language_box.set_homogeneous(false);
+    let language_label = Label::new(Some("辞書の言語:"));
+    language_label.set_size_request(150, -1);
+    language_label.set_tooltip_text(Some(
+        "Hunspell 辞書の名前（例: en_US）。日本語・中国語のテキストはチェックしません",
+    ));
+    language_box.append(&language_label);
+    let language_entry = Entry::new();
+    language_entry.set_text(current_language.as_deref().unwrap_or("en_US"));
+    language_entry.set_sensitive(spell_check.is_active());
+    language_box.append(&language_entry);
+    main_box.append(&language_box);
+    let language_entry_toggle = language_entry.clone();
+    spell_check.connect_toggled(move |check| {
+        language_entry_toggle.set_sensitive(check.is_active());
+    });
+
     // Add scrolled window for better layout with many settings
     let scrolled = gtk4::ScrolledWindow::new();
     scrolled.set_child(Some(&main_box));
@@ -151,8 +365,23 @@ pub fn show_project_settings(parent: &Window, app_state: AppState) {
         let new_snap_grid = snap_grid_check.is_active();
         let new_snap_guides = snap_guides_check.is_active();
         let new_snap_dist = snap_spin.value() as f32;
+        let new_handle_tol = handle_tol_spin.value() as f32;
+        let new_edge_margin = edge_margin_spin.value() as f32;
         let new_autosave_enabled = autosave_check.is_active();
         let new_autosave_minutes = autosave_spin.value() as u32;
+        let new_recovery_interval = recovery_spin.value() as u32;
+        let new_max_history = history_spin.value() as usize;
+        let language = language_entry.text().trim().to_string();
+        let new_spell_check_language =
+            (spell_check.is_active() && !language.is_empty()).then_some(language);
+        let new_background = match (bg_kind.selected(), bg_image.get()) {
+            (1, _) => PageBackground::Color(bg_color.get()),
+            (2, Some(source)) => PageBackground::Image {
+                source,
+                fit: BACKGROUND_FITS[bg_fit.selected() as usize % BACKGROUND_FITS.len()],
+            },
+            _ => PageBackground::None,
+        };
 
         // Update project settings using with_project for mutable access
         app_state_save.with_project(|project| {
@@ -162,14 +391,29 @@ pub fn show_project_settings(parent: &Window, app_state: AppState) {
             project.settings.snap_to_grid = new_snap_grid;
             project.settings.snap_to_guides = new_snap_guides;
             project.settings.snap_distance = new_snap_dist;
+            project.settings.handle_tolerance = new_handle_tol;
             project.settings.autosave_enabled = new_autosave_enabled;
             project.settings.autosave_minutes = new_autosave_minutes;
+            project.settings.recovery_interval_secs = new_recovery_interval;
+            project.settings.max_history = new_max_history;
+            project.settings.default_page_background = new_background;
+            project.settings.page_edge_margin = new_edge_margin;
+            project.settings.spell_check_language = new_spell_check_language;
             tracing::info!(
-                "✅ Project settings saved: width={}, height={}, grid={}, snap_grid={}, snap_guides={}, snap_dist={}, autosave={}, interval={}",
-                new_width, new_height, new_grid_size, new_snap_grid, new_snap_guides, new_snap_dist, new_autosave_enabled, new_autosave_minutes
+                "✅ Project settings saved: width={}, height={}, grid={}, snap_grid={}, snap_guides={}, snap_dist={}, handle_tol={}, autosave={}, interval={}",
+                new_width, new_height, new_grid_size, new_snap_grid, new_snap_guides, new_snap_dist, new_handle_tol, new_autosave_enabled, new_autosave_minutes
             );
         });
 
+        // Push snapping/hit-testing values to the canvas so they take effect immediately
+        let settings = app_state_save.project().settings;
+        render_state.config.borrow_mut().apply_project_settings(&settings);
+        render_state
+            .spell_check
+            .borrow_mut()
+            .set_language(settings.spell_check_language.as_deref());
+        app_state_save.with_undo_stack(|stack| stack.set_max_history(settings.max_history));
+
         dialog_ref.close();
     });
     button_box.append(&save_btn);
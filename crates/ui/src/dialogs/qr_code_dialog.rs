@@ -0,0 +1,152 @@
+//! QR code dialog
+//!
+//! Sets the payload and error-correction level of a new or existing QR code.
+//! The symbol size is previewed while typing, and payloads that do not fit
+//! in a QR code cannot be confirmed.
+
+use gtk4::prelude::*;
+use gtk4::{Align, Box as GtkBox, Button, DropDown, Entry, Label, Orientation, Window};
+use testruct_core::document::{QrCodeElement, QrErrorCorrection, QrMatrix};
+
+/// Show the QR code dialog
+///
+/// `current` pre-fills the dialog when editing an existing QR code.
+/// `on_confirmed` is called with the payload and level when the user confirms.
+pub fn show_qr_code_dialog(
+    parent: &Window,
+    current: Option<&QrCodeElement>,
+    on_confirmed: Box<dyn Fn(String, QrErrorCorrection)>,
+) {
+    let dialog = gtk4::ApplicationWindow::builder()
+        .transient_for(parent)
+        .modal(true)
+        .title("QR コード")
+        .default_width(380)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_start(16);
+    main_box.set_margin_end(16);
+    main_box.set_margin_top(16);
+    main_box.set_margin_bottom(16);
+
+    let title = Label::new(Some("QR コードの内容"));
+    title.add_css_class("title-3");
+    title.set_halign(Align::Start);
+    main_box.append(&title);
+
+    let payload_entry = Entry::new();
+    payload_entry.set_placeholder_text(Some("例: 受験番号 2026-0042"));
+    payload_entry.set_hexpand(true);
+    main_box.append(&payload_entry);
+
+    let level_row = GtkBox::new(Orientation::Horizontal, 8);
+    let level_label = Label::new(Some("誤り訂正レベル:"));
+    level_label.set_halign(Align::Start);
+    level_row.append(&level_label);
+    let level_dropdown =
+        DropDown::from_strings(&["L（約 7%）", "M（約 15%）", "Q（約 25%）", "H（約 30%）"]);
+    level_row.append(&level_dropdown);
+    main_box.append(&level_row);
+
+    let status_label = Label::new(None);
+    status_label.add_css_class("dim-label");
+    status_label.set_halign(Align::Start);
+    status_label.set_wrap(true);
+    main_box.append(&status_label);
+
+    let (payload, level) = current
+        .map(|qr| (qr.payload.clone(), qr.error_correction))
+        .unwrap_or_default();
+    payload_entry.set_text(&payload);
+    let level_index = QrErrorCorrection::ALL
+        .iter()
+        .position(|candidate| *candidate == level)
+        .unwrap_or(1);
+    level_dropdown.set_selected(level_index as u32);
+
+    // Button box
+    let button_box = GtkBox::new(Orientation::Horizontal, 6);
+    button_box.set_halign(Align::End);
+    button_box.set_homogeneous(true);
+    button_box.set_margin_top(12);
+
+    let cancel_btn = Button::with_label("キャンセル");
+    let dialog_ref = dialog.clone();
+    cancel_btn.connect_clicked(move |_| {
+        dialog_ref.close();
+    });
+    button_box.append(&cancel_btn);
+
+    let confirm_btn = Button::with_label(if current.is_some() {
+        "更新"
+    } else {
+        "挿入"
+    });
+    confirm_btn.add_css_class("suggested-action");
+    button_box.append(&confirm_btn);
+
+    let selected_level = |dropdown: &DropDown| {
+        QrErrorCorrection::ALL
+            .get(dropdown.selected() as usize)
+            .copied()
+            .unwrap_or_default()
+    };
+
+    // Preview the symbol size and block payloads that cannot be encoded
+    let update_status = {
+        let payload_entry = payload_entry.clone();
+        let level_dropdown = level_dropdown.clone();
+        let status_label = status_label.clone();
+        let confirm_btn = confirm_btn.clone();
+        move || {
+            let payload = payload_entry.text();
+            let (status, valid) = if payload.is_empty() {
+                ("内容を入力してください".to_string(), false)
+            } else {
+                match QrMatrix::encode(&payload, selected_level(&level_dropdown)) {
+                    Ok(matrix) => (
+                        format!(
+                            "バージョン {}（{} × {} モジュール）",
+                            matrix.version(),
+                            matrix.size(),
+                            matrix.size()
+                        ),
+                        true,
+                    ),
+                    Err(err) => (format!("エンコードできません: {}", err), false),
+                }
+            };
+            status_label.set_text(&status);
+            confirm_btn.set_sensitive(valid);
+        }
+    };
+    update_status();
+    let update = update_status.clone();
+    payload_entry.connect_changed(move |_| update());
+    let update = update_status.clone();
+    level_dropdown.connect_selected_notify(move |_| update());
+
+    let dialog_ref = dialog.clone();
+    let payload_ref = payload_entry.clone();
+    let level_ref = level_dropdown.clone();
+    confirm_btn.connect_clicked(move |_| {
+        let payload = payload_ref.text().to_string();
+        let level = selected_level(&level_ref);
+        dialog_ref.close();
+        on_confirmed(payload, level);
+    });
+
+    // Enter confirms like the button, unless the payload is invalid
+    let confirm_ref = confirm_btn.clone();
+    payload_entry.connect_activate(move |_| {
+        if confirm_ref.is_sensitive() {
+            confirm_ref.emit_clicked();
+        }
+    });
+
+    main_box.append(&button_box);
+    dialog.set_child(Some(&main_box));
+    dialog.present();
+    payload_entry.grab_focus();
+}
@@ -1,6 +1,7 @@
 //! Recent files dialog
 //!
 //! Shows a list of recently opened files and allows opening them.
+//! `open_recent_file` is shared with the File > Open Recent menu.
 
 use crate::app::AppState;
 use crate::canvas::CanvasRenderState;
@@ -104,25 +105,14 @@ pub fn show_recent_files_dialog(
     let render_state_clone = render_state.clone();
     open_btn.connect_clicked(move |_| {
         if let Some(row) = list_box_clone.selected_row() {
-            let path_str = row.widget_name().to_string();
-            let path = std::path::PathBuf::from(&path_str);
-
-            if path.exists() {
-                match crate::io::file_io::load_document(&path) {
-                    Ok(document) => {
-                        app_state_clone.set_active_document(document);
-                        app_state_clone.add_recent_file(path.clone());
-                        render_state_clone.selected_ids.borrow_mut().clear();
-                        drawing_area_clone.queue_draw();
-                        tracing::info!("✅ Document loaded from recent: {}", path.display());
-                        dialog_clone.close();
-                    }
-                    Err(e) => {
-                        tracing::error!("❌ Failed to load document: {}", e);
-                    }
-                }
-            } else {
-                tracing::warn!("⚠️  File no longer exists: {}", path.display());
+            let path = std::path::PathBuf::from(row.widget_name().as_str());
+            if open_recent_file(
+                &app_state_clone,
+                &path,
+                &drawing_area_clone,
+                &render_state_clone,
+            ) {
+                dialog_clone.close();
             }
         }
     });
@@ -156,25 +146,9 @@ pub fn show_recent_files_dialog(
     let drawing_area_dbl = drawing_area.clone();
     let render_state_dbl = render_state.clone();
     list_box.connect_row_activated(move |_, row| {
-        let path_str = row.widget_name().to_string();
-        let path = std::path::PathBuf::from(&path_str);
-
-        if path.exists() {
-            match crate::io::file_io::load_document(&path) {
-                Ok(document) => {
-                    app_state_dbl.set_active_document(document);
-                    app_state_dbl.add_recent_file(path.clone());
-                    render_state_dbl.selected_ids.borrow_mut().clear();
-                    drawing_area_dbl.queue_draw();
-                    tracing::info!("✅ Document loaded from recent: {}", path.display());
-                    dialog_dbl.close();
-                }
-                Err(e) => {
-                    tracing::error!("❌ Failed to load document: {}", e);
-                }
-            }
-        } else {
-            tracing::warn!("⚠️  File no longer exists: {}", path.display());
+        let path = std::path::PathBuf::from(row.widget_name().as_str());
+        if open_recent_file(&app_state_dbl, &path, &drawing_area_dbl, &render_state_dbl) {
+            dialog_dbl.close();
         }
     });
 
@@ -186,3 +160,37 @@ pub fn show_recent_files_dialog(
     dialog.set_child(Some(&main_box));
     dialog.present();
 }
+
+/// Load `path` as the active document, returning `true` on success
+///
+/// A file that no longer exists is dropped from the recent files list.
+pub fn open_recent_file(
+    app_state: &AppState,
+    path: &std::path::Path,
+    drawing_area: &gtk4::DrawingArea,
+    render_state: &CanvasRenderState,
+) -> bool {
+    if !path.exists() {
+        tracing::warn!("⚠️  File no longer exists: {}", path.display());
+        app_state.remove_recent_file(path);
+        return false;
+    }
+
+    match crate::io::file_io::load_document(path) {
+        Ok(document) => {
+            render_state.apply_document_view(&document);
+            app_state.set_active_document(document);
+            app_state.mark_as_saved(path.to_path_buf());
+            render_state.selected_ids.borrow_mut().clear();
+            let _ = drawing_area.activate_action("win.view-state-changed", None);
+            let _ = drawing_area.activate_action("win.pages-changed", None);
+            drawing_area.queue_draw();
+            tracing::info!("✅ Document loaded from recent: {}", path.display());
+            true
+        }
+        Err(e) => {
+            tracing::error!("❌ Failed to load document: {}", e);
+            false
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! Crash recovery dialog
+//!
+//! Offered on startup when a recovery snapshot newer than its source file
+//! exists (see `io::recovery`).
+
+use crate::app::AppState;
+use crate::canvas::CanvasRenderState;
+use crate::io::recovery;
+use gtk4::prelude::*;
+use gtk4::{AlertDialog, Window};
+
+/// Offer to restore the recovery snapshot, if there is one
+///
+/// Recovering replaces the active document with the snapshot, marked as
+/// modified and pointing at its original file. Declining deletes the
+/// snapshot.
+pub async fn offer_crash_recovery(
+    parent: &Window,
+    state: AppState,
+    drawing_area: gtk4::DrawingArea,
+    render_state: CanvasRenderState,
+) {
+    let Some(path) = recovery::autosave_path() else {
+        return;
+    };
+    let Some(snapshot) = recovery::pending_snapshot(&path) else {
+        return;
+    };
+
+    let source = snapshot
+        .source_path
+        .as_ref()
+        .and_then(|source| source.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Untitled".to_string());
+    let dialog = AlertDialog::builder()
+        .modal(true)
+        .message("Recover unsaved changes?")
+        .detail(format!(
+            "Testruct Studio did not shut down properly. Unsaved changes to \"{}\" can be recovered.",
+            source
+        ))
+        .build();
+
+    // Buttons: Discard (0), Recover (1)
+    dialog.set_buttons(&["Discard", "Recover"]);
+    dialog.set_cancel_button(0);
+    dialog.set_default_button(1);
+
+    match dialog.choose_future(Some(parent)).await {
+        Ok(1) => {
+            render_state.apply_document_view(&snapshot.document);
+            state.set_active_document(snapshot.document);
+            state.set_current_file_path(snapshot.source_path);
+            state.mark_as_modified();
+            render_state.selected_ids.borrow_mut().clear();
+            let _ = drawing_area.activate_action("win.view-state-changed", None);
+            let _ = drawing_area.activate_action("win.pages-changed", None);
+            drawing_area.queue_draw();
+            tracing::info!("♻️  Document recovered from: {}", path.display());
+        }
+        _ => {
+            recovery::discard_snapshot(&path);
+            tracing::info!("🧹 Recovery snapshot discarded");
+        }
+    }
+}
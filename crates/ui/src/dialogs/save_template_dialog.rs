@@ -0,0 +1,178 @@
+//! Save As Template dialog
+//!
+//! Asks for a template name and an optional category before writing the
+//! active document into the templates directory. Replacing a saved template
+//! of the same name needs confirmation.
+
+use crate::app::AppState;
+use crate::templates::{self, TemplateEntry};
+use gtk4::prelude::*;
+use gtk4::{AlertDialog, Align, Box as GtkBox, Button, Entry, Label, Orientation, Window};
+use testruct_core::document::Document;
+
+pub fn show_save_template_dialog(parent: &Window, app_state: AppState) {
+    let Some(document) = app_state.active_document() else {
+        tracing::warn!("⚠️  No active document to save as template");
+        return;
+    };
+
+    let dialog = gtk4::ApplicationWindow::builder()
+        .transient_for(parent)
+        .modal(true)
+        .title("テンプレートとして保存")
+        .default_width(400)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_start(16);
+    main_box.set_margin_end(16);
+    main_box.set_margin_top(16);
+    main_box.set_margin_bottom(16);
+
+    let title = Label::new(Some("テンプレートとして保存"));
+    title.add_css_class("title-2");
+    main_box.append(&title);
+
+    // Template name, prefilled with the document title
+    let name_box = GtkBox::new(Orientation::Horizontal, 8);
+    let name_label = Label::new(Some("名前:"));
+    name_label.set_size_request(100, -1);
+    name_label.set_halign(Align::Start);
+    name_box.append(&name_label);
+    let name_entry = Entry::new();
+    name_entry.set_hexpand(true);
+    name_entry.set_text(document.metadata.title.trim());
+    name_entry.set_placeholder_text(Some("テンプレート名"));
+    name_box.append(&name_entry);
+    main_box.append(&name_box);
+
+    // Optional category, existing categories listed in the tooltip
+    let categories = existing_categories();
+    let category_box = GtkBox::new(Orientation::Horizontal, 8);
+    let category_label = Label::new(Some("カテゴリ:"));
+    category_label.set_size_request(100, -1);
+    category_label.set_halign(Align::Start);
+    category_box.append(&category_label);
+    let category_entry = Entry::new();
+    category_entry.set_hexpand(true);
+    category_entry.set_placeholder_text(Some("（任意）"));
+    if !categories.is_empty() {
+        category_entry
+            .set_tooltip_text(Some(&format!("既存のカテゴリ: {}", categories.join(", "))));
+    }
+    category_box.append(&category_entry);
+    main_box.append(&category_box);
+
+    let status_label = Label::new(None);
+    status_label.add_css_class("dim-label");
+    status_label.set_halign(Align::Start);
+    main_box.append(&status_label);
+
+    // Button box
+    let button_box = GtkBox::new(Orientation::Horizontal, 6);
+    button_box.set_halign(Align::End);
+    button_box.set_homogeneous(true);
+
+    let save_btn = Button::with_label("保存");
+    save_btn.add_css_class("suggested-action");
+    let dialog_ref = dialog.clone();
+    save_btn.connect_clicked(move |_| {
+        let name = name_entry.text().trim().to_string();
+        if name.is_empty() {
+            status_label.set_text("テンプレート名を入力してください");
+            return;
+        }
+        let category = category_entry.text().trim().to_string();
+        let category = (!category.is_empty()).then_some(category);
+
+        if !templates::template_exists(&name, category.as_deref()) {
+            write_template(
+                &app_state,
+                &name,
+                category.as_deref(),
+                &document,
+                &dialog_ref,
+                &status_label,
+            );
+            return;
+        }
+
+        // Ask before replacing a saved template of the same name
+        let confirm = AlertDialog::builder()
+            .modal(true)
+            .message(format!("テンプレート「{}」は既にあります", name))
+            .detail("上書きすると、保存済みのテンプレートは元に戻せません。")
+            .build();
+        confirm.set_buttons(&["キャンセル", "上書き"]);
+        confirm.set_cancel_button(0);
+        confirm.set_default_button(0);
+
+        let app_state = app_state.clone();
+        let document = document.clone();
+        let dialog = dialog_ref.clone();
+        let status_label = status_label.clone();
+        let parent = Some(&dialog_ref);
+        confirm.choose(parent, gtk4::gio::Cancellable::NONE, move |response| {
+            if matches!(response, Ok(1)) {
+                write_template(
+                    &app_state,
+                    &name,
+                    category.as_deref(),
+                    &document,
+                    &dialog,
+                    &status_label,
+                );
+            }
+        });
+    });
+    button_box.append(&save_btn);
+
+    let close_btn = Button::with_label("キャンセル");
+    let dialog_ref = dialog.clone();
+    close_btn.connect_clicked(move |_| {
+        dialog_ref.close();
+    });
+    button_box.append(&close_btn);
+
+    main_box.append(&button_box);
+
+    dialog.set_child(Some(&main_box));
+    dialog.present();
+}
+
+/// Save the template and close the dialog, or show why saving failed
+fn write_template(
+    app_state: &AppState,
+    name: &str,
+    category: Option<&str>,
+    document: &Document,
+    dialog: &gtk4::ApplicationWindow,
+    status_label: &Label,
+) {
+    let catalog = app_state.asset_catalog();
+    let catalog = catalog.lock().expect("asset catalog");
+    match templates::save_template(name, category, document, &catalog) {
+        Ok(_) => {
+            tracing::info!("✅ Document saved as template: {}", name);
+            dialog.close();
+        }
+        Err(e) => {
+            tracing::error!("Failed to save template: {}", e);
+            status_label.set_text(&format!("保存に失敗しました: {}", e));
+        }
+    }
+}
+
+/// Category names already used by saved templates
+fn existing_categories() -> Vec<String> {
+    let entries: Vec<TemplateEntry> = templates::list_templates().unwrap_or_else(|e| {
+        tracing::warn!("Failed to list templates: {}", e);
+        Vec::new()
+    });
+    let mut categories: Vec<String> = entries
+        .into_iter()
+        .filter_map(|entry| entry.category)
+        .collect();
+    categories.dedup();
+    categories
+}
@@ -26,7 +26,8 @@ pub fn show_shortcuts_dialog(parent: &Window) {
         ("Ctrl+N", "新規作成"),
         ("Ctrl+O", "ファイルを開く"),
         ("Ctrl+S", "保存"),
-        ("Ctrl+Shift+S", "テンプレートとして保存"),
+        ("Ctrl+Shift+S", "名前を付けて保存"),
+        ("Ctrl+Alt+S", "テンプレートとして保存"),
     ]);
     content_box.append(&file_section);
 
@@ -36,8 +37,10 @@ pub fn show_shortcuts_dialog(parent: &Window) {
         ("Ctrl+Shift+Z", "やり直し（Redo）"),
         ("Ctrl+C", "コピー"),
         ("Ctrl+X", "カット"),
-        ("Ctrl+V", "ペースト"),
+        ("Ctrl+V", "カーソル位置にペースト"),
+        ("Ctrl+Shift+V", "同じ位置にペースト"),
         ("Ctrl+D", "複製"),
+        ("Ctrl+F", "検索と置換"),
         ("Delete", "削除"),
     ]);
     content_box.append(&edit_section);
@@ -61,7 +64,17 @@ pub fn show_shortcuts_dialog(parent: &Window) {
 
     // Tool operations
     let tool_section = create_shortcut_section("ツール", &[
-        ("Ctrl+Shift+I", "画像挿入"),
+        ("V", "選択ツール"),
+        ("T", "テキストツール"),
+        ("I", "画像ツール"),
+        ("R", "長方形ツール"),
+        ("E", "円ツール"),
+        ("L", "直線ツール"),
+        ("A", "矢印ツール"),
+        ("P", "多角形ツール"),
+        ("K", "スポイトツール"),
+        ("M", "計測ツール"),
+        ("Ctrl+I", "画像挿入"),
         ("F1", "このダイアログを表示"),
     ]);
     content_box.append(&tool_section);
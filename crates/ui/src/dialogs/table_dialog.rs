@@ -0,0 +1,78 @@
+//! Insert table dialog
+//!
+//! Asks for the number of rows and columns of a new table.
+
+use gtk4::prelude::*;
+use gtk4::{Adjustment, Align, Box as GtkBox, Button, Label, Orientation, SpinButton, Window};
+
+/// Largest number of rows or columns offered when inserting a table
+pub const MAX_TABLE_DIMENSION: usize = 50;
+
+/// Show the insert table dialog
+///
+/// `on_confirmed` is called with the chosen rows and columns when the user
+/// presses insert.
+pub fn show_insert_table_dialog(parent: &Window, on_confirmed: Box<dyn Fn(usize, usize)>) {
+    let dialog = gtk4::ApplicationWindow::builder()
+        .transient_for(parent)
+        .modal(true)
+        .title("表の挿入")
+        .default_width(300)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_start(16);
+    main_box.set_margin_end(16);
+    main_box.set_margin_top(16);
+    main_box.set_margin_bottom(16);
+
+    let title = Label::new(Some("表のサイズ"));
+    title.add_css_class("title-3");
+    title.set_halign(Align::Start);
+    main_box.append(&title);
+
+    let rows_spin = dimension_row(&main_box, "行数:", 3.0);
+    let columns_spin = dimension_row(&main_box, "列数:", 3.0);
+
+    // Button box
+    let button_box = GtkBox::new(Orientation::Horizontal, 6);
+    button_box.set_halign(Align::End);
+    button_box.set_homogeneous(true);
+    button_box.set_margin_top(12);
+
+    let cancel_btn = Button::with_label("キャンセル");
+    let dialog_ref = dialog.clone();
+    cancel_btn.connect_clicked(move |_| {
+        dialog_ref.close();
+    });
+    button_box.append(&cancel_btn);
+
+    let insert_btn = Button::with_label("挿入");
+    insert_btn.add_css_class("suggested-action");
+    let dialog_ref = dialog.clone();
+    insert_btn.connect_clicked(move |_| {
+        let rows = rows_spin.value_as_int().max(1) as usize;
+        let columns = columns_spin.value_as_int().max(1) as usize;
+        dialog_ref.close();
+        on_confirmed(rows, columns);
+    });
+    button_box.append(&insert_btn);
+
+    main_box.append(&button_box);
+    dialog.set_child(Some(&main_box));
+    dialog.present();
+}
+
+/// Append a labelled spin button for a row or column count
+fn dimension_row(parent: &GtkBox, label: &str, value: f64) -> SpinButton {
+    let row = GtkBox::new(Orientation::Horizontal, 8);
+    let label = Label::new(Some(label));
+    label.set_size_request(80, -1);
+    label.set_halign(Align::Start);
+    row.append(&label);
+    let adjustment = Adjustment::new(value, 1.0, MAX_TABLE_DIMENSION as f64, 1.0, 5.0, 0.0);
+    let spin = SpinButton::new(Some(&adjustment), 1.0, 0);
+    row.append(&spin);
+    parent.append(&row);
+    spin
+}
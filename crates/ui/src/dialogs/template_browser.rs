@@ -1,19 +1,70 @@
+use crate::templates::TemplateEntry;
+use gtk4::glib;
 use gtk4::prelude::*;
 use gtk4::{
-    Align, Box as GtkBox, Button, Label, ListBox, ListBoxRow, Orientation, ScrolledWindow,
-    SearchEntry, Window,
+    Align, Box as GtkBox, Button, ContentFit, FlowBox, Image, Label, Orientation, Picture,
+    ScrolledWindow, SearchEntry, SelectionMode, Widget, Window,
 };
 use std::cell::RefCell;
 use std::rc::Rc;
 use testruct_core::template::Template;
 
+/// Group heading for the project's own template library
+const PROJECT_GROUP: &str = "プロジェクト";
+
+/// Group heading for saved templates without a category
+const UNCATEGORIZED_GROUP: &str = "未分類";
+
+/// A template picked in the browser
+#[derive(Clone, Debug)]
+pub enum TemplateSelection {
+    /// From the project's template library
+    Project(Template),
+    /// A template file saved in the templates directory
+    Saved(TemplateEntry),
+}
+
+impl TemplateSelection {
+    pub fn name(&self) -> &str {
+        match self {
+            TemplateSelection::Project(template) => &template.name,
+            TemplateSelection::Saved(entry) => &entry.name,
+        }
+    }
+
+    /// Heading this template is listed under
+    fn group(&self) -> &str {
+        match self {
+            TemplateSelection::Project(_) => PROJECT_GROUP,
+            TemplateSelection::Saved(entry) => {
+                entry.category.as_deref().unwrap_or(UNCATEGORIZED_GROUP)
+            }
+        }
+    }
+}
+
+/// Size of the thumbnail area on each template card
+const THUMBNAIL_WIDTH: i32 = 120;
+const THUMBNAIL_HEIGHT: i32 = 160;
+
 /// Show the Template Manager dialog with callback
+///
+/// Templates are shown as thumbnail cards: project templates first, then
+/// one grid per category of saved templates (in `templates::list_templates`
+/// order). Saved templates without a thumbnail get one rendered when idle.
 pub fn show_template_browser_async(
     parent: &Window,
     templates: Vec<Template>,
-    on_selected: Box<dyn Fn(Option<Template>)>,
+    saved_templates: Vec<TemplateEntry>,
+    on_selected: Box<dyn Fn(Option<TemplateSelection>)>,
 ) {
-    let selected_template: Rc<RefCell<Option<Template>>> = Rc::new(RefCell::new(None));
+    let selected_template: Rc<RefCell<Option<TemplateSelection>>> = Rc::new(RefCell::new(None));
+
+    let mut entries: Vec<TemplateSelection> = templates
+        .into_iter()
+        .map(TemplateSelection::Project)
+        .collect();
+    entries.extend(saved_templates.into_iter().map(TemplateSelection::Saved));
 
     // Create dialog window for template browser
     let dialog = gtk4::ApplicationWindow::builder()
@@ -40,66 +91,66 @@ pub fn show_template_browser_async(
     search_entry.set_placeholder_text(Some("テンプレートを検索..."));
     main_box.append(&search_entry);
 
-    // Template list
-    let list_box = ListBox::new();
-    list_box.set_vexpand(true);
-    list_box.set_hexpand(true);
-    list_box.add_css_class("boxed-list");
-
-    // Store template data for selection
-    let template_data: Rc<RefCell<Vec<Template>>> = Rc::new(RefCell::new(templates.clone()));
-
-    // Populate list
-    for template in templates.iter() {
-        let row = ListBoxRow::new();
-        let item_box = GtkBox::new(Orientation::Vertical, 6);
-        item_box.set_margin_start(12);
-        item_box.set_margin_end(12);
-        item_box.set_margin_top(8);
-        item_box.set_margin_bottom(8);
-
-        // Template name
-        let name_label = Label::new(Some(&template.name));
-        name_label.add_css_class("title-3");
-        name_label.set_halign(Align::Start);
-        item_box.append(&name_label);
-
-        // Template description (if available)
-        if let Some(desc) = &template.description {
-            let desc_label = Label::new(Some(desc));
-            desc_label.add_css_class("dim-label");
-            desc_label.set_halign(Align::Start);
-            desc_label.set_wrap(true);
-            item_box.append(&desc_label);
+    // Selected template name, updated from every category grid
+    let selected_name_label = Label::new(Some("なし"));
+    selected_name_label.add_css_class("title-4");
+
+    // Thumbnail grids, one per category
+    let sections_box = GtkBox::new(Orientation::Vertical, 6);
+    let mut groups: Vec<(&str, Vec<usize>)> = Vec::new();
+    for (index, template) in entries.iter().enumerate() {
+        match groups.last_mut() {
+            Some((group, indices)) if *group == template.group() => indices.push(index),
+            _ => groups.push((template.group(), vec![index])),
         }
+    }
 
-        // Page count info
-        let info_label = Label::new(Some(&format!("ページ数: {}", template.pages.len())));
-        info_label.add_css_class("dim-label");
-        info_label.set_halign(Align::Start);
-        info_label.set_size_request(200, -1);
-        item_box.append(&info_label);
+    let template_data = Rc::new(entries.clone());
+    let flow_boxes: Rc<RefCell<Vec<FlowBox>>> = Rc::new(RefCell::new(Vec::new()));
+    for (group, indices) in groups {
+        let heading = Label::new(Some(group));
+        heading.add_css_class("heading");
+        heading.set_halign(Align::Start);
+        heading.set_margin_top(6);
+        sections_box.append(&heading);
 
-        row.set_child(Some(&item_box));
-        list_box.append(&row);
-    }
+        let flow_box = FlowBox::new();
+        flow_box.set_selection_mode(SelectionMode::Single);
+        flow_box.set_homogeneous(true);
+        flow_box.set_max_children_per_line(4);
+        flow_box.set_column_spacing(12);
+        flow_box.set_row_spacing(12);
+        for &index in &indices {
+            flow_box.insert(&build_template_card(&template_data[index]), -1);
+        }
 
-    // Handle template selection
-    let selected_ref = selected_template.clone();
-    let template_data_clone = template_data.clone();
-    list_box.connect_row_selected(move |_list_box, row| {
-        if let Some(row) = row {
-            let index = row.index() as usize;
-            if let Some(template) = template_data_clone.borrow().get(index).cloned() {
-                *selected_ref.borrow_mut() = Some(template);
-                tracing::info!("✅ Template selected: {}", row.index());
+        // Only one template can be selected across all grids
+        let selected_ref = selected_template.clone();
+        let template_data_clone = template_data.clone();
+        let flow_boxes_ref = flow_boxes.clone();
+        let selected_name_clone = selected_name_label.clone();
+        flow_box.connect_child_activated(move |flow_box, child| {
+            let Some(&index) = indices.get(child.index() as usize) else {
+                return;
+            };
+            for other in flow_boxes_ref.borrow().iter() {
+                if other != flow_box {
+                    other.unselect_all();
+                }
             }
-        }
-    });
+            let template = template_data_clone[index].clone();
+            selected_name_clone.set_text(template.name());
+            tracing::info!("✅ Template selected: {}", template.name());
+            *selected_ref.borrow_mut() = Some(template);
+        });
+
+        sections_box.append(&flow_box);
+        flow_boxes.borrow_mut().push(flow_box);
+    }
 
-    // Scrolled window for list
+    // Scrolled window for the grids
     let scrolled = ScrolledWindow::new();
-    scrolled.set_child(Some(&list_box));
+    scrolled.set_child(Some(&sections_box));
     scrolled.set_hexpand(true);
     scrolled.set_vexpand(true);
     main_box.append(&scrolled);
@@ -110,20 +161,8 @@ pub fn show_template_browser_async(
     let preview_label = Label::new(Some("選択したテンプレート:"));
     preview_label.add_css_class("dim-label");
     preview_box.append(&preview_label);
-
-    let selected_name_label = Label::new(Some("なし"));
-    selected_name_label.add_css_class("title-4");
     preview_box.append(&selected_name_label);
 
-    // Update preview when selection changes
-    let selected_ref_preview = selected_template.clone();
-    let selected_name_clone = selected_name_label.clone();
-    list_box.connect_row_selected(move |_, _| {
-        if let Some(template) = selected_ref_preview.borrow().as_ref() {
-            selected_name_clone.set_text(&template.name);
-        }
-    });
-
     main_box.append(&preview_box);
 
     // Button box
@@ -174,3 +213,69 @@ pub fn show_template_browser_async(
 
     dialog.present();
 }
+
+/// Card for the grid: thumbnail, name and a short info line
+fn build_template_card(template: &TemplateSelection) -> GtkBox {
+    let card = GtkBox::new(Orientation::Vertical, 4);
+    card.set_margin_start(6);
+    card.set_margin_end(6);
+    card.set_margin_top(6);
+    card.set_margin_bottom(6);
+
+    let thumbnail = GtkBox::new(Orientation::Vertical, 0);
+    thumbnail.set_size_request(THUMBNAIL_WIDTH, THUMBNAIL_HEIGHT);
+    thumbnail.add_css_class("card");
+    card.append(&thumbnail);
+
+    match template {
+        TemplateSelection::Project(_) => set_thumbnail(&thumbnail, &placeholder_icon()),
+        TemplateSelection::Saved(entry) => {
+            set_thumbnail(&thumbnail, &placeholder_icon());
+            // Rendering a missing thumbnail loads the whole template, so
+            // leave it until the dialog is on screen
+            let entry = entry.clone();
+            let thumbnail = thumbnail.clone();
+            glib::idle_add_local_once(move || {
+                if let Some(path) = crate::templates::template_thumbnail(&entry) {
+                    let picture = Picture::for_filename(&path);
+                    picture.set_content_fit(ContentFit::Contain);
+                    picture.set_vexpand(true);
+                    set_thumbnail(&thumbnail, &picture);
+                }
+            });
+        }
+    }
+
+    let name_label = Label::new(Some(template.name()));
+    name_label.add_css_class("heading");
+    name_label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+    name_label.set_max_width_chars(16);
+    card.append(&name_label);
+
+    if let TemplateSelection::Project(template) = template {
+        let info_label = Label::new(Some(&format!("ページ数: {}", template.pages.len())));
+        info_label.add_css_class("dim-label");
+        card.append(&info_label);
+        if let Some(desc) = &template.description {
+            card.set_tooltip_text(Some(desc));
+        }
+    }
+
+    card
+}
+
+fn placeholder_icon() -> Image {
+    let icon = Image::from_icon_name("x-office-document-symbolic");
+    icon.set_pixel_size(48);
+    icon.add_css_class("dim-label");
+    icon.set_vexpand(true);
+    icon
+}
+
+/// Replace the contents of a card's thumbnail area
+fn set_thumbnail(thumbnail: &GtkBox, content: &impl IsA<Widget>) {
+    while let Some(child) = thumbnail.first_child() {
+        thumbnail.remove(&child);
+    }
+    thumbnail.append(content);
+}
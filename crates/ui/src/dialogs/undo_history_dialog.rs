@@ -0,0 +1,113 @@
+//! Undo history dialog
+//!
+//! Debug view of the undo stack: how many commands can be undone and redone,
+//! the history limit from the project settings and roughly how much memory
+//! the history holds. The numbers refresh while the dialog is open, so the
+//! limit can be tuned against real editing sessions.
+
+use crate::app::AppState;
+use gtk4::prelude::*;
+use gtk4::{glib, Align, Box as GtkBox, Button, Grid, Label, Orientation, Window};
+use std::time::Duration;
+
+/// How often the figures are refreshed
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Show the undo history dialog
+pub fn show_undo_history_dialog(parent: &Window, app_state: AppState) {
+    let dialog = gtk4::ApplicationWindow::builder()
+        .transient_for(parent)
+        .modal(false)
+        .title("取り消し履歴")
+        .default_width(320)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_start(12);
+    main_box.set_margin_end(12);
+    main_box.set_margin_top(12);
+    main_box.set_margin_bottom(12);
+
+    let grid = Grid::new();
+    grid.set_row_spacing(6);
+    grid.set_column_spacing(12);
+    let mut values = Vec::new();
+    for (row, caption) in [
+        "取り消せる操作:",
+        "やり直せる操作:",
+        "履歴の上限:",
+        "推定メモリ使用量:",
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let caption = Label::new(Some(caption));
+        caption.set_halign(Align::Start);
+        grid.attach(&caption, 0, row as i32, 1, 1);
+        let value = Label::new(None);
+        value.set_halign(Align::End);
+        value.set_hexpand(true);
+        grid.attach(&value, 1, row as i32, 1, 1);
+        values.push(value);
+    }
+    main_box.append(&grid);
+
+    let refresh = move || {
+        let (undo, redo, limit, memory) = app_state.with_undo_stack(|stack| {
+            (
+                stack.undo_count(),
+                stack.redo_count(),
+                stack.max_history(),
+                stack.memory_usage(),
+            )
+        });
+        values[0].set_text(&format!("{} 件", undo));
+        values[1].set_text(&format!("{} 件", redo));
+        values[2].set_text(&format!("{} 件", limit));
+        values[3].set_text(&format_memory(memory));
+    };
+    refresh();
+
+    let dialog_weak = dialog.downgrade();
+    glib::timeout_add_local(REFRESH_INTERVAL, move || {
+        if dialog_weak.upgrade().is_none() {
+            return glib::ControlFlow::Break;
+        }
+        refresh();
+        glib::ControlFlow::Continue
+    });
+
+    let close_btn = Button::with_label("閉じる");
+    close_btn.set_halign(Align::End);
+    let dialog_close = dialog.clone();
+    close_btn.connect_clicked(move |_| dialog_close.close());
+    main_box.append(&close_btn);
+
+    dialog.set_child(Some(&main_box));
+    dialog.present();
+}
+
+/// Byte count in B, KB or MB
+fn format_memory(bytes: usize) -> String {
+    const KB: f64 = 1024.0;
+    let bytes_f = bytes as f64;
+    if bytes_f < KB {
+        format!("{} B", bytes)
+    } else if bytes_f < KB * KB {
+        format!("{:.1} KB", bytes_f / KB)
+    } else {
+        format!("{:.1} MB", bytes_f / (KB * KB))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_memory_units() {
+        assert_eq!(format_memory(512), "512 B");
+        assert_eq!(format_memory(1536), "1.5 KB");
+        assert_eq!(format_memory(3 * 1024 * 1024), "3.0 MB");
+    }
+}
@@ -75,10 +75,11 @@ pub async fn check_unsaved_changes(
             // Save the document first
             if let Some(path) = state.current_file_path() {
                 // Overwrite existing file
-                if let Some(document) = state.active_document() {
+                if let Some(document) = state.document_to_save() {
                     match crate::io::file_io::save_document(&document, &path) {
                         Ok(_) => {
                             state.mark_as_saved(path);
+                            crate::io::recovery::discard_autosave();
                             tracing::info!("✅ Document saved before operation");
                             true
                         }
@@ -96,11 +97,11 @@ pub async fn check_unsaved_changes(
                 if let Some(save_path) = crate::io::file_dialog::show_save_dialog(
                     &parent.clone().downcast::<gtk4::ApplicationWindow>().unwrap()
                 ).await {
-                    if let Some(document) = state.active_document() {
+                    if let Some(document) = state.document_to_save() {
                         match crate::io::file_io::save_document(&document, &save_path) {
                             Ok(_) => {
-                                state.add_recent_file(save_path.clone());
                                 state.mark_as_saved(save_path);
+                                crate::io::recovery::discard_autosave();
                                 tracing::info!("✅ Document saved before operation");
                                 true
                             }
@@ -119,7 +120,8 @@ pub async fn check_unsaved_changes(
             }
         }
         UnsavedDialogResponse::DontSave => {
-            // Proceed without saving
+            // Proceed without saving; the discarded changes need no recovery
+            crate::io::recovery::discard_autosave();
             true
         }
         UnsavedDialogResponse::Cancel => {
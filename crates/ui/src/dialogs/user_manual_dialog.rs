@@ -28,6 +28,8 @@ pub fn show_user_manual_dialog(parent: &Window) {
    - ドラッグでズーム範囲の移動
    - スクロールホイールでズーム
    - 右クリックでコンテキストメニュー
+   - 計測: 「計測」ボタン（M）を選んでドラッグすると、2 点間の距離と
+     水平・垂直成分をルーラーの単位で表示
 
 2. 図形の作成
    - 選択: ツールバーから「選択」を選択
@@ -43,6 +45,9 @@ pub fn show_user_manual_dialog(parent: &Window) {
    - ハンドルドラッグ: オブジェクトをリサイズ
    - Shift + クリック: 複数選択
    - Ctrl + A: すべて選択
+   - Ctrl + F: テキストの検索と置換
+   - 右クリック: 操作メニュー（空白では貼り付け・すべて選択）
+   - 右クリック（編集中の赤い波線の単語）: スペルの候補で置き換え
 
 4. ビューメニュー
    - F8: グリッド表示/非表示
@@ -0,0 +1,65 @@
+//! EPS export using Cairo's PostScript surface
+//!
+//! EPS holds a single page, so a multi-page document is written as one file
+//! per page with a `_page_N` suffix (like the PNG export).
+
+use anyhow::{anyhow, Result};
+use cairo::{Context, PsSurface};
+use std::path::Path;
+use testruct_core::document::Page;
+use testruct_core::workspace::assets::AssetCatalog;
+use testruct_core::Document;
+use tracing::{debug, info};
+
+use crate::export::image::page_output_path;
+use crate::export::{pdf, PdfExportOptions};
+
+/// Render a document to EPS (one file per page)
+pub fn render_to_eps(
+    document: &Document,
+    output_path: &Path,
+    catalog: &AssetCatalog,
+) -> Result<()> {
+    info!("Exporting to EPS: {}", output_path.display());
+
+    if document.pages.is_empty() {
+        return Err(anyhow!("Document has no pages to export"));
+    }
+
+    if let [page] = &document.pages[..] {
+        return render_page_to_eps(document, page, output_path, catalog);
+    }
+
+    for (index, page) in document.pages.iter().enumerate() {
+        let page_path = page_output_path(output_path, index + 1);
+        debug!("Rendering page {} to: {}", index + 1, page_path.display());
+        render_page_to_eps(document, page, &page_path, catalog)?;
+    }
+
+    info!(
+        "EPS export completed: {} pages exported to {}",
+        document.pages.len(),
+        output_path.display()
+    );
+    Ok(())
+}
+
+/// Render one page to an EPS file sized to the page
+fn render_page_to_eps(
+    document: &Document,
+    page: &Page,
+    output_path: &Path,
+    catalog: &AssetCatalog,
+) -> Result<()> {
+    let page_size = document.page_size_of(page);
+    let surface = PsSurface::new(page_size.width as f64, page_size.height as f64, output_path)
+        .map_err(|e| anyhow!("Failed to create PostScript surface: {}", e))?;
+    surface.set_eps(true);
+
+    let ctx =
+        Context::new(&surface).map_err(|e| anyhow!("Failed to create Cairo context: {}", e))?;
+    pdf::render_page_to_context(&ctx, page, page_size, &PdfExportOptions::default(), catalog)?;
+    drop(ctx);
+    surface.finish();
+    Ok(())
+}
@@ -5,20 +5,37 @@
 use anyhow::{anyhow, Result};
 use cairo::{Context, Format, ImageSurface};
 use std::path::Path;
+use testruct_core::layout::{Point, Size};
+use testruct_core::typography::Color;
 use testruct_core::workspace::assets::AssetCatalog;
 use testruct_core::Document;
 use tracing::{debug, info};
 
-use crate::export::{BackgroundOption, ExportConfig};
+use crate::export::{
+    dpi_to_scale, validate_scale, BackgroundOption, ExportConfig, PngExportOptions,
+};
 
 /// Default DPI for image export
 const DEFAULT_DPI: f64 = 96.0;
 
-/// Render a document to PNG format (one file per page)
+/// Compute output pixel dimensions for a page rendered at the given scale
+///
+/// A scale of 1.0 maps one point to one pixel, so a 2× export of an A4 page
+/// (595×842pt) yields a 1190×1684 pixel image.
+pub fn page_pixel_size(page_size: Size, scale: f64) -> (i32, i32) {
+    let width = (page_size.width as f64 * scale).round() as i32;
+    let height = (page_size.height as f64 * scale).round() as i32;
+    (width.max(1), height.max(1))
+}
+
+/// Render a document to PNG format
+///
+/// Exports the pages selected in `options`. A single page is written to
+/// `output_path`; several pages get one file each with a `_page_N` suffix.
 pub fn render_to_png(
     document: &Document,
     output_path: &Path,
-    dpi: f64,
+    options: &PngExportOptions,
     catalog: &AssetCatalog,
 ) -> Result<()> {
     info!("Exporting to PNG: {}", output_path.display());
@@ -27,15 +44,61 @@ pub fn render_to_png(
         return Err(anyhow!("Document has no pages to export"));
     }
 
-    let dpi = if dpi <= 0.0 { DEFAULT_DPI } else { dpi };
+    let dpi = if options.dpi <= 0.0 { DEFAULT_DPI } else { options.dpi };
     debug!("PNG export DPI: {}", dpi);
+    let scale = dpi_to_scale(dpi);
+    let background = options.background();
+    let page_indices = options.page_indices(document.pages.len())?;
+
+    if let [index] = page_indices[..] {
+        let page = &document.pages[index];
+        return render_page_to_png_with_background(
+            page,
+            output_path,
+            document.page_size_of(page),
+            scale,
+            &background,
+            catalog,
+        );
+    }
 
-    // If multi-page, save each as separate file
-    if document.pages.len() > 1 {
-        export_multi_page_png(document, output_path, dpi, catalog)
-    } else {
-        export_single_page_png(document, output_path, dpi, catalog)
+    for &index in &page_indices {
+        let page_path = page_output_path(output_path, index + 1);
+        debug!("Rendering page {} to: {}", index + 1, page_path.display());
+        let page = &document.pages[index];
+        render_page_to_png_with_background(
+            page,
+            &page_path,
+            document.page_size_of(page),
+            scale,
+            &background,
+            catalog,
+        )?;
     }
+
+    info!(
+        "PNG export completed: {} pages exported to {}",
+        page_indices.len(),
+        output_path.display()
+    );
+    Ok(())
+}
+
+/// Output path for one page of a multi-page export (`name_page_N.ext`)
+pub(crate) fn page_output_path(output_path: &Path, page_num: usize) -> std::path::PathBuf {
+    let output_filename = if let Some(extension) = output_path.extension() {
+        let stem = output_path.file_stem().unwrap();
+        let stem_str = stem.to_string_lossy();
+        let ext_str = extension.to_string_lossy();
+        format!("{}_page_{}.{}", stem_str, page_num, ext_str)
+    } else {
+        format!("{}_page_{}.png", output_path.display(), page_num)
+    };
+
+    output_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(&output_filename)
 }
 
 /// Render a document to JPEG format (one file per page)
@@ -64,7 +127,7 @@ pub fn render_to_jpeg(
     // Convert to PNG first, then to JPEG using image crate
     // For now, we'll just export as PNG (JPEG would require additional dependencies)
     info!("JPEG export currently uses PNG format. Full JPEG support requires additional setup.");
-    render_to_png(document, output_path, dpi, catalog)
+    render_to_png(document, output_path, &PngExportOptions::with_dpi(dpi), catalog)
 }
 
 /// Render a document to PNG format with ExportConfig
@@ -80,10 +143,10 @@ pub fn render_to_png_with_config(
         return Err(anyhow!("Document has no pages to export"));
     }
 
-    let dpi = config.dpi();
+    let scale = validate_scale(config.scale())?;
     debug!(
-        "PNG export: DPI={}, Background={:?}, Resolution={:?}",
-        dpi, config.background, config.resolution
+        "PNG export: DPI={}, Scale={}, Background={:?}, Resolution={:?}",
+        config.dpi(), scale, config.background, config.resolution
     );
 
     // Determine which pages to export
@@ -103,26 +166,28 @@ pub fn render_to_png_with_config(
     let page_count = pages_to_export.len();
     if page_count == 1 {
         let (_, page) = pages_to_export[0];
-        render_page_to_png_with_background(page, output_path, dpi, &config.background, catalog)
+        render_page_to_png_with_background(
+            page,
+            output_path,
+            document.page_size_of(page),
+            scale,
+            &config.background,
+            catalog,
+        )
     } else {
         for (index, page) in &pages_to_export {
             let page_num = index + 1;
-            let output_filename = if let Some(extension) = output_path.extension() {
-                let stem = output_path.file_stem().unwrap();
-                let stem_str = stem.to_string_lossy();
-                let ext_str = extension.to_string_lossy();
-                format!("{}_page_{}.{}", stem_str, page_num, ext_str)
-            } else {
-                format!("{}_page_{}.png", output_path.display(), page_num)
-            };
-
-            let page_path = output_path
-                .parent()
-                .unwrap_or_else(|| Path::new("."))
-                .join(&output_filename);
+            let page_path = page_output_path(output_path, page_num);
 
             debug!("Rendering page {} to: {}", page_num, page_path.display());
-            render_page_to_png_with_background(page, &page_path, dpi, &config.background, catalog)?;
+            render_page_to_png_with_background(
+                page,
+                &page_path,
+                document.page_size_of(page),
+                scale,
+                &config.background,
+                catalog,
+            )?;
         }
 
         info!(
@@ -158,27 +223,18 @@ pub fn render_to_jpeg_with_config(
 fn render_page_to_png_with_background(
     page: &testruct_core::document::Page,
     output_path: &Path,
-    dpi: f64,
+    page_size: Size,
+    scale: f64,
     background: &BackgroundOption,
     catalog: &AssetCatalog,
 ) -> Result<()> {
-    // Use default page dimensions (A4: 595.28 x 841.89 points)
-    let width_points = 595.28;
-    let height_points = 841.89;
-    let width_inches = width_points / 72.0;
-    let height_inches = height_points / 72.0;
-
-    let pixel_width = (width_inches * dpi) as i32;
-    let pixel_height = (height_inches * dpi) as i32;
+    let (pixel_width, pixel_height) = page_pixel_size(page_size, scale);
 
     debug!(
-        "PNG size: {}x{} pixels at {} DPI",
-        pixel_width, pixel_height, dpi
+        "PNG size: {}x{} pixels at {}x scale",
+        pixel_width, pixel_height, scale
     );
 
-    let pixel_width = pixel_width.max(100);
-    let pixel_height = pixel_height.max(100);
-
     // Create image surface
     let surface = ImageSurface::create(Format::ARgb32, pixel_width, pixel_height)
         .map_err(|e| anyhow!("Failed to create image surface: {}", e))?;
@@ -186,12 +242,11 @@ fn render_page_to_png_with_background(
     let ctx =
         Context::new(&surface).map_err(|e| anyhow!("Failed to create Cairo context: {}", e))?;
 
-    // Scale context for DPI
-    let scale = dpi / 72.0;
+    // Scale context so vector content (text, strokes) is rasterized at full resolution
     ctx.scale(scale, scale);
 
     // Render page with background option
-    render_page_to_context_with_background(&ctx, page, background, catalog)?;
+    render_page_to_context_with_background(&ctx, page, page_size, background, catalog)?;
 
     // Write to file
     let mut file = std::fs::File::create(output_path)
@@ -205,16 +260,89 @@ fn render_page_to_png_with_background(
     Ok(())
 }
 
+/// Color of the rendered page at `point` (document coordinates)
+///
+/// Renders the page background and elements into a single pixel, so the
+/// result includes gradients, images and opacity. Returns `None` where
+/// nothing is painted; the color is un-premultiplied and made opaque.
+pub fn sample_page_color(
+    page: &testruct_core::document::Page,
+    page_size: Size,
+    point: Point,
+    catalog: &AssetCatalog,
+) -> Result<Option<Color>> {
+    let mut surface = ImageSurface::create(Format::ARgb32, 1, 1)
+        .map_err(|e| anyhow!("Failed to create image surface: {}", e))?;
+    {
+        let ctx =
+            Context::new(&surface).map_err(|e| anyhow!("Failed to create Cairo context: {}", e))?;
+        ctx.translate(-(point.x as f64), -(point.y as f64));
+        render_page_to_context_with_background(
+            &ctx,
+            page,
+            page_size,
+            &BackgroundOption::Transparent,
+            catalog,
+        )?;
+    }
+    surface.flush();
+
+    // ARGB32 is native-endian premultiplied 0xAARRGGBB
+    let data = surface
+        .data()
+        .map_err(|e| anyhow!("Failed to read image surface: {}", e))?;
+    let pixel = u32::from_ne_bytes([data[0], data[1], data[2], data[3]]);
+    let alpha = (pixel >> 24) & 0xff;
+    if alpha == 0 {
+        return Ok(None);
+    }
+    let channel = |shift: u32| ((pixel >> shift) & 0xff) as f32 / alpha as f32;
+    Ok(Some(Color::from_rgb(channel(16), channel(8), channel(0))))
+}
+
+/// Render a page scaled down to fit within `max_width` × `max_height` pixels
+///
+/// The page keeps its aspect ratio and is drawn on white with its border,
+/// for the page thumbnail sidebar.
+pub fn render_page_thumbnail(
+    page: &testruct_core::document::Page,
+    page_size: Size,
+    max_width: i32,
+    max_height: i32,
+    catalog: &AssetCatalog,
+) -> Result<ImageSurface> {
+    let scale = (max_width as f64 / page_size.width.max(1.0) as f64)
+        .min(max_height as f64 / page_size.height.max(1.0) as f64);
+    let (pixel_width, pixel_height) = page_pixel_size(page_size, scale);
+
+    let surface = ImageSurface::create(Format::ARgb32, pixel_width, pixel_height)
+        .map_err(|e| anyhow!("Failed to create image surface: {}", e))?;
+    {
+        let ctx =
+            Context::new(&surface).map_err(|e| anyhow!("Failed to create Cairo context: {}", e))?;
+        ctx.scale(scale, scale);
+        render_page_to_context_with_background(
+            &ctx,
+            page,
+            page_size,
+            &BackgroundOption::White,
+            catalog,
+        )?;
+    }
+    surface.flush();
+    Ok(surface)
+}
+
 /// Render a single page to Cairo context with configurable background
 fn render_page_to_context_with_background(
     ctx: &Context,
     page: &testruct_core::document::Page,
+    page_size: Size,
     background: &BackgroundOption,
     catalog: &AssetCatalog,
 ) -> Result<()> {
-    // Use default page dimensions (A4)
-    let width = 595.28;
-    let height = 841.89;
+    let width = page_size.width as f64;
+    let height = page_size.height as f64;
 
     // Draw background based on option
     match background.to_color() {
@@ -239,6 +367,15 @@ fn render_page_to_context_with_background(
         }
     }
 
+    // Paint the page's own background
+    crate::export::image_utils::render_page_background(
+        ctx,
+        &page.metadata.background,
+        page_size,
+        catalog,
+    )
+    .map_err(|e| anyhow!("Failed to paint page background: {}", e))?;
+
     // Draw page border only for non-transparent backgrounds
     if !background.is_transparent() {
         ctx.set_source_rgb(0.0, 0.0, 0.0);
@@ -248,145 +385,44 @@ fn render_page_to_context_with_background(
             .map_err(|e| anyhow!("Failed to draw page border: {}", e))?;
     }
 
-    // Render all elements
-    for element in &page.elements {
+    // Render all elements, layer by layer
+    for element in page.layered_elements() {
         render_element_to_context(ctx, element, catalog)?;
     }
 
     Ok(())
 }
 
-/// Export single-page document to PNG
-fn export_single_page_png(
-    document: &Document,
-    output_path: &Path,
-    dpi: f64,
+/// Render a single element to Cairo context, applying its rotation and opacity
+fn render_element_to_context(
+    ctx: &Context,
+    element: &testruct_core::document::DocumentElement,
     catalog: &AssetCatalog,
 ) -> Result<()> {
-    let page = &document.pages[0];
-    render_page_to_png(page, output_path, dpi, catalog)
-}
+    use crate::canvas::rendering::paint_with_opacity;
 
-/// Export multi-page document to multiple PNG files
-fn export_multi_page_png(
-    document: &Document,
-    output_path: &Path,
-    dpi: f64,
-    catalog: &AssetCatalog,
-) -> Result<()> {
-    for (index, page) in document.pages.iter().enumerate() {
-        let page_num = index + 1;
-
-        // Create output filename with page number
-        let output_filename = if let Some(extension) = output_path.extension() {
-            let stem = output_path.file_stem().unwrap();
-            let stem_str = stem.to_string_lossy();
-            let ext_str = extension.to_string_lossy();
-            format!("{}_page_{}.{}", stem_str, page_num, ext_str)
-        } else {
-            format!("{}_page_{}.png", output_path.display(), page_num)
-        };
-
-        let page_path = output_path
-            .parent()
-            .unwrap_or_else(|| Path::new("."))
-            .join(&output_filename);
-
-        debug!("Rendering page {} to: {}", page_num, page_path.display());
-        render_page_to_png(page, &page_path, dpi, catalog)?;
+    // Hidden elements (and their children) are left out of the export
+    if !element.is_visible() {
+        return Ok(());
     }
 
-    info!(
-        "PNG export completed: {} pages exported to {}",
-        document.pages.len(),
-        output_path.display()
-    );
-    Ok(())
-}
-
-/// Render a single page to PNG file
-fn render_page_to_png(
-    _page: &testruct_core::document::Page,
-    output_path: &Path,
-    dpi: f64,
-    catalog: &AssetCatalog,
-) -> Result<()> {
-    // Use default page dimensions (A4: 595.28 x 841.89 points)
-    let width_points = 595.28;
-    let height_points = 841.89;
-    let width_inches = width_points / 72.0; // Assume 72 points per inch
-    let height_inches = height_points / 72.0;
-
-    let pixel_width = (width_inches * dpi) as i32;
-    let pixel_height = (height_inches * dpi) as i32;
-
-    debug!(
-        "PNG size: {}x{} pixels at {} DPI",
-        pixel_width, pixel_height, dpi
-    );
-
-    // Clamp to minimum size
-    let pixel_width = pixel_width.max(100);
-    let pixel_height = pixel_height.max(100);
-
-    // Create image surface
-    let surface = ImageSurface::create(Format::ARgb32, pixel_width, pixel_height)
-        .map_err(|e| anyhow!("Failed to create image surface: {}", e))?;
-
-    let ctx =
-        Context::new(&surface).map_err(|e| anyhow!("Failed to create Cairo context: {}", e))?;
-
-    // Scale context for DPI
-    let scale = dpi / 72.0;
-    ctx.scale(scale, scale);
-
-    // Render page
-    render_page_to_context(&ctx, _page, catalog)?;
-
-    // Write to file
-    let mut file = std::fs::File::create(output_path)
-        .map_err(|e| anyhow!("Failed to create output file: {}", e))?;
-
-    surface
-        .write_to_png(&mut file)
-        .map_err(|e| anyhow!("Failed to write PNG: {}", e))?;
-
-    info!("PNG exported: {}", output_path.display());
-    Ok(())
-}
-
-/// Render a single page to Cairo context
-fn render_page_to_context(
-    ctx: &Context,
-    page: &testruct_core::document::Page,
-    catalog: &AssetCatalog,
-) -> Result<()> {
-    // Set white background
-    ctx.set_source_rgb(1.0, 1.0, 1.0);
-    ctx.paint()
-        .map_err(|e| anyhow!("Failed to paint background: {}", e))?;
-
-    // Use default page dimensions (A4)
-    let width = 595.28;
-    let height = 841.89;
-
-    // Draw page border
-    ctx.set_source_rgb(0.0, 0.0, 0.0);
-    ctx.set_line_width(0.5);
-    ctx.rectangle(0.0, 0.0, width, height);
-    ctx.stroke()
-        .map_err(|e| anyhow!("Failed to draw page border: {}", e))?;
-
-    // Render all elements
-    for element in &page.elements {
-        render_element_to_context(ctx, element, catalog)?;
+    let render = || render_element_contents(ctx, element, catalog);
+    let rotation = element.rotation();
+    if rotation == 0.0 {
+        return paint_with_opacity(ctx, element.opacity(), render);
     }
 
-    Ok(())
+    ctx.save()
+        .map_err(|e| anyhow!("Failed to save context: {}", e))?;
+    crate::canvas::rendering::rotate_about_center(ctx, element.bounds(), rotation);
+    let result = paint_with_opacity(ctx, element.opacity(), render);
+    ctx.restore()
+        .map_err(|e| anyhow!("Failed to restore context: {}", e))?;
+    result
 }
 
-/// Render a single element to Cairo context
-fn render_element_to_context(
+/// Render a single element's contents to Cairo context
+fn render_element_contents(
     ctx: &Context,
     element: &testruct_core::document::DocumentElement,
     catalog: &AssetCatalog,
@@ -407,6 +443,7 @@ fn render_element_to_context(
                 image.source,
                 catalog,
                 &image.bounds,
+                image.fit,
             ) {
                 Ok(_) => {
                     debug!("Image rendered from asset catalog: {}", image.id);
@@ -430,6 +467,14 @@ fn render_element_to_context(
                 render_element_to_context(ctx, child, catalog)?;
             }
         }
+        DocumentElement::Table(table) => {
+            crate::canvas::rendering_table::draw_table(ctx, table)
+                .map_err(|e| anyhow!("Failed to render table: {}", e))?;
+        }
+        DocumentElement::QrCode(qr) => {
+            crate::canvas::rendering_qr::draw_qr_code(ctx, qr)
+                .map_err(|e| anyhow!("Failed to render QR code: {}", e))?;
+        }
     }
 
     Ok(())
@@ -446,54 +491,62 @@ fn render_shape_to_context(
     let y = shape.bounds.origin.y as f64;
     let width = shape.bounds.size.width as f64;
     let height = shape.bounds.size.height as f64;
+    let line_width = crate::canvas::shapes_rendering::stroke_line_width(shape.stroke_width);
+    crate::canvas::shapes_rendering::apply_line_style(ctx, shape.line_style, shape.stroke_width);
 
     // Render based on shape kind
     match shape.kind {
         ShapeKind::Rectangle => {
+            use crate::canvas::shapes_rendering::rounded_rectangle_path;
+            let corner_radius = shape.effective_corner_radius() as f64;
             // Draw fill color if present
             if let Some(fill) = &shape.fill {
-                ctx.set_source_rgb(fill.r as f64, fill.g as f64, fill.b as f64);
-                ctx.rectangle(x, y, width, height);
+                crate::canvas::shapes_rendering::set_fill_source(ctx, fill, &shape.bounds)?;
+                rounded_rectangle_path(ctx, &shape.bounds, corner_radius);
                 ctx.fill()
                     .map_err(|e| anyhow!("Failed to fill rectangle: {}", e))?;
             }
             // Draw stroke if present
-            if let Some(stroke) = &shape.stroke {
-                ctx.set_source_rgb(stroke.r as f64, stroke.g as f64, stroke.b as f64);
-                ctx.set_line_width(1.0);
-                ctx.rectangle(x, y, width, height);
+            if let (Some(stroke), Some(line_width)) = (&shape.stroke, line_width) {
+                crate::canvas::shapes_rendering::set_stroke_source(ctx, stroke);
+                ctx.set_line_width(line_width);
+                rounded_rectangle_path(ctx, &shape.bounds, corner_radius);
                 ctx.stroke()
                     .map_err(|e| anyhow!("Failed to stroke rectangle: {}", e))?;
             }
         }
         ShapeKind::Ellipse => {
+            // Build the path in a scaled space, then restore so the stroke
+            // width is not distorted by the scale
             ctx.save()
                 .map_err(|e| anyhow!("Failed to save context: {}", e))?;
             ctx.translate(x + width / 2.0, y + height / 2.0);
             ctx.scale(width / 2.0, height / 2.0);
             ctx.arc(0.0, 0.0, 1.0, 0.0, std::f64::consts::PI * 2.0);
+            ctx.restore()
+                .map_err(|e| anyhow!("Failed to restore context: {}", e))?;
 
             // Draw fill if present
             if let Some(fill) = &shape.fill {
-                ctx.set_source_rgb(fill.r as f64, fill.g as f64, fill.b as f64);
+                crate::canvas::shapes_rendering::set_fill_source(ctx, fill, &shape.bounds)?;
                 ctx.fill_preserve()
                     .map_err(|e| anyhow!("Failed to fill ellipse: {}", e))?;
             }
             // Draw stroke if present
-            if let Some(stroke) = &shape.stroke {
-                ctx.set_source_rgb(stroke.r as f64, stroke.g as f64, stroke.b as f64);
-                ctx.set_line_width(1.0);
+            if let (Some(stroke), Some(line_width)) = (&shape.stroke, line_width) {
+                crate::canvas::shapes_rendering::set_stroke_source(ctx, stroke);
+                ctx.set_line_width(line_width);
                 ctx.stroke()
                     .map_err(|e| anyhow!("Failed to stroke ellipse: {}", e))?;
+            } else {
+                ctx.new_path();
             }
-            ctx.restore()
-                .map_err(|e| anyhow!("Failed to restore context: {}", e))?;
         }
         ShapeKind::Line => {
             // Lines use stroke color
-            if let Some(stroke) = &shape.stroke {
-                ctx.set_source_rgb(stroke.r as f64, stroke.g as f64, stroke.b as f64);
-                ctx.set_line_width(1.0);
+            if let (Some(stroke), Some(line_width)) = (&shape.stroke, line_width) {
+                crate::canvas::shapes_rendering::set_stroke_source(ctx, stroke);
+                ctx.set_line_width(line_width);
                 ctx.move_to(x, y);
                 ctx.line_to(x + width, y + height);
                 ctx.stroke()
@@ -502,9 +555,9 @@ fn render_shape_to_context(
         }
         ShapeKind::Arrow => {
             // Arrows use stroke color for the line
-            if let Some(stroke) = &shape.stroke {
-                ctx.set_source_rgb(stroke.r as f64, stroke.g as f64, stroke.b as f64);
-                ctx.set_line_width(1.0);
+            if let (Some(stroke), Some(line_width)) = (&shape.stroke, line_width) {
+                crate::canvas::shapes_rendering::set_stroke_source(ctx, stroke);
+                ctx.set_line_width(line_width);
                 ctx.move_to(x, y);
                 ctx.line_to(x + width, y + height);
                 ctx.stroke()
@@ -512,22 +565,18 @@ fn render_shape_to_context(
             }
         }
         ShapeKind::Polygon => {
-            // Placeholder for polygon (same as rectangle)
-            if let Some(fill) = &shape.fill {
-                ctx.set_source_rgb(fill.r as f64, fill.g as f64, fill.b as f64);
-                ctx.rectangle(x, y, width, height);
-                ctx.fill()
-                    .map_err(|e| anyhow!("Failed to fill polygon: {}", e))?;
-            }
-            if let Some(stroke) = &shape.stroke {
-                ctx.set_source_rgb(stroke.r as f64, stroke.g as f64, stroke.b as f64);
-                ctx.set_line_width(1.0);
-                ctx.rectangle(x, y, width, height);
-                ctx.stroke()
-                    .map_err(|e| anyhow!("Failed to stroke polygon: {}", e))?;
-            }
+            crate::canvas::rendering::draw_polygon(
+                ctx,
+                &shape.bounds,
+                &shape.vertex_positions(),
+                &shape.stroke,
+                shape.stroke_width,
+                &shape.fill,
+            )
+            .map_err(|e| anyhow!("Failed to draw polygon: {}", e))?;
         }
     }
+    crate::canvas::shapes_rendering::clear_line_style(ctx);
 
     Ok(())
 }
@@ -564,6 +613,18 @@ fn render_text_to_context(
     ctx: &Context,
     text: &testruct_core::document::TextElement,
 ) -> Result<()> {
+    // Text on a path is drawn glyph by glyph, exactly like the canvas
+    if let Some(path) = &text.text_path {
+        return crate::canvas::rendering_text::draw_text_on_path(
+            ctx,
+            &text.bounds,
+            &text.content,
+            &text.style,
+            path,
+        )
+        .map_err(|e| anyhow!("Failed to render text on path: {}", e));
+    }
+
     ctx.save()
         .map_err(|e| anyhow!("Failed to save context: {}", e))?;
 
@@ -583,15 +644,13 @@ fn render_text_to_context(
             .map_err(|e| anyhow!("Failed to fill text background: {}", e))?;
     }
 
-    // Translate to text origin
-    ctx.translate(bounds.origin.x as f64, bounds.origin.y as f64);
-
-    // Create clipping rectangle
+    // Clip to the content rectangle inside the padding
+    let content = bounds.inset(&text.padding);
     ctx.rectangle(
-        0.0,
-        0.0,
-        bounds.size.width as f64,
-        bounds.size.height as f64,
+        content.origin.x as f64,
+        content.origin.y as f64,
+        content.size.width as f64,
+        content.size.height as f64,
     );
     ctx.clip();
 
@@ -623,13 +682,7 @@ fn render_text_to_context(
     layout.set_font_description(Some(&font_desc));
 
     // Apply text alignment
-    let pango_alignment = match style.alignment {
-        testruct_core::typography::TextAlignment::Start => pango::Alignment::Left,
-        testruct_core::typography::TextAlignment::Center => pango::Alignment::Center,
-        testruct_core::typography::TextAlignment::End => pango::Alignment::Right,
-        testruct_core::typography::TextAlignment::Justified => pango::Alignment::Center,
-    };
-    layout.set_alignment(pango_alignment);
+    crate::canvas::rendering_text::apply_text_alignment(&layout, style.alignment);
 
     // Configure for vertical or horizontal text
     if style.vertical {
@@ -639,10 +692,10 @@ fn render_text_to_context(
         pango_context.set_gravity_hint(pango::GravityHint::Strong);
         layout.context_changed();
         // For vertical text, use height as the constraint
-        layout.set_width((bounds.size.height as f64 * pango::SCALE as f64) as i32);
+        layout.set_width((content.size.height as f64 * pango::SCALE as f64) as i32);
     } else {
         // Horizontal text mode (横書き)
-        layout.set_width((bounds.size.width as f64 * pango::SCALE as f64) as i32);
+        layout.set_width((content.size.width as f64 * pango::SCALE as f64) as i32);
     }
 
     // Apply underline and strikethrough decorations
@@ -659,6 +712,15 @@ fn render_text_to_context(
         layout.set_attributes(Some(&attrs));
     }
 
+    // Rich text runs replace the element-wide decorations
+    if let Some(rich_text) = text.rich_text_for_render() {
+        let attrs = crate::canvas::rendering_text::rich_text_attributes(rich_text);
+        layout.set_attributes(Some(&attrs));
+    }
+
+    // Line height and letter spacing, matching the canvas
+    crate::canvas::rendering_text::apply_text_spacing(&layout, style);
+
     // Set text color
     ctx.set_source_rgb(
         style.color.r as f64,
@@ -666,10 +728,12 @@ fn render_text_to_context(
         style.color.b as f64,
     );
 
-    // Position for vertical text rendering
+    // Position like the canvas: padding and vertical alignment, then the
+    // 90 degree clockwise rotation for vertical text
+    let (origin_x, origin_y) =
+        crate::canvas::rendering_text::text_layout_origin(bounds, style, &text.padding, &layout);
+    ctx.translate(origin_x, origin_y);
     if style.vertical {
-        // Vertical text: rotate 90 degrees clockwise from top-right corner
-        ctx.translate(bounds.size.width as f64, 0.0);
         ctx.rotate(std::f64::consts::FRAC_PI_2);
     }
 
@@ -696,4 +760,47 @@ mod tests {
     fn test_default_dpi() {
         assert_eq!(DEFAULT_DPI, 96.0);
     }
+
+    #[test]
+    fn test_page_pixel_size_a4_2x() {
+        let a4 = testruct_core::document::PageSize::A4.to_size();
+        assert_eq!(page_pixel_size(a4, 2.0), (1190, 1684));
+        assert_eq!(page_pixel_size(a4, 1.0), (595, 842));
+    }
+
+    #[test]
+    fn test_render_page_thumbnail_keeps_aspect_ratio() {
+        let a4 = testruct_core::document::PageSize::A4.to_size();
+        let page = testruct_core::document::Page::empty();
+        let surface = render_page_thumbnail(&page, a4, 120, 120, &AssetCatalog::default()).unwrap();
+
+        // A4 is taller than wide, so the height hits the limit
+        assert_eq!(surface.height(), 120);
+        assert_eq!(surface.width(), 85);
+    }
+
+    #[test]
+    fn test_sample_page_color_reads_topmost_element() {
+        use testruct_core::document::{DocumentElement, Fill, Page};
+
+        let mut page = Page::empty();
+        let mut back = crate::canvas::tools::ShapeFactory::create_rectangle(0.0, 0.0, 100.0, 100.0);
+        let mut front =
+            crate::canvas::tools::ShapeFactory::create_rectangle(50.0, 0.0, 50.0, 100.0);
+        for (element, color) in [(&mut back, (1.0, 0.0, 0.0)), (&mut front, (0.0, 0.0, 1.0))] {
+            if let DocumentElement::Shape(shape) = element {
+                shape.fill = Some(Fill::Solid(Color::from_rgb(color.0, color.1, color.2)));
+                shape.stroke = None;
+            }
+        }
+        page.elements.push(back);
+        page.elements.push(front);
+
+        let size = Size::new(200.0, 200.0);
+        let catalog = AssetCatalog::default();
+        let sample = |x, y| sample_page_color(&page, size, Point::new(x, y), &catalog).unwrap();
+        assert_eq!(sample(25.0, 50.0), Some(Color::from_rgb(1.0, 0.0, 0.0)));
+        assert_eq!(sample(75.0, 50.0), Some(Color::from_rgb(0.0, 0.0, 1.0)));
+        assert_eq!(sample(150.0, 150.0), None);
+    }
 }
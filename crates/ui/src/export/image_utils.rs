@@ -2,7 +2,8 @@
 
 use cairo::Context;
 use std::path::Path;
-use testruct_core::layout::Rect;
+use testruct_core::document::{ImageFit, PageBackground};
+use testruct_core::layout::{Point, Rect, Size};
 use testruct_core::workspace::assets::{AssetCatalog, AssetRef};
 
 /// Render an image from AssetCatalog to a Cairo context
@@ -13,10 +14,11 @@ pub fn render_image_from_asset(
     asset_ref: AssetRef,
     catalog: &AssetCatalog,
     bounds: &Rect,
+    fit: ImageFit,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Get image metadata from catalog
     if let Some(metadata) = catalog.get(asset_ref) {
-        render_image_to_context(ctx, &metadata.path, bounds)
+        render_image_to_context(ctx, &metadata.path, bounds, fit)
     } else {
         // Asset not found in catalog - draw placeholder
         draw_image_placeholder(ctx, bounds).map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
@@ -25,24 +27,22 @@ pub fn render_image_from_asset(
 
 /// Load an image file and render it to a Cairo context
 ///
-/// Supports: JPEG, PNG, GIF, WebP
+/// Supports: JPEG, PNG, GIF, WebP. The image is placed in `bounds` according
+/// to `fit` and clipped to them.
 pub fn render_image_to_context(
     ctx: &Context,
     image_path: &Path,
     bounds: &Rect,
+    fit: ImageFit,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Load the image file
     let img = image::open(image_path)?;
 
-    // Resize image to match bounds while maintaining aspect ratio
-    let width = bounds.size.width as u32;
-    let height = bounds.size.height as u32;
-
-    let resized = if width > 0 && height > 0 {
-        img.resize_to_fill(width, height, image::imageops::FilterType::Lanczos3)
-    } else {
-        img
-    };
+    // Resample to the size the image is drawn at
+    let target = fit.image_rect(img.width() as f32, img.height() as f32, bounds);
+    let width = (target.size.width as u32).max(1);
+    let height = (target.size.height as u32).max(1);
+    let resized = img.resize_exact(width, height, image::imageops::FilterType::Lanczos3);
 
     // Convert to RGBA8 for Cairo compatibility
     let rgba = resized.to_rgba8();
@@ -80,12 +80,19 @@ pub fn render_image_to_context(
         stride,
     )?;
 
-    // Render the image surface to the context
+    // Render the image surface to the context, clipped to the element bounds
     ctx.save()?;
-    ctx.translate(bounds.origin.x as f64, bounds.origin.y as f64);
+    ctx.rectangle(
+        bounds.origin.x as f64,
+        bounds.origin.y as f64,
+        bounds.size.width as f64,
+        bounds.size.height as f64,
+    );
+    ctx.clip();
+    ctx.translate(target.origin.x as f64, target.origin.y as f64);
     ctx.scale(
-        bounds.size.width as f64 / width as f64,
-        bounds.size.height as f64 / height as f64,
+        target.size.width as f64 / width as f64,
+        target.size.height as f64 / height as f64,
     );
 
     ctx.set_source_surface(&surface, 0.0, 0.0)?;
@@ -102,6 +109,42 @@ pub fn render_image_to_context(
     Ok(())
 }
 
+/// Paint a page's own background over the export background
+///
+/// Background images that cannot be loaded are skipped with a warning.
+pub fn render_page_background(
+    ctx: &Context,
+    background: &PageBackground,
+    page_size: Size,
+    catalog: &AssetCatalog,
+) -> Result<(), cairo::Error> {
+    match background {
+        PageBackground::None => {}
+        PageBackground::Color(color) => {
+            ctx.set_source_rgba(
+                color.r as f64,
+                color.g as f64,
+                color.b as f64,
+                color.a as f64,
+            );
+            ctx.rectangle(0.0, 0.0, page_size.width as f64, page_size.height as f64);
+            ctx.fill()?;
+        }
+        PageBackground::Image { source, fit } => {
+            let bounds = Rect::new(Point::new(0.0, 0.0), page_size);
+            match catalog.get(*source) {
+                Some(metadata) => {
+                    if let Err(e) = render_image_to_context(ctx, &metadata.path, &bounds, *fit) {
+                        tracing::warn!("⚠️ Failed to render page background: {}", e);
+                    }
+                }
+                None => tracing::warn!("⚠️ Page background asset not found"),
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Check if an image file exists and is readable
 pub fn is_image_available(image_path: &Path) -> bool {
     if !image_path.exists() {
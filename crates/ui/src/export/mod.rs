@@ -1,18 +1,54 @@
 //! Document export functionality
 //!
-//! Provides PDF, PNG, SVG, and other format export capabilities using Cairo rendering.
+//! Provides PDF (including PDF/A), PNG, SVG, EPS and other format export
+//! capabilities using Cairo rendering.
 
+pub mod eps;
 pub mod image;
 pub mod image_utils;
 pub mod pdf;
+pub mod pdf_a;
 pub mod svg;
 
 use anyhow::Result;
 use std::path::Path;
+use testruct_core::layout::Size;
 use testruct_core::typography::Color;
 use testruct_core::workspace::assets::AssetCatalog;
 use testruct_core::Document;
 
+/// Point-based baseline used to convert between DPI and scale (1pt = 1/72 inch)
+pub const POINTS_PER_INCH: f64 = 72.0;
+
+/// Minimum allowed export scale factor
+pub const MIN_EXPORT_SCALE: f64 = 0.1;
+
+/// Maximum allowed export scale factor
+pub const MAX_EXPORT_SCALE: f64 = 10.0;
+
+/// Convert an export scale factor (1.0 = one pixel per point) to DPI
+pub fn scale_to_dpi(scale: f64) -> f64 {
+    scale * POINTS_PER_INCH
+}
+
+/// Convert a DPI value to an export scale factor
+pub fn dpi_to_scale(dpi: f64) -> f64 {
+    dpi / POINTS_PER_INCH
+}
+
+/// Check that an export scale factor is within the supported range
+pub fn validate_scale(scale: f64) -> Result<f64> {
+    if !scale.is_finite() || !(MIN_EXPORT_SCALE..=MAX_EXPORT_SCALE).contains(&scale) {
+        return Err(anyhow::anyhow!(
+            "Export scale {} is out of range ({}-{})",
+            scale,
+            MIN_EXPORT_SCALE,
+            MAX_EXPORT_SCALE
+        ));
+    }
+    Ok(scale)
+}
+
 /// Resolution scale presets for export
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ResolutionScale {
@@ -37,6 +73,16 @@ impl ResolutionScale {
         }
     }
 
+    /// Create a resolution from a page scale factor (e.g. 2.0 = "2× the page")
+    pub fn from_scale(scale: f64) -> Self {
+        ResolutionScale::Custom(scale_to_dpi(scale))
+    }
+
+    /// Get the page scale factor (output pixels per point)
+    pub fn to_scale(&self) -> f64 {
+        dpi_to_scale(self.to_dpi())
+    }
+
     /// Get the scale multiplier (1x, 2x, 3x)
     pub fn multiplier(&self) -> f64 {
         self.to_dpi() / 96.0
@@ -217,10 +263,127 @@ impl ExportConfig {
         self
     }
 
+    /// Set page scale factor, validating it against the supported range
+    pub fn with_scale(mut self, scale: f64) -> Result<Self> {
+        self.resolution = ResolutionScale::from_scale(validate_scale(scale)?);
+        Ok(self)
+    }
+
     /// Get DPI value for export
     pub fn dpi(&self) -> f64 {
         self.resolution.to_dpi()
     }
+
+    /// Get page scale factor for export (output pixels per point)
+    pub fn scale(&self) -> f64 {
+        self.resolution.to_scale()
+    }
+}
+
+/// Options for multi-page PNG export
+#[derive(Debug, Clone, PartialEq)]
+pub struct PngExportOptions {
+    /// Output resolution
+    pub dpi: f64,
+    /// Zero-based page indices to export (`None` exports every page)
+    pub pages: Option<Vec<usize>>,
+    /// Leave the page background transparent instead of painting it white
+    pub transparent_background: bool,
+}
+
+impl Default for PngExportOptions {
+    fn default() -> Self {
+        Self {
+            dpi: ResolutionScale::Standard.to_dpi(),
+            pages: None,
+            transparent_background: false,
+        }
+    }
+}
+
+impl PngExportOptions {
+    /// Export at the given DPI with default page selection and background
+    pub fn with_dpi(dpi: f64) -> Self {
+        Self {
+            dpi,
+            ..Default::default()
+        }
+    }
+
+    /// Resolve the selected pages against a document with `page_count` pages
+    pub fn page_indices(&self, page_count: usize) -> Result<Vec<usize>> {
+        match &self.pages {
+            None => Ok((0..page_count).collect()),
+            Some(pages) if pages.is_empty() => Err(anyhow::anyhow!("No pages selected for export")),
+            Some(pages) => {
+                if let Some(&index) = pages.iter().find(|&&index| index >= page_count) {
+                    return Err(anyhow::anyhow!("Page index {} out of bounds", index));
+                }
+                Ok(pages.clone())
+            }
+        }
+    }
+
+    /// Background to paint behind the page content
+    pub fn background(&self) -> BackgroundOption {
+        if self.transparent_background {
+            BackgroundOption::Transparent
+        } else {
+            BackgroundOption::White
+        }
+    }
+}
+
+/// Points per millimeter (1pt = 1/72 inch = 25.4/72 mm)
+pub const POINTS_PER_MM: f64 = POINTS_PER_INCH / 25.4;
+
+/// Gap between the bleed edge and the start of a crop mark, in points
+pub const CROP_MARK_OFFSET: f64 = 6.0;
+
+/// Length of a crop mark, in points
+pub const CROP_MARK_LENGTH: f64 = 18.0;
+
+/// Print-production options for PDF export
+///
+/// The defaults (no crop marks, no bleed) write each page at its trim size.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PdfExportOptions {
+    /// Draw crop marks at the trim box corners and registration marks on each side
+    pub crop_marks: bool,
+    /// Bleed added around the trim box, in millimeters
+    pub bleed_mm: f32,
+}
+
+impl PdfExportOptions {
+    /// Bleed in points (negative or invalid values count as no bleed)
+    pub fn bleed_points(&self) -> f64 {
+        if self.bleed_mm.is_finite() && self.bleed_mm > 0.0 {
+            self.bleed_mm as f64 * POINTS_PER_MM
+        } else {
+            0.0
+        }
+    }
+
+    /// Space added on each side of the trim box: the bleed plus room for the marks
+    pub fn margin(&self) -> f64 {
+        let marks = if self.crop_marks {
+            CROP_MARK_OFFSET + CROP_MARK_LENGTH
+        } else {
+            0.0
+        };
+        self.bleed_points() + marks
+    }
+
+    /// Whether the page is laid out for print (marks or bleed around the trim box)
+    pub fn is_print_layout(&self) -> bool {
+        self.margin() > 0.0
+    }
+
+    /// PDF media box size for a page with the given trim size
+    pub fn media_size(&self, trim: Size) -> (f64, f64) {
+        let margin = self.margin() * 2.0;
+        (trim.width as f64 + margin, trim.height as f64 + margin)
+    }
 }
 
 /// Export a document using ExportConfig
@@ -243,14 +406,34 @@ pub fn export_with_config(
             svg::render_to_svg_with_config(document, output_path, config, catalog)
         }
         ExportFormat::PDF => {
-            pdf::render_to_pdf(document, output_path, catalog)
+            pdf::render_to_pdf(document, output_path, &PdfExportOptions::default(), catalog)
         }
     }
 }
 
 /// Export a document to PDF format
 pub fn export_pdf(document: &Document, output_path: &Path, catalog: &AssetCatalog) -> Result<()> {
-    pdf::render_to_pdf(document, output_path, catalog)
+    pdf::render_to_pdf(document, output_path, &PdfExportOptions::default(), catalog)
+}
+
+/// Export a document to PDF with crop marks and bleed
+pub fn export_pdf_with_options(
+    document: &Document,
+    output_path: &Path,
+    options: &PdfExportOptions,
+    catalog: &AssetCatalog,
+) -> Result<()> {
+    pdf::render_to_pdf(document, output_path, options, catalog)
+}
+
+/// Export a document to PDF/A-1b for archiving
+pub fn export_pdf_a(document: &Document, output_path: &Path, catalog: &AssetCatalog) -> Result<()> {
+    pdf_a::render_to_pdf_a(document, output_path, catalog)
+}
+
+/// Export a document to EPS (one file per page for multi-page documents)
+pub fn export_eps(document: &Document, output_path: &Path, catalog: &AssetCatalog) -> Result<()> {
+    eps::render_to_eps(document, output_path, catalog)
 }
 
 /// Export a document to PNG format (legacy API)
@@ -260,7 +443,17 @@ pub fn export_png(
     dpi: f64,
     catalog: &AssetCatalog,
 ) -> Result<()> {
-    image::render_to_png(document, output_path, dpi, catalog)
+    image::render_to_png(document, output_path, &PngExportOptions::with_dpi(dpi), catalog)
+}
+
+/// Export selected pages of a document to PNG at a given DPI
+pub fn export_png_with_options(
+    document: &Document,
+    output_path: &Path,
+    options: &PngExportOptions,
+    catalog: &AssetCatalog,
+) -> Result<()> {
+    image::render_to_png(document, output_path, options, catalog)
 }
 
 /// Export a document to PNG format with full configuration
@@ -323,6 +516,25 @@ mod tests {
         assert_eq!(ResolutionScale::Ultra.multiplier(), 3.0);
     }
 
+    #[test]
+    fn test_scale_dpi_conversion() {
+        assert_eq!(scale_to_dpi(2.0), 144.0);
+        assert_eq!(dpi_to_scale(144.0), 2.0);
+        assert_eq!(ResolutionScale::from_scale(2.0).to_scale(), 2.0);
+        assert_eq!(ResolutionScale::Custom(72.0).to_scale(), 1.0);
+    }
+
+    #[test]
+    fn test_export_config_scale_bounds() {
+        assert!(ExportConfig::png().with_scale(0.05).is_err());
+        assert!(ExportConfig::png().with_scale(10.5).is_err());
+        assert!(ExportConfig::png().with_scale(f64::NAN).is_err());
+
+        let config = ExportConfig::png().with_scale(2.0).unwrap();
+        assert_eq!(config.scale(), 2.0);
+        assert_eq!(config.dpi(), 144.0);
+    }
+
     #[test]
     fn test_background_option_to_color() {
         assert!(BackgroundOption::Transparent.to_color().is_none());
@@ -333,6 +545,47 @@ mod tests {
         assert_eq!(color.r, 0.5);
     }
 
+    #[test]
+    fn test_png_export_options_page_indices() {
+        assert_eq!(PngExportOptions::default().page_indices(3).unwrap(), vec![0, 1, 2]);
+
+        let options = PngExportOptions {
+            pages: Some(vec![1, 2, 3]),
+            ..Default::default()
+        };
+        assert_eq!(options.page_indices(4).unwrap(), vec![1, 2, 3]);
+        assert!(options.page_indices(3).is_err());
+
+        let empty = PngExportOptions {
+            pages: Some(Vec::new()),
+            ..Default::default()
+        };
+        assert!(empty.page_indices(3).is_err());
+    }
+
+    #[test]
+    fn test_pdf_export_options_media_size() {
+        let trim = Size::new(595.0, 842.0);
+        let default = PdfExportOptions::default();
+        assert!(!default.is_print_layout());
+        assert_eq!(default.media_size(trim), (595.0, 842.0));
+
+        let bleed = PdfExportOptions {
+            crop_marks: false,
+            bleed_mm: 25.4,
+        };
+        assert!((bleed.bleed_points() - 72.0).abs() < 1e-3);
+        let (width, height) = bleed.media_size(trim);
+        assert!((width - 739.0).abs() < 1e-3 && (height - 986.0).abs() < 1e-3);
+
+        let marks = PdfExportOptions {
+            crop_marks: true,
+            bleed_mm: -3.0,
+        };
+        assert_eq!(marks.bleed_points(), 0.0);
+        assert_eq!(marks.margin(), CROP_MARK_OFFSET + CROP_MARK_LENGTH);
+    }
+
     #[test]
     fn test_export_format_extension() {
         assert_eq!(ExportFormat::PNG.extension(), "png");
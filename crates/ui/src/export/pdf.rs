@@ -1,47 +1,88 @@
 //! PDF export functionality using Cairo
 //!
 //! Renders a document to PDF format with support for multi-page output.
+//! With [`PdfExportOptions`] each page can get a bleed margin and crop and
+//! registration marks around its trim box for print production.
 
 use anyhow::{anyhow, Result};
-use cairo::{Context, PdfSurface};
+use cairo::{Context, PdfMetadata, PdfSurface};
 use std::path::Path;
+use testruct_core::document::DocumentMetadata;
+use testruct_core::layout::Size;
 use testruct_core::workspace::assets::AssetCatalog;
 use testruct_core::Document;
 use tracing::{debug, info};
 
-/// Default page size (A4: 595.28 x 841.89 points)
-const DEFAULT_PAGE_WIDTH: f64 = 595.28;
-const DEFAULT_PAGE_HEIGHT: f64 = 841.89;
+use crate::export::{PdfExportOptions, CROP_MARK_LENGTH, CROP_MARK_OFFSET};
 
 /// Render a document to PDF
 pub fn render_to_pdf(
     document: &Document,
     output_path: &Path,
+    options: &PdfExportOptions,
     catalog: &AssetCatalog,
 ) -> Result<()> {
     info!("Exporting to PDF: {}", output_path.display());
 
+    let surface = create_surface(document, output_path, options)?;
+    render_pages(&surface, document, options, catalog)?;
+    info!("PDF export completed: {}", output_path.display());
+
+    Ok(())
+}
+
+/// Create a PDF surface sized for the first page, with the document info set
+///
+/// Nothing is drawn yet, so callers can still restrict the PDF version or add
+/// metadata before calling [`render_pages`].
+pub(crate) fn create_surface(
+    document: &Document,
+    output_path: &Path,
+    options: &PdfExportOptions,
+) -> Result<PdfSurface> {
     if document.pages.is_empty() {
         return Err(anyhow!("Document has no pages to export"));
     }
 
-    // Use default page dimensions (A4) for now
-    let width = DEFAULT_PAGE_WIDTH;
-    let height = DEFAULT_PAGE_HEIGHT;
-
-    debug!("PDF page size: {} x {}", width, height);
+    debug!(
+        "PDF options: crop marks={}, bleed={}mm",
+        options.crop_marks, options.bleed_mm
+    );
 
-    // Create PDF surface
-    let surface = PdfSurface::new(width, height, output_path)
+    // The surface starts at the first page's size; each page sets its own
+    let (first_width, first_height) =
+        options.media_size(document.page_size_of(&document.pages[0]));
+    let surface = PdfSurface::new(first_width, first_height, output_path)
         .map_err(|e| anyhow!("Failed to create PDF surface: {}", e))?;
 
+    set_document_info(&surface, &document.metadata)?;
+    Ok(surface)
+}
+
+/// Render every page of `document` onto `surface` and finish the file
+pub(crate) fn render_pages(
+    surface: &PdfSurface,
+    document: &Document,
+    options: &PdfExportOptions,
+    catalog: &AssetCatalog,
+) -> Result<()> {
     let ctx =
-        Context::new(&surface).map_err(|e| anyhow!("Failed to create Cairo context: {}", e))?;
+        Context::new(surface).map_err(|e| anyhow!("Failed to create Cairo context: {}", e))?;
 
-    // Render each page
+    // Render each page at its own size (pages may mix sizes and orientations)
     for (page_index, page) in document.pages.iter().enumerate() {
-        debug!("Rendering page {}", page_index + 1);
-        render_page_to_context(&ctx, page, catalog)?;
+        let page_size = document.page_size_of(page);
+        debug!(
+            "Rendering page {} ({} x {} pt)",
+            page_index + 1,
+            page_size.width,
+            page_size.height
+        );
+        let (media_width, media_height) = options.media_size(page_size);
+        surface
+            .set_size(media_width, media_height)
+            .map_err(|e| anyhow!("Failed to set PDF page size: {}", e))?;
+        render_page_to_context(&ctx, page, page_size, options, catalog)?;
 
         // Move to next page (except for last page)
         if page_index < document.pages.len() - 1 {
@@ -51,16 +92,45 @@ pub fn render_to_pdf(
     }
 
     // Finish PDF
+    drop(ctx);
     surface.finish();
-    info!("PDF export completed: {}", output_path.display());
+    Ok(())
+}
 
+/// Write the document's title, author, subject and keywords to the PDF info
+///
+/// Empty fields are left out.
+fn set_document_info(surface: &PdfSurface, metadata: &DocumentMetadata) -> Result<()> {
+    let fields = [
+        (PdfMetadata::Title, metadata.title.clone()),
+        (PdfMetadata::Author, metadata.author.clone()),
+        (PdfMetadata::Subject, metadata.subject.clone()),
+        (PdfMetadata::Keywords, metadata.keywords_text()),
+        (PdfMetadata::Creator, crate::dialogs::APP_NAME.to_string()),
+    ];
+    for (field, value) in fields {
+        if value.trim().is_empty() {
+            continue;
+        }
+        surface
+            .set_metadata(field, &value)
+            .map_err(|e| anyhow!("Failed to set PDF metadata: {}", e))?;
+    }
     Ok(())
 }
 
 /// Render a single page to Cairo context
-fn render_page_to_context(
+///
+/// The trim box sits `options.margin()` in from the media box corner. The page
+/// background fills the bleed and elements may extend into it; anything
+/// beyond the bleed is clipped so the marks area stays clean.
+///
+/// Shared with the other vector exporters (EPS).
+pub(crate) fn render_page_to_context(
     ctx: &Context,
     page: &testruct_core::document::Page,
+    page_size: Size,
+    options: &PdfExportOptions,
     catalog: &AssetCatalog,
 ) -> Result<()> {
     // Set white background
@@ -68,30 +138,147 @@ fn render_page_to_context(
     ctx.paint()
         .map_err(|e| anyhow!("Failed to paint background: {}", e))?;
 
-    // Use default page size
-    let width = DEFAULT_PAGE_WIDTH;
-    let height = DEFAULT_PAGE_HEIGHT;
+    let width = page_size.width as f64;
+    let height = page_size.height as f64;
+    let margin = options.margin();
+    let bleed = options.bleed_points();
 
-    // Draw page border for visual reference
-    ctx.set_source_rgb(0.0, 0.0, 0.0);
-    ctx.set_line_width(0.5);
-    ctx.rectangle(0.0, 0.0, width, height);
-    ctx.stroke()
-        .map_err(|e| anyhow!("Failed to draw page border: {}", e))?;
+    ctx.save()
+        .map_err(|e| anyhow!("Failed to save context: {}", e))?;
+    ctx.translate(margin, margin);
+    if options.is_print_layout() {
+        ctx.rectangle(-bleed, -bleed, width + bleed * 2.0, height + bleed * 2.0);
+        ctx.clip();
+    }
 
-    // Render all elements
-    for element in &page.elements {
+    // Paint the page's own background, extended over the bleed
+    ctx.save()
+        .map_err(|e| anyhow!("Failed to save context: {}", e))?;
+    ctx.translate(-bleed, -bleed);
+    crate::export::image_utils::render_page_background(
+        ctx,
+        &page.metadata.background,
+        Size::new(
+            (width + bleed * 2.0) as f32,
+            (height + bleed * 2.0) as f32,
+        ),
+        catalog,
+    )
+    .map_err(|e| anyhow!("Failed to paint page background: {}", e))?;
+    ctx.restore()
+        .map_err(|e| anyhow!("Failed to restore context: {}", e))?;
+
+    // Draw page border for visual reference (print layouts use crop marks instead)
+    if !options.is_print_layout() {
+        ctx.set_source_rgb(0.0, 0.0, 0.0);
+        ctx.set_line_width(0.5);
+        ctx.rectangle(0.0, 0.0, width, height);
+        ctx.stroke()
+            .map_err(|e| anyhow!("Failed to draw page border: {}", e))?;
+    }
+
+    // Render all elements, layer by layer
+    for element in page.layered_elements() {
         render_element_to_context(ctx, element, catalog)?;
     }
 
+    ctx.restore()
+        .map_err(|e| anyhow!("Failed to restore context: {}", e))?;
+
+    if options.crop_marks {
+        draw_printer_marks(ctx, page_size, options)
+            .map_err(|e| anyhow!("Failed to draw crop marks: {}", e))?;
+    }
+
     Ok(())
 }
 
-/// Render a single element to Cairo context
+/// Draw crop marks at the trim box corners and a registration mark on each side
+///
+/// Marks start outside the bleed so they never print on the trimmed page.
+fn draw_printer_marks(
+    ctx: &Context,
+    page_size: Size,
+    options: &PdfExportOptions,
+) -> Result<(), cairo::Error> {
+    let margin = options.margin();
+    let (left, top) = (margin, margin);
+    let right = left + page_size.width as f64;
+    let bottom = top + page_size.height as f64;
+    let start = options.bleed_points() + CROP_MARK_OFFSET;
+    let end = start + CROP_MARK_LENGTH;
+
+    ctx.set_source_rgb(0.0, 0.0, 0.0);
+    ctx.set_line_width(0.25);
+
+    // Crop marks: one horizontal and one vertical line per corner, pointing
+    // outwards along the trim edges
+    for (x, y, out_x, out_y) in [
+        (left, top, -1.0, -1.0),
+        (right, top, 1.0, -1.0),
+        (left, bottom, -1.0, 1.0),
+        (right, bottom, 1.0, 1.0),
+    ] {
+        ctx.move_to(x + out_x * start, y);
+        ctx.line_to(x + out_x * end, y);
+        ctx.move_to(x, y + out_y * start);
+        ctx.line_to(x, y + out_y * end);
+    }
+    ctx.stroke()?;
+
+    // Registration marks: a circle with a cross hair centered on each side
+    let center = (start + end) / 2.0;
+    let radius = CROP_MARK_LENGTH / 4.0;
+    let (mid_x, mid_y) = ((left + right) / 2.0, (top + bottom) / 2.0);
+    for (x, y) in [
+        (mid_x, top - center),
+        (mid_x, bottom + center),
+        (left - center, mid_y),
+        (right + center, mid_y),
+    ] {
+        ctx.new_sub_path();
+        ctx.arc(x, y, radius, 0.0, std::f64::consts::TAU);
+        ctx.move_to(x - CROP_MARK_LENGTH / 2.0, y);
+        ctx.line_to(x + CROP_MARK_LENGTH / 2.0, y);
+        ctx.move_to(x, y - CROP_MARK_LENGTH / 2.0);
+        ctx.line_to(x, y + CROP_MARK_LENGTH / 2.0);
+    }
+    ctx.stroke()
+}
+
+/// Render a single element to Cairo context, applying its rotation and opacity
 fn render_element_to_context(
     ctx: &Context,
     element: &testruct_core::document::DocumentElement,
     catalog: &AssetCatalog,
+) -> Result<()> {
+    use crate::canvas::rendering::paint_with_opacity;
+
+    // Hidden elements (and their children) are left out of the export
+    if !element.is_visible() {
+        return Ok(());
+    }
+
+    let render = || render_element_contents(ctx, element, catalog);
+    let rotation = element.rotation();
+    if rotation == 0.0 {
+        return paint_with_opacity(ctx, element.opacity(), render);
+    }
+
+    ctx.save()
+        .map_err(|e| anyhow!("Failed to save context: {}", e))?;
+    crate::canvas::rendering::rotate_about_center(ctx, element.bounds(), rotation);
+    let result = paint_with_opacity(ctx, element.opacity(), render);
+    ctx.restore()
+        .map_err(|e| anyhow!("Failed to restore context: {}", e))?;
+    result
+}
+
+/// Render a single element's contents to Cairo context
+fn render_element_contents(
+    ctx: &Context,
+    element: &testruct_core::document::DocumentElement,
+    catalog: &AssetCatalog,
 ) -> Result<()> {
     use testruct_core::document::DocumentElement;
 
@@ -109,6 +296,7 @@ fn render_element_to_context(
                 image.source,
                 catalog,
                 &image.bounds,
+                image.fit,
             ) {
                 Ok(_) => {
                     debug!("Image rendered from asset catalog: {}", image.id);
@@ -132,6 +320,14 @@ fn render_element_to_context(
                 render_element_to_context(ctx, child, catalog)?;
             }
         }
+        DocumentElement::Table(table) => {
+            crate::canvas::rendering_table::draw_table(ctx, table)
+                .map_err(|e| anyhow!("Failed to render table: {}", e))?;
+        }
+        DocumentElement::QrCode(qr) => {
+            crate::canvas::rendering_qr::draw_qr_code(ctx, qr)
+                .map_err(|e| anyhow!("Failed to render QR code: {}", e))?;
+        }
     }
 
     Ok(())
@@ -148,54 +344,62 @@ fn render_shape_to_context(
     let y = shape.bounds.origin.y as f64;
     let width = shape.bounds.size.width as f64;
     let height = shape.bounds.size.height as f64;
+    let line_width = crate::canvas::shapes_rendering::stroke_line_width(shape.stroke_width);
+    crate::canvas::shapes_rendering::apply_line_style(ctx, shape.line_style, shape.stroke_width);
 
     // Render based on shape kind
     match shape.kind {
         ShapeKind::Rectangle => {
+            use crate::canvas::shapes_rendering::rounded_rectangle_path;
+            let corner_radius = shape.effective_corner_radius() as f64;
             // Draw fill color if present
             if let Some(fill) = &shape.fill {
-                ctx.set_source_rgb(fill.r as f64, fill.g as f64, fill.b as f64);
-                ctx.rectangle(x, y, width, height);
+                crate::canvas::shapes_rendering::set_fill_source(ctx, fill, &shape.bounds)?;
+                rounded_rectangle_path(ctx, &shape.bounds, corner_radius);
                 ctx.fill()
                     .map_err(|e| anyhow!("Failed to fill rectangle: {}", e))?;
             }
             // Draw stroke if present
-            if let Some(stroke) = &shape.stroke {
-                ctx.set_source_rgb(stroke.r as f64, stroke.g as f64, stroke.b as f64);
-                ctx.set_line_width(1.0);
-                ctx.rectangle(x, y, width, height);
+            if let (Some(stroke), Some(line_width)) = (&shape.stroke, line_width) {
+                crate::canvas::shapes_rendering::set_stroke_source(ctx, stroke);
+                ctx.set_line_width(line_width);
+                rounded_rectangle_path(ctx, &shape.bounds, corner_radius);
                 ctx.stroke()
                     .map_err(|e| anyhow!("Failed to stroke rectangle: {}", e))?;
             }
         }
         ShapeKind::Ellipse => {
+            // Build the path in a scaled space, then restore so the stroke
+            // width is not distorted by the scale
             ctx.save()
                 .map_err(|e| anyhow!("Failed to save context: {}", e))?;
             ctx.translate(x + width / 2.0, y + height / 2.0);
             ctx.scale(width / 2.0, height / 2.0);
             ctx.arc(0.0, 0.0, 1.0, 0.0, std::f64::consts::PI * 2.0);
+            ctx.restore()
+                .map_err(|e| anyhow!("Failed to restore context: {}", e))?;
 
             // Draw fill if present
             if let Some(fill) = &shape.fill {
-                ctx.set_source_rgb(fill.r as f64, fill.g as f64, fill.b as f64);
+                crate::canvas::shapes_rendering::set_fill_source(ctx, fill, &shape.bounds)?;
                 ctx.fill_preserve()
                     .map_err(|e| anyhow!("Failed to fill ellipse: {}", e))?;
             }
             // Draw stroke if present
-            if let Some(stroke) = &shape.stroke {
-                ctx.set_source_rgb(stroke.r as f64, stroke.g as f64, stroke.b as f64);
-                ctx.set_line_width(1.0);
+            if let (Some(stroke), Some(line_width)) = (&shape.stroke, line_width) {
+                crate::canvas::shapes_rendering::set_stroke_source(ctx, stroke);
+                ctx.set_line_width(line_width);
                 ctx.stroke()
                     .map_err(|e| anyhow!("Failed to stroke ellipse: {}", e))?;
+            } else {
+                ctx.new_path();
             }
-            ctx.restore()
-                .map_err(|e| anyhow!("Failed to restore context: {}", e))?;
         }
         ShapeKind::Line => {
             // Lines use stroke color
-            if let Some(stroke) = &shape.stroke {
-                ctx.set_source_rgb(stroke.r as f64, stroke.g as f64, stroke.b as f64);
-                ctx.set_line_width(1.0);
+            if let (Some(stroke), Some(line_width)) = (&shape.stroke, line_width) {
+                crate::canvas::shapes_rendering::set_stroke_source(ctx, stroke);
+                ctx.set_line_width(line_width);
                 ctx.move_to(x, y);
                 ctx.line_to(x + width, y + height);
                 ctx.stroke()
@@ -204,9 +408,9 @@ fn render_shape_to_context(
         }
         ShapeKind::Arrow => {
             // Arrows use stroke color for the line
-            if let Some(stroke) = &shape.stroke {
-                ctx.set_source_rgb(stroke.r as f64, stroke.g as f64, stroke.b as f64);
-                ctx.set_line_width(1.0);
+            if let (Some(stroke), Some(line_width)) = (&shape.stroke, line_width) {
+                crate::canvas::shapes_rendering::set_stroke_source(ctx, stroke);
+                ctx.set_line_width(line_width);
                 ctx.move_to(x, y);
                 ctx.line_to(x + width, y + height);
                 ctx.stroke()
@@ -214,22 +418,18 @@ fn render_shape_to_context(
             }
         }
         ShapeKind::Polygon => {
-            // Placeholder for polygon (same as rectangle)
-            if let Some(fill) = &shape.fill {
-                ctx.set_source_rgb(fill.r as f64, fill.g as f64, fill.b as f64);
-                ctx.rectangle(x, y, width, height);
-                ctx.fill()
-                    .map_err(|e| anyhow!("Failed to fill polygon: {}", e))?;
-            }
-            if let Some(stroke) = &shape.stroke {
-                ctx.set_source_rgb(stroke.r as f64, stroke.g as f64, stroke.b as f64);
-                ctx.set_line_width(1.0);
-                ctx.rectangle(x, y, width, height);
-                ctx.stroke()
-                    .map_err(|e| anyhow!("Failed to stroke polygon: {}", e))?;
-            }
+            crate::canvas::rendering::draw_polygon(
+                ctx,
+                &shape.bounds,
+                &shape.vertex_positions(),
+                &shape.stroke,
+                shape.stroke_width,
+                &shape.fill,
+            )
+            .map_err(|e| anyhow!("Failed to draw polygon: {}", e))?;
         }
     }
+    crate::canvas::shapes_rendering::clear_line_style(ctx);
 
     Ok(())
 }
@@ -266,6 +466,18 @@ fn render_text_to_context(
     ctx: &Context,
     text: &testruct_core::document::TextElement,
 ) -> Result<()> {
+    // Text on a path is drawn glyph by glyph, exactly like the canvas
+    if let Some(path) = &text.text_path {
+        return crate::canvas::rendering_text::draw_text_on_path(
+            ctx,
+            &text.bounds,
+            &text.content,
+            &text.style,
+            path,
+        )
+        .map_err(|e| anyhow!("Failed to render text on path: {}", e));
+    }
+
     ctx.save()
         .map_err(|e| anyhow!("Failed to save context: {}", e))?;
 
@@ -285,15 +497,13 @@ fn render_text_to_context(
             .map_err(|e| anyhow!("Failed to fill text background: {}", e))?;
     }
 
-    // Translate to text origin
-    ctx.translate(bounds.origin.x as f64, bounds.origin.y as f64);
-
-    // Create clipping rectangle
+    // Clip to the content rectangle inside the padding
+    let content = bounds.inset(&text.padding);
     ctx.rectangle(
-        0.0,
-        0.0,
-        bounds.size.width as f64,
-        bounds.size.height as f64,
+        content.origin.x as f64,
+        content.origin.y as f64,
+        content.size.width as f64,
+        content.size.height as f64,
     );
     ctx.clip();
 
@@ -325,13 +535,7 @@ fn render_text_to_context(
     layout.set_font_description(Some(&font_desc));
 
     // Apply text alignment
-    let pango_alignment = match style.alignment {
-        testruct_core::typography::TextAlignment::Start => pango::Alignment::Left,
-        testruct_core::typography::TextAlignment::Center => pango::Alignment::Center,
-        testruct_core::typography::TextAlignment::End => pango::Alignment::Right,
-        testruct_core::typography::TextAlignment::Justified => pango::Alignment::Center,
-    };
-    layout.set_alignment(pango_alignment);
+    crate::canvas::rendering_text::apply_text_alignment(&layout, style.alignment);
 
     // Configure for vertical or horizontal text
     if style.vertical {
@@ -341,10 +545,10 @@ fn render_text_to_context(
         pango_context.set_gravity_hint(pango::GravityHint::Strong);
         layout.context_changed();
         // For vertical text, use height as the constraint
-        layout.set_width((bounds.size.height as f64 * pango::SCALE as f64) as i32);
+        layout.set_width((content.size.height as f64 * pango::SCALE as f64) as i32);
     } else {
         // Horizontal text mode (横書き)
-        layout.set_width((bounds.size.width as f64 * pango::SCALE as f64) as i32);
+        layout.set_width((content.size.width as f64 * pango::SCALE as f64) as i32);
     }
 
     // Apply underline and strikethrough decorations
@@ -361,6 +565,15 @@ fn render_text_to_context(
         layout.set_attributes(Some(&attrs));
     }
 
+    // Rich text runs replace the element-wide decorations
+    if let Some(rich_text) = text.rich_text_for_render() {
+        let attrs = crate::canvas::rendering_text::rich_text_attributes(rich_text);
+        layout.set_attributes(Some(&attrs));
+    }
+
+    // Line height and letter spacing, matching the canvas
+    crate::canvas::rendering_text::apply_text_spacing(&layout, style);
+
     // Set text color
     ctx.set_source_rgb(
         style.color.r as f64,
@@ -368,10 +581,12 @@ fn render_text_to_context(
         style.color.b as f64,
     );
 
-    // Position for vertical text rendering
+    // Position like the canvas: padding and vertical alignment, then the
+    // 90 degree clockwise rotation for vertical text
+    let (origin_x, origin_y) =
+        crate::canvas::rendering_text::text_layout_origin(bounds, style, &text.padding, &layout);
+    ctx.translate(origin_x, origin_y);
     if style.vertical {
-        // Vertical text: rotate 90 degrees clockwise from top-right corner
-        ctx.translate(bounds.size.width as f64, 0.0);
         ctx.rotate(std::f64::consts::FRAC_PI_2);
     }
 
@@ -387,11 +602,60 @@ fn render_text_to_context(
 
 #[cfg(test)]
 mod tests {
-    #[allow(unused_imports)]
     use super::*;
+    use crate::export::POINTS_PER_MM;
+
+    /// Page sizes from the `/MediaBox` entries of a PDF without object streams
+    fn media_boxes(pdf: &str) -> Vec<(f64, f64)> {
+        pdf.split("/MediaBox [")
+            .skip(1)
+            .filter_map(|rest| {
+                let numbers: Vec<f64> = rest
+                    .split(']')
+                    .next()?
+                    .split_whitespace()
+                    .filter_map(|number| number.parse().ok())
+                    .collect();
+                match numbers[..] {
+                    [_, _, width, height] => Some((width, height)),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
 
     #[test]
     fn test_pdf_export_module_loads() {
         // Module loads successfully if this compiles and runs
     }
+
+    #[test]
+    fn test_crop_marks_and_bleed_enlarge_every_page() {
+        let mut document = Document::empty("Print");
+        document.pages.push(testruct_core::document::Page::empty());
+        let options = PdfExportOptions {
+            crop_marks: true,
+            bleed_mm: 3.0,
+        };
+        let path = std::env::temp_dir().join(format!("testruct-print-{}.pdf", std::process::id()));
+
+        let surface = create_surface(&document, &path, &options).unwrap();
+        // PDF 1.4 has no object streams, so the page dictionaries stay readable
+        surface.restrict(cairo::PdfVersion::_1_4).unwrap();
+        render_pages(&surface, &document, &options, &AssetCatalog::new()).unwrap();
+        let pdf = String::from_utf8_lossy(&std::fs::read(&path).unwrap()).into_owned();
+        std::fs::remove_file(&path).unwrap();
+
+        // The A4 trim box plus the bleed and room for the marks on every side
+        let margin = 3.0 * POINTS_PER_MM + CROP_MARK_OFFSET + CROP_MARK_LENGTH;
+        let trim = document.page_size_of(&document.pages[0]);
+        let expected_width = trim.width as f64 + 2.0 * margin;
+        let expected_height = trim.height as f64 + 2.0 * margin;
+        let boxes = media_boxes(&pdf);
+        assert_eq!(boxes.len(), 2, "one media box per page");
+        for (width, height) in boxes {
+            assert!((width - expected_width).abs() < 0.01, "width {}", width);
+            assert!((height - expected_height).abs() < 0.01, "height {}", height);
+        }
+    }
 }
@@ -0,0 +1,610 @@
+//! PDF/A export for archiving
+//!
+//! Cairo embeds (subsets of) every font it draws with, but it cannot write the
+//! XMP metadata or the output intent PDF/A requires. The document is rendered
+//! as PDF 1.4 and an incremental update is then appended that adds:
+//!
+//! - an XMP packet declaring PDF/A-1b, mirroring the document info
+//!   (title, author, subject, keywords, creator, producer and dates)
+//! - an sRGB output intent with an embedded ICC profile
+//!
+//! Transparency (element opacity below 100%) is not allowed by PDF/A-1, so
+//! documents that use it are refused rather than written non-conformant.
+
+use anyhow::{anyhow, Result};
+use cairo::{PdfMetadata, PdfVersion};
+use std::path::Path;
+use testruct_core::document::{DocumentElement, DocumentMetadata};
+use testruct_core::workspace::assets::AssetCatalog;
+use testruct_core::Document;
+use tracing::info;
+
+use crate::export::{pdf, PdfExportOptions};
+
+/// Name of the color space of the embedded output intent
+const OUTPUT_CONDITION: &str = "sRGB IEC61966-2.1";
+
+/// Document info mirrored into the XMP packet
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PdfAInfo {
+    pub title: String,
+    pub author: String,
+    pub subject: String,
+    pub keywords: String,
+    pub creator_tool: String,
+    pub producer: String,
+    /// ISO 8601 timestamp used for both the creation and modification date
+    pub date: String,
+}
+
+/// Render a document to a PDF/A-1b file
+///
+/// Fails without writing anything if a visible element is transparent.
+pub fn render_to_pdf_a(
+    document: &Document,
+    output_path: &Path,
+    catalog: &AssetCatalog,
+) -> Result<()> {
+    info!("Exporting to PDF/A: {}", output_path.display());
+
+    if let Some((page, id)) = transparent_element(document) {
+        return Err(anyhow!(
+            "PDF/A-1 does not allow transparency: element {} on page {} has opacity below 100%",
+            id,
+            page
+        ));
+    }
+
+    let date = chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false);
+    let options = PdfExportOptions::default();
+    let surface = pdf::create_surface(document, output_path, &options)?;
+    surface
+        .restrict(PdfVersion::_1_4)
+        .map_err(|e| anyhow!("Failed to restrict PDF version: {}", e))?;
+    for field in [PdfMetadata::CreateDate, PdfMetadata::ModDate] {
+        surface
+            .set_metadata(field, &date)
+            .map_err(|e| anyhow!("Failed to set PDF metadata: {}", e))?;
+    }
+    pdf::render_pages(&surface, document, &options, catalog)?;
+
+    let bytes = std::fs::read(output_path).map_err(|e| anyhow!("Failed to read PDF: {}", e))?;
+    let info = PdfAInfo::new(&document.metadata, producer_of(&bytes), date);
+    let archived = append_pdf_a_metadata(&bytes, &info)?;
+    std::fs::write(output_path, archived).map_err(|e| anyhow!("Failed to write PDF: {}", e))?;
+
+    info!("PDF/A export completed: {}", output_path.display());
+    Ok(())
+}
+
+/// 1-based page number and id of the first visible element with opacity below 100%
+///
+/// Frame and group children are checked too; hidden elements are not exported.
+pub fn transparent_element(document: &Document) -> Option<(usize, uuid::Uuid)> {
+    fn find(elements: &[DocumentElement]) -> Option<uuid::Uuid> {
+        elements
+            .iter()
+            .filter(|element| element.is_visible())
+            .find_map(|element| {
+                if element.opacity() < 1.0 {
+                    return Some(element.id());
+                }
+                element.children().and_then(find)
+            })
+    }
+
+    document
+        .pages
+        .iter()
+        .enumerate()
+        .find_map(|(index, page)| find(&page.elements).map(|id| (index + 1, id)))
+}
+
+impl PdfAInfo {
+    pub fn new(metadata: &DocumentMetadata, producer: String, date: String) -> Self {
+        Self {
+            title: metadata.title.clone(),
+            author: metadata.author.clone(),
+            subject: metadata.subject.clone(),
+            keywords: metadata.keywords_text(),
+            creator_tool: crate::dialogs::APP_NAME.to_string(),
+            producer,
+            date,
+        }
+    }
+}
+
+/// The `/Producer` string Cairo wrote to the document info (empty if missing)
+///
+/// PDF literal strings may contain balanced or escaped parentheses.
+fn producer_of(pdf: &[u8]) -> String {
+    let text = String::from_utf8_lossy(pdf);
+    let Some(start) = text.rfind("/Producer (") else {
+        return String::new();
+    };
+    let mut producer = String::new();
+    let mut depth = 0;
+    let mut chars = text[start + "/Producer (".len()..].chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => producer.extend(chars.next()),
+            '(' => {
+                depth += 1;
+                producer.push(c);
+            }
+            ')' if depth == 0 => break,
+            ')' => {
+                depth -= 1;
+                producer.push(c);
+            }
+            _ => producer.push(c),
+        }
+    }
+    producer
+}
+
+/// Append an incremental update adding PDF/A metadata and an sRGB output intent
+///
+/// Works on PDFs with a classic cross-reference table (as Cairo writes for
+/// PDF 1.4). The catalog is rewritten with `/Metadata` and `/OutputIntents`;
+/// all other objects are left untouched.
+pub fn append_pdf_a_metadata(pdf: &[u8], info: &PdfAInfo) -> Result<Vec<u8>> {
+    let text = String::from_utf8_lossy(pdf);
+    let unsupported = || anyhow!("Unsupported PDF structure for PDF/A conversion");
+
+    let startxref = text.rfind("startxref").ok_or_else(unsupported)?;
+    let prev_xref: usize = text[startxref + "startxref".len()..]
+        .split_whitespace()
+        .next()
+        .and_then(|offset| offset.parse().ok())
+        .ok_or_else(unsupported)?;
+    let trailer_start = text[..startxref].rfind("trailer").ok_or_else(unsupported)?;
+    let trailer = &text[trailer_start..startxref];
+
+    let size: usize = dict_value(trailer, "/Size")
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(unsupported)?;
+    let root: usize = dict_value(trailer, "/Root")
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(unsupported)?;
+    let info_ref = dict_value(trailer, "/Info").map(|number| format!(" /Info {} 0 R", number));
+    let id = match trailer.find("/ID") {
+        Some(start) => {
+            let end = trailer[start..].find(']').ok_or_else(unsupported)?;
+            trailer[start..start + end + 1].to_string()
+        }
+        None => {
+            let id = uuid::Uuid::new_v4().simple().to_string();
+            format!("/ID [<{id}> <{id}>]")
+        }
+    };
+
+    // Catalog dictionary without its closing `>>`
+    let catalog_header = format!("\n{} 0 obj", root);
+    let catalog_start = text.rfind(&catalog_header).ok_or_else(unsupported)? + catalog_header.len();
+    let catalog_end = catalog_start + text[catalog_start..].find("endobj").ok_or_else(unsupported)?;
+    let catalog = text[catalog_start..catalog_end].trim();
+    let catalog = catalog
+        .strip_suffix(">>")
+        .ok_or_else(unsupported)?
+        .trim_end();
+
+    let icc_id = size;
+    let intent_id = size + 1;
+    let metadata_id = size + 2;
+
+    let mut out = pdf.to_vec();
+    if !out.ends_with(b"\n") {
+        out.push(b'\n');
+    }
+    let mut offsets = Vec::new();
+
+    offsets.push((root, out.len()));
+    out.extend_from_slice(
+        format!(
+            "{} 0 obj\n{}\n   /Metadata {} 0 R\n   /OutputIntents [{} 0 R]\n>>\nendobj\n",
+            root, catalog, metadata_id, intent_id
+        )
+        .as_bytes(),
+    );
+
+    let profile = srgb_icc_profile();
+    offsets.push((icc_id, out.len()));
+    out.extend_from_slice(
+        format!("{} 0 obj\n<< /N 3 /Length {} >>\nstream\n", icc_id, profile.len()).as_bytes(),
+    );
+    out.extend_from_slice(&profile);
+    out.extend_from_slice(b"\nendstream\nendobj\n");
+
+    offsets.push((intent_id, out.len()));
+    out.extend_from_slice(
+        format!(
+            "{} 0 obj\n<< /Type /OutputIntent /S /GTS_PDFA1 \
+             /OutputConditionIdentifier ({cond}) /Info ({cond}) \
+             /DestOutputProfile {} 0 R >>\nendobj\n",
+            intent_id,
+            icc_id,
+            cond = OUTPUT_CONDITION
+        )
+        .as_bytes(),
+    );
+
+    let xmp = xmp_packet(info);
+    offsets.push((metadata_id, out.len()));
+    out.extend_from_slice(
+        format!(
+            "{} 0 obj\n<< /Type /Metadata /Subtype /XML /Length {} >>\nstream\n",
+            metadata_id,
+            xmp.len()
+        )
+        .as_bytes(),
+    );
+    out.extend_from_slice(xmp.as_bytes());
+    out.extend_from_slice(b"\nendstream\nendobj\n");
+
+    // Cross-reference section: the rewritten catalog, then the new objects
+    let xref_offset = out.len();
+    let mut xref = format!("xref\n{} 1\n{:010} 00000 n \n{} 3\n", root, offsets[0].1, size);
+    for (_, offset) in &offsets[1..] {
+        xref.push_str(&format!("{:010} 00000 n \n", offset));
+    }
+    xref.push_str(&format!(
+        "trailer\n<< /Size {} /Root {} 0 R{} {} /Prev {} >>\nstartxref\n{}\n%%EOF\n",
+        size + 3,
+        root,
+        info_ref.unwrap_or_default(),
+        id,
+        prev_xref,
+        xref_offset
+    ));
+    out.extend_from_slice(xref.as_bytes());
+    Ok(out)
+}
+
+/// The first token after `key` in a dictionary (e.g. the object number of `/Root 3 0 R`)
+fn dict_value<'t>(dict: &'t str, key: &str) -> Option<&'t str> {
+    let start = dict.find(key)? + key.len();
+    dict[start..]
+        .split(|c: char| c.is_whitespace() || c == '/' || c == '>')
+        .find(|token| !token.is_empty())
+}
+
+/// XMP packet declaring PDF/A-1b conformance with the document info
+fn xmp_packet(info: &PdfAInfo) -> String {
+    let mut dc = String::from("      <dc:format>application/pdf</dc:format>\n");
+    if !info.title.is_empty() {
+        dc.push_str(&format!(
+            "      <dc:title><rdf:Alt><rdf:li xml:lang=\"x-default\">{}</rdf:li></rdf:Alt></dc:title>\n",
+            escape_xml(&info.title)
+        ));
+    }
+    if !info.author.is_empty() {
+        dc.push_str(&format!(
+            "      <dc:creator><rdf:Seq><rdf:li>{}</rdf:li></rdf:Seq></dc:creator>\n",
+            escape_xml(&info.author)
+        ));
+    }
+    if !info.subject.is_empty() {
+        dc.push_str(&format!(
+            "      <dc:description><rdf:Alt><rdf:li xml:lang=\"x-default\">{}</rdf:li></rdf:Alt></dc:description>\n",
+            escape_xml(&info.subject)
+        ));
+    }
+
+    let mut pdf = String::new();
+    if !info.producer.is_empty() {
+        pdf.push_str(&format!(
+            "      <pdf:Producer>{}</pdf:Producer>\n",
+            escape_xml(&info.producer)
+        ));
+    }
+    if !info.keywords.is_empty() {
+        pdf.push_str(&format!(
+            "      <pdf:Keywords>{}</pdf:Keywords>\n",
+            escape_xml(&info.keywords)
+        ));
+    }
+
+    format!(
+        "<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\n\
+         <x:xmpmeta xmlns:x=\"adobe:ns:meta/\">\n\
+         \x20 <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\n\
+         \x20   <rdf:Description rdf:about=\"\" xmlns:pdfaid=\"http://www.aiim.org/pdfa/ns/id/\">\n\
+         \x20     <pdfaid:part>1</pdfaid:part>\n\
+         \x20     <pdfaid:conformance>B</pdfaid:conformance>\n\
+         \x20   </rdf:Description>\n\
+         \x20   <rdf:Description rdf:about=\"\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n\
+         {dc}\
+         \x20   </rdf:Description>\n\
+         \x20   <rdf:Description rdf:about=\"\" xmlns:pdf=\"http://ns.adobe.com/pdf/1.3/\">\n\
+         {pdf}\
+         \x20   </rdf:Description>\n\
+         \x20   <rdf:Description rdf:about=\"\" xmlns:xmp=\"http://ns.adobe.com/xap/1.0/\">\n\
+         \x20     <xmp:CreatorTool>{tool}</xmp:CreatorTool>\n\
+         \x20     <xmp:CreateDate>{date}</xmp:CreateDate>\n\
+         \x20     <xmp:ModifyDate>{date}</xmp:ModifyDate>\n\
+         \x20   </rdf:Description>\n\
+         \x20 </rdf:RDF>\n\
+         </x:xmpmeta>\n\
+         <?xpacket end=\"w\"?>",
+        dc = dc,
+        pdf = pdf,
+        tool = escape_xml(&info.creator_tool),
+        date = escape_xml(&info.date),
+    )
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A minimal ICC v2 display profile for sRGB (D50-adapted primaries, sRGB tone curve)
+pub fn srgb_icc_profile() -> Vec<u8> {
+    fn s15_fixed16(value: f64) -> [u8; 4] {
+        ((value * 65536.0).round() as i32).to_be_bytes()
+    }
+    fn xyz(values: [f64; 3]) -> Vec<u8> {
+        let mut data = b"XYZ \0\0\0\0".to_vec();
+        for value in values {
+            data.extend_from_slice(&s15_fixed16(value));
+        }
+        data
+    }
+
+    let description = "sRGB IEC61966-2.1";
+    let mut desc = b"desc\0\0\0\0".to_vec();
+    desc.extend_from_slice(&(description.len() as u32 + 1).to_be_bytes());
+    desc.extend_from_slice(description.as_bytes());
+    desc.push(0);
+    // Unicode (language code + count) and ScriptCode (code + count + 67 bytes)
+    desc.extend_from_slice(&[0; 8]);
+    desc.extend_from_slice(&[0; 3]);
+    desc.extend_from_slice(&[0; 67]);
+
+    let mut copyright = b"text\0\0\0\0".to_vec();
+    copyright.extend_from_slice(b"No copyright, use freely\0");
+
+    // sRGB transfer function sampled at 1024 points
+    let samples = 1024;
+    let mut curve = b"curv\0\0\0\0".to_vec();
+    curve.extend_from_slice(&(samples as u32).to_be_bytes());
+    for i in 0..samples {
+        let v = i as f64 / (samples - 1) as f64;
+        let linear = if v <= 0.04045 {
+            v / 12.92
+        } else {
+            ((v + 0.055) / 1.055).powf(2.4)
+        };
+        curve.extend_from_slice(&((linear * 65535.0).round() as u16).to_be_bytes());
+    }
+
+    let d50 = [0.9642, 1.0, 0.8249];
+    let tags: Vec<(&[u8; 4], Vec<u8>)> = vec![
+        (b"desc", desc),
+        (b"cprt", copyright),
+        (b"wtpt", xyz(d50)),
+        (b"rXYZ", xyz([0.4361, 0.2225, 0.0139])),
+        (b"gXYZ", xyz([0.3851, 0.7169, 0.0971])),
+        (b"bXYZ", xyz([0.1431, 0.0606, 0.7141])),
+        (b"rTRC", curve),
+    ];
+
+    // Header, tag table (the green and blue curves share the red curve's data)
+    let tag_count = tags.len() + 2;
+    let mut offset = 128 + 4 + tag_count * 12;
+    let mut table = Vec::new();
+    let mut data = Vec::new();
+    let mut curve_entry = (0, 0);
+    for (signature, tag) in &tags {
+        table.extend_from_slice(*signature);
+        table.extend_from_slice(&(offset as u32).to_be_bytes());
+        table.extend_from_slice(&(tag.len() as u32).to_be_bytes());
+        if *signature == b"rTRC" {
+            curve_entry = (offset, tag.len());
+        }
+        data.extend_from_slice(tag);
+        offset += tag.len();
+        while !offset.is_multiple_of(4) {
+            data.push(0);
+            offset += 1;
+        }
+    }
+    for signature in [b"gTRC", b"bTRC"] {
+        table.extend_from_slice(signature);
+        table.extend_from_slice(&(curve_entry.0 as u32).to_be_bytes());
+        table.extend_from_slice(&(curve_entry.1 as u32).to_be_bytes());
+    }
+
+    let mut header = vec![0u8; 128];
+    header[0..4].copy_from_slice(&(offset as u32).to_be_bytes());
+    header[8..12].copy_from_slice(&[0x02, 0x10, 0x00, 0x00]);
+    header[12..16].copy_from_slice(b"mntr");
+    header[16..20].copy_from_slice(b"RGB ");
+    header[20..24].copy_from_slice(b"XYZ ");
+    for (i, value) in [2024u16, 1, 1, 0, 0, 0].iter().enumerate() {
+        header[24 + i * 2..26 + i * 2].copy_from_slice(&value.to_be_bytes());
+    }
+    header[36..40].copy_from_slice(b"acsp");
+    for (i, value) in d50.iter().enumerate() {
+        header[68 + i * 4..72 + i * 4].copy_from_slice(&s15_fixed16(*value));
+    }
+
+    let mut profile = header;
+    profile.extend_from_slice(&(tag_count as u32).to_be_bytes());
+    profile.extend_from_slice(&table);
+    profile.extend_from_slice(&data);
+    profile
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A tiny single-page PDF with a classic cross-reference table
+    fn minimal_pdf() -> Vec<u8> {
+        let objects = [
+            "<< /Type /Catalog /Pages 2 0 R >>",
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>",
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 595 842] >>",
+            "<< /Producer (cairo 1.18.0 (https://cairographics.org)) >>",
+        ];
+        let mut pdf = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::new();
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", index + 1, object).as_bytes());
+        }
+        let xref = pdf.len();
+        pdf.extend_from_slice(b"xref\n0 5\n0000000000 65535 f \n");
+        for offset in offsets {
+            pdf.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+        }
+        pdf.extend_from_slice(
+            format!(
+                "trailer\n<< /Size 5\n   /Root 1 0 R\n   /Info 4 0 R\n>>\nstartxref\n{}\n%%EOF\n",
+                xref
+            )
+            .as_bytes(),
+        );
+        pdf
+    }
+
+    #[test]
+    fn test_appends_pdf_a_metadata_as_incremental_update() {
+        let pdf = minimal_pdf();
+        assert_eq!(producer_of(&pdf), "cairo 1.18.0 (https://cairographics.org)");
+        let info = PdfAInfo {
+            title: "期末試験 & 解答".to_string(),
+            producer: producer_of(&pdf),
+            date: "2026-10-15T09:00:00+09:00".to_string(),
+            ..Default::default()
+        };
+        let archived = append_pdf_a_metadata(&pdf, &info).unwrap();
+        assert!(archived.starts_with(&pdf));
+
+        let text = String::from_utf8_lossy(&archived);
+        assert!(text.contains("/OutputIntents [6 0 R]"));
+        assert!(text.contains("/S /GTS_PDFA1"));
+        assert!(text.contains("<pdfaid:part>1</pdfaid:part>"));
+        assert!(text.contains("期末試験 &amp; 解答"));
+
+        // The update's xref section covers the rewritten catalog and the new objects
+        let (entries, trailer) = xref_section(&archived, last_startxref(&archived));
+        let objects: Vec<usize> = entries.iter().map(|&(object, _)| object).collect();
+        assert_eq!(objects, [1, 5, 6, 7]);
+        for (object, offset) in entries {
+            assert!(archived[offset..].starts_with(format!("{} 0 obj", object).as_bytes()));
+        }
+        assert_eq!(dict_value(&trailer, "/Size"), Some("8"));
+        assert_eq!(dict_value(&trailer, "/Root"), Some("1"));
+        assert_eq!(dict_value(&trailer, "/Info"), Some("4"));
+        assert!(trailer.contains("/ID ["));
+
+        // `/Prev` chains to the original section, which still resolves every object
+        let prev: usize = dict_value(&trailer, "/Prev").unwrap().parse().unwrap();
+        assert_eq!(prev, last_startxref(&pdf));
+        let (original, original_trailer) = xref_section(&archived, prev);
+        assert_eq!(original.len(), 4);
+        for (object, offset) in original {
+            assert!(archived[offset..].starts_with(format!("{} 0 obj", object).as_bytes()));
+        }
+        assert!(!original_trailer.contains("/Prev"));
+    }
+
+    /// Offset of the xref section named by the last `startxref`
+    fn last_startxref(pdf: &[u8]) -> usize {
+        let text = String::from_utf8_lossy(pdf);
+        text.rsplit("startxref")
+            .next()
+            .and_then(|tail| tail.split_whitespace().next())
+            .and_then(|offset| offset.parse().ok())
+            .unwrap()
+    }
+
+    /// In-use `(object, offset)` entries of the xref section at `offset`, and its trailer
+    ///
+    /// Each subsection header gives the first object number and the entry
+    /// count; entries are fixed 20-byte lines.
+    fn xref_section(pdf: &[u8], offset: usize) -> (Vec<(usize, usize)>, String) {
+        assert!(pdf[offset..].starts_with(b"xref\n"));
+        let mut position = offset + "xref\n".len();
+        let mut entries = Vec::new();
+        while !pdf[position..].starts_with(b"trailer") {
+            let line_end = position + pdf[position..].iter().position(|&b| b == b'\n').unwrap();
+            let header = std::str::from_utf8(&pdf[position..line_end]).unwrap();
+            let (first, count) = header.split_once(' ').unwrap();
+            let (first, count): (usize, usize) = (first.parse().unwrap(), count.parse().unwrap());
+            position = line_end + 1;
+            for object in first..first + count {
+                let entry = std::str::from_utf8(&pdf[position..position + 20]).unwrap();
+                assert!(entry.ends_with(" \n"), "xref entries are 20 bytes");
+                if entry.as_bytes()[17] == b'n' {
+                    entries.push((object, entry[..10].parse().unwrap()));
+                }
+                position += 20;
+            }
+        }
+        let text = String::from_utf8_lossy(&pdf[position..]);
+        let trailer = &text[..text.find("startxref").unwrap()];
+        (entries, trailer.to_string())
+    }
+
+    #[test]
+    fn test_finds_transparent_elements() {
+        use testruct_core::document::{FrameElement, Page, ShapeElement, ShapeKind};
+
+        let document_with = |opacity: f32, visible: bool| {
+            let shape = ShapeElement {
+                id: uuid::Uuid::new_v4(),
+                kind: ShapeKind::Rectangle,
+                bounds: Default::default(),
+                stroke: None,
+                stroke_width: 1.0,
+                fill: None,
+                visible,
+                locked: false,
+                rotation: 0.0,
+                vertices: Vec::new(),
+                line_style: Default::default(),
+                opacity,
+                corner_radius: 0.0,
+            };
+            let id = shape.id;
+            let mut document = Document::empty("Test");
+            document.pages.push(Page::empty());
+            document.pages[1].add_element(DocumentElement::Frame(FrameElement {
+                id: uuid::Uuid::new_v4(),
+                bounds: Default::default(),
+                children: vec![DocumentElement::Shape(shape)],
+                visible: true,
+                locked: false,
+                opacity: 1.0,
+            }));
+            (document, id)
+        };
+
+        let (opaque, _) = document_with(1.0, true);
+        assert_eq!(transparent_element(&opaque), None);
+        let (hidden, _) = document_with(0.5, false);
+        assert_eq!(transparent_element(&hidden), None);
+        let (transparent, id) = document_with(0.5, true);
+        assert_eq!(transparent_element(&transparent), Some((2, id)));
+    }
+
+    #[test]
+    fn test_rejects_pdfs_without_xref_table() {
+        assert!(append_pdf_a_metadata(b"%PDF-1.7\n", &PdfAInfo::default()).is_err());
+    }
+
+    #[test]
+    fn test_srgb_profile_header() {
+        let profile = srgb_icc_profile();
+        let size = u32::from_be_bytes(profile[0..4].try_into().unwrap()) as usize;
+        assert_eq!(size, profile.len());
+        assert_eq!(&profile[36..40], b"acsp");
+        assert_eq!(&profile[16..20], b"RGB ");
+    }
+}
@@ -1,21 +1,27 @@
-//! SVG export functionality using Cairo
+//! SVG export
 //!
-//! Renders a document to SVG format with support for multi-page output and background options.
+//! Writes structured SVG markup (see [`writer`]): each element is a
+//! `<g id="{uuid}">`, groups and frames nest their children, and text stays
+//! as `<text>`. Supports multi-page output and background options.
 
 use anyhow::{anyhow, Result};
-use cairo::{Context, SvgSurface};
 use std::path::Path;
 use testruct_core::workspace::assets::AssetCatalog;
 use testruct_core::Document;
 use tracing::{debug, info};
 
-use crate::export::{BackgroundOption, ExportConfig};
+use crate::export::ExportConfig;
 
-/// Default page size (A4: 595.28 x 841.89 points)
-const DEFAULT_PAGE_WIDTH: f64 = 595.28;
-const DEFAULT_PAGE_HEIGHT: f64 = 841.89;
+#[path = "svg_import.rs"]
+mod import;
+#[path = "svg_writer.rs"]
+mod writer;
+
+pub use import::{import_from_svg, import_svg_str};
 
 /// Render a document to SVG
+///
+/// All pages go into one file, stacked top to bottom as `<g id="page-N">`.
 pub fn render_to_svg(
     document: &Document,
     output_path: &Path,
@@ -27,33 +33,9 @@ pub fn render_to_svg(
         return Err(anyhow!("Document has no pages to export"));
     }
 
-    // Use default page dimensions (A4) for now
-    let width = DEFAULT_PAGE_WIDTH;
-    let height = DEFAULT_PAGE_HEIGHT;
-
-    debug!("SVG page size: {} x {}", width, height);
-
-    // Create SVG surface
-    let surface = SvgSurface::new(width, height, Some(output_path))
-        .map_err(|e| anyhow!("Failed to create SVG surface: {}", e))?;
-
-    let ctx =
-        Context::new(&surface).map_err(|e| anyhow!("Failed to create Cairo context: {}", e))?;
-
-    // Render each page
-    for (page_index, page) in document.pages.iter().enumerate() {
-        debug!("Rendering page {}", page_index + 1);
-        render_page_to_context(&ctx, page, catalog)?;
-
-        // Move to next page (except for last page)
-        if page_index < document.pages.len() - 1 {
-            ctx.show_page()
-                .map_err(|e| anyhow!("Failed to show page: {}", e))?;
-        }
-    }
-
-    // Finish SVG
-    surface.finish();
+    let svg = writer::document_svg(document, catalog)
+        .map_err(|e| anyhow!("Failed to lay out SVG text: {}", e))?;
+    std::fs::write(output_path, svg).map_err(|e| anyhow!("Failed to write SVG: {}", e))?;
     info!("SVG export completed: {}", output_path.display());
 
     Ok(())
@@ -77,39 +59,28 @@ pub fn render_to_svg_with_config(
         config.background
     );
 
-    let width = DEFAULT_PAGE_WIDTH;
-    let height = DEFAULT_PAGE_HEIGHT;
-
     // Determine which pages to export
-    let pages_to_export: Vec<(usize, &testruct_core::document::Page)> = if config.export_all_pages {
-        document.pages.iter().enumerate().collect()
+    let pages_to_export: Vec<usize> = if config.export_all_pages {
+        (0..document.pages.len()).collect()
     } else if let Some(page_idx) = config.page_index {
         if page_idx < document.pages.len() {
-            vec![(page_idx, &document.pages[page_idx])]
+            vec![page_idx]
         } else {
             return Err(anyhow!("Page index {} out of bounds", page_idx));
         }
     } else {
-        document.pages.iter().enumerate().collect()
+        (0..document.pages.len()).collect()
     };
 
-    // For multi-page SVG export with single page, output directly
-    // For multi-page, create separate files
+    // A single page is written directly; several pages get one file each
     let page_count = pages_to_export.len();
     if page_count == 1 {
-        let (_, page) = pages_to_export[0];
-        let surface = SvgSurface::new(width, height, Some(output_path))
-            .map_err(|e| anyhow!("Failed to create SVG surface: {}", e))?;
-
-        let ctx = Context::new(&surface)
-            .map_err(|e| anyhow!("Failed to create Cairo context: {}", e))?;
-
-        render_page_to_context_with_background(&ctx, page, &config.background, catalog)?;
-
-        surface.finish();
+        let svg = writer::page_svg(document, pages_to_export[0], &config.background, catalog)
+            .map_err(|e| anyhow!("Failed to lay out SVG text: {}", e))?;
+        std::fs::write(output_path, svg).map_err(|e| anyhow!("Failed to write SVG: {}", e))?;
         info!("SVG exported: {}", output_path.display());
     } else {
-        for (index, page) in &pages_to_export {
+        for index in pages_to_export {
             let page_num = index + 1;
             let output_filename = if let Some(extension) = output_path.extension() {
                 let stem = output_path.file_stem().unwrap();
@@ -127,15 +98,9 @@ pub fn render_to_svg_with_config(
 
             debug!("Rendering page {} to: {}", page_num, page_path.display());
 
-            let surface = SvgSurface::new(width, height, Some(&page_path))
-                .map_err(|e| anyhow!("Failed to create SVG surface: {}", e))?;
-
-            let ctx = Context::new(&surface)
-                .map_err(|e| anyhow!("Failed to create Cairo context: {}", e))?;
-
-            render_page_to_context_with_background(&ctx, page, &config.background, catalog)?;
-
-            surface.finish();
+            let svg = writer::page_svg(document, index, &config.background, catalog)
+                .map_err(|e| anyhow!("Failed to lay out SVG text: {}", e))?;
+            std::fs::write(&page_path, svg).map_err(|e| anyhow!("Failed to write SVG: {}", e))?;
         }
 
         info!(
@@ -147,381 +112,6 @@ pub fn render_to_svg_with_config(
     Ok(())
 }
 
-/// Render a single page to Cairo context with configurable background
-fn render_page_to_context_with_background(
-    ctx: &Context,
-    page: &testruct_core::document::Page,
-    background: &BackgroundOption,
-    catalog: &AssetCatalog,
-) -> Result<()> {
-    let width = DEFAULT_PAGE_WIDTH;
-    let height = DEFAULT_PAGE_HEIGHT;
-
-    // Draw background based on option
-    match background.to_color() {
-        Some(color) => {
-            // Solid color background
-            ctx.set_source_rgba(
-                color.r as f64,
-                color.g as f64,
-                color.b as f64,
-                color.a as f64,
-            );
-            ctx.rectangle(0.0, 0.0, width, height);
-            ctx.fill()
-                .map_err(|e| anyhow!("Failed to paint background: {}", e))?;
-        }
-        None => {
-            // Transparent background - don't paint anything
-            // SVG will have transparent background by default
-        }
-    }
-
-    // Draw page border only for non-transparent backgrounds
-    if !background.is_transparent() {
-        ctx.set_source_rgb(0.0, 0.0, 0.0);
-        ctx.set_line_width(0.5);
-        ctx.rectangle(0.0, 0.0, width, height);
-        ctx.stroke()
-            .map_err(|e| anyhow!("Failed to draw page border: {}", e))?;
-    }
-
-    // Render all elements
-    for element in &page.elements {
-        render_element_to_context(ctx, element, catalog)?;
-    }
-
-    Ok(())
-}
-
-/// Render a single page to Cairo context (legacy)
-fn render_page_to_context(
-    ctx: &Context,
-    page: &testruct_core::document::Page,
-    catalog: &AssetCatalog,
-) -> Result<()> {
-    // Set white background
-    ctx.set_source_rgb(1.0, 1.0, 1.0);
-    ctx.paint()
-        .map_err(|e| anyhow!("Failed to paint background: {}", e))?;
-
-    // Use default page size
-    let width = DEFAULT_PAGE_WIDTH;
-    let height = DEFAULT_PAGE_HEIGHT;
-
-    // Draw page border for visual reference
-    ctx.set_source_rgb(0.0, 0.0, 0.0);
-    ctx.set_line_width(0.5);
-    ctx.rectangle(0.0, 0.0, width, height);
-    ctx.stroke()
-        .map_err(|e| anyhow!("Failed to draw page border: {}", e))?;
-
-    // Render all elements
-    for element in &page.elements {
-        render_element_to_context(ctx, element, catalog)?;
-    }
-
-    Ok(())
-}
-
-/// Render a single element to Cairo context
-fn render_element_to_context(
-    ctx: &Context,
-    element: &testruct_core::document::DocumentElement,
-    catalog: &AssetCatalog,
-) -> Result<()> {
-    use testruct_core::document::DocumentElement;
-
-    match element {
-        DocumentElement::Shape(shape) => {
-            render_shape_to_context(ctx, shape)?;
-        }
-        DocumentElement::Text(text) => {
-            render_text_to_context(ctx, text)?;
-        }
-        DocumentElement::Image(image) => {
-            // Try to load image from catalog, fallback to placeholder if not found
-            match crate::export::image_utils::render_image_from_asset(
-                ctx,
-                image.source,
-                catalog,
-                &image.bounds,
-            ) {
-                Ok(_) => {
-                    debug!("Image rendered from asset catalog: {}", image.id);
-                }
-                Err(e) => {
-                    // If loading fails, draw placeholder and log warning
-                    debug!(
-                        "Failed to render image {}: {}, using placeholder",
-                        image.id, e
-                    );
-                    let _ = crate::export::image_utils::draw_image_placeholder(ctx, &image.bounds);
-                }
-            }
-        }
-        DocumentElement::Frame(frame) => {
-            render_frame_to_context(ctx, frame, catalog)?;
-        }
-        DocumentElement::Group(group) => {
-            // Recursively render group children
-            for child in &group.children {
-                render_element_to_context(ctx, child, catalog)?;
-            }
-        }
-    }
-
-    Ok(())
-}
-
-/// Render a shape element
-fn render_shape_to_context(
-    ctx: &Context,
-    shape: &testruct_core::document::ShapeElement,
-) -> Result<()> {
-    use testruct_core::document::ShapeKind;
-
-    let x = shape.bounds.origin.x as f64;
-    let y = shape.bounds.origin.y as f64;
-    let width = shape.bounds.size.width as f64;
-    let height = shape.bounds.size.height as f64;
-
-    // Render based on shape kind
-    match shape.kind {
-        ShapeKind::Rectangle => {
-            // Draw fill color if present
-            if let Some(fill) = &shape.fill {
-                ctx.set_source_rgb(fill.r as f64, fill.g as f64, fill.b as f64);
-                ctx.rectangle(x, y, width, height);
-                ctx.fill()
-                    .map_err(|e| anyhow!("Failed to fill rectangle: {}", e))?;
-            }
-            // Draw stroke if present
-            if let Some(stroke) = &shape.stroke {
-                ctx.set_source_rgb(stroke.r as f64, stroke.g as f64, stroke.b as f64);
-                ctx.set_line_width(1.0);
-                ctx.rectangle(x, y, width, height);
-                ctx.stroke()
-                    .map_err(|e| anyhow!("Failed to stroke rectangle: {}", e))?;
-            }
-        }
-        ShapeKind::Ellipse => {
-            ctx.save()
-                .map_err(|e| anyhow!("Failed to save context: {}", e))?;
-            ctx.translate(x + width / 2.0, y + height / 2.0);
-            ctx.scale(width / 2.0, height / 2.0);
-            ctx.arc(0.0, 0.0, 1.0, 0.0, std::f64::consts::PI * 2.0);
-
-            // Draw fill if present
-            if let Some(fill) = &shape.fill {
-                ctx.set_source_rgb(fill.r as f64, fill.g as f64, fill.b as f64);
-                ctx.fill_preserve()
-                    .map_err(|e| anyhow!("Failed to fill ellipse: {}", e))?;
-            }
-            // Draw stroke if present
-            if let Some(stroke) = &shape.stroke {
-                ctx.set_source_rgb(stroke.r as f64, stroke.g as f64, stroke.b as f64);
-                ctx.set_line_width(1.0);
-                ctx.stroke()
-                    .map_err(|e| anyhow!("Failed to stroke ellipse: {}", e))?;
-            }
-            ctx.restore()
-                .map_err(|e| anyhow!("Failed to restore context: {}", e))?;
-        }
-        ShapeKind::Line => {
-            // Lines use stroke color
-            if let Some(stroke) = &shape.stroke {
-                ctx.set_source_rgb(stroke.r as f64, stroke.g as f64, stroke.b as f64);
-                ctx.set_line_width(1.0);
-                ctx.move_to(x, y);
-                ctx.line_to(x + width, y + height);
-                ctx.stroke()
-                    .map_err(|e| anyhow!("Failed to stroke line: {}", e))?;
-            }
-        }
-        ShapeKind::Arrow => {
-            // Arrows use stroke color for the line
-            if let Some(stroke) = &shape.stroke {
-                ctx.set_source_rgb(stroke.r as f64, stroke.g as f64, stroke.b as f64);
-                ctx.set_line_width(1.0);
-                ctx.move_to(x, y);
-                ctx.line_to(x + width, y + height);
-                ctx.stroke()
-                    .map_err(|e| anyhow!("Failed to stroke arrow: {}", e))?;
-            }
-        }
-        ShapeKind::Polygon => {
-            // Placeholder for polygon (same as rectangle)
-            if let Some(fill) = &shape.fill {
-                ctx.set_source_rgb(fill.r as f64, fill.g as f64, fill.b as f64);
-                ctx.rectangle(x, y, width, height);
-                ctx.fill()
-                    .map_err(|e| anyhow!("Failed to fill polygon: {}", e))?;
-            }
-            if let Some(stroke) = &shape.stroke {
-                ctx.set_source_rgb(stroke.r as f64, stroke.g as f64, stroke.b as f64);
-                ctx.set_line_width(1.0);
-                ctx.rectangle(x, y, width, height);
-                ctx.stroke()
-                    .map_err(|e| anyhow!("Failed to stroke polygon: {}", e))?;
-            }
-        }
-    }
-
-    Ok(())
-}
-
-/// Render a frame element (with recursive children)
-fn render_frame_to_context(
-    ctx: &Context,
-    frame: &testruct_core::document::FrameElement,
-    catalog: &AssetCatalog,
-) -> Result<()> {
-    let x = frame.bounds.origin.x as f64;
-    let y = frame.bounds.origin.y as f64;
-    let width = frame.bounds.size.width as f64;
-    let height = frame.bounds.size.height as f64;
-
-    // Draw frame border
-    ctx.set_source_rgb(0.9, 0.9, 0.9);
-    ctx.set_line_width(1.0);
-    ctx.rectangle(x, y, width, height);
-    ctx.stroke()
-        .map_err(|e| anyhow!("Failed to stroke frame: {}", e))?;
-
-    // Render frame children recursively
-    for child in &frame.children {
-        render_element_to_context(ctx, child, catalog)?;
-    }
-
-    debug!("Frame rendered with {} children", frame.children.len());
-    Ok(())
-}
-
-/// Render a text element with Pango layout
-fn render_text_to_context(
-    ctx: &Context,
-    text: &testruct_core::document::TextElement,
-) -> Result<()> {
-    ctx.save()
-        .map_err(|e| anyhow!("Failed to save context: {}", e))?;
-
-    let bounds = &text.bounds;
-    let style = &text.style;
-
-    // Draw background color if specified
-    if let Some(bg_color) = style.background_color {
-        ctx.set_source_rgb(bg_color.r as f64, bg_color.g as f64, bg_color.b as f64);
-        ctx.rectangle(
-            bounds.origin.x as f64,
-            bounds.origin.y as f64,
-            bounds.size.width as f64,
-            bounds.size.height as f64,
-        );
-        ctx.fill()
-            .map_err(|e| anyhow!("Failed to fill text background: {}", e))?;
-    }
-
-    // Translate to text origin
-    ctx.translate(bounds.origin.x as f64, bounds.origin.y as f64);
-
-    // Create clipping rectangle
-    ctx.rectangle(
-        0.0,
-        0.0,
-        bounds.size.width as f64,
-        bounds.size.height as f64,
-    );
-    ctx.clip();
-
-    // Create Pango layout
-    let layout = pangocairo::functions::create_layout(ctx);
-    layout.set_text(&text.content);
-
-    // Set font with styling
-    let mut font_desc = pango::FontDescription::new();
-    font_desc.set_family(&style.font_family);
-    font_desc.set_size((style.font_size * pango::SCALE as f32) as i32);
-
-    // Apply font weight
-    let pango_weight = match style.weight {
-        testruct_core::typography::FontWeight::Thin => pango::Weight::Thin,
-        testruct_core::typography::FontWeight::Light => pango::Weight::Light,
-        testruct_core::typography::FontWeight::Regular => pango::Weight::Normal,
-        testruct_core::typography::FontWeight::Medium => pango::Weight::Medium,
-        testruct_core::typography::FontWeight::Bold => pango::Weight::Bold,
-        testruct_core::typography::FontWeight::Black => pango::Weight::Ultrabold,
-    };
-    font_desc.set_weight(pango_weight);
-
-    // Apply italic style
-    if style.italic {
-        font_desc.set_style(pango::Style::Italic);
-    }
-
-    layout.set_font_description(Some(&font_desc));
-
-    // Apply text alignment
-    let pango_alignment = match style.alignment {
-        testruct_core::typography::TextAlignment::Start => pango::Alignment::Left,
-        testruct_core::typography::TextAlignment::Center => pango::Alignment::Center,
-        testruct_core::typography::TextAlignment::End => pango::Alignment::Right,
-        testruct_core::typography::TextAlignment::Justified => pango::Alignment::Center,
-    };
-    layout.set_alignment(pango_alignment);
-
-    // Configure for vertical or horizontal text
-    if style.vertical {
-        // Vertical text mode (縦書き)
-        let pango_context = layout.context();
-        pango_context.set_base_gravity(pango::Gravity::East);
-        pango_context.set_gravity_hint(pango::GravityHint::Strong);
-        layout.context_changed();
-        // For vertical text, use height as the constraint
-        layout.set_width((bounds.size.height as f64 * pango::SCALE as f64) as i32);
-    } else {
-        // Horizontal text mode (横書き)
-        layout.set_width((bounds.size.width as f64 * pango::SCALE as f64) as i32);
-    }
-
-    // Apply underline and strikethrough decorations
-    if style.underline || style.strikethrough {
-        let attrs = pango::AttrList::new();
-        if style.underline {
-            let underline_attr = pango::AttrInt::new_underline(pango::Underline::Single);
-            attrs.insert(underline_attr);
-        }
-        if style.strikethrough {
-            let strikethrough_attr = pango::AttrInt::new_strikethrough(true);
-            attrs.insert(strikethrough_attr);
-        }
-        layout.set_attributes(Some(&attrs));
-    }
-
-    // Set text color
-    ctx.set_source_rgb(
-        style.color.r as f64,
-        style.color.g as f64,
-        style.color.b as f64,
-    );
-
-    // Position for vertical text rendering
-    if style.vertical {
-        // Vertical text: rotate 90 degrees clockwise from top-right corner
-        ctx.translate(bounds.size.width as f64, 0.0);
-        ctx.rotate(std::f64::consts::FRAC_PI_2);
-    }
-
-    // Render the text using pangocairo
-    pangocairo::functions::show_layout(ctx, &layout);
-
-    ctx.restore()
-        .map_err(|e| anyhow!("Failed to restore context: {}", e))?;
-
-    debug!("Text rendered: '{}' in SVG (vertical: {})", text.content, style.vertical);
-    Ok(())
-}
-
 #[cfg(test)]
 mod tests {
     #[allow(unused_imports)]
@@ -0,0 +1,860 @@
+//! SVG import
+//!
+//! Converts the basic SVG primitives (rect, circle, ellipse, line, polygon,
+//! text, image) into editable document elements. Groups are flattened and
+//! `translate()` transforms are applied; other transforms and unsupported
+//! tags (paths, gradients, ...) are skipped with a warning.
+
+use anyhow::{anyhow, Context as _, Result};
+use std::collections::HashMap;
+use std::path::Path;
+use testruct_core::document::{
+    DocumentElement, Fill, ImageElement, ImageFit, LineStyle, PageSize, ShapeElement, ShapeKind,
+    TextElement, DEFAULT_TEXT_PADDING,
+};
+use testruct_core::layout::{Insets, Point, Rect, Size};
+use testruct_core::typography::{Color, FontWeight, TextAlignment, TextStyle};
+use testruct_core::Document;
+use tracing::{info, warn};
+
+/// Import an SVG file as a new single-page document
+///
+/// Relative image paths are resolved against the SVG's directory and
+/// registered in the document's asset catalog.
+pub fn import_from_svg(path: &Path) -> Result<Document> {
+    info!("Importing SVG: {}", path.display());
+
+    let source = std::fs::read_to_string(path).context("Failed to read SVG file")?;
+    let title = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "Imported SVG".to_string());
+    let mut document = import_svg_str(&source, path.parent())?;
+    document.metadata.title = title;
+
+    info!(
+        "SVG import completed: {} element(s)",
+        document.pages[0].elements.len()
+    );
+    Ok(document)
+}
+
+/// Import SVG source text as a new single-page document
+pub fn import_svg_str(source: &str, base_dir: Option<&Path>) -> Result<Document> {
+    let tokens = tokenize(source)?;
+    let mut document = Document::empty("Imported SVG");
+    let mut importer = Importer {
+        document: &mut document,
+        base_dir,
+        styles: vec![InheritedStyle::default()],
+        warned: Vec::new(),
+    };
+    importer.run(&tokens)?;
+    Ok(document)
+}
+
+/// One markup token of the SVG source
+#[derive(Debug, PartialEq)]
+enum Token {
+    Open {
+        name: String,
+        attrs: HashMap<String, String>,
+        self_closing: bool,
+    },
+    Close(String),
+    Text(String),
+}
+
+/// Split SVG source into tags and text, dropping comments and declarations
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            tokens.push(Token::Text(decode_entities(rest)));
+            break;
+        };
+        if start > 0 {
+            tokens.push(Token::Text(decode_entities(&rest[..start])));
+        }
+        rest = &rest[start..];
+
+        if let Some(after) = rest.strip_prefix("<!--") {
+            let end = after.find("-->").ok_or_else(|| anyhow!("Unterminated comment"))?;
+            rest = &after[end + 3..];
+        } else if let Some(after) = rest.strip_prefix("<![CDATA[") {
+            let end = after.find("]]>").ok_or_else(|| anyhow!("Unterminated CDATA"))?;
+            tokens.push(Token::Text(after[..end].to_string()));
+            rest = &after[end + 3..];
+        } else if rest.starts_with("<?") || rest.starts_with("<!") {
+            let end = rest.find('>').ok_or_else(|| anyhow!("Unterminated declaration"))?;
+            rest = &rest[end + 1..];
+        } else if let Some(after) = rest.strip_prefix("</") {
+            let end = after.find('>').ok_or_else(|| anyhow!("Unterminated closing tag"))?;
+            tokens.push(Token::Close(local_name(after[..end].trim()).to_string()));
+            rest = &after[end + 1..];
+        } else {
+            let end = tag_end(rest).ok_or_else(|| anyhow!("Unterminated tag"))?;
+            let body = rest[1..end].trim_end();
+            let (body, self_closing) = match body.strip_suffix('/') {
+                Some(body) => (body, true),
+                None => (body, false),
+            };
+            let name_end = body
+                .find(|c: char| c.is_whitespace())
+                .unwrap_or(body.len());
+            tokens.push(Token::Open {
+                name: local_name(&body[..name_end]).to_string(),
+                attrs: parse_attributes(&body[name_end..]),
+                self_closing,
+            });
+            rest = &rest[end + 1..];
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Index of the `>` closing the tag at the start of `source`, skipping quoted values
+fn tag_end(source: &str) -> Option<usize> {
+    let mut quote = None;
+    for (index, c) in source.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '>') => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Tag or attribute name without its namespace prefix (`svg:rect` → `rect`)
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+fn parse_attributes(source: &str) -> HashMap<String, String> {
+    let mut attrs = HashMap::new();
+    let mut rest = source.trim_start();
+
+    while let Some(eq) = rest.find('=') {
+        let name = rest[..eq].trim();
+        let value_part = rest[eq + 1..].trim_start();
+        let Some(quote) = value_part.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            break;
+        };
+        let Some(close) = value_part[1..].find(quote) else {
+            break;
+        };
+        let value = &value_part[1..close + 1];
+        // Keep `xlink:href` reachable as `href`, but never shadow a plain `href`
+        let key = local_name(name).to_string();
+        if !(key == "href" && attrs.contains_key("href")) {
+            attrs.insert(key, decode_entities(value));
+        }
+        rest = value_part[close + 2..].trim_start();
+    }
+
+    // Inline `style` declarations are applied like presentation attributes
+    if let Some(style) = attrs.get("style").cloned() {
+        for declaration in style.split(';') {
+            if let Some((property, value)) = declaration.split_once(':') {
+                attrs.insert(property.trim().to_string(), value.trim().to_string());
+            }
+        }
+    }
+    attrs
+}
+
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let Some(semi) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..semi];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Presentation attributes inherited from enclosing `<g>` elements
+#[derive(Clone, Debug)]
+struct InheritedStyle {
+    /// SVG fills black unless told otherwise
+    fill: Option<Color>,
+    stroke: Option<Color>,
+    stroke_width: f32,
+    font_size: f32,
+    font_family: Option<String>,
+    offset: (f32, f32),
+}
+
+impl Default for InheritedStyle {
+    fn default() -> Self {
+        Self {
+            fill: Some(Color::from_rgb(0.0, 0.0, 0.0)),
+            stroke: None,
+            stroke_width: 1.0,
+            font_size: 16.0,
+            font_family: None,
+            offset: (0.0, 0.0),
+        }
+    }
+}
+
+struct Importer<'a> {
+    document: &'a mut Document,
+    base_dir: Option<&'a Path>,
+    styles: Vec<InheritedStyle>,
+    warned: Vec<String>,
+}
+
+impl Importer<'_> {
+    fn run(&mut self, tokens: &[Token]) -> Result<()> {
+        let mut index = 0;
+        while index < tokens.len() {
+            match &tokens[index] {
+                Token::Open {
+                    name,
+                    attrs,
+                    self_closing,
+                } => {
+                    let name = name.as_str();
+                    match name {
+                        "svg" => self.apply_root_size(attrs),
+                        "g" | "a" | "switch" => {}
+                        "rect" | "circle" | "ellipse" | "line" | "polygon" | "image" => {
+                            let style = self.element_style(attrs);
+                            if let Some(element) = self.build_element(name, attrs, &style) {
+                                self.document.pages[0].add_element(element);
+                            }
+                        }
+                        "text" => {
+                            let (content, next) = collect_text(tokens, index, *self_closing);
+                            let style = self.element_style(attrs);
+                            if let Some(element) = build_text(attrs, &content, &style) {
+                                self.document.pages[0].add_element(element);
+                            }
+                            index = next;
+                            continue;
+                        }
+                        "defs" | "title" | "desc" | "metadata" | "style" | "symbol"
+                        | "clipPath" | "mask" | "linearGradient" | "radialGradient"
+                        | "pattern" | "marker" => {
+                            // Not rendered directly; skip the whole subtree
+                            index = skip_subtree(tokens, index, *self_closing);
+                            continue;
+                        }
+                        other => {
+                            self.warn_once(other);
+                            if !*self_closing {
+                                index = skip_subtree(tokens, index, false);
+                                continue;
+                            }
+                        }
+                    }
+
+                    // Containers (and the root) push a style for their children
+                    if !*self_closing && matches!(name, "svg" | "g" | "a" | "switch") {
+                        let style = self.element_style(attrs);
+                        self.styles.push(style);
+                    } else if !*self_closing {
+                        // Leaf elements written as <rect ...></rect>
+                        index = skip_subtree(tokens, index, false);
+                        continue;
+                    }
+                }
+                Token::Close(name) => {
+                    if matches!(name.as_str(), "svg" | "g" | "a" | "switch") && self.styles.len() > 1
+                    {
+                        self.styles.pop();
+                    }
+                }
+                Token::Text(_) => {}
+            }
+            index += 1;
+        }
+        Ok(())
+    }
+
+    fn warn_once(&mut self, tag: &str) {
+        if !self.warned.iter().any(|t| t == tag) {
+            warn!("⚠️ SVG import: skipping unsupported <{}> element(s)", tag);
+            self.warned.push(tag.to_string());
+        }
+    }
+
+    /// Use the viewBox (or width/height) as the page size
+    fn apply_root_size(&mut self, attrs: &HashMap<String, String>) {
+        let view_box: Vec<f32> = attrs
+            .get("viewBox")
+            .map(|v| {
+                v.split(|c: char| c == ',' || c.is_whitespace())
+                    .filter_map(|n| n.parse().ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let size = if let [min_x, min_y, width, height] = view_box[..] {
+            // Shift content so the viewBox origin lands on the page origin
+            let style = &mut self.styles[0];
+            style.offset = (-min_x, -min_y);
+            Some((width, height))
+        } else {
+            length(attrs, "width").zip(length(attrs, "height"))
+        };
+        if let Some((width, height)) = size.filter(|(w, h)| *w > 0.0 && *h > 0.0) {
+            self.document.metadata.page_size = PageSize::Custom(width, height);
+        }
+    }
+
+    /// Inherited style overridden by the element's own attributes
+    fn element_style(&self, attrs: &HashMap<String, String>) -> InheritedStyle {
+        let mut style = self.styles.last().cloned().unwrap_or_default();
+        let opacity = number(attrs, "opacity").unwrap_or(1.0);
+
+        if let Some(value) = attrs.get("fill") {
+            match parse_color(value) {
+                Some(fill) => style.fill = fill,
+                None => warn!("⚠️ SVG import: unsupported fill '{}'", value),
+            }
+        }
+        if let Some(value) = attrs.get("stroke") {
+            match parse_color(value) {
+                Some(stroke) => style.stroke = stroke,
+                None => warn!("⚠️ SVG import: unsupported stroke '{}'", value),
+            }
+        }
+        let fill_opacity = number(attrs, "fill-opacity").unwrap_or(1.0) * opacity;
+        let stroke_opacity = number(attrs, "stroke-opacity").unwrap_or(1.0) * opacity;
+        style.fill = style.fill.map(|c| c.with_alpha(c.a * fill_opacity));
+        style.stroke = style.stroke.map(|c| c.with_alpha(c.a * stroke_opacity));
+
+        if let Some(width) = length(attrs, "stroke-width") {
+            style.stroke_width = width;
+        }
+        if let Some(size) = length(attrs, "font-size") {
+            style.font_size = size;
+        }
+        if let Some(family) = attrs.get("font-family") {
+            let family = family.split(',').next().unwrap_or("").trim();
+            style.font_family = Some(family.trim_matches(|c| c == '\'' || c == '"').to_string());
+        }
+        if let Some(transform) = attrs.get("transform") {
+            match parse_translate(transform) {
+                Some((dx, dy)) => {
+                    style.offset.0 += dx;
+                    style.offset.1 += dy;
+                }
+                None => warn!(
+                    "⚠️ SVG import: ignoring unsupported transform '{}'",
+                    transform
+                ),
+            }
+        }
+        style
+    }
+
+    fn build_element(
+        &mut self,
+        name: &str,
+        attrs: &HashMap<String, String>,
+        style: &InheritedStyle,
+    ) -> Option<DocumentElement> {
+        let (ox, oy) = style.offset;
+        let coord = |key: &str| length(attrs, key).unwrap_or(0.0);
+
+        let (kind, bounds, vertices) = match name {
+            "rect" => (
+                ShapeKind::Rectangle,
+                rect(ox + coord("x"), oy + coord("y"), coord("width"), coord("height"))?,
+                Vec::new(),
+            ),
+            "circle" => {
+                let r = coord("r");
+                (
+                    ShapeKind::Ellipse,
+                    rect(ox + coord("cx") - r, oy + coord("cy") - r, r * 2.0, r * 2.0)?,
+                    Vec::new(),
+                )
+            }
+            "ellipse" => {
+                let (rx, ry) = (coord("rx"), coord("ry"));
+                (
+                    ShapeKind::Ellipse,
+                    rect(ox + coord("cx") - rx, oy + coord("cy") - ry, rx * 2.0, ry * 2.0)?,
+                    Vec::new(),
+                )
+            }
+            "line" => {
+                // Lines are stored top-left to bottom-right, like drawn lines
+                let (x1, y1) = (ox + coord("x1"), oy + coord("y1"));
+                let (x2, y2) = (ox + coord("x2"), oy + coord("y2"));
+                let bounds = Rect::new(
+                    Point::new(x1.min(x2), y1.min(y2)),
+                    Size::new((x2 - x1).abs(), (y2 - y1).abs()),
+                );
+                (ShapeKind::Line, bounds, Vec::new())
+            }
+            "polygon" => {
+                let numbers: Vec<f32> = attrs
+                    .get("points")?
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter_map(|n| n.parse().ok())
+                    .collect();
+                let points: Vec<Point> = numbers
+                    .chunks_exact(2)
+                    .map(|p| Point::new(ox + p[0], oy + p[1]))
+                    .collect();
+                let (bounds, vertices) = ShapeElement::polygon_geometry(&points)?;
+                (ShapeKind::Polygon, bounds, vertices)
+            }
+            "image" => return self.build_image(attrs, style),
+            _ => return None,
+        };
+
+        let line_style = match attrs.get("stroke-dasharray").map(|d| d.trim()) {
+            None | Some("none") | Some("") => LineStyle::Solid,
+            Some(dashes) => {
+                let first: f32 = dashes
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .find_map(|n| n.parse().ok())
+                    .unwrap_or(0.0);
+                if first <= style.stroke_width {
+                    LineStyle::Dotted
+                } else {
+                    LineStyle::Dashed
+                }
+            }
+        };
+        // Lines only ever stroke; SVG's default black fill doesn't apply to them
+        let fill = if kind == ShapeKind::Line {
+            None
+        } else {
+            style.fill.map(Fill::Solid)
+        };
+        // Rounded rects: elliptical corners are approximated with one radius
+        let corner_radius = match kind {
+            ShapeKind::Rectangle => length(attrs, "rx")
+                .or_else(|| length(attrs, "ry"))
+                .unwrap_or(0.0),
+            _ => 0.0,
+        };
+
+        Some(DocumentElement::Shape(ShapeElement {
+            id: uuid::Uuid::new_v4(),
+            kind,
+            bounds,
+            stroke: style.stroke,
+            stroke_width: style.stroke_width,
+            fill,
+            visible: true,
+            locked: false,
+            rotation: 0.0,
+            vertices,
+            line_style,
+            opacity: 1.0,
+            corner_radius,
+        }))
+    }
+
+    fn build_image(
+        &mut self,
+        attrs: &HashMap<String, String>,
+        style: &InheritedStyle,
+    ) -> Option<DocumentElement> {
+        let href = attrs.get("href")?;
+        if href.starts_with("data:") {
+            warn!("⚠️ SVG import: skipping embedded (data URI) image");
+            return None;
+        }
+        let (ox, oy) = style.offset;
+        let coord = |key: &str| length(attrs, key).unwrap_or(0.0);
+        let bounds = rect(ox + coord("x"), oy + coord("y"), coord("width"), coord("height"))?;
+
+        let href = href.strip_prefix("file://").unwrap_or(href);
+        let path = match self.base_dir {
+            Some(dir) if Path::new(href).is_relative() => dir.join(href),
+            _ => Path::new(href).to_path_buf(),
+        };
+        let source = self.document.assets.register(path);
+        // SVG's default preserveAspectRatio (`xMidYMid meet`) letterboxes
+        let fit = match attrs.get("preserveAspectRatio").map(|v| v.trim()) {
+            Some("none") => ImageFit::Stretch,
+            Some(value) if value.ends_with("slice") => ImageFit::Cover,
+            _ => ImageFit::Contain,
+        };
+
+        Some(DocumentElement::Image(ImageElement {
+            id: uuid::Uuid::new_v4(),
+            source,
+            bounds,
+            visible: true,
+            locked: false,
+            rotation: 0.0,
+            fit,
+            opacity: 1.0,
+        }))
+    }
+}
+
+/// Text content of the `<text>` at `start` (including nested `<tspan>`s)
+/// and the index of the token after it
+fn collect_text(tokens: &[Token], start: usize, self_closing: bool) -> (String, usize) {
+    if self_closing {
+        return (String::new(), start + 1);
+    }
+    let mut content = String::new();
+    let mut depth = 0;
+    for (index, token) in tokens.iter().enumerate().skip(start + 1) {
+        match token {
+            Token::Text(text) => content.push_str(text),
+            Token::Open { self_closing, .. } if !self_closing => depth += 1,
+            Token::Close(_) if depth == 0 => return (content, index + 1),
+            Token::Close(_) => depth -= 1,
+            Token::Open { .. } => {}
+        }
+    }
+    (content, tokens.len())
+}
+
+/// Index of the token after the element starting at `start`
+fn skip_subtree(tokens: &[Token], start: usize, self_closing: bool) -> usize {
+    if self_closing {
+        return start + 1;
+    }
+    let mut depth = 0;
+    for (index, token) in tokens.iter().enumerate().skip(start + 1) {
+        match token {
+            Token::Open { self_closing, .. } if !self_closing => depth += 1,
+            Token::Close(_) if depth == 0 => return index + 1,
+            Token::Close(_) => depth -= 1,
+            _ => {}
+        }
+    }
+    tokens.len()
+}
+
+/// Text element positioned so its first baseline sits at the SVG `y`
+fn build_text(
+    attrs: &HashMap<String, String>,
+    content: &str,
+    style: &InheritedStyle,
+) -> Option<DocumentElement> {
+    // Collapse whitespace like SVG does by default
+    let content = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    if content.is_empty() {
+        return None;
+    }
+
+    let font_size = style.font_size.max(1.0);
+    let (ox, oy) = style.offset;
+    let x = ox + length(attrs, "x").unwrap_or(0.0);
+    let baseline = oy + length(attrs, "y").unwrap_or(0.0);
+
+    // Estimated advance: half an em for ASCII, a full em for wide (e.g. CJK) text
+    let text_width: f32 = content
+        .chars()
+        .map(|c| if c.is_ascii() { 0.6 } else { 1.0 })
+        .sum::<f32>()
+        * font_size;
+    let padding = DEFAULT_TEXT_PADDING;
+    let width = text_width + padding * 2.0;
+    let height = font_size * 1.2 + padding * 2.0;
+
+    let (alignment, left) = match attrs.get("text-anchor").map(String::as_str) {
+        Some("middle") => (TextAlignment::Center, x - width / 2.0),
+        Some("end") => (TextAlignment::End, x - width + padding),
+        _ => (TextAlignment::Start, x - padding),
+    };
+    let top = baseline - font_size * 0.8 - padding;
+
+    let mut text_style = TextStyle {
+        font_size,
+        alignment,
+        color: style.fill.unwrap_or(Color::from_rgb(0.0, 0.0, 0.0)),
+        ..Default::default()
+    };
+    if let Some(family) = &style.font_family {
+        text_style.font_family = family.clone();
+    }
+    match attrs.get("font-weight").map(String::as_str) {
+        Some("bold" | "bolder" | "700" | "800") => text_style.weight = FontWeight::Bold,
+        Some("900") => text_style.weight = FontWeight::Black,
+        Some("lighter" | "300") => text_style.weight = FontWeight::Light,
+        _ => {}
+    }
+    text_style.italic = matches!(
+        attrs.get("font-style").map(String::as_str),
+        Some("italic" | "oblique")
+    );
+
+    Some(DocumentElement::Text(TextElement {
+        id: uuid::Uuid::new_v4(),
+        content,
+        style: text_style,
+        bounds: Rect::new(Point::new(left, top), Size::new(width, height)),
+        auto_resize_height: false,
+        visible: true,
+        locked: false,
+        rotation: 0.0,
+        rich_text: None,
+        padding: Insets::uniform(DEFAULT_TEXT_PADDING),
+        text_path: None,
+        opacity: 1.0,
+    }))
+}
+
+/// A rectangle, or `None` when it would be empty (SVG doesn't render those)
+fn rect(x: f32, y: f32, width: f32, height: f32) -> Option<Rect> {
+    (width > 0.0 && height > 0.0).then(|| Rect::new(Point::new(x, y), Size::new(width, height)))
+}
+
+fn number(attrs: &HashMap<String, String>, key: &str) -> Option<f32> {
+    attrs.get(key)?.trim().parse().ok()
+}
+
+/// A length attribute in document units (absolute units are converted; `%` is unsupported)
+fn length(attrs: &HashMap<String, String>, key: &str) -> Option<f32> {
+    parse_length(attrs.get(key)?)
+}
+
+fn parse_length(value: &str) -> Option<f32> {
+    let value = value.trim();
+    let units = [
+        ("px", 1.0),
+        ("pt", 1.0),
+        ("mm", 72.0 / 25.4),
+        ("cm", 72.0 / 2.54),
+        ("in", 72.0),
+    ];
+    for (suffix, factor) in units {
+        if let Some(number) = value.strip_suffix(suffix) {
+            return number.trim().parse::<f32>().ok().map(|n| n * factor);
+        }
+    }
+    value.parse().ok()
+}
+
+/// Offset of a `translate(x[, y])` transform; `None` for anything else
+fn parse_translate(transform: &str) -> Option<(f32, f32)> {
+    let args = transform
+        .trim()
+        .strip_prefix("translate")?
+        .trim_start()
+        .strip_prefix('(')?
+        .strip_suffix(')')?;
+    let numbers: Vec<f32> = args
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|n| !n.is_empty())
+        .map(|n| n.parse().ok())
+        .collect::<Option<_>>()?;
+    match numbers[..] {
+        [dx] => Some((dx, 0.0)),
+        [dx, dy] => Some((dx, dy)),
+        _ => None,
+    }
+}
+
+/// Parse an SVG paint: `Some(None)` is `none`, `None` is an unsupported value
+fn parse_color(value: &str) -> Option<Option<Color>> {
+    let value = value.trim();
+    let rgb = |r: u8, g: u8, b: u8| {
+        Some(Some(Color::from_rgb(
+            r as f32 / 255.0,
+            g as f32 / 255.0,
+            b as f32 / 255.0,
+        )))
+    };
+
+    if let Some(hex) = value.strip_prefix('#') {
+        let digit = |i: usize| u8::from_str_radix(hex.get(i..i + 1)?, 16).ok();
+        let pair = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        return match hex.len() {
+            3 => rgb(digit(0)? * 17, digit(1)? * 17, digit(2)? * 17),
+            6 => rgb(pair(0)?, pair(2)?, pair(4)?),
+            _ => None,
+        };
+    }
+    if let Some(args) = value
+        .strip_prefix("rgb(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        let channels: Vec<u8> = args
+            .split(',')
+            .map(|c| {
+                let c = c.trim();
+                match c.strip_suffix('%') {
+                    Some(percent) => percent.trim().parse::<f32>().ok().map(|p| p * 2.55),
+                    None => c.parse::<f32>().ok(),
+                }
+                .map(|v| v.clamp(0.0, 255.0).round() as u8)
+            })
+            .collect::<Option<_>>()?;
+        return match channels[..] {
+            [r, g, b] => rgb(r, g, b),
+            _ => None,
+        };
+    }
+
+    match value.to_ascii_lowercase().as_str() {
+        "none" | "transparent" => Some(None),
+        "black" => rgb(0, 0, 0),
+        "white" => rgb(255, 255, 255),
+        "red" => rgb(255, 0, 0),
+        "green" => rgb(0, 128, 0),
+        "lime" => rgb(0, 255, 0),
+        "blue" => rgb(0, 0, 255),
+        "yellow" => rgb(255, 255, 0),
+        "orange" => rgb(255, 165, 0),
+        "purple" => rgb(128, 0, 128),
+        "gray" | "grey" => rgb(128, 128, 128),
+        "silver" => rgb(192, 192, 192),
+        "navy" => rgb(0, 0, 128),
+        "teal" => rgb(0, 128, 128),
+        "maroon" => rgb(128, 0, 0),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_color_forms() {
+        let red = Color::from_rgb(1.0, 0.0, 0.0);
+        assert_eq!(parse_color("#f00"), Some(Some(red)));
+        assert_eq!(parse_color("#FF0000"), Some(Some(red)));
+        assert_eq!(parse_color("rgb(255, 0, 0)"), Some(Some(red)));
+        assert_eq!(parse_color("red"), Some(Some(red)));
+        assert_eq!(parse_color("none"), Some(None));
+        assert_eq!(parse_color("url(#gradient)"), None);
+    }
+
+    #[test]
+    fn test_parse_length_units_and_translate() {
+        assert_eq!(parse_length("12"), Some(12.0));
+        assert_eq!(parse_length("12px"), Some(12.0));
+        assert_eq!(parse_length("1in"), Some(72.0));
+        assert_eq!(parse_length("50%"), None);
+        assert_eq!(parse_translate("translate(10, 20)"), Some((10.0, 20.0)));
+        assert_eq!(parse_translate("translate(5)"), Some((5.0, 0.0)));
+        assert_eq!(parse_translate("rotate(45)"), None);
+    }
+
+    #[test]
+    fn test_import_basic_primitives() {
+        let svg = r##"<?xml version="1.0"?>
+            <!-- legacy diagram -->
+            <svg xmlns="http://www.w3.org/2000/svg" width="400" height="300">
+              <rect x="10" y="20" width="100" height="50" fill="#00ff00" stroke="black"/>
+              <g transform="translate(100, 100)" fill="none" stroke="blue">
+                <circle cx="10" cy="10" r="10"/>
+                <ellipse cx="50" cy="20" rx="30" ry="10" style="stroke-width: 3"/>
+              </g>
+              <line x1="0" y1="0" x2="40" y2="30" stroke="red" stroke-dasharray="8 4"/>
+              <path d="M0 0 L10 10"/>
+              <text x="20" y="200" font-size="20">Hello &amp; <tspan>世界</tspan></text>
+            </svg>"##;
+        let document = import_svg_str(svg, None).expect("valid svg");
+        assert_eq!(document.metadata.page_size, PageSize::Custom(400.0, 300.0));
+
+        let elements = &document.pages[0].elements;
+        // The path is skipped
+        assert_eq!(elements.len(), 5);
+
+        let DocumentElement::Shape(rect) = &elements[0] else {
+            panic!("expected rectangle");
+        };
+        assert_eq!(rect.kind, ShapeKind::Rectangle);
+        assert_eq!(rect.bounds.origin, Point::new(10.0, 20.0));
+        assert_eq!(rect.fill, Some(Fill::Solid(Color::from_rgb(0.0, 1.0, 0.0))));
+        assert_eq!(rect.stroke, Some(Color::from_rgb(0.0, 0.0, 0.0)));
+
+        let DocumentElement::Shape(circle) = &elements[1] else {
+            panic!("expected circle");
+        };
+        assert_eq!(circle.kind, ShapeKind::Ellipse);
+        assert_eq!(circle.bounds.origin, Point::new(100.0, 100.0));
+        assert_eq!(circle.fill, None);
+        assert_eq!(circle.stroke, Some(Color::from_rgb(0.0, 0.0, 1.0)));
+
+        let DocumentElement::Shape(ellipse) = &elements[2] else {
+            panic!("expected ellipse");
+        };
+        assert_eq!(ellipse.stroke_width, 3.0);
+
+        let DocumentElement::Shape(line) = &elements[3] else {
+            panic!("expected line");
+        };
+        assert_eq!(line.kind, ShapeKind::Line);
+        assert_eq!(line.line_style, LineStyle::Dashed);
+
+        let DocumentElement::Text(text) = &elements[4] else {
+            panic!("expected text");
+        };
+        assert_eq!(text.content, "Hello & 世界");
+        assert_eq!(text.style.font_size, 20.0);
+    }
+
+    #[test]
+    fn test_import_resolves_relative_images() {
+        let svg = r#"<svg viewBox="0 0 100 100"><image x="0" y="0" width="20" height="10" xlink:href="img/photo.png"/></svg>"#;
+        let document = import_svg_str(svg, Some(Path::new("/tmp/diagrams"))).expect("valid svg");
+        let DocumentElement::Image(image) = &document.pages[0].elements[0] else {
+            panic!("expected image");
+        };
+        let asset = document.assets.get(image.source).expect("registered asset");
+        assert_eq!(asset.path, Path::new("/tmp/diagrams/img/photo.png"));
+        assert_eq!(image.fit, ImageFit::Contain);
+    }
+
+    #[test]
+    fn test_import_maps_preserve_aspect_ratio_to_fit() {
+        let svg = r#"<svg viewBox="0 0 100 100">
+            <image width="20" height="10" href="a.png" preserveAspectRatio="none"/>
+            <image width="20" height="10" href="b.png" preserveAspectRatio="xMidYMid slice"/>
+        </svg>"#;
+        let document = import_svg_str(svg, None).expect("valid svg");
+        let fits: Vec<ImageFit> = document.pages[0]
+            .elements
+            .iter()
+            .filter_map(|element| match element {
+                DocumentElement::Image(image) => Some(image.fit),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(fits, vec![ImageFit::Stretch, ImageFit::Cover]);
+    }
+}
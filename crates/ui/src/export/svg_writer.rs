@@ -0,0 +1,1195 @@
+//! SVG writer
+//!
+//! Serializes pages as structured SVG markup for downstream scripting. Every
+//! element is wrapped in a `<g id="{uuid}">` carrying its opacity and
+//! rotation, and frames and groups nest their children's `<g>` elements, so
+//! external tools can target individual elements. Text stays as `<text>`
+//! with font attributes, one `<tspan>` per line as Pango wraps it in the box.
+//! Images are embedded as base64 `data:` URIs so the file stands alone.
+
+use gtk4::{cairo, pango};
+use std::fmt::Write as _;
+use std::ops::Range;
+use testruct_core::document::{
+    DocumentElement, Fill, FrameElement, ImageElement, ImageFit, LineStyle, Page, PageBackground,
+    QrCodeElement, ShapeElement, ShapeKind, TableElement, TextElement, TextPath,
+    DEFAULT_TEXT_PADDING, QR_QUIET_ZONE,
+};
+use testruct_core::layout::{Insets, Point, Rect, Size};
+use testruct_core::typography::{Color, FontWeight, RichText, TextAlignment, TextStyle};
+use testruct_core::workspace::assets::{AssetCatalog, AssetRef};
+use testruct_core::Document;
+
+use crate::canvas::rendering_text;
+use crate::canvas::shapes_rendering::stroke_line_width;
+use crate::export::BackgroundOption;
+
+/// Distance between baselines, as a multiple of the font size, at line height 1.0
+const LINE_ADVANCE_EM: f64 = 1.2;
+
+/// Stroke color of frame outlines (matches the other exporters)
+const FRAME_BORDER_COLOR: Color = Color {
+    r: 0.9,
+    g: 0.9,
+    b: 0.9,
+    a: 1.0,
+};
+
+/// SVG for every page of `document`, stacked top to bottom
+///
+/// Each page becomes a `<g id="page-N">` translated below the previous one.
+///
+/// Fails if Cairo cannot create the scratch surface used to lay out text.
+pub fn document_svg(document: &Document, catalog: &AssetCatalog) -> Result<String, cairo::Error> {
+    let mut writer = SvgWriter::new(catalog);
+    let mut width: f32 = 0.0;
+    let mut offset: f32 = 0.0;
+    for (index, page) in document.pages.iter().enumerate() {
+        let size = document.page_size_of(page);
+        writer.write_page(page, index, size, offset, &BackgroundOption::White)?;
+        width = width.max(size.width);
+        offset += size.height;
+    }
+    Ok(writer.finish(Size::new(width, offset), &document.metadata.title))
+}
+
+/// SVG for a single page of `document`
+pub fn page_svg(
+    document: &Document,
+    page_index: usize,
+    background: &BackgroundOption,
+    catalog: &AssetCatalog,
+) -> Result<String, cairo::Error> {
+    let page = &document.pages[page_index];
+    let size = document.page_size_of(page);
+    let mut writer = SvgWriter::new(catalog);
+    writer.write_page(page, page_index, size, 0.0, background)?;
+    Ok(writer.finish(size, &document.metadata.title))
+}
+
+/// Accumulates the body and `<defs>` of an SVG document
+struct SvgWriter<'a> {
+    catalog: &'a AssetCatalog,
+    body: String,
+    defs: String,
+    gradient_count: usize,
+    depth: usize,
+}
+
+impl<'a> SvgWriter<'a> {
+    fn new(catalog: &'a AssetCatalog) -> Self {
+        Self {
+            catalog,
+            body: String::new(),
+            defs: String::new(),
+            gradient_count: 0,
+            depth: 1,
+        }
+    }
+
+    /// Wrap the body in the `<svg>` root sized to `size` points
+    fn finish(self, size: Size, title: &str) -> String {
+        let mut svg = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" \
+             xmlns:xlink=\"http://www.w3.org/1999/xlink\" version=\"1.1\" \
+             width=\"{w}pt\" height=\"{h}pt\" viewBox=\"0 0 {w} {h}\">",
+            w = num(size.width),
+            h = num(size.height)
+        );
+        if !title.trim().is_empty() {
+            let _ = writeln!(svg, "  <title>{}</title>", escape(title));
+        }
+        if !self.defs.is_empty() {
+            svg.push_str("  <defs>\n");
+            svg.push_str(&self.defs);
+            svg.push_str("  </defs>\n");
+        }
+        svg.push_str(&self.body);
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Append one line of markup at the current nesting depth
+    fn line(&mut self, markup: &str) {
+        for _ in 0..self.depth {
+            self.body.push_str("  ");
+        }
+        self.body.push_str(markup);
+        self.body.push('\n');
+    }
+
+    fn open(&mut self, markup: &str) {
+        self.line(markup);
+        self.depth += 1;
+    }
+
+    fn close(&mut self, tag: &str) {
+        self.depth -= 1;
+        self.line(&format!("</{}>", tag));
+    }
+
+    fn write_page(
+        &mut self,
+        page: &Page,
+        index: usize,
+        size: Size,
+        offset: f32,
+        background: &BackgroundOption,
+    ) -> Result<(), cairo::Error> {
+        let transform = if offset != 0.0 {
+            format!(" transform=\"translate(0 {})\"", num(offset))
+        } else {
+            String::new()
+        };
+        self.open(&format!(
+            "<g id=\"page-{}\" class=\"page\"{}>",
+            index + 1,
+            transform
+        ));
+
+        let page_rect = Rect::new(Point::new(0.0, 0.0), size);
+        if let Some(color) = background.to_color() {
+            self.line(&format!(
+                "<rect {} fill=\"{}\"{} stroke=\"none\"/>",
+                rect_attributes(&page_rect),
+                color_hex(color),
+                alpha_attribute("fill-opacity", color.a)
+            ));
+        }
+        match &page.metadata.background {
+            PageBackground::None => {}
+            PageBackground::Color(color) => self.line(&format!(
+                "<rect class=\"page-background\" {} fill=\"{}\"{} stroke=\"none\"/>",
+                rect_attributes(&page_rect),
+                color_hex(*color),
+                alpha_attribute("fill-opacity", color.a)
+            )),
+            PageBackground::Image { source, fit } => {
+                self.write_image_source(*source, &page_rect, *fit, " class=\"page-background\"")
+            }
+        }
+        if !background.is_transparent() {
+            self.line(&format!(
+                "<rect class=\"page-border\" {} fill=\"none\" stroke=\"#000000\" stroke-width=\"0.5\"/>",
+                rect_attributes(&page_rect)
+            ));
+        }
+
+        for element in page.layered_elements() {
+            self.write_element(element)?;
+        }
+        self.close("g");
+        Ok(())
+    }
+
+    /// Write `element` as a `<g>` with its id, opacity and rotation
+    fn write_element(&mut self, element: &DocumentElement) -> Result<(), cairo::Error> {
+        // Hidden elements (and their children) are left out of the export
+        if !element.is_visible() {
+            return Ok(());
+        }
+
+        let (class, name) = match element {
+            DocumentElement::Shape(_) => ("shape", None),
+            DocumentElement::Text(_) => ("text", None),
+            DocumentElement::Image(_) => ("image", None),
+            DocumentElement::Frame(_) => ("frame", None),
+            DocumentElement::Group(group) => ("group", Some(group.name.as_str())),
+            DocumentElement::Table(_) => ("table", None),
+            DocumentElement::QrCode(_) => ("qr-code", None),
+        };
+        let mut markup = format!("<g id=\"{}\" class=\"{}\"", element.id(), class);
+        if let Some(name) = name.filter(|name| !name.is_empty()) {
+            let _ = write!(markup, " data-name=\"{}\"", escape(name));
+        }
+        let opacity = element.opacity();
+        if opacity < 1.0 {
+            let _ = write!(markup, " opacity=\"{}\"", num(opacity.max(0.0)));
+        }
+        let rotation = element.rotation();
+        if rotation != 0.0 {
+            let bounds = element.bounds();
+            let _ = write!(
+                markup,
+                " transform=\"rotate({} {} {})\"",
+                num(rotation),
+                num(bounds.origin.x + bounds.size.width / 2.0),
+                num(bounds.origin.y + bounds.size.height / 2.0)
+            );
+        }
+        markup.push('>');
+        self.open(&markup);
+
+        match element {
+            DocumentElement::Shape(shape) => self.write_shape(shape),
+            DocumentElement::Text(text) if text.text_path.is_some() => {
+                self.write_text_on_path(text)
+            }
+            DocumentElement::Text(text) => self.write_text(
+                &text.bounds,
+                &text.content,
+                &text.style,
+                text.rich_text_for_render(),
+                &text.padding,
+            )?,
+            DocumentElement::Image(image) => self.write_image(image),
+            DocumentElement::Frame(frame) => self.write_frame(frame)?,
+            DocumentElement::Group(group) => {
+                for child in &group.children {
+                    self.write_element(child)?;
+                }
+            }
+            DocumentElement::Table(table) => self.write_table(table)?,
+            DocumentElement::QrCode(qr) => self.write_qr_code(qr),
+        }
+        self.close("g");
+        Ok(())
+    }
+
+    fn write_shape(&mut self, shape: &ShapeElement) {
+        let bounds = &shape.bounds;
+        let fill = self.fill_attributes(shape.fill.as_ref(), bounds);
+        let stroke = stroke_attributes(shape.stroke, shape.stroke_width, shape.line_style);
+        match shape.kind {
+            ShapeKind::Rectangle => {
+                let radius = shape.effective_corner_radius();
+                let corners = if radius > 0.0 {
+                    format!(" rx=\"{r}\" ry=\"{r}\"", r = num(radius))
+                } else {
+                    String::new()
+                };
+                self.line(&format!(
+                    "<rect {}{}{}{}/>",
+                    rect_attributes(bounds),
+                    corners,
+                    fill,
+                    stroke
+                ));
+            }
+            ShapeKind::Ellipse => {
+                self.line(&format!(
+                    "<ellipse cx=\"{}\" cy=\"{}\" rx=\"{}\" ry=\"{}\"{}{}/>",
+                    num(bounds.origin.x + bounds.size.width / 2.0),
+                    num(bounds.origin.y + bounds.size.height / 2.0),
+                    num(bounds.size.width / 2.0),
+                    num(bounds.size.height / 2.0),
+                    fill,
+                    stroke
+                ));
+            }
+            ShapeKind::Line | ShapeKind::Arrow => {
+                self.line(&format!(
+                    "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" fill=\"none\"{}/>",
+                    num(bounds.origin.x),
+                    num(bounds.origin.y),
+                    num(bounds.origin.x + bounds.size.width),
+                    num(bounds.origin.y + bounds.size.height),
+                    stroke
+                ));
+            }
+            ShapeKind::Polygon => {
+                let mut vertices = shape.vertex_positions();
+                if vertices.len() < 3 {
+                    vertices = regular_polygon(bounds, 5);
+                }
+                let points: Vec<String> = vertices
+                    .iter()
+                    .map(|p| format!("{},{}", num(p.x), num(p.y)))
+                    .collect();
+                self.line(&format!(
+                    "<polygon points=\"{}\"{}{}/>",
+                    points.join(" "),
+                    fill,
+                    stroke
+                ));
+            }
+        }
+    }
+
+    /// `fill` attributes, adding a `<linearGradient>` to the defs for gradients
+    fn fill_attributes(&mut self, fill: Option<&Fill>, bounds: &Rect) -> String {
+        let Some(fill) = fill else {
+            return " fill=\"none\"".to_string();
+        };
+        match fill {
+            Fill::Solid(color) => format!(
+                " fill=\"{}\"{}",
+                color_hex(*color),
+                alpha_attribute("fill-opacity", color.a)
+            ),
+            Fill::LinearGradient { start, end, .. } => {
+                let Some((from, to)) = fill.gradient_line(bounds) else {
+                    return " fill=\"none\"".to_string();
+                };
+                self.gradient_count += 1;
+                let id = format!("gradient-{}", self.gradient_count);
+                let _ = writeln!(
+                    self.defs,
+                    "    <linearGradient id=\"{}\" gradientUnits=\"userSpaceOnUse\" \
+                     x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\">",
+                    id,
+                    num(from.x),
+                    num(from.y),
+                    num(to.x),
+                    num(to.y)
+                );
+                for (offset, color) in [(0, start), (1, end)] {
+                    let _ = writeln!(
+                        self.defs,
+                        "      <stop offset=\"{}\" stop-color=\"{}\"{}/>",
+                        offset,
+                        color_hex(*color),
+                        alpha_attribute("stop-opacity", color.a)
+                    );
+                }
+                self.defs.push_str("    </linearGradient>\n");
+                format!(" fill=\"url(#{})\"", id)
+            }
+        }
+    }
+
+    /// Write a text block as `<text>` with one `<tspan>` per line
+    ///
+    /// Lines break where Pango wraps the text at the content width, as in the
+    /// PDF and PNG exports. Rich text runs get their own nested `<tspan>` with
+    /// their font attributes. Lines are placed inside `padding` one line
+    /// advance apart, and justified lines other than paragraph endings are
+    /// stretched to the content width with `textLength`.
+    fn write_text(
+        &mut self,
+        bounds: &Rect,
+        content: &str,
+        style: &TextStyle,
+        rich_text: Option<&RichText>,
+        padding: &Insets,
+    ) -> Result<(), cairo::Error> {
+        if let Some(background) = style.background_color {
+            self.line(&format!(
+                "<rect {} fill=\"{}\"{} stroke=\"none\"/>",
+                rect_attributes(bounds),
+                color_hex(background),
+                alpha_attribute("fill-opacity", background.a)
+            ));
+        }
+
+        let advance = style.font_size as f64 * LINE_ADVANCE_EM * style.line_height.max(0.1) as f64;
+        let content_rect = bounds.inset(padding);
+        let left = content_rect.origin.x as f64;
+        let top = content_rect.origin.y as f64;
+        let (anchor, x) = match style.alignment {
+            TextAlignment::Start | TextAlignment::Justified => (None, left),
+            TextAlignment::Center => (Some("middle"), left + content_rect.size.width as f64 / 2.0),
+            TextAlignment::End => (Some("end"), left + content_rect.size.width as f64),
+        };
+
+        let measure = if style.vertical {
+            content_rect.size.height
+        } else {
+            content_rect.size.width
+        };
+        let plain_text = match rich_text {
+            Some(rich_text) => rich_text.get_plain_text(),
+            None => content.to_string(),
+        };
+        let line_ranges = layout_lines(&plain_text, style, rich_text, measure)?;
+        let lines = text_lines(content, rich_text, &line_ranges);
+        let extent = (advance * lines.len() as f64) as f32;
+        let available = if style.vertical {
+            content_rect.size.width
+        } else {
+            content_rect.size.height
+        };
+        let offset = style.vertical_alignment.offset(available, extent) as f64;
+
+        let mut markup = format!("<text{} xml:space=\"preserve\"", font_attributes(style));
+        if let Some(anchor) = anchor {
+            let _ = write!(markup, " text-anchor=\"{}\"", anchor);
+        }
+        if style.vertical {
+            markup.push_str(" writing-mode=\"tb-rl\"");
+        }
+        markup.push('>');
+        self.open(&markup);
+
+        for (index, line) in lines.into_iter().enumerate() {
+            // Horizontal lines run downwards; vertical columns run right to left
+            let (line_x, line_y) = if style.vertical {
+                let right = left + content_rect.size.width as f64 - offset;
+                (right - advance / 2.0 - advance * index as f64, top)
+            } else {
+                let baseline = top + offset + style.font_size as f64;
+                (x, baseline + advance * index as f64)
+            };
+            let mut spans = String::new();
+            for (text, run_style) in line {
+                match run_style {
+                    Some(run_style) => {
+                        let _ = write!(
+                            spans,
+                            "<tspan{}>{}</tspan>",
+                            font_attributes(run_style),
+                            escape(text)
+                        );
+                    }
+                    None => spans.push_str(&escape(text)),
+                }
+            }
+            let paragraph_end = line_ranges[index].1;
+            let justify = if style.alignment == TextAlignment::Justified && !paragraph_end {
+                format!(" textLength=\"{}\" lengthAdjust=\"spacing\"", num(measure))
+            } else {
+                String::new()
+            };
+            self.line(&format!(
+                "<tspan x=\"{}\" y=\"{}\"{}>{}</tspan>",
+                num(line_x),
+                num(line_y),
+                justify,
+                spans
+            ));
+        }
+        self.close("text");
+        Ok(())
+    }
+
+    /// Write text that follows its path as `<textPath>`, with the path in the defs
+    ///
+    /// A circle starts at its start angle, or opposite it for centred text,
+    /// so the text has the whole circle on either side of its anchor. Like
+    /// the canvas, the whole text uses the element style, not its rich text runs.
+    fn write_text_on_path(&mut self, text: &TextElement) {
+        let Some(path) = &text.text_path else {
+            return;
+        };
+        let (bounds, style) = (&text.bounds, &text.style);
+        if let Some(background) = style.background_color {
+            self.line(&format!(
+                "<rect {} fill=\"{}\"{} stroke=\"none\"/>",
+                rect_attributes(bounds),
+                color_hex(background),
+                alpha_attribute("fill-opacity", background.a)
+            ));
+        }
+
+        let (anchor, start_offset) = match style.alignment {
+            TextAlignment::Start | TextAlignment::Justified => (None, "0%"),
+            TextAlignment::Center => (Some("middle"), "50%"),
+            TextAlignment::End => (Some("end"), "100%"),
+        };
+        let d = match path {
+            TextPath::Circle {
+                radius,
+                start_angle,
+            } => {
+                let begin = match style.alignment {
+                    TextAlignment::Center => start_angle - 180.0,
+                    _ => *start_angle,
+                };
+                let center = TextPath::circle_center(bounds);
+                let from = TextPath::circle_point(center, *radius, begin);
+                let half = TextPath::circle_point(center, *radius, begin + 180.0);
+                format!(
+                    "M{},{}A{r},{r} 0 1 1 {},{}A{r},{r} 0 1 1 {},{}",
+                    num(from.x),
+                    num(from.y),
+                    num(half.x),
+                    num(half.y),
+                    num(from.x),
+                    num(from.y),
+                    r = num(*radius)
+                )
+            }
+            TextPath::Bezier { start, segments } => {
+                let point = |p: &Point| {
+                    format!(
+                        "{},{}",
+                        num(bounds.origin.x + p.x),
+                        num(bounds.origin.y + p.y)
+                    )
+                };
+                let mut d = format!("M{}", point(start));
+                for segment in segments {
+                    let _ = write!(
+                        d,
+                        "C{} {} {}",
+                        point(&segment.control1),
+                        point(&segment.control2),
+                        point(&segment.end)
+                    );
+                }
+                d
+            }
+        };
+        let path_id = format!("text-path-{}", text.id);
+        let _ = writeln!(
+            self.defs,
+            "    <path id=\"{}\" d=\"{}\" fill=\"none\"/>",
+            path_id, d
+        );
+
+        let mut markup = format!("<text{} xml:space=\"preserve\"", font_attributes(style));
+        if let Some(anchor) = anchor {
+            let _ = write!(markup, " text-anchor=\"{}\"", anchor);
+        }
+        markup.push('>');
+        self.open(&markup);
+        self.line(&format!(
+            "<textPath xlink:href=\"#{}\" startOffset=\"{}\">{}</textPath>",
+            path_id,
+            start_offset,
+            escape(&text.content.replace('\n', " "))
+        ));
+        self.close("text");
+    }
+
+    fn write_image(&mut self, image: &ImageElement) {
+        self.write_image_source(image.source, &image.bounds, image.fit, "");
+    }
+
+    /// Write an `<image>` embedding the asset file, or a placeholder if it can't be read
+    fn write_image_source(&mut self, source: AssetRef, bounds: &Rect, fit: ImageFit, extra: &str) {
+        let aspect = match fit {
+            ImageFit::Stretch => "none",
+            ImageFit::Contain => "xMidYMid meet",
+            ImageFit::Cover => "xMidYMid slice",
+        };
+        let href = self.catalog.get(source).and_then(|metadata| {
+            std::fs::read(&metadata.path)
+                .map(|bytes| image_data_uri(&metadata.path, &bytes))
+                .map_err(|e| {
+                    tracing::warn!("⚠️ Failed to read image {}: {}", metadata.path.display(), e)
+                })
+                .ok()
+        });
+        match href {
+            Some(href) => {
+                self.line(&format!(
+                    "<image{} {} preserveAspectRatio=\"{}\" xlink:href=\"{}\"/>",
+                    extra,
+                    rect_attributes(bounds),
+                    aspect,
+                    href
+                ));
+            }
+            None => {
+                tracing::debug!("Image asset not available, writing placeholder");
+                self.line(&format!(
+                    "<rect class=\"image-placeholder\" {} fill=\"#e6e6e6\" stroke=\"#999999\" stroke-width=\"1\"/>",
+                    rect_attributes(bounds)
+                ));
+            }
+        }
+    }
+
+    fn write_frame(&mut self, frame: &FrameElement) -> Result<(), cairo::Error> {
+        self.line(&format!(
+            "<rect {} fill=\"none\" stroke=\"{}\" stroke-width=\"1\"/>",
+            rect_attributes(&frame.bounds),
+            color_hex(FRAME_BORDER_COLOR)
+        ));
+        for child in &frame.children {
+            self.write_element(child)?;
+        }
+        Ok(())
+    }
+
+    fn write_table(&mut self, table: &TableElement) -> Result<(), cairo::Error> {
+        for row in 0..table.rows {
+            for column in 0..table.columns {
+                let Some(content) = table.cell(row, column) else {
+                    continue;
+                };
+                let text = content.get_plain_text();
+                if text.is_empty() {
+                    continue;
+                }
+                self.write_text(
+                    &table.cell_bounds(row, column),
+                    &text,
+                    &table.cell_style,
+                    Some(content),
+                    &Insets::uniform(DEFAULT_TEXT_PADDING),
+                )?;
+            }
+        }
+
+        let border = &table.border;
+        if stroke_line_width(border.width).is_none() {
+            return Ok(());
+        }
+        let bounds = &table.bounds;
+        let (x, y) = (bounds.origin.x, bounds.origin.y);
+        let (width, height) = (bounds.size.width, bounds.size.height);
+        let mut path = format!(
+            "M{},{}h{}v{}h{}z",
+            num(x),
+            num(y),
+            num(width),
+            num(height),
+            num(-width)
+        );
+        for column in 1..table.columns {
+            let line_x = x + width * column as f32 / table.columns as f32;
+            let _ = write!(path, " M{},{}v{}", num(line_x), num(y), num(height));
+        }
+        for row in 1..table.rows {
+            let line_y = y + height * row as f32 / table.rows as f32;
+            let _ = write!(path, " M{},{}h{}", num(x), num(line_y), num(width));
+        }
+        self.line(&format!(
+            "<path d=\"{}\" fill=\"none\"{}/>",
+            path,
+            stroke_attributes(Some(border.color), border.width, border.line_style)
+        ));
+        Ok(())
+    }
+
+    fn write_qr_code(&mut self, qr: &QrCodeElement) {
+        let Ok(matrix) = qr.encode() else {
+            self.line(&format!(
+                "<rect class=\"qr-placeholder\" {} fill=\"#e6e6e6\" stroke=\"#999999\" stroke-width=\"1\"/>",
+                rect_attributes(&qr.bounds)
+            ));
+            return;
+        };
+
+        let side = qr.bounds.size.width.min(qr.bounds.size.height).max(0.0);
+        let x = qr.bounds.origin.x + (qr.bounds.size.width - side) / 2.0;
+        let y = qr.bounds.origin.y + (qr.bounds.size.height - side) / 2.0;
+        let module = side / (matrix.size() + QR_QUIET_ZONE * 2) as f32;
+        let symbol_x = x + module * QR_QUIET_ZONE as f32;
+        let symbol_y = y + module * QR_QUIET_ZONE as f32;
+
+        self.line(&format!(
+            "<rect {} fill=\"#ffffff\" stroke=\"none\"/>",
+            rect_attributes(&Rect::new(Point::new(x, y), Size::new(side, side)))
+        ));
+
+        // One subpath per horizontal run of dark modules keeps the output small
+        let mut path = String::new();
+        for row in 0..matrix.size() {
+            let mut column = 0;
+            while column < matrix.size() {
+                if !matrix.is_dark(column, row) {
+                    column += 1;
+                    continue;
+                }
+                let start = column;
+                while column < matrix.size() && matrix.is_dark(column, row) {
+                    column += 1;
+                }
+                let run = (column - start) as f32 * module;
+                let _ = write!(
+                    path,
+                    "M{},{}h{}v{}h{}z",
+                    num(symbol_x + start as f32 * module),
+                    num(symbol_y + row as f32 * module),
+                    num(run),
+                    num(module),
+                    num(-run)
+                );
+            }
+        }
+        self.line(&format!("<path d=\"{}\" fill=\"#000000\" stroke=\"none\"/>", path));
+    }
+}
+
+/// Byte range of each line Pango lays `text` out in, and whether it ends a paragraph
+///
+/// The layout is configured like the PDF text (font, rich text runs, spacing,
+/// vertical gravity) and wrapped at `width`, the content width, or height for
+/// vertical text. Ranges exclude the line break itself.
+fn layout_lines(
+    text: &str,
+    style: &TextStyle,
+    rich_text: Option<&RichText>,
+    width: f32,
+) -> Result<Vec<(Range<usize>, bool)>, cairo::Error> {
+    let surface = cairo::ImageSurface::create(cairo::Format::ARgb32, 1, 1)?;
+    let ctx = cairo::Context::new(&surface)?;
+    let layout = pangocairo::functions::create_layout(&ctx);
+    layout.set_text(text);
+
+    let mut font_desc = pango::FontDescription::new();
+    font_desc.set_family(&style.font_family);
+    font_desc.set_size((style.font_size * pango::SCALE as f32) as i32);
+    font_desc.set_weight(rendering_text::pango_weight(style.weight));
+    if style.italic {
+        font_desc.set_style(pango::Style::Italic);
+    }
+    layout.set_font_description(Some(&font_desc));
+    rendering_text::apply_text_alignment(&layout, style.alignment);
+    if let Some(rich_text) = rich_text {
+        layout.set_attributes(Some(&rendering_text::rich_text_attributes(rich_text)));
+    }
+    rendering_text::apply_text_spacing(&layout, style);
+
+    if style.vertical {
+        let pango_context = layout.context();
+        pango_context.set_base_gravity(pango::Gravity::East);
+        pango_context.set_gravity_hint(pango::GravityHint::Strong);
+        layout.context_changed();
+    }
+    layout.set_width((width.max(0.0) as f64 * pango::SCALE as f64) as i32);
+    layout.set_wrap(pango::WrapMode::WordChar);
+
+    Ok(layout
+        .lines_readonly()
+        .iter()
+        .map(|line| {
+            let start = (line.start_index().max(0) as usize).min(text.len());
+            let end = (start + line.length().max(0) as usize).min(text.len());
+            let line_text = &text[start..end];
+            let trimmed = line_text.trim_end_matches(['\n', '\r']);
+            let paragraph_end = trimmed.len() < line_text.len()
+                || end == text.len()
+                || text[end..].starts_with(['\n', '\r']);
+            (start..start + trimmed.len(), paragraph_end)
+        })
+        .collect())
+}
+
+/// Pieces of each line, with the run style (`None` inherits)
+///
+/// `lines` are byte ranges into the plain text, which for rich text is its
+/// runs joined together.
+fn text_lines<'t>(
+    content: &'t str,
+    rich_text: Option<&'t RichText>,
+    lines: &[(Range<usize>, bool)],
+) -> Vec<Vec<(&'t str, Option<&'t TextStyle>)>> {
+    let runs: Vec<(Range<usize>, &str, Option<&TextStyle>)> = match rich_text {
+        Some(rich_text) => rich_text
+            .run_byte_ranges()
+            .into_iter()
+            .zip(&rich_text.runs)
+            .map(|((range, style), run)| (range, run.text.as_str(), Some(style)))
+            .collect(),
+        None => vec![(0..content.len(), content, None)],
+    };
+
+    lines
+        .iter()
+        .map(|(line, _)| {
+            runs.iter()
+                .filter_map(|(range, text, style)| {
+                    let start = line.start.max(range.start);
+                    let end = line.end.min(range.end);
+                    if start >= end {
+                        return None;
+                    }
+                    Some((&text[start - range.start..end - range.start], *style))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Font family, size, weight, style, decoration, color and spacing attributes
+fn font_attributes(style: &TextStyle) -> String {
+    let mut attrs = format!(
+        " font-family=\"{}\" font-size=\"{}\" font-weight=\"{}\"",
+        escape(&style.font_family),
+        num(style.font_size),
+        font_weight_value(style.weight)
+    );
+    if style.italic {
+        attrs.push_str(" font-style=\"italic\"");
+    }
+    let decorations: Vec<&str> = [
+        (style.underline, "underline"),
+        (style.strikethrough, "line-through"),
+    ]
+    .into_iter()
+    .filter_map(|(on, name)| on.then_some(name))
+    .collect();
+    if !decorations.is_empty() {
+        let _ = write!(attrs, " text-decoration=\"{}\"", decorations.join(" "));
+    }
+    let _ = write!(
+        attrs,
+        " fill=\"{}\"{}",
+        color_hex(style.color),
+        alpha_attribute("fill-opacity", style.color.a)
+    );
+    if style.letter_spacing != 0.0 {
+        let _ = write!(attrs, " letter-spacing=\"{}\"", num(style.letter_spacing));
+    }
+    attrs
+}
+
+/// CSS numeric weight for a font weight
+fn font_weight_value(weight: FontWeight) -> u16 {
+    match weight {
+        FontWeight::Thin => 100,
+        FontWeight::Light => 300,
+        FontWeight::Regular => 400,
+        FontWeight::Medium => 500,
+        FontWeight::Bold => 700,
+        FontWeight::Black => 900,
+    }
+}
+
+/// `stroke` attributes for an outline, or `stroke="none"` when there is none
+fn stroke_attributes(stroke: Option<Color>, stroke_width: f32, line_style: LineStyle) -> String {
+    let (Some(color), Some(width)) = (stroke, stroke_line_width(stroke_width)) else {
+        return " stroke=\"none\"".to_string();
+    };
+    let mut attrs = format!(
+        " stroke=\"{}\"{} stroke-width=\"{}\"",
+        color_hex(color),
+        alpha_attribute("stroke-opacity", color.a),
+        num(width)
+    );
+    let dashes = line_style.dash_pattern(stroke_width);
+    if !dashes.is_empty() {
+        let dashes: Vec<String> = dashes.iter().map(|d| num(*d)).collect();
+        let _ = write!(attrs, " stroke-dasharray=\"{}\"", dashes.join(" "));
+    }
+    attrs
+}
+
+/// Vertices of a regular polygon inscribed in the ellipse of `bounds`, first vertex on top
+fn regular_polygon(bounds: &Rect, sides: usize) -> Vec<Point> {
+    let center_x = bounds.origin.x + bounds.size.width / 2.0;
+    let center_y = bounds.origin.y + bounds.size.height / 2.0;
+    (0..sides)
+        .map(|i| {
+            let angle = -std::f32::consts::FRAC_PI_2
+                + 2.0 * std::f32::consts::PI * i as f32 / sides as f32;
+            Point::new(
+                center_x + bounds.size.width / 2.0 * angle.cos(),
+                center_y + bounds.size.height / 2.0 * angle.sin(),
+            )
+        })
+        .collect()
+}
+
+/// `x`, `y`, `width` and `height` attributes for a rectangle
+fn rect_attributes(rect: &Rect) -> String {
+    format!(
+        "x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"",
+        num(rect.origin.x),
+        num(rect.origin.y),
+        num(rect.size.width.max(0.0)),
+        num(rect.size.height.max(0.0))
+    )
+}
+
+/// Opacity attribute for a color alpha, left out when opaque
+fn alpha_attribute(name: &str, alpha: f32) -> String {
+    if alpha < 1.0 {
+        format!(" {}=\"{}\"", name, num(alpha.max(0.0)))
+    } else {
+        String::new()
+    }
+}
+
+/// `#rrggbb` form of a color (alpha is written separately)
+fn color_hex(color: Color) -> String {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        channel(color.r),
+        channel(color.g),
+        channel(color.b)
+    )
+}
+
+/// Compact number formatting: at most three decimals, no trailing zeros
+fn num(value: impl Into<f64>) -> String {
+    let formatted = format!("{:.3}", value.into());
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    match trimmed {
+        "-0" | "" => "0".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// `data:` URI embedding an image file, with the MIME type from its extension
+fn image_data_uri(path: &std::path::Path, bytes: &[u8]) -> String {
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    let mime = match extension.as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "bmp" => "image/bmp",
+        _ => "image/png",
+    };
+    format!("data:{};base64,{}", mime, base64_encode(bytes))
+}
+
+/// Standard base64 with padding
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &byte)| n | ((byte as u32) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Escape text for use in XML content and attribute values
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            other => escaped.push(other),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use testruct_core::document::GroupElement;
+    use testruct_core::typography::VerticalAlignment;
+
+    fn rectangle(bounds: Rect) -> ShapeElement {
+        ShapeElement {
+            id: uuid::Uuid::new_v4(),
+            kind: ShapeKind::Rectangle,
+            bounds,
+            stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
+            stroke_width: 1.0,
+            fill: Some(Fill::Solid(Color::from_rgb(1.0, 0.0, 0.0))),
+            visible: true,
+            locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_groups_nest_children_with_ids() {
+        let bounds = Rect::new(Point::new(10.0, 20.0), Size::new(100.0, 50.0));
+        let child = rectangle(bounds);
+        let group = GroupElement {
+            id: uuid::Uuid::new_v4(),
+            name: "Answer <1>".to_string(),
+            bounds,
+            children: vec![DocumentElement::Shape(child.clone())],
+            visible: true,
+            locked: false,
+            opacity: 0.5,
+        };
+        let mut document = Document::empty("Test");
+        document.pages[0].add_element(DocumentElement::Group(group.clone()));
+
+        let catalog = AssetCatalog::new();
+        let svg = page_svg(&document, 0, &BackgroundOption::White, &catalog).unwrap();
+        let group_at = svg
+            .find(&format!("<g id=\"{}\" class=\"group\"", group.id))
+            .expect("group should be wrapped in a <g> with its id");
+        let child_at = svg
+            .find(&format!("<g id=\"{}\" class=\"shape\"", child.id))
+            .expect("child should be wrapped in a <g> with its id");
+        assert!(child_at > group_at);
+        assert!(svg.contains("data-name=\"Answer &lt;1&gt;\""));
+        assert!(svg.contains("opacity=\"0.5\""));
+        assert!(svg.contains("<rect x=\"10\" y=\"20\" width=\"100\" height=\"50\" fill=\"#ff0000\""));
+    }
+
+    #[test]
+    fn test_text_is_written_as_text_with_font_attributes() {
+        let text = TextElement {
+            id: uuid::Uuid::new_v4(),
+            content: "問1 & 問2\n答え".to_string(),
+            style: TextStyle {
+                font_family: "Noto Sans JP".to_string(),
+                font_size: 12.0,
+                weight: FontWeight::Bold,
+                alignment: TextAlignment::Center,
+                italic: true,
+                ..Default::default()
+            },
+            bounds: Rect::new(Point::new(0.0, 0.0), Size::new(200.0, 40.0)),
+            auto_resize_height: false,
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+            rotation: 0.0,
+            rich_text: None,
+            padding: Insets::uniform(DEFAULT_TEXT_PADDING),
+            text_path: None,
+        };
+        let mut document = Document::empty("Test");
+        document.pages[0].add_element(DocumentElement::Text(text.clone()));
+
+        let catalog = AssetCatalog::new();
+        let svg = page_svg(&document, 0, &BackgroundOption::Transparent, &catalog).unwrap();
+        assert!(svg.contains(&format!("<g id=\"{}\" class=\"text\">", text.id)));
+        assert!(svg.contains(
+            "<text font-family=\"Noto Sans JP\" font-size=\"12\" font-weight=\"700\" font-style=\"italic\""
+        ));
+        assert!(svg.contains("text-anchor=\"middle\""));
+        assert!(svg.contains("<tspan x=\"100\" y=\"17\">問1 &amp; 問2</tspan>"));
+        assert!(svg.contains("<tspan x=\"100\" y=\"31.4\">答え</tspan>"));
+        assert!(!svg.contains("page-border"));
+    }
+
+    #[test]
+    fn test_text_honours_padding_and_vertical_alignment() {
+        let text = TextElement {
+            id: uuid::Uuid::new_v4(),
+            content: "氏名".to_string(),
+            style: TextStyle {
+                font_size: 10.0,
+                vertical_alignment: VerticalAlignment::Bottom,
+                ..Default::default()
+            },
+            bounds: Rect::new(Point::new(0.0, 0.0), Size::new(200.0, 100.0)),
+            auto_resize_height: false,
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+            rotation: 0.0,
+            rich_text: None,
+            padding: Insets::uniform(10.0),
+            text_path: None,
+        };
+        let mut document = Document::empty("Test");
+        document.pages[0].add_element(DocumentElement::Text(text));
+
+        // 80pt of content height, one 12pt line: 68pt below the top padding
+        let catalog = AssetCatalog::new();
+        let svg = page_svg(&document, 0, &BackgroundOption::Transparent, &catalog).unwrap();
+        assert!(svg.contains("<tspan x=\"10\" y=\"88\">氏名</tspan>"));
+    }
+
+    #[test]
+    fn test_long_text_wraps_to_the_box_width() {
+        let text = TextElement {
+            id: uuid::Uuid::new_v4(),
+            content: "次の文章を読んで、後の問いに答えなさい。".repeat(4),
+            style: TextStyle {
+                font_size: 12.0,
+                alignment: TextAlignment::Justified,
+                ..Default::default()
+            },
+            bounds: Rect::new(Point::new(0.0, 0.0), Size::new(120.0, 200.0)),
+            auto_resize_height: false,
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+            rotation: 0.0,
+            rich_text: None,
+            padding: Insets::uniform(10.0),
+            text_path: None,
+        };
+        let mut document = Document::empty("Test");
+        document.pages[0].add_element(DocumentElement::Text(text));
+
+        let catalog = AssetCatalog::new();
+        let svg = page_svg(&document, 0, &BackgroundOption::Transparent, &catalog).unwrap();
+        let lines = svg.matches("<tspan x=").count();
+        assert!(lines > 1, "expected wrapped lines, got {}", lines);
+        // Every line but the last is stretched to the 100pt content width
+        let stretched = svg.matches("textLength=\"100\" lengthAdjust=\"spacing\"");
+        assert_eq!(stretched.count(), lines - 1);
+    }
+
+    #[test]
+    fn test_circular_text_uses_a_text_path() {
+        let bounds = Rect::new(Point::new(0.0, 0.0), Size::new(100.0, 100.0));
+        let text = TextElement {
+            id: uuid::Uuid::new_v4(),
+            content: "合格証明".to_string(),
+            style: TextStyle {
+                alignment: TextAlignment::Center,
+                ..Default::default()
+            },
+            bounds,
+            auto_resize_height: false,
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+            rotation: 0.0,
+            rich_text: None,
+            padding: Insets::uniform(DEFAULT_TEXT_PADDING),
+            text_path: Some(TextPath::circle_in(&bounds)),
+        };
+        let mut document = Document::empty("Test");
+        document.pages[0].add_element(DocumentElement::Text(text.clone()));
+
+        // Centred on 12 o'clock, so the path starts at 6 o'clock
+        let catalog = AssetCatalog::new();
+        let svg = page_svg(&document, 0, &BackgroundOption::Transparent, &catalog).unwrap();
+        assert!(svg.contains(&format!(
+            "<path id=\"text-path-{}\" d=\"M50,100A50,50 0 1 1 50,0A50,50 0 1 1 50,100\"",
+            text.id
+        )));
+        assert!(svg.contains(&format!(
+            "<textPath xlink:href=\"#text-path-{}\" startOffset=\"50%\">合格証明</textPath>",
+            text.id
+        )));
+    }
+
+    #[test]
+    fn test_images_are_embedded_as_data_uris() {
+        let path = std::env::temp_dir().join(format!("testruct-svg-{}.png", uuid::Uuid::new_v4()));
+        std::fs::write(&path, b"PNG").unwrap();
+        let mut catalog = AssetCatalog::new();
+        let source = catalog.register(&path);
+        let image = ImageElement {
+            id: uuid::Uuid::new_v4(),
+            source,
+            bounds: Rect::new(Point::new(0.0, 0.0), Size::new(10.0, 10.0)),
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+            rotation: 0.0,
+            fit: ImageFit::Contain,
+        };
+        let mut document = Document::empty("Test");
+        document.pages[0].add_element(DocumentElement::Image(image));
+
+        let svg = page_svg(&document, 0, &BackgroundOption::Transparent, &catalog).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(svg.contains("xlink:href=\"data:image/png;base64,UE5H\""));
+        assert!(!svg.contains("file://"));
+    }
+
+    #[test]
+    fn test_base64_encoding() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64_encode(&[0xff, 0xfe]), "//4=");
+    }
+
+    #[test]
+    fn test_number_formatting() {
+        assert_eq!(num(1.0f32), "1");
+        assert_eq!(num(0.30000001f32), "0.3");
+        assert_eq!(num(-0.0001f64), "0");
+        assert_eq!(num(12.3456f64), "12.346");
+    }
+}
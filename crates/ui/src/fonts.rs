@@ -0,0 +1,82 @@
+//! Installed font discovery
+//!
+//! Enumerates the font families Pango can see (including CJK families such
+//! as Noto Sans CJK JP or Hiragino) into a core `FontCatalog`. Enumeration
+//! walks every face of every family, so the result is built once and cached
+//! for the lifetime of the process.
+
+use gtk4::pango;
+use gtk4::pango::prelude::*;
+use std::sync::OnceLock;
+use testruct_core::typography::{FontCatalog, FontDescriptor, FontWeight};
+
+/// Families offered when Pango reports no fonts at all
+const FALLBACK_FONT_FAMILIES: &[&str] = &[
+    "Noto Sans JP",
+    "Noto Serif JP",
+    "Noto Sans",
+    "Noto Serif",
+    "Noto Sans Mono",
+];
+
+/// The installed fonts, enumerated on first use
+pub fn system_font_catalog() -> &'static FontCatalog {
+    static CATALOG: OnceLock<FontCatalog> = OnceLock::new();
+    CATALOG.get_or_init(|| {
+        let catalog = enumerate_system_fonts();
+        tracing::info!("🔤 Found {} installed font families", catalog.len());
+        catalog
+    })
+}
+
+/// Installed family names sorted for display
+pub fn system_font_families() -> Vec<String> {
+    system_font_catalog()
+        .descriptors()
+        .into_iter()
+        .map(|descriptor| descriptor.family.clone())
+        .collect()
+}
+
+fn enumerate_system_fonts() -> FontCatalog {
+    let mut catalog = FontCatalog::new();
+    let font_map = pangocairo::FontMap::default();
+
+    for family in font_map.list_families() {
+        let mut weights = Vec::new();
+        for face in family.list_faces() {
+            let weight = font_weight(face.describe().weight());
+            if !weights.contains(&weight) {
+                weights.push(weight);
+            }
+        }
+        catalog.register(FontDescriptor {
+            family: family.name().to_string(),
+            weights,
+        });
+    }
+
+    if catalog.is_empty() {
+        tracing::warn!("⚠️ No fonts reported by Pango, using fallback families");
+        for family in FALLBACK_FONT_FAMILIES {
+            catalog.register(FontDescriptor {
+                family: family.to_string(),
+                weights: vec![FontWeight::Regular, FontWeight::Bold],
+            });
+        }
+    }
+
+    catalog
+}
+
+/// Nearest document weight for a Pango face weight
+fn font_weight(weight: pango::Weight) -> FontWeight {
+    match weight {
+        pango::Weight::Thin | pango::Weight::Ultralight => FontWeight::Thin,
+        pango::Weight::Light | pango::Weight::Semilight | pango::Weight::Book => FontWeight::Light,
+        pango::Weight::Medium | pango::Weight::Semibold => FontWeight::Medium,
+        pango::Weight::Bold | pango::Weight::Ultrabold => FontWeight::Bold,
+        pango::Weight::Heavy | pango::Weight::Ultraheavy => FontWeight::Black,
+        _ => FontWeight::Regular,
+    }
+}
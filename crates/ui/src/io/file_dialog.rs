@@ -22,6 +22,28 @@ pub async fn show_open_dialog(parent: &gtk4::ApplicationWindow) -> Option<PathBu
     }
 }
 
+/// Show an open dialog for SVG files to import and return the selected path
+pub async fn show_import_svg_dialog(parent: &gtk4::ApplicationWindow) -> Option<PathBuf> {
+    let dialog = FileDialog::new();
+    dialog.set_title("Import SVG");
+
+    let filter = gtk4::FileFilter::new();
+    filter.add_mime_type("image/svg+xml");
+    filter.add_pattern("*.svg");
+    filter.set_name(Some("SVG"));
+    dialog.set_default_filter(Some(&filter));
+
+    if let Some(docs_dir) = crate::io::file_io::default_documents_dir() {
+        let file = gtk4::gio::File::for_path(&docs_dir);
+        dialog.set_initial_folder(Some(&file));
+    }
+
+    match dialog.open_future(Some(parent)).await {
+        Ok(file) => file.path(),
+        Err(_) => None,
+    }
+}
+
 /// Show a save file dialog and return the selected path
 pub async fn show_save_dialog(parent: &gtk4::ApplicationWindow) -> Option<PathBuf> {
     let dialog = FileDialog::new();
@@ -53,7 +75,8 @@ pub async fn show_export_dialog(parent: &gtk4::ApplicationWindow, format: &str)
     }
 
     let extension = match format {
-        "pdf" => "pdf",
+        "pdf" | "pdf-a" => "pdf",
+        "eps" => "eps",
         "png" => "png",
         "jpeg" => "jpg",
         "svg" => "svg",
@@ -6,8 +6,45 @@ use anyhow::{Context, Result};
 use std::path::Path;
 use testruct_core::Document;
 
+/// Options controlling how a document is written
+#[derive(Debug, Clone, Copy)]
+pub struct SaveOptions {
+    /// Drop asset catalog entries no image references any more
+    pub prune_unused_assets: bool,
+}
+
+impl Default for SaveOptions {
+    fn default() -> Self {
+        Self {
+            prune_unused_assets: true,
+        }
+    }
+}
+
 /// Save a document to a JSON file
 pub fn save_document(document: &Document, path: &Path) -> Result<()> {
+    save_document_with_options(document, path, SaveOptions::default())
+}
+
+/// Save a document to a JSON file with explicit options
+///
+/// Pruning works on a copy, so the in-memory document keeps its catalog.
+pub fn save_document_with_options(
+    document: &Document,
+    path: &Path,
+    options: SaveOptions,
+) -> Result<()> {
+    let pruned;
+    let document = if options.prune_unused_assets && document.assets.has_unused(document) {
+        let mut copy = document.clone();
+        let removed = copy.assets.prune_unused(document);
+        tracing::info!("🧹 Pruned {} unused asset(s) before saving", removed);
+        pruned = copy;
+        &pruned
+    } else {
+        document
+    };
+
     let json =
         serde_json::to_string_pretty(document).context("Failed to serialize document to JSON")?;
 
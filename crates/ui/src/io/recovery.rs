@@ -0,0 +1,87 @@
+//! Crash recovery snapshots
+//!
+//! While a document has unsaved changes it is periodically written to an
+//! `.autosave` file in the documents directory, together with the path of
+//! the file it was opened from. On startup a snapshot that is newer than
+//! that file can be offered for recovery; a manual save discards it.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use testruct_core::Document;
+
+/// File name of the recovery snapshot inside the documents directory
+pub const AUTOSAVE_FILE_NAME: &str = "testruct-recovery.autosave";
+
+/// A document snapshot written by the recovery timer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoverySnapshot {
+    /// File the document was opened from or last saved to (`None` if never saved)
+    #[serde(default)]
+    pub source_path: Option<PathBuf>,
+    pub document: Document,
+}
+
+/// Default location of the recovery snapshot
+pub fn autosave_path() -> Option<PathBuf> {
+    super::default_documents_dir().map(|dir| dir.join(AUTOSAVE_FILE_NAME))
+}
+
+/// Write a recovery snapshot of `document` to `path`
+pub fn write_snapshot(path: &Path, document: &Document, source_path: Option<&Path>) -> Result<()> {
+    let snapshot = RecoverySnapshot {
+        source_path: source_path.map(Path::to_path_buf),
+        document: document.clone(),
+    };
+    let json = serde_json::to_string(&snapshot).context("Failed to serialize recovery snapshot")?;
+    std::fs::write(path, json).context("Failed to write recovery snapshot")?;
+    tracing::debug!("💾 Recovery snapshot written to: {}", path.display());
+    Ok(())
+}
+
+/// Read a recovery snapshot from `path`
+pub fn read_snapshot(path: &Path) -> Result<RecoverySnapshot> {
+    let json = std::fs::read_to_string(path).context("Failed to read recovery snapshot")?;
+    serde_json::from_str(&json).context("Failed to deserialize recovery snapshot")
+}
+
+/// Snapshot at `path` worth offering for recovery
+///
+/// A snapshot qualifies when it can be read and is newer than its source
+/// file (or the source was never saved or no longer exists). Unreadable
+/// snapshots are treated as absent.
+pub fn pending_snapshot(path: &Path) -> Option<RecoverySnapshot> {
+    let snapshot_time = std::fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    let snapshot = match read_snapshot(path) {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            tracing::warn!("⚠️  Ignoring unreadable recovery snapshot: {:#}", e);
+            return None;
+        }
+    };
+
+    let source_time = snapshot
+        .source_path
+        .as_deref()
+        .and_then(|source| std::fs::metadata(source).and_then(|m| m.modified()).ok());
+    match source_time {
+        Some(source_time) if source_time >= snapshot_time => None,
+        _ => Some(snapshot),
+    }
+}
+
+/// Remove the recovery snapshot at `path`, if any
+pub fn discard_snapshot(path: &Path) {
+    match std::fs::remove_file(path) {
+        Ok(()) => tracing::debug!("🧹 Recovery snapshot removed: {}", path.display()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => tracing::warn!("⚠️  Failed to remove recovery snapshot: {}", e),
+    }
+}
+
+/// Remove the default recovery snapshot after a successful manual save
+pub fn discard_autosave() {
+    if let Some(path) = autosave_path() {
+        discard_snapshot(&path);
+    }
+}
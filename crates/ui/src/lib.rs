@@ -6,6 +6,7 @@ pub mod clipboard;
 pub mod dialogs;
 pub mod error;
 pub mod export;
+pub mod fonts;
 pub mod io;
 pub mod menu;
 pub mod panels;
@@ -3,18 +3,22 @@
 //! This module provides a builder for constructing the application menu
 //! structure with all file, edit, view, tools, and help menus.
 
-use glib::Cast;
+use glib::{Cast, ToVariant};
 use gtk4::{gio, glib, PopoverMenuBar};
+use std::path::PathBuf;
 
 /// Builder for constructing the application menu model
 pub struct MenuBuilder;
 
 impl MenuBuilder {
     /// Build the complete menu model with all submenus
-    pub fn build_menu_model() -> gio::MenuModel {
+    ///
+    /// `recent_files` is the section listing recent files; it is filled
+    /// separately with [`populate_recent_files_menu`].
+    pub fn build_menu_model(recent_files: &gio::Menu) -> gio::MenuModel {
         let menu = gio::Menu::new();
 
-        menu.append_submenu(Some("_File"), &Self::build_file_menu());
+        menu.append_submenu(Some("_File"), &Self::build_file_menu(recent_files));
         menu.append_submenu(Some("_Edit"), &Self::build_edit_menu());
         menu.append_submenu(Some("_View"), &Self::build_view_menu());
         menu.append_submenu(Some("_Tools"), &Self::build_tools_menu());
@@ -24,19 +28,31 @@ impl MenuBuilder {
     }
 
     /// Build the File menu with document operations
-    fn build_file_menu() -> gio::Menu {
+    fn build_file_menu(recent_files: &gio::Menu) -> gio::Menu {
         let file_menu = gio::Menu::new();
         file_menu.append(Some("_New"), Some("win.new"));
         file_menu.append(Some("_Open..."), Some("win.open"));
-        file_menu.append(Some("_Recent Files..."), Some("win.recent-files"));
+
+        let recent_menu = gio::Menu::new();
+        recent_menu.append_section(None, recent_files);
+        let manage_section = gio::Menu::new();
+        manage_section.append(Some("_Recent Files..."), Some("win.recent-files"));
+        recent_menu.append_section(None, &manage_section);
+        file_menu.append_submenu(Some("Open _Recent"), &recent_menu);
+
+        file_menu.append(Some("_Import SVG..."), Some("win.import-svg"));
         file_menu.append(Some("_Save"), Some("win.save"));
         file_menu.append(Some("Save _As..."), Some("win.save-as"));
+        file_menu.append(Some("Document _Properties..."), Some("win.document-properties"));
 
         let export_section = gio::Menu::new();
+        export_section.append(Some("Pre_flight Check..."), Some("win.preflight"));
         export_section.append(Some("Export as PDF"), Some("win.export-pdf"));
+        export_section.append(Some("Export as PDF/A (Archive)"), Some("win.export-pdf-a"));
         export_section.append(Some("Export as PNG"), Some("win.export-png"));
         export_section.append(Some("Export as JPEG"), Some("win.export-jpeg"));
         export_section.append(Some("Export as SVG"), Some("win.export-svg"));
+        export_section.append(Some("Export as EPS"), Some("win.export-eps"));
         file_menu.append_section(None, &export_section);
 
         file_menu
@@ -52,6 +68,7 @@ impl MenuBuilder {
         clipboard_section.append(Some("Cu_t"), Some("win.cut"));
         clipboard_section.append(Some("_Copy"), Some("win.copy"));
         clipboard_section.append(Some("_Paste"), Some("win.paste"));
+        clipboard_section.append(Some("Paste in _Place"), Some("win.paste-in-place"));
         edit_menu.append_section(None, &clipboard_section);
 
         let edit_section = gio::Menu::new();
@@ -60,6 +77,29 @@ impl MenuBuilder {
         edit_section.append(Some("_Delete"), Some("win.delete"));
         edit_menu.append_section(None, &edit_section);
 
+        let group_section = gio::Menu::new();
+        group_section.append(Some("_Group"), Some("win.group"));
+        group_section.append(Some("_Ungroup"), Some("win.ungroup"));
+        group_section.append(Some("_Flatten Group"), Some("win.flatten-group"));
+        edit_menu.append_section(None, &group_section);
+
+        let page_align_menu = gio::Menu::new();
+        page_align_menu.append(Some("_Left Edge"), Some("win.align-page-left"));
+        page_align_menu.append(Some("Center _Horizontally"), Some("win.align-page-center-h"));
+        page_align_menu.append(Some("_Right Edge"), Some("win.align-page-right"));
+        page_align_menu.append(Some("_Top Edge"), Some("win.align-page-top"));
+        page_align_menu.append(Some("Center _Vertically"), Some("win.align-page-center-v"));
+        page_align_menu.append(Some("_Bottom Edge"), Some("win.align-page-bottom"));
+        edit_menu.append_submenu(Some("Align to _Page"), &page_align_menu);
+
+        let find_section = gio::Menu::new();
+        find_section.append(Some("_Find and Replace..."), Some("win.find-replace"));
+        find_section.append(
+            Some("Insert Special _Character..."),
+            Some("win.insert-special-character"),
+        );
+        edit_menu.append_section(None, &find_section);
+
         edit_menu
     }
 
@@ -74,6 +114,7 @@ impl MenuBuilder {
         panels_section.append(Some("_Layers Panel"), Some("win.toggle-layers"));
         panels_section.append(Some("_Properties Panel"), Some("win.toggle-properties"));
         panels_section.append(Some("_JSON Editor"), Some("win.json-editor"));
+        panels_section.append(Some("_Undo History..."), Some("win.undo-history"));
         view_menu.append_section(None, &panels_section);
 
         view_menu
@@ -83,15 +124,19 @@ impl MenuBuilder {
     fn build_tools_menu() -> gio::Menu {
         let tools_menu = gio::Menu::new();
         tools_menu.append(Some("_Templates"), Some("win.templates"));
+        tools_menu.append(Some("_Save as Template..."), Some("win.save-template"));
         tools_menu.append(Some("_Item Library"), Some("win.toggle-item-library"));
         tools_menu.append(Some("_Block Tools"), Some("win.toggle-block-tools"));
 
         let insert_section = gio::Menu::new();
         insert_section.append(Some("Insert _Image..."), Some("win.insert-image"));
+        insert_section.append(Some("Insert T_able..."), Some("win.insert-table"));
+        insert_section.append(Some("Insert _QR Code..."), Some("win.insert-qr-code"));
         tools_menu.append_section(None, &insert_section);
 
         let tools_section = gio::Menu::new();
         tools_section.append(Some("_Settings"), Some("win.settings"));
+        tools_section.append(Some("_Keyboard Shortcuts..."), Some("win.key-bindings"));
         tools_menu.append_section(None, &tools_section);
 
         tools_menu
@@ -108,9 +153,34 @@ impl MenuBuilder {
     }
 }
 
-pub fn build_menu_bar() -> PopoverMenuBar {
-    let model = MenuBuilder::build_menu_model();
+/// Build the menu bar and the (still empty) recent files section
+pub fn build_menu_bar() -> (PopoverMenuBar, gio::Menu) {
+    let recent_files = gio::Menu::new();
+    let model = MenuBuilder::build_menu_model(&recent_files);
     let menu_bar = PopoverMenuBar::from_model(Some(&model));
     glib::set_application_name("Testruct Studio");
-    menu_bar
+    (menu_bar, recent_files)
+}
+
+/// Replace the recent files section with one `win.open-recent` item per file
+pub fn populate_recent_files_menu(menu: &gio::Menu, files: &[PathBuf]) {
+    menu.remove_all();
+    if files.is_empty() {
+        // No action: shown as a disabled placeholder
+        menu.append(Some("No Recent Files"), Some("win.no-recent-files"));
+        return;
+    }
+
+    for path in files {
+        let label = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+        let item = gio::MenuItem::new(Some(&label), None);
+        item.set_action_and_target_value(
+            Some("win.open-recent"),
+            Some(&path.to_string_lossy().to_string().to_variant()),
+        );
+        menu.append_item(&item);
+    }
 }
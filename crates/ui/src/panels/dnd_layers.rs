@@ -131,6 +131,10 @@ impl DndLayersPanel {
             }
             DocumentElement::Frame(_) => "Frame".to_string(),
             DocumentElement::Group(group) => format!("Group: {}", group.name),
+            DocumentElement::Table(table) => format!("Table: {} × {}", table.rows, table.columns),
+            DocumentElement::QrCode(qr) => {
+                format!("QR: {}", qr.payload.chars().take(15).collect::<String>())
+            }
         };
 
         // Element label
@@ -4,8 +4,23 @@ use gtk4::{
     prelude::*, Box as GtkBox, Button, Label, ListBox, ListBoxRow, Orientation, ScrolledWindow,
     SearchEntry,
 };
+use std::cell::Cell;
+use std::rc::Rc;
 use std::sync::{Arc, Mutex};
-use testruct_db::ItemBank;
+use testruct_db::{Item, ItemBank, ItemSort};
+
+/// Number of items loaded per page in the library list
+pub const ITEMS_PER_PAGE: usize = 50;
+
+/// Paging controls below the item list
+#[derive(Clone)]
+struct PageControls {
+    container: GtkBox,
+    prev_button: Button,
+    next_button: Button,
+    label: Label,
+    offset: Rc<Cell<usize>>,
+}
 
 /// Components for the item library panel
 pub struct ItemLibraryComponents {
@@ -44,6 +59,9 @@ pub fn build_item_library_panel(item_bank: Arc<Mutex<ItemBank>>) -> ItemLibraryC
     add_button.set_tooltip_text(Some("新しいアイテムを追加"));
     search_action_box.append(&add_button);
 
+    let tag_button = super::item_tag_manager::build_tag_manager_button(item_bank.clone());
+    search_action_box.append(&tag_button);
+
     container.append(&search_action_box);
 
     // Item list
@@ -61,8 +79,51 @@ pub fn build_item_library_panel(item_bank: Arc<Mutex<ItemBank>>) -> ItemLibraryC
     scrolled.set_vexpand(true);
     container.append(&scrolled);
 
+    // Page controls
+    let page_box = GtkBox::new(Orientation::Horizontal, 6);
+    page_box.set_halign(gtk4::Align::Center);
+    let prev_button = Button::with_label("前へ");
+    prev_button.add_css_class("flat");
+    let page_label = Label::new(None);
+    page_label.add_css_class("dim-label");
+    let next_button = Button::with_label("次へ");
+    next_button.add_css_class("flat");
+    page_box.append(&prev_button);
+    page_box.append(&page_label);
+    page_box.append(&next_button);
+    container.append(&page_box);
+
+    let pages = PageControls {
+        container: page_box,
+        prev_button,
+        next_button,
+        label: page_label,
+        offset: Rc::new(Cell::new(0)),
+    };
+
     // Load initial items
-    refresh_item_list(&item_list, &item_bank);
+    refresh_item_list(&item_list, &item_bank, &pages);
+
+    {
+        let item_list = item_list.clone();
+        let item_bank = item_bank.clone();
+        let pages_ref = pages.clone();
+        pages.prev_button.connect_clicked(move |_| {
+            let offset = pages_ref.offset.get().saturating_sub(ITEMS_PER_PAGE);
+            pages_ref.offset.set(offset);
+            refresh_item_list(&item_list, &item_bank, &pages_ref);
+        });
+    }
+    {
+        let item_list = item_list.clone();
+        let item_bank = item_bank.clone();
+        let pages_ref = pages.clone();
+        pages.next_button.connect_clicked(move |_| {
+            let offset = pages_ref.offset.get() + ITEMS_PER_PAGE;
+            pages_ref.offset.set(offset);
+            refresh_item_list(&item_list, &item_bank, &pages_ref);
+        });
+    }
 
     // Set up row selection handler - prevent event propagation
     let _item_list_clone = item_list.clone();
@@ -76,11 +137,15 @@ pub fn build_item_library_panel(item_bank: Arc<Mutex<ItemBank>>) -> ItemLibraryC
     // Search functionality
     let item_list_clone = item_list.clone();
     let item_bank_clone = item_bank.clone();
+    let search_pages = pages.clone();
     search_entry.connect_search_changed(move |entry| {
         let query = entry.text().to_string();
         if query.is_empty() {
-            refresh_item_list(&item_list_clone, &item_bank_clone);
+            search_pages.offset.set(0);
+            search_pages.container.set_visible(true);
+            refresh_item_list(&item_list_clone, &item_bank_clone, &search_pages);
         } else {
+            search_pages.container.set_visible(false);
             search_items(&item_list_clone, &item_bank_clone, &query);
         }
     });
@@ -113,30 +178,40 @@ pub fn build_item_library_panel(item_bank: Arc<Mutex<ItemBank>>) -> ItemLibraryC
     }
 }
 
-/// Refresh the item list with all items from the database
-fn refresh_item_list(list: &ListBox, item_bank: &Arc<Mutex<ItemBank>>) {
-    // Clear existing items
+/// Remove all rows from the item list
+fn clear_item_list(list: &ListBox) {
     while let Some(row) = list.first_child() {
         list.remove(&row);
     }
+}
+
+/// Append a row showing an item's title and difficulty
+fn append_item_row(list: &ListBox, item: &Item) {
+    let row_label = format!("{}\n{}", item.title, item.difficulty.as_str());
+    let row = ListBoxRow::new();
+    let label = Label::new(Some(&row_label));
+    label.set_wrap(true);
+    label.set_margin_top(6);
+    label.set_margin_bottom(6);
+    label.set_margin_start(6);
+    label.set_margin_end(6);
+    label.set_halign(gtk4::Align::Start);
+    row.set_child(Some(&label));
+    list.insert(&row, -1);
+}
+
+/// Refresh the item list with the current page of items from the database
+fn refresh_item_list(list: &ListBox, item_bank: &Arc<Mutex<ItemBank>>, pages: &PageControls) {
+    clear_item_list(list);
 
     // Load items from database
     match item_bank.lock() {
-        Ok(bank) => match bank.get_all_items(Some(100)) {
-            Ok(items) => {
-                for item in items {
-                    let row_label = format!("{}\n{}", item.title, item.difficulty.as_str());
-                    let row = ListBoxRow::new();
-                    let label = Label::new(Some(&row_label));
-                    label.set_wrap(true);
-                    label.set_margin_top(6);
-                    label.set_margin_bottom(6);
-                    label.set_margin_start(6);
-                    label.set_margin_end(6);
-                    label.set_halign(gtk4::Align::Start);
-                    row.set_child(Some(&label));
-                    list.insert(&row, -1);
+        Ok(bank) => match load_page(&bank, pages) {
+            Ok((items, total)) => {
+                for item in &items {
+                    append_item_row(list, item);
                 }
+                update_page_controls(pages, total);
             }
             Err(e) => {
                 tracing::warn!("Failed to load items: {}", e);
@@ -148,29 +223,47 @@ fn refresh_item_list(list: &ListBox, item_bank: &Arc<Mutex<ItemBank>>) {
     }
 }
 
+/// Load the page at the current offset, stepping back if it is past the end
+fn load_page(bank: &ItemBank, pages: &PageControls) -> anyhow::Result<(Vec<Item>, usize)> {
+    // Items may have been deleted since the page was opened
+    let total = bank.count_items()?.max(0) as usize;
+    if pages.offset.get() >= total {
+        pages.offset.set(last_page_offset(total));
+    }
+
+    let offset = pages.offset.get();
+    let (items, total) = bank.list_items(offset, ITEMS_PER_PAGE, ItemSort::default())?;
+    Ok((items, total.max(0) as usize))
+}
+
+/// Offset of the last page for `total` items
+fn last_page_offset(total: usize) -> usize {
+    total.saturating_sub(1) / ITEMS_PER_PAGE * ITEMS_PER_PAGE
+}
+
+/// Update the page label and enable the prev/next buttons
+fn update_page_controls(pages: &PageControls, total: usize) {
+    let offset = pages.offset.get();
+    let page_count = total.div_ceil(ITEMS_PER_PAGE).max(1);
+    pages
+        .label
+        .set_text(&format!("{} / {}", offset / ITEMS_PER_PAGE + 1, page_count));
+    pages.prev_button.set_sensitive(offset > 0);
+    pages
+        .next_button
+        .set_sensitive(offset + ITEMS_PER_PAGE < total);
+}
+
 /// Search items in the database
 fn search_items(list: &ListBox, item_bank: &Arc<Mutex<ItemBank>>, query: &str) {
-    // Clear existing items
-    while let Some(row) = list.first_child() {
-        list.remove(&row);
-    }
+    clear_item_list(list);
 
     // Search items
     match item_bank.lock() {
         Ok(bank) => match bank.search_items(query) {
             Ok(items) => {
-                for item in items {
-                    let row_label = format!("{}\n{}", item.title, item.difficulty.as_str());
-                    let row = ListBoxRow::new();
-                    let label = Label::new(Some(&row_label));
-                    label.set_wrap(true);
-                    label.set_margin_top(6);
-                    label.set_margin_bottom(6);
-                    label.set_margin_start(6);
-                    label.set_margin_end(6);
-                    label.set_halign(gtk4::Align::Start);
-                    row.set_child(Some(&label));
-                    list.insert(&row, -1);
+                for item in &items {
+                    append_item_row(list, item);
                 }
             }
             Err(e) => {
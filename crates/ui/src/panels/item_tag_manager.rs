@@ -0,0 +1,177 @@
+//! Tag management popover for the item library
+//!
+//! Lists the item bank tags with rename and delete controls, plus a merge
+//! row that folds one tag into another across all items.
+
+use gtk4::{
+    prelude::*, Align, Box as GtkBox, Button, DropDown, Entry, Label, MenuButton, Orientation,
+    Popover, ScrolledWindow,
+};
+use std::sync::{Arc, Mutex};
+use testruct_db::{ItemBank, Tag};
+
+/// Build the "タグ" menu button with its management popover
+///
+/// The popover contents are rebuilt from the database every time it opens.
+pub fn build_tag_manager_button(item_bank: Arc<Mutex<ItemBank>>) -> MenuButton {
+    let content = GtkBox::new(Orientation::Vertical, 6);
+    content.set_margin_start(12);
+    content.set_margin_end(12);
+    content.set_margin_top(12);
+    content.set_margin_bottom(12);
+
+    let scroller = ScrolledWindow::new();
+    scroller.set_min_content_width(280);
+    scroller.set_min_content_height(220);
+    scroller.set_child(Some(&content));
+
+    let popover = Popover::new();
+    popover.set_child(Some(&scroller));
+
+    let content_ref = content.clone();
+    popover.connect_show(move |_| {
+        rebuild_tag_manager(&content_ref, &item_bank);
+    });
+
+    let button = MenuButton::builder().label("タグ").build();
+    button.add_css_class("flat");
+    button.set_tooltip_text(Some("タグの名前変更・統合・削除"));
+    button.set_popover(Some(&popover));
+    button
+}
+
+/// Replace the popover contents with the current tag list
+fn rebuild_tag_manager(content: &GtkBox, item_bank: &Arc<Mutex<ItemBank>>) {
+    while let Some(child) = content.first_child() {
+        content.remove(&child);
+    }
+
+    let tags = match item_bank.lock() {
+        Ok(bank) => match bank.get_tags() {
+            Ok(tags) => tags,
+            Err(e) => {
+                tracing::warn!("Failed to load tags: {}", e);
+                Vec::new()
+            }
+        },
+        Err(e) => {
+            tracing::warn!("Failed to lock item bank: {}", e);
+            Vec::new()
+        }
+    };
+
+    let heading = Label::new(Some("タグ管理"));
+    heading.add_css_class("section-heading");
+    heading.set_halign(Align::Start);
+    content.append(&heading);
+
+    if tags.is_empty() {
+        let empty = Label::new(Some("タグがありません"));
+        empty.add_css_class("dim-label");
+        content.append(&empty);
+        return;
+    }
+
+    for tag in &tags {
+        content.append(&build_tag_row(tag, content, item_bank));
+    }
+
+    if tags.len() >= 2 {
+        content.append(&build_merge_row(&tags, content, item_bank));
+    }
+}
+
+/// One tag: editable name (Enter renames) and a delete button
+fn build_tag_row(tag: &Tag, content: &GtkBox, item_bank: &Arc<Mutex<ItemBank>>) -> GtkBox {
+    let row = GtkBox::new(Orientation::Horizontal, 6);
+
+    let name_entry = Entry::new();
+    name_entry.set_text(&tag.name);
+    name_entry.set_hexpand(true);
+    name_entry.set_tooltip_text(Some(&format!("{} (Enterで名前を変更)", tag.category)));
+    row.append(&name_entry);
+
+    let tag_id = tag.id;
+    let old_name = tag.name.clone();
+    let content_ref = content.clone();
+    let bank_ref = item_bank.clone();
+    name_entry.connect_activate(move |entry| {
+        let name = entry.text().to_string();
+        if name.trim() == old_name {
+            return;
+        }
+        with_bank(&bank_ref, |bank| bank.rename_tag(&tag_id, &name));
+        rebuild_tag_manager(&content_ref, &bank_ref);
+    });
+
+    let delete_button = Button::with_label("削除");
+    delete_button.add_css_class("flat");
+    delete_button.add_css_class("destructive-action");
+    delete_button.set_tooltip_text(Some("すべてのアイテムからこのタグを削除"));
+    let content_ref = content.clone();
+    let bank_ref = item_bank.clone();
+    delete_button.connect_clicked(move |_| {
+        with_bank(&bank_ref, |bank| bank.delete_tag(&tag_id));
+        rebuild_tag_manager(&content_ref, &bank_ref);
+    });
+    row.append(&delete_button);
+
+    row
+}
+
+/// Merge controls: source tag → target tag
+fn build_merge_row(tags: &[Tag], content: &GtkBox, item_bank: &Arc<Mutex<ItemBank>>) -> GtkBox {
+    let merge_box = GtkBox::new(Orientation::Vertical, 6);
+    merge_box.set_margin_top(6);
+
+    let merge_label = Label::new(Some("タグを統合"));
+    merge_label.set_halign(Align::Start);
+    merge_box.append(&merge_label);
+
+    let names: Vec<&str> = tags.iter().map(|tag| tag.name.as_str()).collect();
+    let row = GtkBox::new(Orientation::Horizontal, 6);
+    let from_combo = DropDown::from_strings(&names);
+    let into_combo = DropDown::from_strings(&names);
+    into_combo.set_selected(1);
+    row.append(&from_combo);
+    row.append(&Label::new(Some("→")));
+    row.append(&into_combo);
+
+    let merge_button = Button::with_label("統合");
+    let tag_ids: Vec<uuid::Uuid> = tags.iter().map(|tag| tag.id).collect();
+    let content_ref = content.clone();
+    let bank_ref = item_bank.clone();
+    merge_button.connect_clicked(move |_| {
+        let from = tag_ids.get(from_combo.selected() as usize);
+        let into = tag_ids.get(into_combo.selected() as usize);
+        let (Some(from), Some(into)) = (from, into) else {
+            return;
+        };
+        if from == into {
+            return;
+        }
+        with_bank(&bank_ref, |bank| bank.merge_tags(from, into));
+        rebuild_tag_manager(&content_ref, &bank_ref);
+    });
+    row.append(&merge_button);
+
+    merge_box.append(&row);
+    merge_box
+}
+
+/// Run a tag operation against the item bank, logging failures
+fn with_bank(
+    item_bank: &Arc<Mutex<ItemBank>>,
+    operation: impl FnOnce(&ItemBank) -> anyhow::Result<()>,
+) {
+    match item_bank.lock() {
+        Ok(bank) => {
+            if let Err(e) = operation(&bank) {
+                tracing::warn!("⚠️ Tag operation failed: {}", e);
+            }
+        }
+        Err(e) => {
+            tracing::warn!("Failed to lock item bank: {}", e);
+        }
+    }
+}
@@ -131,6 +131,14 @@ fn get_element_info(element: &testruct_core::document::DocumentElement) -> (Stri
             format!("Group: {}", group.name),
             format!("Group: {}", group.name),
         ),
+        testruct_core::document::DocumentElement::Table(table) => (
+            format!("Table: {} × {}", table.rows, table.columns),
+            format!("Table: {} × {}", table.rows, table.columns),
+        ),
+        testruct_core::document::DocumentElement::QrCode(qr) => {
+            let payload: String = qr.payload.chars().take(15).collect();
+            (format!("QR: {}", payload), format!("QR: {}", payload))
+        }
     }
 }
 
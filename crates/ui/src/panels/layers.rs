@@ -87,6 +87,14 @@ fn create_layer_item(element: &testruct_core::document::DocumentElement, index:
         testruct_core::document::DocumentElement::Group(group) => {
             ("Group", format!("Group: {}", group.name))
         }
+        testruct_core::document::DocumentElement::Table(table) => (
+            "Table",
+            format!("Table: {} × {}", table.rows, table.columns),
+        ),
+        testruct_core::document::DocumentElement::QrCode(qr) => (
+            "QR Code",
+            format!("QR: {}", qr.payload.chars().take(15).collect::<String>()),
+        ),
     };
 
     // Create element label
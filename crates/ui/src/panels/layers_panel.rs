@@ -1,73 +1,187 @@
 //! Layers Panel with interactive controls
 //!
-//! Provides an interactive layers panel with:
-//! - Layer reordering via Up/Down buttons
+//! Lists the named layers of the active page from top to bottom, each one
+//! followed by its elements. Provides:
+//! - Adding, renaming, reordering and removing layers
+//! - Whole-layer visibility and lock toggles
+//! - Moving the selected elements onto a layer
+//! - Element reordering via Up/Down buttons
 //! - Visibility toggling (eye icon)
 //! - Lock toggling (lock icon)
 //! - Layer selection sync with canvas
 //! - Visual feedback for selected layers
+//!
+//! Layer edits go through `AppLayersCommand` so they can be undone. The panel
+//! polls the document on a short timer and rebuilds itself when the layers,
+//! the elements or the selection of the active page change.
 
 use gtk4::prelude::*;
-use gtk4::{Box as GtkBox, Button, GestureClick, Label, Orientation, ScrolledWindow};
+use gtk4::{
+    glib, Box as GtkBox, Button, DrawingArea, Entry, GestureClick, Label, Orientation,
+    ScrolledWindow,
+};
 use std::cell::RefCell;
 use std::rc::Rc;
-use testruct_core::document::DocumentElement;
+use std::time::Duration;
+use testruct_core::document::{DocumentElement, Layer, Page, PageId};
 
 use crate::app::AppState;
-use crate::canvas::CanvasView;
+use crate::canvas::{CanvasRenderState, CanvasView};
+
+/// How often the panel checks the document for changes
+const REFRESH_INTERVAL: Duration = Duration::from_millis(300);
 
 /// Interactive layers panel
 pub struct LayersPanel {
     pub container: ScrolledWindow,
 }
 
+/// What the panel currently shows; a rebuild happens when this changes
+#[derive(PartialEq)]
+struct Snapshot {
+    page: PageId,
+    layers: Vec<Layer>,
+    /// Top-level element ids with their visible and locked flags
+    elements: Vec<(uuid::Uuid, bool, bool)>,
+    selected: Vec<uuid::Uuid>,
+}
+
+/// Everything the panel's buttons need
+struct LayersPanelContext {
+    container: ScrolledWindow,
+    app_state: AppState,
+    drawing_area: DrawingArea,
+    render_state: CanvasRenderState,
+    shown: RefCell<Option<Snapshot>>,
+}
+
 impl LayersPanel {
-    /// Create a new layers panel with document elements
-    pub fn new(
-        elements: &[DocumentElement],
-        app_state: &AppState,
-        canvas_view: &CanvasView,
-    ) -> Self {
+    /// Create a layers panel for the active page and keep it up to date
+    pub fn new(app_state: &AppState, canvas_view: &CanvasView) -> Self {
         let scrolled = ScrolledWindow::new();
         scrolled.set_vexpand(true);
         scrolled.set_hexpand(false);
         scrolled.set_width_request(240);
 
-        // Build layers list
-        let layers_box = Self::build_layers_list(elements, app_state, canvas_view);
-        scrolled.set_child(Some(&layers_box));
+        let context = LayersPanelContext::new(&scrolled, app_state, canvas_view);
+        context.refresh();
+
+        let weak_container = scrolled.downgrade();
+        glib::timeout_add_local(REFRESH_INTERVAL, move || {
+            // Stop once the panel has been destroyed
+            if weak_container.upgrade().is_none() {
+                return glib::ControlFlow::Break;
+            }
+            context.refresh();
+            glib::ControlFlow::Continue
+        });
 
         LayersPanel {
             container: scrolled,
         }
     }
 
-    /// Build layers list from document elements
-    fn build_layers_list(
-        elements: &[DocumentElement],
-        app_state: &AppState,
-        canvas_view: &CanvasView,
-    ) -> GtkBox {
+    /// Get the container widget
+    pub fn widget(&self) -> &ScrolledWindow {
+        &self.container
+    }
+}
+
+impl LayersPanelContext {
+    fn new(container: &ScrolledWindow, app_state: &AppState, canvas_view: &CanvasView) -> Rc<Self> {
+        Rc::new(Self {
+            container: container.clone(),
+            app_state: app_state.clone(),
+            drawing_area: canvas_view.drawing_area(),
+            render_state: canvas_view.render_state().clone(),
+            shown: RefCell::new(None),
+        })
+    }
+
+    /// Rebuild the list if the active page or the selection changed
+    fn refresh(self: &Rc<Self>) {
+        let Some(page) = self.app_state.active_page() else {
+            return;
+        };
+        let snapshot = Snapshot {
+            page: page.id,
+            layers: page.layers.clone(),
+            elements: page
+                .elements
+                .iter()
+                .map(|e| (e.id(), e.is_visible(), e.is_locked()))
+                .collect(),
+            selected: self.render_state.selected_ids.borrow().clone(),
+        };
+        if self.shown.borrow().as_ref() == Some(&snapshot) {
+            return;
+        }
+        self.container
+            .set_child(Some(&self.build_layers_list(&page)));
+        *self.shown.borrow_mut() = Some(snapshot);
+    }
+
+    /// Apply `edit` to a copy of the active page and push its layers as an undoable change
+    fn edit_layers(self: &Rc<Self>, description: &str, edit: impl FnOnce(&mut Page) -> bool) {
+        let Some(mut page) = self.app_state.active_page() else {
+            return;
+        };
+        if !edit(&mut page) {
+            return;
+        }
+        let command = crate::undo_redo::AppLayersCommand::new(
+            self.app_state.clone(),
+            self.app_state.active_page_index(),
+            page.layers,
+            description,
+        );
+        self.app_state.push_command(Box::new(command));
+        self.drawing_area.queue_draw();
+        self.refresh();
+        tracing::info!("✅ {}", description);
+    }
+
+    /// Build the layer list, top layer first, each followed by its elements
+    fn build_layers_list(self: &Rc<Self>, page: &Page) -> GtkBox {
         let container = GtkBox::new(Orientation::Vertical, 1);
         container.set_vexpand(true);
         container.set_hexpand(true);
 
-        // Iterate through elements in reverse order (bottom to top visually)
-        for (visual_index, element) in elements.iter().enumerate().rev() {
-            let actual_index = elements.len() - 1 - visual_index;
-
-            let layer_item = Self::create_layer_item(
-                element,
-                visual_index,
-                actual_index,
-                app_state,
-                canvas_view,
-            );
-            container.append(&layer_item);
+        // Toolbar
+        let add_btn = Button::with_label("+ Layer");
+        add_btn.add_css_class("flat");
+        add_btn.set_halign(gtk4::Align::Start);
+        add_btn.set_margin_start(4);
+        add_btn.set_tooltip_text(Some("Add a layer on top"));
+        let add_context = self.clone();
+        add_btn.connect_clicked(move |_| {
+            add_context.edit_layers("Add Layer", |page| {
+                let name = format!("Layer {}", page.layers.len() + 1);
+                page.add_layer(name);
+                true
+            });
+        });
+        container.append(&add_btn);
+
+        for layer_index in (0..page.layers.len()).rev() {
+            container.append(&self.build_layer_header(page, layer_index));
+
+            let elements = page.layer_elements(layer_index);
+            if elements.is_empty() {
+                let placeholder = Label::new(Some("Empty layer"));
+                placeholder.set_margin_top(4);
+                placeholder.set_margin_bottom(4);
+                placeholder.add_css_class("dim-label");
+                container.append(&placeholder);
+            }
+            // Topmost element first
+            for (position, element) in elements.iter().enumerate().rev() {
+                container.append(&self.create_layer_item(element, position));
+            }
         }
 
         // Add placeholder if empty
-        if elements.is_empty() {
+        if page.layers.is_empty() {
             let placeholder = Label::new(Some("No layers"));
             placeholder.set_margin_top(20);
             placeholder.set_margin_bottom(20);
@@ -78,18 +192,121 @@ impl LayersPanel {
         container
     }
 
-    /// Create a single interactive layer item
-    fn create_layer_item(
-        element: &DocumentElement,
-        visual_index: usize,
-        _actual_index: usize,
-        app_state: &AppState,
-        canvas_view: &CanvasView,
-    ) -> GtkBox {
+    /// Header row of one layer with its whole-layer controls
+    fn build_layer_header(self: &Rc<Self>, page: &Page, layer_index: usize) -> GtkBox {
+        let layer = &page.layers[layer_index];
+        let layer_id = layer.id;
+        let header = GtkBox::new(Orientation::Horizontal, 2);
+        header.add_css_class("toolbar");
+        header.set_margin_top(6);
+        header.set_margin_start(4);
+        header.set_margin_end(4);
+
+        // Layer visibility
+        let vis_btn = Button::with_label(if layer.visible { "👁" } else { "👁‍🗨" });
+        vis_btn.add_css_class("flat");
+        vis_btn.set_tooltip_text(Some("Show or hide layer"));
+        let vis_context = self.clone();
+        vis_btn.connect_clicked(move |_| {
+            vis_context.edit_layers("Toggle Layer Visibility", |page| {
+                page.layer_mut(layer_id)
+                    .map(|layer| layer.visible = !layer.visible)
+                    .is_some()
+            });
+        });
+        header.append(&vis_btn);
+
+        // Layer lock
+        let lock_btn = Button::with_label(if layer.locked { "🔒" } else { "🔓" });
+        lock_btn.add_css_class("flat");
+        lock_btn.set_tooltip_text(Some("Lock or unlock layer"));
+        let lock_context = self.clone();
+        lock_btn.connect_clicked(move |_| {
+            lock_context.edit_layers("Toggle Layer Lock", |page| {
+                page.layer_mut(layer_id)
+                    .map(|layer| layer.locked = !layer.locked)
+                    .is_some()
+            });
+        });
+        header.append(&lock_btn);
+
+        // Layer name, renamed on Enter
+        let name_entry = Entry::new();
+        name_entry.set_text(&layer.name);
+        name_entry.set_hexpand(true);
+        name_entry.set_width_chars(6);
+        name_entry.set_tooltip_text(Some("Layer name (press Enter to rename)"));
+        let rename_context = self.clone();
+        name_entry.connect_activate(move |entry| {
+            let name = entry.text().trim().to_string();
+            rename_context.edit_layers("Rename Layer", |page| match page.layer_mut(layer_id) {
+                Some(layer) if !name.is_empty() && layer.name != name => {
+                    layer.name = name;
+                    true
+                }
+                _ => false,
+            });
+        });
+        header.append(&name_entry);
+
+        // Reorder buttons (Up/Down); layers are stored bottom to top
+        let up_btn = Button::with_label("↑");
+        up_btn.add_css_class("flat");
+        up_btn.set_tooltip_text(Some("Move layer up"));
+        up_btn.set_sensitive(layer_index + 1 < page.layers.len());
+        let up_context = self.clone();
+        up_btn.connect_clicked(move |_| {
+            up_context.edit_layers("Move Layer Up", |page| {
+                page.move_layer(layer_index, layer_index + 1).is_some()
+            });
+        });
+        header.append(&up_btn);
+
+        let down_btn = Button::with_label("↓");
+        down_btn.add_css_class("flat");
+        down_btn.set_tooltip_text(Some("Move layer down"));
+        down_btn.set_sensitive(layer_index > 0);
+        let down_context = self.clone();
+        down_btn.connect_clicked(move |_| {
+            down_context.edit_layers("Move Layer Down", |page| {
+                layer_index > 0 && page.move_layer(layer_index, layer_index - 1).is_some()
+            });
+        });
+        header.append(&down_btn);
+
+        // Move the selection onto this layer
+        let assign_btn = Button::with_label("⇐");
+        assign_btn.add_css_class("flat");
+        assign_btn.set_tooltip_text(Some("Move selected elements to this layer"));
+        assign_btn.set_sensitive(!self.render_state.selected_ids.borrow().is_empty());
+        let assign_context = self.clone();
+        assign_btn.connect_clicked(move |_| {
+            let selected = assign_context.render_state.selected_ids.borrow().clone();
+            assign_context.edit_layers("Move to Layer", |page| {
+                page.assign_to_layer(&selected, layer_id)
+            });
+        });
+        header.append(&assign_btn);
+
+        let delete_btn = Button::with_label("🗑");
+        delete_btn.add_css_class("flat");
+        delete_btn.set_tooltip_text(Some("Delete layer (its elements move to the layer below)"));
+        delete_btn.set_sensitive(page.layers.len() > 1);
+        let delete_context = self.clone();
+        delete_btn.connect_clicked(move |_| {
+            delete_context.edit_layers("Delete Layer", |page| page.remove_layer(layer_id));
+        });
+        header.append(&delete_btn);
+
+        header
+    }
+
+    /// Create a single interactive element item
+    fn create_layer_item(self: &Rc<Self>, element: &DocumentElement, position: usize) -> GtkBox {
         let element_id = element.id();
         let item_box = GtkBox::new(Orientation::Horizontal, 4);
         item_box.add_css_class("layer-item");
-        item_box.set_margin_start(4);
+        item_box.set_margin_start(16);
         item_box.set_margin_end(4);
         item_box.set_margin_top(2);
         item_box.set_margin_bottom(2);
@@ -97,8 +314,8 @@ impl LayersPanel {
         item_box.set_hexpand(true);
 
         // Check if this element is currently selected
-        let is_selected = canvas_view
-            .render_state()
+        let is_selected = self
+            .render_state
             .selected_ids
             .borrow()
             .contains(&element_id);
@@ -109,40 +326,45 @@ impl LayersPanel {
         }
 
         // Visibility toggle button (eye icon)
-        let vis_icon = if element.is_visible() { "👁" } else { "👁‍🗨" };
+        let vis_icon = if element.is_visible() {
+            "👁"
+        } else {
+            "👁‍🗨"
+        };
         let visibility_btn = Button::with_label(vis_icon);
         visibility_btn.set_width_request(28);
         visibility_btn.set_height_request(24);
         visibility_btn.add_css_class("flat");
         visibility_btn.set_tooltip_text(Some("Toggle visibility"));
 
-        // Track visibility state
-        let is_visible = Rc::new(RefCell::new(element.is_visible()));
-
-        // Connect visibility toggle signal
+        // Connect visibility toggle signal (undoable)
         {
-            let state_c = app_state.clone();
-            let canvas_c = canvas_view.drawing_area();
+            let state_c = self.app_state.clone();
+            let canvas_c = self.drawing_area.clone();
             let id_c = element_id;
-            let is_visible_c = is_visible.clone();
 
             visibility_btn.connect_clicked(move |btn| {
-                let mut visible = is_visible_c.borrow_mut();
-                *visible = !*visible;
-                let new_visible = *visible;
-                drop(visible);
+                let Some(visible) = state_c.active_page().and_then(|page| {
+                    page.elements
+                        .iter()
+                        .find(|e| e.id() == id_c)
+                        .map(|e| e.is_visible())
+                }) else {
+                    return;
+                };
+                let new_visible = !visible;
 
-                btn.set_label(if new_visible { "👁" } else { "👁‍🗨" });
+                let command = crate::undo_redo::AppPropertyChangeCommand::new(
+                    state_c.clone(),
+                    vec![id_c],
+                    state_c.active_page_index(),
+                    crate::undo_redo::PropertyValue::Visible(new_visible),
+                );
+                state_c.push_command(Box::new(command));
 
-                state_c.with_mutable_active_document(|doc| {
-                    if let Some(page) = doc.pages.first_mut() {
-                        if let Some(element) = page.elements.iter_mut().find(|e| e.id() == id_c) {
-                            element.set_visible(new_visible);
-                            tracing::info!("Layer visibility: {} for {:?}", new_visible, id_c);
-                        }
-                    }
-                });
+                btn.set_label(if new_visible { "👁" } else { "👁‍🗨" });
                 canvas_c.queue_draw();
+                tracing::info!("Layer visibility: {} for {:?}", new_visible, id_c);
             });
         }
 
@@ -156,33 +378,31 @@ impl LayersPanel {
         lock_btn.add_css_class("flat");
         lock_btn.set_tooltip_text(Some("Toggle lock"));
 
-        // Track lock state
-        let is_locked = Rc::new(RefCell::new(element.is_locked()));
-
         // Connect lock toggle signal
         {
-            let state_c = app_state.clone();
-            let canvas_c = canvas_view.drawing_area();
+            let state_c = self.app_state.clone();
+            let canvas_c = self.drawing_area.clone();
             let id_c = element_id;
-            let is_locked_c = is_locked.clone();
 
             lock_btn.connect_clicked(move |btn| {
-                let mut locked = is_locked_c.borrow_mut();
-                *locked = !*locked;
-                let new_locked = *locked;
-                drop(locked);
+                let page_index = state_c.active_page_index();
+                let new_locked = state_c
+                    .with_mutable_active_document(|doc| {
+                        let page = doc.pages.get_mut(page_index)?;
+                        let element = page.elements.iter_mut().find(|e| e.id() == id_c)?;
+                        let new_locked = !element.is_locked();
+                        element.set_locked(new_locked);
+                        Some(new_locked)
+                    })
+                    .flatten();
+                let Some(new_locked) = new_locked else {
+                    return;
+                };
 
                 btn.set_label(if new_locked { "🔒" } else { "🔓" });
-
-                state_c.with_mutable_active_document(|doc| {
-                    if let Some(page) = doc.pages.first_mut() {
-                        if let Some(element) = page.elements.iter_mut().find(|e| e.id() == id_c) {
-                            element.set_locked(new_locked);
-                            tracing::info!("Layer lock: {} for {:?}", new_locked, id_c);
-                        }
-                    }
-                });
+                state_c.mark_as_modified();
                 canvas_c.queue_draw();
+                tracing::info!("Layer lock: {} for {:?}", new_locked, id_c);
             });
         }
 
@@ -193,8 +413,8 @@ impl LayersPanel {
             DocumentElement::Text(text) => {
                 format!(
                     "Text: {}",
-                    if text.content.len() > 15 {
-                        format!("{}...", &text.content[..15])
+                    if text.content.chars().count() > 15 {
+                        format!("{}...", text.content.chars().take(15).collect::<String>())
                     } else {
                         text.content.clone()
                     }
@@ -213,6 +433,10 @@ impl LayersPanel {
             }
             DocumentElement::Frame(_) => "Frame".to_string(),
             DocumentElement::Group(group) => format!("Group: {}", group.name),
+            DocumentElement::Table(table) => format!("Table: {} × {}", table.rows, table.columns),
+            DocumentElement::QrCode(qr) => {
+                format!("QR: {}", qr.payload.chars().take(15).collect::<String>())
+            }
         };
 
         // Element label
@@ -224,7 +448,7 @@ impl LayersPanel {
         type_label_widget.add_css_class("monospace");
         label_box.append(&type_label_widget);
 
-        let index_label = Label::new(Some(&format!("Layer {}", visual_index + 1)));
+        let index_label = Label::new(Some(&format!("#{}", position + 1)));
         index_label.set_halign(gtk4::Align::Start);
         index_label.add_css_class("dim-label");
         index_label.add_css_class("small-text");
@@ -248,13 +472,14 @@ impl LayersPanel {
             up_btn.add_css_class("flat");
             up_btn.set_tooltip_text(Some("Move layer up (forward in z-order)"));
 
-            let state_c = app_state.clone();
-            let canvas_c = canvas_view.drawing_area();
+            let state_c = self.app_state.clone();
+            let canvas_c = self.drawing_area.clone();
             let id_c = element_id;
 
             up_btn.connect_clicked(move |_| {
+                let page_index = state_c.active_page_index();
                 state_c.with_mutable_active_document(|doc| {
-                    if let Some(page) = doc.pages.first_mut() {
+                    if let Some(page) = doc.pages.get_mut(page_index) {
                         if let Some(pos) = page.elements.iter().position(|e| e.id() == id_c) {
                             if pos < page.elements.len() - 1 {
                                 page.elements.swap(pos, pos + 1);
@@ -277,13 +502,14 @@ impl LayersPanel {
             down_btn.add_css_class("flat");
             down_btn.set_tooltip_text(Some("Move layer down (backward in z-order)"));
 
-            let state_c = app_state.clone();
-            let canvas_c = canvas_view.drawing_area();
+            let state_c = self.app_state.clone();
+            let canvas_c = self.drawing_area.clone();
             let id_c = element_id;
 
             down_btn.connect_clicked(move |_| {
+                let page_index = state_c.active_page_index();
                 state_c.with_mutable_active_document(|doc| {
-                    if let Some(page) = doc.pages.first_mut() {
+                    if let Some(page) = doc.pages.get_mut(page_index) {
                         if let Some(pos) = page.elements.iter().position(|e| e.id() == id_c) {
                             if pos > 0 {
                                 page.elements.swap(pos, pos - 1);
@@ -305,8 +531,8 @@ impl LayersPanel {
         click_gesture.set_button(gtk4::gdk::BUTTON_PRIMARY);
 
         {
-            let selected_ids = canvas_view.render_state().selected_ids.clone();
-            let drawing_area = canvas_view.drawing_area();
+            let selected_ids = self.render_state.selected_ids.clone();
+            let drawing_area = self.drawing_area.clone();
             let id_c = element_id;
 
             click_gesture.connect_pressed(move |gesture, _n_press, _x, _y| {
@@ -315,10 +541,8 @@ impl LayersPanel {
                     Some(event) => event.modifier_state(),
                     None => gtk4::gdk::ModifierType::empty(),
                 };
-                let shift_pressed =
-                    modifier_state.contains(gtk4::gdk::ModifierType::SHIFT_MASK);
-                let ctrl_pressed =
-                    modifier_state.contains(gtk4::gdk::ModifierType::CONTROL_MASK);
+                let shift_pressed = modifier_state.contains(gtk4::gdk::ModifierType::SHIFT_MASK);
+                let ctrl_pressed = modifier_state.contains(gtk4::gdk::ModifierType::CONTROL_MASK);
 
                 // Update canvas selection
                 let mut selected = selected_ids.borrow_mut();
@@ -350,20 +574,13 @@ impl LayersPanel {
 
         item_box
     }
-
-    /// Get the container widget
-    pub fn widget(&self) -> &ScrolledWindow {
-        &self.container
-    }
 }
 
-/// Helper function to update layers panel with current document state
+/// Helper function to rebuild the layers panel from the current document state
 pub fn update_layers_panel(
     panel_container: &gtk4::ScrolledWindow,
-    elements: &[DocumentElement],
     app_state: &AppState,
     canvas_view: &CanvasView,
 ) {
-    let layers_box = LayersPanel::build_layers_list(elements, app_state, canvas_view);
-    panel_container.set_child(Some(&layers_box));
+    LayersPanelContext::new(panel_container, app_state, canvas_view).refresh();
 }
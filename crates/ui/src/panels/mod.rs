@@ -1,12 +1,17 @@
 pub mod dnd_layers;
 mod item_library;
+mod item_tag_manager;
 pub mod layer_dnd;
 mod layers;
 pub mod layers_panel;
+pub mod page_strip;
 pub mod pages_panel;
 mod properties;
 mod properties_groups;
 pub mod property_handlers;
+pub mod selection_breadcrumbs;
+pub mod status_bar;
+mod swatches;
 
 pub use dnd_layers::{update_dnd_layers_panel, DndLayersPanel};
 pub use item_library::{build_item_library_panel, ItemLibraryComponents};
@@ -15,8 +20,11 @@ pub use layer_dnd::{
 };
 pub use layers::{build_layer_panel, build_layers_list, LayerItem};
 pub use layers_panel::{update_layers_panel, LayersPanel};
+pub use page_strip::build_page_strip;
 pub use pages_panel::{get_page_count, update_pages_panel, PagesPanel};
 pub use properties::{
     build_property_panel, build_property_panel_with_components, PropertyPanelComponents,
 };
 pub use property_handlers::{update_property_panel_on_selection, wire_property_signals};
+pub use status_bar::build_status_bar;
+pub use swatches::{build_swatches_panel, wire_swatches_panel, SwatchesPanelComponents};
@@ -0,0 +1,251 @@
+//! Page thumbnail sidebar
+//!
+//! A vertical strip with a small rendering of every page. The active page is
+//! the selected row; clicking a thumbnail switches to its page and dragging
+//! one onto another reorders pages through an undoable command.
+//!
+//! The strip polls the document on a short timer. Page additions, removals
+//! and reorders rebuild the rows right away, while a thumbnail whose page
+//! was edited is only re-rendered once its content revision has stayed the
+//! same for a whole tick, so continuous edits don't re-render every frame.
+
+use gtk4::prelude::*;
+use gtk4::{
+    gdk, glib, Align, Box as GtkBox, DrawingArea, Label, ListBox, ListBoxRow, Orientation, Picture,
+    PolicyType, ScrolledWindow, SelectionMode,
+};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+use testruct_core::document::{Document, Page, PageId};
+
+use crate::app::AppState;
+use crate::canvas::CanvasView;
+
+/// Largest thumbnail size in pixels
+const THUMBNAIL_MAX_WIDTH: i32 = 120;
+const THUMBNAIL_MAX_HEIGHT: i32 = 160;
+
+/// How often the strip checks the document for changes
+const REFRESH_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Rendered thumbnail of one page
+struct Thumbnail {
+    revision: u64,
+    /// Revision seen on the previous tick that has not been rendered yet
+    pending: Option<u64>,
+    texture: Option<gdk::Texture>,
+}
+
+/// Rows currently shown and the thumbnails behind them
+struct PageStrip {
+    list: ListBox,
+    app_state: AppState,
+    drawing_area: DrawingArea,
+    shown_pages: RefCell<Vec<PageId>>,
+    pictures: RefCell<Vec<Picture>>,
+    thumbnails: RefCell<HashMap<PageId, Thumbnail>>,
+}
+
+/// Build the page thumbnail sidebar and start keeping it up to date
+pub fn build_page_strip(app_state: &AppState, canvas_view: &CanvasView) -> ScrolledWindow {
+    let list = ListBox::new();
+    list.set_selection_mode(SelectionMode::Single);
+    list.add_css_class("navigation-sidebar");
+
+    let scrolled = ScrolledWindow::new();
+    scrolled.set_policy(PolicyType::Never, PolicyType::Automatic);
+    scrolled.set_vexpand(true);
+    scrolled.set_width_request(THUMBNAIL_MAX_WIDTH + 32);
+    scrolled.set_child(Some(&list));
+
+    let strip = Rc::new(PageStrip {
+        list: list.clone(),
+        app_state: app_state.clone(),
+        drawing_area: canvas_view.drawing_area(),
+        shown_pages: RefCell::new(Vec::new()),
+        pictures: RefCell::new(Vec::new()),
+        thumbnails: RefCell::new(HashMap::new()),
+    });
+
+    let activate_strip = strip.clone();
+    list.connect_row_activated(move |_, row| {
+        let index = row.index().max(0) as usize;
+        match activate_strip.app_state.set_active_page_index(index) {
+            Ok(()) => {
+                activate_strip.notify_pages_changed();
+                tracing::info!("✅ Page {} selected from thumbnails", index + 1);
+            }
+            Err(e) => tracing::warn!("⚠️  Failed to select page: {}", e),
+        }
+    });
+
+    strip.refresh();
+    let weak_list = list.downgrade();
+    let timer_strip = strip.clone();
+    glib::timeout_add_local(REFRESH_INTERVAL, move || {
+        // Stop once the sidebar has been destroyed
+        if weak_list.upgrade().is_none() {
+            return glib::ControlFlow::Break;
+        }
+        timer_strip.refresh();
+        glib::ControlFlow::Continue
+    });
+
+    scrolled
+}
+
+impl PageStrip {
+    /// Sync rows, thumbnails and the active page with the document
+    fn refresh(self: &Rc<Self>) {
+        let Some(document) = self.app_state.active_document() else {
+            return;
+        };
+        let page_ids: Vec<PageId> = document.pages.iter().map(|page| page.id).collect();
+
+        if *self.shown_pages.borrow() != page_ids {
+            self.thumbnails
+                .borrow_mut()
+                .retain(|id, _| page_ids.contains(id));
+            self.rebuild_rows(&document);
+            *self.shown_pages.borrow_mut() = page_ids;
+        } else {
+            for (index, page) in document.pages.iter().enumerate() {
+                if self.thumbnail_is_due(page) {
+                    self.render_thumbnail(page, document.page_size_of(page), index);
+                }
+            }
+        }
+
+        // Highlight the active page
+        let active = self.app_state.active_page_index() as i32;
+        let selected = self.list.selected_row().map(|row| row.index());
+        if selected != Some(active) {
+            if let Some(row) = self.list.row_at_index(active) {
+                self.list.select_row(Some(&row));
+            }
+        }
+    }
+
+    /// Whether `page` changed and its revision has settled since the last tick
+    fn thumbnail_is_due(&self, page: &Page) -> bool {
+        let revision = self.app_state.page_revision(page.id);
+        let mut thumbnails = self.thumbnails.borrow_mut();
+        let Some(thumbnail) = thumbnails.get_mut(&page.id) else {
+            return true;
+        };
+        if thumbnail.revision == revision {
+            thumbnail.pending = None;
+            return false;
+        }
+        if thumbnail.pending == Some(revision) {
+            return true;
+        }
+        thumbnail.pending = Some(revision);
+        false
+    }
+
+    /// Recreate one row per page, reusing thumbnails that are still current
+    fn rebuild_rows(self: &Rc<Self>, document: &Document) {
+        while let Some(child) = self.list.first_child() {
+            self.list.remove(&child);
+        }
+        self.pictures.borrow_mut().clear();
+
+        for (index, page) in document.pages.iter().enumerate() {
+            let row = self.build_row(index);
+            self.list.append(&row);
+            let current = self
+                .thumbnails
+                .borrow()
+                .get(&page.id)
+                .is_some_and(|thumbnail| {
+                    thumbnail.revision == self.app_state.page_revision(page.id)
+                });
+            if current {
+                let texture = self.thumbnails.borrow()[&page.id].texture.clone();
+                self.pictures.borrow()[index].set_paintable(texture.as_ref());
+            } else {
+                self.render_thumbnail(page, document.page_size_of(page), index);
+            }
+        }
+    }
+
+    /// Row with an empty picture and the page number, draggable for reordering
+    fn build_row(self: &Rc<Self>, index: usize) -> ListBoxRow {
+        let content = GtkBox::new(Orientation::Vertical, 4);
+        content.set_margin_top(6);
+        content.set_margin_bottom(6);
+        content.set_halign(Align::Center);
+
+        let picture = Picture::new();
+        picture.set_can_shrink(false);
+        picture.set_size_request(THUMBNAIL_MAX_WIDTH, -1);
+        content.append(&picture);
+        self.pictures.borrow_mut().push(picture);
+
+        let label = Label::new(Some(&format!("{}", index + 1)));
+        label.add_css_class("dim-label");
+        content.append(&label);
+
+        let row = ListBoxRow::new();
+        row.set_child(Some(&content));
+        row.set_tooltip_text(Some(&format!("ページ {}", index + 1)));
+
+        let moved_strip = self.clone();
+        super::pages_panel::attach_page_reorder_dnd(&row, index, &self.app_state, move || {
+            moved_strip.notify_pages_changed();
+        });
+
+        row
+    }
+
+    /// Render `page` and show it in the row at `index`
+    fn render_thumbnail(&self, page: &Page, page_size: testruct_core::layout::Size, index: usize) {
+        let revision = self.app_state.page_revision(page.id);
+        let texture = {
+            let catalog = self.app_state.asset_catalog();
+            let catalog = catalog.lock().expect("asset catalog");
+            crate::export::image::render_page_thumbnail(
+                page,
+                page_size,
+                THUMBNAIL_MAX_WIDTH,
+                THUMBNAIL_MAX_HEIGHT,
+                &catalog,
+            )
+        }
+        .and_then(|surface| {
+            let mut png_data = Vec::new();
+            surface.write_to_png(&mut png_data)?;
+            let bytes = glib::Bytes::from_owned(png_data);
+            Ok(gdk::Texture::from_bytes(&bytes)?)
+        });
+        let texture = match texture {
+            Ok(texture) => Some(texture),
+            Err(e) => {
+                tracing::warn!("⚠️  Failed to render page {} thumbnail: {}", index + 1, e);
+                None
+            }
+        };
+
+        if let Some(picture) = self.pictures.borrow().get(index) {
+            picture.set_paintable(texture.as_ref());
+        }
+        self.thumbnails.borrow_mut().insert(
+            page.id,
+            Thumbnail {
+                revision,
+                pending: None,
+                texture,
+            },
+        );
+    }
+
+    /// Let the window refresh page indicators and the canvas, then resync the strip
+    fn notify_pages_changed(self: &Rc<Self>) {
+        let _ = self.drawing_area.activate_action("win.pages-changed", None);
+        self.drawing_area.queue_draw();
+        self.refresh();
+    }
+}
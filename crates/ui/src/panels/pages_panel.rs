@@ -1,17 +1,27 @@
 //! Pages Panel for multi-page document management
 //!
 //! Provides:
-//! - Page list display with thumbnails
+//! - Page list display with cached thumbnails (only edited pages re-render)
 //! - Page switching/navigation
 //! - Add/delete pages with confirmation
 //! - Active page tracking and highlighting
+//! - Drag-to-reorder pages (undoable)
 
 use gtk4::prelude::*;
-use gtk4::{Box as GtkBox, Button, Label, Orientation, ScrolledWindow};
+use gtk4::{
+    gdk, Box as GtkBox, Button, DragSource, DrawingArea, DropTarget, Label, Orientation, Picture,
+    ScrolledWindow,
+};
+use std::cell::RefCell;
 use testruct_core::document::{Document, Page};
 
 use crate::app::AppState;
-use crate::canvas::{page_thumbnail::generate_page_thumbnail, CanvasView};
+use crate::canvas::{page_thumbnail::ThumbnailCache, CanvasView};
+
+// Rendered page thumbnails survive panel rebuilds; stale pages are re-rendered on demand
+thread_local! {
+    static THUMBNAIL_CACHE: RefCell<ThumbnailCache> = RefCell::new(ThumbnailCache::new());
+}
 
 /// Pages panel for document page management
 pub struct PagesPanel {
@@ -27,7 +37,7 @@ impl PagesPanel {
         scrolled.set_width_request(200);
 
         // Build pages list
-        let pages_box = Self::build_pages_list(document, app_state, canvas_view);
+        let pages_box = Self::build_pages_list(document, app_state, &canvas_view.drawing_area());
         scrolled.set_child(Some(&pages_box));
 
         PagesPanel {
@@ -39,7 +49,7 @@ impl PagesPanel {
     fn build_pages_list(
         document: &Document,
         app_state: &AppState,
-        canvas_view: &CanvasView,
+        drawing_area: &DrawingArea,
     ) -> GtkBox {
         let container = GtkBox::new(Orientation::Vertical, 8);
         container.set_margin_start(8);
@@ -55,6 +65,8 @@ impl PagesPanel {
         title.set_halign(gtk4::Align::Start);
         container.append(&title);
 
+        THUMBNAIL_CACHE.with(|cache| cache.borrow_mut().retain_pages(&document.pages));
+
         // Pages list
         if document.pages.is_empty() {
             let placeholder = Label::new(Some("No pages"));
@@ -62,7 +74,7 @@ impl PagesPanel {
             container.append(&placeholder);
         } else {
             for (index, page) in document.pages.iter().enumerate() {
-                let page_item = Self::create_page_item(page, index, app_state, canvas_view);
+                let page_item = Self::create_page_item(page, index, app_state, drawing_area);
                 container.append(&page_item);
             }
         }
@@ -74,11 +86,11 @@ impl PagesPanel {
 
         {
             let state_c = app_state.clone();
-            let canvas_c = canvas_view.drawing_area();
+            let canvas_c = drawing_area.clone();
 
             add_btn.connect_clicked(move |_| {
+                let new_page = state_c.with_project(|project| project.settings.new_page());
                 let new_page_index = state_c.with_mutable_active_document(|doc| {
-                    let new_page = Page::empty();
                     doc.pages.push(new_page);
                     let new_index = doc.pages.len() - 1;
                     tracing::info!("📄 New page added. Total pages: {}", doc.pages.len());
@@ -92,6 +104,7 @@ impl PagesPanel {
                     }
                 }
 
+                let _ = canvas_c.activate_action("win.pages-changed", None);
                 canvas_c.queue_draw();
                 tracing::info!("✅ Page added and selected");
             });
@@ -107,7 +120,7 @@ impl PagesPanel {
         page: &Page,
         index: usize,
         app_state: &AppState,
-        canvas_view: &CanvasView,
+        drawing_area: &DrawingArea,
     ) -> GtkBox {
         let item_box = GtkBox::new(Orientation::Vertical, 4);
         item_box.add_css_class("page-item");
@@ -154,12 +167,25 @@ impl PagesPanel {
         preview_box.add_css_class("page-preview");
         preview_box.set_halign(gtk4::Align::Fill);
 
-        // Generate and display thumbnail
-        match generate_page_thumbnail(page) {
-            Ok(_png_data) => {
-                // Successfully generated thumbnail - show visual indicator
-                // Full thumbnail rendering requires complex GTK4 image loading
-                // For now, display element summary as a preview indicator
+        // Display cached thumbnail (re-rendered only if the page changed)
+        let revision = app_state.page_revision(page.id);
+        let thumbnail =
+            THUMBNAIL_CACHE.with(|cache| cache.borrow_mut().get_or_render(page, revision));
+        match thumbnail {
+            Ok(png_data) => {
+                let bytes = glib::Bytes::from(png_data.as_slice());
+                match gdk::Texture::from_bytes(&bytes) {
+                    Ok(texture) => {
+                        let picture = Picture::for_paintable(&texture);
+                        picture.set_can_shrink(true);
+                        preview_box.append(&picture);
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to load thumbnail for page {}: {}", index, e);
+                    }
+                }
+
+                // Element summary below the preview
                 let summary_box = GtkBox::new(Orientation::Vertical, 4);
                 summary_box.set_margin_top(8);
                 summary_box.set_margin_bottom(8);
@@ -170,6 +196,8 @@ impl PagesPanel {
                 let mut image_count = 0;
                 let mut frame_count = 0;
                 let mut group_count = 0;
+                let mut table_count = 0;
+                let mut qr_count = 0;
 
                 for element in &page.elements {
                     match element {
@@ -178,6 +206,8 @@ impl PagesPanel {
                         testruct_core::document::DocumentElement::Image(_) => image_count += 1,
                         testruct_core::document::DocumentElement::Frame(_) => frame_count += 1,
                         testruct_core::document::DocumentElement::Group(_) => group_count += 1,
+                        testruct_core::document::DocumentElement::Table(_) => table_count += 1,
+                        testruct_core::document::DocumentElement::QrCode(_) => qr_count += 1,
                     }
                 }
 
@@ -197,6 +227,18 @@ impl PagesPanel {
                     }
                     summary_str.push_str(&format!("🖼 {}", image_count));
                 }
+                if table_count > 0 {
+                    if !summary_str.is_empty() {
+                        summary_str.push(' ');
+                    }
+                    summary_str.push_str(&format!("▦ {}", table_count));
+                }
+                if qr_count > 0 {
+                    if !summary_str.is_empty() {
+                        summary_str.push(' ');
+                    }
+                    summary_str.push_str(&format!("🔳 {}", qr_count));
+                }
                 if frame_count > 0 || group_count > 0 {
                     if !summary_str.is_empty() {
                         summary_str.push(' ');
@@ -241,13 +283,14 @@ impl PagesPanel {
         let select_btn = Button::with_label("Select");
         select_btn.set_hexpand(true);
         let state_c = app_state.clone();
-        let canvas_c = canvas_view.drawing_area();
+        let canvas_c = drawing_area.clone();
         let page_index = index;
 
         select_btn.connect_clicked(move |_| {
             match state_c.set_active_page_index(page_index) {
                 Ok(()) => {
                     tracing::info!("✅ Page {} selected", page_index + 1);
+                    let _ = canvas_c.activate_action("win.pages-changed", None);
                     canvas_c.queue_draw();
                 }
                 Err(e) => {
@@ -266,7 +309,7 @@ impl PagesPanel {
 
         {
             let state_c = app_state.clone();
-            let canvas_c = canvas_view.drawing_area();
+            let canvas_c = drawing_area.clone();
             let page_index = index;
 
             delete_btn.connect_clicked(move |btn| {
@@ -282,34 +325,22 @@ impl PagesPanel {
                     return;
                 }
 
-                // Delete the page
-                state_c.with_mutable_active_document(|doc| {
-                    if page_index < doc.pages.len() {
-                        doc.pages.remove(page_index);
-                        tracing::info!("🗑 Page {} deleted. Remaining: {}", page_index + 1, doc.pages.len());
+                // Delete the page (the active page index is clamped by AppState)
+                match state_c.delete_page(page_index) {
+                    Ok(()) => {
+                        tracing::info!(
+                            "🗑 Page {} deleted. Remaining: {}",
+                            page_index + 1,
+                            state_c.page_count()
+                        );
+                        state_c.mark_as_modified();
                     }
-                });
-
-                // Adjust active page index if needed
-                let current_active = state_c.active_page_index();
-                let new_page_count = state_c
-                    .with_active_document(|doc| doc.pages.len())
-                    .unwrap_or(0);
-
-                if current_active >= new_page_count && new_page_count > 0 {
-                    // Active page was deleted or is now out of bounds
-                    let new_active = new_page_count - 1;
-                    if let Err(e) = state_c.set_active_page_index(new_active) {
-                        tracing::warn!("Failed to adjust active page: {}", e);
-                    }
-                } else if page_index < current_active {
-                    // A page before the active page was deleted, adjust index
-                    let new_active = current_active.saturating_sub(1);
-                    if let Err(e) = state_c.set_active_page_index(new_active) {
-                        tracing::warn!("Failed to adjust active page: {}", e);
+                    Err(e) => {
+                        tracing::warn!("⚠️ Failed to delete page: {}", e);
                     }
                 }
 
+                let _ = canvas_c.activate_action("win.pages-changed", None);
                 canvas_c.queue_draw();
             });
         }
@@ -318,8 +349,82 @@ impl PagesPanel {
 
         item_box.append(&controls_box);
 
+        Self::attach_reorder_dnd(&item_box, index, app_state, drawing_area);
+
         item_box
     }
+
+    /// Make a page item draggable and accept other pages dropped onto it
+    ///
+    /// After a page is dropped the list is rebuilt in place so labels and
+    /// thumbnails reflect the new order.
+    fn attach_reorder_dnd(
+        item_box: &GtkBox,
+        index: usize,
+        app_state: &AppState,
+        drawing_area: &DrawingArea,
+    ) {
+        let state_c = app_state.clone();
+        let canvas_c = drawing_area.clone();
+        let item_c = item_box.clone();
+        attach_page_reorder_dnd(item_box, index, app_state, move || {
+            if let Some(scrolled) = item_c
+                .ancestor(ScrolledWindow::static_type())
+                .and_then(|w| w.downcast::<ScrolledWindow>().ok())
+            {
+                if let Some(document) = state_c.active_document() {
+                    let pages_box = Self::build_pages_list(&document, &state_c, &canvas_c);
+                    scrolled.set_child(Some(&pages_box));
+                }
+            }
+
+            // Let the window refresh page indicators
+            let _ = canvas_c.activate_action("win.pages-changed", None);
+            canvas_c.queue_draw();
+        });
+    }
+}
+
+/// Drag-and-drop page reordering shared by the page lists
+///
+/// `widget` shows the page at `index`: it can be dragged, and dropping
+/// another page onto it moves that page to `index` through an undoable
+/// command (the active page follows, see `AppState::move_page`). Dropping
+/// a page onto itself is ignored. `on_moved` runs after a successful move.
+pub(crate) fn attach_page_reorder_dnd<W, F>(
+    widget: &W,
+    index: usize,
+    app_state: &AppState,
+    on_moved: F,
+) where
+    W: IsA<gtk4::Widget>,
+    F: Fn() + 'static,
+{
+    let drag_source = DragSource::new();
+    drag_source.set_actions(gdk::DragAction::MOVE);
+    drag_source.connect_prepare(move |_, _, _| {
+        Some(gdk::ContentProvider::for_value(&(index as u32).to_value()))
+    });
+    widget.add_controller(drag_source);
+
+    let drop_target = DropTarget::new(glib::Type::U32, gdk::DragAction::MOVE);
+    let state = app_state.clone();
+    drop_target.connect_drop(move |_, value, _, _| {
+        let Ok(from) = value.get::<u32>() else {
+            return false;
+        };
+        let from = from as usize;
+        if from == index {
+            return false;
+        }
+
+        let command = crate::undo_redo::AppMovePageCommand::new(state.clone(), from, index);
+        state.push_command(Box::new(command));
+        tracing::info!("✅ Page {} moved to position {}", from + 1, index + 1);
+        on_moved();
+        true
+    });
+    widget.add_controller(drop_target);
 }
 
 /// Helper function to update pages panel
@@ -329,7 +434,7 @@ pub fn update_pages_panel(
     app_state: &AppState,
     canvas_view: &CanvasView,
 ) {
-    let pages_box = PagesPanel::build_pages_list(document, app_state, canvas_view);
+    let pages_box = PagesPanel::build_pages_list(document, app_state, &canvas_view.drawing_area());
     panel_container.set_child(Some(&pages_box));
 }
 
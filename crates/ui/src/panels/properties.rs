@@ -1,7 +1,8 @@
 //! Property panel UI for editing object properties
 //!
 //! Provides a comprehensive property panel with sections for typography,
-//! border styles, layer ordering, alignment, grouping, and shape styling.
+//! border styles, layer ordering, alignment, grouping, shape styling, gradient
+//! fills, image fit, rotation and opacity.
 
 use gtk4::prelude::*;
 use gtk4::{
@@ -20,10 +21,17 @@ pub struct PropertyPanelComponents {
     pub font_family_combo: DropDown,
     pub font_size_spin: SpinButton,
     pub line_height_scale: Scale,
+    pub letter_spacing_spin: SpinButton,
     pub text_align_combo: DropDown,
+    pub text_vertical_align_combo: DropDown,
     pub border_style_combo: DropDown,
     pub auto_resize_switch: Switch,
     pub vertical_switch: Switch,
+    /// Text padding spin buttons: top, right, bottom, left
+    pub text_padding_spins: [SpinButton; 4],
+    pub text_circle_switch: Switch,
+    pub text_circle_radius_spin: SpinButton,
+    pub text_circle_angle_spin: SpinButton,
     pub bold_button: gtk4::ToggleButton,
     pub italic_button: gtk4::ToggleButton,
     pub underline_button: gtk4::ToggleButton,
@@ -33,6 +41,14 @@ pub struct PropertyPanelComponents {
     pub fill_color_button: Button,
     pub stroke_color_button: Button,
     pub stroke_width_spin: SpinButton,
+    pub line_style_combo: DropDown,
+    pub corner_radius_spin: SpinButton,
+    pub fill_type_combo: DropDown,
+    pub gradient_end_button: Button,
+    pub gradient_angle_spin: SpinButton,
+    pub image_fit_combo: DropDown,
+    pub rotation_spin: SpinButton,
+    pub opacity_scale: Scale,
     pub group_status_label: Label,
     pub group_name_entry: gtk4::Entry,
     pub ungroup_btn: Button,
@@ -90,11 +106,22 @@ fn build_property_panel_components() -> PropertyPanelComponents {
     let (text_content_buffer, text_content_view) = build_text_content_section(&container);
 
     // Typography section
-    let (font_family_combo, font_size_spin, line_height_scale, text_align_combo) =
-        build_typography_section(&container);
+    let (
+        font_family_combo,
+        font_size_spin,
+        line_height_scale,
+        letter_spacing_spin,
+        text_align_combo,
+        text_vertical_align_combo,
+    ) = build_typography_section(&container);
+
+    // Text options section (auto-resize, vertical writing, padding)
+    let (auto_resize_switch, vertical_switch, text_padding_spins) =
+        build_text_options_section(&container);
 
-    // Text options section (auto-resize, vertical writing)
-    let (auto_resize_switch, vertical_switch) = build_text_options_section(&container);
+    // Circular text section (radius, start angle)
+    let (text_circle_switch, text_circle_radius_spin, text_circle_angle_spin) =
+        build_text_path_section(&container);
 
     // Border section
     let border_style_combo = build_border_section(&container);
@@ -127,8 +154,23 @@ fn build_property_panel_components() -> PropertyPanelComponents {
     let text_background_color_button = build_text_background_color_section(&container);
 
     // Shape styling section
-    let (fill_color_button, stroke_color_button, stroke_width_spin) =
-        build_shape_styling_section(&container);
+    let (
+        fill_color_button,
+        stroke_color_button,
+        stroke_width_spin,
+        line_style_combo,
+        corner_radius_spin,
+    ) = build_shape_styling_section(&container);
+
+    // Gradient section (fill type, end color, angle)
+    let (fill_type_combo, gradient_end_button, gradient_angle_spin) =
+        build_gradient_section(&container);
+
+    // Image section (fit mode)
+    let image_fit_combo = build_image_section(&container);
+
+    // Transform section (rotation, opacity)
+    let (rotation_spin, opacity_scale) = build_transform_section(&container);
 
     // Set the container as the child of scrolled window
     scrolled_window.set_child(Some(&container));
@@ -141,10 +183,16 @@ fn build_property_panel_components() -> PropertyPanelComponents {
         font_family_combo,
         font_size_spin,
         line_height_scale,
+        letter_spacing_spin,
         text_align_combo,
+        text_vertical_align_combo,
         border_style_combo,
         auto_resize_switch,
         vertical_switch,
+        text_padding_spins,
+        text_circle_switch,
+        text_circle_radius_spin,
+        text_circle_angle_spin,
         bold_button,
         italic_button,
         underline_button,
@@ -154,6 +202,14 @@ fn build_property_panel_components() -> PropertyPanelComponents {
         fill_color_button,
         stroke_color_button,
         stroke_width_spin,
+        line_style_combo,
+        corner_radius_spin,
+        fill_type_combo,
+        gradient_end_button,
+        gradient_angle_spin,
+        image_fit_combo,
+        rotation_spin,
+        opacity_scale,
         group_status_label,
         group_name_entry,
         ungroup_btn,
@@ -1,12 +1,13 @@
 //! Individual property group UI builders
 //!
 //! This module contains functions for building specific property group sections
-//! including typography, text formatting, borders, layers, alignment, groups, and shape styling.
+//! including typography, text formatting, borders, layers, alignment, groups, shape styling
+//! and images.
 
 use gtk4::prelude::*;
 use gtk4::{
-    Adjustment, Box as GtkBox, Button, DropDown, Label, Orientation, Scale, SpinButton, Switch,
-    ToggleButton,
+    pango, Adjustment, Box as GtkBox, Button, DropDown, Label, ListItem, Orientation, Scale,
+    SignalListItemFactory, SpinButton, StringObject, Switch, ToggleButton,
 };
 
 /// Build title section with separator
@@ -60,10 +61,11 @@ pub(super) fn build_text_content_section(container: &GtkBox) -> (gtk4::TextBuffe
     (text_buffer, text_view)
 }
 
-/// Build typography section (font family, size, line height, text alignment)
+/// Build typography section (font family, size, line height, letter spacing, text alignment,
+/// vertical alignment)
 pub(super) fn build_typography_section(
     container: &GtkBox,
-) -> (DropDown, SpinButton, Scale, DropDown) {
+) -> (DropDown, SpinButton, Scale, SpinButton, DropDown, DropDown) {
     // Header
     let typo_header = GtkBox::new(Orientation::Horizontal, 8);
     typo_header.set_margin_start(12);
@@ -90,22 +92,23 @@ pub(super) fn build_typography_section(
     font_label.add_css_class("property-label");
     font_section.append(&font_label);
 
-    let font_family_combo = DropDown::from_strings(&[
-        "Noto Sans JP",
-        "Noto Serif JP",
-        "Noto Sans",
-        "Noto Serif",
-        "Noto Sans Mono",
-        "Arial",
-        "Times New Roman",
-        "Courier New",
-        "Georgia",
-        "Verdana",
-        "Helvetica",
-        "Liberation Mono",
-        "DejaVu Sans Mono",
-    ]);
-    font_family_combo.set_selected(0); // Default to Noto Sans JP
+    // Installed families (cached after the first panel is built)
+    let families = crate::fonts::system_font_families();
+    let family_names: Vec<&str> = families.iter().map(String::as_str).collect();
+    let font_family_combo = DropDown::from_strings(&family_names);
+    font_family_combo.set_list_factory(Some(&font_preview_factory()));
+    // Type-to-search over the family names; hundreds of fonts are common
+    font_family_combo.set_expression(Some(gtk4::PropertyExpression::new(
+        StringObject::static_type(),
+        None::<&gtk4::Expression>,
+        "string",
+    )));
+    font_family_combo.set_enable_search(true);
+    let default_index = families
+        .iter()
+        .position(|family| family == "Noto Sans JP")
+        .unwrap_or(0);
+    font_family_combo.set_selected(default_index as u32); // Default to Noto Sans JP when installed
     font_section.append(&font_family_combo);
     container.append(&font_section);
 
@@ -138,6 +141,18 @@ pub(super) fn build_typography_section(
     line_height_section.append(&line_height_scale);
     container.append(&line_height_section);
 
+    // Letter Spacing
+    let letter_spacing_section = GtkBox::new(Orientation::Vertical, 5);
+    let letter_spacing_label = Label::new(Some("文字間隔 (pt)"));
+    letter_spacing_label.set_xalign(0.0);
+    letter_spacing_label.add_css_class("heading");
+    letter_spacing_section.append(&letter_spacing_label);
+
+    let letter_spacing_adj = Adjustment::new(0.0, -5.0, 20.0, 0.5, 2.0, 0.0);
+    let letter_spacing_spin = SpinButton::new(Some(&letter_spacing_adj), 0.5, 1);
+    letter_spacing_section.append(&letter_spacing_spin);
+    container.append(&letter_spacing_section);
+
     // Text Alignment
     let align_section = GtkBox::new(Orientation::Vertical, 5);
     let align_label = Label::new(Some("テキスト配置"));
@@ -150,14 +165,67 @@ pub(super) fn build_typography_section(
     align_section.append(&text_align_combo);
     container.append(&align_section);
 
+    // Vertical Alignment
+    let vertical_align_section = GtkBox::new(Orientation::Vertical, 5);
+    let vertical_align_label = Label::new(Some("縦位置"));
+    vertical_align_label.set_xalign(0.0);
+    vertical_align_label.add_css_class("heading");
+    vertical_align_section.append(&vertical_align_label);
+
+    let text_vertical_align_combo = DropDown::from_strings(&["上揃え", "中央", "下揃え"]);
+    text_vertical_align_combo.set_selected(0); // Default to top
+    vertical_align_section.append(&text_vertical_align_combo);
+    container.append(&vertical_align_section);
+
     (
         font_family_combo,
         font_size_spin,
         line_height_scale,
+        letter_spacing_spin,
         text_align_combo,
+        text_vertical_align_combo,
     )
 }
 
+/// List factory that draws each font family name in its own font
+///
+/// List views only bind the rows that are on screen, so the preview font is
+/// loaded as rows scroll into view rather than for every family up front.
+fn font_preview_factory() -> SignalListItemFactory {
+    let factory = SignalListItemFactory::new();
+    factory.connect_setup(|_, item| {
+        let Some(item) = item.downcast_ref::<ListItem>() else {
+            return;
+        };
+        let label = Label::new(None);
+        label.set_xalign(0.0);
+        item.set_child(Some(&label));
+    });
+    factory.connect_bind(|_, item| {
+        let Some(item) = item.downcast_ref::<ListItem>() else {
+            return;
+        };
+        let (Some(label), Some(family)) = (
+            item.child().and_downcast::<Label>(),
+            item.item().and_downcast::<StringObject>(),
+        ) else {
+            return;
+        };
+        let family = family.string();
+
+        let mut font_desc = pango::FontDescription::new();
+        font_desc.set_family(&family);
+        let attrs = pango::AttrList::new();
+        attrs.insert(pango::AttrFontDesc::new(&font_desc));
+
+        label.set_text(&family);
+        label.set_attributes(Some(&attrs));
+        // Symbol fonts may not draw their own name legibly
+        label.set_tooltip_text(Some(&family));
+    });
+    factory
+}
+
 /// Build text formatting section with bold/italic/underline/strikethrough buttons
 pub(super) fn build_text_formatting_buttons(
     container: &GtkBox,
@@ -256,8 +324,8 @@ pub(super) fn build_text_background_color_section(container: &GtkBox) -> Button
     bg_color_button
 }
 
-/// Build text options section (auto-resize, vertical writing)
-pub(super) fn build_text_options_section(container: &GtkBox) -> (Switch, Switch) {
+/// Build text options section (auto-resize, vertical writing, padding)
+pub(super) fn build_text_options_section(container: &GtkBox) -> (Switch, Switch, [SpinButton; 4]) {
     let text_options_header = GtkBox::new(Orientation::Horizontal, 8);
     text_options_header.set_margin_start(12);
     text_options_header.set_margin_top(12);
@@ -304,9 +372,79 @@ pub(super) fn build_text_options_section(container: &GtkBox) -> (Switch, Switch)
     vertical_box.append(&vertical_switch);
 
     text_options_section.append(&vertical_box);
+
+    // Padding between the bounds and the text (top, right, bottom, left)
+    let padding_label = Label::new(Some("余白 (pt)"));
+    padding_label.set_xalign(0.0);
+    padding_label.add_css_class("heading");
+    text_options_section.append(&padding_label);
+
+    let padding_grid = gtk4::Grid::new();
+    padding_grid.set_row_spacing(5);
+    padding_grid.set_column_spacing(8);
+    let padding_spins = ["上", "右", "下", "左"].map(|side| {
+        let adjustment = Adjustment::new(5.0, 0.0, 100.0, 0.5, 5.0, 0.0);
+        let spin = SpinButton::new(Some(&adjustment), 0.5, 1);
+        spin.set_tooltip_text(Some(&format!("{}の余白", side)));
+        (Label::new(Some(side)), spin)
+    });
+    for (index, (label, spin)) in padding_spins.iter().enumerate() {
+        let (row, column) = (index as i32 / 2, index as i32 % 2 * 2);
+        padding_grid.attach(label, column, row, 1, 1);
+        padding_grid.attach(spin, column + 1, row, 1, 1);
+    }
+    text_options_section.append(&padding_grid);
     container.append(&text_options_section);
 
-    (auto_resize_switch, vertical_switch)
+    (
+        auto_resize_switch,
+        vertical_switch,
+        padding_spins.map(|(_, spin)| spin),
+    )
+}
+
+/// Build circular text section (switch, radius, start angle)
+pub(super) fn build_text_path_section(container: &GtkBox) -> (Switch, SpinButton, SpinButton) {
+    let text_path_section = GtkBox::new(Orientation::Vertical, 5);
+    text_path_section.set_margin_start(12);
+    text_path_section.set_margin_end(12);
+    text_path_section.set_margin_top(8);
+
+    let circle_box = GtkBox::new(Orientation::Horizontal, 8);
+    let circle_label = Label::new(Some("円形テキスト"));
+    circle_label.set_xalign(0.0);
+    circle_label.set_hexpand(true);
+    circle_box.append(&circle_label);
+
+    let text_circle_switch = Switch::new();
+    text_circle_switch.set_active(false);
+    text_circle_switch.set_halign(gtk4::Align::End);
+    circle_box.append(&text_circle_switch);
+    text_path_section.append(&circle_box);
+
+    let circle_grid = gtk4::Grid::new();
+    circle_grid.set_row_spacing(5);
+    circle_grid.set_column_spacing(8);
+
+    let radius_adj = Adjustment::new(50.0, 1.0, 1000.0, 1.0, 10.0, 0.0);
+    let text_circle_radius_spin = SpinButton::new(Some(&radius_adj), 1.0, 1);
+    circle_grid.attach(&Label::new(Some("半径 (pt)")), 0, 0, 1, 1);
+    circle_grid.attach(&text_circle_radius_spin, 1, 0, 1, 1);
+
+    let angle_adj = Adjustment::new(0.0, -360.0, 360.0, 1.0, 15.0, 0.0);
+    let text_circle_angle_spin = SpinButton::new(Some(&angle_adj), 1.0, 0);
+    text_circle_angle_spin.set_tooltip_text(Some("12時の位置から時計回りの角度"));
+    circle_grid.attach(&Label::new(Some("開始角度 (°)")), 0, 1, 1, 1);
+    circle_grid.attach(&text_circle_angle_spin, 1, 1, 1, 1);
+
+    text_path_section.append(&circle_grid);
+    container.append(&text_path_section);
+
+    (
+        text_circle_switch,
+        text_circle_radius_spin,
+        text_circle_angle_spin,
+    )
 }
 
 /// Build border section
@@ -519,8 +657,10 @@ pub(super) fn build_group_section(container: &GtkBox) -> (Label, gtk4::Entry, Bu
     (group_status_label, group_name_entry, ungroup_btn)
 }
 
-/// Build shape styling section (colors and stroke width)
-pub(super) fn build_shape_styling_section(container: &GtkBox) -> (Button, Button, SpinButton) {
+/// Build shape styling section (colors, stroke width and corner radius)
+pub(super) fn build_shape_styling_section(
+    container: &GtkBox,
+) -> (Button, Button, SpinButton, DropDown, SpinButton) {
     let shape_header = GtkBox::new(Orientation::Horizontal, 8);
     shape_header.set_margin_start(12);
     shape_header.set_margin_top(12);
@@ -576,7 +716,201 @@ pub(super) fn build_shape_styling_section(container: &GtkBox) -> (Button, Button
     stroke_width_box.append(&stroke_width_spin);
     shape_section.append(&stroke_width_box);
 
+    // Line Style (order matches LineStyle: solid, dashed, dotted)
+    let line_style_box = GtkBox::new(Orientation::Horizontal, 8);
+    let line_style_label = Label::new(Some("線種"));
+    line_style_label.set_hexpand(true);
+    line_style_label.set_xalign(0.0);
+    line_style_box.append(&line_style_label);
+
+    let line_style_combo = DropDown::from_strings(&["実線", "破線", "点線"]);
+    line_style_combo.set_selected(0);
+    line_style_combo.set_halign(gtk4::Align::End);
+    line_style_box.append(&line_style_combo);
+    shape_section.append(&line_style_box);
+
+    // Corner Radius (rectangles only)
+    let corner_radius_box = GtkBox::new(Orientation::Horizontal, 8);
+    let corner_radius_label = Label::new(Some("角丸 (pt)"));
+    corner_radius_label.set_hexpand(true);
+    corner_radius_label.set_xalign(0.0);
+    corner_radius_box.append(&corner_radius_label);
+
+    let corner_radius_adj = Adjustment::new(0.0, 0.0, 500.0, 1.0, 10.0, 0.0);
+    let corner_radius_spin = SpinButton::new(Some(&corner_radius_adj), 1.0, 0);
+    corner_radius_spin.set_halign(gtk4::Align::End);
+    corner_radius_box.append(&corner_radius_spin);
+    shape_section.append(&corner_radius_box);
+
     container.append(&shape_section);
 
-    (fill_color_button, stroke_color_button, stroke_width_spin)
+    (
+        fill_color_button,
+        stroke_color_button,
+        stroke_width_spin,
+        line_style_combo,
+        corner_radius_spin,
+    )
+}
+
+/// Build gradient section (fill type, end color and angle)
+///
+/// The gradient start color is the fill color from the shape styling section.
+pub(super) fn build_gradient_section(container: &GtkBox) -> (DropDown, Button, SpinButton) {
+    let gradient_header = GtkBox::new(Orientation::Horizontal, 8);
+    gradient_header.set_margin_start(12);
+    gradient_header.set_margin_top(12);
+
+    let gradient_icon = Label::new(Some("🌈"));
+    gradient_icon.add_css_class("section-icon");
+
+    let gradient_label = Label::new(Some("グラデーション"));
+    gradient_label.add_css_class("section-heading");
+    gradient_label.set_halign(gtk4::Align::Start);
+
+    gradient_header.append(&gradient_icon);
+    gradient_header.append(&gradient_label);
+    container.append(&gradient_header);
+
+    let gradient_section = GtkBox::new(Orientation::Vertical, 8);
+    gradient_section.set_margin_start(12);
+    gradient_section.set_margin_end(12);
+
+    // Fill type (order matches the handler: solid, linear gradient)
+    let fill_type_box = GtkBox::new(Orientation::Horizontal, 8);
+    let fill_type_label = Label::new(Some("塗りの種類"));
+    fill_type_label.set_hexpand(true);
+    fill_type_label.set_xalign(0.0);
+    fill_type_box.append(&fill_type_label);
+
+    let fill_type_combo = DropDown::from_strings(&["単色", "線形グラデーション"]);
+    fill_type_combo.set_selected(0);
+    fill_type_combo.set_halign(gtk4::Align::End);
+    fill_type_box.append(&fill_type_combo);
+    gradient_section.append(&fill_type_box);
+
+    // End color (the start color is the fill color)
+    let end_color_box = GtkBox::new(Orientation::Horizontal, 8);
+    let end_color_label = Label::new(Some("終了色"));
+    end_color_label.set_hexpand(true);
+    end_color_label.set_xalign(0.0);
+    end_color_box.append(&end_color_label);
+
+    let gradient_end_button = Button::with_label("色を選択");
+    gradient_end_button.set_halign(gtk4::Align::End);
+    end_color_box.append(&gradient_end_button);
+    gradient_section.append(&end_color_box);
+
+    // Angle (degrees, clockwise from left-to-right)
+    let angle_box = GtkBox::new(Orientation::Horizontal, 8);
+    let angle_label = Label::new(Some("角度 (°)"));
+    angle_label.set_hexpand(true);
+    angle_label.set_xalign(0.0);
+    angle_box.append(&angle_label);
+
+    let angle_adj = Adjustment::new(0.0, 0.0, 359.0, 1.0, 45.0, 0.0);
+    let gradient_angle_spin = SpinButton::new(Some(&angle_adj), 1.0, 0);
+    gradient_angle_spin.set_wrap(true);
+    gradient_angle_spin.set_halign(gtk4::Align::End);
+    angle_box.append(&gradient_angle_spin);
+    gradient_section.append(&angle_box);
+
+    container.append(&gradient_section);
+
+    (fill_type_combo, gradient_end_button, gradient_angle_spin)
+}
+
+/// Build transform section (rotation, opacity)
+pub(super) fn build_transform_section(container: &GtkBox) -> (SpinButton, Scale) {
+    let transform_header = GtkBox::new(Orientation::Horizontal, 8);
+    transform_header.set_margin_start(12);
+    transform_header.set_margin_top(12);
+
+    let transform_icon = Label::new(Some("↻"));
+    transform_icon.add_css_class("section-icon");
+
+    let transform_label = Label::new(Some("変形"));
+    transform_label.add_css_class("section-heading");
+    transform_label.set_halign(gtk4::Align::Start);
+
+    transform_header.append(&transform_icon);
+    transform_header.append(&transform_label);
+    container.append(&transform_header);
+
+    let transform_section = GtkBox::new(Orientation::Vertical, 8);
+    transform_section.set_margin_start(12);
+    transform_section.set_margin_end(12);
+
+    // Rotation (degrees, clockwise)
+    let rotation_box = GtkBox::new(Orientation::Horizontal, 8);
+    let rotation_label = Label::new(Some("回転 (°)"));
+    rotation_label.set_xalign(0.0);
+    rotation_label.set_hexpand(true);
+    rotation_box.append(&rotation_label);
+
+    let rotation_adj = Adjustment::new(0.0, -180.0, 180.0, 1.0, 15.0, 0.0);
+    let rotation_spin = SpinButton::new(Some(&rotation_adj), 1.0, 0);
+    rotation_spin.set_wrap(true);
+    rotation_spin.set_halign(gtk4::Align::End);
+    rotation_box.append(&rotation_spin);
+    transform_section.append(&rotation_box);
+
+    // Opacity (percent)
+    let opacity_box = GtkBox::new(Orientation::Vertical, 5);
+    let opacity_label = Label::new(Some("不透明度 (%)"));
+    opacity_label.set_xalign(0.0);
+    opacity_box.append(&opacity_label);
+
+    let opacity_adj = Adjustment::new(100.0, 0.0, 100.0, 1.0, 10.0, 0.0);
+    let opacity_scale = Scale::new(gtk4::Orientation::Horizontal, Some(&opacity_adj));
+    opacity_scale.set_digits(0);
+    opacity_scale.set_value_pos(gtk4::PositionType::Right);
+    opacity_box.append(&opacity_scale);
+    transform_section.append(&opacity_box);
+
+    container.append(&transform_section);
+
+    (rotation_spin, opacity_scale)
+}
+
+/// Build image section (fit mode)
+pub(super) fn build_image_section(container: &GtkBox) -> DropDown {
+    let image_header = GtkBox::new(Orientation::Horizontal, 8);
+    image_header.set_margin_start(12);
+    image_header.set_margin_top(12);
+
+    let image_icon = Label::new(Some("🖼"));
+    image_icon.add_css_class("section-icon");
+
+    let image_label = Label::new(Some("画像"));
+    image_label.add_css_class("section-heading");
+    image_label.set_halign(gtk4::Align::Start);
+
+    image_header.append(&image_icon);
+    image_header.append(&image_label);
+    container.append(&image_header);
+
+    let image_section = GtkBox::new(Orientation::Vertical, 8);
+    image_section.set_margin_start(12);
+    image_section.set_margin_end(12);
+
+    // Fit mode (order matches ImageFit: stretch, contain, cover)
+    let fit_box = GtkBox::new(Orientation::Horizontal, 8);
+    let fit_label = Label::new(Some("表示方法"));
+    fit_label.set_hexpand(true);
+    fit_label.set_xalign(0.0);
+    fit_box.append(&fit_label);
+
+    let image_fit_combo = DropDown::from_strings(&["引き伸ばし", "全体を表示", "トリミング"]);
+    image_fit_combo.set_selected(0);
+    image_fit_combo.set_halign(gtk4::Align::End);
+    image_fit_combo.set_tooltip_text(Some(
+        "引き伸ばし: 枠に合わせて変形 / 全体を表示: 縦横比を保って枠内に収める / トリミング: 縦横比を保って枠を埋める",
+    ));
+    fit_box.append(&image_fit_combo);
+    image_section.append(&fit_box);
+
+    container.append(&image_section);
+
+    image_fit_combo
 }
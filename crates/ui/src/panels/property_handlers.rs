@@ -9,20 +9,31 @@ mod text_handlers;
 #[path = "property_handlers_shape.rs"]
 mod shape_handlers;
 
+#[path = "property_handlers_gradient.rs"]
+mod gradient_handlers;
+
+#[path = "property_handlers_slider.rs"]
+mod slider_undo;
+
+pub use gradient_handlers::{
+    wire_fill_type_signal, wire_gradient_angle_signal, wire_gradient_end_color_signal,
+};
 pub use shape_handlers::{
-    color_to_hex, wire_auto_resize_signal, wire_fill_color_signal, wire_stroke_color_signal,
-    wire_stroke_width_signal,
+    color_to_hex, color_to_label, wire_auto_resize_signal, wire_corner_radius_signal,
+    wire_fill_color_signal, wire_image_fit_signal, wire_line_style_signal, wire_opacity_signal,
+    wire_rotation_signal, wire_stroke_color_signal, wire_stroke_width_signal,
 };
 pub use text_handlers::{
     find_string_index, wire_alignment_dropdown, wire_bold_signal, wire_font_family_signal,
-    wire_font_size_signal, wire_italic_signal, wire_line_height_signal, wire_strikethrough_signal,
-    wire_text_background_color_signal, wire_text_color_signal, wire_text_content_signal,
-    wire_underline_signal, wire_vertical_signal,
+    wire_font_size_signal, wire_italic_signal, wire_letter_spacing_signal, wire_line_height_signal,
+    wire_strikethrough_signal, wire_text_background_color_signal, wire_text_color_signal,
+    wire_text_content_signal, wire_text_padding_signal, wire_text_path_signals,
+    wire_underline_signal, wire_vertical_alignment_dropdown, wire_vertical_signal,
 };
 
 use gtk4::prelude::*;
-use testruct_core::document::DocumentElement;
-use testruct_core::typography::TextAlignment;
+use testruct_core::document::{DocumentElement, ShapeKind, TextPath};
+use testruct_core::typography::{TextAlignment, VerticalAlignment};
 
 use super::PropertyPanelComponents;
 use crate::app::AppState;
@@ -92,12 +103,24 @@ pub fn wire_property_signals(
         drawing_area.clone(),
         render_state.clone(),
     );
+    wire_vertical_alignment_dropdown(
+        components,
+        app_state.clone(),
+        drawing_area.clone(),
+        render_state.clone(),
+    );
     wire_line_height_signal(
         components,
         app_state.clone(),
         drawing_area.clone(),
         render_state.clone(),
     );
+    wire_letter_spacing_signal(
+        components,
+        app_state.clone(),
+        drawing_area.clone(),
+        render_state.clone(),
+    );
     wire_text_content_signal(
         components,
         app_state.clone(),
@@ -110,8 +133,38 @@ pub fn wire_property_signals(
         drawing_area.clone(),
         render_state.clone(),
     );
+    wire_text_padding_signal(
+        components,
+        app_state.clone(),
+        drawing_area.clone(),
+        render_state.clone(),
+    );
+    wire_text_path_signals(
+        components,
+        app_state.clone(),
+        drawing_area.clone(),
+        render_state.clone(),
+    );
 
     // Shape properties
+    wire_fill_type_signal(
+        components,
+        app_state.clone(),
+        drawing_area.clone(),
+        render_state.clone(),
+    );
+    wire_gradient_end_color_signal(
+        components,
+        app_state.clone(),
+        drawing_area.clone(),
+        render_state.clone(),
+    );
+    wire_gradient_angle_signal(
+        components,
+        app_state.clone(),
+        drawing_area.clone(),
+        render_state.clone(),
+    );
     wire_stroke_color_signal(
         components,
         app_state.clone(),
@@ -136,6 +189,36 @@ pub fn wire_property_signals(
         drawing_area.clone(),
         render_state.clone(),
     );
+    wire_line_style_signal(
+        components,
+        app_state.clone(),
+        drawing_area.clone(),
+        render_state.clone(),
+    );
+    wire_corner_radius_signal(
+        components,
+        app_state.clone(),
+        drawing_area.clone(),
+        render_state.clone(),
+    );
+    wire_image_fit_signal(
+        components,
+        app_state.clone(),
+        drawing_area.clone(),
+        render_state.clone(),
+    );
+    wire_rotation_signal(
+        components,
+        app_state.clone(),
+        drawing_area.clone(),
+        render_state.clone(),
+    );
+    wire_opacity_signal(
+        components,
+        app_state.clone(),
+        drawing_area.clone(),
+        render_state.clone(),
+    );
 
     tracing::info!("✅ Property panel signals wired");
 }
@@ -228,19 +311,33 @@ pub fn update_property_panel_on_selection(
     let mut auto_mixed = false;
     let mut selected_text: Option<testruct_core::document::TextElement> = None;
     let mut fill_applicable = false;
-    let mut fill_state: Option<Option<testruct_core::typography::Color>> = None;
+    let mut fill_state: Option<Option<testruct_core::document::Fill>> = None;
     let mut fill_mixed = false;
     let mut stroke_applicable = false;
     let mut stroke_state: Option<Option<testruct_core::typography::Color>> = None;
     let mut stroke_mixed = false;
     let mut stroke_width_state: Option<f32> = None;
     let mut stroke_width_mixed = false;
+    let mut line_style_state: Option<testruct_core::document::LineStyle> = None;
+    let mut line_style_mixed = false;
+    let mut corner_radius_state: Option<f32> = None;
+    let mut image_fit_state: Option<testruct_core::document::ImageFit> = None;
+    let mut image_fit_mixed = false;
+    let mut rotation_state: Option<f32> = None;
+    let mut opacity_state: Option<f32> = None;
 
     if !selected_ids.is_empty() {
+        let page_index = app_state.active_page_index();
         app_state.with_active_document(|doc| {
-            if let Some(page) = doc.pages.first() {
+            if let Some(page) = doc.pages.get(page_index) {
                 for element in &page.elements {
                     if selected_ids.contains(&element.id()) {
+                        if opacity_state.is_none() {
+                            opacity_state = Some(element.opacity());
+                        }
+                        if element.can_rotate() && rotation_state.is_none() {
+                            rotation_state = Some(element.rotation());
+                        }
                         match element {
                             DocumentElement::Shape(shape) => {
                                 fill_applicable = true;
@@ -271,7 +368,28 @@ pub fn update_property_panel_on_selection(
                                         }
                                     }
                                 }
+
+                                if shape.kind == ShapeKind::Rectangle
+                                    && corner_radius_state.is_none()
+                                {
+                                    corner_radius_state = Some(shape.corner_radius);
+                                }
+
+                                match line_style_state {
+                                    None => line_style_state = Some(shape.line_style),
+                                    Some(prev) if prev != shape.line_style => {
+                                        line_style_mixed = true;
+                                    }
+                                    _ => {}
+                                }
                             }
+                            DocumentElement::Image(image) => match image_fit_state {
+                                None => image_fit_state = Some(image.fit),
+                                Some(prev) if prev != image.fit => {
+                                    image_fit_mixed = true;
+                                }
+                                _ => {}
+                            },
                             DocumentElement::Text(text) => {
                                 if selected_text.is_none() {
                                     selected_text = Some(text.clone());
@@ -299,14 +417,48 @@ pub fn update_property_panel_on_selection(
         components.fill_color_button.set_sensitive(true);
         let label = if fill_mixed {
             "混在".to_string()
-        } else if let Some(Some(color)) = fill_state.as_ref() {
-            color_to_hex(color)
+        } else if let Some(Some(fill)) = fill_state.as_ref() {
+            color_to_label(&fill.primary_color())
         } else {
             "なし".to_string()
         };
         components.fill_color_button.set_label(&label);
     }
 
+    // Update gradient controls from the first shape's fill, so syncing never
+    // changes it; they are only editable for a single, unmixed fill
+    let gradient = match fill_state {
+        Some(Some(testruct_core::document::Fill::LinearGradient { end, angle, .. })) => {
+            Some((end, angle))
+        }
+        _ => None,
+    };
+    let gradient_editable = gradient.is_some() && !fill_mixed;
+    components
+        .fill_type_combo
+        .set_sensitive(fill_applicable && !fill_mixed);
+    components
+        .fill_type_combo
+        .set_selected(if gradient.is_some() { 1 } else { 0 });
+    components
+        .gradient_end_button
+        .set_sensitive(gradient_editable);
+    components
+        .gradient_angle_spin
+        .set_sensitive(gradient_editable);
+    match gradient {
+        Some((end, angle)) => {
+            components
+                .gradient_end_button
+                .set_label(&color_to_label(&end));
+            components.gradient_angle_spin.set_value(angle as f64);
+        }
+        None => {
+            components.gradient_end_button.set_label("色を選択");
+            components.gradient_angle_spin.set_value(0.0);
+        }
+    }
+
     if !stroke_applicable {
         components.stroke_color_button.set_sensitive(false);
         components.stroke_color_button.set_label("色を選択");
@@ -315,7 +467,7 @@ pub fn update_property_panel_on_selection(
         let label = if stroke_mixed {
             "混在".to_string()
         } else if let Some(Some(color)) = stroke_state.as_ref() {
-            color_to_hex(color)
+            color_to_label(color)
         } else {
             "なし".to_string()
         };
@@ -353,9 +505,41 @@ pub fn update_property_panel_on_selection(
     components
         .line_height_scale
         .set_sensitive(text_controls_enabled);
+    components
+        .letter_spacing_spin
+        .set_sensitive(text_controls_enabled);
+    for spin in &components.text_padding_spins {
+        spin.set_sensitive(text_controls_enabled);
+    }
+    components
+        .text_circle_switch
+        .set_sensitive(text_controls_enabled);
+    let circle = selected_text.as_ref().and_then(|text| match text.text_path {
+        Some(TextPath::Circle {
+            radius,
+            start_angle,
+        }) => Some((radius, start_angle)),
+        _ => None,
+    });
+    if components.text_circle_switch.state() != circle.is_some() {
+        components.text_circle_switch.set_state(circle.is_some());
+    }
+    if let Some((radius, start_angle)) = circle {
+        components.text_circle_radius_spin.set_value(radius as f64);
+        components.text_circle_angle_spin.set_value(start_angle as f64);
+    }
+    components
+        .text_circle_radius_spin
+        .set_sensitive(circle.is_some());
+    components
+        .text_circle_angle_spin
+        .set_sensitive(circle.is_some());
     components
         .text_align_combo
         .set_sensitive(text_controls_enabled);
+    components
+        .text_vertical_align_combo
+        .set_sensitive(text_controls_enabled);
     components.bold_button.set_sensitive(text_controls_enabled);
     components
         .italic_button
@@ -387,6 +571,14 @@ pub fn update_property_panel_on_selection(
         let clamped_line_height =
             (text.style.line_height as f64).clamp(line_adjustment.lower(), line_adjustment.upper());
         components.line_height_scale.set_value(clamped_line_height);
+        components
+            .letter_spacing_spin
+            .set_value(text.style.letter_spacing as f64);
+        let padding = text.padding;
+        let sides = [padding.top, padding.right, padding.bottom, padding.left];
+        for (spin, value) in components.text_padding_spins.iter().zip(sides) {
+            spin.set_value(value as f64);
+        }
 
         if let Some(font_index) =
             find_string_index(&components.font_family_combo, &text.style.font_family)
@@ -406,6 +598,17 @@ pub fn update_property_panel_on_selection(
             components.text_align_combo.set_selected(align_index);
         }
 
+        let vertical_align_index = match text.style.vertical_alignment {
+            VerticalAlignment::Top => 0,
+            VerticalAlignment::Middle => 1,
+            VerticalAlignment::Bottom => 2,
+        };
+        if components.text_vertical_align_combo.selected() != vertical_align_index {
+            components
+                .text_vertical_align_combo
+                .set_selected(vertical_align_index);
+        }
+
         // Update text formatting buttons state
         if components.bold_button.is_active()
             != (text.style.weight == testruct_core::typography::FontWeight::Bold)
@@ -449,4 +652,73 @@ pub fn update_property_panel_on_selection(
             }
         }
     }
+
+    // Update line style dropdown
+    if !stroke_applicable {
+        components.line_style_combo.set_sensitive(false);
+        components.line_style_combo.set_selected(0);
+    } else {
+        components.line_style_combo.set_sensitive(true);
+        if !line_style_mixed {
+            if let Some(line_style) = line_style_state {
+                components
+                    .line_style_combo
+                    .set_selected(shape_handlers::line_style_index(line_style));
+            }
+        }
+    }
+
+    // Update corner radius spinner (shows the first selected rectangle's radius)
+    match corner_radius_state {
+        Some(corner_radius) => {
+            components.corner_radius_spin.set_sensitive(true);
+            components
+                .corner_radius_spin
+                .set_value(corner_radius as f64);
+        }
+        None => {
+            components.corner_radius_spin.set_sensitive(false);
+            components.corner_radius_spin.set_value(0.0);
+        }
+    }
+
+    // Update image fit dropdown
+    match image_fit_state {
+        None => {
+            components.image_fit_combo.set_sensitive(false);
+            components.image_fit_combo.set_selected(0);
+        }
+        Some(fit) => {
+            components.image_fit_combo.set_sensitive(true);
+            if !image_fit_mixed {
+                components
+                    .image_fit_combo
+                    .set_selected(shape_handlers::image_fit_index(fit));
+            }
+        }
+    }
+
+    // Update rotation spinner (shows the first rotatable element's angle)
+    match rotation_state {
+        Some(rotation) => {
+            components.rotation_spin.set_sensitive(true);
+            components.rotation_spin.set_value(rotation as f64);
+        }
+        None => {
+            components.rotation_spin.set_sensitive(false);
+            components.rotation_spin.set_value(0.0);
+        }
+    }
+
+    // Update opacity slider (shows the first selected element's opacity)
+    match opacity_state {
+        Some(opacity) => {
+            components.opacity_scale.set_sensitive(true);
+            components.opacity_scale.set_value(opacity as f64 * 100.0);
+        }
+        None => {
+            components.opacity_scale.set_sensitive(false);
+            components.opacity_scale.set_value(100.0);
+        }
+    }
 }
@@ -0,0 +1,184 @@
+//! Gradient fill signal handlers
+//!
+//! Handles the fill type dropdown, gradient end color and gradient angle.
+//! The start color of a gradient is edited with the regular fill color button.
+
+use gtk4::{gio, prelude::*, ColorDialog};
+use testruct_core::document::{DocumentElement, Fill};
+use testruct_core::typography::Color;
+
+use super::shape_handlers::{color_to_rgba, rgba_to_color};
+use super::slider_undo::SliderUndo;
+use super::PropertyPanelComponents;
+use crate::app::AppState;
+use crate::undo_redo::PropertyValue;
+
+/// End color for gradients created from a solid (or empty) fill
+const DEFAULT_GRADIENT_END: Color = Color {
+    r: 1.0,
+    g: 1.0,
+    b: 1.0,
+    a: 1.0,
+};
+
+/// Fill of the first selected shape on the active page
+///
+/// Returns `None` when no shape is selected and `Some(None)` for a shape
+/// without fill.
+pub(super) fn selected_fill(app_state: &AppState, selected: &[uuid::Uuid]) -> Option<Option<Fill>> {
+    let page_index = app_state.active_page_index();
+    app_state
+        .with_active_document(|doc| {
+            doc.pages.get(page_index).and_then(|page| {
+                page.elements.iter().find_map(|element| match element {
+                    DocumentElement::Shape(shape) if selected.contains(&shape.id) => {
+                        Some(shape.fill)
+                    }
+                    _ => None,
+                })
+            })
+        })
+        .flatten()
+}
+
+/// Push an undoable fill change for the selection and refresh the panel
+fn push_fill_change(
+    components: &PropertyPanelComponents,
+    app_state: &AppState,
+    drawing_area: &gtk4::DrawingArea,
+    selected: Vec<uuid::Uuid>,
+    fill: Fill,
+) {
+    let page_index = app_state.active_page_index();
+    let command = crate::undo_redo::AppPropertyChangeCommand::new(
+        app_state.clone(),
+        selected.clone(),
+        page_index,
+        crate::undo_redo::PropertyValue::Fill(Some(fill)),
+    );
+    app_state.push_command(Box::new(command));
+    app_state.mark_as_modified();
+    drawing_area.queue_draw();
+    super::update_property_panel_on_selection(components, app_state, &selected);
+}
+
+/// Wire fill type dropdown (solid / linear gradient)
+pub fn wire_fill_type_signal(
+    components: &PropertyPanelComponents,
+    app_state: AppState,
+    drawing_area: gtk4::DrawingArea,
+    render_state: crate::canvas::CanvasRenderState,
+) {
+    let dropdown = components.fill_type_combo.clone();
+    let panel = components.clone();
+
+    dropdown.connect_selected_notify(move |dropdown| {
+        let selected: Vec<uuid::Uuid> = render_state.selected_ids.borrow().clone();
+        let Some(current) = selected_fill(&app_state, &selected) else {
+            return;
+        };
+
+        // Skip no-op changes (e.g. the panel syncing to a new selection)
+        let wants_gradient = dropdown.selected() == 1;
+        let fill = match (current, wants_gradient) {
+            (Some(Fill::LinearGradient { start, .. }), false) => Fill::Solid(start),
+            (Some(Fill::Solid(color)), true) => Fill::LinearGradient {
+                start: color,
+                end: DEFAULT_GRADIENT_END,
+                angle: 0.0,
+            },
+            (None, true) => Fill::LinearGradient {
+                start: Color::from_rgb(0.0, 0.0, 0.0),
+                end: DEFAULT_GRADIENT_END,
+                angle: 0.0,
+            },
+            _ => return,
+        };
+
+        push_fill_change(&panel, &app_state, &drawing_area, selected, fill);
+        tracing::debug!("✅ Fill type changed (with undo support)");
+    });
+}
+
+/// Wire gradient end color button
+pub fn wire_gradient_end_color_signal(
+    components: &PropertyPanelComponents,
+    app_state: AppState,
+    drawing_area: gtk4::DrawingArea,
+    render_state: crate::canvas::CanvasRenderState,
+) {
+    let button = components.gradient_end_button.clone();
+    let panel = components.clone();
+
+    button.connect_clicked(move |widget| {
+        let selected: Vec<uuid::Uuid> = render_state.selected_ids.borrow().clone();
+        let Some(Some(Fill::LinearGradient { end, .. })) = selected_fill(&app_state, &selected)
+        else {
+            tracing::warn!("⚠️ グラデーションの図形が選択されていません");
+            return;
+        };
+
+        let parent_window = widget
+            .root()
+            .and_then(|root| root.downcast::<gtk4::Window>().ok());
+
+        let dialog = ColorDialog::builder()
+            .modal(true)
+            .title("終了色を選択")
+            .with_alpha(true)
+            .build();
+
+        let panel = panel.clone();
+        let app_state = app_state.clone();
+        let drawing_area = drawing_area.clone();
+        dialog.choose_rgba(
+            parent_window.as_ref(),
+            Some(&color_to_rgba(&end)),
+            None::<&gio::Cancellable>,
+            move |result| {
+                let Ok(rgba) = result else {
+                    return;
+                };
+                // Re-read the fill in case it changed while the dialog was open
+                let Some(Some(Fill::LinearGradient { start, angle, .. })) =
+                    selected_fill(&app_state, &selected)
+                else {
+                    return;
+                };
+                let fill = Fill::LinearGradient {
+                    start,
+                    end: rgba_to_color(&rgba),
+                    angle,
+                };
+                push_fill_change(&panel, &app_state, &drawing_area, selected, fill);
+                tracing::debug!("✅ Gradient end color updated (with undo support)");
+            },
+        );
+    });
+}
+
+/// Wire gradient angle spinner: a drag or a run of clicks is undone in one step
+pub fn wire_gradient_angle_signal(
+    components: &PropertyPanelComponents,
+    app_state: AppState,
+    drawing_area: gtk4::DrawingArea,
+    render_state: crate::canvas::CanvasRenderState,
+) {
+    let spinner = components.gradient_angle_spin.clone();
+    let slider_undo = SliderUndo::new(app_state.clone(), drawing_area, render_state.clone());
+
+    spinner.connect_value_changed(move |spin| {
+        let angle = spin.value() as f32;
+        let selected: Vec<uuid::Uuid> = render_state.selected_ids.borrow().clone();
+        let Some(Some(Fill::LinearGradient { start, end, .. })) =
+            selected_fill(&app_state, &selected)
+        else {
+            return;
+        };
+
+        let fill = Fill::LinearGradient { start, end, angle };
+        if slider_undo.preview(PropertyValue::Fill(Some(fill))) {
+            tracing::debug!("✅ Gradient angle set to {}° (with undo support)", angle);
+        }
+    });
+}
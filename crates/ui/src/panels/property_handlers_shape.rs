@@ -1,13 +1,17 @@
 //! Shape property signal handlers
 //!
-//! Handles shape-specific property panel controls (stroke color, fill color, auto-resize)
+//! Handles shape-specific property panel controls (stroke color, fill color, line style,
+//! corner radius, auto-resize, image fit, rotation, opacity)
 
 use gtk4::{gdk, gio};
 use gtk4::{prelude::*, ColorDialog};
-use testruct_core::document::DocumentElement;
+use testruct_core::document::{DocumentElement, Fill, ImageFit, LineStyle};
 
+use super::slider_undo::SliderUndo;
 use super::PropertyPanelComponents;
 use crate::app::AppState;
+use crate::canvas::rendering::fit_text_height;
+use crate::undo_redo::PropertyValue;
 
 /// Wire stroke color button
 pub fn wire_stroke_color_signal(
@@ -56,7 +60,7 @@ pub fn wire_stroke_color_signal(
         let dialog = ColorDialog::builder()
             .modal(true)
             .title("枠線色を選択")
-            .with_alpha(false)
+            .with_alpha(true)
             .build();
 
         let selected_ids_for_cb = selected_ids.clone();
@@ -133,8 +137,8 @@ pub fn wire_fill_color_signal(
                     for element in &page.elements {
                         if selected_ids.contains(&element.id()) {
                             if let DocumentElement::Shape(shape) = element {
-                                if let Some(color) = &shape.fill {
-                                    return Some(color_to_rgba(color));
+                                if let Some(fill) = &shape.fill {
+                                    return Some(color_to_rgba(&fill.primary_color()));
                                 }
                             }
                         }
@@ -147,7 +151,7 @@ pub fn wire_fill_color_signal(
         let dialog = ColorDialog::builder()
             .modal(true)
             .title("塗りつぶし色を選択")
-            .with_alpha(false)
+            .with_alpha(true)
             .build();
 
         let selected_ids_for_cb = selected_ids.clone();
@@ -164,12 +168,27 @@ pub fn wire_fill_color_signal(
                     let fill_color = rgba_to_color(&rgba);
                     let page_index = app_state_for_cb.active_page_index();
 
+                    // Gradients keep their end color and angle; the button edits the start
+                    let fill = match super::gradient_handlers::selected_fill(
+                        &app_state_for_cb,
+                        &selected_ids_for_cb,
+                    ) {
+                        Some(Some(Fill::LinearGradient { end, angle, .. })) => {
+                            Fill::LinearGradient {
+                                start: fill_color,
+                                end,
+                                angle,
+                            }
+                        }
+                        _ => Fill::Solid(fill_color),
+                    };
+
                     // Create undo command for fill color change
                     let command = crate::undo_redo::AppPropertyChangeCommand::new(
                         app_state_for_cb.clone(),
                         selected_ids_for_cb.clone(),
                         page_index,
-                        crate::undo_redo::PropertyValue::FillColor(Some(fill_color)),
+                        crate::undo_redo::PropertyValue::Fill(Some(fill)),
                     );
 
                     // Push command (this executes the change and adds to undo stack)
@@ -218,7 +237,7 @@ pub fn wire_auto_resize_signal(
                         if let DocumentElement::Text(text) = element {
                             text.auto_resize_height = state;
                             if state {
-                                recompute_auto_height(text);
+                                fit_text_height(text);
                             }
                             changed = true;
                         }
@@ -239,22 +258,201 @@ pub fn wire_auto_resize_signal(
     });
 }
 
-fn recompute_auto_height(text: &mut testruct_core::document::TextElement) {
-    if !text.auto_resize_height {
-        return;
+/// Wire rotation spinner: a drag or a run of clicks is undone in one step
+pub fn wire_rotation_signal(
+    components: &PropertyPanelComponents,
+    app_state: AppState,
+    drawing_area: gtk4::DrawingArea,
+    render_state: crate::canvas::CanvasRenderState,
+) {
+    let spinner = components.rotation_spin.clone();
+    let slider_undo = SliderUndo::new(app_state, drawing_area, render_state);
+
+    spinner.connect_value_changed(move |spin| {
+        let rotation = spin.value() as f32;
+        if slider_undo.preview(PropertyValue::Rotation(rotation)) {
+            tracing::debug!("✅ Rotation set to {}° (with undo support)", rotation);
+        }
+    });
+}
+
+/// Wire opacity slider: a drag is undone in one step
+pub fn wire_opacity_signal(
+    components: &PropertyPanelComponents,
+    app_state: AppState,
+    drawing_area: gtk4::DrawingArea,
+    render_state: crate::canvas::CanvasRenderState,
+) {
+    let scale = components.opacity_scale.clone();
+    let slider_undo = SliderUndo::new(app_state, drawing_area, render_state);
+
+    scale.connect_value_changed(move |scale| {
+        let opacity = (scale.value() / 100.0) as f32;
+        if slider_undo.preview(PropertyValue::Opacity(opacity)) {
+            tracing::debug!(
+                "✅ Opacity set to {:.0}% (with undo support)",
+                opacity * 100.0
+            );
+        }
+    });
+}
+
+/// Wire corner radius spinner: a drag or a run of clicks is undone in one step
+pub fn wire_corner_radius_signal(
+    components: &PropertyPanelComponents,
+    app_state: AppState,
+    drawing_area: gtk4::DrawingArea,
+    render_state: crate::canvas::CanvasRenderState,
+) {
+    let spinner = components.corner_radius_spin.clone();
+    let slider_undo = SliderUndo::new(app_state, drawing_area, render_state);
+
+    spinner.connect_value_changed(move |spin| {
+        let corner_radius = spin.value() as f32;
+        if slider_undo.preview(PropertyValue::CornerRadius(corner_radius)) {
+            tracing::debug!(
+                "✅ Corner radius set to {} (with undo support)",
+                corner_radius
+            );
+        }
+    });
+}
+
+/// Wire line style dropdown
+pub fn wire_line_style_signal(
+    components: &PropertyPanelComponents,
+    app_state: AppState,
+    drawing_area: gtk4::DrawingArea,
+    render_state: crate::canvas::CanvasRenderState,
+) {
+    let dropdown = components.line_style_combo.clone();
+
+    dropdown.connect_selected_notify(move |dropdown| {
+        let line_style = line_style_from_index(dropdown.selected());
+        let selected: Vec<uuid::Uuid> = render_state.selected_ids.borrow().clone();
+        if selected.is_empty() {
+            return;
+        }
+
+        // Skip no-op changes (e.g. the panel syncing to a new selection)
+        let page_index = app_state.active_page_index();
+        let differs = app_state
+            .with_active_document(|doc| {
+                doc.pages.get(page_index).is_some_and(|page| {
+                    page.elements.iter().any(|element| match element {
+                        DocumentElement::Shape(shape) => {
+                            selected.contains(&shape.id) && shape.line_style != line_style
+                        }
+                        _ => false,
+                    })
+                })
+            })
+            .unwrap_or(false);
+        if !differs {
+            return;
+        }
+
+        let command = crate::undo_redo::AppPropertyChangeCommand::new(
+            app_state.clone(),
+            selected,
+            page_index,
+            crate::undo_redo::PropertyValue::LineStyle(line_style),
+        );
+        app_state.push_command(Box::new(command));
+        app_state.mark_as_modified();
+        drawing_area.queue_draw();
+        tracing::debug!("✅ Line style set to {:?} (with undo support)", line_style);
+    });
+}
+
+/// Line style for a dropdown position (実線 / 破線 / 点線)
+pub(super) fn line_style_from_index(index: u32) -> LineStyle {
+    match index {
+        1 => LineStyle::Dashed,
+        2 => LineStyle::Dotted,
+        _ => LineStyle::Solid,
     }
+}
 
-    let width = text.bounds.size.width.max(1.0);
-    let new_height =
-        crate::canvas::rendering::measure_text_height(&text.content, &text.style, width);
-    text.bounds.size.height = new_height.max(1.0);
+/// Dropdown position for a line style
+pub(super) fn line_style_index(line_style: LineStyle) -> u32 {
+    match line_style {
+        LineStyle::Solid => 0,
+        LineStyle::Dashed => 1,
+        LineStyle::Dotted => 2,
+    }
 }
 
-fn color_to_rgba(color: &testruct_core::typography::Color) -> gdk::RGBA {
+/// Wire image fit dropdown
+pub fn wire_image_fit_signal(
+    components: &PropertyPanelComponents,
+    app_state: AppState,
+    drawing_area: gtk4::DrawingArea,
+    render_state: crate::canvas::CanvasRenderState,
+) {
+    let dropdown = components.image_fit_combo.clone();
+
+    dropdown.connect_selected_notify(move |dropdown| {
+        let fit = image_fit_from_index(dropdown.selected());
+        let selected: Vec<uuid::Uuid> = render_state.selected_ids.borrow().clone();
+        if selected.is_empty() {
+            return;
+        }
+
+        // Skip no-op changes (e.g. the panel syncing to a new selection)
+        let page_index = app_state.active_page_index();
+        let differs = app_state
+            .with_active_document(|doc| {
+                doc.pages.get(page_index).is_some_and(|page| {
+                    page.elements.iter().any(|element| match element {
+                        DocumentElement::Image(image) => {
+                            selected.contains(&image.id) && image.fit != fit
+                        }
+                        _ => false,
+                    })
+                })
+            })
+            .unwrap_or(false);
+        if !differs {
+            return;
+        }
+
+        let command = crate::undo_redo::AppPropertyChangeCommand::new(
+            app_state.clone(),
+            selected,
+            page_index,
+            crate::undo_redo::PropertyValue::ImageFit(fit),
+        );
+        app_state.push_command(Box::new(command));
+        app_state.mark_as_modified();
+        drawing_area.queue_draw();
+        tracing::debug!("✅ Image fit set to {:?} (with undo support)", fit);
+    });
+}
+
+/// Image fit for a dropdown position (引き伸ばし / 全体を表示 / トリミング)
+pub(super) fn image_fit_from_index(index: u32) -> ImageFit {
+    match index {
+        1 => ImageFit::Contain,
+        2 => ImageFit::Cover,
+        _ => ImageFit::Stretch,
+    }
+}
+
+/// Dropdown position for an image fit
+pub(super) fn image_fit_index(fit: ImageFit) -> u32 {
+    match fit {
+        ImageFit::Stretch => 0,
+        ImageFit::Contain => 1,
+        ImageFit::Cover => 2,
+    }
+}
+
+pub(super) fn color_to_rgba(color: &testruct_core::typography::Color) -> gdk::RGBA {
     gdk::RGBA::new(color.r, color.g, color.b, color.a)
 }
 
-fn rgba_to_color(rgba: &gdk::RGBA) -> testruct_core::typography::Color {
+pub(super) fn rgba_to_color(rgba: &gdk::RGBA) -> testruct_core::typography::Color {
     testruct_core::typography::Color {
         r: rgba.red(),
         g: rgba.green(),
@@ -273,148 +471,30 @@ pub fn color_to_hex(color: &testruct_core::typography::Color) -> String {
     )
 }
 
-/// State for debounced stroke width undo
-struct StrokeWidthUndoState {
-    /// Original stroke widths when user started changing
-    original_widths: Vec<(uuid::Uuid, f32)>,
-    /// Selected element IDs when change started
-    selected_ids: Vec<uuid::Uuid>,
-    /// Pending timeout source ID
-    timeout_source: Option<gtk4::glib::SourceId>,
-    /// Whether we're in the middle of a change sequence
-    is_changing: bool,
+/// Hex color for a button label, followed by the opacity when not opaque
+pub fn color_to_label(color: &testruct_core::typography::Color) -> String {
+    let alpha = (color.a.clamp(0.0, 1.0) * 100.0).round();
+    if alpha < 100.0 {
+        format!("{} {:.0}%", color_to_hex(color), alpha)
+    } else {
+        color_to_hex(color)
+    }
 }
 
-/// Wire stroke width spinner to update shape stroke width with debounced undo
+/// Wire stroke width spinner: a drag or a run of clicks is undone in one step
 pub fn wire_stroke_width_signal(
     components: &PropertyPanelComponents,
     app_state: AppState,
     drawing_area: gtk4::DrawingArea,
     render_state: crate::canvas::CanvasRenderState,
 ) {
-    use std::cell::RefCell;
-    use std::rc::Rc;
-
     let spinner = components.stroke_width_spin.clone();
-
-    // Shared state for debounce tracking
-    let undo_state = Rc::new(RefCell::new(StrokeWidthUndoState {
-        original_widths: Vec::new(),
-        selected_ids: Vec::new(),
-        timeout_source: None,
-        is_changing: false,
-    }));
-
-    let undo_state_clone = undo_state.clone();
-    let app_state_clone = app_state.clone();
-    let render_state_clone = render_state.clone();
+    let slider_undo = SliderUndo::new(app_state, drawing_area, render_state);
 
     spinner.connect_value_changed(move |spin| {
         let stroke_width = spin.value() as f32;
-        let mut state = undo_state_clone.borrow_mut();
-
-        // Get current selection
-        let selected: Vec<uuid::Uuid> = render_state_clone.selected_ids.borrow().clone();
-        if selected.is_empty() {
-            return;
+        if slider_undo.preview(PropertyValue::StrokeWidth(stroke_width)) {
+            tracing::debug!("✅ Stroke width set to {} (with undo support)", stroke_width);
         }
-
-        // If this is the first change in a sequence, capture original values
-        if !state.is_changing {
-            state.is_changing = true;
-            state.selected_ids = selected.clone();
-            state.original_widths.clear();
-
-            // Capture original stroke widths
-            app_state_clone.with_active_document(|doc| {
-                if let Some(page) = doc.pages.first() {
-                    for element in &page.elements {
-                        if let DocumentElement::Shape(shape) = element {
-                            if selected.contains(&shape.id) {
-                                state.original_widths.push((shape.id, shape.stroke_width));
-                            }
-                        }
-                    }
-                }
-            });
-        }
-
-        // Cancel any existing timeout
-        if let Some(source_id) = state.timeout_source.take() {
-            source_id.remove();
-        }
-
-        drop(state); // Release borrow before document mutation
-
-        // Apply the change immediately for visual feedback
-        let changed = app_state_clone.with_mutable_active_document(|doc| {
-            let mut modified = false;
-            if let Some(page) = doc.pages.first_mut() {
-                for element in &mut page.elements {
-                    if let DocumentElement::Shape(shape) = element {
-                        if selected.contains(&shape.id) {
-                            shape.stroke_width = stroke_width;
-                            modified = true;
-                        }
-                    }
-                }
-            }
-            modified
-        });
-
-        if changed.unwrap_or(false) {
-            app_state_clone.mark_as_modified();
-        }
-
-        drawing_area.queue_draw();
-
-        // Set up debounce timeout (500ms)
-        let undo_state_timeout = undo_state_clone.clone();
-        let app_state_timeout = app_state_clone.clone();
-        let final_stroke_width = stroke_width;
-
-        let source_id = gtk4::glib::timeout_add_local_once(
-            std::time::Duration::from_millis(500),
-            move || {
-                let mut state = undo_state_timeout.borrow_mut();
-
-                if !state.is_changing || state.original_widths.is_empty() {
-                    state.is_changing = false;
-                    return;
-                }
-
-                // Create undo command with original values and final value
-                let page_index = app_state_timeout.active_page_index();
-
-                // Only create undo if value actually changed
-                let value_changed = state.original_widths.iter().any(|(_, orig)| {
-                    (*orig - final_stroke_width).abs() > 0.001
-                });
-
-                if value_changed {
-                    // Create command with pre-captured original values
-                    let command = crate::undo_redo::AppStrokeWidthCommand::new(
-                        app_state_timeout.clone(),
-                        page_index,
-                        state.original_widths.clone(),
-                        final_stroke_width,
-                    );
-
-                    // Push command to undo stack (execute() is a no-op since value is already applied)
-                    app_state_timeout.push_command(Box::new(command));
-
-                    tracing::debug!("✅ Stroke width undo command created (debounced)");
-                }
-
-                // Reset state
-                state.is_changing = false;
-                state.original_widths.clear();
-                state.selected_ids.clear();
-                state.timeout_source = None;
-            },
-        );
-
-        // Store the source ID
-        undo_state_clone.borrow_mut().timeout_source = Some(source_id);
     });
 }
@@ -0,0 +1,172 @@
+//! Coalesced undo for numeric property controls
+//!
+//! Spin buttons and sliders emit `value-changed` for every step of a drag.
+//! [`SliderUndo`] previews each value directly on the document and, once the
+//! control has been still for a moment, pushes a single
+//! `AppPropertyChangeCommand` holding the values from before the drag and the
+//! final value, so one undo reverts the whole drag. Undo and redo push a drag
+//! that is still waiting first, through `AppState::register_pending_edit`.
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::time::Duration;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+
+use crate::app::AppState;
+use crate::canvas::CanvasRenderState;
+use crate::undo_redo::{AppPropertyChangeCommand, PropertyValue};
+
+/// Quiet time after the last change before the drag counts as finished
+const COMMIT_DELAY: Duration = Duration::from_millis(400);
+
+/// A drag that has not been pushed to the undo stack yet
+struct PendingChange {
+    page_index: usize,
+    /// Selection the drag started with
+    selected_ids: Vec<uuid::Uuid>,
+    /// Values from before the drag, for the elements the property applies to
+    old_values: Vec<(uuid::Uuid, PropertyValue)>,
+    new_value: PropertyValue,
+}
+
+/// Groups the changes of one numeric control into single undo steps
+#[derive(Clone)]
+pub struct SliderUndo {
+    inner: Rc<SliderUndoInner>,
+}
+
+struct SliderUndoInner {
+    app_state: AppState,
+    drawing_area: gtk4::DrawingArea,
+    render_state: CanvasRenderState,
+    pending: RefCell<Option<PendingChange>>,
+    timeout_source: RefCell<Option<glib::SourceId>>,
+    /// Hook registered with the app state, which only keeps it weakly
+    flush: Rc<dyn Fn()>,
+}
+
+impl SliderUndo {
+    pub fn new(
+        app_state: AppState,
+        drawing_area: gtk4::DrawingArea,
+        render_state: CanvasRenderState,
+    ) -> Self {
+        let inner = Rc::new_cyclic(|weak: &Weak<SliderUndoInner>| {
+            // Undo during the quiet time must revert this drag, not the step
+            // before; the hook holds the control weakly to avoid a cycle
+            // through the app state
+            let weak = weak.clone();
+            let flush: Rc<dyn Fn()> = Rc::new(move || {
+                if let Some(inner) = weak.upgrade() {
+                    SliderUndo { inner }.commit();
+                }
+            });
+            SliderUndoInner {
+                app_state,
+                drawing_area,
+                render_state,
+                pending: RefCell::default(),
+                timeout_source: RefCell::default(),
+                flush,
+            }
+        });
+        inner.app_state.register_pending_edit(&inner.flush);
+        Self { inner }
+    }
+
+    /// Apply `value` to the selection and schedule the undo step
+    ///
+    /// Values matching what the selection already has (e.g. the panel
+    /// syncing to a new selection) are ignored. Returns whether the document
+    /// changed.
+    pub fn preview(&self, value: PropertyValue) -> bool {
+        let selected: Vec<uuid::Uuid> = self.inner.render_state.selected_ids.borrow().clone();
+        if selected.is_empty() {
+            return false;
+        }
+        let page_index = self.inner.app_state.active_page_index();
+
+        // A new selection or page starts a new undo step
+        let same_target = self.inner.pending.borrow().as_ref().is_some_and(|pending| {
+            pending.page_index == page_index && pending.selected_ids == selected
+        });
+        if !same_target {
+            self.commit();
+        }
+
+        let mut pending = self.inner.pending.borrow_mut();
+        if pending.is_none() {
+            let old_values = AppPropertyChangeCommand::current_values(
+                &self.inner.app_state,
+                &selected,
+                page_index,
+                &value,
+            );
+            if old_values.iter().all(|(_, old)| old.approx_eq(&value)) {
+                return false;
+            }
+            *pending = Some(PendingChange {
+                page_index,
+                selected_ids: selected,
+                old_values,
+                new_value: value.clone(),
+            });
+        }
+        let Some(change) = pending.as_mut() else {
+            return false;
+        };
+        change.new_value = value;
+        let element_ids: Vec<uuid::Uuid> = change.old_values.iter().map(|(id, _)| *id).collect();
+        AppPropertyChangeCommand::apply_to(
+            &self.inner.app_state,
+            &element_ids,
+            page_index,
+            &change.new_value,
+        );
+        drop(pending);
+
+        self.inner.app_state.mark_as_modified();
+        self.inner.drawing_area.queue_draw();
+
+        if let Some(source_id) = self.inner.timeout_source.borrow_mut().take() {
+            source_id.remove();
+        }
+        let slider_undo = self.clone();
+        let source_id = glib::timeout_add_local_once(COMMIT_DELAY, move || {
+            slider_undo.inner.timeout_source.borrow_mut().take();
+            slider_undo.commit();
+        });
+        *self.inner.timeout_source.borrow_mut() = Some(source_id);
+        true
+    }
+
+    /// Push the pending drag, if any, as one undoable command
+    pub fn commit(&self) {
+        if let Some(source_id) = self.inner.timeout_source.borrow_mut().take() {
+            source_id.remove();
+        }
+        let Some(change) = self.inner.pending.borrow_mut().take() else {
+            return;
+        };
+        if change
+            .old_values
+            .iter()
+            .all(|(_, old)| old.approx_eq(&change.new_value))
+        {
+            // Dragged back to where it started
+            return;
+        }
+
+        let command = AppPropertyChangeCommand::with_old_values(
+            self.inner.app_state.clone(),
+            change.page_index,
+            change.old_values,
+            change.new_value.clone(),
+        );
+        self.inner.app_state.push_command(Box::new(command));
+        self.inner.drawing_area.queue_draw();
+        tracing::debug!("✅ {:?} committed as one undo step", change.new_value);
+    }
+}
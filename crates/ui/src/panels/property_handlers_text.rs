@@ -3,10 +3,13 @@
 //! Handles text-specific property panel controls (font family, size, bold, italic, color, etc.)
 
 use gtk4::{gdk, gio, prelude::*, ColorDialog, StringList};
-use testruct_core::document::DocumentElement;
+use testruct_core::document::{DocumentElement, TextElement, TextPath};
 
+use super::slider_undo::SliderUndo;
 use super::PropertyPanelComponents;
 use crate::app::AppState;
+use crate::canvas::rendering::fit_text_height;
+use crate::undo_redo::{PaddingSide, PropertyValue};
 
 /// Wire font family selection
 pub fn wire_font_family_signal(
@@ -28,7 +31,7 @@ pub fn wire_font_family_signal(
                             if let DocumentElement::Text(text) = element {
                                 if selected.contains(&text.id) {
                                     text.style.font_family = font_name.clone();
-                                    recompute_auto_height(text);
+                                    fit_text_height(text);
                                     modified = true;
                                     tracing::debug!("✅ Font family changed to {}", font_name);
                                 }
@@ -48,7 +51,7 @@ pub fn wire_font_family_signal(
     });
 }
 
-/// Wire font size spinner
+/// Wire font size spinner: a drag or a run of clicks is undone in one step
 pub fn wire_font_size_signal(
     components: &PropertyPanelComponents,
     app_state: AppState,
@@ -56,39 +59,13 @@ pub fn wire_font_size_signal(
     render_state: crate::canvas::CanvasRenderState,
 ) {
     let spin = components.font_size_spin.clone();
+    let slider_undo = SliderUndo::new(app_state, drawing_area, render_state);
 
     spin.connect_value_changed(move |spinner| {
         let font_size = spinner.value() as f32;
-
-        let selected = render_state.selected_ids.borrow();
-        if !selected.is_empty() {
-            let selected_ids = selected.clone();
-            drop(selected);
-
-            let changed = app_state.with_mutable_active_document(|doc| {
-                let mut modified = false;
-                if let Some(page) = doc.pages.first_mut() {
-                    for element in &mut page.elements {
-                        match element {
-                            DocumentElement::Text(text) if selected_ids.contains(&text.id) => {
-                                text.style.font_size = font_size;
-                                recompute_auto_height(text);
-                                modified = true;
-                                tracing::debug!("✅ Font size changed to: {}px", font_size);
-                            }
-                            _ => {}
-                        }
-                    }
-                }
-                modified
-            });
-
-            if changed.unwrap_or(false) {
-                app_state.mark_as_modified();
-            }
+        if slider_undo.preview(PropertyValue::FontSize(font_size)) {
+            tracing::debug!("✅ Font size changed to: {}px", font_size);
         }
-
-        drawing_area.queue_draw();
     });
 }
 
@@ -117,7 +94,7 @@ pub fn wire_bold_signal(
                                 } else {
                                     testruct_core::typography::FontWeight::Regular
                                 };
-                                recompute_auto_height(text);
+                                fit_text_height(text);
                                 modified = true;
                                 tracing::debug!("✅ Bold: {}", is_bold);
                             }
@@ -157,7 +134,7 @@ pub fn wire_italic_signal(
                         if let DocumentElement::Text(text) = element {
                             if selected.contains(&text.id) {
                                 text.style.italic = is_italic;
-                                recompute_auto_height(text);
+                                fit_text_height(text);
                                 modified = true;
                                 tracing::debug!("✅ Italic: {}", is_italic);
                             }
@@ -197,7 +174,7 @@ pub fn wire_underline_signal(
                         if let DocumentElement::Text(text) = element {
                             if selected.contains(&text.id) {
                                 text.style.underline = is_underline;
-                                recompute_auto_height(text);
+                                fit_text_height(text);
                                 modified = true;
                                 tracing::debug!("✅ Underline: {}", is_underline);
                             }
@@ -237,7 +214,7 @@ pub fn wire_strikethrough_signal(
                         if let DocumentElement::Text(text) = element {
                             if selected.contains(&text.id) {
                                 text.style.strikethrough = is_strikethrough;
-                                recompute_auto_height(text);
+                                fit_text_height(text);
                                 modified = true;
                                 tracing::debug!("✅ Strikethrough: {}", is_strikethrough);
                             }
@@ -325,7 +302,7 @@ pub fn wire_text_background_color_signal(
                                 if selected_ids_for_cb.contains(&element.id()) {
                                     if let DocumentElement::Text(text) = element {
                                         text.style.background_color = Some(bg_color);
-                                        recompute_auto_height(text);
+                                        fit_text_height(text);
                                         changed = true;
                                     }
                                 }
@@ -419,7 +396,7 @@ pub fn wire_text_color_signal(
                                 if selected_ids_for_cb.contains(&element.id()) {
                                     if let DocumentElement::Text(text) = element {
                                         text.style.color = text_color;
-                                        recompute_auto_height(text);
+                                        fit_text_height(text);
                                         changed = true;
                                     }
                                 }
@@ -495,33 +472,42 @@ pub fn wire_alignment_dropdown(
     });
 }
 
-/// Wire line height scale
-pub fn wire_line_height_signal(
+/// Wire vertical alignment dropdown
+pub fn wire_vertical_alignment_dropdown(
     components: &PropertyPanelComponents,
     app_state: AppState,
     drawing_area: gtk4::DrawingArea,
     render_state: crate::canvas::CanvasRenderState,
 ) {
-    let scale = components.line_height_scale.clone();
+    use testruct_core::typography::VerticalAlignment;
+    let combo = components.text_vertical_align_combo.clone();
 
-    scale.connect_value_changed(move |scale_widget| {
-        let line_height = scale_widget.value() as f32;
+    combo.connect_notify_local(Some("selected"), move |combo_box, _pspec| {
+        let vertical_alignment = match combo_box.selected() {
+            1 => VerticalAlignment::Middle, // 中央
+            2 => VerticalAlignment::Bottom, // 下揃え
+            _ => VerticalAlignment::Top,    // 上揃え
+        };
+        let page_index = app_state.active_page_index();
 
         let changed = app_state.with_mutable_active_document(|doc| {
             let selected = render_state.selected_ids.borrow();
             let mut modified = false;
-            if !selected.is_empty() {
-                if let Some(page) = doc.pages.first_mut() {
-                    for element in &mut page.elements {
-                        match element {
-                            DocumentElement::Text(text) if selected.contains(&text.id) => {
-                                text.style.line_height = line_height;
-                                recompute_auto_height(text);
-                                modified = true;
-                                tracing::debug!("✅ Line height changed to: {}", line_height);
-                            }
-                            _ => {}
+            if let Some(page) = doc.pages.get_mut(page_index) {
+                for element in &mut page.elements {
+                    match element {
+                        DocumentElement::Text(text)
+                            if selected.contains(&text.id)
+                                && text.style.vertical_alignment != vertical_alignment =>
+                        {
+                            text.style.vertical_alignment = vertical_alignment;
+                            modified = true;
+                            tracing::debug!(
+                                "✅ Text vertical alignment changed to: {:?}",
+                                vertical_alignment
+                            );
                         }
+                        _ => {}
                     }
                 }
             }
@@ -530,9 +516,44 @@ pub fn wire_line_height_signal(
 
         if changed.unwrap_or(false) {
             app_state.mark_as_modified();
+            drawing_area.queue_draw();
         }
+    });
+}
 
-        drawing_area.queue_draw();
+/// Wire line height scale: a drag is undone in one step
+pub fn wire_line_height_signal(
+    components: &PropertyPanelComponents,
+    app_state: AppState,
+    drawing_area: gtk4::DrawingArea,
+    render_state: crate::canvas::CanvasRenderState,
+) {
+    let scale = components.line_height_scale.clone();
+    let slider_undo = SliderUndo::new(app_state, drawing_area, render_state);
+
+    scale.connect_value_changed(move |scale_widget| {
+        let line_height = scale_widget.value() as f32;
+        if slider_undo.preview(PropertyValue::LineHeight(line_height)) {
+            tracing::debug!("✅ Line height changed to: {}", line_height);
+        }
+    });
+}
+
+/// Wire letter spacing spin button: a drag or a run of clicks is undone in one step
+pub fn wire_letter_spacing_signal(
+    components: &PropertyPanelComponents,
+    app_state: AppState,
+    drawing_area: gtk4::DrawingArea,
+    render_state: crate::canvas::CanvasRenderState,
+) {
+    let spin = components.letter_spacing_spin.clone();
+    let slider_undo = SliderUndo::new(app_state, drawing_area, render_state);
+
+    spin.connect_value_changed(move |spin_widget| {
+        let letter_spacing = spin_widget.value() as f32;
+        if slider_undo.preview(PropertyValue::LetterSpacing(letter_spacing)) {
+            tracing::debug!("✅ Letter spacing changed to: {}", letter_spacing);
+        }
     });
 }
 
@@ -565,7 +586,7 @@ pub fn wire_text_content_signal(
                             if let DocumentElement::Text(text) = element {
                                 if text.id == *id {
                                     text.content = text_content.clone();
-                                    recompute_auto_height(text);
+                                    fit_text_height(text);
                                     modified = true;
                                     tracing::debug!("✅ Text content updated from property panel");
                                 }
@@ -607,7 +628,7 @@ pub fn wire_vertical_signal(
                         match element {
                             DocumentElement::Text(text) if selected_ids.contains(&text.id) => {
                                 text.style.vertical = is_vertical;
-                                recompute_auto_height(text);
+                                fit_text_height(text);
                                 modified = true;
                                 tracing::debug!("✅ Vertical writing mode: {}", is_vertical);
                             }
@@ -628,15 +649,154 @@ pub fn wire_vertical_signal(
     });
 }
 
-fn recompute_auto_height(text: &mut testruct_core::document::TextElement) {
-    if !text.auto_resize_height {
-        return;
+/// Wire the text padding spin buttons (top, right, bottom, left)
+///
+/// Each side is undone on its own, one step per drag or run of clicks.
+pub fn wire_text_padding_signal(
+    components: &PropertyPanelComponents,
+    app_state: AppState,
+    drawing_area: gtk4::DrawingArea,
+    render_state: crate::canvas::CanvasRenderState,
+) {
+    let sides = [
+        PaddingSide::Top,
+        PaddingSide::Right,
+        PaddingSide::Bottom,
+        PaddingSide::Left,
+    ];
+
+    for (spin, side) in components.text_padding_spins.iter().zip(sides) {
+        let slider_undo = SliderUndo::new(
+            app_state.clone(),
+            drawing_area.clone(),
+            render_state.clone(),
+        );
+        spin.connect_value_changed(move |spin_widget| {
+            let value = spin_widget.value() as f32;
+            if slider_undo.preview(PropertyValue::TextPadding(side, value)) {
+                tracing::debug!("✅ Text padding ({:?}) changed to: {}", side, value);
+            }
+        });
     }
+}
+
+/// Wire the circular text switch and its radius and start angle spin buttons
+///
+/// Turning the switch on fits a circle into the text box; a Bézier path set
+/// in the document is only replaced when the switch is turned on.
+pub fn wire_text_path_signals(
+    components: &PropertyPanelComponents,
+    app_state: AppState,
+    drawing_area: gtk4::DrawingArea,
+    render_state: crate::canvas::CanvasRenderState,
+) {
+    // Apply `update` to every selected text box on the active page
+    let update_selected_texts = {
+        let app_state = app_state.clone();
+        let drawing_area = drawing_area.clone();
+        let render_state = render_state.clone();
+        move |update: &dyn Fn(&mut TextElement) -> bool| {
+            let page_index = app_state.active_page_index();
+            let changed = app_state.with_mutable_active_document(|doc| {
+                let selected = render_state.selected_ids.borrow();
+                let mut modified = false;
+                if let Some(page) = doc.pages.get_mut(page_index) {
+                    for element in &mut page.elements {
+                        match element {
+                            DocumentElement::Text(text) if selected.contains(&text.id) => {
+                                modified |= update(text);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                modified
+            });
+
+            if changed.unwrap_or(false) {
+                app_state.mark_as_modified();
+                drawing_area.queue_draw();
+            }
+        }
+    };
+
+    let update = update_selected_texts;
+    let radius_spin = components.text_circle_radius_spin.clone();
+    let angle_spin = components.text_circle_angle_spin.clone();
+    components
+        .text_circle_switch
+        .connect_state_set(move |_switch, enabled| {
+            radius_spin.set_sensitive(enabled);
+            angle_spin.set_sensitive(enabled);
+            update(&|text| {
+                let is_circle = matches!(text.text_path, Some(TextPath::Circle { .. }));
+                if enabled == is_circle {
+                    return false;
+                }
+                text.text_path = enabled.then(|| TextPath::circle_in(&text.bounds));
+                tracing::debug!("✅ Circular text: {}", enabled);
+                true
+            });
+            gtk4::glib::Propagation::Proceed
+        });
+
+    let radius_undo = SliderUndo::new(
+        app_state.clone(),
+        drawing_area.clone(),
+        render_state.clone(),
+    );
+    let angle_undo = SliderUndo::new(app_state.clone(), drawing_area, render_state.clone());
+
+    // The radius is clamped so the circle stays inside every selected text box
+    components
+        .text_circle_radius_spin
+        .connect_value_changed(move |spin| {
+            let value = spin.value() as f32;
+            let max_radius = max_selected_circle_radius(&app_state, &render_state);
+            if value > max_radius {
+                // Comes back through this handler with the clamped value
+                spin.set_value(max_radius as f64);
+                return;
+            }
+            if radius_undo.preview(PropertyValue::CircleRadius(value)) {
+                tracing::debug!("✅ Circle radius changed to: {}", value);
+            }
+        });
 
-    let width = text.bounds.size.width.max(1.0);
-    let new_height =
-        crate::canvas::rendering::measure_text_height(&text.content, &text.style, width);
-    text.bounds.size.height = new_height.max(1.0);
+    components
+        .text_circle_angle_spin
+        .connect_value_changed(move |spin| {
+            let value = spin.value() as f32;
+            if angle_undo.preview(PropertyValue::CircleStartAngle(value)) {
+                tracing::debug!("✅ Circle start angle changed to: {}°", value);
+            }
+        });
+}
+
+/// Largest radius that keeps the circle inside every selected circular text box
+fn max_selected_circle_radius(
+    app_state: &AppState,
+    render_state: &crate::canvas::CanvasRenderState,
+) -> f32 {
+    let selected = render_state.selected_ids.borrow();
+    app_state
+        .with_active_page(|page| {
+            page.elements
+                .iter()
+                .filter_map(|element| match element {
+                    DocumentElement::Text(text) if selected.contains(&text.id) => {
+                        match text.text_path {
+                            Some(TextPath::Circle { .. }) => {
+                                Some(TextPath::max_circle_radius(&text.bounds))
+                            }
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                })
+                .fold(f32::INFINITY, f32::min)
+        })
+        .unwrap_or(f32::INFINITY)
 }
 
 fn dropdown_string(dropdown: &gtk4::DropDown, index: u32) -> Option<String> {
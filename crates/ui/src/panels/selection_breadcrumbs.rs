@@ -0,0 +1,157 @@
+//! Group hierarchy of the selection, shown in the status bar
+//!
+//! When a single element is selected, its enclosing frames and groups are
+//! listed outermost first, e.g. "大問1 › 設問 › テキスト: 解答欄". Clicking an
+//! ancestor selects it. Multiple or empty selections hide the breadcrumbs.
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Button, DrawingArea, Label, Orientation};
+use testruct_core::document::{DocumentElement, ShapeKind};
+
+use crate::app::AppState;
+use crate::canvas::{CanvasRenderState, CanvasView};
+
+/// Characters of text content shown in a breadcrumb segment
+const TEXT_PREVIEW_CHARS: usize = 12;
+
+/// Build the breadcrumb bar and keep it in sync with the selection
+pub fn build_selection_breadcrumbs(canvas_view: &CanvasView, app_state: AppState) -> GtkBox {
+    let container = GtkBox::new(Orientation::Horizontal, 2);
+    container.set_visible(false);
+
+    let render_state = canvas_view.render_state().clone();
+    let drawing_area = canvas_view.drawing_area();
+    let listener_container = container.clone();
+    let listener_render_state = render_state.clone();
+    render_state.connect_selection_changed(move |selection| {
+        refresh_breadcrumbs(
+            &listener_container,
+            selection,
+            &app_state,
+            &listener_render_state,
+            &drawing_area,
+        );
+    });
+
+    container
+}
+
+/// Rebuild the segments for `selection`
+pub fn refresh_breadcrumbs(
+    container: &GtkBox,
+    selection: &[uuid::Uuid],
+    app_state: &AppState,
+    render_state: &CanvasRenderState,
+    drawing_area: &DrawingArea,
+) {
+    while let Some(child) = container.first_child() {
+        container.remove(&child);
+    }
+
+    let segments = match selection {
+        [id] => selection_path(app_state, *id),
+        _ => Vec::new(),
+    };
+    container.set_visible(!segments.is_empty());
+
+    let last = segments.len().saturating_sub(1);
+    for (index, (id, label)) in segments.into_iter().enumerate() {
+        if index > 0 {
+            let separator = Label::new(Some("›"));
+            separator.add_css_class("dim-label");
+            container.append(&separator);
+        }
+        if index == last {
+            let current = Label::new(Some(&label));
+            current.add_css_class("heading");
+            container.append(&current);
+            continue;
+        }
+
+        let button = Button::with_label(&label);
+        button.add_css_class("flat");
+        button.set_tooltip_text(Some("このグループを選択"));
+        let render_state = render_state.clone();
+        let drawing_area = drawing_area.clone();
+        button.connect_clicked(move |_| {
+            *render_state.selected_ids.borrow_mut() = vec![id];
+            render_state.notify_selection_changed();
+            drawing_area.queue_draw();
+        });
+        container.append(&button);
+    }
+}
+
+/// Ids and labels from the outermost group down to the selected element
+fn selection_path(app_state: &AppState, element_id: uuid::Uuid) -> Vec<(uuid::Uuid, String)> {
+    let page_index = app_state.active_page_index();
+    app_state
+        .with_active_document(|doc| {
+            doc.pages
+                .get(page_index)
+                .and_then(|page| page.element_path(element_id))
+                .map(|path| {
+                    path.into_iter()
+                        .map(|element| (element.id(), breadcrumb_label(element)))
+                        .collect()
+                })
+        })
+        .flatten()
+        .unwrap_or_default()
+}
+
+/// Short label for one breadcrumb segment
+pub fn breadcrumb_label(element: &DocumentElement) -> String {
+    match element {
+        DocumentElement::Group(group) if !group.name.trim().is_empty() => group.name.clone(),
+        DocumentElement::Group(_) => "グループ".to_string(),
+        DocumentElement::Frame(_) => "フレーム".to_string(),
+        DocumentElement::Text(text) => {
+            let preview: String = text.content.chars().take(TEXT_PREVIEW_CHARS).collect();
+            let preview = preview.lines().next().unwrap_or_default().trim();
+            if preview.is_empty() {
+                "テキスト".to_string()
+            } else if text.content.chars().count() > TEXT_PREVIEW_CHARS {
+                format!("テキスト: {}…", preview)
+            } else {
+                format!("テキスト: {}", preview)
+            }
+        }
+        DocumentElement::Image(_) => "画像".to_string(),
+        DocumentElement::Shape(shape) => match shape.kind {
+            ShapeKind::Rectangle => "長方形",
+            ShapeKind::Ellipse => "楕円",
+            ShapeKind::Line => "直線",
+            ShapeKind::Arrow => "矢印",
+            ShapeKind::Polygon => "多角形",
+        }
+        .to_string(),
+        DocumentElement::Table(table) => format!("表 {} × {}", table.rows, table.columns),
+        DocumentElement::QrCode(_) => "QR コード".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use testruct_core::document::GroupElement;
+
+    #[test]
+    fn test_breadcrumb_label_names_groups() {
+        let mut group = GroupElement {
+            id: uuid::Uuid::new_v4(),
+            name: "大問1".to_string(),
+            bounds: Default::default(),
+            children: Vec::new(),
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+        };
+        assert_eq!(
+            breadcrumb_label(&DocumentElement::Group(group.clone())),
+            "大問1"
+        );
+        group.name = " ".to_string();
+        assert_eq!(breadcrumb_label(&DocumentElement::Group(group)), "グループ");
+    }
+}
@@ -0,0 +1,160 @@
+//! Status bar below the canvas
+//!
+//! Shows the pointer position in the current ruler unit, the zoom level, a
+//! summary of the selection and the group breadcrumbs of a single selected
+//! element. Only labels are updated: pointer motion and
+//! selection changes arrive through `CanvasRenderState` listeners and never
+//! queue a canvas redraw. The zoom (and the selection, for code paths that
+//! change it without notifying) is picked up on a short timer.
+
+use gtk4::prelude::*;
+use gtk4::{glib, Box as GtkBox, Label, Orientation, Separator};
+use std::time::Duration;
+use testruct_core::layout::Point;
+
+use super::selection_breadcrumbs::{build_selection_breadcrumbs, refresh_breadcrumbs};
+use crate::app::AppState;
+use crate::canvas::rendering::RulerUnit;
+use crate::canvas::CanvasView;
+
+/// How often the zoom and selection readouts are checked
+const REFRESH_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Build the status bar and keep it in sync with the canvas
+pub fn build_status_bar(canvas_view: &CanvasView, app_state: AppState) -> GtkBox {
+    let bar = GtkBox::new(Orientation::Horizontal, 12);
+    bar.add_css_class("toolbar");
+    bar.set_margin_start(12);
+    bar.set_margin_end(12);
+    bar.set_margin_top(2);
+    bar.set_margin_bottom(2);
+
+    let position_label = status_label(&format_cursor_position(None, RulerUnit::default()));
+    position_label.set_width_chars(28);
+    bar.append(&position_label);
+    bar.append(&Separator::new(Orientation::Vertical));
+
+    let zoom_label = status_label(&format_zoom(1.0));
+    bar.append(&zoom_label);
+    bar.append(&Separator::new(Orientation::Vertical));
+
+    let selection_label = status_label(&format_selection_summary(0));
+    bar.append(&selection_label);
+
+    let breadcrumbs = build_selection_breadcrumbs(canvas_view, app_state.clone());
+    breadcrumbs.set_hexpand(true);
+    bar.append(&breadcrumbs);
+
+    let render_state = canvas_view.render_state().clone();
+
+    let ruler_config = render_state.ruler_config.clone();
+    let pointer_label = position_label.clone();
+    render_state.connect_pointer_moved(move |position| {
+        let unit = ruler_config.borrow().unit;
+        pointer_label.set_text(&format_cursor_position(position, unit));
+    });
+
+    let selected_label = selection_label.clone();
+    render_state.connect_selection_changed(move |selection| {
+        selected_label.set_text(&format_selection_summary(selection.len()));
+    });
+
+    let drawing_area = canvas_view.drawing_area();
+    let shown_selection = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+    let listener_shown_selection = shown_selection.clone();
+    render_state.connect_selection_changed(move |selection| {
+        *listener_shown_selection.borrow_mut() = selection.to_vec();
+    });
+
+    let weak_bar = bar.downgrade();
+    glib::timeout_add_local(REFRESH_INTERVAL, move || {
+        // Stop once the status bar has been destroyed
+        if weak_bar.upgrade().is_none() {
+            return glib::ControlFlow::Break;
+        }
+        set_text_if_changed(&zoom_label, &format_zoom(render_state.config.borrow().zoom));
+        let selected = render_state.selected_ids.borrow().clone();
+        set_text_if_changed(&selection_label, &format_selection_summary(selected.len()));
+        if *shown_selection.borrow() != selected {
+            refresh_breadcrumbs(
+                &breadcrumbs,
+                &selected,
+                &app_state,
+                &render_state,
+                &drawing_area,
+            );
+            *shown_selection.borrow_mut() = selected;
+        }
+        glib::ControlFlow::Continue
+    });
+
+    bar
+}
+
+fn set_text_if_changed(label: &Label, text: &str) {
+    if label.text() != text {
+        label.set_text(text);
+    }
+}
+
+fn status_label(text: &str) -> Label {
+    let label = Label::new(Some(text));
+    label.add_css_class("dim-label");
+    label.set_xalign(0.0);
+    label
+}
+
+/// Pointer readout, e.g. "X: 25.4 mm  Y: 10.0 mm"
+pub fn format_cursor_position(position: Option<Point>, unit: RulerUnit) -> String {
+    match position {
+        Some(point) => format!(
+            "X: {:.1} {unit}  Y: {:.1} {unit}",
+            unit.from_pixels(point.x as f64),
+            unit.from_pixels(point.y as f64),
+            unit = unit.label()
+        ),
+        None => "X: –  Y: –".to_string(),
+    }
+}
+
+/// Zoom readout, e.g. "ズーム: 150%"
+pub fn format_zoom(zoom: f64) -> String {
+    format!("ズーム: {:.0}%", zoom * 100.0)
+}
+
+/// Selection readout, e.g. "2 個のオブジェクトを選択中"
+pub fn format_selection_summary(count: usize) -> String {
+    match count {
+        0 => "選択なし".to_string(),
+        count => format!("{} 個のオブジェクトを選択中", count),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_cursor_position_uses_ruler_unit() {
+        let point = Some(Point::new(96.0, 48.0));
+        assert_eq!(
+            format_cursor_position(point, RulerUnit::Pixels),
+            "X: 96.0 px  Y: 48.0 px"
+        );
+        assert_eq!(
+            format_cursor_position(point, RulerUnit::Millimeters),
+            "X: 25.4 mm  Y: 12.7 mm"
+        );
+        assert_eq!(
+            format_cursor_position(None, RulerUnit::Points),
+            "X: –  Y: –"
+        );
+    }
+
+    #[test]
+    fn test_format_zoom_and_selection() {
+        assert_eq!(format_zoom(1.5), "ズーム: 150%");
+        assert_eq!(format_selection_summary(0), "選択なし");
+        assert_eq!(format_selection_summary(2), "2 個のオブジェクトを選択中");
+    }
+}
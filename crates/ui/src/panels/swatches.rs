@@ -0,0 +1,270 @@
+//! Color swatches panel
+//!
+//! Shows the project palette as clickable color chips. Clicking a chip
+//! applies the color to the fill or stroke of the selected shapes; the add
+//! button stores the selection's current color (or the last eyedropper
+//! sample when no shape is selected) in the palette. Right-click
+//! removes a user swatch (the named palette colors stay).
+
+use gtk4::{
+    prelude::*, Align, Box as GtkBox, Button, DrawingArea, DropDown, FlowBox, GestureClick, Label,
+    Orientation, ScrolledWindow, SelectionMode,
+};
+use testruct_core::document::{DocumentElement, Fill, ShapeElement};
+use testruct_core::typography::Color;
+
+use super::PropertyPanelComponents;
+use crate::app::AppState;
+use crate::canvas::{CanvasRenderState, CanvasView};
+
+/// Size of a color chip in pixels
+const SWATCH_SIZE: i32 = 24;
+
+/// Components for the swatches panel
+#[derive(Clone)]
+pub struct SwatchesPanelComponents {
+    pub container: GtkBox,
+    pub swatch_grid: FlowBox,
+    /// 0 = fill, 1 = stroke
+    pub target_combo: DropDown,
+    pub add_button: Button,
+}
+
+/// Everything a swatch click needs to apply its color
+#[derive(Clone)]
+struct SwatchContext {
+    panel: SwatchesPanelComponents,
+    app_state: AppState,
+    drawing_area: DrawingArea,
+    render_state: CanvasRenderState,
+    property_components: PropertyPanelComponents,
+}
+
+/// Build the swatches panel
+pub fn build_swatches_panel() -> SwatchesPanelComponents {
+    let container = GtkBox::new(Orientation::Vertical, 6);
+    container.set_margin_top(12);
+    container.set_margin_bottom(12);
+    container.set_margin_start(12);
+    container.set_margin_end(12);
+
+    let title = Label::new(Some("スウォッチ"));
+    title.add_css_class("title-3");
+    container.append(&title);
+
+    // Target and add button
+    let action_box = GtkBox::new(Orientation::Horizontal, 6);
+    let target_label = Label::new(Some("適用先:"));
+    target_label.set_halign(Align::Start);
+    action_box.append(&target_label);
+
+    let target_combo = DropDown::from_strings(&["塗り", "線"]);
+    target_combo.set_hexpand(true);
+    target_combo.set_tooltip_text(Some("スウォッチの色を適用する対象"));
+    action_box.append(&target_combo);
+
+    let add_button = Button::with_label("+ 追加");
+    add_button.add_css_class("flat");
+    add_button.set_tooltip_text(Some(
+        "選択中の図形の色（未選択時はスポイトの色）をパレットに追加",
+    ));
+    action_box.append(&add_button);
+    container.append(&action_box);
+
+    let swatch_grid = FlowBox::new();
+    swatch_grid.set_selection_mode(SelectionMode::None);
+    swatch_grid.set_homogeneous(true);
+    swatch_grid.set_max_children_per_line(8);
+    swatch_grid.set_row_spacing(4);
+    swatch_grid.set_column_spacing(4);
+    swatch_grid.set_valign(Align::Start);
+
+    let scrolled = ScrolledWindow::new();
+    scrolled.set_child(Some(&swatch_grid));
+    scrolled.set_hexpand(true);
+    scrolled.set_vexpand(true);
+    container.append(&scrolled);
+
+    let hint = Label::new(Some("右クリックで追加した色を削除"));
+    hint.add_css_class("dim-label");
+    hint.set_halign(Align::Start);
+    container.append(&hint);
+
+    SwatchesPanelComponents {
+        container,
+        swatch_grid,
+        target_combo,
+        add_button,
+    }
+}
+
+/// Fill the grid from the project palette and connect the add button
+pub fn wire_swatches_panel(
+    components: &SwatchesPanelComponents,
+    app_state: AppState,
+    canvas_view: &CanvasView,
+    property_components: &PropertyPanelComponents,
+) {
+    let context = SwatchContext {
+        panel: components.clone(),
+        app_state,
+        drawing_area: canvas_view.drawing_area(),
+        render_state: canvas_view.render_state().clone(),
+        property_components: property_components.clone(),
+    };
+    refresh_swatches(&context);
+
+    let add_context = context.clone();
+    components.add_button.connect_clicked(move |_| {
+        let selected: Vec<uuid::Uuid> = add_context.render_state.selected_ids.borrow().clone();
+        let apply_to_stroke = add_context.panel.target_combo.selected() == 1;
+        let color = match first_selected_shape(&add_context.app_state, &selected) {
+            Some(shape) if apply_to_stroke => shape.stroke,
+            Some(shape) => shape.fill.map(|fill| fill.primary_color()),
+            None => add_context.render_state.tool_state.borrow().sampled_color,
+        };
+        let Some(color) = color else {
+            tracing::warn!("⚠️ パレットに追加する色を持つ図形が選択されていません");
+            return;
+        };
+
+        let added = add_context
+            .app_state
+            .with_project(|project| project.settings.palette.add_swatch(color));
+        if added {
+            refresh_swatches(&add_context);
+            tracing::info!("🎨 Added swatch to the project palette");
+        }
+    });
+}
+
+/// Rebuild the color chips from the project palette
+fn refresh_swatches(context: &SwatchContext) {
+    let grid = &context.panel.swatch_grid;
+    while let Some(child) = grid.first_child() {
+        grid.remove(&child);
+    }
+
+    let palette = context.app_state.project().settings.palette;
+    let named_count = palette.colors().len() - palette.swatches.len();
+    for (index, color) in palette.colors().into_iter().enumerate() {
+        let swatch_index = index.checked_sub(named_count);
+        grid.insert(&build_swatch_button(context, color, swatch_index), -1);
+    }
+}
+
+/// A chip applying `color` on click; user swatches are removable via right-click
+fn build_swatch_button(
+    context: &SwatchContext,
+    color: Color,
+    swatch_index: Option<usize>,
+) -> Button {
+    let chip = DrawingArea::new();
+    chip.set_content_width(SWATCH_SIZE);
+    chip.set_content_height(SWATCH_SIZE);
+    chip.set_draw_func(move |_, ctx, width, height| {
+        ctx.set_source_rgba(
+            color.r as f64,
+            color.g as f64,
+            color.b as f64,
+            color.a as f64,
+        );
+        ctx.rectangle(0.0, 0.0, width as f64, height as f64);
+        let _ = ctx.fill_preserve();
+        ctx.set_source_rgba(0.0, 0.0, 0.0, 0.3);
+        ctx.set_line_width(1.0);
+        let _ = ctx.stroke();
+    });
+
+    let button = Button::new();
+    button.add_css_class("flat");
+    button.set_child(Some(&chip));
+    button.set_tooltip_text(Some(&color_hex(&color)));
+
+    let click_context = context.clone();
+    button.connect_clicked(move |_| apply_swatch(&click_context, color));
+
+    if let Some(index) = swatch_index {
+        let gesture = GestureClick::new();
+        gesture.set_button(gtk4::gdk::BUTTON_SECONDARY);
+        let remove_context = context.clone();
+        gesture.connect_pressed(move |_, _, _, _| {
+            remove_context
+                .app_state
+                .with_project(|project| project.settings.palette.remove_swatch(index));
+            refresh_swatches(&remove_context);
+            tracing::info!("🎨 Removed swatch from the project palette");
+        });
+        button.add_controller(gesture);
+    }
+
+    button
+}
+
+/// Apply `color` to the fill or stroke of the selected shapes (undoable)
+fn apply_swatch(context: &SwatchContext, color: Color) {
+    let selected: Vec<uuid::Uuid> = context.render_state.selected_ids.borrow().clone();
+    let Some(shape) = first_selected_shape(&context.app_state, &selected) else {
+        tracing::warn!("⚠️ 色を適用する図形が選択されていません");
+        return;
+    };
+
+    let value = if context.panel.target_combo.selected() == 1 {
+        crate::undo_redo::PropertyValue::StrokeColor(Some(color))
+    } else {
+        // Gradients keep their end color and angle; the swatch sets the start
+        let fill = match shape.fill {
+            Some(Fill::LinearGradient { end, angle, .. }) => Fill::LinearGradient {
+                start: color,
+                end,
+                angle,
+            },
+            _ => Fill::Solid(color),
+        };
+        crate::undo_redo::PropertyValue::Fill(Some(fill))
+    };
+
+    let page_index = context.app_state.active_page_index();
+    let command = crate::undo_redo::AppPropertyChangeCommand::new(
+        context.app_state.clone(),
+        selected.clone(),
+        page_index,
+        value,
+    );
+    context.app_state.push_command(Box::new(command));
+    context.drawing_area.queue_draw();
+    super::update_property_panel_on_selection(
+        &context.property_components,
+        &context.app_state,
+        &selected,
+    );
+    tracing::debug!("✅ Swatch color applied (with undo support)");
+}
+
+/// First selected shape on the active page
+fn first_selected_shape(app_state: &AppState, selected: &[uuid::Uuid]) -> Option<ShapeElement> {
+    let page_index = app_state.active_page_index();
+    app_state
+        .with_active_document(|doc| {
+            doc.pages.get(page_index).and_then(|page| {
+                page.elements.iter().find_map(|element| match element {
+                    DocumentElement::Shape(shape) if selected.contains(&shape.id) => {
+                        Some(shape.clone())
+                    }
+                    _ => None,
+                })
+            })
+        })
+        .flatten()
+}
+
+/// `#rrggbb` label for the swatch tooltip
+fn color_hex(color: &Color) -> String {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        channel(color.r),
+        channel(color.g),
+        channel(color.b)
+    )
+}
@@ -1,10 +1,15 @@
 //! Template system for document templates
 //!
 //! Provides functionality to save, load, and manage document templates.
+//! Each template JSON file has a PNG preview of its first page next to it.
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use testruct_core::document::Document;
+use testruct_core::workspace::assets::AssetCatalog;
+
+/// Resolution of template preview images (A4 comes out around 200×280 px)
+const THUMBNAIL_DPI: f64 = 24.0;
 
 /// Get the templates directory path
 pub fn templates_dir() -> PathBuf {
@@ -21,25 +26,122 @@ pub fn init_templates_dir() -> std::io::Result<()> {
     Ok(())
 }
 
-/// Save document as a template
-pub fn save_template(name: &str, document: &Document) -> std::io::Result<()> {
-    init_templates_dir()?;
+/// A template file saved under `templates_dir`
+///
+/// Templates without a category sit directly in the templates directory;
+/// categorized ones live in a subfolder named after the category.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TemplateEntry {
+    pub name: String,
+    pub category: Option<String>,
+}
 
-    let dir = templates_dir();
-    let file_path = dir.join(format!("{}.json", name));
+/// Path of the template file for `name` in `category`
+///
+/// Path separators and leading dots in the name or category are replaced,
+/// so `..` or a hidden name can never point outside the templates directory.
+pub fn template_path(name: &str, category: Option<&str>) -> PathBuf {
+    let mut dir = templates_dir();
+    if let Some(category) = category.map(str::trim).filter(|c| !c.is_empty()) {
+        dir = dir.join(file_safe(category));
+    }
+    dir.join(format!("{}.json", file_safe(name.trim())))
+}
+
+/// Whether a template named `name` already exists in `category`
+pub fn template_exists(name: &str, category: Option<&str>) -> bool {
+    template_path(name, category).exists()
+}
+
+fn file_safe(name: &str) -> String {
+    let mut leading = true;
+    name.chars()
+        .map(|c| {
+            leading &= c == '.';
+            if leading || matches!(c, '/' | '\\') {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Save document as a template, optionally in a category subfolder
+///
+/// Also writes the preview thumbnail, drawing images from `catalog`. A
+/// failed thumbnail is only logged; the browser regenerates it on demand.
+pub fn save_template(
+    name: &str,
+    category: Option<&str>,
+    document: &Document,
+    catalog: &AssetCatalog,
+) -> std::io::Result<()> {
+    let file_path = template_path(name, category);
+    if let Some(dir) = file_path.parent() {
+        fs::create_dir_all(dir)?;
+    }
 
     let json = serde_json::to_string_pretty(document)
         .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
 
     fs::write(&file_path, json)?;
     tracing::info!("✅ Template saved: {}", file_path.display());
+
+    if let Err(e) = render_thumbnail(document, &file_path.with_extension("png"), catalog) {
+        tracing::warn!("⚠️ Failed to render template thumbnail: {}", e);
+    }
     Ok(())
 }
 
+/// Preview image of a saved template, rendering it first if missing
+///
+/// Templates saved before thumbnails existed are rendered from their JSON,
+/// with images drawn from the template's own asset catalog.
+pub fn template_thumbnail(entry: &TemplateEntry) -> Option<PathBuf> {
+    let thumbnail_path =
+        template_path(&entry.name, entry.category.as_deref()).with_extension("png");
+    if thumbnail_path.exists() {
+        return Some(thumbnail_path);
+    }
+
+    let document = match load_template(entry) {
+        Ok(document) => document,
+        Err(e) => {
+            tracing::warn!(
+                "⚠️ Cannot load template {} for thumbnail: {}",
+                entry.name,
+                e
+            );
+            return None;
+        }
+    };
+    match render_thumbnail(&document, &thumbnail_path, &document.assets) {
+        Ok(()) => Some(thumbnail_path),
+        Err(e) => {
+            tracing::warn!("⚠️ Failed to render template thumbnail: {}", e);
+            None
+        }
+    }
+}
+
+/// Render the first page of `document` into a low-resolution PNG
+fn render_thumbnail(
+    document: &Document,
+    output_path: &Path,
+    catalog: &AssetCatalog,
+) -> anyhow::Result<()> {
+    let options = crate::export::PngExportOptions {
+        dpi: THUMBNAIL_DPI,
+        pages: Some(vec![0]),
+        transparent_background: false,
+    };
+    crate::export::export_png_with_options(document, output_path, &options, catalog)
+}
+
 /// Load document from template
-pub fn load_template(name: &str) -> std::io::Result<Document> {
-    let dir = templates_dir();
-    let file_path = dir.join(format!("{}.json", name));
+pub fn load_template(entry: &TemplateEntry) -> std::io::Result<Document> {
+    let file_path = template_path(&entry.name, entry.category.as_deref());
 
     let json = fs::read_to_string(&file_path)?;
     let document: Document = serde_json::from_str(&json)
@@ -50,42 +152,71 @@ pub fn load_template(name: &str) -> std::io::Result<Document> {
 }
 
 /// List all available templates
-pub fn list_templates() -> std::io::Result<Vec<String>> {
+///
+/// Uncategorized templates come first, then each category in name order.
+pub fn list_templates() -> std::io::Result<Vec<TemplateEntry>> {
     init_templates_dir()?;
+    list_templates_in(&templates_dir())
+}
 
-    let dir = templates_dir();
+fn list_templates_in(dir: &Path) -> std::io::Result<Vec<TemplateEntry>> {
     let mut templates = Vec::new();
 
     if !dir.exists() {
         return Ok(templates);
     }
 
-    for entry in fs::read_dir(&dir)? {
-        let entry = entry?;
-        let path = entry.path();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
 
-        if path.extension().is_some_and(|ext| ext == "json") {
-            if let Some(name) = path.file_stem() {
-                if let Some(name_str) = name.to_str() {
-                    templates.push(name_str.to_string());
-                }
+        if path.is_dir() {
+            let Some(category) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            for name in json_stems(&path)? {
+                templates.push(TemplateEntry {
+                    name,
+                    category: Some(category.to_string()),
+                });
             }
         }
     }
+    for name in json_stems(dir)? {
+        templates.push(TemplateEntry {
+            name,
+            category: None,
+        });
+    }
 
-    templates.sort();
+    templates.sort_by(|a, b| (&a.category, &a.name).cmp(&(&b.category, &b.name)));
     Ok(templates)
 }
 
-/// Delete a template
-pub fn delete_template(name: &str) -> std::io::Result<()> {
-    let dir = templates_dir();
-    let file_path = dir.join(format!("{}.json", name));
+/// Stems of the `.json` files directly inside `dir`
+fn json_stems(dir: &Path) -> std::io::Result<Vec<String>> {
+    let mut stems = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
 
-    if file_path.exists() {
-        fs::remove_file(&file_path)?;
-        tracing::info!("✅ Template deleted: {}", name);
+        if path.extension().is_some_and(|ext| ext == "json") {
+            if let Some(name_str) = path.file_stem().and_then(|name| name.to_str()) {
+                stems.push(name_str.to_string());
+            }
+        }
     }
+    Ok(stems)
+}
+
+/// Delete a template and its thumbnail
+pub fn delete_template(entry: &TemplateEntry) -> std::io::Result<()> {
+    let file_path = template_path(&entry.name, entry.category.as_deref());
+
+    for path in [file_path.with_extension("png"), file_path] {
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+    }
+    tracing::info!("✅ Template deleted: {}", entry.name);
 
     Ok(())
 }
@@ -105,4 +236,59 @@ mod tests {
         let result = list_templates();
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_template_path_uses_category_folder() {
+        let dir = templates_dir();
+        assert_eq!(template_path("Flyer", None), dir.join("Flyer.json"));
+        assert_eq!(
+            template_path("Flyer", Some("Events")),
+            dir.join("Events").join("Flyer.json")
+        );
+        assert_eq!(template_path("Flyer", Some("  ")), dir.join("Flyer.json"));
+        assert_eq!(template_path("a/b", None), dir.join("a_b.json"));
+    }
+
+    #[test]
+    fn test_template_path_stays_in_templates_dir() {
+        let dir = templates_dir();
+        let path = template_path("..", Some(".."));
+        assert_eq!(path, dir.join("__").join("__.json"));
+        assert!(path
+            .components()
+            .all(|part| part != std::path::Component::ParentDir));
+
+        assert_eq!(template_path(".hidden", None), dir.join("_hidden.json"));
+        assert_eq!(
+            template_path("v1.2", Some(".")),
+            dir.join("_").join("v1.2.json")
+        );
+    }
+
+    #[test]
+    fn test_list_templates_groups_by_category() {
+        let dir = std::env::temp_dir().join(format!("testruct-templates-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("Events")).unwrap();
+        fs::write(dir.join("Plain.json"), "{}").unwrap();
+        fs::write(dir.join("Plain.png"), "").unwrap();
+        fs::write(dir.join("Events").join("Flyer.json"), "{}").unwrap();
+        fs::write(dir.join("notes.txt"), "").unwrap();
+
+        let entries = list_templates_in(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            entries,
+            vec![
+                TemplateEntry {
+                    name: "Plain".to_string(),
+                    category: None,
+                },
+                TemplateEntry {
+                    name: "Flyer".to_string(),
+                    category: Some("Events".to_string()),
+                },
+            ]
+        );
+    }
 }
@@ -0,0 +1,433 @@
+//! Guide manager popover
+//!
+//! Opened from "ガイドを管理…" in the view popover. A new guide can be placed
+//! by typing its position, and every existing guide is listed with an entry
+//! for exact repositioning. Positions are typed and shown in the current
+//! ruler unit and stored in canvas pixels.
+//!
+//! Presets add margin, column or row guides computed from the active page
+//! size, and "すべてのガイドを消去" removes every guide. Each change made here
+//! replaces the guide set through one `GuidesCommand`, so a single undo
+//! reverts a whole preset.
+
+use super::ToolbarWidgets;
+use crate::app::AppState;
+use crate::canvas::grid_rendering::{Guide, GuideOrientation};
+use crate::canvas::rendering::RulerUnit;
+use crate::canvas::{CanvasRenderState, CanvasView};
+use crate::undo_redo::GuidesCommand;
+use gtk4::prelude::*;
+use gtk4::{
+    Adjustment, Box as GtkBox, Button, DrawingArea, DropDown, Entry, Label, Orientation, Separator,
+    SpinButton,
+};
+use testruct_core::layout::Size;
+
+/// Orientation choices in the order shown by the drop-down
+const ORIENTATION_CHOICES: [GuideOrientation; 2] =
+    [GuideOrientation::Horizontal, GuideOrientation::Vertical];
+
+/// Margin inset offered by the margin preset, in canvas pixels
+const DEFAULT_MARGIN_INSET: f64 = 36.0;
+
+/// Column and row counts offered by the presets
+const DEFAULT_DIVISIONS: f64 = 3.0;
+const MAX_DIVISIONS: f64 = 24.0;
+
+/// Widgets and state the guide list is rebuilt from
+#[derive(Clone)]
+struct GuideList {
+    list_box: GtkBox,
+    render_state: CanvasRenderState,
+    drawing_area: DrawingArea,
+    app_state: AppState,
+}
+
+/// Show the guide manager when its menu button is clicked
+pub fn wire_guide_list(toolbar: &ToolbarWidgets, canvas_view: &CanvasView, app_state: &AppState) {
+    let guide_list = GuideList {
+        list_box: toolbar.guide_list_box.clone(),
+        render_state: canvas_view.render_state().clone(),
+        drawing_area: canvas_view.drawing_area(),
+        app_state: app_state.clone(),
+    };
+    guide_list.list_box.set_margin_start(12);
+    guide_list.list_box.set_margin_end(12);
+    guide_list.list_box.set_margin_top(12);
+    guide_list.list_box.set_margin_bottom(12);
+
+    let popover = toolbar.guide_list_popover.clone();
+    toolbar
+        .buttons
+        .guide_list_menu_btn
+        .connect_clicked(move |_| {
+            guide_list.rebuild();
+            popover.popup();
+        });
+}
+
+/// Parse a typed guide position in `unit` into canvas pixels
+///
+/// The value may carry the unit suffix ("25 mm"). It must lie on the page,
+/// i.e. between 0 and `extent` pixels along the axis the guide measures.
+pub fn parse_guide_position(text: &str, unit: RulerUnit, extent: f64) -> Result<f32, String> {
+    let text = text.trim();
+    let number = text.strip_suffix(unit.label()).unwrap_or(text).trim();
+    let value: f64 = number
+        .parse()
+        .map_err(|_| format!("数値を入力してください ({})", unit.label()))?;
+    let pixels = unit.to_pixels(value);
+    if !pixels.is_finite() || pixels < 0.0 || pixels > extent {
+        return Err(format!(
+            "ページ内の位置を入力してください (0〜{} {})",
+            format_guide_position(extent as f32, unit),
+            unit.label()
+        ));
+    }
+    Ok(pixels as f32)
+}
+
+/// Guide position in canvas pixels shown in `unit`
+pub fn format_guide_position(position: f32, unit: RulerUnit) -> String {
+    let value = format!("{:.2}", unit.from_pixels(position as f64));
+    value
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+/// Page length a guide of `orientation` is measured along
+fn guide_extent(page_size: Size, orientation: GuideOrientation) -> f64 {
+    match orientation {
+        GuideOrientation::Horizontal => page_size.height as f64,
+        GuideOrientation::Vertical => page_size.width as f64,
+    }
+}
+
+/// Guides `inset` pixels inside each page edge
+pub fn margin_guides(page_size: Size, inset: f32) -> Vec<Guide> {
+    vec![
+        Guide::new(GuideOrientation::Vertical, inset),
+        Guide::new(GuideOrientation::Vertical, page_size.width - inset),
+        Guide::new(GuideOrientation::Horizontal, inset),
+        Guide::new(GuideOrientation::Horizontal, page_size.height - inset),
+    ]
+}
+
+/// Vertical guides splitting the page width into `count` equal columns
+pub fn column_guides(page_size: Size, count: u32) -> Vec<Guide> {
+    division_guides(GuideOrientation::Vertical, page_size.width, count)
+}
+
+/// Horizontal guides splitting the page height into `count` equal rows
+pub fn row_guides(page_size: Size, count: u32) -> Vec<Guide> {
+    division_guides(GuideOrientation::Horizontal, page_size.height, count)
+}
+
+fn division_guides(orientation: GuideOrientation, extent: f32, count: u32) -> Vec<Guide> {
+    (1..count)
+        .map(|i| Guide::new(orientation, extent * i as f32 / count as f32))
+        .collect()
+}
+
+fn orientation_label(orientation: GuideOrientation) -> &'static str {
+    match orientation {
+        GuideOrientation::Horizontal => "水平",
+        GuideOrientation::Vertical => "垂直",
+    }
+}
+
+impl GuideList {
+    fn page_size(&self) -> Size {
+        self.app_state
+            .active_page_size()
+            .unwrap_or(Size::new(800.0, 600.0))
+    }
+
+    fn guides(&self) -> Vec<Guide> {
+        self.render_state.config.borrow().guides.clone()
+    }
+
+    /// Replace the guide set as one undoable step and refresh the list
+    fn replace_guides(&self, guides: Vec<Guide>, description: &str) {
+        let command = GuidesCommand::new(self.render_state.config.clone(), guides, description);
+        self.app_state.push_command(Box::new(command));
+        tracing::info!("✅ {}", description);
+        self.drawing_area.queue_draw();
+        self.rebuild();
+    }
+
+    /// Recreate the entry row and one row per existing guide
+    fn rebuild(&self) {
+        while let Some(child) = self.list_box.first_child() {
+            self.list_box.remove(&child);
+        }
+
+        let unit = self.render_state.ruler_config.borrow().unit;
+
+        let heading = Label::new(Some("ガイドを追加"));
+        heading.add_css_class("section-heading");
+        heading.set_halign(gtk4::Align::Start);
+        self.list_box.append(&heading);
+
+        let add_row = GtkBox::new(Orientation::Horizontal, 6);
+        let labels: Vec<&str> = ORIENTATION_CHOICES
+            .iter()
+            .map(|orientation| orientation_label(*orientation))
+            .collect();
+        let orientation_combo = DropDown::from_strings(&labels);
+        add_row.append(&orientation_combo);
+
+        let position_entry = Entry::new();
+        position_entry.set_placeholder_text(Some("位置"));
+        position_entry.set_width_chars(8);
+        position_entry.set_hexpand(true);
+        add_row.append(&position_entry);
+        add_row.append(&Label::new(Some(unit.label())));
+
+        let add_btn = Button::with_label("追加");
+        add_row.append(&add_btn);
+        self.list_box.append(&add_row);
+
+        let error_label = Label::new(None);
+        error_label.add_css_class("error");
+        error_label.set_halign(gtk4::Align::Start);
+        error_label.set_wrap(true);
+        error_label.set_visible(false);
+        self.list_box.append(&error_label);
+
+        let add_guide = {
+            let list = self.clone();
+            let combo = orientation_combo.clone();
+            let entry = position_entry.clone();
+            let error_label = error_label.clone();
+            move || {
+                let orientation = ORIENTATION_CHOICES
+                    .get(combo.selected() as usize)
+                    .copied()
+                    .unwrap_or(GuideOrientation::Horizontal);
+                let extent = guide_extent(list.page_size(), orientation);
+                match parse_guide_position(&entry.text(), unit, extent) {
+                    Ok(position) => {
+                        let mut guides = list.guides();
+                        guides.push(Guide::new(orientation, position));
+                        list.replace_guides(guides, "Add Guide");
+                    }
+                    Err(message) => {
+                        error_label.set_text(&message);
+                        error_label.set_visible(true);
+                    }
+                }
+            }
+        };
+        let add_from_entry = add_guide.clone();
+        position_entry.connect_activate(move |_| add_from_entry());
+        add_btn.connect_clicked(move |_| add_guide());
+
+        self.append_presets(unit);
+        self.list_box
+            .append(&Separator::new(Orientation::Horizontal));
+
+        let guides = self.guides();
+        if guides.is_empty() {
+            let empty = Label::new(Some("ガイドはありません"));
+            empty.add_css_class("dim-label");
+            self.list_box.append(&empty);
+        }
+        for (index, guide) in guides.iter().enumerate() {
+            self.list_box
+                .append(&self.build_guide_row(index, guide, unit, &error_label));
+        }
+
+        let clear_btn = Button::with_label("すべてのガイドを消去");
+        clear_btn.add_css_class("destructive-action");
+        clear_btn.set_sensitive(!guides.is_empty());
+        let list = self.clone();
+        clear_btn.connect_clicked(move |_| list.replace_guides(Vec::new(), "Clear Guides"));
+        self.list_box.append(&clear_btn);
+    }
+
+    /// Margin, column and row preset rows
+    fn append_presets(&self, unit: RulerUnit) {
+        let heading = Label::new(Some("プリセット"));
+        heading.add_css_class("section-heading");
+        heading.set_halign(gtk4::Align::Start);
+        self.list_box.append(&heading);
+
+        let page_size = self.page_size();
+        let max_inset = unit.from_pixels(page_size.width.min(page_size.height) as f64 / 2.0);
+        let margin_adj = Adjustment::new(
+            unit.from_pixels(DEFAULT_MARGIN_INSET).min(max_inset),
+            0.0,
+            max_inset,
+            1.0,
+            10.0,
+            0.0,
+        );
+        let margin_spin = SpinButton::new(Some(&margin_adj), 1.0, 1);
+        let list = self.clone();
+        let margin_value = margin_spin.clone();
+        self.append_preset_row(
+            &format!("余白 ({})", unit.label()),
+            &margin_spin,
+            "余白ガイド",
+            move || {
+                let inset = unit.to_pixels(margin_value.value()) as f32;
+                let mut guides = list.guides();
+                guides.extend(margin_guides(list.page_size(), inset));
+                list.replace_guides(guides, "Margin Guides");
+            },
+        );
+
+        let column_spin = SpinButton::with_range(1.0, MAX_DIVISIONS, 1.0);
+        column_spin.set_value(DEFAULT_DIVISIONS);
+        let list = self.clone();
+        let column_value = column_spin.clone();
+        self.append_preset_row("列", &column_spin, "列ガイド", move || {
+            let mut guides = list.guides();
+            guides.extend(column_guides(list.page_size(), column_value.value() as u32));
+            list.replace_guides(guides, "Column Guides");
+        });
+
+        let row_spin = SpinButton::with_range(1.0, MAX_DIVISIONS, 1.0);
+        row_spin.set_value(DEFAULT_DIVISIONS);
+        let list = self.clone();
+        let row_value = row_spin.clone();
+        self.append_preset_row("行", &row_spin, "行ガイド", move || {
+            let mut guides = list.guides();
+            guides.extend(row_guides(list.page_size(), row_value.value() as u32));
+            list.replace_guides(guides, "Row Guides");
+        });
+    }
+
+    fn append_preset_row(
+        &self,
+        label: &str,
+        spin: &SpinButton,
+        button_label: &str,
+        apply: impl Fn() + 'static,
+    ) {
+        let row = GtkBox::new(Orientation::Horizontal, 6);
+        let name = Label::new(Some(label));
+        name.set_hexpand(true);
+        name.set_halign(gtk4::Align::Start);
+        row.append(&name);
+        row.append(spin);
+        let apply_btn = Button::with_label(button_label);
+        apply_btn.connect_clicked(move |_| apply());
+        row.append(&apply_btn);
+        self.list_box.append(&row);
+    }
+
+    /// Row with the orientation, an editable position and a delete button
+    fn build_guide_row(
+        &self,
+        index: usize,
+        guide: &Guide,
+        unit: RulerUnit,
+        error_label: &Label,
+    ) -> GtkBox {
+        let row = GtkBox::new(Orientation::Horizontal, 6);
+        let name = Label::new(Some(orientation_label(guide.orientation)));
+        name.set_width_chars(4);
+        row.append(&name);
+
+        let entry = Entry::new();
+        entry.set_text(&format_guide_position(guide.position, unit));
+        entry.set_width_chars(8);
+        entry.set_hexpand(true);
+        entry.set_tooltip_text(Some("Enter で位置を更新"));
+        row.append(&entry);
+        row.append(&Label::new(Some(unit.label())));
+
+        let list = self.clone();
+        let orientation = guide.orientation;
+        let error_label = error_label.clone();
+        entry.connect_activate(move |entry| {
+            let extent = guide_extent(list.page_size(), orientation);
+            match parse_guide_position(&entry.text(), unit, extent) {
+                Ok(position) => {
+                    let mut guides = list.guides();
+                    if let Some(guide) = guides.get_mut(index) {
+                        guide.position = position;
+                    }
+                    list.replace_guides(guides, "Move Guide");
+                }
+                Err(message) => {
+                    error_label.set_text(&message);
+                    error_label.set_visible(true);
+                }
+            }
+        });
+
+        let delete_btn = Button::with_label("🗑");
+        delete_btn.add_css_class("flat");
+        delete_btn.set_tooltip_text(Some("ガイドを削除"));
+        let list = self.clone();
+        delete_btn.connect_clicked(move |_| {
+            let mut guides = list.guides();
+            if index < guides.len() {
+                guides.remove(index);
+            }
+            list.replace_guides(guides, "Delete Guide");
+        });
+        row.append(&delete_btn);
+
+        row
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_guide_position_converts_units() {
+        assert_eq!(
+            parse_guide_position("120", RulerUnit::Pixels, 600.0),
+            Ok(120.0)
+        );
+        let mm = parse_guide_position(" 25.4 mm", RulerUnit::Millimeters, 600.0).unwrap();
+        assert!((mm - 96.0).abs() < 1e-3);
+        let pt = parse_guide_position("72", RulerUnit::Points, 600.0).unwrap();
+        assert!((pt - 96.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_parse_guide_position_rejects_off_page_values() {
+        assert!(parse_guide_position("abc", RulerUnit::Pixels, 600.0).is_err());
+        assert!(parse_guide_position("-1", RulerUnit::Pixels, 600.0).is_err());
+        assert!(parse_guide_position("601", RulerUnit::Pixels, 600.0).is_err());
+        assert!(parse_guide_position("600", RulerUnit::Pixels, 600.0).is_ok());
+        // 600px is about 158.75mm
+        assert!(parse_guide_position("160", RulerUnit::Millimeters, 600.0).is_err());
+    }
+
+    #[test]
+    fn test_presets_follow_page_size() {
+        let page = Size::new(600.0, 800.0);
+
+        let margins = margin_guides(page, 50.0);
+        let positions: Vec<f32> = margins.iter().map(|guide| guide.position).collect();
+        assert_eq!(positions, vec![50.0, 550.0, 50.0, 750.0]);
+
+        let columns = column_guides(page, 3);
+        assert_eq!(columns.len(), 2);
+        assert!(columns
+            .iter()
+            .all(|guide| guide.orientation == GuideOrientation::Vertical));
+        assert_eq!(columns[0].position, 200.0);
+        assert_eq!(columns[1].position, 400.0);
+
+        let rows = row_guides(page, 4);
+        let positions: Vec<f32> = rows.iter().map(|guide| guide.position).collect();
+        assert_eq!(positions, vec![200.0, 400.0, 600.0]);
+        assert!(row_guides(page, 1).is_empty());
+    }
+
+    #[test]
+    fn test_format_guide_position_trims_zeros() {
+        assert_eq!(format_guide_position(96.0, RulerUnit::Millimeters), "25.4");
+        assert_eq!(format_guide_position(100.0, RulerUnit::Pixels), "100");
+    }
+}
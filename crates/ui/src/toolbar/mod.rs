@@ -3,12 +3,15 @@
 //! Provides builders for the primary and secondary toolbars with all controls
 //! organized by functional groups.
 
+pub mod guide_list;
+pub mod selection_state;
 pub mod toolbar_shapes;
 
+use crate::canvas::rendering::{GridConfig, MAX_GRID_SPACING, MIN_GRID_SPACING};
 use gtk4::prelude::*;
 use gtk4::{
-    Box as GtkBox, Button, Label, MenuButton, Orientation, Popover, ScrolledWindow, Separator,
-    ToggleButton,
+    Adjustment, Box as GtkBox, Button, Label, MenuButton, Orientation, Popover, ScrolledWindow,
+    Separator, SpinButton, ToggleButton,
 };
 
 /// Container for all toolbar-related widgets
@@ -58,14 +61,18 @@ pub struct ToolbarButtons {
     pub zoom_out_btn: Button,
     pub zoom_100_btn: Button,
     pub zoom_in_btn: Button,
+    pub zoom_fit_btn: Button,
 
     // Page info label (Primary toolbar)
     pub page_info_label: Label,
 
     // View popover toggles
     pub ruler_menu_toggle: ToggleButton,
+    pub ruler_unit_btn: Button,
     pub guides_menu_toggle: ToggleButton,
     pub snap_to_guides_toggle: ToggleButton,
+    pub snap_to_objects_toggle: ToggleButton,
+    pub grid_spacing_spin: SpinButton,
     pub guide_list_menu_btn: Button,
 
     // Object operations (Secondary toolbar)
@@ -81,6 +88,24 @@ pub struct ToolbarButtons {
     pub snap_to_guides_btn: ToggleButton,
 }
 
+/// Format the "active / total" page indicator text
+pub fn format_page_info(active_index: usize, page_count: usize) -> String {
+    format!("{} / {}", active_index + 1, page_count.max(1))
+}
+
+/// Refresh the page indicator label from the current application state
+pub fn update_page_info_label(label: &Label, app_state: &crate::app::AppState) {
+    label.set_text(&format_page_info(
+        app_state.active_page_index(),
+        app_state.page_count(),
+    ));
+}
+
+/// Label for the ruler unit button in the view popover
+pub fn ruler_unit_label(unit: crate::canvas::rendering::RulerUnit) -> String {
+    format!("ルーラー単位: {}", unit.label())
+}
+
 /// Public build function for toolbar
 pub fn build_toolbar() -> ToolbarWidgets {
     ToolbarBuilder::build()
@@ -112,10 +137,14 @@ impl ToolbarBuilder {
             zoom_out_btn: primary_buttons.zoom_out_btn,
             zoom_100_btn: primary_buttons.zoom_100_btn,
             zoom_in_btn: primary_buttons.zoom_in_btn,
+            zoom_fit_btn: primary_buttons.zoom_fit_btn,
             page_info_label: primary_buttons.page_info_label,
             ruler_menu_toggle: primary_buttons.ruler_menu_toggle,
+            ruler_unit_btn: primary_buttons.ruler_unit_btn,
             guides_menu_toggle: primary_buttons.guides_menu_toggle,
             snap_to_guides_toggle: primary_buttons.snap_to_guides_toggle,
+            snap_to_objects_toggle: primary_buttons.snap_to_objects_toggle,
+            grid_spacing_spin: primary_buttons.grid_spacing_spin,
             guide_list_menu_btn: primary_buttons.guide_list_menu_btn,
             group_btn: secondary_buttons.group_btn,
             ungroup_btn: secondary_buttons.ungroup_btn,
@@ -269,6 +298,12 @@ impl ToolbarBuilder {
         ruler_menu_toggle.set_active(true);
         view_popover_box.append(&ruler_menu_toggle);
 
+        let ruler_unit_btn = Button::with_label(&ruler_unit_label(Default::default()));
+        ruler_unit_btn.add_css_class("flat");
+        ruler_unit_btn.set_halign(gtk4::Align::Start);
+        ruler_unit_btn.set_tooltip_text(Some("ルーラーの単位を切り替え (px → mm → pt)"));
+        view_popover_box.append(&ruler_unit_btn);
+
         let guides_menu_toggle = ToggleButton::with_label("ガイド");
         guides_menu_toggle.add_css_class("flat");
         guides_menu_toggle.set_active(true);
@@ -279,6 +314,26 @@ impl ToolbarBuilder {
         snap_to_guides_toggle.set_active(true);
         view_popover_box.append(&snap_to_guides_toggle);
 
+        let snap_to_objects_toggle = ToggleButton::with_label("オブジェクトにスナップ");
+        snap_to_objects_toggle.add_css_class("flat");
+        snap_to_objects_toggle.set_active(true);
+        view_popover_box.append(&snap_to_objects_toggle);
+
+        let grid_spacing_box = GtkBox::new(Orientation::Horizontal, 8);
+        grid_spacing_box.append(&Label::new(Some("グリッド間隔 (px)")));
+        let grid_spacing_adj = Adjustment::new(
+            GridConfig::default().spacing as f64,
+            MIN_GRID_SPACING as f64,
+            MAX_GRID_SPACING as f64,
+            1.0,
+            10.0,
+            0.0,
+        );
+        let grid_spacing_spin = SpinButton::new(Some(&grid_spacing_adj), 1.0, 0);
+        grid_spacing_spin.set_tooltip_text(Some("グリッドとグリッドスナップの間隔"));
+        grid_spacing_box.append(&grid_spacing_spin);
+        view_popover_box.append(&grid_spacing_box);
+
         let guide_list_popover = Popover::new();
         let guide_list_box = GtkBox::new(Orientation::Vertical, 8);
         let guide_list_scroller = ScrolledWindow::new();
@@ -330,6 +385,11 @@ impl ToolbarBuilder {
         zoom_in_btn.set_tooltip_text(Some("ズームイン (Ctrl+=)"));
         zoom_box.append(&zoom_in_btn);
 
+        let zoom_fit_btn = Button::with_label("⤢");
+        zoom_fit_btn.add_css_class("flat");
+        zoom_fit_btn.set_tooltip_text(Some("ウィンドウに合わせる (Ctrl+1)"));
+        zoom_box.append(&zoom_fit_btn);
+
         primary_toolbar.append(&zoom_box);
 
         let page_info_label = Label::new(Some("1 / 1"));
@@ -356,10 +416,14 @@ impl ToolbarBuilder {
                 zoom_out_btn,
                 zoom_100_btn,
                 zoom_in_btn,
+                zoom_fit_btn,
                 page_info_label,
                 ruler_menu_toggle,
+                ruler_unit_btn,
                 guides_menu_toggle,
                 snap_to_guides_toggle,
+                snap_to_objects_toggle,
+                grid_spacing_spin,
                 guide_list_menu_btn,
                 view_popover,
                 guide_list_popover,
@@ -387,21 +451,25 @@ impl ToolbarBuilder {
         let group_btn = Button::with_label("グループ");
         group_btn.add_css_class("flat");
         group_btn.set_tooltip_text(Some("グループ化 (Alt+G)"));
+        group_btn.set_sensitive(false);
         ops_box.append(&group_btn);
 
         let ungroup_btn = Button::with_label("グループ解除");
         ungroup_btn.add_css_class("flat");
         ungroup_btn.set_tooltip_text(Some("グループ解除"));
+        ungroup_btn.set_sensitive(false);
         ops_box.append(&ungroup_btn);
 
         let lock_btn = Button::with_label("ロック");
         lock_btn.add_css_class("flat");
         lock_btn.set_tooltip_text(Some("選択オブジェクトをロック"));
+        lock_btn.set_sensitive(false);
         ops_box.append(&lock_btn);
 
         let unlock_btn = Button::with_label("ロック解除");
         unlock_btn.add_css_class("flat");
         unlock_btn.set_tooltip_text(Some("ロック解除"));
+        unlock_btn.set_sensitive(false);
         ops_box.append(&unlock_btn);
 
         secondary_toolbar.append(&ops_box);
@@ -480,10 +548,14 @@ struct PrimaryToolbarButtons {
     zoom_out_btn: Button,
     zoom_100_btn: Button,
     zoom_in_btn: Button,
+    zoom_fit_btn: Button,
     page_info_label: Label,
     ruler_menu_toggle: ToggleButton,
+    ruler_unit_btn: Button,
     guides_menu_toggle: ToggleButton,
     snap_to_guides_toggle: ToggleButton,
+    snap_to_objects_toggle: ToggleButton,
+    grid_spacing_spin: SpinButton,
     guide_list_menu_btn: Button,
     view_popover: Popover,
     guide_list_popover: Popover,
@@ -0,0 +1,132 @@
+//! Selection-aware enablement for object operation buttons
+//!
+//! Decides which of group/ungroup/lock/unlock apply to the current selection
+//! so the toolbar can disable buttons that would do nothing.
+
+use super::ToolbarButtons;
+use gtk4::prelude::*;
+use testruct_core::document::DocumentElement;
+
+/// Which object operations are available for a selection
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ObjectActionState {
+    /// Two or more objects selected
+    pub can_group: bool,
+    /// At least one selected object is a group (frame with children)
+    pub can_ungroup: bool,
+    /// At least one selected object is unlocked
+    pub can_lock: bool,
+    /// At least one selected object is locked
+    pub can_unlock: bool,
+}
+
+impl ObjectActionState {
+    /// Compute availability from the page elements and the selected ids
+    pub fn from_selection(elements: &[DocumentElement], selected_ids: &[uuid::Uuid]) -> Self {
+        let mut state = Self {
+            can_group: selected_ids.len() >= 2,
+            ..Default::default()
+        };
+
+        for element in elements {
+            if !selected_ids.contains(&element.id()) {
+                continue;
+            }
+            if let DocumentElement::Frame(frame) = element {
+                if !frame.children.is_empty() {
+                    state.can_ungroup = true;
+                }
+            }
+            if element.is_locked() {
+                state.can_unlock = true;
+            } else {
+                state.can_lock = true;
+            }
+        }
+
+        state
+    }
+
+    /// Apply the availability to the toolbar buttons
+    pub fn apply(&self, buttons: &ToolbarButtons) {
+        buttons.group_btn.set_sensitive(self.can_group);
+        buttons.ungroup_btn.set_sensitive(self.can_ungroup);
+        buttons.lock_btn.set_sensitive(self.can_lock);
+        buttons.unlock_btn.set_sensitive(self.can_unlock);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use testruct_core::document::{FrameElement, ShapeElement, ShapeKind};
+    use testruct_core::layout::{Point, Rect, Size};
+
+    fn bounds() -> Rect {
+        Rect::new(Point::new(0.0, 0.0), Size::new(10.0, 10.0))
+    }
+
+    fn shape(locked: bool) -> DocumentElement {
+        DocumentElement::Shape(ShapeElement {
+            id: uuid::Uuid::new_v4(),
+            kind: ShapeKind::Rectangle,
+            bounds: bounds(),
+            stroke: None,
+            stroke_width: 1.0,
+            fill: None,
+            visible: true,
+            locked,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
+        })
+    }
+
+    fn group(children: Vec<DocumentElement>) -> DocumentElement {
+        DocumentElement::Frame(FrameElement {
+            id: uuid::Uuid::new_v4(),
+            bounds: bounds(),
+            children,
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+        })
+    }
+
+    #[test]
+    fn test_empty_selection_disables_everything() {
+        let elements = vec![shape(false)];
+        let state = ObjectActionState::from_selection(&elements, &[]);
+        assert_eq!(state, ObjectActionState::default());
+    }
+
+    #[test]
+    fn test_single_unlocked_shape() {
+        let elements = vec![shape(false)];
+        let state = ObjectActionState::from_selection(&elements, &[elements[0].id()]);
+        assert!(!state.can_group);
+        assert!(!state.can_ungroup);
+        assert!(state.can_lock);
+        assert!(!state.can_unlock);
+    }
+
+    #[test]
+    fn test_two_shapes_with_mixed_lock_state() {
+        let elements = vec![shape(false), shape(true)];
+        let ids: Vec<_> = elements.iter().map(|e| e.id()).collect();
+        let state = ObjectActionState::from_selection(&elements, &ids);
+        assert!(state.can_group);
+        assert!(state.can_lock);
+        assert!(state.can_unlock);
+    }
+
+    #[test]
+    fn test_group_selected_enables_ungroup() {
+        let elements = vec![group(vec![shape(false), shape(false)])];
+        let state = ObjectActionState::from_selection(&elements, &[elements[0].id()]);
+        assert!(state.can_ungroup);
+        assert!(!state.can_group);
+    }
+}
@@ -1,6 +1,6 @@
 //! Tool selection controls for the toolbar
 //!
-//! Provides buttons for selecting canvas tools (Select, Rectangle, Circle, Text).
+//! Provides buttons for selecting canvas tools (Select, Rectangle, Circle, Polygon, Text).
 
 use crate::canvas::tools::ToolMode;
 use gtk4::prelude::*;
@@ -13,6 +13,7 @@ pub struct ToolButtons {
     pub circle_btn: ToggleButton,
     pub line_btn: ToggleButton,
     pub arrow_btn: ToggleButton,
+    pub polygon_btn: ToggleButton,
     pub image_btn: ToggleButton,
     pub text_btn: ToggleButton,
 }
@@ -26,6 +27,7 @@ impl ToolButtons {
             circle_btn: ToggleButton::with_label("Circle"),
             line_btn: ToggleButton::with_label("Line"),
             arrow_btn: ToggleButton::with_label("Arrow"),
+            polygon_btn: ToggleButton::with_label("Polygon"),
             image_btn: ToggleButton::with_label("Image"),
             text_btn: ToggleButton::with_label("Text"),
         }
@@ -49,6 +51,8 @@ impl ToolButtons {
             ToolMode::Line
         } else if self.arrow_btn.is_active() {
             ToolMode::Arrow
+        } else if self.polygon_btn.is_active() {
+            ToolMode::Polygon
         } else if self.image_btn.is_active() {
             ToolMode::Image
         } else if self.text_btn.is_active() {
@@ -66,6 +70,7 @@ impl ToolButtons {
         self.circle_btn.set_active(false);
         self.line_btn.set_active(false);
         self.arrow_btn.set_active(false);
+        self.polygon_btn.set_active(false);
         self.image_btn.set_active(false);
         self.text_btn.set_active(false);
 
@@ -76,9 +81,11 @@ impl ToolButtons {
             ToolMode::Circle => self.circle_btn.set_active(true),
             ToolMode::Line => self.line_btn.set_active(true),
             ToolMode::Arrow => self.arrow_btn.set_active(true),
+            ToolMode::Polygon => self.polygon_btn.set_active(true),
             ToolMode::Image => self.image_btn.set_active(true),
             ToolMode::Text => self.text_btn.set_active(true),
-            ToolMode::Pan => {} // Pan is not a selectable tool button
+            // Pan, Eyedropper and Measure are not selectable tool buttons here
+            ToolMode::Pan | ToolMode::Eyedropper | ToolMode::Measure => {}
         }
     }
 }
@@ -128,6 +135,9 @@ pub fn build_tool_section(container: &GtkBox) -> ToolButtons {
     buttons.arrow_btn.add_css_class("flat");
     tool_row2.append(&buttons.arrow_btn);
 
+    buttons.polygon_btn.add_css_class("flat");
+    tool_row2.append(&buttons.polygon_btn);
+
     buttons.image_btn.add_css_class("flat");
     tool_row2.append(&buttons.image_btn);
 
@@ -3,10 +3,16 @@
 //! These commands work directly with AppState instead of Arc<Mutex<Document>>,
 //! making them compatible with the application's architecture.
 
-use super::Command;
+use super::{elements_memory_usage, Command};
 use crate::app::AppState;
-use testruct_core::document::DocumentElement;
-use testruct_core::typography::Color;
+use crate::canvas::rendering::fit_text_height;
+use std::ops::Range;
+use testruct_core::document::{
+    DocumentElement, Fill, ImageFit, Layer, LineStyle, Page, PageId, QrErrorCorrection,
+    SearchOptions, ShapeKind, TextPath, ZOrderChange,
+};
+use testruct_core::layout::Insets;
+use testruct_core::typography::{Color, RichText};
 use uuid::Uuid;
 
 /// Delete command that works with AppState
@@ -85,6 +91,10 @@ impl Command for AppDeleteCommand {
     fn description(&self) -> &str {
         "Delete"
     }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of_val(self) + elements_memory_usage(&self.deleted_elements)
+    }
 }
 
 impl std::fmt::Debug for AppDeleteCommand {
@@ -158,6 +168,14 @@ impl AppMoveCommand {
                             group.bounds.origin.x += dx;
                             group.bounds.origin.y += dy;
                         }
+                        DocumentElement::Table(table) => {
+                            table.bounds.origin.x += dx;
+                            table.bounds.origin.y += dy;
+                        }
+                        DocumentElement::QrCode(qr) => {
+                            qr.bounds.origin.x += dx;
+                            qr.bounds.origin.y += dy;
+                        }
                     }
                     moved_count += 1;
                 }
@@ -224,7 +242,7 @@ impl Command for AppCreateCommand {
 
             self.app_state.with_mutable_active_document(|doc| {
                 if page_index < doc.pages.len() {
-                    doc.pages[page_index].add_element(element);
+                    doc.pages[page_index].add_element_to_active_layer(element);
                 }
             });
 
@@ -262,6 +280,10 @@ impl Command for AppCreateCommand {
     fn description(&self) -> &str {
         "Create"
     }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of_val(self) + elements_memory_usage(&self.element)
+    }
 }
 
 impl std::fmt::Debug for AppCreateCommand {
@@ -274,12 +296,16 @@ impl std::fmt::Debug for AppCreateCommand {
 }
 
 /// Group command that works with AppState
+///
+/// The selected top-level elements are moved as they are into a frame used as
+/// the group container, so groups among them stay nested.
 pub struct AppGroupCommand {
     app_state: AppState,
     group_id: Uuid,
     element_ids: Vec<Uuid>,
     page_index: usize,
-    grouped_elements: Vec<DocumentElement>,
+    order_before: Vec<Uuid>,
+    layers_before: Vec<Layer>,
 }
 
 impl AppGroupCommand {
@@ -290,111 +316,62 @@ impl AppGroupCommand {
             group_id: Uuid::new_v4(),
             element_ids,
             page_index,
-            grouped_elements: Vec::new(),
+            order_before: Vec::new(),
+            layers_before: Vec::new(),
         }
     }
+
+    /// Id of the group container created by this command
+    pub fn group_id(&self) -> Uuid {
+        self.group_id
+    }
 }
 
 impl Command for AppGroupCommand {
     fn execute(&mut self) -> Result<String, String> {
-        use testruct_core::document::FrameElement;
-        use testruct_core::layout::{Point, Rect, Size};
-
-        let element_ids = self.element_ids.clone();
         let page_index = self.page_index;
         let group_id = self.group_id;
-        let mut bounds = Rect {
-            origin: Point { x: 0.0, y: 0.0 },
-            size: Size { width: 0.0, height: 0.0 },
-        };
-        let mut first = true;
-
-        self.app_state.with_mutable_active_document(|doc| {
-            if page_index >= doc.pages.len() {
-                return;
-            }
-
-            let page = &mut doc.pages[page_index];
-
-            // Calculate combined bounds
-            for element in page.elements.iter() {
-                if element_ids.contains(&element.id()) {
-                    let elem_bounds = match element {
-                        DocumentElement::Shape(s) => &s.bounds,
-                        DocumentElement::Text(t) => &t.bounds,
-                        DocumentElement::Image(i) => &i.bounds,
-                        DocumentElement::Frame(f) => &f.bounds,
-                        DocumentElement::Group(g) => &g.bounds,
-                    };
-                    if first {
-                        bounds = *elem_bounds;
-                        first = false;
-                    } else {
-                        let min_x = bounds.origin.x.min(elem_bounds.origin.x);
-                        let min_y = bounds.origin.y.min(elem_bounds.origin.y);
-                        let max_x = (bounds.origin.x + bounds.size.width)
-                            .max(elem_bounds.origin.x + elem_bounds.size.width);
-                        let max_y = (bounds.origin.y + bounds.size.height)
-                            .max(elem_bounds.origin.y + elem_bounds.size.height);
-                        bounds = Rect {
-                            origin: Point { x: min_x, y: min_y },
-                            size: Size { width: max_x - min_x, height: max_y - min_y },
-                        };
-                    }
-                }
-            }
-
-            // Remove elements (from end to start) and preserve original order
-            let mut indices: Vec<usize> = page.elements.iter()
-                .enumerate()
-                .filter_map(|(i, e)| if element_ids.contains(&e.id()) { Some(i) } else { None })
-                .collect();
-            indices.sort_by(|a, b| b.cmp(a));
-
-            for idx in indices {
-                self.grouped_elements.push(page.elements.remove(idx));
-            }
-            // Reverse to maintain original order
-            self.grouped_elements.reverse();
-
-            // Create and add frame (used as group container)
-            let frame = FrameElement {
-                id: group_id,
-                bounds,
-                children: self.grouped_elements.clone(),
-                visible: true,
-                locked: false,
-            };
-            page.add_element(DocumentElement::Frame(frame));
-        });
+        let element_ids = self.element_ids.clone();
 
+        let before = self
+            .app_state
+            .with_mutable_active_document(|doc| {
+                let page = doc.pages.get_mut(page_index)?;
+                let before = (page.element_order(), page.layers.clone());
+                page.group_elements(group_id, &element_ids).then_some(before)
+            })
+            .flatten();
+
+        let Some((order, layers)) = before else {
+            return Err("No elements to group".to_string());
+        };
+        self.order_before = order;
+        self.layers_before = layers;
         Ok(format!("Grouped {} elements", self.element_ids.len()))
     }
 
     fn undo(&mut self) -> Result<String, String> {
         let page_index = self.page_index;
         let group_id = self.group_id;
-
-        self.app_state.with_mutable_active_document(|doc| {
-            if page_index >= doc.pages.len() {
-                return;
-            }
-
-            let page = &mut doc.pages[page_index];
-
-            // Find and remove the frame (group container)
-            if let Some(pos) = page.elements.iter().position(|e| e.id() == group_id) {
-                if let DocumentElement::Frame(frame) = page.elements.remove(pos) {
-                    // Restore children
-                    for child in frame.children {
-                        page.add_element(child);
-                    }
-                }
-            }
-        });
-
-        self.grouped_elements.clear();
-        Ok("Ungrouped elements".to_string())
+        let order = std::mem::take(&mut self.order_before);
+        let layers = std::mem::take(&mut self.layers_before);
+
+        let restored = self
+            .app_state
+            .with_mutable_active_document(|doc| {
+                let page = doc.pages.get_mut(page_index)?;
+                page.ungroup(group_id)?;
+                // Put the children back where they were before grouping
+                page.restore_element_order(&order);
+                page.layers = layers;
+                Some(())
+            })
+            .flatten();
+
+        match restored {
+            Some(()) => Ok("Ungrouped elements".to_string()),
+            None => Err("Group not found".to_string()),
+        }
     }
 
     fn description(&self) -> &str {
@@ -412,11 +389,14 @@ impl std::fmt::Debug for AppGroupCommand {
 }
 
 /// Ungroup command that works with AppState
+///
+/// Only one level is dissolved: groups inside the ungrouped one are kept.
 pub struct AppUngroupCommand {
     app_state: AppState,
     group_id: Uuid,
     page_index: usize,
     group_element: Option<DocumentElement>,
+    layers_before: Vec<Layer>,
     child_ids: Vec<Uuid>,
 }
 
@@ -428,6 +408,7 @@ impl AppUngroupCommand {
             group_id,
             page_index,
             group_element: None,
+            layers_before: Vec::new(),
             child_ids: Vec::new(),
         }
     }
@@ -438,77 +419,376 @@ impl Command for AppUngroupCommand {
         let page_index = self.page_index;
         let group_id = self.group_id;
 
+        let ungrouped = self
+            .app_state
+            .with_mutable_active_document(|doc| {
+                let page = doc.pages.get_mut(page_index)?;
+                let index = page.z_order(group_id)?;
+                let group = page.elements[index].clone();
+                let layers = page.layers.clone();
+                page.ungroup(group_id).map(|child_ids| (group, layers, child_ids))
+            })
+            .flatten();
+
+        let Some((group, layers, child_ids)) = ungrouped else {
+            return Err("Group not found".to_string());
+        };
+        self.group_element = Some(group);
+        self.layers_before = layers;
+        self.child_ids = child_ids;
+        Ok(format!("Ungrouped {} elements", self.child_ids.len()))
+    }
+
+    fn undo(&mut self) -> Result<String, String> {
+        let page_index = self.page_index;
+        let child_ids = self.child_ids.clone();
+        let layers = std::mem::take(&mut self.layers_before);
+        let Some(group) = self.group_element.take() else {
+            return Err("No group to restore".to_string());
+        };
+
         self.app_state.with_mutable_active_document(|doc| {
-            if page_index >= doc.pages.len() {
-                return;
+            if let Some(page) = doc.pages.get_mut(page_index) {
+                // Put the group back where its first child sits
+                let index = page
+                    .elements
+                    .iter()
+                    .position(|e| child_ids.contains(&e.id()))
+                    .unwrap_or(page.elements.len());
+                page.elements.retain(|e| !child_ids.contains(&e.id()));
+                page.elements.insert(index, group);
+                page.layers = layers;
             }
+        });
 
-            let page = &mut doc.pages[page_index];
+        Ok("Re-grouped elements".to_string())
+    }
 
-            // Find and remove the frame (group container)
-            if let Some(pos) = page.elements.iter().position(|e| e.id() == group_id) {
-                let group_elem = page.elements.remove(pos);
-                if let DocumentElement::Frame(frame) = &group_elem {
-                    // Store child IDs for undo
-                    self.child_ids = frame.children.iter().map(|c| c.id()).collect();
+    fn description(&self) -> &str {
+        "Ungroup"
+    }
 
-                    // Add children back to page
-                    for child in frame.children.clone() {
-                        page.add_element(child);
-                    }
-                }
-                self.group_element = Some(group_elem);
-            }
-        });
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of_val(self) + elements_memory_usage(&self.group_element)
+    }
+}
 
-        Ok(format!("Ungrouped {} elements", self.child_ids.len()))
+impl std::fmt::Debug for AppUngroupCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AppUngroupCommand")
+            .field("group_id", &self.group_id)
+            .finish()
     }
+}
 
-    fn undo(&mut self) -> Result<String, String> {
+/// Flatten command that permanently dissolves a group into its leaf elements
+///
+/// Unlike ungroup, nested groups are dissolved too and the group's opacity,
+/// visibility and lock state are baked into the children.
+pub struct AppFlattenGroupCommand {
+    app_state: AppState,
+    group_id: Uuid,
+    page_index: usize,
+    group_element: Option<DocumentElement>,
+    layers_before: Vec<Layer>,
+    child_ids: Vec<Uuid>,
+}
+
+impl AppFlattenGroupCommand {
+    /// Create a new flatten command
+    pub fn new(app_state: AppState, group_id: Uuid, page_index: usize) -> Self {
+        Self {
+            app_state,
+            group_id,
+            page_index,
+            group_element: None,
+            layers_before: Vec::new(),
+            child_ids: Vec::new(),
+        }
+    }
+}
+
+impl Command for AppFlattenGroupCommand {
+    fn execute(&mut self) -> Result<String, String> {
         let page_index = self.page_index;
-        let child_ids = self.child_ids.clone();
+        let group_id = self.group_id;
 
-        if let Some(group_elem) = self.group_element.take() {
-            self.app_state.with_mutable_active_document(|doc| {
-                if page_index >= doc.pages.len() {
-                    return;
-                }
+        let flattened = self
+            .app_state
+            .with_mutable_active_document(|doc| {
+                let page = doc.pages.get_mut(page_index)?;
+                let index = page.z_order(group_id)?;
+                let group = page.elements[index].clone();
+                let layers = page.layers.clone();
+                page.flatten_group(group_id).map(|child_ids| (group, layers, child_ids))
+            })
+            .flatten();
+
+        let Some((group, layers, child_ids)) = flattened else {
+            return Err("Group not found".to_string());
+        };
+        self.group_element = Some(group);
+        self.layers_before = layers;
+        self.child_ids = child_ids;
+        Ok(format!("Flattened group into {} elements", self.child_ids.len()))
+    }
 
-                let page = &mut doc.pages[page_index];
+    fn undo(&mut self) -> Result<String, String> {
+        let page_index = self.page_index;
+        let child_ids = self.child_ids.clone();
+        let layers = std::mem::take(&mut self.layers_before);
+        let Some(group) = self.group_element.take() else {
+            return Err("No group to restore".to_string());
+        };
 
-                // Remove children
+        self.app_state.with_mutable_active_document(|doc| {
+            if let Some(page) = doc.pages.get_mut(page_index) {
+                // Put the group back where its first child sits
+                let index = page
+                    .elements
+                    .iter()
+                    .position(|e| child_ids.contains(&e.id()))
+                    .unwrap_or(page.elements.len());
                 page.elements.retain(|e| !child_ids.contains(&e.id()));
+                page.elements.insert(index, group);
+                page.layers = layers;
+            }
+        });
 
-                // Add group back
-                page.add_element(group_elem);
-            });
-
-            Ok("Re-grouped elements".to_string())
-        } else {
-            Err("No group to restore".to_string())
-        }
+        Ok("Restored flattened group".to_string())
     }
 
     fn description(&self) -> &str {
-        "Ungroup"
+        "Flatten group"
+    }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of_val(self) + elements_memory_usage(&self.group_element)
     }
 }
 
-impl std::fmt::Debug for AppUngroupCommand {
+impl std::fmt::Debug for AppFlattenGroupCommand {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("AppUngroupCommand")
+        f.debug_struct("AppFlattenGroupCommand")
             .field("group_id", &self.group_id)
+            .field("child_ids", &self.child_ids)
             .finish()
     }
 }
 
 /// Property value that can be changed and undone
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum PropertyValue {
     StrokeColor(Option<Color>),
-    FillColor(Option<Color>),
+    /// Solid or gradient fill (shapes)
+    Fill(Option<Fill>),
     StrokeWidth(f32),
     AutoResizeHeight(bool),
+    /// Rotation in degrees (shapes, text, images)
+    Rotation(f32),
+    /// Outline dash style (shapes)
+    LineStyle(LineStyle),
+    /// Corner radius (rectangles)
+    CornerRadius(f32),
+    /// Scaling of the image into its bounds (images)
+    ImageFit(ImageFit),
+    /// Opacity from 0.0 to 1.0 (all elements)
+    Opacity(f32),
+    /// Whether the element is drawn, hit-tested and exported (all elements)
+    Visible(bool),
+    /// Font size in points (text)
+    FontSize(f32),
+    /// Line height multiplier (text)
+    LineHeight(f32),
+    /// Extra space between characters (text)
+    LetterSpacing(f32),
+    /// One side of the padding inside the text box (text)
+    TextPadding(PaddingSide, f32),
+    /// Radius of circular text, kept inside the text box (text on a circle)
+    CircleRadius(f32),
+    /// Start angle of circular text in degrees (text on a circle)
+    CircleStartAngle(f32),
+}
+
+/// Side of an [`Insets`] edited by one padding control
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaddingSide {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+
+impl PaddingSide {
+    /// The inset on this side
+    pub fn of(self, insets: &mut Insets) -> &mut f32 {
+        match self {
+            PaddingSide::Top => &mut insets.top,
+            PaddingSide::Right => &mut insets.right,
+            PaddingSide::Bottom => &mut insets.bottom,
+            PaddingSide::Left => &mut insets.left,
+        }
+    }
+}
+
+impl PropertyValue {
+    /// The element's current value of the same property, if it has one
+    pub fn read(&self, element: &DocumentElement) -> Option<PropertyValue> {
+        match (self, element) {
+            (PropertyValue::StrokeColor(_), DocumentElement::Shape(shape)) => {
+                Some(PropertyValue::StrokeColor(shape.stroke))
+            }
+            (PropertyValue::Fill(_), DocumentElement::Shape(shape)) => {
+                Some(PropertyValue::Fill(shape.fill))
+            }
+            (PropertyValue::StrokeWidth(_), DocumentElement::Shape(shape)) => {
+                Some(PropertyValue::StrokeWidth(shape.stroke_width))
+            }
+            (PropertyValue::AutoResizeHeight(_), DocumentElement::Text(text)) => {
+                Some(PropertyValue::AutoResizeHeight(text.auto_resize_height))
+            }
+            (PropertyValue::LineStyle(_), DocumentElement::Shape(shape)) => {
+                Some(PropertyValue::LineStyle(shape.line_style))
+            }
+            (PropertyValue::CornerRadius(_), DocumentElement::Shape(shape))
+                if shape.kind == ShapeKind::Rectangle =>
+            {
+                Some(PropertyValue::CornerRadius(shape.corner_radius))
+            }
+            (PropertyValue::ImageFit(_), DocumentElement::Image(image)) => {
+                Some(PropertyValue::ImageFit(image.fit))
+            }
+            (
+                PropertyValue::Rotation(_),
+                DocumentElement::Shape(_) | DocumentElement::Text(_) | DocumentElement::Image(_),
+            ) => Some(PropertyValue::Rotation(element.rotation())),
+            (PropertyValue::Opacity(_), element) => Some(PropertyValue::Opacity(element.opacity())),
+            (PropertyValue::Visible(_), element) => {
+                Some(PropertyValue::Visible(element.is_visible()))
+            }
+            (PropertyValue::FontSize(_), DocumentElement::Text(text)) => {
+                Some(PropertyValue::FontSize(text.style.font_size))
+            }
+            (PropertyValue::LineHeight(_), DocumentElement::Text(text)) => {
+                Some(PropertyValue::LineHeight(text.style.line_height))
+            }
+            (PropertyValue::LetterSpacing(_), DocumentElement::Text(text)) => {
+                Some(PropertyValue::LetterSpacing(text.style.letter_spacing))
+            }
+            (PropertyValue::TextPadding(side, _), DocumentElement::Text(text)) => {
+                let mut padding = text.padding;
+                Some(PropertyValue::TextPadding(*side, *side.of(&mut padding)))
+            }
+            (PropertyValue::CircleRadius(_), DocumentElement::Text(text)) => match text.text_path {
+                Some(TextPath::Circle { radius, .. }) => Some(PropertyValue::CircleRadius(radius)),
+                _ => None,
+            },
+            (PropertyValue::CircleStartAngle(_), DocumentElement::Text(text)) => {
+                match text.text_path {
+                    Some(TextPath::Circle { start_angle, .. }) => {
+                        Some(PropertyValue::CircleStartAngle(start_angle))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Set the property on `element`, returning whether it applies to it
+    pub fn apply(&self, element: &mut DocumentElement) -> bool {
+        match (self, element) {
+            (PropertyValue::StrokeColor(color), DocumentElement::Shape(shape)) => {
+                shape.stroke = *color;
+            }
+            (PropertyValue::Fill(fill), DocumentElement::Shape(shape)) => {
+                shape.fill = *fill;
+            }
+            (PropertyValue::StrokeWidth(width), DocumentElement::Shape(shape)) => {
+                shape.stroke_width = *width;
+            }
+            (PropertyValue::AutoResizeHeight(auto), DocumentElement::Text(text)) => {
+                text.auto_resize_height = *auto;
+            }
+            (PropertyValue::LineStyle(style), DocumentElement::Shape(shape)) => {
+                shape.line_style = *style;
+            }
+            (PropertyValue::CornerRadius(radius), DocumentElement::Shape(shape))
+                if shape.kind == ShapeKind::Rectangle =>
+            {
+                shape.corner_radius = radius.max(0.0);
+            }
+            (PropertyValue::ImageFit(fit), DocumentElement::Image(image)) => {
+                image.fit = *fit;
+            }
+            (PropertyValue::Rotation(degrees), element) => return element.set_rotation(*degrees),
+            (PropertyValue::Opacity(opacity), element) => element.set_opacity(*opacity),
+            (PropertyValue::Visible(visible), element) => element.set_visible(*visible),
+            (PropertyValue::FontSize(size), DocumentElement::Text(text)) => {
+                text.style.font_size = *size;
+                fit_text_height(text);
+            }
+            (PropertyValue::LineHeight(line_height), DocumentElement::Text(text)) => {
+                text.style.line_height = *line_height;
+                fit_text_height(text);
+            }
+            (PropertyValue::LetterSpacing(spacing), DocumentElement::Text(text)) => {
+                text.style.letter_spacing = *spacing;
+                fit_text_height(text);
+            }
+            (PropertyValue::TextPadding(side, value), DocumentElement::Text(text)) => {
+                *side.of(&mut text.padding) = *value;
+                fit_text_height(text);
+            }
+            (PropertyValue::CircleRadius(value), DocumentElement::Text(text)) => {
+                let max_radius = TextPath::max_circle_radius(&text.bounds);
+                match &mut text.text_path {
+                    Some(TextPath::Circle { radius, .. }) => *radius = value.min(max_radius),
+                    _ => return false,
+                }
+            }
+            (PropertyValue::CircleStartAngle(value), DocumentElement::Text(text)) => {
+                match &mut text.text_path {
+                    Some(TextPath::Circle { start_angle, .. }) => *start_angle = *value,
+                    _ => return false,
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Whether both are the same property with (nearly) the same value
+    ///
+    /// Numbers within 0.001 count as equal, so a spin button echoing a stored
+    /// `f32` back is not mistaken for an edit.
+    pub fn approx_eq(&self, other: &PropertyValue) -> bool {
+        match (self, other) {
+            (PropertyValue::StrokeWidth(a), PropertyValue::StrokeWidth(b))
+            | (PropertyValue::Rotation(a), PropertyValue::Rotation(b))
+            | (PropertyValue::CornerRadius(a), PropertyValue::CornerRadius(b))
+            | (PropertyValue::Opacity(a), PropertyValue::Opacity(b))
+            | (PropertyValue::FontSize(a), PropertyValue::FontSize(b))
+            | (PropertyValue::LineHeight(a), PropertyValue::LineHeight(b))
+            | (PropertyValue::LetterSpacing(a), PropertyValue::LetterSpacing(b))
+            | (PropertyValue::CircleRadius(a), PropertyValue::CircleRadius(b))
+            | (PropertyValue::CircleStartAngle(a), PropertyValue::CircleStartAngle(b)) => {
+                (a - b).abs() < 0.001
+            }
+            (PropertyValue::TextPadding(side, a), PropertyValue::TextPadding(other_side, b)) => {
+                side == other_side && (a - b).abs() < 0.001
+            }
+            (
+                PropertyValue::Fill(Some(Fill::LinearGradient { start, end, angle })),
+                PropertyValue::Fill(Some(Fill::LinearGradient {
+                    start: other_start,
+                    end: other_end,
+                    angle: other_angle,
+                })),
+            ) => start == other_start && end == other_end && (angle - other_angle).abs() < 0.001,
+            _ => self == other,
+        }
+    }
 }
 
 /// Command for changing shape/text properties with undo support
@@ -531,9 +811,22 @@ impl AppPropertyChangeCommand {
     ) -> Self {
         let description_text = match &new_value {
             PropertyValue::StrokeColor(_) => "Change Stroke Color".to_string(),
-            PropertyValue::FillColor(_) => "Change Fill Color".to_string(),
+            PropertyValue::Fill(_) => "Change Fill".to_string(),
             PropertyValue::StrokeWidth(_) => "Change Stroke Width".to_string(),
             PropertyValue::AutoResizeHeight(_) => "Change Auto Resize".to_string(),
+            PropertyValue::Rotation(_) => "Change Rotation".to_string(),
+            PropertyValue::LineStyle(_) => "Change Line Style".to_string(),
+            PropertyValue::CornerRadius(_) => "Change Corner Radius".to_string(),
+            PropertyValue::ImageFit(_) => "Change Image Fit".to_string(),
+            PropertyValue::Opacity(_) => "Change Opacity".to_string(),
+            PropertyValue::Visible(true) => "Show Element".to_string(),
+            PropertyValue::Visible(false) => "Hide Element".to_string(),
+            PropertyValue::FontSize(_) => "Change Font Size".to_string(),
+            PropertyValue::LineHeight(_) => "Change Line Height".to_string(),
+            PropertyValue::LetterSpacing(_) => "Change Letter Spacing".to_string(),
+            PropertyValue::TextPadding(..) => "Change Text Padding".to_string(),
+            PropertyValue::CircleRadius(_) => "Change Circle Radius".to_string(),
+            PropertyValue::CircleStartAngle(_) => "Change Circle Start Angle".to_string(),
         };
 
         Self {
@@ -546,83 +839,84 @@ impl AppPropertyChangeCommand {
         }
     }
 
-    fn capture_old_values(&mut self) {
-        let element_ids = self.element_ids.clone();
-        let page_index = self.page_index;
-        let new_value = self.new_value.clone();
-
-        self.app_state.with_active_document(|doc| {
-            if page_index >= doc.pages.len() {
-                return;
-            }
-
-            let page = &doc.pages[page_index];
+    /// Command for a change already applied to the document
+    ///
+    /// `old_values` are the values from before the change, e.g. from before a
+    /// slider drag, so undoing restores them in one step.
+    pub fn with_old_values(
+        app_state: AppState,
+        page_index: usize,
+        old_values: Vec<(Uuid, PropertyValue)>,
+        new_value: PropertyValue,
+    ) -> Self {
+        let element_ids = old_values.iter().map(|(id, _)| *id).collect();
+        Self {
+            old_values,
+            ..Self::new(app_state, element_ids, page_index, new_value)
+        }
+    }
 
-            for element in &page.elements {
-                if element_ids.contains(&element.id()) {
-                    let old_value = match (&new_value, element) {
-                        (PropertyValue::StrokeColor(_), DocumentElement::Shape(shape)) => {
-                            Some(PropertyValue::StrokeColor(shape.stroke))
-                        }
-                        (PropertyValue::FillColor(_), DocumentElement::Shape(shape)) => {
-                            Some(PropertyValue::FillColor(shape.fill))
-                        }
-                        (PropertyValue::StrokeWidth(_), DocumentElement::Shape(shape)) => {
-                            Some(PropertyValue::StrokeWidth(shape.stroke_width))
-                        }
-                        (PropertyValue::AutoResizeHeight(_), DocumentElement::Text(text)) => {
-                            Some(PropertyValue::AutoResizeHeight(text.auto_resize_height))
-                        }
-                        _ => None,
-                    };
+    /// Current values of `value`'s property for the elements on a page
+    ///
+    /// Elements the property does not apply to are left out.
+    pub fn current_values(
+        app_state: &AppState,
+        element_ids: &[Uuid],
+        page_index: usize,
+        value: &PropertyValue,
+    ) -> Vec<(Uuid, PropertyValue)> {
+        app_state
+            .with_active_document(|doc| {
+                doc.pages
+                    .get(page_index)
+                    .map(|page| {
+                        page.elements
+                            .iter()
+                            .filter(|element| element_ids.contains(&element.id()))
+                            .filter_map(|element| Some((element.id(), value.read(element)?)))
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            })
+            .unwrap_or_default()
+    }
 
-                    if let Some(value) = old_value {
-                        self.old_values.push((element.id(), value));
+    /// Set `value` on the elements of a page without recording a command
+    ///
+    /// Used to preview a change before the command is pushed.
+    pub fn apply_to(
+        app_state: &AppState,
+        element_ids: &[Uuid],
+        page_index: usize,
+        value: &PropertyValue,
+    ) -> bool {
+        app_state
+            .with_mutable_active_document(|doc| {
+                let Some(page) = doc.pages.get_mut(page_index) else {
+                    return false;
+                };
+                let mut changed = false;
+                for element in &mut page.elements {
+                    if element_ids.contains(&element.id()) {
+                        changed |= value.apply(element);
                     }
                 }
-            }
-        });
+                changed
+            })
+            .unwrap_or(false)
     }
 
-    fn apply_value(&self, value: &PropertyValue, element_ids: &[Uuid]) -> bool {
-        let page_index = self.page_index;
-        let value = value.clone();
-        let element_ids = element_ids.to_vec();
-        let mut changed = false;
-
-        self.app_state.with_mutable_active_document(|doc| {
-            if page_index >= doc.pages.len() {
-                return;
-            }
-
-            let page = &mut doc.pages[page_index];
-
-            for element in &mut page.elements {
-                if element_ids.contains(&element.id()) {
-                    match (&value, element) {
-                        (PropertyValue::StrokeColor(color), DocumentElement::Shape(shape)) => {
-                            shape.stroke = *color;
-                            changed = true;
-                        }
-                        (PropertyValue::FillColor(color), DocumentElement::Shape(shape)) => {
-                            shape.fill = *color;
-                            changed = true;
-                        }
-                        (PropertyValue::StrokeWidth(width), DocumentElement::Shape(shape)) => {
-                            shape.stroke_width = *width;
-                            changed = true;
-                        }
-                        (PropertyValue::AutoResizeHeight(auto), DocumentElement::Text(text)) => {
-                            text.auto_resize_height = *auto;
-                            changed = true;
-                        }
-                        _ => {}
-                    }
-                }
-            }
-        });
+    fn capture_old_values(&mut self) {
+        self.old_values = Self::current_values(
+            &self.app_state,
+            &self.element_ids,
+            self.page_index,
+            &self.new_value,
+        );
+    }
 
-        changed
+    fn apply_value(&self, value: &PropertyValue, element_ids: &[Uuid]) -> bool {
+        Self::apply_to(&self.app_state, element_ids, self.page_index, value)
     }
 }
 
@@ -669,50 +963,40 @@ impl std::fmt::Debug for AppPropertyChangeCommand {
     }
 }
 
-/// Command for stroke width change with pre-captured original values
-/// Used for debounced undo where values are already applied to the document
-pub struct AppStrokeWidthCommand {
+/// Command for resize operation with undo support
+pub struct AppResizeCommand {
     app_state: AppState,
+    element_id: Uuid,
     page_index: usize,
-    /// Original stroke widths (element_id, old_width)
-    original_values: Vec<(Uuid, f32)>,
-    /// New stroke width
-    new_width: f32,
-    /// Whether the command has been executed (to handle re-execution after undo)
-    executed: bool,
+    /// Original bounds before resize
+    old_bounds: testruct_core::layout::Rect,
+    /// New bounds after resize
+    new_bounds: testruct_core::layout::Rect,
 }
 
-impl AppStrokeWidthCommand {
-    /// Create a new stroke width command with pre-captured original values
+impl AppResizeCommand {
+    /// Create a new resize command
     pub fn new(
         app_state: AppState,
+        element_id: Uuid,
         page_index: usize,
-        original_values: Vec<(Uuid, f32)>,
-        new_width: f32,
+        old_bounds: testruct_core::layout::Rect,
+        new_bounds: testruct_core::layout::Rect,
     ) -> Self {
         Self {
             app_state,
+            element_id,
             page_index,
-            original_values,
-            new_width,
-            executed: false,
+            old_bounds,
+            new_bounds,
         }
     }
-}
-
-impl Command for AppStrokeWidthCommand {
-    fn execute(&mut self) -> Result<String, String> {
-        // If already executed (initial apply), just mark as executed
-        // The value is already applied to the document
-        if !self.executed {
-            self.executed = true;
-            return Ok("Change Stroke Width".to_string());
-        }
 
-        // Re-execute after undo - apply new width
+    fn apply_bounds(&self, bounds: &testruct_core::layout::Rect) -> bool {
         let page_index = self.page_index;
-        let new_width = self.new_width;
-        let element_ids: Vec<Uuid> = self.original_values.iter().map(|(id, _)| *id).collect();
+        let element_id = self.element_id;
+        let bounds = *bounds;
+        let mut applied = false;
 
         self.app_state.with_mutable_active_document(|doc| {
             if page_index >= doc.pages.len() {
@@ -720,98 +1004,11 @@ impl Command for AppStrokeWidthCommand {
             }
             let page = &mut doc.pages[page_index];
             for element in &mut page.elements {
-                if let DocumentElement::Shape(shape) = element {
-                    if element_ids.contains(&shape.id) {
-                        shape.stroke_width = new_width;
-                    }
-                }
-            }
-        });
-
-        Ok("Change Stroke Width".to_string())
-    }
-
-    fn undo(&mut self) -> Result<String, String> {
-        let page_index = self.page_index;
-        let original_values = self.original_values.clone();
-
-        self.app_state.with_mutable_active_document(|doc| {
-            if page_index >= doc.pages.len() {
-                return;
-            }
-            let page = &mut doc.pages[page_index];
-            for element in &mut page.elements {
-                if let DocumentElement::Shape(shape) = element {
-                    if let Some((_, orig_width)) = original_values.iter().find(|(id, _)| *id == shape.id) {
-                        shape.stroke_width = *orig_width;
-                    }
-                }
-            }
-        });
-
-        Ok("Undo: Change Stroke Width".to_string())
-    }
-
-    fn description(&self) -> &str {
-        "Change Stroke Width"
-    }
-}
-
-impl std::fmt::Debug for AppStrokeWidthCommand {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("AppStrokeWidthCommand")
-            .field("new_width", &self.new_width)
-            .field("element_count", &self.original_values.len())
-            .finish()
-    }
-}
-
-/// Command for resize operation with undo support
-pub struct AppResizeCommand {
-    app_state: AppState,
-    element_id: Uuid,
-    page_index: usize,
-    /// Original bounds before resize
-    old_bounds: testruct_core::layout::Rect,
-    /// New bounds after resize
-    new_bounds: testruct_core::layout::Rect,
-}
-
-impl AppResizeCommand {
-    /// Create a new resize command
-    pub fn new(
-        app_state: AppState,
-        element_id: Uuid,
-        page_index: usize,
-        old_bounds: testruct_core::layout::Rect,
-        new_bounds: testruct_core::layout::Rect,
-    ) -> Self {
-        Self {
-            app_state,
-            element_id,
-            page_index,
-            old_bounds,
-            new_bounds,
-        }
-    }
-
-    fn apply_bounds(&self, bounds: &testruct_core::layout::Rect) -> bool {
-        let page_index = self.page_index;
-        let element_id = self.element_id;
-        let bounds = *bounds;
-        let mut applied = false;
-
-        self.app_state.with_mutable_active_document(|doc| {
-            if page_index >= doc.pages.len() {
-                return;
-            }
-            let page = &mut doc.pages[page_index];
-            for element in &mut page.elements {
-                match element {
-                    DocumentElement::Shape(shape) if shape.id == element_id => {
-                        shape.bounds = bounds;
-                        applied = true;
-                        return;
+                match element {
+                    DocumentElement::Shape(shape) if shape.id == element_id => {
+                        shape.bounds = bounds;
+                        applied = true;
+                        return;
                     }
                     DocumentElement::Text(text) if text.id == element_id => {
                         text.bounds = bounds;
@@ -833,6 +1030,16 @@ impl AppResizeCommand {
                         applied = true;
                         return;
                     }
+                    DocumentElement::Table(table) if table.id == element_id => {
+                        table.bounds = bounds;
+                        applied = true;
+                        return;
+                    }
+                    DocumentElement::QrCode(qr) if qr.id == element_id => {
+                        qr.bounds = bounds;
+                        applied = true;
+                        return;
+                    }
                     _ => {}
                 }
             }
@@ -873,3 +1080,658 @@ impl std::fmt::Debug for AppResizeCommand {
             .finish()
     }
 }
+
+/// Command for replacing the text of one table cell
+pub struct AppTableCellCommand {
+    app_state: AppState,
+    table_id: Uuid,
+    page_index: usize,
+    row: usize,
+    column: usize,
+    new_text: String,
+    /// Cell content before the edit, restored on undo
+    old_content: Option<testruct_core::typography::RichText>,
+}
+
+impl AppTableCellCommand {
+    /// Create a command setting cell `row`, `column` of a table to `new_text`
+    pub fn new(
+        app_state: AppState,
+        table_id: Uuid,
+        page_index: usize,
+        (row, column): (usize, usize),
+        new_text: impl Into<String>,
+    ) -> Self {
+        Self {
+            app_state,
+            table_id,
+            page_index,
+            row,
+            column,
+            new_text: new_text.into(),
+            old_content: None,
+        }
+    }
+
+    /// Run `f` on the table, returning its result if the table was found
+    fn with_table<R>(
+        &self,
+        f: impl FnOnce(&mut testruct_core::document::TableElement) -> R,
+    ) -> Option<R> {
+        let page_index = self.page_index;
+        let table_id = self.table_id;
+        self.app_state
+            .with_mutable_active_document(|doc| {
+                let page = doc.pages.get_mut(page_index)?;
+                page.elements
+                    .iter_mut()
+                    .find_map(|element| match element {
+                        DocumentElement::Table(table) if table.id == table_id => Some(table),
+                        _ => None,
+                    })
+                    .map(f)
+            })
+            .flatten()
+    }
+}
+
+impl Command for AppTableCellCommand {
+    fn execute(&mut self) -> Result<String, String> {
+        let (row, column) = (self.row, self.column);
+        let new_text = self.new_text.clone();
+        let old_content = self
+            .with_table(|table| {
+                let old = table.cell(row, column).cloned();
+                table.set_cell_text(row, column, &new_text);
+                old
+            })
+            .flatten()
+            .ok_or_else(|| format!("Table cell {}, {} not found", row + 1, column + 1))?;
+        self.old_content = Some(old_content);
+        Ok("Edit Table Cell".to_string())
+    }
+
+    fn undo(&mut self) -> Result<String, String> {
+        let Some(old_content) = self.old_content.take() else {
+            return Err("Table cell has not been changed".to_string());
+        };
+        let (row, column) = (self.row, self.column);
+        let restored = self
+            .with_table(|table| match table.cell_mut(row, column) {
+                Some(cell) => {
+                    *cell = old_content;
+                    true
+                }
+                None => false,
+            })
+            .unwrap_or(false);
+        if restored {
+            Ok("Undo: Edit Table Cell".to_string())
+        } else {
+            Err("Failed to restore table cell".to_string())
+        }
+    }
+
+    fn description(&self) -> &str {
+        "Edit Table Cell"
+    }
+}
+
+impl std::fmt::Debug for AppTableCellCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AppTableCellCommand")
+            .field("table_id", &self.table_id)
+            .field("cell", &(self.row, self.column))
+            .field("new_text", &self.new_text)
+            .finish()
+    }
+}
+
+/// Command for changing the payload and error-correction level of a QR code
+pub struct AppQrCodeCommand {
+    app_state: AppState,
+    qr_id: Uuid,
+    page_index: usize,
+    new_payload: String,
+    new_level: QrErrorCorrection,
+    /// Payload and level before the edit, restored on undo
+    old_value: Option<(String, QrErrorCorrection)>,
+}
+
+impl AppQrCodeCommand {
+    /// Create a command setting the payload and level of a QR code
+    pub fn new(
+        app_state: AppState,
+        qr_id: Uuid,
+        page_index: usize,
+        new_payload: impl Into<String>,
+        new_level: QrErrorCorrection,
+    ) -> Self {
+        Self {
+            app_state,
+            qr_id,
+            page_index,
+            new_payload: new_payload.into(),
+            new_level,
+            old_value: None,
+        }
+    }
+
+    /// Replace the QR code's payload and level, returning the previous ones
+    fn replace(
+        &self,
+        payload: String,
+        level: QrErrorCorrection,
+    ) -> Option<(String, QrErrorCorrection)> {
+        let page_index = self.page_index;
+        let qr_id = self.qr_id;
+        self.app_state
+            .with_mutable_active_document(|doc| {
+                let page = doc.pages.get_mut(page_index)?;
+                match page.element_mut(qr_id)? {
+                    DocumentElement::QrCode(qr) => {
+                        let old_payload = std::mem::replace(&mut qr.payload, payload);
+                        let old_level = std::mem::replace(&mut qr.error_correction, level);
+                        Some((old_payload, old_level))
+                    }
+                    _ => None,
+                }
+            })
+            .flatten()
+    }
+}
+
+impl Command for AppQrCodeCommand {
+    fn execute(&mut self) -> Result<String, String> {
+        let old_value = self
+            .replace(self.new_payload.clone(), self.new_level)
+            .ok_or_else(|| format!("QR code {} not found", self.qr_id))?;
+        self.old_value = Some(old_value);
+        Ok("Edit QR Code".to_string())
+    }
+
+    fn undo(&mut self) -> Result<String, String> {
+        let Some((payload, level)) = self.old_value.take() else {
+            return Err("QR code has not been changed".to_string());
+        };
+        self.replace(payload, level)
+            .map(|_| "Undo: Edit QR Code".to_string())
+            .ok_or_else(|| "Failed to restore QR code".to_string())
+    }
+
+    fn description(&self) -> &str {
+        "Edit QR Code"
+    }
+}
+
+impl std::fmt::Debug for AppQrCodeCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AppQrCodeCommand")
+            .field("qr_id", &self.qr_id)
+            .field("new_payload", &self.new_payload)
+            .field("new_level", &self.new_level)
+            .finish()
+    }
+}
+
+/// Command for replacing text in one text element
+///
+/// Find-and-replace pushes one of these per matching element inside an
+/// undo batch, so "Replace All" undoes in a single step. The spelling menu
+/// replaces a single word by its byte range.
+pub struct AppReplaceTextCommand {
+    app_state: AppState,
+    page_index: usize,
+    element_id: Uuid,
+    target: ReplaceTarget,
+    replacement: String,
+    /// Content and rich text before the replacement, restored on undo
+    old_value: Option<(String, Option<RichText>)>,
+}
+
+/// What an [`AppReplaceTextCommand`] replaces
+#[derive(Debug)]
+enum ReplaceTarget {
+    /// Every match of a query
+    Matches {
+        query: String,
+        options: SearchOptions,
+    },
+    /// One byte range of the content
+    Range(Range<usize>),
+}
+
+impl AppReplaceTextCommand {
+    /// Create a command replacing `query` with `replacement` in a text element
+    pub fn new(
+        app_state: AppState,
+        page_index: usize,
+        element_id: Uuid,
+        query: impl Into<String>,
+        replacement: impl Into<String>,
+        options: SearchOptions,
+    ) -> Self {
+        Self {
+            app_state,
+            page_index,
+            element_id,
+            target: ReplaceTarget::Matches {
+                query: query.into(),
+                options,
+            },
+            replacement: replacement.into(),
+            old_value: None,
+        }
+    }
+
+    /// Create a command replacing the content bytes in `range` of a text element
+    pub fn range(
+        app_state: AppState,
+        page_index: usize,
+        element_id: Uuid,
+        range: Range<usize>,
+        replacement: impl Into<String>,
+    ) -> Self {
+        Self {
+            app_state,
+            page_index,
+            element_id,
+            target: ReplaceTarget::Range(range),
+            replacement: replacement.into(),
+            old_value: None,
+        }
+    }
+}
+
+impl Command for AppReplaceTextCommand {
+    fn execute(&mut self) -> Result<String, String> {
+        let page_index = self.page_index;
+        let element_id = self.element_id;
+        let replaced = self
+            .app_state
+            .with_mutable_active_document(|doc| {
+                let text = doc
+                    .pages
+                    .get_mut(page_index)?
+                    .text_element_mut(element_id)?;
+                let old_value = (text.content.clone(), text.rich_text.clone());
+                let count = match &self.target {
+                    ReplaceTarget::Matches { query, options } => {
+                        text.replace_matches(query, &self.replacement, *options)
+                    }
+                    ReplaceTarget::Range(range) => {
+                        usize::from(text.replace_range(range.clone(), &self.replacement))
+                    }
+                };
+                (count > 0).then_some((old_value, count))
+            })
+            .flatten();
+
+        let Some((old_value, count)) = replaced else {
+            return Err(format!(
+                "Nothing to replace ({:?}) in {}",
+                self.target, element_id
+            ));
+        };
+        self.old_value = Some(old_value);
+        Ok(format!("Replace Text ({} matches)", count))
+    }
+
+    fn undo(&mut self) -> Result<String, String> {
+        let Some((content, rich_text)) = self.old_value.take() else {
+            return Err("Text has not been replaced".to_string());
+        };
+        let page_index = self.page_index;
+        let element_id = self.element_id;
+        self.app_state
+            .with_mutable_active_document(|doc| {
+                let text = doc
+                    .pages
+                    .get_mut(page_index)?
+                    .text_element_mut(element_id)?;
+                text.content = content;
+                text.rich_text = rich_text;
+                Some(())
+            })
+            .flatten()
+            .map(|_| "Undo: Replace Text".to_string())
+            .ok_or_else(|| "Failed to restore text".to_string())
+    }
+
+    fn description(&self) -> &str {
+        "Replace Text"
+    }
+}
+
+impl std::fmt::Debug for AppReplaceTextCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AppReplaceTextCommand")
+            .field("element_id", &self.element_id)
+            .field("target", &self.target)
+            .field("replacement", &self.replacement)
+            .finish()
+    }
+}
+
+/// Command for duplicating a page with undo support
+///
+/// The first execution deep-copies the source page (fresh page and element ids);
+/// undo removes the copy and keeps it so redo restores the exact same page.
+pub struct AppDuplicatePageCommand {
+    app_state: AppState,
+    source_page_id: PageId,
+    new_page_id: Option<PageId>,
+    /// Removed duplicate kept for redo
+    removed_page: Option<Page>,
+    /// Index the duplicate occupied before undo
+    removed_index: usize,
+}
+
+impl AppDuplicatePageCommand {
+    /// Create a new duplicate page command
+    pub fn new(app_state: AppState, source_page_id: PageId) -> Self {
+        Self {
+            app_state,
+            source_page_id,
+            new_page_id: None,
+            removed_page: None,
+            removed_index: 0,
+        }
+    }
+
+    /// Id of the duplicated page (available after execution)
+    pub fn new_page_id(&self) -> Option<PageId> {
+        self.new_page_id
+    }
+}
+
+impl Command for AppDuplicatePageCommand {
+    fn execute(&mut self) -> Result<String, String> {
+        let source_page_id = self.source_page_id;
+        let removed_page = self.removed_page.take();
+        let removed_index = self.removed_index;
+
+        let new_id = self
+            .app_state
+            .with_mutable_active_document(|doc| match removed_page {
+                Some(page) => {
+                    let id = page.id;
+                    let index = removed_index.min(doc.pages.len());
+                    doc.pages.insert(index, page);
+                    Some(id)
+                }
+                None => doc.duplicate_page(source_page_id),
+            })
+            .flatten();
+
+        match new_id {
+            Some(id) => {
+                self.new_page_id = Some(id);
+                Ok("Duplicated page".to_string())
+            }
+            None => Err("Source page not found".to_string()),
+        }
+    }
+
+    fn undo(&mut self) -> Result<String, String> {
+        let Some(new_page_id) = self.new_page_id else {
+            return Err("No duplicated page to remove".to_string());
+        };
+
+        let removed = self
+            .app_state
+            .with_mutable_active_document(|doc| {
+                let index = doc.page_index(new_page_id)?;
+                Some((index, doc.pages.remove(index)))
+            })
+            .flatten();
+
+        match removed {
+            Some((index, page)) => {
+                self.removed_index = index;
+                self.removed_page = Some(page);
+                self.app_state.clamp_active_page_index();
+                Ok("Undo: Duplicate page".to_string())
+            }
+            None => Err("Duplicated page not found".to_string()),
+        }
+    }
+
+    fn description(&self) -> &str {
+        "Duplicate Page"
+    }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of_val(self)
+            + elements_memory_usage(self.removed_page.iter().flat_map(|page| &page.elements))
+    }
+}
+
+impl std::fmt::Debug for AppDuplicatePageCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AppDuplicatePageCommand")
+            .field("source_page_id", &self.source_page_id)
+            .field("new_page_id", &self.new_page_id)
+            .finish()
+    }
+}
+
+/// Command for reordering pages with undo support
+pub struct AppMovePageCommand {
+    app_state: AppState,
+    from_index: usize,
+    to_index: usize,
+    /// Index the page actually landed on (targets are clamped)
+    moved_to: Option<usize>,
+}
+
+impl AppMovePageCommand {
+    /// Create a new move page command
+    pub fn new(app_state: AppState, from_index: usize, to_index: usize) -> Self {
+        Self {
+            app_state,
+            from_index,
+            to_index,
+            moved_to: None,
+        }
+    }
+}
+
+impl Command for AppMovePageCommand {
+    fn execute(&mut self) -> Result<String, String> {
+        let new_index = self.app_state.move_page(self.from_index, self.to_index)?;
+        self.moved_to = Some(new_index);
+        Ok(format!(
+            "Moved page {} to {}",
+            self.from_index + 1,
+            new_index + 1
+        ))
+    }
+
+    fn undo(&mut self) -> Result<String, String> {
+        let Some(moved_to) = self.moved_to else {
+            return Err("Page has not been moved".to_string());
+        };
+        self.app_state.move_page(moved_to, self.from_index)?;
+        Ok("Undo: Move page".to_string())
+    }
+
+    fn description(&self) -> &str {
+        "Move Page"
+    }
+}
+
+impl std::fmt::Debug for AppMovePageCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AppMovePageCommand")
+            .field("from_index", &self.from_index)
+            .field("to_index", &self.to_index)
+            .field("moved_to", &self.moved_to)
+            .finish()
+    }
+}
+
+/// Command for restacking elements (bring to front, send backward, ...)
+pub struct AppZOrderCommand {
+    app_state: AppState,
+    element_ids: Vec<Uuid>,
+    page_index: usize,
+    change: ZOrderChange,
+    /// Element order before the change, restored on undo
+    old_order: Option<Vec<Uuid>>,
+}
+
+impl AppZOrderCommand {
+    /// Create a new z-order command for the given elements
+    pub fn new(
+        app_state: AppState,
+        element_ids: Vec<Uuid>,
+        page_index: usize,
+        change: ZOrderChange,
+    ) -> Self {
+        Self {
+            app_state,
+            element_ids,
+            page_index,
+            change,
+            old_order: None,
+        }
+    }
+}
+
+impl Command for AppZOrderCommand {
+    fn execute(&mut self) -> Result<String, String> {
+        let page_index = self.page_index;
+        let element_ids = &self.element_ids;
+        let change = self.change;
+        let old_order = self
+            .app_state
+            .with_mutable_active_document(|doc| {
+                let page = doc.pages.get_mut(page_index)?;
+                let order = page.element_order();
+                page.reorder_elements(element_ids, change).then_some(order)
+            })
+            .flatten();
+
+        match old_order {
+            Some(order) => {
+                self.old_order = Some(order);
+                Ok(format!("{:?}: {} elements", change, element_ids.len()))
+            }
+            None => Err("Element order unchanged".to_string()),
+        }
+    }
+
+    fn undo(&mut self) -> Result<String, String> {
+        let Some(order) = self.old_order.take() else {
+            return Err("Elements have not been reordered".to_string());
+        };
+        let page_index = self.page_index;
+        self.app_state.with_mutable_active_document(|doc| {
+            if let Some(page) = doc.pages.get_mut(page_index) {
+                page.restore_element_order(&order);
+            }
+        });
+        Ok(format!("Undo: {:?}", self.change))
+    }
+
+    fn description(&self) -> &str {
+        match self.change {
+            ZOrderChange::BringToFront => "Bring to Front",
+            ZOrderChange::BringForward => "Bring Forward",
+            ZOrderChange::SendBackward => "Send Backward",
+            ZOrderChange::SendToBack => "Send to Back",
+        }
+    }
+}
+
+impl std::fmt::Debug for AppZOrderCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AppZOrderCommand")
+            .field("element_ids", &self.element_ids)
+            .field("page_index", &self.page_index)
+            .field("change", &self.change)
+            .finish()
+    }
+}
+
+/// Command for editing the layers of a page
+///
+/// Adding, removing, reordering, renaming, showing/hiding and locking layers
+/// and assigning elements to them only change `Page::layers`, so the command
+/// swaps in the edited layer list and puts the old one back on undo.
+pub struct AppLayersCommand {
+    app_state: AppState,
+    page_index: usize,
+    new_layers: Vec<Layer>,
+    /// Layers before the change, restored on undo
+    old_layers: Option<Vec<Layer>>,
+    description_text: String,
+}
+
+impl AppLayersCommand {
+    /// Create a command replacing the layers of the page at `page_index`
+    pub fn new(
+        app_state: AppState,
+        page_index: usize,
+        new_layers: Vec<Layer>,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            app_state,
+            page_index,
+            new_layers,
+            old_layers: None,
+            description_text: description.into(),
+        }
+    }
+}
+
+impl Command for AppLayersCommand {
+    fn execute(&mut self) -> Result<String, String> {
+        let page_index = self.page_index;
+        let new_layers = self.new_layers.clone();
+        let old_layers = self
+            .app_state
+            .with_mutable_active_document(|doc| {
+                let page = doc.pages.get_mut(page_index)?;
+                Some(std::mem::replace(&mut page.layers, new_layers))
+            })
+            .flatten();
+
+        match old_layers {
+            Some(layers) => {
+                self.old_layers = Some(layers);
+                Ok(self.description_text.clone())
+            }
+            None => Err(format!("Page {} not found", page_index + 1)),
+        }
+    }
+
+    fn undo(&mut self) -> Result<String, String> {
+        let Some(layers) = self.old_layers.take() else {
+            return Err("Layers have not been changed".to_string());
+        };
+        let page_index = self.page_index;
+        self.app_state.with_mutable_active_document(|doc| {
+            if let Some(page) = doc.pages.get_mut(page_index) {
+                page.layers = layers;
+            }
+        });
+        Ok(format!("Undo: {}", self.description_text))
+    }
+
+    fn description(&self) -> &str {
+        &self.description_text
+    }
+}
+
+impl std::fmt::Debug for AppLayersCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AppLayersCommand")
+            .field("page_index", &self.page_index)
+            .field("layers", &self.new_layers.len())
+            .field("description", &self.description_text)
+            .finish()
+    }
+}
@@ -4,22 +4,26 @@
 //! document changes, with support for batched operations.
 
 use std::collections::VecDeque;
+use testruct_core::document::DocumentElement;
 
 // Re-export command implementations from submodules
 mod app_commands;
 mod undo_redo_group;
+mod undo_redo_guides;
 mod undo_redo_shape;
 mod undo_redo_text;
 
 pub use undo_redo_group::GroupCommand;
+pub use undo_redo_guides::GuidesCommand;
 pub use undo_redo_shape::{CreateCommand, DeleteCommand, MoveCommand};
 pub use undo_redo_text::{DuplicateCommand, PasteCommand};
 
 // AppState-compatible commands (recommended for new code)
 pub use app_commands::{
-    AppCreateCommand, AppDeleteCommand, AppGroupCommand, AppMoveCommand,
-    AppPropertyChangeCommand, AppResizeCommand, AppStrokeWidthCommand, AppUngroupCommand,
-    PropertyValue,
+    AppCreateCommand, AppDeleteCommand, AppDuplicatePageCommand, AppFlattenGroupCommand,
+    AppGroupCommand, AppLayersCommand, AppMoveCommand, AppMovePageCommand,
+    AppPropertyChangeCommand, AppQrCodeCommand, AppReplaceTextCommand, AppResizeCommand,
+    AppTableCellCommand, AppUngroupCommand, AppZOrderCommand, PaddingSide, PropertyValue,
 };
 
 /// Command trait for undo/redo operations
@@ -32,6 +36,30 @@ pub trait Command: std::fmt::Debug {
 
     /// Get a description of this command
     fn description(&self) -> &str;
+
+    /// Approximate bytes held by this command
+    ///
+    /// Commands that keep cloned elements or pages should add their size on
+    /// top of the struct itself.
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
+}
+
+/// Default number of commands kept in the undo history
+pub const DEFAULT_MAX_HISTORY: usize = 100;
+
+/// Approximate bytes held by cloned elements, measured by their JSON size
+pub(crate) fn elements_memory_usage<'a>(
+    elements: impl IntoIterator<Item = &'a DocumentElement>,
+) -> usize {
+    elements
+        .into_iter()
+        .map(|element| {
+            std::mem::size_of::<DocumentElement>()
+                + serde_json::to_vec(element).map_or(0, |json| json.len())
+        })
+        .sum()
 }
 
 /// Undo/Redo stack manager
@@ -59,7 +87,7 @@ pub struct UndoRedoStack {
 impl UndoRedoStack {
     /// Create a new undo/redo stack with default capacity (100 commands)
     pub fn new() -> Self {
-        Self::with_capacity(100)
+        Self::with_capacity(DEFAULT_MAX_HISTORY)
     }
 
     /// Create a new undo/redo stack with specified capacity
@@ -88,9 +116,7 @@ impl UndoRedoStack {
                 self.undo_stack.push_back(command);
 
                 // Limit stack size (O(1) operation with VecDeque)
-                if self.undo_stack.len() > self.max_history {
-                    self.undo_stack.pop_front();
-                }
+                self.trim_history();
             }
         }
     }
@@ -110,9 +136,7 @@ impl UndoRedoStack {
             self.undo_stack.push_back(Box::new(batch_cmd));
 
             // Limit stack size
-            if self.undo_stack.len() > self.max_history {
-                self.undo_stack.pop_front();
-            }
+            self.trim_history();
 
             // Clear redo stack
             self.redo_stack.clear();
@@ -178,6 +202,34 @@ impl UndoRedoStack {
     pub fn redo_count(&self) -> usize {
         self.redo_stack.len()
     }
+
+    /// Maximum number of commands kept in the undo history
+    pub fn max_history(&self) -> usize {
+        self.max_history
+    }
+
+    /// Change the history limit, dropping the oldest commands past it
+    pub fn set_max_history(&mut self, max_history: usize) {
+        self.max_history = max_history;
+        self.trim_history();
+    }
+
+    /// Approximate bytes held by the undo and redo history
+    pub fn memory_usage(&self) -> usize {
+        self.undo_stack
+            .iter()
+            .chain(self.redo_stack.iter())
+            .chain(self.batch_commands.iter())
+            .map(|command| command.memory_usage())
+            .sum()
+    }
+
+    /// Drop the oldest commands until the undo stack fits `max_history`
+    fn trim_history(&mut self) {
+        while self.undo_stack.len() > self.max_history {
+            self.undo_stack.pop_front();
+        }
+    }
 }
 
 impl Default for UndoRedoStack {
@@ -221,6 +273,15 @@ impl Command for BatchCommand {
     fn description(&self) -> &str {
         &self.description
     }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of_val(self)
+            + self
+                .commands
+                .iter()
+                .map(|command| command.memory_usage())
+                .sum::<usize>()
+    }
 }
 
 #[cfg(test)]
@@ -292,4 +353,20 @@ mod tests {
         assert!(stack.can_undo());
         assert_eq!(stack.undo_count(), 1); // Should be single batched command
     }
+
+    #[test]
+    fn test_set_max_history_drops_oldest() {
+        let mut stack = UndoRedoStack::new();
+        for index in 0..5 {
+            stack.push(Box::new(MockCommand::new(&format!("Cmd {}", index))));
+        }
+        let usage = stack.memory_usage();
+        assert!(usage >= 5 * std::mem::size_of::<MockCommand>());
+
+        stack.set_max_history(2);
+        assert_eq!(stack.max_history(), 2);
+        assert_eq!(stack.undo_count(), 2);
+        assert_eq!(stack.undo_description(), Some("Cmd 4"));
+        assert!(stack.memory_usage() < usage);
+    }
 }
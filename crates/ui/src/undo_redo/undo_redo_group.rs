@@ -3,7 +3,7 @@
 //! Provides commands for grouping and ungrouping document elements.
 
 use super::undo_redo_shape::elem_id;
-use super::Command;
+use super::{elements_memory_usage, Command};
 use std::sync::{Arc, Mutex};
 use testruct_core::document::{Document, DocumentElement, GroupElement};
 use testruct_core::layout::{Point, Rect, Size};
@@ -68,6 +68,8 @@ impl Command for GroupCommand {
                     DocumentElement::Shape(s) => &s.bounds,
                     DocumentElement::Frame(f) => &f.bounds,
                     DocumentElement::Group(g) => &g.bounds,
+                    DocumentElement::Table(t) => &t.bounds,
+                    DocumentElement::QrCode(q) => &q.bounds,
                 };
 
                 if first {
@@ -106,9 +108,12 @@ impl Command for GroupCommand {
 
         indices_to_remove.sort_by(|a, b| b.cmp(a)); // Remove from end to start
 
+        self.grouped_elements.clear();
         for idx in indices_to_remove {
             self.grouped_elements.push(page.elements.remove(idx));
         }
+        // Reverse to maintain original order
+        self.grouped_elements.reverse();
 
         // Create group element
         let group = GroupElement {
@@ -118,6 +123,7 @@ impl Command for GroupCommand {
             children: self.grouped_elements.clone(),
             visible: true,
             locked: false,
+            opacity: 1.0,
         };
 
         // Add group to page
@@ -158,4 +164,8 @@ impl Command for GroupCommand {
     fn description(&self) -> &str {
         "Group objects"
     }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of_val(self) + elements_memory_usage(&self.grouped_elements)
+    }
 }
@@ -0,0 +1,69 @@
+//! Guide undo/redo command
+//!
+//! Guides live in the canvas render configuration rather than in a page, so
+//! this command edits the shared `RenderConfig`. The canvas writes the view
+//! state (guides included) back to the document on its next draw.
+
+use super::Command;
+use crate::canvas::grid_rendering::Guide;
+use crate::canvas::rendering::RenderConfig;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Command replacing the whole set of guides
+///
+/// Used for clearing guides and applying presets, so that one undo restores
+/// every guide the action replaced.
+pub struct GuidesCommand {
+    config: Rc<RefCell<RenderConfig>>,
+    new_guides: Vec<Guide>,
+    /// Guides before the change, restored on undo
+    old_guides: Option<Vec<Guide>>,
+    description_text: String,
+}
+
+impl GuidesCommand {
+    /// Create a command replacing the guides of `config` with `new_guides`
+    pub fn new(
+        config: Rc<RefCell<RenderConfig>>,
+        new_guides: Vec<Guide>,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            config,
+            new_guides,
+            old_guides: None,
+            description_text: description.into(),
+        }
+    }
+}
+
+impl Command for GuidesCommand {
+    fn execute(&mut self) -> Result<String, String> {
+        let new_guides = self.new_guides.clone();
+        let old_guides = std::mem::replace(&mut self.config.borrow_mut().guides, new_guides);
+        self.old_guides = Some(old_guides);
+        Ok(self.description_text.clone())
+    }
+
+    fn undo(&mut self) -> Result<String, String> {
+        let Some(guides) = self.old_guides.take() else {
+            return Err("Guides have not been changed".to_string());
+        };
+        self.config.borrow_mut().guides = guides;
+        Ok(format!("Undo: {}", self.description_text))
+    }
+
+    fn description(&self) -> &str {
+        &self.description_text
+    }
+}
+
+impl std::fmt::Debug for GuidesCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GuidesCommand")
+            .field("guides", &self.new_guides.len())
+            .field("description", &self.description_text)
+            .finish()
+    }
+}
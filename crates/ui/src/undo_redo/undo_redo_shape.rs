@@ -3,7 +3,7 @@
 //! Provides commands for creating, deleting, pasting, and duplicating shapes
 //! and other document elements.
 
-use super::Command;
+use super::{elements_memory_usage, Command};
 use std::sync::{Arc, Mutex};
 use testruct_core::document::{Document, DocumentElement};
 use uuid::Uuid;
@@ -16,6 +16,8 @@ pub(crate) fn elem_id(element: &DocumentElement) -> Uuid {
         DocumentElement::Image(i) => i.id,
         DocumentElement::Shape(s) => s.id,
         DocumentElement::Group(g) => g.id,
+        DocumentElement::Table(t) => t.id,
+        DocumentElement::QrCode(q) => q.id,
     }
 }
 
@@ -80,6 +82,10 @@ impl Command for DeleteCommand {
     fn description(&self) -> &str {
         "Delete object"
     }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of_val(self) + elements_memory_usage(&self.deleted_element)
+    }
 }
 
 impl std::fmt::Debug for DeleteCommand {
@@ -151,6 +157,14 @@ impl MoveCommand {
                         group.bounds.origin.x += dx;
                         group.bounds.origin.y += dy;
                     }
+                    DocumentElement::Table(table) => {
+                        table.bounds.origin.x += dx;
+                        table.bounds.origin.y += dy;
+                    }
+                    DocumentElement::QrCode(qr) => {
+                        qr.bounds.origin.x += dx;
+                        qr.bounds.origin.y += dy;
+                    }
                 }
                 return Ok(format!("Moved element {} by ({}, {})", self.element_id, dx, dy));
             }
@@ -245,4 +259,8 @@ impl Command for CreateCommand {
     fn description(&self) -> &str {
         "Create object"
     }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of_val(self) + elements_memory_usage(&self.element)
+    }
 }
@@ -29,7 +29,7 @@ impl PasteCommand {
             let mut doc = document.lock().expect("document");
             if page_index < doc.pages.len() {
                 for elem in elements {
-                    doc.pages[page_index].add_element(elem);
+                    doc.pages[page_index].add_element_to_active_layer(elem);
                 }
             }
         }
@@ -133,9 +133,19 @@ impl Command for DuplicateCommand {
                         group.bounds.origin.x += 20.0;
                         group.bounds.origin.y += 20.0;
                     }
+                    DocumentElement::Table(table) => {
+                        table.id = new_id;
+                        table.bounds.origin.x += 20.0;
+                        table.bounds.origin.y += 20.0;
+                    }
+                    DocumentElement::QrCode(qr) => {
+                        qr.id = new_id;
+                        qr.bounds.origin.x += 20.0;
+                        qr.bounds.origin.y += 20.0;
+                    }
                 }
 
-                page.add_element(new_elem);
+                page.add_copy(*orig_id, new_elem);
                 self.duplicated_ids.push(new_id);
             }
         }
@@ -1,10 +1,12 @@
 //! Alignment and distribution actions for selected objects
 //!
-//! Provides alignment operations: left, center (horizontal), right, top, center (vertical), bottom
+//! Provides alignment operations: left, center (horizontal), right, top, center (vertical), bottom,
+//! plus equal-gap distribution. The align-page-* variants align the selection to the active page
+//! instead, keeping the project's page edge margin at the edges. Every operation is pushed as one
+//! undoable batch of moves.
 
 use gtk4::prelude::*;
-use testruct_core::document::DocumentElement;
-use testruct_core::layout::{Point, Rect};
+use testruct_core::layout::{Rect, Size};
 
 use super::common::add_window_action;
 
@@ -243,6 +245,33 @@ pub fn register(
             );
         });
     }
+
+    // Align to the page (works on a single object too)
+    for (name, alignment) in [
+        ("align-page-left", PageAlignment::Left),
+        ("align-page-center-h", PageAlignment::CenterH),
+        ("align-page-right", PageAlignment::Right),
+        ("align-page-top", PageAlignment::Top),
+        ("align-page-center-v", PageAlignment::CenterV),
+        ("align-page-bottom", PageAlignment::Bottom),
+    ] {
+        let state_c = state.clone();
+        let selection = render_state.selected_ids.clone();
+        let drawing_c = drawing_area.clone();
+
+        add_window_action(window, name, move |_| {
+            let ids = selection.borrow().clone();
+            if ids.is_empty() {
+                tracing::warn!("⚠️  No objects selected for alignment");
+                return;
+            }
+            if apply_page_alignment(&state_c, &ids, alignment) {
+                state_c.mark_as_modified();
+                drawing_c.queue_draw();
+                tracing::info!("✅ Objects aligned to page ({})", alignment.description());
+            }
+        });
+    }
 }
 
 fn execute_alignment(
@@ -272,9 +301,11 @@ fn execute_alignment(
     let ids: Vec<uuid::Uuid> = selected.iter().copied().collect();
     drop(selected);
 
-    apply_alignment(state, &ids, alignment_type);
-    drawing_area.queue_draw();
-    tracing::info!("{success_log}");
+    if apply_alignment(state, &ids, alignment_type) {
+        state.mark_as_modified();
+        drawing_area.queue_draw();
+        tracing::info!("{success_log}");
+    }
 }
 
 /// Alignment type
@@ -290,241 +321,347 @@ pub enum AlignmentType {
     DistributeV,
 }
 
-/// Helper function to get bounds from a DocumentElement
-fn get_element_bounds(element: &DocumentElement) -> Rect {
-    match element {
-        DocumentElement::Shape(shape) => shape.bounds,
-        DocumentElement::Text(text) => text.bounds,
-        DocumentElement::Image(image) => image.bounds,
-        DocumentElement::Frame(frame) => frame.bounds,
-        DocumentElement::Group(group) => group.bounds,
+impl AlignmentType {
+    /// Undo history label
+    fn description(&self) -> &'static str {
+        match self {
+            AlignmentType::AlignLeft => "Align Left",
+            AlignmentType::AlignCenterH => "Align Center",
+            AlignmentType::AlignRight => "Align Right",
+            AlignmentType::AlignTop => "Align Top",
+            AlignmentType::AlignCenterV => "Align Middle",
+            AlignmentType::AlignBottom => "Align Bottom",
+            AlignmentType::DistributeH => "Distribute Horizontally",
+            AlignmentType::DistributeV => "Distribute Vertically",
+        }
+    }
+}
+
+/// Alignment of the selection to the active page
+#[derive(Clone, Copy, Debug)]
+pub enum PageAlignment {
+    Left,
+    CenterH,
+    Right,
+    Top,
+    CenterV,
+    Bottom,
+}
+
+impl PageAlignment {
+    /// Undo history label
+    fn description(&self) -> &'static str {
+        match self {
+            PageAlignment::Left => "Align to Page Left",
+            PageAlignment::CenterH => "Center on Page Horizontally",
+            PageAlignment::Right => "Align to Page Right",
+            PageAlignment::Top => "Align to Page Top",
+            PageAlignment::CenterV => "Center on Page Vertically",
+            PageAlignment::Bottom => "Align to Page Bottom",
+        }
+    }
+}
+
+/// Bounds of the selected top-level objects on the active page
+fn selected_bounds(
+    state: &crate::app::AppState,
+    page_index: usize,
+    selected_ids: &[uuid::Uuid],
+) -> Vec<(uuid::Uuid, Rect)> {
+    state
+        .with_active_document(|doc| {
+            doc.pages
+                .get(page_index)
+                .map(|page| {
+                    page.elements
+                        .iter()
+                        .filter(|element| selected_ids.contains(&element.id()))
+                        .map(|element| (element.id(), *element.bounds()))
+                        .collect()
+                })
+                .unwrap_or_default()
+        })
+        .unwrap_or_default()
+}
+
+/// Push `moves` as one undo batch labelled `description`
+fn push_moves(
+    state: &crate::app::AppState,
+    page_index: usize,
+    moves: Vec<(uuid::Uuid, f32, f32)>,
+    description: &str,
+) -> bool {
+    if moves.is_empty() {
+        return false;
+    }
+
+    // One batch so a single undo reverts the whole operation
+    state.with_undo_stack(|stack| stack.begin_batch(description));
+    for (id, dx, dy) in moves {
+        let command =
+            crate::undo_redo::AppMoveCommand::new(state.clone(), vec![id], page_index, dx, dy);
+        state.push_command(Box::new(command));
+    }
+    state.with_undo_stack(|stack| stack.end_batch());
+    true
+}
+
+/// Align the selected objects to the active page
+///
+/// Returns `true` if any object moved.
+fn apply_page_alignment(
+    state: &crate::app::AppState,
+    selected_ids: &[uuid::Uuid],
+    alignment: PageAlignment,
+) -> bool {
+    let page_index = state.active_page_index();
+    let Some(page_size) = state
+        .with_active_document(|doc| doc.pages.get(page_index).map(|page| doc.page_size_of(page)))
+        .flatten()
+    else {
+        return false;
+    };
+    let margin = state.project().settings.page_edge_margin;
+
+    let bounds = selected_bounds(state, page_index, selected_ids);
+    let moves = page_alignment_moves(&bounds, page_size, margin, alignment);
+    push_moves(state, page_index, moves, alignment.description())
+}
+
+/// Compute the moves that align the selection's combined bounds to the page
+///
+/// All objects move by the same amount, so their arrangement is kept. Edge
+/// alignments stay `margin` inside the page edge.
+fn page_alignment_moves(
+    selected_bounds: &[(uuid::Uuid, Rect)],
+    page_size: Size,
+    margin: f32,
+    alignment: PageAlignment,
+) -> Vec<(uuid::Uuid, f32, f32)> {
+    if selected_bounds.is_empty() {
+        return Vec::new();
+    }
+    let (mut x, mut y) = (f32::INFINITY, f32::INFINITY);
+    let (mut right, mut bottom) = (f32::NEG_INFINITY, f32::NEG_INFINITY);
+    for (_, bounds) in selected_bounds {
+        x = x.min(bounds.origin.x);
+        y = y.min(bounds.origin.y);
+        right = right.max(bounds.origin.x + bounds.size.width);
+        bottom = bottom.max(bounds.origin.y + bounds.size.height);
+    }
+    let (width, height) = (right - x, bottom - y);
+    let (dx, dy) = match alignment {
+        PageAlignment::Left => (margin - x, 0.0),
+        PageAlignment::CenterH => ((page_size.width - width) / 2.0 - x, 0.0),
+        PageAlignment::Right => (page_size.width - margin - width - x, 0.0),
+        PageAlignment::Top => (0.0, margin - y),
+        PageAlignment::CenterV => (0.0, (page_size.height - height) / 2.0 - y),
+        PageAlignment::Bottom => (0.0, page_size.height - margin - height - y),
+    };
+    if dx == 0.0 && dy == 0.0 {
+        return Vec::new();
     }
+    selected_bounds
+        .iter()
+        .map(|(id, _)| (*id, dx, dy))
+        .collect()
 }
 
-/// Apply alignment to selected objects
+/// Apply alignment to the selected objects on the active page
+///
+/// Returns `true` if any object moved.
 fn apply_alignment(
     state: &crate::app::AppState,
     selected_ids: &[uuid::Uuid],
     alignment_type: AlignmentType,
-) {
-    state.with_active_document(|doc| {
-        if let Some(page) = doc.pages.first_mut() {
-            // Collect bounds for all selected elements
-            let mut selected_bounds: Vec<(uuid::Uuid, Rect)> = Vec::new();
-            for element in &page.elements {
-                if selected_ids.contains(&element.id()) {
-                    selected_bounds.push((element.id(), get_element_bounds(element)));
-                }
-            }
+) -> bool {
+    let page_index = state.active_page_index();
+    let selected_bounds = selected_bounds(state, page_index, selected_ids);
+    let moves = alignment_moves(&selected_bounds, alignment_type);
+    push_moves(state, page_index, moves, alignment_type.description())
+}
 
-            if selected_bounds.len() < 2 {
-                return;
-            }
+/// Compute the move `(id, dx, dy)` for every object that changes position
+fn alignment_moves(
+    selected_bounds: &[(uuid::Uuid, Rect)],
+    alignment_type: AlignmentType,
+) -> Vec<(uuid::Uuid, f32, f32)> {
+    let min_count = match alignment_type {
+        AlignmentType::DistributeH | AlignmentType::DistributeV => 3,
+        _ => 2,
+    };
+    if selected_bounds.len() < min_count {
+        return Vec::new();
+    }
 
-            // Handle distribute operations separately
-            match alignment_type {
-                AlignmentType::DistributeH => {
-                    if selected_bounds.len() < 3 {
-                        return;
+    let min_x = selected_bounds
+        .iter()
+        .map(|(_, bounds)| bounds.origin.x)
+        .fold(f32::INFINITY, f32::min);
+    let max_x = selected_bounds
+        .iter()
+        .map(|(_, bounds)| bounds.origin.x + bounds.size.width)
+        .fold(f32::NEG_INFINITY, f32::max);
+    let min_y = selected_bounds
+        .iter()
+        .map(|(_, bounds)| bounds.origin.y)
+        .fold(f32::INFINITY, f32::min);
+    let max_y = selected_bounds
+        .iter()
+        .map(|(_, bounds)| bounds.origin.y + bounds.size.height)
+        .fold(f32::NEG_INFINITY, f32::max);
+
+    let moves: Vec<(uuid::Uuid, f32, f32)> = match alignment_type {
+        AlignmentType::DistributeH => distribute_moves(selected_bounds, true),
+        AlignmentType::DistributeV => distribute_moves(selected_bounds, false),
+        _ => selected_bounds
+            .iter()
+            .map(|(id, bounds)| {
+                let (dx, dy) = match alignment_type {
+                    AlignmentType::AlignLeft => (min_x - bounds.origin.x, 0.0),
+                    AlignmentType::AlignRight => (max_x - bounds.size.width - bounds.origin.x, 0.0),
+                    AlignmentType::AlignCenterH => (
+                        (min_x + max_x - bounds.size.width) / 2.0 - bounds.origin.x,
+                        0.0,
+                    ),
+                    AlignmentType::AlignTop => (0.0, min_y - bounds.origin.y),
+                    AlignmentType::AlignBottom => {
+                        (0.0, max_y - bounds.size.height - bounds.origin.y)
                     }
-                    // Sort by x position
-                    selected_bounds.sort_by(|a, b| a.1.origin.x.partial_cmp(&b.1.origin.x).unwrap());
-
-                    let leftmost_x = selected_bounds.first().unwrap().1.origin.x;
-                    let rightmost_right = {
-                        let last = selected_bounds.last().unwrap();
-                        last.1.origin.x + last.1.size.width
-                    };
-                    let total_span = rightmost_right - leftmost_x;
-                    let total_width: f32 = selected_bounds.iter().map(|(_, b)| b.size.width).sum();
-                    let gap = (total_span - total_width) / (selected_bounds.len() - 1) as f32;
-
-                    let mut current_x = leftmost_x;
-                    for (id, bounds) in &selected_bounds {
-                        let new_x = current_x;
-                        current_x += bounds.size.width + gap;
-
-                        // Apply new position
-                        for element in &mut page.elements {
-                            if element.id() == *id {
-                                let elem_bounds = element.bounds_mut();
-                                elem_bounds.origin.x = new_x;
-                                break;
-                            }
-                        }
-                    }
-                    return;
-                }
-                AlignmentType::DistributeV => {
-                    if selected_bounds.len() < 3 {
-                        return;
-                    }
-                    // Sort by y position
-                    selected_bounds.sort_by(|a, b| a.1.origin.y.partial_cmp(&b.1.origin.y).unwrap());
-
-                    let topmost_y = selected_bounds.first().unwrap().1.origin.y;
-                    let bottommost_bottom = {
-                        let last = selected_bounds.last().unwrap();
-                        last.1.origin.y + last.1.size.height
-                    };
-                    let total_span = bottommost_bottom - topmost_y;
-                    let total_height: f32 = selected_bounds.iter().map(|(_, b)| b.size.height).sum();
-                    let gap = (total_span - total_height) / (selected_bounds.len() - 1) as f32;
-
-                    let mut current_y = topmost_y;
-                    for (id, bounds) in &selected_bounds {
-                        let new_y = current_y;
-                        current_y += bounds.size.height + gap;
-
-                        // Apply new position
-                        for element in &mut page.elements {
-                            if element.id() == *id {
-                                let elem_bounds = element.bounds_mut();
-                                elem_bounds.origin.y = new_y;
-                                break;
-                            }
-                        }
-                    }
-                    return;
-                }
-                _ => {}
-            }
+                    AlignmentType::AlignCenterV => (
+                        0.0,
+                        (min_y + max_y - bounds.size.height) / 2.0 - bounds.origin.y,
+                    ),
+                    AlignmentType::DistributeH | AlignmentType::DistributeV => (0.0, 0.0),
+                };
+                (*id, dx, dy)
+            })
+            .collect(),
+    };
 
-            // Calculate reference value based on alignment type
-            let reference = match alignment_type {
-                AlignmentType::AlignLeft => selected_bounds
-                    .iter()
-                    .map(|(_, bounds)| bounds.origin.x)
-                    .fold(f32::INFINITY, f32::min),
-                AlignmentType::AlignRight => selected_bounds
-                    .iter()
-                    .map(|(_, bounds)| bounds.origin.x + bounds.size.width)
-                    .fold(f32::NEG_INFINITY, f32::max),
-                AlignmentType::AlignCenterH => {
-                    let min_x = selected_bounds
-                        .iter()
-                        .map(|(_, bounds)| bounds.origin.x)
-                        .fold(f32::INFINITY, f32::min);
-                    let max_x = selected_bounds
-                        .iter()
-                        .map(|(_, bounds)| bounds.origin.x + bounds.size.width)
-                        .fold(f32::NEG_INFINITY, f32::max);
-                    (min_x + max_x) / 2.0
-                }
-                AlignmentType::AlignTop => selected_bounds
-                    .iter()
-                    .map(|(_, bounds)| bounds.origin.y)
-                    .fold(f32::INFINITY, f32::min),
-                AlignmentType::AlignBottom => selected_bounds
-                    .iter()
-                    .map(|(_, bounds)| bounds.origin.y + bounds.size.height)
-                    .fold(f32::NEG_INFINITY, f32::max),
-                AlignmentType::AlignCenterV => {
-                    let min_y = selected_bounds
-                        .iter()
-                        .map(|(_, bounds)| bounds.origin.y)
-                        .fold(f32::INFINITY, f32::min);
-                    let max_y = selected_bounds
-                        .iter()
-                        .map(|(_, bounds)| bounds.origin.y + bounds.size.height)
-                        .fold(f32::NEG_INFINITY, f32::max);
-                    (min_y + max_y) / 2.0
-                }
-                AlignmentType::DistributeH | AlignmentType::DistributeV => 0.0, // Already handled
-            };
-
-            // Apply alignment to each selected element
-            for (element_id, _bounds) in selected_bounds {
-                for element in &mut page.elements {
-                    if element.id() == element_id {
-                        let new_bounds = match alignment_type {
-                            AlignmentType::AlignLeft => {
-                                let elem_bounds = get_element_bounds(element);
-                                Rect {
-                                    origin: Point {
-                                        x: reference,
-                                        y: elem_bounds.origin.y,
-                                    },
-                                    size: elem_bounds.size,
-                                }
-                            }
-                            AlignmentType::AlignRight => {
-                                let elem_bounds = get_element_bounds(element);
-                                Rect {
-                                    origin: Point {
-                                        x: reference - elem_bounds.size.width,
-                                        y: elem_bounds.origin.y,
-                                    },
-                                    size: elem_bounds.size,
-                                }
-                            }
-                            AlignmentType::AlignCenterH => {
-                                let elem_bounds = get_element_bounds(element);
-                                Rect {
-                                    origin: Point {
-                                        x: reference - elem_bounds.size.width / 2.0,
-                                        y: elem_bounds.origin.y,
-                                    },
-                                    size: elem_bounds.size,
-                                }
-                            }
-                            AlignmentType::AlignTop => {
-                                let elem_bounds = get_element_bounds(element);
-                                Rect {
-                                    origin: Point {
-                                        x: elem_bounds.origin.x,
-                                        y: reference,
-                                    },
-                                    size: elem_bounds.size,
-                                }
-                            }
-                            AlignmentType::AlignBottom => {
-                                let elem_bounds = get_element_bounds(element);
-                                Rect {
-                                    origin: Point {
-                                        x: elem_bounds.origin.x,
-                                        y: reference - elem_bounds.size.height,
-                                    },
-                                    size: elem_bounds.size,
-                                }
-                            }
-                            AlignmentType::AlignCenterV => {
-                                let elem_bounds = get_element_bounds(element);
-                                Rect {
-                                    origin: Point {
-                                        x: elem_bounds.origin.x,
-                                        y: reference - elem_bounds.size.height / 2.0,
-                                    },
-                                    size: elem_bounds.size,
-                                }
-                            }
-                            // Distribute cases are handled above and return early
-                            AlignmentType::DistributeH | AlignmentType::DistributeV => {
-                                continue;
-                            }
-                        };
-
-                        match element {
-                            DocumentElement::Shape(shape) => {
-                                shape.bounds = new_bounds;
-                            }
-                            DocumentElement::Text(text) => {
-                                text.bounds = new_bounds;
-                            }
-                            DocumentElement::Image(image) => {
-                                image.bounds = new_bounds;
-                            }
-                            DocumentElement::Frame(frame) => {
-                                frame.bounds = new_bounds;
-                            }
-                            DocumentElement::Group(group) => {
-                                group.bounds = new_bounds;
-                            }
-                        }
-                        break;
-                    }
-                }
-            }
+    moves
+        .into_iter()
+        .filter(|(_, dx, dy)| *dx != 0.0 || *dy != 0.0)
+        .collect()
+}
+
+/// Equal-gap distribution along one axis
+///
+/// Objects are ordered by their leading edge; the first and last stay put
+/// and the ones in between are spaced so every gap is the same.
+fn distribute_moves(
+    selected_bounds: &[(uuid::Uuid, Rect)],
+    horizontal: bool,
+) -> Vec<(uuid::Uuid, f32, f32)> {
+    let axis = |bounds: &Rect| {
+        if horizontal {
+            (bounds.origin.x, bounds.size.width)
+        } else {
+            (bounds.origin.y, bounds.size.height)
+        }
+    };
+
+    let mut sorted: Vec<(uuid::Uuid, f32, f32)> = selected_bounds
+        .iter()
+        .map(|(id, bounds)| {
+            let (start, length) = axis(bounds);
+            (*id, start, length)
+        })
+        .collect();
+    sorted.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    let (_, first_start, _) = sorted[0];
+    let (_, last_start, last_length) = sorted[sorted.len() - 1];
+    let total_length: f32 = sorted.iter().map(|(_, _, length)| length).sum();
+    let gap = (last_start + last_length - first_start - total_length) / (sorted.len() - 1) as f32;
+
+    let mut position = first_start;
+    let mut moves = Vec::new();
+    for (index, (id, start, length)) in sorted.iter().enumerate() {
+        // Outermost objects are the fixed anchors
+        if index > 0 && index < sorted.len() - 1 {
+            let delta = position - start;
+            moves.push(if horizontal {
+                (*id, delta, 0.0)
+            } else {
+                (*id, 0.0, delta)
+            });
         }
-    });
+        position += length + gap;
+    }
+    moves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use testruct_core::layout::Point;
+
+    fn rect(x: f32, y: f32, w: f32, h: f32) -> (uuid::Uuid, Rect) {
+        (
+            uuid::Uuid::new_v4(),
+            Rect::new(Point::new(x, y), Size::new(w, h)),
+        )
+    }
+
+    #[test]
+    fn test_distribute_keeps_outermost_fixed() {
+        // 0..20, 30..40, 90..100: span 100, widths 40 → gaps of 30, middle moves to 50
+        let bounds = vec![
+            rect(90.0, 0.0, 10.0, 10.0),
+            rect(0.0, 0.0, 20.0, 10.0),
+            rect(30.0, 5.0, 10.0, 10.0),
+        ];
+        let moves = alignment_moves(&bounds, AlignmentType::DistributeH);
+        assert_eq!(moves, vec![(bounds[2].0, 20.0, 0.0)]);
+
+        let vertical = vec![
+            rect(0.0, 0.0, 10.0, 10.0),
+            rect(0.0, 15.0, 10.0, 10.0),
+            rect(0.0, 40.0, 10.0, 10.0),
+        ];
+        let moves = alignment_moves(&vertical, AlignmentType::DistributeV);
+        assert_eq!(moves, vec![(vertical[1].0, 0.0, 5.0)]);
+    }
+
+    #[test]
+    fn test_distribute_needs_three_objects() {
+        let bounds = vec![rect(0.0, 0.0, 10.0, 10.0), rect(50.0, 0.0, 10.0, 10.0)];
+        assert!(alignment_moves(&bounds, AlignmentType::DistributeH).is_empty());
+        assert!(alignment_moves(&bounds, AlignmentType::DistributeV).is_empty());
+    }
+
+    #[test]
+    fn test_align_right_moves_only_misaligned() {
+        let bounds = vec![rect(0.0, 0.0, 10.0, 10.0), rect(20.0, 0.0, 30.0, 10.0)];
+        let moves = alignment_moves(&bounds, AlignmentType::AlignRight);
+        assert_eq!(moves, vec![(bounds[0].0, 40.0, 0.0)]);
+    }
+
+    #[test]
+    fn test_page_alignment_moves_the_selection_as_a_whole() {
+        let page = Size::new(600.0, 800.0);
+        let bounds = vec![
+            rect(100.0, 100.0, 50.0, 50.0),
+            rect(200.0, 150.0, 100.0, 50.0),
+        ];
+        // Combined bounds: x 100..300, y 100..200
+        let moves = page_alignment_moves(&bounds, page, 20.0, PageAlignment::CenterH);
+        assert_eq!(
+            moves,
+            vec![(bounds[0].0, 100.0, 0.0), (bounds[1].0, 100.0, 0.0)]
+        );
+
+        let single = vec![rect(100.0, 100.0, 50.0, 40.0)];
+        let right = page_alignment_moves(&single, page, 20.0, PageAlignment::Right);
+        assert_eq!(right, vec![(single[0].0, 430.0, 0.0)]);
+        let bottom = page_alignment_moves(&single, page, 20.0, PageAlignment::Bottom);
+        assert_eq!(bottom, vec![(single[0].0, 0.0, 640.0)]);
+        let centered = page_alignment_moves(&single, page, 20.0, PageAlignment::CenterV);
+        assert_eq!(centered, vec![(single[0].0, 0.0, 280.0)]);
+
+        let at_margin = vec![rect(20.0, 20.0, 10.0, 10.0)];
+        assert!(page_alignment_moves(&at_margin, page, 20.0, PageAlignment::Left).is_empty());
+        assert!(page_alignment_moves(&at_margin, page, 20.0, PageAlignment::Top).is_empty());
+    }
 }
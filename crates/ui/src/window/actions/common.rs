@@ -14,48 +14,10 @@ where
 }
 
 /// Set keyboard accelerators for window-level actions
-pub fn set_accelerators(window: &gtk4::ApplicationWindow) {
+///
+/// Uses the user's key bindings, falling back to the defaults for actions
+/// that have not been remapped.
+pub fn set_accelerators(window: &gtk4::ApplicationWindow, state: &crate::app::AppState) {
     let app = window.application().unwrap();
-
-    let shortcuts = [
-        ("win.new", "<Primary>n"),
-        ("win.open", "<Primary>o"),
-        ("win.save", "<Primary>s"),
-        ("win.save-as", "<Primary><Shift>s"),
-        ("win.undo", "<Primary>z"),
-        ("win.redo", "<Primary><Shift>z"),
-        ("win.select-all", "<Primary>a"),
-        ("win.copy", "<Primary>c"),
-        ("win.paste", "<Primary>v"),
-        ("win.group", "<Primary>g"),
-        ("win.ungroup", "<Primary><Shift>g"),
-        ("win.add-page", "<Primary><Shift>n"),
-        ("win.delete-page", "<Primary><Shift>d"),
-        ("win.duplicate-page", "<Primary><Shift>d"),
-        ("win.move-page-up", "<Primary><Shift>Page_Up"),
-        ("win.move-page-down", "<Primary><Shift>Page_Down"),
-        ("win.insert-image", "<Primary>i"),
-        ("win.toggle-grid", "F8"),
-        ("win.toggle-guides", "F7"),
-        ("win.toggle-rulers", "F6"),
-        // Zoom shortcuts
-        ("win.zoom-in", "<Primary>equal"),
-        ("win.zoom-out", "<Primary>minus"),
-        ("win.zoom-100", "<Primary>0"),
-        ("win.zoom-fit-window", "<Primary>1"),
-        ("win.zoom-fit-selection", "<Primary>2"),
-        // Alignment shortcuts
-        ("win.align-left", "<Primary><Shift>l"),
-        ("win.align-center-h", "<Primary><Shift>c"),
-        ("win.align-right", "<Primary><Shift>r"),
-        ("win.align-top", "<Primary><Shift>t"),
-        ("win.align-center-v", "<Primary><Shift>m"),
-        ("win.align-bottom", "<Primary><Shift>b"),
-        ("win.distribute-h", "<Primary><Shift>h"),
-        ("win.distribute-v", "<Primary><Shift>v"),
-    ];
-
-    for (action, accel) in &shortcuts {
-        app.set_accels_for_action(action, &[accel]);
-    }
+    state.key_bindings().apply(&app);
 }
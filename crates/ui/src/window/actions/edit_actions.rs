@@ -1,4 +1,5 @@
-//! Edit action handlers (undo, redo, select-all)
+//! Edit action handlers (undo, redo, select-all, edit text, special characters,
+//! find and replace)
 
 use super::common::add_window_action;
 use gtk4::prelude::*;
@@ -39,17 +40,13 @@ pub fn register(
     add_window_action(window, "select-all", move |_| {
         tracing::info!("Action: select all objects");
 
-        let all_ids = select_all_state.get_all_object_ids();
+        let all_ids = select_all_state.selectable_object_ids();
 
         if all_ids.is_empty() {
-            tracing::info!("⚠️  No objects to select");
+            tracing::info!("⚠️  No unlocked objects to select");
         } else {
-            let mut selected = select_all_render_state.selected_ids.borrow_mut();
-            selected.clear();
-            for id in &all_ids {
-                selected.push(*id);
-            }
-            drop(selected);
+            *select_all_render_state.selected_ids.borrow_mut() = all_ids.clone();
+            select_all_render_state.notify_selection_changed();
             select_all_drawing_area.queue_draw();
             tracing::info!("✅ Selected {} objects", all_ids.len());
         }
@@ -80,6 +77,8 @@ pub fn register(
                     testruct_core::document::DocumentElement::Image(i) => i.id,
                     testruct_core::document::DocumentElement::Frame(f) => f.id,
                     testruct_core::document::DocumentElement::Group(g) => g.id,
+                    testruct_core::document::DocumentElement::Table(t) => t.id,
+                    testruct_core::document::DocumentElement::QrCode(q) => q.id,
                 };
                 !selected_ids.contains(&element_id)
             });
@@ -99,78 +98,11 @@ pub fn register(
     let duplicate_render_state = canvas_view.render_state().clone();
     add_window_action(window, "duplicate", move |_| {
         tracing::info!("Action: duplicate selected objects");
-
-        let selected_ids: Vec<uuid::Uuid> = {
-            duplicate_render_state.selected_ids.borrow().clone()
-        };
-
-        if selected_ids.is_empty() {
-            tracing::info!("⚠️  No objects selected to duplicate");
-            return;
-        }
-
-        let new_ids = duplicate_state.with_active_page(|page| {
-            let mut new_elements = Vec::new();
-            let mut new_ids = Vec::new();
-
-            for element in &page.elements {
-                let element_id = match element {
-                    testruct_core::document::DocumentElement::Shape(s) => s.id,
-                    testruct_core::document::DocumentElement::Text(t) => t.id,
-                    testruct_core::document::DocumentElement::Image(i) => i.id,
-                    testruct_core::document::DocumentElement::Frame(f) => f.id,
-                    testruct_core::document::DocumentElement::Group(g) => g.id,
-                };
-
-                if selected_ids.contains(&element_id) {
-                    let mut cloned = element.clone();
-                    let new_id = uuid::Uuid::new_v4();
-                    // Offset the duplicated element
-                    match &mut cloned {
-                        testruct_core::document::DocumentElement::Shape(s) => {
-                            s.id = new_id;
-                            s.bounds.origin.x += 20.0;
-                            s.bounds.origin.y += 20.0;
-                        }
-                        testruct_core::document::DocumentElement::Text(t) => {
-                            t.id = new_id;
-                            t.bounds.origin.x += 20.0;
-                            t.bounds.origin.y += 20.0;
-                        }
-                        testruct_core::document::DocumentElement::Image(i) => {
-                            i.id = new_id;
-                            i.bounds.origin.x += 20.0;
-                            i.bounds.origin.y += 20.0;
-                        }
-                        testruct_core::document::DocumentElement::Frame(f) => {
-                            f.id = new_id;
-                            f.bounds.origin.x += 20.0;
-                            f.bounds.origin.y += 20.0;
-                        }
-                        testruct_core::document::DocumentElement::Group(g) => {
-                            g.id = new_id;
-                            g.bounds.origin.x += 20.0;
-                            g.bounds.origin.y += 20.0;
-                        }
-                    }
-                    new_ids.push(new_id);
-                    new_elements.push(cloned);
-                }
-            }
-
-            page.elements.extend(new_elements);
-            new_ids
-        }).unwrap_or_default();
-
-        // Select the duplicated objects
-        {
-            let mut selected = duplicate_render_state.selected_ids.borrow_mut();
-            selected.clear();
-            selected.extend(new_ids.iter());
-        }
-        duplicate_drawing_area.queue_draw();
-
-        tracing::info!("✅ Duplicated {} object(s)", new_ids.len());
+        crate::canvas::input::handle_duplicate(
+            &duplicate_render_state,
+            &duplicate_state,
+            &duplicate_drawing_area,
+        );
     });
 
     // Cut selected objects to clipboard
@@ -179,57 +111,106 @@ pub fn register(
     let cut_render_state = canvas_view.render_state().clone();
     add_window_action(window, "cut", move |_| {
         tracing::info!("Action: cut selected objects");
+        crate::canvas::input::handle_cut(&cut_render_state, &cut_state, &cut_drawing_area);
+    });
+
+    // Start editing the selected text box, like double-clicking it
+    let edit_text_state = state.clone();
+    let edit_text_drawing_area = canvas_view.drawing_area();
+    let edit_text_render_state = canvas_view.render_state().clone();
+    let edit_text_entry = canvas_view.ime_entry();
+    add_window_action(window, "edit-text", move |_| {
+        tracing::info!("Action: edit selected text");
 
         let selected_ids: Vec<uuid::Uuid> = {
-            cut_render_state.selected_ids.borrow().clone()
+            edit_text_render_state.selected_ids.borrow().clone()
         };
-
-        if selected_ids.is_empty() {
-            tracing::info!("⚠️  No objects selected to cut");
+        let [selected_id] = selected_ids[..] else {
+            tracing::info!("⚠️  Select a single text box to edit");
             return;
-        }
-
-        // Copy to clipboard first
-        let copied = cut_state.with_active_page(|page| {
-            let mut copied_elements = Vec::new();
-            for element in &page.elements {
-                let element_id = match element {
-                    testruct_core::document::DocumentElement::Shape(s) => s.id,
-                    testruct_core::document::DocumentElement::Text(t) => t.id,
-                    testruct_core::document::DocumentElement::Image(i) => i.id,
-                    testruct_core::document::DocumentElement::Frame(f) => f.id,
-                    testruct_core::document::DocumentElement::Group(g) => g.id,
-                };
-                if selected_ids.contains(&element_id) {
-                    copied_elements.push(element.clone());
+        };
+        let content = edit_text_state.active_page().and_then(|page| {
+            page.elements.iter().find_map(|element| match element {
+                testruct_core::document::DocumentElement::Text(text) if text.id == selected_id => {
+                    Some(text.content.clone())
                 }
-            }
-            copied_elements
-        }).unwrap_or_default();
+                _ => None,
+            })
+        });
+        let Some(content) = content else {
+            tracing::info!("⚠️  Selected object is not a text box");
+            return;
+        };
 
-        // Store in clipboard
-        crate::clipboard::copy_to_clipboard(copied);
+        let char_count = content.chars().count();
+        {
+            let mut tool_state = edit_text_render_state.tool_state.borrow_mut();
+            tool_state.editing_text_id = Some(selected_id);
+            tool_state.editing_cursor_pos = char_count;
+        }
 
-        // Delete the elements
-        let deleted_count = cut_state.with_active_page(|page| {
-            let before_count = page.elements.len();
-            page.elements.retain(|element| {
-                let element_id = match element {
-                    testruct_core::document::DocumentElement::Shape(s) => s.id,
-                    testruct_core::document::DocumentElement::Text(t) => t.id,
-                    testruct_core::document::DocumentElement::Image(i) => i.id,
-                    testruct_core::document::DocumentElement::Frame(f) => f.id,
-                    testruct_core::document::DocumentElement::Group(g) => g.id,
-                };
-                !selected_ids.contains(&element_id)
-            });
-            before_count - page.elements.len()
-        }).unwrap_or(0);
+        // Show IME Entry for Japanese input support
+        edit_text_entry.set_text(&content);
+        edit_text_entry.set_visible(true);
+        edit_text_entry.grab_focus();
+        edit_text_entry.set_position(char_count as i32);
+        edit_text_drawing_area.queue_draw();
 
-        // Clear selection
-        cut_render_state.selected_ids.borrow_mut().clear();
-        cut_drawing_area.queue_draw();
+        tracing::info!("✅ Editing text element {}", selected_id);
+    });
 
-        tracing::info!("✅ Cut {} object(s) to clipboard", deleted_count);
+    // Pick a symbol to insert at the text cursor
+    let palette_state = state.clone();
+    let palette_drawing_area = canvas_view.drawing_area();
+    let palette_render_state = canvas_view.render_state().clone();
+    let palette_entry = canvas_view.ime_entry();
+    add_window_action(window, "insert-special-character", move |_| {
+        tracing::info!("Action: insert special character");
+
+        let shown = crate::canvas::input::character_palette::show_character_palette(
+            &palette_drawing_area,
+            &palette_render_state,
+            &palette_state,
+            &palette_entry,
+        );
+        if !shown {
+            tracing::info!("⚠️  Start editing a text box to insert special characters");
+        }
+    });
+
+    // Find and replace across all pages
+    let find_state = state.clone();
+    let find_window = window.clone();
+    let find_drawing_area = canvas_view.drawing_area();
+    let find_render_state = canvas_view.render_state().clone();
+    add_window_action(window, "find-replace", move |_| {
+        tracing::info!("Action: find and replace");
+
+        let navigate_state = find_state.clone();
+        let navigate_drawing_area = find_drawing_area.clone();
+        let navigate_render_state = find_render_state.clone();
+        let replaced_drawing_area = find_drawing_area.clone();
+
+        let window_as_base = find_window.clone().upcast::<gtk4::Window>();
+        crate::dialogs::show_find_replace_dialog(
+            &window_as_base,
+            find_state.clone(),
+            Box::new(move |found| {
+                if found.page_index != navigate_state.active_page_index()
+                    && navigate_state
+                        .set_active_page_index(found.page_index)
+                        .is_ok()
+                {
+                    let _ = navigate_drawing_area.activate_action("win.pages-changed", None);
+                }
+                *navigate_render_state.selected_ids.borrow_mut() = vec![found.element_id];
+                navigate_render_state.notify_selection_changed();
+                navigate_drawing_area.queue_draw();
+            }),
+            Box::new(move |count| {
+                replaced_drawing_area.queue_draw();
+                tracing::info!("✅ Replaced {} occurrence(s)", count);
+            }),
+        );
     });
 }
@@ -1,65 +1,210 @@
-//! Export action handlers (PDF, PNG, JPEG, SVG)
+//! Export action handlers (PDF, PDF/A, PNG, JPEG, SVG, EPS) and the preflight check
 
 use super::common::add_window_action;
 use gtk4::prelude::*;
 
 /// Register export actions
-pub fn register(window: &gtk4::ApplicationWindow, state: crate::app::AppState) {
+pub fn register(
+    window: &gtk4::ApplicationWindow,
+    state: crate::app::AppState,
+    canvas_view: &crate::canvas::CanvasView,
+) {
+    let target = PreflightTarget::new(canvas_view);
+
+    // Check the document for problems before exporting
+    let preflight_state = state.clone();
+    let preflight_window = window.clone();
+    let preflight_target = target.clone();
+    add_window_action(window, "preflight", move |_| {
+        tracing::info!("Action: preflight check");
+
+        let window_as_base = preflight_window.clone().upcast::<gtk4::Window>();
+        crate::dialogs::show_preflight_dialog(
+            &window_as_base,
+            preflight_state.clone(),
+            preflight_target.navigator(preflight_state.clone()),
+            None,
+        );
+    });
+
     let export_state = state.clone();
+    let export_target = target.clone();
     let window_weak_pdf = window.downgrade();
     add_window_action(window, "export-pdf", move |_| {
         tracing::info!("Action: export as PDF");
         if let Some(window) = window_weak_pdf.upgrade() {
-            perform_pdf_export(&window, &export_state);
+            let (export_window, state) = (window.clone(), export_state.clone());
+            export_after_preflight(&window, &export_state, &export_target, move || {
+                perform_pdf_export(&export_window, &state)
+            });
         }
     });
 
     let export_state = state.clone();
+    let export_target = target.clone();
     let window_weak_png = window.downgrade();
     add_window_action(window, "export-png", move |_| {
         tracing::info!("Action: export as PNG");
         if let Some(window) = window_weak_png.upgrade() {
-            perform_image_export(&window, &export_state, "png");
+            let (export_window, state) = (window.clone(), export_state.clone());
+            export_after_preflight(&window, &export_state, &export_target, move || {
+                perform_raster_export(&export_window, &state, crate::export::ExportFormat::PNG)
+            });
         }
     });
 
     let export_state = state.clone();
+    let export_target = target.clone();
     let window_weak_jpeg = window.downgrade();
     add_window_action(window, "export-jpeg", move |_| {
         tracing::info!("Action: export as JPEG");
         if let Some(window) = window_weak_jpeg.upgrade() {
-            perform_image_export(&window, &export_state, "jpeg");
+            let (export_window, state) = (window.clone(), export_state.clone());
+            export_after_preflight(&window, &export_state, &export_target, move || {
+                perform_raster_export(&export_window, &state, crate::export::ExportFormat::JPEG)
+            });
         }
     });
 
-    let export_state = state.clone();
-    let window_weak_svg = window.downgrade();
-    add_window_action(window, "export-svg", move |_| {
-        tracing::info!("Action: export as SVG");
-        if let Some(window) = window_weak_svg.upgrade() {
-            perform_image_export(&window, &export_state, "svg");
+    for (name, format) in [
+        ("export-svg", "svg"),
+        ("export-pdf-a", "pdf-a"),
+        ("export-eps", "eps"),
+    ] {
+        let export_state = state.clone();
+        let export_target = target.clone();
+        let window_weak = window.downgrade();
+        add_window_action(window, name, move |_| {
+            tracing::info!("Action: export as {}", format.to_uppercase());
+            if let Some(window) = window_weak.upgrade() {
+                let (export_window, state) = (window.clone(), export_state.clone());
+                export_after_preflight(&window, &export_state, &export_target, move || {
+                    perform_image_export(&export_window, &state, format)
+                });
+            }
+        });
+    }
+}
+
+/// Canvas widgets the preflight dialog navigates in
+#[derive(Clone)]
+struct PreflightTarget {
+    drawing_area: gtk4::DrawingArea,
+    render_state: crate::canvas::CanvasRenderState,
+    container: gtk4::ScrolledWindow,
+}
+
+impl PreflightTarget {
+    fn new(canvas_view: &crate::canvas::CanvasView) -> Self {
+        Self {
+            drawing_area: canvas_view.drawing_area(),
+            render_state: canvas_view.render_state().clone(),
+            container: canvas_view.container(),
         }
-    });
+    }
+
+    /// Callback that shows an issue's page, selects its element and scrolls to it
+    fn navigator(&self, state: crate::app::AppState) -> Box<dyn Fn(usize, uuid::Uuid)> {
+        let target = self.clone();
+        Box::new(move |page_index, element_id| {
+            if page_index != state.active_page_index()
+                && state.set_active_page_index(page_index).is_ok()
+            {
+                let _ = target
+                    .drawing_area
+                    .activate_action("win.pages-changed", None);
+            }
+
+            let bounds = state
+                .with_active_page(|page| {
+                    let path = page.element_path(element_id)?;
+                    path.last().map(|element| *element.bounds())
+                })
+                .flatten();
+            if let Some(bounds) = bounds {
+                let viewport_width = target.container.width() as f64;
+                let viewport_height = target.container.height() as f64;
+                let ruler_size = target.render_state.ruler_config.borrow().size;
+                target.render_state.config.borrow_mut().center_on(
+                    &bounds,
+                    viewport_width,
+                    viewport_height,
+                    ruler_size,
+                );
+            }
+
+            *target.render_state.selected_ids.borrow_mut() = vec![element_id];
+            target.render_state.notify_selection_changed();
+            target.render_state.mark_full_redraw();
+            target.drawing_area.queue_draw();
+        })
+    }
 }
 
-/// Perform PDF export
+/// Run `export` now, or once the user chooses to export despite preflight issues
+///
+/// With issues, the preflight dialog lists them with a button to export
+/// anyway; it is not modal, so they can be fixed first instead.
+fn export_after_preflight(
+    window: &gtk4::ApplicationWindow,
+    state: &crate::app::AppState,
+    target: &PreflightTarget,
+    export: impl Fn() + 'static,
+) {
+    let issues = crate::dialogs::preflight_dialog::active_document_issues(state);
+    if issues.is_empty() {
+        export();
+        return;
+    }
+    tracing::warn!("⚠️  Preflight found {} issue(s) before export", issues.len());
+
+    let parent = window.clone().upcast::<gtk4::Window>();
+    crate::dialogs::show_preflight_dialog(
+        &parent,
+        state.clone(),
+        target.navigator(state.clone()),
+        Some(Box::new(export)),
+    );
+}
+
+/// Perform PDF export after asking for crop marks and bleed
 fn perform_pdf_export(window: &gtk4::ApplicationWindow, state: &crate::app::AppState) {
-    if state.active_document().is_some() {
-        tracing::info!("Exporting active document to PDF");
+    if state.active_document().is_none() {
+        tracing::warn!("No active document to export");
+        return;
+    }
 
-        let window_clone = window.clone();
-        let state_clone = state.clone();
+    let window_clone = window.clone();
+    let state_clone = state.clone();
+    let parent = window.clone().upcast::<gtk4::Window>();
+
+    crate::dialogs::show_pdf_export_options(
+        &parent,
+        Box::new(move |options| {
+            let window_clone = window_clone.clone();
+            let state_clone = state_clone.clone();
+            tracing::info!("Exporting active document to PDF");
+
+            glib::spawn_future_local(async move {
+                let Some(path) =
+                    crate::io::file_dialog::show_export_dialog(&window_clone, "pdf").await
+                else {
+                    tracing::info!("PDF export cancelled by user");
+                    return;
+                };
+
+                let Some(document) = state_clone.active_document() else {
+                    tracing::warn!("No active document to export");
+                    return;
+                };
 
-        glib::spawn_future_local(async move {
-            if let Some(path) =
-                crate::io::file_dialog::show_export_dialog(&window_clone, "pdf").await
-            {
                 let catalog = state_clone.asset_catalog();
                 let catalog_lock = catalog.lock().expect("Failed to lock asset catalog");
 
-                match crate::export::export_pdf(
-                    &state_clone.active_document().unwrap(),
+                match crate::export::export_pdf_with_options(
+                    &document,
                     &path,
+                    &options,
                     &catalog_lock,
                 ) {
                     Ok(_) => {
@@ -69,16 +214,72 @@ fn perform_pdf_export(window: &gtk4::ApplicationWindow, state: &crate::app::AppS
                         tracing::error!("❌ PDF export failed: {}", e);
                     }
                 }
-            } else {
-                tracing::info!("PDF export cancelled by user");
-            }
-        });
-    } else {
+            });
+        }),
+    );
+}
+
+/// Perform raster export (PNG/JPEG) after asking for resolution options
+fn perform_raster_export(
+    window: &gtk4::ApplicationWindow,
+    state: &crate::app::AppState,
+    format: crate::export::ExportFormat,
+) {
+    if state.active_document().is_none() {
         tracing::warn!("No active document to export");
+        return;
     }
+
+    let window_clone = window.clone();
+    let state_clone = state.clone();
+    let parent = window.clone().upcast::<gtk4::Window>();
+
+    crate::dialogs::show_image_export_options(
+        &parent,
+        format,
+        Box::new(move |config| {
+            let window_clone = window_clone.clone();
+            let state_clone = state_clone.clone();
+            let format_str = match config.format {
+                crate::export::ExportFormat::JPEG => "jpeg",
+                _ => "png",
+            };
+
+            glib::spawn_future_local(async move {
+                let Some(path) =
+                    crate::io::file_dialog::show_export_dialog(&window_clone, format_str).await
+                else {
+                    tracing::info!("{} export cancelled by user", format_str.to_uppercase());
+                    return;
+                };
+
+                let Some(document) = state_clone.active_document() else {
+                    tracing::warn!("No active document to export");
+                    return;
+                };
+
+                let catalog = state_clone.asset_catalog();
+                let catalog_lock = catalog.lock().expect("Failed to lock asset catalog");
+
+                match crate::export::export_with_config(&document, &path, &config, &catalog_lock) {
+                    Ok(_) => {
+                        tracing::info!(
+                            "✅ {} export completed at {:.2}x: {}",
+                            format_str.to_uppercase(),
+                            config.scale(),
+                            path.display()
+                        );
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ {} export failed: {}", format_str.to_uppercase(), e);
+                    }
+                }
+            });
+        }),
+    );
 }
 
-/// Perform image export (PNG/JPEG/SVG)
+/// Perform export without an options dialog (PNG/JPEG/SVG/EPS/PDF-A)
 fn perform_image_export(
     window: &gtk4::ApplicationWindow,
     state: &crate::app::AppState,
@@ -86,6 +287,9 @@ fn perform_image_export(
 ) {
     if state.active_document().is_some() {
         tracing::info!("Exporting active document to {}", format.to_uppercase());
+        if format == "pdf-a" && reject_transparent_pdf_a(window, state) {
+            return;
+        }
 
         let window_clone = window.clone();
         let state_clone = state.clone();
@@ -117,6 +321,16 @@ fn perform_image_export(
                         &path,
                         &catalog_lock,
                     ),
+                    "eps" => crate::export::export_eps(
+                        &state_clone.active_document().unwrap(),
+                        &path,
+                        &catalog_lock,
+                    ),
+                    "pdf-a" => crate::export::export_pdf_a(
+                        &state_clone.active_document().unwrap(),
+                        &path,
+                        &catalog_lock,
+                    ),
                     _ => Err(anyhow::anyhow!("Unknown format: {}", format_str)),
                 };
 
@@ -140,3 +354,30 @@ fn perform_image_export(
         tracing::warn!("No active document to export");
     }
 }
+
+/// Explain that PDF/A cannot hold transparency if the active document uses it
+///
+/// Returns `true` when the export has to be abandoned.
+fn reject_transparent_pdf_a(
+    window: &gtk4::ApplicationWindow,
+    state: &crate::app::AppState,
+) -> bool {
+    let Some(document) = state.active_document() else {
+        return false;
+    };
+    let Some((page, _)) = crate::export::pdf_a::transparent_element(&document) else {
+        return false;
+    };
+    tracing::warn!("⚠️  PDF/A export refused: page {} uses transparency", page);
+    let alert = gtk4::AlertDialog::builder()
+        .modal(true)
+        .message("PDF/A では透明度を使用できません")
+        .detail(format!(
+            "{} ページ目に不透明度が 100% 未満のオブジェクトがあります。\
+             不透明度を 100% にしてから書き出してください。",
+            page
+        ))
+        .build();
+    alert.show(Some(window));
+    true
+}
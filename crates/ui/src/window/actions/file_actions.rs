@@ -51,6 +51,25 @@ pub fn register(
         }
     });
 
+    let import_state = state.clone();
+    let window_weak_import = window.downgrade();
+    let import_drawing_area = canvas_view.drawing_area();
+    let import_render_state = canvas_view.render_state().clone();
+    add_window_action(window, "import-svg", move |_| {
+        tracing::info!("Action: import SVG");
+        if let Some(window) = window_weak_import.upgrade() {
+            let state_clone = import_state.clone();
+            let drawing_area_clone = import_drawing_area.clone();
+            let render_state_clone = import_render_state.clone();
+            glib::spawn_future_local(async move {
+                // The import replaces the open document, so check for unsaved changes first
+                if crate::dialogs::check_unsaved_changes(&state_clone, &window.clone().upcast()).await {
+                    perform_import_svg_async(&window, &state_clone, drawing_area_clone, render_state_clone).await;
+                }
+            });
+        }
+    });
+
     let save_state = state.clone();
     let window_weak_save_overwrite = window.downgrade();
     add_window_action(window, "save", move |_| {
@@ -86,12 +105,29 @@ pub fn register(
         }
     });
 
+    let window_weak_properties = window.downgrade();
+    let properties_state = state.clone();
+    add_window_action(window, "document-properties", move |_| {
+        tracing::info!("Action: edit document properties");
+        if let Some(window) = window_weak_properties.upgrade() {
+            crate::dialogs::show_metadata_dialog(
+                &window.clone().upcast(),
+                properties_state.clone(),
+            );
+        }
+    });
+
     // Page management actions
     let page_state = state.clone();
+    let add_drawing_area = canvas_view.drawing_area();
     add_window_action(window, "add-page", move |_| {
         tracing::info!("Action: add page");
         match page_state.add_page() {
             Ok(_) => {
+                // Switch to the new (last) page
+                let _ = page_state.set_active_page_index(page_state.page_count() - 1);
+                page_state.mark_as_modified();
+                notify_pages_changed(&add_drawing_area);
                 tracing::info!("✅ Page added. Total pages: {}", page_state.page_count());
             }
             Err(e) => {
@@ -101,10 +137,13 @@ pub fn register(
     });
 
     let page_state = state.clone();
+    let delete_drawing_area = canvas_view.drawing_area();
     add_window_action(window, "delete-page", move |_| {
         tracing::info!("Action: delete page");
-        match page_state.delete_page(0) {
+        match page_state.delete_page(page_state.active_page_index()) {
             Ok(_) => {
+                page_state.mark_as_modified();
+                notify_pages_changed(&delete_drawing_area);
                 tracing::info!("✅ Page deleted. Total pages: {}", page_state.page_count());
             }
             Err(e) => {
@@ -114,55 +153,141 @@ pub fn register(
     });
 
     let page_state = state.clone();
+    let prev_drawing_area = canvas_view.drawing_area();
+    add_window_action(window, "prev-page", move |_| {
+        let active = page_state.active_page_index();
+        if active > 0 && page_state.set_active_page_index(active - 1).is_ok() {
+            notify_pages_changed(&prev_drawing_area);
+        }
+    });
+
+    let page_state = state.clone();
+    let next_drawing_area = canvas_view.drawing_area();
+    add_window_action(window, "next-page", move |_| {
+        let active = page_state.active_page_index();
+        if page_state.set_active_page_index(active + 1).is_ok() {
+            notify_pages_changed(&next_drawing_area);
+        }
+    });
+
+    let page_state = state.clone();
+    let duplicate_drawing_area = canvas_view.drawing_area();
     add_window_action(window, "duplicate-page", move |_| {
         tracing::info!("Action: duplicate page");
-        match page_state.duplicate_page(0) {
-            Ok(_) => {
-                tracing::info!(
-                    "✅ Page duplicated. Total pages: {}",
-                    page_state.page_count()
-                );
-            }
-            Err(e) => {
-                tracing::warn!("⚠️  Failed to duplicate page: {}", e);
-            }
+        let Some(page_id) = page_state.active_page().map(|page| page.id) else {
+            tracing::warn!("⚠️  Failed to duplicate page: no active page");
+            return;
+        };
+
+        let page_count_before = page_state.page_count();
+        let command = crate::undo_redo::AppDuplicatePageCommand::new(page_state.clone(), page_id);
+        page_state.push_command(Box::new(command));
+        if page_state.page_count() == page_count_before {
+            tracing::warn!("⚠️  Failed to duplicate page");
+            return;
         }
+
+        // Switch to the duplicate, which sits right after the original
+        let _ = page_state.set_active_page_index(page_state.active_page_index() + 1);
+        page_state.mark_as_modified();
+        notify_pages_changed(&duplicate_drawing_area);
+        tracing::info!(
+            "✅ Page duplicated. Total pages: {}",
+            page_state.page_count()
+        );
     });
 
     let page_state = state.clone();
+    let move_up_drawing_area = canvas_view.drawing_area();
     add_window_action(window, "move-page-up", move |_| {
         tracing::info!("Action: move page up");
-        match page_state.move_page_up(0) {
-            Ok(_) => {
-                tracing::info!("✅ Page moved up");
-            }
-            Err(e) => {
-                tracing::warn!("⚠️  Failed to move page up: {}", e);
-            }
+        let active = page_state.active_page_index();
+        if active == 0 {
+            tracing::warn!("⚠️  Failed to move page up: already first");
+            return;
         }
+        let command =
+            crate::undo_redo::AppMovePageCommand::new(page_state.clone(), active, active - 1);
+        page_state.push_command(Box::new(command));
+        page_state.mark_as_modified();
+        notify_pages_changed(&move_up_drawing_area);
+        tracing::info!("✅ Page moved up");
     });
 
     let page_state = state.clone();
+    let move_down_drawing_area = canvas_view.drawing_area();
     add_window_action(window, "move-page-down", move |_| {
         tracing::info!("Action: move page down");
-        match page_state.move_page_down(0) {
-            Ok(_) => {
-                tracing::info!("✅ Page moved down");
-            }
-            Err(e) => {
-                tracing::warn!("⚠️  Failed to move page down: {}", e);
-            }
+        let active = page_state.active_page_index();
+        if active + 1 >= page_state.page_count() {
+            tracing::warn!("⚠️  Failed to move page down: already last");
+            return;
+        }
+        let command =
+            crate::undo_redo::AppMovePageCommand::new(page_state.clone(), active, active + 1);
+        page_state.push_command(Box::new(command));
+        page_state.mark_as_modified();
+        notify_pages_changed(&move_down_drawing_area);
+        tracing::info!("✅ Page moved down");
+    });
+}
+
+/// Register the File > Open Recent actions and fill the menu
+///
+/// Each menu item targets `win.open-recent` with the file path;
+/// `win.recent-files-changed` rebuilds the menu after the list changes.
+pub fn register_recent_files_menu(
+    window: &gtk4::ApplicationWindow,
+    state: crate::app::AppState,
+    canvas_view: &CanvasView,
+    recent_files_menu: &gtk4::gio::Menu,
+) {
+    let open_recent_state = state.clone();
+    let window_weak_open_recent = window.downgrade();
+    let open_recent_drawing_area = canvas_view.drawing_area();
+    let open_recent_render_state = canvas_view.render_state().clone();
+    let open_recent = gtk4::gio::SimpleAction::new("open-recent", Some(glib::VariantTy::STRING));
+    open_recent.connect_activate(move |_, parameter| {
+        let Some(path) = parameter.and_then(|p| p.get::<String>()) else {
+            return;
+        };
+        tracing::info!("Action: open recent file {}", path);
+        if let Some(window) = window_weak_open_recent.upgrade() {
+            let state_clone = open_recent_state.clone();
+            let drawing_area_clone = open_recent_drawing_area.clone();
+            let render_state_clone = open_recent_render_state.clone();
+            glib::spawn_future_local(async move {
+                if crate::dialogs::check_unsaved_changes(&state_clone, &window.clone().upcast())
+                    .await
+                {
+                    crate::dialogs::recent_files_dialog::open_recent_file(
+                        &state_clone,
+                        std::path::Path::new(&path),
+                        &drawing_area_clone,
+                        &render_state_clone,
+                    );
+                }
+            });
         }
     });
+    window.add_action(&open_recent);
+
+    let recent_menu_state = state.clone();
+    let recent_menu = recent_files_menu.clone();
+    add_window_action(window, "recent-files-changed", move |_| {
+        crate::menu::populate_recent_files_menu(&recent_menu, &recent_menu_state.recent_files());
+    });
+    crate::menu::populate_recent_files_menu(recent_files_menu, &state.recent_files());
 }
 
 /// Perform new document creation
 fn perform_new_document(state: &crate::app::AppState) {
     tracing::info!("Creating new document");
 
+    let first_page = state.with_project(|project| project.settings.new_page());
     let doc = testruct_core::document::DocumentBuilder::new()
         .with_title("Untitled")
-        .add_page(testruct_core::document::Page::empty())
+        .add_page(first_page)
         .build()
         .expect("Failed to create document");
 
@@ -187,12 +312,13 @@ async fn perform_open_document_async(
     if let Some(path) = crate::io::file_dialog::show_open_dialog(window).await {
         match crate::io::file_io::load_document(&path) {
             Ok(document) => {
+                render_state.apply_document_view(&document);
                 state.set_active_document(document);
-                // Add to recent files and mark as saved with the file path
-                state.add_recent_file(path.clone());
+                // Mark as saved with the file path (also records it as recent)
                 state.mark_as_saved(path.clone());
                 render_state.selected_ids.borrow_mut().clear();
-                drawing_area.queue_draw();
+                let _ = drawing_area.activate_action("win.view-state-changed", None);
+                notify_pages_changed(&drawing_area);
                 tracing::info!("✅ Document loaded and activated: {}", path.display());
             }
             Err(e) => {
@@ -204,6 +330,42 @@ async fn perform_open_document_async(
     }
 }
 
+/// Import an SVG file as a new, unsaved document
+async fn perform_import_svg_async(
+    window: &gtk4::ApplicationWindow,
+    state: &crate::app::AppState,
+    drawing_area: gtk4::DrawingArea,
+    render_state: crate::canvas::CanvasRenderState,
+) {
+    let Some(path) = crate::io::file_dialog::show_import_svg_dialog(window).await else {
+        tracing::info!("SVG import cancelled by user");
+        return;
+    };
+
+    match crate::export::svg::import_from_svg(&path) {
+        Ok(document) => {
+            // Imported images are drawn from the application catalog
+            state
+                .asset_catalog()
+                .lock()
+                .expect("asset catalog")
+                .extend_from(&document.assets);
+            render_state.apply_document_view(&document);
+            state.set_active_document(document);
+            // Not saved yet: no file path, but there is content to save
+            state.clear_document_state();
+            state.mark_as_modified();
+            render_state.selected_ids.borrow_mut().clear();
+            let _ = drawing_area.activate_action("win.view-state-changed", None);
+            notify_pages_changed(&drawing_area);
+            tracing::info!("✅ SVG imported as new document: {}", path.display());
+        }
+        Err(e) => {
+            tracing::error!("❌ Failed to import SVG: {}", e);
+        }
+    }
+}
+
 /// Perform save document (overwrite if path exists, otherwise show save dialog)
 fn perform_save_document(window: &gtk4::ApplicationWindow, state: &crate::app::AppState) {
     tracing::info!("Saving document");
@@ -211,10 +373,11 @@ fn perform_save_document(window: &gtk4::ApplicationWindow, state: &crate::app::A
     // Check if we have an existing file path
     if let Some(path) = state.current_file_path() {
         // Overwrite existing file
-        if let Some(document) = state.active_document() {
+        if let Some(document) = state.document_to_save() {
             match crate::io::file_io::save_document(&document, &path) {
                 Ok(_) => {
                     state.mark_as_saved(path.clone());
+                    crate::io::recovery::discard_autosave();
                     tracing::info!("✅ Document saved (overwrite): {}", path.display());
                 }
                 Err(e) => {
@@ -242,13 +405,13 @@ fn perform_save_as_document(window: &gtk4::ApplicationWindow, state: &crate::app
         glib::spawn_future_local(async move {
             if let Some(path) = crate::io::file_dialog::show_save_dialog(&window_clone).await {
                 match crate::io::file_io::save_document(
-                    &state_clone.active_document().unwrap(),
+                    &state_clone.document_to_save().unwrap(),
                     &path,
                 ) {
                     Ok(_) => {
-                        // Add to recent files and mark as saved with the new path
-                        state_clone.add_recent_file(path.clone());
+                        // Mark as saved with the new path (also records it as recent)
                         state_clone.mark_as_saved(path.clone());
+                        crate::io::recovery::discard_autosave();
                         tracing::info!("✅ Document saved as: {}", path.display());
                     }
                     Err(e) => {
@@ -263,3 +426,9 @@ fn perform_save_as_document(window: &gtk4::ApplicationWindow, state: &crate::app
         tracing::warn!("No active document to save");
     }
 }
+
+/// Ask the window to refresh page indicators and redraw the active page
+fn notify_pages_changed(drawing_area: &gtk4::DrawingArea) {
+    let _ = drawing_area.activate_action("win.pages-changed", None);
+    drawing_area.queue_draw();
+}
@@ -1,9 +1,9 @@
-//! Object grouping/ungrouping action handlers
+//! Object grouping/ungrouping/flattening action handlers
 
 use super::common::add_window_action;
 use gtk4::prelude::*;
 
-/// Register group/ungroup actions
+/// Register group/ungroup/flatten actions
 pub fn register(
     window: &gtk4::ApplicationWindow,
     state: crate::app::AppState,
@@ -62,22 +62,23 @@ pub fn register(
             selected_ids.clone()
         };
 
-        // Find frame IDs that are groups (frames with children)
+        // Only top-level frames and groups with children can be ungrouped
+        let page_index = ungroup_state.active_page_index();
         let frame_ids_to_ungroup: Vec<uuid::Uuid> = ungroup_state
             .with_active_document(|doc| {
-                let mut frame_ids = Vec::new();
-                if let Some(page) = doc.pages.first() {
-                    for element in &page.elements {
-                        if selected_ids_vec.contains(&element.id()) {
-                            if let testruct_core::document::DocumentElement::Frame(frame) = element {
-                                if !frame.children.is_empty() {
-                                    frame_ids.push(frame.id);
-                                }
-                            }
-                        }
-                    }
-                }
-                frame_ids
+                doc.pages
+                    .get(page_index)
+                    .map(|page| {
+                        page.elements
+                            .iter()
+                            .filter(|element| selected_ids_vec.contains(&element.id()))
+                            .filter(|element| {
+                                element.children().is_some_and(|c| !c.is_empty())
+                            })
+                            .map(|element| element.id())
+                            .collect()
+                    })
+                    .unwrap_or_default()
             })
             .unwrap_or_default();
 
@@ -87,8 +88,6 @@ pub fn register(
         }
 
         // Create and execute ungroup commands with undo support
-        let page_index = ungroup_state.active_page_index();
-
         for frame_id in &frame_ids_to_ungroup {
             let command = crate::undo_redo::AppUngroupCommand::new(
                 ungroup_state.clone(),
@@ -106,6 +105,60 @@ pub fn register(
         tracing::info!("✅ Ungrouped {} groups (with undo support)", frame_ids_to_ungroup.len());
     });
 
+    let flatten_state = state.clone();
+    let flatten_drawing_area = canvas_view.drawing_area();
+    let flatten_render_state = canvas_view.render_state().selected_ids.clone();
+    add_window_action(window, "flatten-group", move |_| {
+        tracing::info!("Action: flatten selected groups");
+
+        let selected_ids: Vec<uuid::Uuid> = flatten_render_state.borrow().clone();
+        if selected_ids.is_empty() {
+            tracing::warn!("⚠️  No objects selected for flattening");
+            return;
+        }
+
+        // Only top-level frames and groups with children can be flattened
+        let page_index = flatten_state.active_page_index();
+        let group_ids: Vec<uuid::Uuid> = flatten_state
+            .with_active_document(|doc| {
+                doc.pages
+                    .get(page_index)
+                    .map(|page| {
+                        page.elements
+                            .iter()
+                            .filter(|element| selected_ids.contains(&element.id()))
+                            .filter(|element| {
+                                element.children().is_some_and(|c| !c.is_empty())
+                            })
+                            .map(|element| element.id())
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            })
+            .unwrap_or_default();
+
+        if group_ids.is_empty() {
+            tracing::warn!("⚠️  Selected items are not groups");
+            return;
+        }
+
+        // One batch so a single undo restores every group
+        flatten_state.with_undo_stack(|stack| stack.begin_batch("Flatten groups"));
+        for group_id in &group_ids {
+            let command = crate::undo_redo::AppFlattenGroupCommand::new(
+                flatten_state.clone(),
+                *group_id,
+                page_index,
+            );
+            flatten_state.push_command(Box::new(command));
+        }
+        flatten_state.with_undo_stack(|stack| stack.end_batch());
+
+        flatten_render_state.borrow_mut().clear();
+        flatten_drawing_area.queue_draw();
+        tracing::info!("✅ Flattened {} groups (with undo support)", group_ids.len());
+    });
+
     // Wire property panel ungroup button to the same action
     let ungroup_button = property_components.ungroup_btn.clone();
     let window_for_button = window.clone();
@@ -1,17 +1,27 @@
-//! Help action handlers (manual, about, settings, keyboard shortcuts)
+//! Help action handlers (manual, about, settings, keyboard shortcuts, key bindings,
+//! undo history)
 
 use super::common::add_window_action;
 use gtk4::prelude::*;
 
 /// Register help menu actions
-pub fn register(window: &gtk4::ApplicationWindow, state: crate::app::AppState) {
+pub fn register(
+    window: &gtk4::ApplicationWindow,
+    state: crate::app::AppState,
+    canvas_view: &crate::canvas::CanvasView,
+) {
     let window_weak_settings = window.downgrade();
     let state_settings = state.clone();
+    let render_state_settings = canvas_view.render_state().clone();
     add_window_action(window, "settings", move |_| {
         tracing::info!("Action: show settings");
         if let Some(window) = window_weak_settings.upgrade() {
             let window_base = window.clone().upcast::<gtk4::Window>();
-            crate::dialogs::show_project_settings(&window_base, state_settings.clone());
+            crate::dialogs::show_project_settings(
+                &window_base,
+                state_settings.clone(),
+                render_state_settings.clone(),
+            );
             tracing::info!("✅ Settings dialog displayed");
         }
     });
@@ -37,10 +47,16 @@ pub fn register(window: &gtk4::ApplicationWindow, state: crate::app::AppState) {
         }
     });
 
-    // Register F1 shortcut for keyboard shortcuts
-    if let Some(app) = window.application() {
-        app.set_accels_for_action("win.keyboard-shortcuts", &["F1"]);
-    }
+    let window_weak_bindings = window.downgrade();
+    let state_bindings = state.clone();
+    add_window_action(window, "key-bindings", move |_| {
+        tracing::info!("Action: edit key bindings");
+        if let Some(window) = window_weak_bindings.upgrade() {
+            let window_base = window.clone().upcast::<gtk4::Window>();
+            crate::dialogs::show_key_bindings_dialog(&window_base, state_bindings.clone());
+            tracing::info!("✅ Key bindings dialog displayed");
+        }
+    });
 
     let window_weak_about = window.downgrade();
     add_window_action(window, "about", move |_| {
@@ -62,4 +78,16 @@ pub fn register(window: &gtk4::ApplicationWindow, state: crate::app::AppState) {
             tracing::info!("✅ JSON editor displayed");
         }
     });
+
+    // Undo stack figures for tuning the history limit
+    let window_weak_history = window.downgrade();
+    let state_history = state.clone();
+    add_window_action(window, "undo-history", move |_| {
+        tracing::info!("Action: show undo history");
+        if let Some(window) = window_weak_history.upgrade() {
+            let window_base = window.clone().upcast::<gtk4::Window>();
+            crate::dialogs::show_undo_history_dialog(&window_base, state_history.clone());
+            tracing::info!("✅ Undo history dialog displayed");
+        }
+    });
 }
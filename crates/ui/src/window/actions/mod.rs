@@ -1,14 +1,14 @@
 //! Window-level action handlers for menu and toolbar events
 //!
 //! This module organizes action callbacks into logical sections:
-//! - File operations (new, open, save, save-as)
-//! - Export operations (PDF, PNG, JPEG, SVG)
+//! - File operations (new, open, open recent, save, save-as)
+//! - Export operations (PDF, PNG, JPEG, SVG, preflight check)
 //! - Edit operations (undo, redo, select-all)
 //! - View toggles (grid, guides, rulers)
 //! - Page management (add, delete, duplicate, move pages)
 //! - Tool operations (image insertion, templates)
 //! - Z-order operations (bring-to-front, send-to-back, etc.)
-//! - Grouping operations (group, ungroup)
+//! - Grouping operations (group, ungroup, flatten)
 //! - Clipboard operations (copy, paste)
 //! - Help operations (manual, about, settings)
 
@@ -24,6 +24,8 @@ mod layer_actions;
 mod tools_actions;
 mod view_actions;
 
+pub use file_actions::register_recent_files_menu;
+
 use crate::window::actions::common::add_window_action;
 use gtk4::prelude::*;
 use gtk4::Box as GtkBox;
@@ -40,7 +42,7 @@ pub fn register_window_actions(
 ) {
     // Register actions from each module
     file_actions::register(window, state.clone(), canvas_view);
-    export_actions::register(window, state.clone());
+    export_actions::register(window, state.clone(), canvas_view);
     edit_actions::register(window, state.clone(), canvas_view);
     view_actions::register(
         window,
@@ -55,7 +57,16 @@ pub fn register_window_actions(
     clipboard_actions::register(window, state.clone(), canvas_view);
     layer_actions::register(window, state.clone(), canvas_view);
     alignment_actions::register(window, state.clone(), canvas_view, property_components);
-    help_actions::register(window, state.clone());
+    help_actions::register(window, state.clone(), canvas_view);
+
+    // Page order/count changed (page panel, duplicate, reorder): refresh indicators
+    let pages_state = state.clone();
+    let pages_label = toolbar_buttons.page_info_label.clone();
+    let pages_drawing_area = canvas_view.drawing_area();
+    add_window_action(window, "pages-changed", move |_| {
+        crate::toolbar::update_page_info_label(&pages_label, &pages_state);
+        pages_drawing_area.queue_draw();
+    });
 
     // Register block tools toggle action
     add_window_action(window, "toggle-block-tools", |_| {
@@ -67,6 +78,7 @@ pub fn register_window_actions(
     // Register lock action
     let lock_state = state.clone();
     let lock_render_state = canvas_view.render_state().selected_ids.clone();
+    let lock_canvas_state = canvas_view.render_state().clone();
     let lock_drawing_area = canvas_view.drawing_area();
     add_window_action(window, "lock", move |_| {
         tracing::info!("Action: lock selected objects");
@@ -91,12 +103,15 @@ pub fn register_window_actions(
         }).unwrap_or(0);
 
         tracing::info!("✅ {} object(s) locked", locked_count);
+        // Lock state changed without a selection change; refresh enablement
+        lock_canvas_state.notify_selection_changed();
         lock_drawing_area.queue_draw();
     });
 
     // Register unlock action
     let unlock_state = state.clone();
     let unlock_render_state = canvas_view.render_state().selected_ids.clone();
+    let unlock_canvas_state = canvas_view.render_state().clone();
     let unlock_drawing_area = canvas_view.drawing_area();
     add_window_action(window, "unlock", move |_| {
         tracing::info!("Action: unlock selected objects");
@@ -121,9 +136,10 @@ pub fn register_window_actions(
         }).unwrap_or(0);
 
         tracing::info!("✅ {} object(s) unlocked", unlocked_count);
+        unlock_canvas_state.notify_selection_changed();
         unlock_drawing_area.queue_draw();
     });
 
     // Set keyboard accelerators
-    common::set_accelerators(window);
+    common::set_accelerators(window, &state);
 }
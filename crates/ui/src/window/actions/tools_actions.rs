@@ -1,4 +1,4 @@
-//! Tool action handlers (image insertion, templates, z-order)
+//! Tool action handlers (image, table and QR code insertion, templates, z-order)
 
 use super::common::add_window_action;
 use gtk4::prelude::*;
@@ -46,6 +46,9 @@ pub fn register(
                     },
                     visible: true,
                     locked: false,
+                    rotation: 0.0,
+                    fit: Default::default(),
+                    opacity: 1.0,
                 };
 
                 match state_ref.add_element_to_active_page(
@@ -63,21 +66,107 @@ pub fn register(
         );
     });
 
+    // Table insertion
+    let insert_table_state = state.clone();
+    let insert_table_window = window.clone();
+    let insert_table_drawing_area = canvas_view.drawing_area();
+    let insert_table_render_state = canvas_view.render_state().clone();
+    add_window_action(window, "insert-table", move |_| {
+        tracing::info!("Action: insert table");
+
+        let state_ref = insert_table_state.clone();
+        let drawing_area = insert_table_drawing_area.clone();
+        let render_state = insert_table_render_state.clone();
+
+        let window_as_base = insert_table_window.clone().upcast::<gtk4::Window>();
+        crate::dialogs::show_insert_table_dialog(
+            &window_as_base,
+            Box::new(move |rows, columns| {
+                let element =
+                    crate::canvas::tools::ShapeFactory::create_table(100.0, 100.0, rows, columns);
+                let table_id = element.id();
+                let command = crate::undo_redo::AppCreateCommand::new(
+                    state_ref.clone(),
+                    element,
+                    state_ref.active_page_index(),
+                );
+                state_ref.push_command(Box::new(command));
+
+                {
+                    let mut selected = render_state.selected_ids.borrow_mut();
+                    selected.clear();
+                    selected.push(table_id);
+                }
+                render_state.notify_selection_changed();
+                drawing_area.queue_draw();
+                tracing::info!("✅ Table inserted: {} × {}", rows, columns);
+            }),
+        );
+    });
+
+    // Insert QR code
+    let insert_qr_state = state.clone();
+    let insert_qr_window = window.clone();
+    let insert_qr_drawing_area = canvas_view.drawing_area();
+    let insert_qr_render_state = canvas_view.render_state().clone();
+    add_window_action(window, "insert-qr-code", move |_| {
+        tracing::info!("Action: insert QR code");
+
+        let state_ref = insert_qr_state.clone();
+        let drawing_area = insert_qr_drawing_area.clone();
+        let render_state = insert_qr_render_state.clone();
+
+        let window_as_base = insert_qr_window.clone().upcast::<gtk4::Window>();
+        crate::dialogs::show_qr_code_dialog(
+            &window_as_base,
+            None,
+            Box::new(move |payload, level| {
+                let element = crate::canvas::tools::ShapeFactory::create_qr_code(
+                    100.0, 100.0, payload, level,
+                );
+                let qr_id = element.id();
+                let command = crate::undo_redo::AppCreateCommand::new(
+                    state_ref.clone(),
+                    element,
+                    state_ref.active_page_index(),
+                );
+                state_ref.push_command(Box::new(command));
+
+                {
+                    let mut selected = render_state.selected_ids.borrow_mut();
+                    selected.clear();
+                    selected.push(qr_id);
+                }
+                render_state.notify_selection_changed();
+                drawing_area.queue_draw();
+                tracing::info!("✅ QR code inserted");
+            }),
+        );
+    });
+
     // Template browser
     let templates_state = state.clone();
     let templates_window = window.clone();
+    let templates_drawing_area = canvas_view.drawing_area();
+    let templates_render_state = canvas_view.render_state().clone();
     add_window_action(window, "templates", move |_| {
         tracing::info!("Action: show templates");
 
         let window_ref = templates_window.clone();
         let state_ref = templates_state.clone();
+        let drawing_area = templates_drawing_area.clone();
+        let render_state = templates_render_state.clone();
 
         let window_as_base = window_ref.clone().upcast::<gtk4::Window>();
 
         let project = state_ref.project();
         let templates: Vec<_> = project.templates.iter().collect();
+        let saved_templates = crate::templates::list_templates().unwrap_or_else(|e| {
+            tracing::warn!("⚠️  Failed to list saved templates: {}", e);
+            Vec::new()
+        });
 
-        if templates.is_empty() {
+        if templates.is_empty() && saved_templates.is_empty() {
             tracing::warn!("⚠️  No templates available");
             return;
         }
@@ -85,8 +174,9 @@ pub fn register(
         crate::dialogs::show_template_browser_async(
             &window_as_base,
             templates,
-            Box::new(move |selected_template| {
-                if let Some(template) = selected_template {
+            saved_templates,
+            Box::new(move |selected_template| match selected_template {
+                Some(crate::dialogs::TemplateSelection::Project(template)) => {
                     tracing::info!("✅ Template selected: {}", template.name);
 
                     let project = state_ref.project();
@@ -97,124 +187,109 @@ pub fn register(
                     } else {
                         tracing::error!("❌ Failed to create document from template");
                     }
-                } else {
+                }
+                Some(crate::dialogs::TemplateSelection::Saved(entry)) => {
+                    tracing::info!("✅ Saved template selected: {}", entry.name);
+
+                    match crate::templates::load_template(&entry) {
+                        Ok(document) => {
+                            render_state.apply_document_view(&document);
+                            state_ref.set_active_document(document);
+                            // A new, unsaved document based on the template
+                            state_ref.clear_document_state();
+                            render_state.selected_ids.borrow_mut().clear();
+                            let _ = drawing_area.activate_action("win.view-state-changed", None);
+                            let _ = drawing_area.activate_action("win.pages-changed", None);
+                            drawing_area.queue_draw();
+                            tracing::info!("✅ New document created from template");
+                        }
+                        Err(e) => {
+                            tracing::error!("❌ Failed to load template: {}", e);
+                        }
+                    }
+                }
+                None => {
                     tracing::info!("⚠️  Template selection cancelled");
                 }
             }),
         );
     });
 
+    // Save the current document as a template
+    let save_template_state = state.clone();
+    let save_template_window = window.clone();
+    add_window_action(window, "save-template", move |_| {
+        tracing::info!("Action: save as template");
+
+        let window_as_base = save_template_window.clone().upcast::<gtk4::Window>();
+        crate::dialogs::show_save_template_dialog(&window_as_base, save_template_state.clone());
+    });
+
     // Z-order button handlers
-    register_zorder_actions(state, canvas_view, property_components);
+    register_zorder_actions(window, state, canvas_view, property_components);
 }
 
-/// Register z-order button handlers
+/// Register z-order actions and the property panel buttons that trigger them
+///
+/// Each action restacks every selected element on the active page through
+/// an undoable `AppZOrderCommand`.
 fn register_zorder_actions(
+    window: &gtk4::ApplicationWindow,
     state: crate::app::AppState,
     canvas_view: &crate::canvas::CanvasView,
     property_components: &crate::panels::PropertyPanelComponents,
 ) {
-    let bring_to_front_state = state.clone();
-    let bring_to_front_drawing_area = canvas_view.drawing_area();
-    let bring_to_front_render_state = canvas_view.render_state().selected_ids.clone();
-    property_components
-        .bring_to_front_btn
-        .connect_clicked(move |_| {
-            tracing::info!("Action: bring to front");
-            let selected_ids = bring_to_front_render_state.borrow();
-            if let Some(element_id) = selected_ids.first().copied() {
-                bring_to_front_state.with_active_document(|doc| {
-                    if let Some(page) = doc.pages.get_mut(0) {
-                        if page.bring_to_front(element_id) {
-                            tracing::info!("✅ Element brought to front");
-                            bring_to_front_drawing_area.queue_draw();
-                        } else {
-                            tracing::warn!("⚠️  Element not found");
-                        }
-                    } else {
-                        tracing::warn!("⚠️  No active page");
-                    }
-                });
-            } else {
-                tracing::warn!("⚠️  No element selected");
-            }
-        });
+    use testruct_core::document::ZOrderChange;
 
-    let bring_forward_state = state.clone();
-    let bring_forward_drawing_area = canvas_view.drawing_area();
-    let bring_forward_render_state = canvas_view.render_state().selected_ids.clone();
-    property_components
-        .bring_forward_btn
-        .connect_clicked(move |_| {
-            tracing::info!("Action: bring forward");
-            let selected_ids = bring_forward_render_state.borrow();
-            if let Some(element_id) = selected_ids.first().copied() {
-                bring_forward_state.with_active_document(|doc| {
-                    if let Some(page) = doc.pages.get_mut(0) {
-                        if page.bring_forward(element_id) {
-                            tracing::info!("✅ Element brought forward");
-                            bring_forward_drawing_area.queue_draw();
-                        } else {
-                            tracing::warn!("⚠️  Element already at front or not found");
-                        }
-                    } else {
-                        tracing::warn!("⚠️  No active page");
-                    }
-                });
-            } else {
-                tracing::warn!("⚠️  No element selected");
-            }
-        });
+    let actions = [
+        (
+            "bring-to-front",
+            ZOrderChange::BringToFront,
+            &property_components.bring_to_front_btn,
+        ),
+        (
+            "bring-forward",
+            ZOrderChange::BringForward,
+            &property_components.bring_forward_btn,
+        ),
+        (
+            "send-backward",
+            ZOrderChange::SendBackward,
+            &property_components.send_backward_btn,
+        ),
+        (
+            "send-to-back",
+            ZOrderChange::SendToBack,
+            &property_components.send_to_back_btn,
+        ),
+    ];
 
-    let send_to_back_state = state.clone();
-    let send_to_back_drawing_area = canvas_view.drawing_area();
-    let send_to_back_render_state = canvas_view.render_state().selected_ids.clone();
-    property_components
-        .send_to_back_btn
-        .connect_clicked(move |_| {
-            tracing::info!("Action: send to back");
-            let selected_ids = send_to_back_render_state.borrow();
-            if let Some(element_id) = selected_ids.first().copied() {
-                send_to_back_state.with_active_document(|doc| {
-                    if let Some(page) = doc.pages.get_mut(0) {
-                        if page.send_to_back(element_id) {
-                            tracing::info!("✅ Element sent to back");
-                            send_to_back_drawing_area.queue_draw();
-                        } else {
-                            tracing::warn!("⚠️  Element not found");
-                        }
-                    } else {
-                        tracing::warn!("⚠️  No active page");
-                    }
-                });
-            } else {
+    for (name, change, button) in actions {
+        let zorder_state = state.clone();
+        let zorder_drawing_area = canvas_view.drawing_area();
+        let zorder_render_state = canvas_view.render_state().clone();
+        add_window_action(window, name, move |_| {
+            tracing::info!("Action: {}", name);
+            let selected_ids = zorder_render_state.selected_ids.borrow().clone();
+            if selected_ids.is_empty() {
                 tracing::warn!("⚠️  No element selected");
+                return;
             }
+
+            let page_index = zorder_state.active_page_index();
+            let command = crate::undo_redo::AppZOrderCommand::new(
+                zorder_state.clone(),
+                selected_ids,
+                page_index,
+                change,
+            );
+            zorder_state.push_command(Box::new(command));
+            zorder_drawing_area.queue_draw();
         });
 
-    let send_backward_state = state.clone();
-    let send_backward_drawing_area = canvas_view.drawing_area();
-    let send_backward_render_state = canvas_view.render_state().selected_ids.clone();
-    property_components
-        .send_backward_btn
-        .connect_clicked(move |_| {
-            tracing::info!("Action: send backward");
-            let selected_ids = send_backward_render_state.borrow();
-            if let Some(element_id) = selected_ids.first().copied() {
-                send_backward_state.with_active_document(|doc| {
-                    if let Some(page) = doc.pages.get_mut(0) {
-                        if page.send_backward(element_id) {
-                            tracing::info!("✅ Element sent backward");
-                            send_backward_drawing_area.queue_draw();
-                        } else {
-                            tracing::warn!("⚠️  Element already at back or not found");
-                        }
-                    } else {
-                        tracing::warn!("⚠️  No active page");
-                    }
-                });
-            } else {
-                tracing::warn!("⚠️  No element selected");
-            }
+        let action_name = format!("win.{}", name);
+        button.connect_clicked(move |button| {
+            let _ = button.activate_action(&action_name, None);
         });
+    }
 }
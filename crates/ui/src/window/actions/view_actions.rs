@@ -72,6 +72,64 @@ pub fn register(
         rulers_drawing_area.queue_draw();
     });
 
+    // View settings replaced wholesale (e.g. restored from an opened document)
+    let view_drawing_area = canvas_view.drawing_area();
+    let view_render_state = canvas_view.render_state().clone();
+    let view_buttons = toolbar_buttons.clone();
+    add_window_action(window, "view-state-changed", move |_| {
+        let config = view_render_state.config.borrow();
+        view_buttons
+            .zoom_100_btn
+            .set_label(&format_zoom_percent(config.zoom));
+        view_buttons.grid_toggle_btn.set_label(if config.show_grid {
+            "グリッド: ON"
+        } else {
+            "グリッド: OFF"
+        });
+        view_buttons.guides_visible_btn.set_label(if config.show_guides {
+            "ガイド: ON"
+        } else {
+            "ガイド: OFF"
+        });
+        view_buttons.rulers_visible_btn.set_label(if config.show_rulers {
+            "ルーラー: ON"
+        } else {
+            "ルーラー: OFF"
+        });
+        drop(config);
+        view_drawing_area.queue_draw();
+    });
+
+    let unit_drawing_area = canvas_view.drawing_area();
+    let unit_render_state = canvas_view.render_state().clone();
+    let unit_btn = toolbar_buttons.ruler_unit_btn.clone();
+    add_window_action(window, "cycle-ruler-unit", move |_| {
+        let mut ruler_config = unit_render_state.ruler_config.borrow_mut();
+        ruler_config.unit = ruler_config.unit.next();
+        let unit = ruler_config.unit;
+        drop(ruler_config);
+        tracing::info!("✅ Ruler unit switched to {}", unit.label());
+        unit_btn.set_label(&crate::toolbar::ruler_unit_label(unit));
+        unit_drawing_area.queue_draw();
+    });
+
+    // Grid spacing spinner: drawing and snapping both read the same value
+    let spacing_drawing_area = canvas_view.drawing_area();
+    let spacing_render_state = canvas_view.render_state().clone();
+    toolbar_buttons
+        .grid_spacing_spin
+        .connect_value_changed(move |spin| {
+            let spacing = spin.value() as f32;
+            let mut config = spacing_render_state.config.borrow_mut();
+            if config.grid_spacing() == spacing {
+                return;
+            }
+            config.set_grid_spacing(spacing);
+            drop(config);
+            tracing::info!("✅ Grid spacing set to {}px", spacing);
+            spacing_drawing_area.queue_draw();
+        });
+
     let tool_palette_toggle = tool_palette.clone();
     add_window_action(window, "toggle-tool-palette", move |_| {
         tracing::info!("Action: toggle tool palette");
@@ -130,35 +188,34 @@ pub fn register(
         zoom_in_canvas.queue_draw();
     });
 
-    // Zoom to fit window (Ctrl+1)
+    // Zoom to fit window (Ctrl+1): fit the page into the visible area and center it
     let zoom_fit_canvas = canvas_view.drawing_area();
+    let zoom_fit_container = canvas_view.container();
     let zoom_fit_state = canvas_view.render_state().clone();
     let zoom_fit_btn = toolbar_buttons.zoom_100_btn.clone();
     let zoom_fit_app_state = app_state.clone();
     add_window_action(window, "zoom-fit-window", move |_| {
         tracing::info!("Action: zoom to fit window");
 
-        // Get page size from document
+        // Fit the active page, which may override the document's page size
         let page_size = zoom_fit_app_state
-            .active_document()
-            .map(|doc| doc.metadata.page_size.to_size())
+            .active_page_size()
             .unwrap_or(testruct_core::layout::Size::new(800.0, 600.0));
 
-        // Get canvas size
-        let canvas_width = zoom_fit_canvas.width() as f64 - 40.0; // margin for rulers
-        let canvas_height = zoom_fit_canvas.height() as f64 - 40.0;
-
-        // Calculate zoom to fit
-        let zoom_x = canvas_width / page_size.width as f64;
-        let zoom_y = canvas_height / page_size.height as f64;
-        let new_zoom = zoom_x.min(zoom_y).clamp(0.1, 4.0);
+        // Visible area of the scrolled canvas (rulers are drawn inside it)
+        let viewport_width = zoom_fit_container.width() as f64;
+        let viewport_height = zoom_fit_container.height() as f64;
+        let ruler_size = zoom_fit_state.ruler_config.borrow().size;
 
         let mut config = zoom_fit_state.config.borrow_mut();
-        config.zoom = new_zoom;
-        config.pan_x = 0.0;
-        config.pan_y = 0.0;
+        config.fit_page_to_viewport(&page_size, viewport_width, viewport_height, ruler_size);
+        let new_zoom = config.zoom;
         drop(config);
 
+        // Pan is relative to the widget origin, so scroll back to it
+        zoom_fit_container.hadjustment().set_value(0.0);
+        zoom_fit_container.vadjustment().set_value(0.0);
+
         zoom_fit_btn.set_label(&format_zoom_percent(new_zoom));
         tracing::info!("✅ Zoom fit to window: {:.0}%", new_zoom * 100.0);
         zoom_fit_canvas.queue_draw();
@@ -248,6 +305,19 @@ pub fn register(
         snap_guides_canvas.queue_draw();
     });
 
+    // Snap to object edges toggle
+    let snap_objects_canvas = canvas_view.drawing_area();
+    let snap_objects_state = canvas_view.render_state().clone();
+    add_window_action(window, "toggle-snap-objects", move |_| {
+        tracing::info!("Action: toggle snap to objects");
+        let mut config = snap_objects_state.config.borrow_mut();
+        config.snap_to_objects = !config.snap_to_objects;
+        let new_state = config.snap_to_objects;
+        drop(config);
+        tracing::info!("✅ Snap to objects toggled: {}", new_state);
+        snap_objects_canvas.queue_draw();
+    });
+
     // Snap to grid toggle
     let snap_grid_canvas = canvas_view.drawing_area();
     let snap_grid_state = canvas_view.render_state().clone();
@@ -9,6 +9,13 @@ pub fn bind_events(components: &WindowComponents, state: AppState) {
     // Bind toolbar buttons to window actions
     bind_toolbar_buttons(components);
 
+    // Guide manager popover in the view menu
+    crate::toolbar::guide_list::wire_guide_list(
+        &components.toolbar,
+        &components.canvas_view,
+        &state,
+    );
+
     // Bind tool palette buttons to tool switching
     bind_tool_selection(components);
 
@@ -53,6 +60,7 @@ fn bind_toolbar_buttons(components: &WindowComponents) {
     bind_button(&toolbar.zoom_out_btn, window, "win.zoom-out");
     bind_button(&toolbar.zoom_100_btn, window, "win.zoom-100");
     bind_button(&toolbar.zoom_in_btn, window, "win.zoom-in");
+    bind_button(&toolbar.zoom_fit_btn, window, "win.zoom-fit-window");
 
     // Object operations (Secondary toolbar)
     bind_button(&toolbar.group_btn, window, "win.group");
@@ -66,9 +74,15 @@ fn bind_toolbar_buttons(components: &WindowComponents) {
         window,
         "win.toggle-snap-guides",
     );
+    bind_toggle_button(
+        &toolbar.snap_to_objects_toggle,
+        window,
+        "win.toggle-snap-objects",
+    );
 
     // Popover toggles
     bind_toggle_button(&toolbar.ruler_menu_toggle, window, "win.toggle-rulers");
+    bind_button(&toolbar.ruler_unit_btn, window, "win.cycle-ruler-unit");
     bind_toggle_button(&toolbar.guides_menu_toggle, window, "win.toggle-guides");
 }
 
@@ -127,6 +141,9 @@ fn bind_tool_selection(components: &WindowComponents) {
             all_buttons.circle_btn.set_active(false);
             all_buttons.line_btn.set_active(false);
             all_buttons.arrow_btn.set_active(false);
+            all_buttons.polygon_btn.set_active(false);
+            all_buttons.eyedropper_btn.set_active(false);
+            all_buttons.measure_btn.set_active(false);
             let mut tool_state = state_select.tool_state.borrow_mut();
             tool_state.current_tool = ToolMode::Select;
             tracing::info!("✅ Tool switched to: Select");
@@ -147,6 +164,9 @@ fn bind_tool_selection(components: &WindowComponents) {
             all_buttons.circle_btn.set_active(false);
             all_buttons.line_btn.set_active(false);
             all_buttons.arrow_btn.set_active(false);
+            all_buttons.polygon_btn.set_active(false);
+            all_buttons.eyedropper_btn.set_active(false);
+            all_buttons.measure_btn.set_active(false);
             let mut tool_state = state_text.tool_state.borrow_mut();
             tool_state.current_tool = ToolMode::Text;
             tracing::info!("✅ Tool switched to: 📝 Text");
@@ -167,6 +187,9 @@ fn bind_tool_selection(components: &WindowComponents) {
             all_buttons.circle_btn.set_active(false);
             all_buttons.line_btn.set_active(false);
             all_buttons.arrow_btn.set_active(false);
+            all_buttons.polygon_btn.set_active(false);
+            all_buttons.eyedropper_btn.set_active(false);
+            all_buttons.measure_btn.set_active(false);
             let mut tool_state = state_rect.tool_state.borrow_mut();
             tool_state.current_tool = ToolMode::Rectangle;
             tracing::info!("✅ Tool switched to: Rectangle");
@@ -187,6 +210,9 @@ fn bind_tool_selection(components: &WindowComponents) {
             all_buttons.rect_btn.set_active(false);
             all_buttons.line_btn.set_active(false);
             all_buttons.arrow_btn.set_active(false);
+            all_buttons.polygon_btn.set_active(false);
+            all_buttons.eyedropper_btn.set_active(false);
+            all_buttons.measure_btn.set_active(false);
             let mut tool_state = state_circle.tool_state.borrow_mut();
             tool_state.current_tool = ToolMode::Circle;
             tracing::info!("✅ Tool switched to: Circle");
@@ -207,6 +233,9 @@ fn bind_tool_selection(components: &WindowComponents) {
             all_buttons.rect_btn.set_active(false);
             all_buttons.circle_btn.set_active(false);
             all_buttons.arrow_btn.set_active(false);
+            all_buttons.polygon_btn.set_active(false);
+            all_buttons.eyedropper_btn.set_active(false);
+            all_buttons.measure_btn.set_active(false);
             let mut tool_state = state_line.tool_state.borrow_mut();
             tool_state.current_tool = ToolMode::Line;
             tracing::info!("✅ Tool switched to: Line");
@@ -227,6 +256,9 @@ fn bind_tool_selection(components: &WindowComponents) {
             all_buttons.rect_btn.set_active(false);
             all_buttons.circle_btn.set_active(false);
             all_buttons.line_btn.set_active(false);
+            all_buttons.polygon_btn.set_active(false);
+            all_buttons.eyedropper_btn.set_active(false);
+            all_buttons.measure_btn.set_active(false);
             let mut tool_state = state_arrow.tool_state.borrow_mut();
             tool_state.current_tool = ToolMode::Arrow;
             tracing::info!("✅ Tool switched to: Arrow");
@@ -235,6 +267,30 @@ fn bind_tool_selection(components: &WindowComponents) {
         }
     });
 
+    // Polygon tool button
+    let state_polygon = render_state.clone();
+    let drawing_area_polygon = drawing_area.clone();
+    let all_buttons = tool_buttons.clone();
+    tool_buttons.polygon_btn.connect_toggled(move |btn| {
+        if btn.is_active() {
+            all_buttons.select_btn.set_active(false);
+            all_buttons.text_btn.set_active(false);
+            all_buttons.image_btn.set_active(false);
+            all_buttons.rect_btn.set_active(false);
+            all_buttons.circle_btn.set_active(false);
+            all_buttons.line_btn.set_active(false);
+            all_buttons.arrow_btn.set_active(false);
+            all_buttons.eyedropper_btn.set_active(false);
+            all_buttons.measure_btn.set_active(false);
+            let mut tool_state = state_polygon.tool_state.borrow_mut();
+            tool_state.current_tool = ToolMode::Polygon;
+            tool_state.polygon_points.clear();
+            tracing::info!("✅ Tool switched to: Polygon");
+            drop(tool_state);
+            drawing_area_polygon.queue_draw();
+        }
+    });
+
     // Image tool button
     let state_image = render_state.clone();
     let drawing_area_image = drawing_area.clone();
@@ -247,6 +303,9 @@ fn bind_tool_selection(components: &WindowComponents) {
             all_buttons.circle_btn.set_active(false);
             all_buttons.line_btn.set_active(false);
             all_buttons.arrow_btn.set_active(false);
+            all_buttons.polygon_btn.set_active(false);
+            all_buttons.eyedropper_btn.set_active(false);
+            all_buttons.measure_btn.set_active(false);
             let mut tool_state = state_image.tool_state.borrow_mut();
             tool_state.current_tool = ToolMode::Image;
             tracing::info!("✅ Tool switched to: Image");
@@ -254,6 +313,52 @@ fn bind_tool_selection(components: &WindowComponents) {
             drawing_area_image.queue_draw();
         }
     });
+
+    // Eyedropper tool button
+    let state_eyedropper = render_state.clone();
+    let drawing_area_eyedropper = drawing_area.clone();
+    let all_buttons = tool_buttons.clone();
+    tool_buttons.eyedropper_btn.connect_toggled(move |btn| {
+        if btn.is_active() {
+            all_buttons.select_btn.set_active(false);
+            all_buttons.text_btn.set_active(false);
+            all_buttons.image_btn.set_active(false);
+            all_buttons.rect_btn.set_active(false);
+            all_buttons.circle_btn.set_active(false);
+            all_buttons.line_btn.set_active(false);
+            all_buttons.arrow_btn.set_active(false);
+            all_buttons.polygon_btn.set_active(false);
+            all_buttons.measure_btn.set_active(false);
+            let mut tool_state = state_eyedropper.tool_state.borrow_mut();
+            tool_state.current_tool = ToolMode::Eyedropper;
+            tracing::info!("✅ Tool switched to: 🎨 Eyedropper");
+            drop(tool_state);
+            drawing_area_eyedropper.queue_draw();
+        }
+    });
+
+    // Measure tool button: the measurement disappears with the tool
+    let state_measure = render_state.clone();
+    let drawing_area_measure = drawing_area.clone();
+    let all_buttons = tool_buttons.clone();
+    tool_buttons.measure_btn.connect_toggled(move |btn| {
+        if btn.is_active() {
+            all_buttons.select_btn.set_active(false);
+            all_buttons.text_btn.set_active(false);
+            all_buttons.image_btn.set_active(false);
+            all_buttons.rect_btn.set_active(false);
+            all_buttons.circle_btn.set_active(false);
+            all_buttons.line_btn.set_active(false);
+            all_buttons.arrow_btn.set_active(false);
+            all_buttons.polygon_btn.set_active(false);
+            all_buttons.eyedropper_btn.set_active(false);
+            state_measure.tool_state.borrow_mut().current_tool = ToolMode::Measure;
+            tracing::info!("✅ Tool switched to: 📏 Measure");
+        } else {
+            state_measure.tool_state.borrow_mut().measure_line = None;
+        }
+        drawing_area_measure.queue_draw();
+    });
 }
 
 /// Monitor tool state changes and update UI buttons accordingly
@@ -281,6 +386,9 @@ fn setup_tool_monitor(components: &WindowComponents) {
             tool_buttons.circle_btn.set_active(false);
             tool_buttons.line_btn.set_active(false);
             tool_buttons.arrow_btn.set_active(false);
+            tool_buttons.polygon_btn.set_active(false);
+            tool_buttons.eyedropper_btn.set_active(false);
+            tool_buttons.measure_btn.set_active(false);
 
             // Activate the correct button for the current tool
             match current_tool {
@@ -291,6 +399,9 @@ fn setup_tool_monitor(components: &WindowComponents) {
                 ToolMode::Circle => tool_buttons.circle_btn.set_active(true),
                 ToolMode::Line => tool_buttons.line_btn.set_active(true),
                 ToolMode::Arrow => tool_buttons.arrow_btn.set_active(true),
+                ToolMode::Polygon => tool_buttons.polygon_btn.set_active(true),
+                ToolMode::Eyedropper => tool_buttons.eyedropper_btn.set_active(true),
+                ToolMode::Measure => tool_buttons.measure_btn.set_active(true),
                 ToolMode::Pan => {} // Pan tool doesn't have a button in the palette
             }
         }
@@ -299,29 +410,47 @@ fn setup_tool_monitor(components: &WindowComponents) {
     });
 }
 
-/// Monitor selection changes and update property panel accordingly
+/// Monitor selection changes and notify selection listeners
+///
+/// Listeners registered on the render state update the property panel and
+/// the toolbar's object operation buttons.
 fn setup_selection_monitor(components: &WindowComponents, app_state: AppState) {
     use gtk4::glib;
 
     let render_state = components.canvas_view.render_state().clone();
+
     let property_components = components.property_components.clone();
+    let property_state = app_state.clone();
+    render_state.connect_selection_changed(move |selection| {
+        // Update the property panel text buffer with the selected element's content
+        crate::panels::update_property_panel_on_selection(
+            &property_components,
+            &property_state,
+            selection,
+        );
+    });
+
+    let toolbar_buttons = components.toolbar.buttons.clone();
+    render_state.connect_selection_changed(move |selection| {
+        let elements = app_state
+            .active_page()
+            .map(|page| page.elements)
+            .unwrap_or_default();
+        crate::toolbar::selection_state::ObjectActionState::from_selection(&elements, selection)
+            .apply(&toolbar_buttons);
+    });
+
     let mut last_selection: Vec<uuid::Uuid> = Vec::new();
 
     // Set up a periodic check every 50ms to detect selection changes
     glib::source::timeout_add_local(std::time::Duration::from_millis(50), move || {
         let current_selection = render_state.selected_ids.borrow().clone();
 
-        // Only update property panel if selection has changed
+        // Only notify listeners if selection has changed
         if current_selection != last_selection {
-            last_selection = current_selection.clone();
-            tracing::debug!("✅ Selection changed, updating property panel");
-
-            // Update the property panel text buffer with the selected element's content
-            crate::panels::update_property_panel_on_selection(
-                &property_components,
-                &app_state,
-                &current_selection,
-            );
+            last_selection = current_selection;
+            tracing::debug!("✅ Selection changed, notifying listeners");
+            render_state.notify_selection_changed();
         }
 
         glib::ControlFlow::Continue
@@ -25,7 +25,7 @@ pub fn build_widgets(app: &Application, app_state: AppState) -> WindowComponents
     // Menu bar
     eprintln!("📋 Building menu bar...");
     let t1 = std::time::Instant::now();
-    let menu_bar = build_menu_bar();
+    let (menu_bar, recent_files_menu) = build_menu_bar();
     eprintln!("⏱️  Menu bar built: {}ms", t1.elapsed().as_millis());
     root.append(&menu_bar);
 
@@ -47,9 +47,11 @@ pub fn build_widgets(app: &Application, app_state: AppState) -> WindowComponents
         tool_palette,
         properties_panel,
         property_components,
+        swatches_components,
     ) = layout_v2::build_layout(app_state.clone(), toolbar_widgets.clone());
     eprintln!("⏱️  Main layout built: {}ms", t3.elapsed().as_millis());
     root.append(&main_content);
+    root.append(&crate::panels::build_status_bar(&canvas_view, app_state.clone()));
 
     eprintln!("🎯 Setting window content...");
     window.set_child(Some(&root));
@@ -67,10 +69,12 @@ pub fn build_widgets(app: &Application, app_state: AppState) -> WindowComponents
         layer_panel,
         properties_panel.clone(),
         menu_bar,
+        recent_files_menu,
         toolbar_widgets,
         tool_palette_buttons,
         tool_palette,
         properties_panel,
         property_components,
+        swatches_components,
     )
 }
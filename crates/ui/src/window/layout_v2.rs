@@ -1,7 +1,7 @@
 //! Complete window layout matching the original design
 //!
 //! Implements the 3-pane layout with:
-//! - Left: Tool Palette (fixed width)
+//! - Left: Tool Palette (fixed width) and page thumbnails
 //! - Center: Canvas with overlay panels and page navigation
 //! - Right: Properties Panel (fixed width)
 //! - Bottom: Status Bar
@@ -24,6 +24,9 @@ pub struct ToolPaletteButtons {
     pub circle_btn: ToggleButton,
     pub line_btn: ToggleButton,
     pub arrow_btn: ToggleButton,
+    pub polygon_btn: ToggleButton,
+    pub eyedropper_btn: ToggleButton,
+    pub measure_btn: ToggleButton,
 }
 
 /// Build the complete window layout (content only - menubar and toolbars are added separately)
@@ -37,6 +40,7 @@ pub fn build_layout(
     GtkBox,
     GtkBox,
     crate::panels::PropertyPanelComponents,
+    crate::panels::SwatchesPanelComponents,
 ) {
     let main_container = GtkBox::new(Orientation::Vertical, 0);
 
@@ -54,11 +58,18 @@ pub fn build_layout(
 
     // CENTER: Canvas with overlays
     let canvas_view = CanvasView::new(app_state.clone());
+
+    // Page thumbnails between the tool palette and the canvas
+    let page_strip = crate::panels::build_page_strip(&app_state, &canvas_view);
+    panes_box.append(&page_strip);
+    panes_box.append(&Separator::new(Orientation::Vertical));
+
     let (canvas_section, _page_nav_bar) = build_canvas_section(&canvas_view);
     panes_box.append(&canvas_section);
 
-    // RIGHT: Properties Panel with Item Library
-    let (properties_panel, property_components) = build_properties_panel(&app_state);
+    // RIGHT: Properties Panel with Item Library, Swatches and Layers
+    let (properties_panel, property_components, swatches_components) =
+        build_properties_panel(&app_state, &canvas_view);
     panes_box.append(&properties_panel);
 
     main_container.append(&panes_box);
@@ -74,6 +85,7 @@ pub fn build_layout(
         tool_palette,
         properties_panel,
         property_components,
+        swatches_components,
     )
 }
 
@@ -112,6 +124,22 @@ fn build_tool_palette() -> (GtkBox, ToolPaletteButtons) {
     image_btn.set_tooltip_text(Some("画像ツール (I)"));
     palette.append(&image_btn);
 
+    let eyedropper_btn = ToggleButton::with_label("スポイト");
+    eyedropper_btn.add_css_class("tool-button");
+    eyedropper_btn.set_halign(Align::Fill);
+    eyedropper_btn.set_tooltip_text(Some(
+        "スポイトツール (K): クリックで色を取得して塗りに適用、Shift+クリックで線に適用",
+    ));
+    palette.append(&eyedropper_btn);
+
+    let measure_btn = ToggleButton::with_label("計測");
+    measure_btn.add_css_class("tool-button");
+    measure_btn.set_halign(Align::Fill);
+    measure_btn.set_tooltip_text(Some(
+        "計測ツール (M): ドラッグで 2 点間の距離をルーラーの単位で表示",
+    ));
+    palette.append(&measure_btn);
+
     palette.append(&Separator::new(Orientation::Horizontal));
 
     // Shapes section
@@ -123,23 +151,35 @@ fn build_tool_palette() -> (GtkBox, ToolPaletteButtons) {
     let rect_btn = ToggleButton::with_label("長方形");
     rect_btn.add_css_class("tool-button");
     rect_btn.set_halign(Align::Fill);
+    rect_btn.set_tooltip_text(Some("長方形ツール (R)"));
     palette.append(&rect_btn);
 
     let circle_btn = ToggleButton::with_label("円");
     circle_btn.add_css_class("tool-button");
     circle_btn.set_halign(Align::Fill);
+    circle_btn.set_tooltip_text(Some("円ツール (E)"));
     palette.append(&circle_btn);
 
     let line_btn = ToggleButton::with_label("直線");
     line_btn.add_css_class("tool-button");
     line_btn.set_halign(Align::Fill);
+    line_btn.set_tooltip_text(Some("直線ツール (L)"));
     palette.append(&line_btn);
 
     let arrow_btn = ToggleButton::with_label("矢印");
     arrow_btn.add_css_class("tool-button");
     arrow_btn.set_halign(Align::Fill);
+    arrow_btn.set_tooltip_text(Some("矢印ツール (A)"));
     palette.append(&arrow_btn);
 
+    let polygon_btn = ToggleButton::with_label("多角形");
+    polygon_btn.add_css_class("tool-button");
+    polygon_btn.set_halign(Align::Fill);
+    polygon_btn.set_tooltip_text(Some(
+        "多角形ツール (P): クリックで頂点を追加、ダブルクリックまたは Enter で確定",
+    ));
+    palette.append(&polygon_btn);
+
     let tool_buttons = ToolPaletteButtons {
         select_btn,
         text_btn,
@@ -148,6 +188,9 @@ fn build_tool_palette() -> (GtkBox, ToolPaletteButtons) {
         circle_btn,
         line_btn,
         arrow_btn,
+        polygon_btn,
+        eyedropper_btn,
+        measure_btn,
     };
 
     (palette, tool_buttons)
@@ -189,11 +232,13 @@ fn build_page_nav_bar() -> GtkBox {
 
     let prev_btn = Button::with_label("◀");
     prev_btn.add_css_class("flat");
+    prev_btn.set_action_name(Some("win.prev-page"));
     prev_btn.set_tooltip_text(Some("前のページ (PageUp)"));
     nav_buttons.append(&prev_btn);
 
     let next_btn = Button::with_label("▶");
     next_btn.add_css_class("flat");
+    next_btn.set_action_name(Some("win.next-page"));
     next_btn.set_tooltip_text(Some("次のページ (PageDown)"));
     nav_buttons.append(&next_btn);
 
@@ -220,26 +265,31 @@ fn build_page_nav_bar() -> GtkBox {
 
     let add_btn = Button::with_label("追加");
     add_btn.add_css_class("flat");
+    add_btn.set_action_name(Some("win.add-page"));
     add_btn.set_tooltip_text(Some("ページを追加 (Ctrl+Shift+N)"));
     action_buttons.append(&add_btn);
 
     let duplicate_btn = Button::with_label("複製");
     duplicate_btn.add_css_class("flat");
+    duplicate_btn.set_action_name(Some("win.duplicate-page"));
     duplicate_btn.set_tooltip_text(Some("現在のページを複製"));
     action_buttons.append(&duplicate_btn);
 
     let move_up_btn = Button::with_label("↑");
     move_up_btn.add_css_class("flat");
+    move_up_btn.set_action_name(Some("win.move-page-up"));
     move_up_btn.set_tooltip_text(Some("ページを上に移動"));
     action_buttons.append(&move_up_btn);
 
     let move_down_btn = Button::with_label("↓");
     move_down_btn.add_css_class("flat");
+    move_down_btn.set_action_name(Some("win.move-page-down"));
     move_down_btn.set_tooltip_text(Some("ページを下に移動"));
     action_buttons.append(&move_down_btn);
 
     let delete_btn = Button::with_label("削除");
     delete_btn.add_css_class("flat");
+    delete_btn.set_action_name(Some("win.delete-page"));
     delete_btn.set_tooltip_text(Some("現在のページを削除"));
     action_buttons.append(&delete_btn);
 
@@ -251,7 +301,12 @@ fn build_page_nav_bar() -> GtkBox {
 /// Build the right properties panel with tabbed interface
 fn build_properties_panel(
     app_state: &AppState,
-) -> (GtkBox, crate::panels::PropertyPanelComponents) {
+    canvas_view: &CanvasView,
+) -> (
+    GtkBox,
+    crate::panels::PropertyPanelComponents,
+    crate::panels::SwatchesPanelComponents,
+) {
     let properties = GtkBox::new(Orientation::Vertical, 0);
     properties.add_css_class("properties-panel");
     properties.set_width_request(240); // Fixed width
@@ -276,7 +331,17 @@ fn build_properties_panel(
     let item_lib_label = Label::new(Some("アイテムライブラリ"));
     notebook.append_page(&item_lib_components.container, Some(&item_lib_label));
 
-    (properties, property_components)
+    // Tab 3: Swatches (wired once the canvas exists, see MainWindow::build)
+    let swatches_components = crate::panels::build_swatches_panel();
+    let swatches_label = Label::new(Some("スウォッチ"));
+    notebook.append_page(&swatches_components.container, Some(&swatches_label));
+
+    // Tab 4: Layers of the active page
+    let layers_panel = crate::panels::LayersPanel::new(app_state, canvas_view);
+    let layers_label = Label::new(Some("レイヤー"));
+    notebook.append_page(layers_panel.widget(), Some(&layers_label));
+
+    (properties, property_components, swatches_components)
 }
 
 /// Build the status bar
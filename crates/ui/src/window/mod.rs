@@ -3,10 +3,13 @@ mod bindings;
 mod layout;
 mod layout_v2;
 
-use crate::app::{start_auto_save_timer, AppState, DEFAULT_AUTO_SAVE_DELAY_SECS};
+use crate::app::{
+    start_auto_save_timer, start_recovery_timer, AppState, DEFAULT_AUTO_SAVE_DELAY_SECS,
+};
 use crate::canvas::CanvasView;
 use crate::toolbar::ToolbarWidgets;
 use crate::window::layout_v2::ToolPaletteButtons;
+use gtk4::prelude::*;
 use gtk4::{Application, ApplicationWindow};
 
 pub struct MainWindow {
@@ -20,6 +23,20 @@ impl MainWindow {
         // Store window reference in AppState for later access
         state.set_window(&components.window);
 
+        // Seed canvas snapping/hit-testing from the project settings
+        components
+            .canvas_view
+            .render_state()
+            .config
+            .borrow_mut()
+            .apply_project_settings(&state.project().settings);
+        components
+            .canvas_view
+            .render_state()
+            .spell_check
+            .borrow_mut()
+            .set_language(state.project().settings.spell_check_language.as_deref());
+
         actions::register_window_actions(
             &components.window,
             state.clone(),
@@ -29,14 +46,63 @@ impl MainWindow {
             &components.property_components,
             &components.toolbar.buttons,
         );
+        actions::register_recent_files_menu(
+            &components.window,
+            state.clone(),
+            &components.canvas_view,
+            &components.recent_files_menu,
+        );
         crate::panels::wire_property_signals(
             &components.property_components,
             state.clone(),
             &components.canvas_view,
         );
+        crate::panels::wire_swatches_panel(
+            &components.swatches_components,
+            state.clone(),
+            &components.canvas_view,
+            &components.property_components,
+        );
         bindings::bind_events(&components, state.clone());
 
-        // Start auto-save timer
+        // Offer to recover unsaved changes from a previous session once the window is up
+        let recovery_parent = components.window.clone().upcast::<gtk4::Window>();
+        let recovery_state = state.clone();
+        let recovery_drawing_area = components.canvas_view.drawing_area();
+        let recovery_render_state = components.canvas_view.render_state().clone();
+        gtk4::glib::spawn_future_local(async move {
+            crate::dialogs::offer_crash_recovery(
+                &recovery_parent,
+                recovery_state,
+                recovery_drawing_area,
+                recovery_render_state,
+            )
+            .await;
+        });
+
+        // Ask before closing a window with unsaved changes
+        let close_state = state.clone();
+        let close_confirmed = std::rc::Rc::new(std::cell::Cell::new(false));
+        components.window.connect_close_request(move |window| {
+            if close_confirmed.get() || !close_state.is_modified() {
+                return false.into();
+            }
+
+            let window = window.clone();
+            let state = close_state.clone();
+            let confirmed = close_confirmed.clone();
+            gtk4::glib::spawn_future_local(async move {
+                let parent = window.clone().upcast::<gtk4::Window>();
+                if crate::dialogs::check_unsaved_changes(&state, &parent).await {
+                    confirmed.set(true);
+                    window.close();
+                }
+            });
+            true.into()
+        });
+
+        // Start auto-save and crash recovery timers
+        let _recovery_source = start_recovery_timer(state.clone());
         let _auto_save_source = start_auto_save_timer(state, DEFAULT_AUTO_SAVE_DELAY_SECS);
 
         components.window
@@ -49,11 +115,14 @@ pub struct WindowComponents {
     pub layer_panel: gtk4::ListView,
     pub property_panel: gtk4::Box,
     pub menu_bar: gtk4::PopoverMenuBar,
+    /// File > Open Recent section, rebuilt by `win.recent-files-changed`
+    pub recent_files_menu: gtk4::gio::Menu,
     pub toolbar: ToolbarWidgets,
     pub tool_palette_buttons: ToolPaletteButtons,
     pub tool_palette: gtk4::Box,
     pub properties_panel: gtk4::Box,
     pub property_components: crate::panels::PropertyPanelComponents,
+    pub swatches_components: crate::panels::SwatchesPanelComponents,
 }
 
 impl WindowComponents {
@@ -63,11 +132,13 @@ impl WindowComponents {
         layer_panel: gtk4::ListView,
         property_panel: gtk4::Box,
         menu_bar: gtk4::PopoverMenuBar,
+        recent_files_menu: gtk4::gio::Menu,
         toolbar: ToolbarWidgets,
         tool_palette_buttons: ToolPaletteButtons,
         tool_palette: gtk4::Box,
         properties_panel: gtk4::Box,
         property_components: crate::panels::PropertyPanelComponents,
+        swatches_components: crate::panels::SwatchesPanelComponents,
     ) -> Self {
         Self {
             window,
@@ -75,11 +146,13 @@ impl WindowComponents {
             layer_panel,
             property_panel,
             menu_bar,
+            recent_files_menu,
             toolbar,
             tool_palette_buttons,
             tool_palette,
             properties_panel,
             property_components,
+            swatches_components,
         }
     }
 }
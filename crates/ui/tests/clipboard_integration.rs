@@ -4,8 +4,10 @@
 //! when accessing the shared clipboard state.
 
 use std::sync::Mutex;
-use testruct_core::document::{DocumentElement, ShapeElement, ShapeKind, TextElement};
-use testruct_core::layout::{Point, Rect, Size};
+use testruct_core::document::{
+    DocumentElement, Fill, ShapeElement, ShapeKind, TextElement, DEFAULT_TEXT_PADDING,
+};
+use testruct_core::layout::{Insets, Point, Rect, Size};
 use testruct_core::typography::{Color, TextStyle};
 use testruct_ui::clipboard;
 use uuid::Uuid;
@@ -26,9 +28,14 @@ fn create_test_shape(x: f32, y: f32) -> DocumentElement {
         },
         stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
         stroke_width: 1.0,
-        fill: Some(Color::from_rgb(1.0, 0.0, 0.0)),
+        fill: Some(Fill::Solid(Color::from_rgb(1.0, 0.0, 0.0))),
         visible: true,
         locked: false,
+        rotation: 0.0,
+        vertices: Vec::new(),
+        line_style: Default::default(),
+        opacity: 1.0,
+        corner_radius: 0.0,
     })
 }
 
@@ -47,6 +54,11 @@ fn create_test_text(x: f32, y: f32, content: &str) -> DocumentElement {
         auto_resize_height: false,
         visible: true,
         locked: false,
+        rotation: 0.0,
+        rich_text: None,
+        padding: Insets::uniform(DEFAULT_TEXT_PADDING),
+        text_path: None,
+        opacity: 1.0,
     })
 }
 
@@ -367,9 +379,14 @@ fn test_element_properties_preserved() {
         },
         stroke: Some(Color::from_rgb(0.5, 0.3, 0.1)),
         stroke_width: 3.5,
-        fill: Some(Color::from_rgb(0.8, 0.6, 0.4)),
+        fill: Some(Fill::Solid(Color::from_rgb(0.8, 0.6, 0.4))),
         visible: true,
         locked: false,
+        rotation: 0.0,
+        vertices: Vec::new(),
+        line_style: Default::default(),
+        opacity: 1.0,
+        corner_radius: 0.0,
     });
 
     clipboard::copy_to_clipboard(vec![original]);
@@ -409,6 +426,11 @@ fn test_text_content_preserved() {
         auto_resize_height: true,
         visible: true,
         locked: false,
+        rotation: 0.0,
+        rich_text: None,
+        padding: Insets::uniform(DEFAULT_TEXT_PADDING),
+        text_path: None,
+        opacity: 1.0,
     });
 
     clipboard::copy_to_clipboard(vec![original]);
@@ -2,7 +2,7 @@
 
 use std::sync::{Arc, Mutex};
 use testruct_core::document::{
-    Document, DocumentBuilder, DocumentElement, Page, ShapeElement, ShapeKind,
+    Document, DocumentBuilder, DocumentElement, Fill, Page, ShapeElement, ShapeKind,
 };
 use testruct_core::layout::{Point, Rect, Size};
 use testruct_core::typography::Color;
@@ -33,9 +33,14 @@ fn create_test_document_with_layers() -> Arc<Mutex<Document>> {
                 },
                 stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
                 stroke_width: 1.0,
-                fill: Some(Color::from_rgb(1.0, 0.0, 0.0)),
+                fill: Some(Fill::Solid(Color::from_rgb(1.0, 0.0, 0.0))),
                 visible: true,
                 locked: false,
+                rotation: 0.0,
+                vertices: Vec::new(),
+                line_style: Default::default(),
+                opacity: 1.0,
+                corner_radius: 0.0,
             }));
         }
     }
@@ -3,7 +3,7 @@
 use std::fs;
 use std::path::PathBuf;
 use testruct_core::document::{
-    Document, DocumentBuilder, DocumentElement, Page, ShapeElement, ShapeKind,
+    Document, DocumentBuilder, DocumentElement, Fill, Page, ShapeElement, ShapeKind,
 };
 use testruct_core::layout::Rect;
 use testruct_core::typography::Color;
@@ -30,9 +30,14 @@ fn create_test_document() -> Document {
             },
             stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
             stroke_width: 1.0,
-            fill: Some(Color::from_rgb(1.0, 0.0, 0.0)),
+            fill: Some(Fill::Solid(Color::from_rgb(1.0, 0.0, 0.0))),
             visible: true,
             locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
         });
         page.add_element(shape);
     }
@@ -135,6 +140,41 @@ fn test_save_load_roundtrip() {
     let _ = fs::remove_file(&file_path);
 }
 
+#[test]
+fn test_view_state_survives_save_and_load() {
+    use testruct_ui::app::AppState;
+    use testruct_ui::canvas::rendering::{Guide, GuideOrientation, RenderConfig};
+
+    let file_path = std::env::temp_dir().join("testruct_view_state_test.json");
+    let _ = fs::remove_file(&file_path);
+
+    let mut config = RenderConfig::default();
+    config.zoom = 1.75;
+    config.pan_x = -30.0;
+    config.pan_y = 12.0;
+    config.show_grid = false;
+    config.guides = vec![Guide::new(GuideOrientation::Horizontal, 200.0)];
+
+    let state = AppState::default();
+    state.set_view_state(config.view_state());
+    let document = state.active_document().expect("active document");
+    testruct_ui::io::save_document(&document, &file_path).expect("Failed to save document");
+
+    let loaded = testruct_ui::io::load_document(&file_path).expect("Failed to load document");
+    let mut restored = RenderConfig::default();
+    restored.apply_view_state(loaded.metadata.view.as_ref().expect("view state saved"));
+
+    assert_eq!(restored.zoom, 1.75);
+    assert_eq!(restored.pan_x, -30.0);
+    assert_eq!(restored.pan_y, 12.0);
+    assert!(!restored.show_grid);
+    assert_eq!(restored.guides.len(), 1);
+    assert_eq!(restored.guides[0].orientation, GuideOrientation::Horizontal);
+    assert_eq!(restored.guides[0].position, 200.0);
+
+    let _ = fs::remove_file(&file_path);
+}
+
 #[test]
 fn test_default_filename_format() {
     let filename = testruct_ui::io::default_filename();
@@ -196,3 +236,69 @@ fn test_save_invalid_json_handling() {
     // Clean up
     let _ = fs::remove_file(&file_path);
 }
+
+#[test]
+fn test_save_prunes_unused_assets() {
+    use testruct_ui::io::{save_document_with_options, SaveOptions};
+
+    let file_path = {
+        let mut path = std::env::temp_dir();
+        path.push("testruct_prune_assets_test.json");
+        path
+    };
+    let _ = fs::remove_file(&file_path);
+
+    let mut document = create_test_document();
+    document.assets.register("deleted.png");
+
+    // Disabled: the stale entry is written
+    save_document_with_options(
+        &document,
+        &file_path,
+        SaveOptions {
+            prune_unused_assets: false,
+        },
+    )
+    .expect("Failed to save document");
+    let loaded = testruct_ui::io::load_document(&file_path).expect("Failed to load document");
+    assert_eq!(loaded.assets.len(), 1);
+
+    // Default: the stale entry is dropped, the in-memory catalog is untouched
+    testruct_ui::io::save_document(&document, &file_path).expect("Failed to save document");
+    let loaded = testruct_ui::io::load_document(&file_path).expect("Failed to load document");
+    assert!(loaded.assets.is_empty());
+    assert_eq!(document.assets.len(), 1);
+
+    let _ = fs::remove_file(&file_path);
+}
+
+#[test]
+fn test_recovery_snapshot_is_pending_until_source_is_saved() {
+    use testruct_ui::io::recovery;
+
+    let dir = std::env::temp_dir();
+    let source_path = dir.join("testruct_recovery_source.json");
+    let snapshot_path = dir.join("testruct_recovery_test.autosave");
+    let _ = fs::remove_file(&source_path);
+    let _ = fs::remove_file(&snapshot_path);
+
+    let document = create_test_document();
+    testruct_ui::io::save_document(&document, &source_path).expect("save source");
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    recovery::write_snapshot(&snapshot_path, &document, Some(&source_path))
+        .expect("write snapshot");
+
+    // Newer than its source: offered, with the source path kept
+    let pending = recovery::pending_snapshot(&snapshot_path).expect("pending snapshot");
+    assert_eq!(pending.source_path.as_deref(), Some(source_path.as_path()));
+    assert_eq!(pending.document.id, document.id);
+
+    // Saving the source afterwards makes the snapshot stale
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    testruct_ui::io::save_document(&document, &source_path).expect("save source again");
+    assert!(recovery::pending_snapshot(&snapshot_path).is_none());
+
+    recovery::discard_snapshot(&snapshot_path);
+    assert!(!snapshot_path.exists());
+    let _ = fs::remove_file(&source_path);
+}
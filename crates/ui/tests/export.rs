@@ -3,7 +3,7 @@
 use std::fs;
 use std::sync::{Arc, Mutex};
 use testruct_core::document::{
-    Document, DocumentBuilder, DocumentElement, Page, ShapeElement, ShapeKind,
+    Document, DocumentBuilder, DocumentElement, Fill, Page, ShapeElement, ShapeKind,
 };
 use testruct_core::layout::{Point, Rect, Size};
 use testruct_core::typography::Color;
@@ -31,9 +31,14 @@ fn create_test_document() -> Arc<Mutex<Document>> {
             },
             stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
             stroke_width: 1.0,
-            fill: Some(Color::from_rgb(1.0, 0.0, 0.0)),
+            fill: Some(Fill::Solid(Color::from_rgb(1.0, 0.0, 0.0))),
             visible: true,
             locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
         }));
 
         // Circle
@@ -49,9 +54,14 @@ fn create_test_document() -> Arc<Mutex<Document>> {
             },
             stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
             stroke_width: 1.0,
-            fill: Some(Color::from_rgb(0.0, 1.0, 0.0)),
+            fill: Some(Fill::Solid(Color::from_rgb(0.0, 1.0, 0.0))),
             visible: true,
             locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
         }));
 
         // Line
@@ -70,6 +80,11 @@ fn create_test_document() -> Arc<Mutex<Document>> {
             fill: None,
             visible: true,
             locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
         }));
     }
 
@@ -226,7 +241,8 @@ fn test_export_empty_document() {
 // Extended Export Tests
 // ============================================
 
-use testruct_core::document::TextElement;
+use testruct_core::document::{TextElement, DEFAULT_TEXT_PADDING};
+use testruct_core::layout::Insets;
 use testruct_core::typography::TextStyle;
 
 fn create_document_with_text() -> Arc<Mutex<Document>> {
@@ -249,6 +265,11 @@ fn create_document_with_text() -> Arc<Mutex<Document>> {
             auto_resize_height: false,
             visible: true,
             locked: false,
+            rotation: 0.0,
+            rich_text: None,
+            padding: Insets::uniform(DEFAULT_TEXT_PADDING),
+            text_path: None,
+            opacity: 1.0,
         }));
 
         // Add rectangle
@@ -261,9 +282,14 @@ fn create_document_with_text() -> Arc<Mutex<Document>> {
             },
             stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
             stroke_width: 2.0,
-            fill: Some(Color::from_rgb(0.5, 0.5, 0.8)),
+            fill: Some(Fill::Solid(Color::from_rgb(0.5, 0.5, 0.8))),
             visible: true,
             locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
         }));
     }
 
@@ -346,6 +372,30 @@ fn test_png_export_with_scale() {
     let _ = fs::remove_file(&file_path);
 }
 
+#[test]
+fn test_png_export_2x_a4_pixel_dimensions() {
+    use testruct_ui::export::{export_png_with_config, ExportConfig};
+
+    let doc = create_test_document();
+    let file_path = {
+        let mut path = std::env::temp_dir();
+        path.push("test_export_a4_2x.png");
+        path
+    };
+
+    let _ = fs::remove_file(&file_path);
+
+    let d = doc.lock().unwrap();
+    let config = ExportConfig::png().with_scale(2.0).expect("2x is a valid scale");
+    export_png_with_config(&d, &file_path, &config, &d.assets).expect("PNG export should succeed");
+
+    // A4 is 595x842pt, so a 2x export is 1190x1684 pixels
+    let (width, height) = image::image_dimensions(&file_path).expect("PNG should be readable");
+    assert_eq!((width, height), (1190, 1684));
+
+    let _ = fs::remove_file(&file_path);
+}
+
 #[test]
 fn test_export_all_shape_types() {
     let mut doc = DocumentBuilder::new()
@@ -365,9 +415,14 @@ fn test_export_all_shape_types() {
             },
             stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
             stroke_width: 1.0,
-            fill: Some(Color::from_rgb(1.0, 0.0, 0.0)),
+            fill: Some(Fill::Solid(Color::from_rgb(1.0, 0.0, 0.0))),
             visible: true,
             locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
         }));
 
         // Ellipse
@@ -380,9 +435,14 @@ fn test_export_all_shape_types() {
             },
             stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
             stroke_width: 1.0,
-            fill: Some(Color::from_rgb(0.0, 1.0, 0.0)),
+            fill: Some(Fill::Solid(Color::from_rgb(0.0, 1.0, 0.0))),
             visible: true,
             locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
         }));
 
         // Line
@@ -398,6 +458,11 @@ fn test_export_all_shape_types() {
             fill: None,
             visible: true,
             locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
         }));
 
         // Arrow
@@ -413,6 +478,11 @@ fn test_export_all_shape_types() {
             fill: None,
             visible: true,
             locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
         }));
     }
 
@@ -457,9 +527,14 @@ fn test_export_multipage_document() {
             },
             stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
             stroke_width: 1.0,
-            fill: Some(Color::from_rgb(i as f32 * 0.3, 0.5, 0.5)),
+            fill: Some(Fill::Solid(Color::from_rgb(i as f32 * 0.3, 0.5, 0.5))),
             visible: true,
             locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
         }));
     }
 
@@ -484,6 +559,74 @@ fn test_export_multipage_document() {
     let _ = fs::remove_file(&file_path);
 }
 
+#[test]
+fn test_pdf_export_with_crop_marks_and_bleed() {
+    use testruct_ui::export::{export_pdf_with_options, PdfExportOptions};
+
+    let doc = create_test_document();
+    let file_path = {
+        let mut path = std::env::temp_dir();
+        path.push("test_export_print.pdf");
+        path
+    };
+    let _ = fs::remove_file(&file_path);
+
+    let options = PdfExportOptions {
+        crop_marks: true,
+        bleed_mm: 3.0,
+    };
+    let d = doc.lock().unwrap();
+    export_pdf_with_options(&d, &file_path, &options, &d.assets)
+        .expect("PDF export with crop marks should succeed");
+
+    assert!(file_path.exists(), "PDF file should be created");
+    let size = fs::metadata(&file_path).ok().map(|m| m.len()).unwrap_or(0);
+    assert!(size > 0, "PDF file should have content");
+
+    let _ = fs::remove_file(&file_path);
+}
+
+#[test]
+fn test_pdf_a_export_adds_archive_metadata() {
+    let doc = create_test_document();
+    let file_path = std::env::temp_dir().join("test_export_archive.pdf");
+    let _ = fs::remove_file(&file_path);
+
+    let d = doc.lock().unwrap();
+    testruct_ui::export::export_pdf_a(&d, &file_path, &d.assets)
+        .expect("PDF/A export should succeed");
+
+    let bytes = fs::read(&file_path).expect("PDF should be readable");
+    let content = String::from_utf8_lossy(&bytes);
+    assert!(content.starts_with("%PDF-1.4"));
+    assert!(content.contains("/GTS_PDFA1"));
+    assert!(content.contains("<pdfaid:part>1</pdfaid:part>"));
+
+    let _ = fs::remove_file(&file_path);
+}
+
+#[test]
+fn test_eps_export_writes_one_file_per_page() {
+    let doc = DocumentBuilder::new()
+        .with_title("EPS")
+        .add_page(Page::empty())
+        .add_page(Page::empty())
+        .build()
+        .expect("Failed to create test document");
+    let dir = std::env::temp_dir();
+    let file_path = dir.join("test_export.eps");
+    let pages = [dir.join("test_export_page_1.eps"), dir.join("test_export_page_2.eps")];
+
+    testruct_ui::export::export_eps(&doc, &file_path, &doc.assets)
+        .expect("EPS export should succeed");
+
+    for page in &pages {
+        let content = fs::read_to_string(page).expect("EPS page should be written");
+        assert!(content.starts_with("%!PS-Adobe-3.0 EPSF-3.0"));
+        let _ = fs::remove_file(page);
+    }
+}
+
 #[test]
 fn test_export_invisible_elements() {
     let mut doc = DocumentBuilder::new()
@@ -503,9 +646,14 @@ fn test_export_invisible_elements() {
             },
             stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
             stroke_width: 1.0,
-            fill: Some(Color::from_rgb(1.0, 0.0, 0.0)),
+            fill: Some(Fill::Solid(Color::from_rgb(1.0, 0.0, 0.0))),
             visible: true,
             locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
         }));
 
         // Invisible shape
@@ -518,9 +666,14 @@ fn test_export_invisible_elements() {
             },
             stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
             stroke_width: 1.0,
-            fill: Some(Color::from_rgb(0.0, 1.0, 0.0)),
+            fill: Some(Fill::Solid(Color::from_rgb(0.0, 1.0, 0.0))),
             visible: false, // Invisible
             locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
         }));
     }
 
@@ -539,3 +692,127 @@ fn test_export_invisible_elements() {
 
     let _ = fs::remove_file(&file_path);
 }
+
+#[test]
+fn test_png_export_page_range_writes_numbered_files() {
+    use testruct_ui::export::{export_png_with_options, PngExportOptions};
+
+    let doc = DocumentBuilder::new()
+        .with_title("Answer Key")
+        .add_page(Page::empty())
+        .add_page(Page::empty())
+        .add_page(Page::empty())
+        .add_page(Page::empty())
+        .build()
+        .expect("Failed to create test document");
+    let dir = std::env::temp_dir();
+    let file_path = dir.join("test_export_range.png");
+    let page_paths: Vec<_> = (1..=4)
+        .map(|n| dir.join(format!("test_export_range_page_{}.png", n)))
+        .collect();
+    for path in &page_paths {
+        let _ = fs::remove_file(path);
+    }
+
+    let options = PngExportOptions {
+        dpi: 144.0,
+        pages: Some(vec![1, 2, 3]),
+        transparent_background: true,
+    };
+    let result = export_png_with_options(&doc, &file_path, &options, &doc.assets);
+
+    if result.is_ok() {
+        assert!(!page_paths[0].exists(), "Page 1 was not selected");
+        for path in &page_paths[1..] {
+            assert!(path.exists(), "{} should be created", path.display());
+        }
+    }
+
+    for path in &page_paths {
+        let _ = fs::remove_file(path);
+    }
+}
+
+#[test]
+fn test_png_export_uses_shape_stroke_width() {
+    use testruct_ui::export::{export_png_with_options, PngExportOptions};
+
+    let mut doc = DocumentBuilder::new()
+        .with_title("Stroke Width")
+        .add_page(Page::empty())
+        .build()
+        .expect("Failed to create test document");
+    doc.pages[0].add_element(DocumentElement::Shape(ShapeElement {
+        id: Uuid::new_v4(),
+        kind: ShapeKind::Rectangle,
+        bounds: Rect::new(Point::new(100.0, 100.0), Size::new(200.0, 200.0)),
+        stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
+        stroke_width: 10.0,
+        fill: None,
+        visible: true,
+        locked: false,
+        rotation: 0.0,
+        vertices: Vec::new(),
+        line_style: Default::default(),
+        opacity: 1.0,
+        corner_radius: 0.0,
+    }));
+
+    let file_path = std::env::temp_dir().join("test_export_stroke_width.png");
+    let _ = fs::remove_file(&file_path);
+
+    // 72 DPI renders one pixel per point
+    export_png_with_options(&doc, &file_path, &PngExportOptions::with_dpi(72.0), &doc.assets)
+        .expect("PNG export should succeed");
+
+    // A 10pt stroke centered on x=100 covers 95..105; a 1pt stroke would not reach x=104
+    let image = image::open(&file_path).expect("PNG should be readable").to_rgba8();
+    let pixel = image.get_pixel(104, 200);
+    assert!(pixel[0] < 64, "expected stroke pixel, got {:?}", pixel);
+    let inside = image.get_pixel(110, 200);
+    assert!(inside[0] > 192, "expected unstroked interior, got {:?}", inside);
+
+    let _ = fs::remove_file(&file_path);
+}
+
+#[test]
+fn test_svg_export_writes_linear_gradient() {
+    let mut doc = DocumentBuilder::new()
+        .with_title("Gradient")
+        .add_page(Page::empty())
+        .build()
+        .expect("Failed to create test document");
+    doc.pages[0].add_element(DocumentElement::Shape(ShapeElement {
+        id: Uuid::new_v4(),
+        kind: ShapeKind::Rectangle,
+        bounds: Rect::new(Point::new(50.0, 50.0), Size::new(200.0, 100.0)),
+        stroke: None,
+        stroke_width: 1.0,
+        fill: Some(Fill::LinearGradient {
+            start: Color::from_rgb(1.0, 0.0, 0.0),
+            end: Color::from_rgb(0.0, 0.0, 1.0),
+            angle: 45.0,
+        }),
+        visible: true,
+        locked: false,
+        rotation: 0.0,
+        vertices: Vec::new(),
+        line_style: Default::default(),
+        opacity: 1.0,
+        corner_radius: 0.0,
+    }));
+
+    let file_path = std::env::temp_dir().join("test_export_gradient.svg");
+    let _ = fs::remove_file(&file_path);
+
+    testruct_ui::export::export_svg(&doc, &file_path, &doc.assets)
+        .expect("SVG export should succeed");
+
+    let content = fs::read_to_string(&file_path).expect("SVG should be readable");
+    assert!(
+        content.contains("<linearGradient"),
+        "gradient fill should be exported as <linearGradient>"
+    );
+
+    let _ = fs::remove_file(&file_path);
+}
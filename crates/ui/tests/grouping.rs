@@ -2,7 +2,7 @@
 
 use std::sync::{Arc, Mutex};
 use testruct_core::document::{
-    Document, DocumentBuilder, DocumentElement, Page, ShapeElement, ShapeKind,
+    Document, DocumentBuilder, DocumentElement, Fill, Page, ShapeElement, ShapeKind,
 };
 use testruct_core::layout::{Point, Rect, Size};
 use testruct_core::typography::Color;
@@ -32,9 +32,14 @@ fn create_test_shape(x: f32, y: f32) -> DocumentElement {
         },
         stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
         stroke_width: 1.0,
-        fill: Some(Color::from_rgb(1.0, 0.0, 0.0)),
+        fill: Some(Fill::Solid(Color::from_rgb(1.0, 0.0, 0.0))),
         visible: true,
         locked: false,
+        rotation: 0.0,
+        vertices: Vec::new(),
+        line_style: Default::default(),
+        opacity: 1.0,
+        corner_radius: 0.0,
     })
 }
 
@@ -227,3 +232,56 @@ fn test_group_command_with_stack() {
         }
     }
 }
+
+#[test]
+fn test_ungroup_keeps_nested_groups() {
+    use testruct_ui::app::AppState;
+    use testruct_ui::undo_redo::{AppGroupCommand, AppUngroupCommand};
+
+    let state = AppState::default();
+    let shapes: Vec<DocumentElement> = (0..4)
+        .map(|i| create_test_shape((i as f32) * 110.0, 10.0))
+        .collect();
+    let ids: Vec<Uuid> = shapes.iter().map(|shape| shape.id()).collect();
+    state.with_mutable_active_document(|doc| {
+        for shape in shapes {
+            doc.pages[0].add_element(shape);
+        }
+    });
+    let page_order = || {
+        state
+            .with_active_document(|doc| doc.pages[0].element_order())
+            .expect("active document")
+    };
+
+    // Group three elements, then group that group with the fourth
+    let mut inner = AppGroupCommand::new(state.clone(), ids[..3].to_vec(), 0);
+    inner.execute().expect("group inner");
+    let inner_id = inner.group_id();
+    let mut outer = AppGroupCommand::new(state.clone(), vec![inner_id, ids[3]], 0);
+    outer.execute().expect("group outer");
+    assert_eq!(page_order(), vec![outer.group_id()]);
+
+    // Ungrouping once only dissolves the outer group
+    let mut ungroup = AppUngroupCommand::new(state.clone(), outer.group_id(), 0);
+    ungroup.execute().expect("ungroup");
+    assert_eq!(page_order(), vec![inner_id, ids[3]]);
+    let inner_children: Vec<Uuid> = state
+        .with_active_document(|doc| {
+            doc.pages[0].elements[0]
+                .children()
+                .expect("inner group intact")
+                .iter()
+                .map(|child| child.id())
+                .collect()
+        })
+        .expect("active document");
+    assert_eq!(inner_children, ids[..3]);
+
+    // Undoing everything brings back the original elements in order
+    ungroup.undo().expect("undo ungroup");
+    assert_eq!(page_order(), vec![outer.group_id()]);
+    outer.undo().expect("undo outer");
+    inner.undo().expect("undo inner");
+    assert_eq!(page_order(), ids);
+}
@@ -2,7 +2,7 @@
 
 use std::sync::{Arc, Mutex};
 use testruct_core::document::{
-    Document, DocumentBuilder, DocumentElement, Page, ShapeElement, ShapeKind,
+    Document, DocumentBuilder, DocumentElement, Fill, Page, ShapeElement, ShapeKind,
 };
 use testruct_core::layout::{Point, Rect, Size};
 use testruct_core::typography::Color;
@@ -33,9 +33,14 @@ fn create_test_document_with_shapes() -> Arc<Mutex<Document>> {
                 },
                 stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
                 stroke_width: 1.0,
-                fill: Some(Color::from_rgb(1.0, 0.0, 0.0)),
+                fill: Some(Fill::Solid(Color::from_rgb(1.0, 0.0, 0.0))),
                 visible: true,
                 locked: false,
+                rotation: 0.0,
+                vertices: Vec::new(),
+                line_style: Default::default(),
+                opacity: 1.0,
+                corner_radius: 0.0,
             }));
         }
     }
@@ -128,6 +133,8 @@ fn test_layer_names_generation() {
             DocumentElement::Image(_) => "Image".to_string(),
             DocumentElement::Frame(_) => "Frame".to_string(),
             DocumentElement::Group(group) => format!("Group: {}", group.name),
+            DocumentElement::Table(_) => "Table".to_string(),
+            DocumentElement::QrCode(_) => "QrCode".to_string(),
         };
 
         assert!(!name.is_empty(), "Layer {} should have a name", index);
@@ -162,6 +169,8 @@ fn test_layer_bounds_accessibility() {
             DocumentElement::Image(i) => &i.bounds,
             DocumentElement::Frame(f) => &f.bounds,
             DocumentElement::Group(g) => &g.bounds,
+            DocumentElement::Table(t) => &t.bounds,
+            DocumentElement::QrCode(q) => &q.bounds,
         };
 
         assert!(bounds.size.width > 0.0);
@@ -190,9 +199,14 @@ fn test_layer_children_in_group() {
         },
         stroke: None,
         stroke_width: 1.0,
-        fill: Some(Color::from_rgb(1.0, 0.0, 0.0)),
+        fill: Some(Fill::Solid(Color::from_rgb(1.0, 0.0, 0.0))),
         visible: true,
         locked: false,
+        rotation: 0.0,
+        vertices: Vec::new(),
+        line_style: Default::default(),
+        opacity: 1.0,
+        corner_radius: 0.0,
     });
 
     let child2 = DocumentElement::Shape(ShapeElement {
@@ -207,9 +221,14 @@ fn test_layer_children_in_group() {
         },
         stroke: None,
         stroke_width: 1.0,
-        fill: Some(Color::from_rgb(0.0, 1.0, 0.0)),
+        fill: Some(Fill::Solid(Color::from_rgb(0.0, 1.0, 0.0))),
         visible: true,
         locked: false,
+        rotation: 0.0,
+        vertices: Vec::new(),
+        line_style: Default::default(),
+        opacity: 1.0,
+        corner_radius: 0.0,
     });
 
     // Create group
@@ -226,6 +245,7 @@ fn test_layer_children_in_group() {
         children: vec![child1, child2],
         visible: true,
         locked: false,
+        opacity: 1.0,
     });
 
     if let Some(page) = doc.pages.first_mut() {
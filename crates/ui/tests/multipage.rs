@@ -2,7 +2,7 @@
 
 use std::sync::{Arc, Mutex};
 use testruct_core::document::{
-    Document, DocumentBuilder, DocumentElement, Page, ShapeElement, ShapeKind,
+    Document, DocumentBuilder, DocumentElement, Fill, Page, ShapeElement, ShapeKind,
 };
 use testruct_core::layout::{Point, Rect, Size};
 use testruct_core::typography::Color;
@@ -32,9 +32,14 @@ fn create_multipage_document() -> Arc<Mutex<Document>> {
             },
             stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
             stroke_width: 1.0,
-            fill: Some(Color::from_rgb(1.0, 0.0, 0.0)),
+            fill: Some(Fill::Solid(Color::from_rgb(1.0, 0.0, 0.0))),
             visible: true,
             locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
         }));
     }
 
@@ -59,9 +64,14 @@ fn create_multipage_document() -> Arc<Mutex<Document>> {
                 },
                 stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
                 stroke_width: 1.0,
-                fill: Some(Color::from_rgb(1.0, 0.0, 0.0)),
+                fill: Some(Fill::Solid(Color::from_rgb(1.0, 0.0, 0.0))),
                 visible: true,
                 locked: false,
+                rotation: 0.0,
+                vertices: Vec::new(),
+                line_style: Default::default(),
+                opacity: 1.0,
+                corner_radius: 0.0,
             }));
         }
 
@@ -199,9 +209,14 @@ fn test_page_element_independence() {
         },
         stroke: None,
         stroke_width: 1.0,
-        fill: Some(Color::from_rgb(1.0, 0.0, 0.0)),
+        fill: Some(Fill::Solid(Color::from_rgb(1.0, 0.0, 0.0))),
         visible: true,
         locked: false,
+        rotation: 0.0,
+        vertices: Vec::new(),
+        line_style: Default::default(),
+        opacity: 1.0,
+        corner_radius: 0.0,
     });
 
     doc.pages[0].add_element(shape1);
@@ -220,9 +235,14 @@ fn test_page_element_independence() {
         },
         stroke: None,
         stroke_width: 1.0,
-        fill: Some(Color::from_rgb(0.0, 1.0, 0.0)),
+        fill: Some(Fill::Solid(Color::from_rgb(0.0, 1.0, 0.0))),
         visible: true,
         locked: false,
+        rotation: 0.0,
+        vertices: Vec::new(),
+        line_style: Default::default(),
+        opacity: 1.0,
+        corner_radius: 0.0,
     });
 
     page2.add_element(shape2);
@@ -261,3 +281,44 @@ fn test_page_access_bounds() {
     assert!(d.pages.get(3).is_none());
     assert!(d.pages.get(100).is_none());
 }
+
+#[test]
+fn test_delete_page_clamps_active_page_index() {
+    use testruct_ui::app::AppState;
+
+    let state = AppState::default();
+    state.add_page().expect("add page");
+    state.add_page().expect("add page");
+    state.set_active_page_index(2).expect("select last page");
+
+    // Deleting the active last page moves to the new last page
+    state.delete_page(2).expect("delete page");
+    assert_eq!(state.active_page_index(), 1);
+
+    // Deleting an earlier page keeps the same page active
+    let active_id = state.active_page().expect("active page").id;
+    state.delete_page(0).expect("delete page");
+    assert_eq!(state.active_page_index(), 0);
+    assert_eq!(state.active_page().expect("active page").id, active_id);
+}
+
+#[test]
+fn test_move_page_keeps_viewed_page_active() {
+    use testruct_ui::app::AppState;
+
+    let state = AppState::default();
+    state.add_page().expect("add page");
+    state.add_page().expect("add page");
+
+    // Moving the active page keeps it active at its new position
+    state.set_active_page_index(0).expect("select first page");
+    let active_id = state.active_page().expect("active page").id;
+    assert_eq!(state.move_page(0, 2), Ok(2));
+    assert_eq!(state.active_page_index(), 2);
+    assert_eq!(state.active_page().expect("active page").id, active_id);
+
+    // Moving another page across the active one shifts the index instead
+    assert_eq!(state.move_page(0, 2), Ok(2));
+    assert_eq!(state.active_page_index(), 1);
+    assert_eq!(state.active_page().expect("active page").id, active_id);
+}
@@ -2,10 +2,10 @@
 
 use std::sync::{Arc, Mutex};
 use testruct_core::document::{
-    Document, DocumentBuilder, DocumentElement, FrameElement, GroupElement, ImageElement, Page,
-    ShapeElement, ShapeKind, TextElement,
+    Document, DocumentBuilder, DocumentElement, Fill, FrameElement, GroupElement, ImageElement,
+    Page, ShapeElement, ShapeKind, TextElement, DEFAULT_TEXT_PADDING,
 };
-use testruct_core::layout::{Point, Rect, Size};
+use testruct_core::layout::{Insets, Point, Rect, Size};
 use testruct_core::typography::{Color, TextStyle};
 use testruct_core::workspace::assets::AssetRef;
 use uuid::Uuid;
@@ -31,9 +31,14 @@ fn create_test_document_with_shapes() -> Arc<Mutex<Document>> {
             },
             stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
             stroke_width: 1.0,
-            fill: Some(Color::from_rgb(1.0, 0.0, 0.0)),
+            fill: Some(Fill::Solid(Color::from_rgb(1.0, 0.0, 0.0))),
             visible: true,
             locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
         }));
 
         // Add ellipse shape
@@ -49,9 +54,14 @@ fn create_test_document_with_shapes() -> Arc<Mutex<Document>> {
             },
             stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
             stroke_width: 1.0,
-            fill: Some(Color::from_rgb(0.0, 1.0, 0.0)),
+            fill: Some(Fill::Solid(Color::from_rgb(0.0, 1.0, 0.0))),
             visible: true,
             locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
         }));
 
         // Add line shape
@@ -70,6 +80,11 @@ fn create_test_document_with_shapes() -> Arc<Mutex<Document>> {
             fill: None,
             visible: true,
             locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
         }));
     }
 
@@ -98,6 +113,11 @@ fn create_test_document_with_text() -> Arc<Mutex<Document>> {
             auto_resize_height: false,
             visible: true,
             locked: false,
+            rotation: 0.0,
+            rich_text: None,
+            padding: Insets::uniform(DEFAULT_TEXT_PADDING),
+            text_path: None,
+            opacity: 1.0,
         }));
 
         page.add_element(DocumentElement::Text(TextElement {
@@ -114,6 +134,11 @@ fn create_test_document_with_text() -> Arc<Mutex<Document>> {
             auto_resize_height: false,
             visible: true,
             locked: false,
+            rotation: 0.0,
+            rich_text: None,
+            padding: Insets::uniform(DEFAULT_TEXT_PADDING),
+            text_path: None,
+            opacity: 1.0,
         }));
     }
 
@@ -140,6 +165,9 @@ fn create_test_document_with_images() -> Arc<Mutex<Document>> {
             source: AssetRef::new(),
             visible: true,
             locked: false,
+            rotation: 0.0,
+            fit: Default::default(),
+            opacity: 1.0,
         }));
 
         page.add_element(DocumentElement::Image(ImageElement {
@@ -154,6 +182,9 @@ fn create_test_document_with_images() -> Arc<Mutex<Document>> {
             source: AssetRef::new(),
             visible: true,
             locked: false,
+            rotation: 0.0,
+            fit: Default::default(),
+            opacity: 1.0,
         }));
     }
 
@@ -181,9 +212,14 @@ fn create_test_document_with_mixed_elements() -> Arc<Mutex<Document>> {
             },
             stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
             stroke_width: 1.0,
-            fill: Some(Color::from_rgb(1.0, 0.5, 0.0)),
+            fill: Some(Fill::Solid(Color::from_rgb(1.0, 0.5, 0.0))),
             visible: true,
             locked: false,
+            rotation: 0.0,
+            vertices: Vec::new(),
+            line_style: Default::default(),
+            opacity: 1.0,
+            corner_radius: 0.0,
         }));
 
         // Text
@@ -201,6 +237,11 @@ fn create_test_document_with_mixed_elements() -> Arc<Mutex<Document>> {
             auto_resize_height: false,
             visible: true,
             locked: false,
+            rotation: 0.0,
+            rich_text: None,
+            padding: Insets::uniform(DEFAULT_TEXT_PADDING),
+            text_path: None,
+            opacity: 1.0,
         }));
 
         // Image
@@ -216,6 +257,9 @@ fn create_test_document_with_mixed_elements() -> Arc<Mutex<Document>> {
             source: AssetRef::new(),
             visible: true,
             locked: false,
+            rotation: 0.0,
+            fit: Default::default(),
+            opacity: 1.0,
         }));
 
         // Frame
@@ -231,6 +275,7 @@ fn create_test_document_with_mixed_elements() -> Arc<Mutex<Document>> {
             children: vec![],
             visible: true,
             locked: false,
+            opacity: 1.0,
         }));
     }
 
@@ -421,6 +466,11 @@ fn test_page_change_detection() {
         fill: None,
         visible: true,
         locked: false,
+        rotation: 0.0,
+        vertices: Vec::new(),
+        line_style: Default::default(),
+        opacity: 1.0,
+        corner_radius: 0.0,
     }));
 
     // Page with 1 element, old hash of 0 - should detect as changed
@@ -473,9 +523,14 @@ fn test_thumbnail_multiple_shapes() {
                 },
                 stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
                 stroke_width: 1.0,
-                fill: Some(Color::from_rgb(0.5, 0.5, 0.5)),
+                fill: Some(Fill::Solid(Color::from_rgb(0.5, 0.5, 0.5))),
                 visible: true,
                 locked: false,
+                rotation: 0.0,
+                vertices: Vec::new(),
+                line_style: Default::default(),
+                opacity: 1.0,
+                corner_radius: 0.0,
             }));
         }
     }
@@ -519,6 +574,7 @@ fn test_thumbnail_with_frames_and_groups() {
             children: vec![],
             visible: true,
             locked: false,
+            opacity: 1.0,
         }));
 
         // Add group
@@ -535,6 +591,7 @@ fn test_thumbnail_with_frames_and_groups() {
             children: vec![],
             visible: true,
             locked: false,
+            opacity: 1.0,
         }));
     }
 
@@ -624,3 +681,63 @@ fn test_thumbnail_different_pages_different_sizes() {
         );
     }
 }
+
+#[test]
+fn test_thumbnail_cache_rerenders_only_changed_page() {
+    use testruct_ui::canvas::page_thumbnail::ThumbnailCache;
+
+    let pages = vec![Page::empty(), Page::empty(), Page::empty()];
+    let mut cache = ThumbnailCache::new();
+
+    for page in &pages {
+        cache.get_or_render(page, 0).expect("thumbnail");
+    }
+    assert_eq!(cache.render_count(), 3);
+
+    // Unchanged revisions are served from the cache
+    for page in &pages {
+        cache.get_or_render(page, 0).expect("thumbnail");
+    }
+    assert_eq!(cache.render_count(), 3);
+
+    // Only the edited page is re-rendered
+    for (page, revision) in pages.iter().zip([0, 1, 0]) {
+        cache.get_or_render(page, revision).expect("thumbnail");
+    }
+    assert_eq!(cache.render_count(), 4);
+
+    cache.retain_pages(&pages[..1]);
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn test_editing_active_page_bumps_only_its_revision() {
+    use testruct_ui::app::AppState;
+    use testruct_ui::canvas::page_thumbnail::ThumbnailCache;
+
+    let state = AppState::default();
+    state.add_page().expect("add page");
+    let pages = state
+        .with_active_document(|doc| doc.pages.clone())
+        .expect("document");
+
+    let mut cache = ThumbnailCache::new();
+    for page in &pages {
+        cache
+            .get_or_render(page, state.page_revision(page.id))
+            .expect("thumbnail");
+    }
+    assert_eq!(cache.render_count(), 2);
+
+    state.set_active_page_index(1).expect("select page");
+    state.mark_as_modified();
+    assert_eq!(state.page_revision(pages[0].id), 0);
+    assert_eq!(state.page_revision(pages[1].id), 1);
+
+    for page in &pages {
+        cache
+            .get_or_render(page, state.page_revision(page.id))
+            .expect("thumbnail");
+    }
+    assert_eq!(cache.render_count(), 3);
+}
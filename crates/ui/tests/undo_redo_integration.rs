@@ -2,7 +2,7 @@
 
 use std::sync::{Arc, Mutex};
 use testruct_core::document::{
-    Document, DocumentBuilder, DocumentElement, Page, ShapeElement, ShapeKind,
+    Document, DocumentBuilder, DocumentElement, Fill, Page, ShapeElement, ShapeKind,
 };
 use testruct_core::layout::{Point, Rect, Size};
 use testruct_core::typography::Color;
@@ -32,9 +32,14 @@ fn create_test_shape() -> DocumentElement {
         },
         stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
         stroke_width: 1.0,
-        fill: Some(Color::from_rgb(1.0, 0.0, 0.0)),
+        fill: Some(Fill::Solid(Color::from_rgb(1.0, 0.0, 0.0))),
         visible: true,
         locked: false,
+        rotation: 0.0,
+        vertices: Vec::new(),
+        line_style: Default::default(),
+        opacity: 1.0,
+        corner_radius: 0.0,
     })
 }
 
@@ -510,9 +515,14 @@ fn test_move_two_elements_separately() {
         },
         stroke: Some(Color::from_rgb(0.0, 0.0, 0.0)),
         stroke_width: 1.0,
-        fill: Some(Color::from_rgb(0.0, 1.0, 0.0)),
+        fill: Some(Fill::Solid(Color::from_rgb(0.0, 1.0, 0.0))),
         visible: true,
         locked: false,
+        rotation: 0.0,
+        vertices: Vec::new(),
+        line_style: Default::default(),
+        opacity: 1.0,
+        corner_radius: 0.0,
     });
 
     let id1 = shape1.id();
@@ -576,3 +586,662 @@ fn test_move_two_elements_separately() {
         assert_eq!(elem2.bounds().origin.x, 100.0);
     }
 }
+
+#[test]
+fn test_duplicate_page_command_undo_redo() {
+    use testruct_ui::app::AppState;
+    use testruct_ui::undo_redo::AppDuplicatePageCommand;
+
+    let state = AppState::default();
+    state
+        .add_element_to_active_page(create_test_shape())
+        .expect("active page exists");
+    let source_id = state.active_page().expect("active page").id;
+
+    let mut cmd = AppDuplicatePageCommand::new(state.clone(), source_id);
+    assert!(cmd.execute().is_ok());
+    assert_eq!(state.page_count(), 2);
+
+    let new_id = cmd.new_page_id().expect("duplicate id");
+    let (source_elem, copy_elem) = state
+        .with_active_document(|doc| (doc.pages[0].elements[0].clone(), doc.pages[1].elements[0].clone()))
+        .expect("document");
+    assert_ne!(source_elem.id(), copy_elem.id());
+    assert_eq!(source_elem.bounds(), copy_elem.bounds());
+
+    assert!(cmd.undo().is_ok());
+    assert_eq!(state.page_count(), 1);
+
+    // Redo restores the same duplicate rather than creating another one
+    assert!(cmd.execute().is_ok());
+    assert_eq!(state.page_count(), 2);
+    let restored_id = state.with_active_document(|doc| doc.pages[1].id);
+    assert_eq!(restored_id, Some(new_id));
+}
+
+#[test]
+fn test_move_page_command_undo_redo() {
+    use testruct_ui::app::AppState;
+    use testruct_ui::undo_redo::AppMovePageCommand;
+
+    let state = AppState::default();
+    state.add_page().expect("add page");
+    state.add_page().expect("add page");
+    let original: Vec<_> = state
+        .with_active_document(|doc| doc.pages.iter().map(|p| p.id).collect())
+        .expect("document");
+
+    let mut cmd = AppMovePageCommand::new(state.clone(), 0, 2);
+    assert!(cmd.execute().is_ok());
+    let moved: Vec<_> = state
+        .with_active_document(|doc| doc.pages.iter().map(|p| p.id).collect())
+        .expect("document");
+    assert_eq!(moved, vec![original[1], original[2], original[0]]);
+
+    assert!(cmd.undo().is_ok());
+    let restored: Vec<_> = state
+        .with_active_document(|doc| doc.pages.iter().map(|p| p.id).collect::<Vec<_>>())
+        .expect("document");
+    assert_eq!(restored, original);
+}
+
+#[test]
+fn test_resize_undo_restores_pre_drag_bounds_after_live_preview() {
+    use testruct_ui::app::AppState;
+    use testruct_ui::undo_redo::AppResizeCommand;
+
+    let state = AppState::default();
+    let shape = create_test_shape();
+    let id = shape.id();
+    let original = *shape.bounds();
+    state.add_element_to_active_page(shape).expect("active page exists");
+
+    // Live preview during the drag mutates the element directly
+    let preview = Rect::new(Point::new(10.0, 20.0), Size::new(130.0, 70.0));
+    state.with_mutable_active_document(|doc| *doc.pages[0].elements[0].bounds_mut() = preview);
+
+    let final_bounds = Rect::new(Point::new(10.0, 20.0), Size::new(140.0, 80.0));
+    state.push_command(Box::new(AppResizeCommand::new(
+        state.clone(),
+        id,
+        0,
+        original,
+        final_bounds,
+    )));
+    let bounds = || state.with_active_document(|doc| *doc.pages[0].elements[0].bounds());
+    assert_eq!(bounds(), Some(final_bounds));
+
+    assert!(state.undo());
+    assert_eq!(bounds(), Some(original));
+    assert!(state.redo());
+    assert_eq!(bounds(), Some(final_bounds));
+}
+
+#[test]
+fn test_rotation_property_change_undo_redo() {
+    use testruct_ui::app::AppState;
+    use testruct_ui::undo_redo::{AppPropertyChangeCommand, PropertyValue};
+
+    let state = AppState::default();
+    let shape = create_test_shape();
+    let id = shape.id();
+    state
+        .add_element_to_active_page(shape)
+        .expect("active page exists");
+
+    let rotation = |state: &AppState| {
+        state
+            .with_active_document(|doc| doc.pages[0].elements[0].rotation())
+            .expect("document")
+    };
+
+    let mut cmd =
+        AppPropertyChangeCommand::new(state.clone(), vec![id], 0, PropertyValue::Rotation(45.0));
+    assert!(cmd.execute().is_ok());
+    assert_eq!(rotation(&state), 45.0);
+
+    assert!(cmd.undo().is_ok());
+    assert_eq!(rotation(&state), 0.0);
+
+    assert!(cmd.execute().is_ok());
+    assert_eq!(rotation(&state), 45.0);
+}
+
+#[test]
+fn test_undo_pushes_pending_coalesced_edit_first() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use testruct_ui::app::AppState;
+    use testruct_ui::undo_redo::{AppPropertyChangeCommand, PropertyValue};
+
+    let state = AppState::default();
+    let shape = create_test_shape();
+    let id = shape.id();
+    state
+        .add_element_to_active_page(shape)
+        .expect("active page exists");
+
+    let rotation = |state: &AppState| {
+        state
+            .with_active_document(|doc| doc.pages[0].elements[0].rotation())
+            .expect("document")
+    };
+
+    let mut first =
+        AppPropertyChangeCommand::new(state.clone(), vec![id], 0, PropertyValue::Rotation(45.0));
+    assert!(first.execute().is_ok());
+    state.push_command(Box::new(first));
+
+    // A slider drag previewed on the document, still waiting for its quiet time
+    let value = PropertyValue::Rotation(90.0);
+    let old_values = AppPropertyChangeCommand::current_values(&state, &[id], 0, &value);
+    assert!(AppPropertyChangeCommand::apply_to(&state, &[id], 0, &value));
+    let pending = Rc::new(RefCell::new(Some((old_values, value))));
+    let flush_state = state.clone();
+    // The state only holds the hook weakly, so the test keeps it alive
+    let flush: Rc<dyn Fn()> = Rc::new(move || {
+        if let Some((old_values, value)) = pending.borrow_mut().take() {
+            let command = AppPropertyChangeCommand::with_old_values(
+                flush_state.clone(),
+                0,
+                old_values,
+                value,
+            );
+            flush_state.push_command(Box::new(command));
+        }
+    });
+    state.register_pending_edit(&flush);
+    assert_eq!(rotation(&state), 90.0);
+
+    // Undo reverts the drag, not the step before it
+    assert!(state.undo());
+    assert_eq!(rotation(&state), 45.0);
+    assert!(state.undo());
+    assert_eq!(rotation(&state), 0.0);
+    assert!(state.redo());
+    assert!(state.redo());
+    assert_eq!(rotation(&state), 90.0);
+}
+
+#[test]
+fn test_line_style_property_change_undo_redo() {
+    use testruct_core::document::LineStyle;
+    use testruct_ui::app::AppState;
+    use testruct_ui::undo_redo::{AppPropertyChangeCommand, PropertyValue};
+
+    let state = AppState::default();
+    let shape = create_test_shape();
+    let id = shape.id();
+    state
+        .add_element_to_active_page(shape)
+        .expect("active page exists");
+
+    let line_style = |state: &AppState| {
+        state
+            .with_active_document(|doc| match &doc.pages[0].elements[0] {
+                DocumentElement::Shape(shape) => shape.line_style,
+                _ => panic!("expected shape"),
+            })
+            .expect("document")
+    };
+
+    let mut cmd = AppPropertyChangeCommand::new(
+        state.clone(),
+        vec![id],
+        0,
+        PropertyValue::LineStyle(LineStyle::Dashed),
+    );
+    assert!(cmd.execute().is_ok());
+    assert_eq!(line_style(&state), LineStyle::Dashed);
+
+    assert!(cmd.undo().is_ok());
+    assert_eq!(line_style(&state), LineStyle::Solid);
+
+    assert!(cmd.execute().is_ok());
+    assert_eq!(line_style(&state), LineStyle::Dashed);
+}
+
+#[test]
+fn test_undo_stack_marks_document_modified() {
+    use testruct_ui::app::AppState;
+    use testruct_ui::undo_redo::{AppPropertyChangeCommand, PropertyValue};
+
+    let state = AppState::default();
+    let shape = create_test_shape();
+    let id = shape.id();
+    state
+        .add_element_to_active_page(shape)
+        .expect("active page exists");
+    assert!(!state.is_modified());
+
+    // Pushing runs the command
+    let cmd =
+        AppPropertyChangeCommand::new(state.clone(), vec![id], 0, PropertyValue::Rotation(45.0));
+    state.push_command(Box::new(cmd));
+    let rotation = state.with_active_page(|page| page.elements[0].rotation());
+    assert_eq!(rotation, Some(45.0));
+    assert!(state.is_modified());
+
+    // Undo runs the command against the same state without deadlocking
+    assert!(state.undo());
+    assert!(state.redo());
+    assert!(state.is_modified());
+}
+
+#[test]
+fn test_visibility_toggle_undo_redo() {
+    use testruct_ui::app::AppState;
+    use testruct_ui::undo_redo::{AppPropertyChangeCommand, PropertyValue};
+
+    let state = AppState::default();
+    let shape = create_test_shape();
+    let id = shape.id();
+    state
+        .add_element_to_active_page(shape)
+        .expect("active page exists");
+
+    let visible = |state: &AppState| {
+        state
+            .with_active_document(|doc| doc.pages[0].elements[0].is_visible())
+            .expect("document")
+    };
+
+    let cmd =
+        AppPropertyChangeCommand::new(state.clone(), vec![id], 0, PropertyValue::Visible(false));
+    state.push_command(Box::new(cmd));
+    assert!(!visible(&state));
+
+    assert!(state.undo());
+    assert!(visible(&state));
+
+    assert!(state.redo());
+    assert!(!visible(&state));
+}
+
+#[test]
+fn test_coalesced_slider_drag_is_one_undo_step() {
+    use testruct_ui::app::AppState;
+    use testruct_ui::undo_redo::{AppPropertyChangeCommand, PropertyValue};
+
+    let state = AppState::default();
+    let shape = create_test_shape();
+    let id = shape.id();
+    state
+        .add_element_to_active_page(shape)
+        .expect("active page exists");
+
+    let opacity = |state: &AppState| {
+        state
+            .with_active_document(|doc| doc.pages[0].elements[0].opacity())
+            .expect("document")
+    };
+
+    // Preview every step of the drag, then push one command for all of them
+    let before =
+        AppPropertyChangeCommand::current_values(&state, &[id], 0, &PropertyValue::Opacity(0.0));
+    assert_eq!(before, vec![(id, PropertyValue::Opacity(1.0))]);
+    for step in [0.9, 0.7, 0.4] {
+        assert!(AppPropertyChangeCommand::apply_to(
+            &state,
+            &[id],
+            0,
+            &PropertyValue::Opacity(step)
+        ));
+    }
+    let cmd = AppPropertyChangeCommand::with_old_values(
+        state.clone(),
+        0,
+        before,
+        PropertyValue::Opacity(0.4),
+    );
+    state.push_command(Box::new(cmd));
+    assert_eq!(state.with_undo_stack(|stack| stack.undo_count()), 1);
+    assert_eq!(opacity(&state), 0.4);
+
+    assert!(state.undo());
+    assert_eq!(opacity(&state), 1.0);
+    assert!(state.redo());
+    assert_eq!(opacity(&state), 0.4);
+
+    assert!(PropertyValue::Opacity(0.4).approx_eq(&PropertyValue::Opacity(0.4004)));
+    assert!(!PropertyValue::Opacity(0.4).approx_eq(&PropertyValue::Rotation(0.4)));
+}
+
+/// Preview every step of a slider drag, then push one command for the whole
+/// drag, as the property panel's `SliderUndo` does
+fn drag_property(
+    state: &testruct_ui::app::AppState,
+    id: Uuid,
+    steps: &[testruct_ui::undo_redo::PropertyValue],
+) {
+    use testruct_ui::undo_redo::AppPropertyChangeCommand;
+
+    let last = steps.last().expect("at least one step").clone();
+    let old_values = AppPropertyChangeCommand::current_values(state, &[id], 0, &last);
+    for step in steps {
+        AppPropertyChangeCommand::apply_to(state, &[id], 0, step);
+    }
+    let command = AppPropertyChangeCommand::with_old_values(state.clone(), 0, old_values, last);
+    state.push_command(Box::new(command));
+}
+
+#[test]
+fn test_corner_radius_drag_is_one_undo_step() {
+    use testruct_ui::app::AppState;
+    use testruct_ui::undo_redo::PropertyValue;
+
+    let state = AppState::default();
+    let shape = create_test_shape();
+    let id = shape.id();
+    state
+        .add_element_to_active_page(shape)
+        .expect("active page exists");
+
+    let corner_radius = |state: &AppState| {
+        state
+            .with_active_document(|doc| match &doc.pages[0].elements[0] {
+                DocumentElement::Shape(shape) => shape.corner_radius,
+                _ => unreachable!("the page holds the test shape"),
+            })
+            .expect("document")
+    };
+
+    let steps = [2.0, 5.0, 8.0].map(PropertyValue::CornerRadius);
+    drag_property(&state, id, &steps);
+    assert_eq!(state.with_undo_stack(|stack| stack.undo_count()), 1);
+    assert_eq!(corner_radius(&state), 8.0);
+
+    assert!(state.undo());
+    assert_eq!(corner_radius(&state), 0.0);
+    assert!(state.redo());
+    assert_eq!(corner_radius(&state), 8.0);
+}
+
+#[test]
+fn test_gradient_angle_drag_is_one_undo_step() {
+    use testruct_ui::app::AppState;
+    use testruct_ui::undo_redo::{AppPropertyChangeCommand, PropertyValue};
+
+    let state = AppState::default();
+    let shape = create_test_shape();
+    let id = shape.id();
+    state
+        .add_element_to_active_page(shape)
+        .expect("active page exists");
+
+    let gradient = |angle: f32| Fill::LinearGradient {
+        start: Color::from_rgb(1.0, 0.0, 0.0),
+        end: Color::from_rgb(0.0, 0.0, 1.0),
+        angle,
+    };
+    let fill = |state: &AppState| {
+        state
+            .with_active_document(|doc| match &doc.pages[0].elements[0] {
+                DocumentElement::Shape(shape) => shape.fill,
+                _ => unreachable!("the page holds the test shape"),
+            })
+            .expect("document")
+    };
+    let start = PropertyValue::Fill(Some(gradient(0.0)));
+    assert!(AppPropertyChangeCommand::apply_to(&state, &[id], 0, &start));
+
+    let steps = [15.0, 40.0, 90.0].map(|angle| PropertyValue::Fill(Some(gradient(angle))));
+    drag_property(&state, id, &steps);
+    assert_eq!(state.with_undo_stack(|stack| stack.undo_count()), 1);
+    assert_eq!(fill(&state), Some(gradient(90.0)));
+
+    assert!(state.undo());
+    assert_eq!(fill(&state), Some(gradient(0.0)));
+}
+
+#[test]
+fn test_rotation_drag_is_one_undo_step() {
+    use testruct_ui::app::AppState;
+    use testruct_ui::undo_redo::PropertyValue;
+
+    let state = AppState::default();
+    let shape = create_test_shape();
+    let id = shape.id();
+    state
+        .add_element_to_active_page(shape)
+        .expect("active page exists");
+
+    let rotation = |state: &AppState| {
+        state
+            .with_active_document(|doc| doc.pages[0].elements[0].rotation())
+            .expect("document")
+    };
+
+    // Clicking the spin button's arrow steps through every value
+    let steps = [1.0, 2.0, 3.0, 4.0, 5.0].map(PropertyValue::Rotation);
+    drag_property(&state, id, &steps);
+    assert_eq!(state.with_undo_stack(|stack| stack.undo_count()), 1);
+    assert_eq!(rotation(&state), 5.0);
+
+    assert!(state.undo());
+    assert_eq!(rotation(&state), 0.0);
+    assert!(!state.can_undo());
+}
+
+#[test]
+fn test_layers_command_undo_redo() {
+    use testruct_ui::app::AppState;
+    use testruct_ui::undo_redo::AppLayersCommand;
+
+    let state = AppState::default();
+    let shape = create_test_shape();
+    let id = shape.id();
+    state
+        .add_element_to_active_page(shape)
+        .expect("active page exists");
+
+    // Move the shape onto a new hidden layer
+    let mut page = state.active_page().expect("active page");
+    let layer = page.add_layer("Hidden");
+    page.assign_to_layer(&[id], layer);
+    page.layer_mut(layer).expect("layer").visible = false;
+    let cmd = AppLayersCommand::new(state.clone(), 0, page.layers, "Hide Layer");
+    state.push_command(Box::new(cmd));
+
+    let drawn = |state: &AppState| {
+        state
+            .active_page()
+            .map(|page| page.layered_elements().len())
+            .expect("active page")
+    };
+    assert_eq!(drawn(&state), 0);
+    assert!(state.is_modified());
+
+    assert!(state.undo());
+    assert_eq!(drawn(&state), 1);
+    assert_eq!(state.active_page().expect("active page").layers.len(), 1);
+
+    assert!(state.redo());
+    assert_eq!(drawn(&state), 0);
+}
+
+#[test]
+fn test_guides_command_undo_redo() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use testruct_ui::canvas::grid_rendering::{Guide, GuideOrientation};
+    use testruct_ui::canvas::rendering::RenderConfig;
+    use testruct_ui::undo_redo::{GuidesCommand, UndoRedoStack};
+
+    let config = Rc::new(RefCell::new(RenderConfig::default()));
+    config.borrow_mut().guides = vec![Guide::new(GuideOrientation::Horizontal, 40.0)];
+
+    // Replacing the guides with a preset is undone as one step
+    let preset = vec![
+        Guide::new(GuideOrientation::Vertical, 100.0),
+        Guide::new(GuideOrientation::Vertical, 200.0),
+        Guide::new(GuideOrientation::Vertical, 300.0),
+    ];
+    let mut stack = UndoRedoStack::default();
+    stack.push(Box::new(GuidesCommand::new(
+        config.clone(),
+        preset,
+        "Column Guides",
+    )));
+    assert_eq!(config.borrow().guides.len(), 3);
+
+    assert!(stack.undo());
+    assert_eq!(config.borrow().guides.len(), 1);
+    assert_eq!(config.borrow().guides[0].position, 40.0);
+
+    assert!(stack.redo());
+    assert_eq!(config.borrow().guides.len(), 3);
+}
+
+#[test]
+fn test_table_cell_command_undo_redo() {
+    use testruct_ui::app::AppState;
+    use testruct_ui::canvas::tools::ShapeFactory;
+    use testruct_ui::undo_redo::AppTableCellCommand;
+
+    let state = AppState::default();
+    let table = ShapeFactory::create_table(0.0, 0.0, 2, 2);
+    let table_id = table.id();
+    state
+        .add_element_to_active_page(table)
+        .expect("active page exists");
+    let cell_text = |state: &AppState| {
+        let page = state.active_page().expect("active page");
+        match &page.elements[0] {
+            DocumentElement::Table(table) => table.cell(1, 0).expect("cell").get_plain_text(),
+            _ => panic!("expected table"),
+        }
+    };
+
+    let mut cmd = AppTableCellCommand::new(state.clone(), table_id, 0, (1, 0), "答え");
+    assert!(cmd.execute().is_ok());
+    assert_eq!(cell_text(&state), "答え");
+
+    assert!(cmd.undo().is_ok());
+    assert_eq!(cell_text(&state), "");
+
+    assert!(cmd.execute().is_ok());
+    assert_eq!(cell_text(&state), "答え");
+
+    // Cells outside the grid are rejected
+    let mut outside = AppTableCellCommand::new(state.clone(), table_id, 0, (2, 0), "x");
+    assert!(outside.execute().is_err());
+}
+
+#[test]
+fn test_qr_code_command_undo_redo() {
+    use testruct_core::document::QrErrorCorrection;
+    use testruct_ui::app::AppState;
+    use testruct_ui::canvas::tools::ShapeFactory;
+    use testruct_ui::undo_redo::AppQrCodeCommand;
+
+    let state = AppState::default();
+    let qr = ShapeFactory::create_qr_code(0.0, 0.0, "exam:0001", QrErrorCorrection::Medium);
+    let qr_id = qr.id();
+    state
+        .add_element_to_active_page(qr)
+        .expect("active page exists");
+    let qr_value = |state: &AppState| {
+        let page = state.active_page().expect("active page");
+        match &page.elements[0] {
+            DocumentElement::QrCode(qr) => (qr.payload.clone(), qr.error_correction),
+            _ => panic!("expected QR code"),
+        }
+    };
+
+    let mut cmd = AppQrCodeCommand::new(
+        state.clone(),
+        qr_id,
+        0,
+        "exam:0002",
+        QrErrorCorrection::High,
+    );
+    assert!(cmd.execute().is_ok());
+    assert_eq!(
+        qr_value(&state),
+        ("exam:0002".to_string(), QrErrorCorrection::High)
+    );
+
+    assert!(cmd.undo().is_ok());
+    assert_eq!(
+        qr_value(&state),
+        ("exam:0001".to_string(), QrErrorCorrection::Medium)
+    );
+
+    // Unknown ids are rejected
+    let mut missing = AppQrCodeCommand::new(
+        state.clone(),
+        Uuid::new_v4(),
+        0,
+        "x",
+        QrErrorCorrection::Low,
+    );
+    assert!(missing.execute().is_err());
+}
+
+#[test]
+fn test_replace_all_is_one_undo_step() {
+    use testruct_core::document::SearchOptions;
+    use testruct_ui::app::AppState;
+    use testruct_ui::canvas::tools::ShapeFactory;
+    use testruct_ui::undo_redo::AppReplaceTextCommand;
+
+    let state = AppState::default();
+    let first = ShapeFactory::create_text(0.0, 0.0, 200.0, 40.0, "問1 問2".to_string());
+    state
+        .add_element_to_active_page(first)
+        .expect("active page exists");
+    state.add_page().expect("page added");
+    state.set_active_page_index(1).expect("second page");
+    let second = ShapeFactory::create_text(0.0, 0.0, 200.0, 40.0, "問3".to_string());
+    state
+        .add_element_to_active_page(second)
+        .expect("active page exists");
+
+    let contents = |state: &AppState| {
+        state
+            .with_active_document(|doc| {
+                doc.pages
+                    .iter()
+                    .flat_map(|page| page.elements.iter())
+                    .filter_map(|element| match element {
+                        DocumentElement::Text(text) => Some(text.content.clone()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .expect("document")
+    };
+
+    let options = SearchOptions::default();
+    let matches = state
+        .with_active_document(|doc| doc.find_text("問", options))
+        .expect("document");
+    assert_eq!(matches.len(), 2);
+
+    state.with_undo_stack(|stack| stack.begin_batch("Replace All"));
+    for found in &matches {
+        let cmd = AppReplaceTextCommand::new(
+            state.clone(),
+            found.page_index,
+            found.element_id,
+            "問",
+            "設問",
+            options,
+        );
+        state.push_command(Box::new(cmd));
+    }
+    state.with_undo_stack(|stack| stack.end_batch());
+    assert_eq!(contents(&state), ["設問1 設問2", "設問3"]);
+
+    // A single undo restores both pages
+    assert!(state.undo());
+    assert_eq!(contents(&state), ["問1 問2", "問3"]);
+    assert!(state.redo());
+    assert_eq!(contents(&state), ["設問1 設問2", "設問3"]);
+
+    // Elements without a match are rejected
+    let mut no_match =
+        AppReplaceTextCommand::new(state.clone(), 1, matches[1].element_id, "問", "x", options);
+    assert!(no_match.execute().is_err());
+}